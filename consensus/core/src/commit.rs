@@ -345,6 +345,25 @@ pub fn load_committed_subdag_from_store(
     CommittedSubDag::new(leader_block_ref, blocks, timestamp_ms, commit.index())
 }
 
+/// An inclusive range of commit indices.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct CommitRange {
+    pub(crate) start: CommitIndex,
+    pub(crate) end: CommitIndex,
+}
+
+impl CommitRange {
+    pub(crate) fn new(start: CommitIndex, end: CommitIndex) -> Self {
+        assert!(start <= end, "Invalid commit range {start}..={end}");
+        Self { start, end }
+    }
+
+    /// Number of commits in the range.
+    pub(crate) fn size(&self) -> usize {
+        (self.end - self.start + 1) as usize
+    }
+}
+
 pub struct CommitConsumer {
     // A channel to send the committed sub dags through
     pub sender: UnboundedSender<CommittedSubDag>,
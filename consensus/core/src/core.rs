@@ -20,10 +20,12 @@ use crate::{
         Slot, VerifiedBlock, GENESIS_ROUND,
     },
     block_manager::BlockManager,
+    commit::LeaderStatus,
     commit_observer::CommitObserver,
     context::Context,
     dag_state::DagState,
     error::{ConsensusError, ConsensusResult},
+    leader_schedule::LeaderSchedule,
     stake_aggregator::{QuorumThreshold, StakeAggregator},
     threshold_clock::ThresholdClock,
     transaction::{TransactionConsumer, TransactionGuard},
@@ -53,6 +55,10 @@ pub(crate) struct Core {
     block_manager: BlockManager,
     /// Used to make commit decisions for leader blocks in the dag.
     committer: UniversalCommitter,
+    /// The leader schedule, shared with the committer. It is updated from the
+    /// reputation scores of the authorities every
+    /// `CONSENSUS_COMMITS_PER_SCHEDULE` commits.
+    leader_schedule: LeaderSchedule,
     /// The last produced block
     last_proposed_block: VerifiedBlock,
     /// The blocks of the last included ancestors per authority. This vector is
@@ -92,10 +98,16 @@ impl Core {
     ) -> Self {
         let last_decided_leader = dag_state.read().last_commit_leader();
 
-        let committer = UniversalCommitterBuilder::new(context.clone(), dag_state.clone())
-            .with_number_of_leaders(NUM_LEADERS_PER_ROUND)
-            .with_pipeline(true)
-            .build();
+        let leader_schedule = LeaderSchedule::from_store(context.clone(), dag_state.clone());
+
+        let committer = UniversalCommitterBuilder::new(
+            context.clone(),
+            leader_schedule.clone(),
+            dag_state.clone(),
+        )
+        .with_number_of_leaders(NUM_LEADERS_PER_ROUND)
+        .with_pipeline(true)
+        .build();
 
         // Recover the last proposed block
         let last_proposed_block = dag_state
@@ -125,6 +137,7 @@ impl Core {
             last_included_ancestors,
             block_manager,
             committer,
+            leader_schedule,
             last_decided_leader,
             commit_observer,
             signals,
@@ -369,24 +382,71 @@ impl Core {
             .with_label_values(&["Core::try_commit"])
             .start_timer();
 
+        let mut committed_subdags = Vec::new();
         // TODO: Add optimization to abort early without quorum for a round.
-        let sequenced_leaders = self.committer.try_commit(self.last_decided_leader);
+        loop {
+            // The leader schedule must be updated exactly every
+            // `CONSENSUS_COMMITS_PER_SCHEDULE` commits, so that all authorities
+            // switch schedules at the same commit. So commit at most up to the
+            // next schedule update before deciding further leaders. Without
+            // leader scoring the schedule never changes.
+            let commits_until_update = if self
+                .context
+                .protocol_config
+                .mysticeti_leader_scoring_and_schedule()
+            {
+                let commits_until_update = self
+                    .leader_schedule
+                    .commits_until_leader_schedule_update(self.dag_state.clone());
+                if commits_until_update == 0 {
+                    self.leader_schedule.update_leader_schedule(&self.dag_state);
+                    continue;
+                }
+                commits_until_update
+            } else {
+                usize::MAX
+            };
+
+            let sequenced_leaders = self.committer.try_commit(self.last_decided_leader);
+            if sequenced_leaders.is_empty() {
+                break;
+            }
+
+            // Keep the decided leaders up to the one that completes the current
+            // schedule. Skipped leaders do not count as commits.
+            let mut num_committed = 0;
+            let sequenced_leaders = sequenced_leaders
+                .into_iter()
+                .take_while(|leader| {
+                    if num_committed == commits_until_update {
+                        return false;
+                    }
+                    if matches!(leader, LeaderStatus::Commit(_)) {
+                        num_committed += 1;
+                    }
+                    true
+                })
+                .collect::<Vec<_>>();
 
-        if let Some(last) = sequenced_leaders.last() {
+            let Some(last) = sequenced_leaders.last() else {
+                break;
+            };
             self.last_decided_leader = last.get_decided_slot();
             self.context
                 .metrics
                 .node_metrics
                 .last_decided_leader_round
                 .set(self.last_decided_leader.round as i64);
-        }
 
-        let committed_leaders = sequenced_leaders
-            .into_iter()
-            .filter_map(|leader| leader.into_committed_block())
-            .collect::<Vec<_>>();
+            let committed_leaders = sequenced_leaders
+                .into_iter()
+                .filter_map(|leader| leader.into_committed_block())
+                .collect::<Vec<_>>();
+
+            committed_subdags.extend(self.commit_observer.handle_commit(committed_leaders)?);
+        }
 
-        self.commit_observer.handle_commit(committed_leaders)
+        Ok(committed_subdags)
     }
 
     pub(crate) fn get_missing_blocks(&self) -> BTreeSet<BlockRef> {
@@ -614,7 +674,7 @@ mod test {
     use crate::{
         block::{genesis_blocks, TestBlock},
         block_verifier::NoopBlockVerifier,
-        commit::CommitAPI as _,
+        commit::{CommitAPI as _, CommitRange},
         storage::{mem_store::MemStore, Store, WriteBatch},
         transaction::TransactionClient,
         CommitConsumer, CommitIndex,
@@ -1245,6 +1305,171 @@ mod test {
         assert_eq!(all_stored_commits.len(), 6);
     }
 
+    /// Commits of a single `try_commit` call are truncated at the schedule
+    /// boundaries, so that the leader schedule is updated exactly every
+    /// `num_commits_per_schedule` commits, and the schedule is restored from
+    /// the store.
+    #[tokio::test]
+    async fn test_core_try_commit_updates_leader_schedule_at_boundaries() {
+        telemetry_subscribers::init_for_testing();
+        const COMMITS_PER_SCHEDULE: u64 = 2;
+
+        let mut cores = create_cores(vec![1, 1, 1, 1]);
+        for (core, _, _, _, _) in &mut cores {
+            core.leader_schedule = core
+                .leader_schedule
+                .clone()
+                .with_num_commits_per_schedule(COMMITS_PER_SCHEDULE);
+        }
+
+        // Build the DAG for 10 rounds without the blocks of authority 3.
+        let excluded_authority = AuthorityIndex::new_for_test(3);
+        let mut last_round_blocks = Vec::new();
+        let mut all_blocks = Vec::new();
+        for round in 1..=10 {
+            let mut this_round_blocks = Vec::new();
+            for (core, _, _, _, _) in &mut cores {
+                if core.context.own_index == excluded_authority {
+                    continue;
+                }
+                core.add_blocks(last_round_blocks.clone()).unwrap();
+                core.force_new_block(round).unwrap();
+                this_round_blocks.push(core.last_proposed_block().clone());
+            }
+            last_round_blocks.clone_from(&this_round_blocks);
+            all_blocks.extend(this_round_blocks);
+        }
+
+        // Let authority 3 commit the whole DAG within a single `try_commit` call.
+        let (core, _, _, _, store) = &mut cores[excluded_authority];
+        core.add_blocks(all_blocks).unwrap();
+
+        let last_commit_index = store
+            .read_last_commit()
+            .unwrap()
+            .expect("last commit should be set")
+            .index();
+        assert!(last_commit_index as u64 >= 2 * COMMITS_PER_SCHEDULE);
+        let last_scored_commit_index =
+            last_commit_index - last_commit_index % COMMITS_PER_SCHEDULE as CommitIndex;
+
+        // The schedule was updated once per completed schedule, each time from
+        // exactly `COMMITS_PER_SCHEDULE` commits.
+        assert_eq!(
+            core.context
+                .metrics
+                .node_metrics
+                .leader_schedule_updates_total
+                .get(),
+            last_commit_index as u64 / COMMITS_PER_SCHEDULE
+        );
+        let reputation_scores = core.dag_state.read().last_reputation_scores();
+        assert_eq!(
+            reputation_scores.commit_range,
+            CommitRange::new(
+                last_scored_commit_index - COMMITS_PER_SCHEDULE as CommitIndex + 1,
+                last_scored_commit_index
+            )
+        );
+        assert_eq!(
+            core.dag_state.read().unscored_committed_subdags_count(),
+            (last_commit_index - last_scored_commit_index) as u64
+        );
+
+        // Scores are persisted with the commits, so a recovered DagState either
+        // has the latest scores or the previous ones with a full schedule of
+        // unscored commits, which triggers the same update on the next commit.
+        let recovered_dag_state = DagState::new(core.context.clone(), store.clone());
+        let recovered_scores = recovered_dag_state.last_reputation_scores();
+        let recovered_unscored = recovered_dag_state.unscored_committed_subdags_count();
+        assert_eq!(
+            recovered_scores.commit_range.end as u64 + recovered_unscored,
+            last_commit_index as u64
+        );
+        if recovered_unscored < COMMITS_PER_SCHEDULE {
+            assert_eq!(recovered_scores, reputation_scores);
+        } else {
+            assert_eq!(recovered_unscored, COMMITS_PER_SCHEDULE);
+        }
+    }
+
+    /// Without leader scoring the leader schedule is never updated, so
+    /// leaders keep being elected in the original order, however many commits
+    /// are made.
+    #[tokio::test]
+    async fn test_core_try_commit_keeps_leader_schedule_without_scoring() {
+        telemetry_subscribers::init_for_testing();
+        let _guard = ProtocolConfig::apply_overrides_for_testing(|_, mut config| {
+            config.set_mysticeti_leader_scoring_and_schedule_for_testing(false);
+            config
+        });
+        const COMMITS_PER_SCHEDULE: u64 = 2;
+
+        let mut cores = create_cores(vec![1, 1, 1, 1]);
+        for (core, _, _, _, _) in &mut cores {
+            core.leader_schedule = core
+                .leader_schedule
+                .clone()
+                .with_num_commits_per_schedule(COMMITS_PER_SCHEDULE);
+        }
+
+        // Build the DAG for 10 rounds without the blocks of authority 3.
+        let excluded_authority = AuthorityIndex::new_for_test(3);
+        let mut last_round_blocks = Vec::new();
+        let mut all_blocks = Vec::new();
+        for round in 1..=10 {
+            let mut this_round_blocks = Vec::new();
+            for (core, _, _, _, _) in &mut cores {
+                if core.context.own_index == excluded_authority {
+                    continue;
+                }
+                core.add_blocks(last_round_blocks.clone()).unwrap();
+                core.force_new_block(round).unwrap();
+                this_round_blocks.push(core.last_proposed_block().clone());
+            }
+            last_round_blocks.clone_from(&this_round_blocks);
+            all_blocks.extend(this_round_blocks);
+        }
+
+        let (core, _, _, _, store) = &mut cores[excluded_authority];
+        core.add_blocks(all_blocks).unwrap();
+
+        // Commits were not truncated at the schedule boundaries.
+        let last_commit_index = store
+            .read_last_commit()
+            .unwrap()
+            .expect("last commit should be set")
+            .index();
+        assert!(last_commit_index as u64 >= 2 * COMMITS_PER_SCHEDULE);
+
+        // No reputation scores were calculated, buffered or persisted.
+        assert_eq!(
+            core.context
+                .metrics
+                .node_metrics
+                .leader_schedule_updates_total
+                .get(),
+            0
+        );
+        assert!(core.dag_state.read().last_reputation_scores().is_empty());
+        assert_eq!(core.dag_state.read().unscored_committed_subdags_count(), 0);
+        assert!(
+            store
+                .read_last_reputation_scores()
+                .unwrap()
+                .unwrap_or_default()
+                .is_empty()
+        );
+
+        // The authority that did not propose any blocks is still elected.
+        for round in 1..=12 {
+            assert_eq!(
+                core.leader_schedule.elect_leader(round, 0),
+                AuthorityIndex::new_for_test(round % 4)
+            );
+        }
+    }
+
     /// Creates cores for the specified number of authorities for their
     /// corresponding stakes. The method returns the cores and their
     /// respective signal receivers are returned in `AuthorityIndex` order asc.
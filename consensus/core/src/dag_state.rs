@@ -17,8 +17,13 @@ use crate::{
     block::{
         genesis_blocks, BlockAPI, BlockDigest, BlockRef, Round, Slot, VerifiedBlock, GENESIS_ROUND,
     },
-    commit::{CommitAPI as _, CommitDigest, CommitIndex, CommitRef, TrustedCommit},
+    commit::{
+        load_committed_subdag_from_store, CommitAPI as _, CommitDigest, CommitIndex, CommitRef,
+        CommittedSubDag, TrustedCommit,
+    },
     context::Context,
+    leader_schedule::CONSENSUS_COMMITS_PER_SCHEDULE,
+    leader_scoring::ReputationScores,
     stake_aggregator::{QuorumThreshold, StakeAggregator},
     storage::{Store, WriteBatch},
 };
@@ -65,6 +70,13 @@ pub(crate) struct DagState {
     blocks_to_write: Vec<VerifiedBlock>,
     commits_to_write: Vec<TrustedCommit>,
 
+    // Committed sub-dags that have not been used to calculate reputation scores yet.
+    unscored_committed_subdags: Vec<CommittedSubDag>,
+
+    // The reputation scores of the current leader schedule. Persisted with every
+    // commit info.
+    last_reputation_scores: ReputationScores,
+
     // Persistent storage for blocks, commits and other consensus data.
     store: Arc<dyn Store>,
 
@@ -96,6 +108,38 @@ impl DagState {
                 vec![0; num_authorities]
             }
         };
+        // Reputation scores are stored apart from the commit info, and are missing
+        // for data written before they were persisted.
+        let last_reputation_scores = store
+            .read_last_reputation_scores()
+            .unwrap_or_else(|e| panic!("Failed to read from storage: {:?}", e))
+            .unwrap_or_default();
+
+        // Recover the committed sub-dags that were not scored yet, i.e. the ones
+        // committed after the range of the last reputation scores. Without scores,
+        // only the commits of the current schedule period are unscored. Nothing
+        // is scored when leader scoring is disabled.
+        let unscored_committed_subdags = match &last_commit {
+            Some(last_commit)
+                if context
+                    .protocol_config
+                    .mysticeti_leader_scoring_and_schedule() =>
+            {
+                let first_unscored_commit = if last_reputation_scores.is_empty() {
+                    let last_index = last_commit.index();
+                    last_index - last_index % CONSENSUS_COMMITS_PER_SCHEDULE as CommitIndex + 1
+                } else {
+                    last_reputation_scores.commit_range.end + 1
+                };
+                store
+                    .scan_commits(first_unscored_commit..last_commit.index() + 1)
+                    .unwrap_or_else(|e| panic!("Failed to read from storage: {:?}", e))
+                    .into_iter()
+                    .map(|commit| load_committed_subdag_from_store(store.as_ref(), commit))
+                    .collect()
+            }
+            _ => vec![],
+        };
 
         let mut state = Self {
             context,
@@ -109,6 +153,8 @@ impl DagState {
             commits_to_vote: VecDeque::new(),
             blocks_to_write: vec![],
             commits_to_write: vec![],
+            unscored_committed_subdags,
+            last_reputation_scores,
             store,
             cached_rounds,
         };
@@ -562,6 +608,38 @@ impl DagState {
         self.last_committed_rounds.clone()
     }
 
+    /// Buffers committed sub-dags, to be scored on the next leader schedule
+    /// update. Nothing is buffered when leader scoring is disabled.
+    pub(crate) fn add_unscored_committed_subdags(
+        &mut self,
+        committed_subdags: Vec<CommittedSubDag>,
+    ) {
+        if self
+            .context
+            .protocol_config
+            .mysticeti_leader_scoring_and_schedule()
+        {
+            self.unscored_committed_subdags.extend(committed_subdags);
+        }
+    }
+
+    pub(crate) fn unscored_committed_subdags_count(&self) -> u64 {
+        self.unscored_committed_subdags.len() as u64
+    }
+
+    pub(crate) fn take_unscored_committed_subdags(&mut self) -> Vec<CommittedSubDag> {
+        std::mem::take(&mut self.unscored_committed_subdags)
+    }
+
+    /// The reputation scores of the current leader schedule.
+    pub(crate) fn last_reputation_scores(&self) -> ReputationScores {
+        self.last_reputation_scores.clone()
+    }
+
+    pub(crate) fn set_last_reputation_scores(&mut self, reputation_scores: ReputationScores) {
+        self.last_reputation_scores = reputation_scores;
+    }
+
     /// After each flush, DagState becomes persisted in storage and it expected
     /// to recover all internal states from storage after restarts.
     pub(crate) fn flush(&mut self) {
@@ -584,6 +662,7 @@ impl DagState {
                 commits,
                 // TODO: limit to write at most once per commit round with multi-leader.
                 self.last_committed_rounds.clone(),
+                self.last_reputation_scores.clone(),
             ))
             .unwrap_or_else(|e| panic!("Failed to write to storage: {:?}", e));
         self.context
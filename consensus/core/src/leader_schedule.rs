@@ -2,40 +2,147 @@
 // Modifications Copyright (c) 2024 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use std::sync::Arc;
+use std::{
+    collections::BTreeMap,
+    fmt::{Debug, Formatter},
+    sync::Arc,
+};
 
-use consensus_config::AuthorityIndex;
+use consensus_config::{AuthorityIndex, Stake};
+use parking_lot::RwLock;
 use rand::{prelude::SliceRandom, rngs::StdRng, SeedableRng};
 
-use crate::context::Context;
+use crate::{
+    context::Context,
+    dag_state::DagState,
+    leader_scoring::{ReputationScoreCalculator, ReputationScores},
+};
+
+/// The number of commits after which the leader schedule is recomputed from
+/// the reputation scores of the authorities. It must be the same for all
+/// authorities, so that they all switch schedules at the same commit.
+pub(crate) const CONSENSUS_COMMITS_PER_SCHEDULE: u64 = 300;
 
 /// The LeaderSchedule is responsible for producing the leader schedule across
-/// an epoch. For now it is a simple wrapper around Context to provide a leader
-/// for a round deterministically.
-// TODO: complete full leader schedule changes
+/// an epoch. Leaders are elected by a stake-weighted shuffle seeded by the
+/// round, and then swapped according to the [`LeaderSwapTable`], which is
+/// recomputed every `num_commits_per_schedule` commits from the reputation
+/// scores of the authorities.
 #[derive(Clone)]
 pub(crate) struct LeaderSchedule {
     context: Arc<Context>,
+    num_commits_per_schedule: u64,
+    leader_swap_table: Arc<RwLock<LeaderSwapTable>>,
 }
 
 impl LeaderSchedule {
-    pub fn new(context: Arc<Context>) -> Self {
-        Self { context }
-    }
-
-    pub fn elect_leader(&self, round: u32, leader_offset: u32) -> AuthorityIndex {
-        cfg_if::cfg_if! {
-            // TODO: we need to differentiate the leader strategy in tests, so for
-            // some type of testing (ex sim tests) we can use the staked approach.
-            if #[cfg(test)] {
-                AuthorityIndex::new_for_test((round + leader_offset) % self.context.committee.size() as u32)
-            } else {
-                self.elect_leader_stake_based(round, leader_offset)
-            }
+    pub(crate) fn new(context: Arc<Context>) -> Self {
+        Self {
+            context,
+            num_commits_per_schedule: CONSENSUS_COMMITS_PER_SCHEDULE,
+            leader_swap_table: Arc::new(RwLock::new(LeaderSwapTable::default())),
+        }
+    }
+
+    /// Restores the leader schedule from the reputation scores that were
+    /// persisted together with the last commit.
+    pub(crate) fn from_store(context: Arc<Context>, dag_state: Arc<RwLock<DagState>>) -> Self {
+        let reputation_scores = dag_state.read().last_reputation_scores();
+        let leader_schedule = Self::new(context.clone());
+        if context
+            .protocol_config
+            .mysticeti_leader_scoring_and_schedule()
+            && !reputation_scores.is_empty()
+        {
+            tracing::info!("Restoring leader schedule from {reputation_scores:?}");
+            reputation_scores.update_metrics(context.clone());
+            leader_schedule.update_leader_swap_table(LeaderSwapTable::new(
+                context.clone(),
+                reputation_scores,
+                context
+                    .protocol_config
+                    .consensus_bad_nodes_stake_threshold(),
+            ));
         }
+        leader_schedule
+    }
+
+    #[cfg(test)]
+    pub(crate) fn with_num_commits_per_schedule(mut self, num_commits_per_schedule: u64) -> Self {
+        self.num_commits_per_schedule = num_commits_per_schedule;
+        self
+    }
+
+    /// Returns how many commits can still be made before the leader schedule
+    /// has to be recomputed.
+    pub(crate) fn commits_until_leader_schedule_update(
+        &self,
+        dag_state: Arc<RwLock<DagState>>,
+    ) -> usize {
+        let unscored_committed_subdags_count = dag_state.read().unscored_committed_subdags_count();
+        assert!(
+            unscored_committed_subdags_count <= self.num_commits_per_schedule,
+            "Unscored committed subdags count exceeds the number of commits per schedule"
+        );
+        self.num_commits_per_schedule
+            .checked_sub(unscored_committed_subdags_count)
+            .unwrap() as usize
+    }
+
+    /// Computes the reputation scores from the committed sub-dags that have
+    /// not been scored yet and switches to a new leader swap table. The new
+    /// scores are persisted with the next commit flushed by `DagState`.
+    pub(crate) fn update_leader_schedule(&self, dag_state: &RwLock<DagState>) {
+        let _s = self
+            .context
+            .metrics
+            .node_metrics
+            .scope_processing_time
+            .with_label_values(&["LeaderSchedule::update_leader_schedule"])
+            .start_timer();
+
+        let mut dag_state = dag_state.write();
+        let unscored_subdags = dag_state.take_unscored_committed_subdags();
+        let reputation_scores =
+            ReputationScoreCalculator::new(self.context.clone(), &unscored_subdags).calculate();
+        reputation_scores.update_metrics(self.context.clone());
+
+        self.update_leader_swap_table(LeaderSwapTable::new(
+            self.context.clone(),
+            reputation_scores.clone(),
+            self.context
+                .protocol_config
+                .consensus_bad_nodes_stake_threshold(),
+        ));
+        dag_state.set_last_reputation_scores(reputation_scores);
+
+        self.context
+            .metrics
+            .node_metrics
+            .leader_schedule_updates_total
+            .inc();
+    }
+
+    pub(crate) fn elect_leader(&self, round: u32, leader_offset: u32) -> AuthorityIndex {
+        let leader = {
+            cfg_if::cfg_if! {
+                // TODO: we need to differentiate the leader strategy in tests, so for
+                // some type of testing (ex sim tests) we can use the staked approach.
+                if #[cfg(test)] {
+                    AuthorityIndex::new_for_test((round + leader_offset) % self.context.committee.size() as u32)
+                } else {
+                    self.elect_leader_stake_based(round, leader_offset)
+                }
+            }
+        };
+
+        self.leader_swap_table
+            .read()
+            .swap(leader, round, leader_offset)
+            .unwrap_or(leader)
     }
 
-    pub fn elect_leader_stake_based(&self, round: u32, offset: u32) -> AuthorityIndex {
+    pub(crate) fn elect_leader_stake_based(&self, round: u32, offset: u32) -> AuthorityIndex {
         assert!((offset as usize) < self.context.committee.size());
 
         // To ensure that we elect different leaders for the same round (using
@@ -63,6 +170,177 @@ impl LeaderSchedule {
 
         leader_index
     }
+
+    fn update_leader_swap_table(&self, table: LeaderSwapTable) {
+        let read_guard = self.leader_swap_table.read();
+        let old_commit_range = &read_guard.reputation_scores.commit_range;
+        let new_commit_range = &table.reputation_scores.commit_range;
+
+        // Unless this is the first schedule, the new scores must be calculated
+        // from the commits right after the ones of the previous schedule.
+        if !read_guard.reputation_scores.is_empty() {
+            assert_eq!(
+                old_commit_range.end + 1,
+                new_commit_range.start,
+                "The new leader swap table has an invalid commit range"
+            );
+        }
+        drop(read_guard);
+
+        tracing::debug!("Updating {table:?}");
+        self.context
+            .metrics
+            .node_metrics
+            .leader_swap_bad_nodes
+            .set(table.bad_nodes.len() as i64);
+
+        let mut write_guard = self.leader_swap_table.write();
+        *write_guard = table;
+    }
+}
+
+/// Swaps out the worst performing leaders for the best performing ones. The
+/// table is derived from the reputation scores only, so it is the same on all
+/// authorities.
+#[derive(Default, Clone)]
+pub(crate) struct LeaderSwapTable {
+    /// The authorities with the highest scores, that can replace a bad leader.
+    /// Ordered by score, highest first.
+    pub(crate) good_nodes: Vec<(AuthorityIndex, String, Stake)>,
+    /// The authorities with the lowest scores, that are swapped out whenever
+    /// they are elected as leaders.
+    pub(crate) bad_nodes: BTreeMap<AuthorityIndex, (String, Stake)>,
+    /// The scores the table was built from.
+    pub(crate) reputation_scores: ReputationScores,
+}
+
+impl LeaderSwapTable {
+    /// Builds a swap table where both the good and the bad nodes account for
+    /// at most `swap_stake_threshold` percent of the total stake. The
+    /// threshold must be in the range `[0, 33]`, so that the good nodes are
+    /// always a majority of the honest authorities.
+    pub(crate) fn new(
+        context: Arc<Context>,
+        reputation_scores: ReputationScores,
+        swap_stake_threshold: u64,
+    ) -> Self {
+        assert!(
+            (0..=33).contains(&swap_stake_threshold),
+            "The swap_stake_threshold ({swap_stake_threshold}) should be in range [0 - 33], out of bounds parameter detected"
+        );
+
+        // Nothing to swap if all authorities performed equally.
+        let authorities_by_score = reputation_scores.authorities_by_score_desc(context.clone());
+        if authorities_by_score.first().map(|(_, score)| *score)
+            == authorities_by_score.last().map(|(_, score)| *score)
+        {
+            return Self {
+                good_nodes: vec![],
+                bad_nodes: BTreeMap::new(),
+                reputation_scores,
+            };
+        }
+
+        let good_nodes = Self::retrieve_first_nodes(
+            context.clone(),
+            authorities_by_score.iter().cloned(),
+            swap_stake_threshold,
+        );
+
+        // The bad nodes are the ones with the lowest scores, excluding any node that
+        // was already picked as good.
+        let bad_nodes = Self::retrieve_first_nodes(
+            context.clone(),
+            authorities_by_score.iter().rev().cloned(),
+            swap_stake_threshold,
+        )
+        .into_iter()
+        .filter(|(authority, _, _)| !good_nodes.iter().any(|(good, _, _)| good == authority))
+        .map(|(authority, hostname, stake)| (authority, (hostname, stake)))
+        .collect::<BTreeMap<_, _>>();
+
+        tracing::debug!("Good nodes: {good_nodes:?}, bad nodes: {bad_nodes:?}");
+
+        Self {
+            good_nodes,
+            bad_nodes,
+            reputation_scores,
+        }
+    }
+
+    /// Returns the authority that should lead instead of `leader`, if
+    /// `leader` is a bad node. The replacement is chosen among the good nodes
+    /// using the round and the leader offset as seed, so it is deterministic
+    /// and differs between the leaders of the same round.
+    pub(crate) fn swap(
+        &self,
+        leader: AuthorityIndex,
+        leader_round: u32,
+        leader_offset: u32,
+    ) -> Option<AuthorityIndex> {
+        if self.good_nodes.is_empty() || !self.bad_nodes.contains_key(&leader) {
+            return None;
+        }
+
+        let mut seed_bytes = [0u8; 32];
+        seed_bytes[24..28].copy_from_slice(&leader_round.to_le_bytes());
+        seed_bytes[28..32].copy_from_slice(&leader_offset.to_le_bytes());
+        let mut rng = StdRng::from_seed(seed_bytes);
+
+        let (authority, hostname, _stake) = self
+            .good_nodes
+            .choose(&mut rng)
+            .expect("There should be at least one good node available");
+
+        tracing::trace!(
+            "Swapping bad leader {leader} -> {authority} ({hostname}) for round {leader_round} and offset {leader_offset}"
+        );
+        Some(*authority)
+    }
+
+    /// Takes authorities from the provided iterator while their accumulated
+    /// stake stays within `stake_threshold` percent of the total stake.
+    fn retrieve_first_nodes(
+        context: Arc<Context>,
+        authorities: impl Iterator<Item = (AuthorityIndex, u64)>,
+        stake_threshold: u64,
+    ) -> Vec<(AuthorityIndex, String, Stake)> {
+        let mut filtered_authorities = Vec::new();
+        let mut stake = 0;
+        for (authority_idx, _score) in authorities {
+            let authority = context.committee.authority(authority_idx);
+            stake += authority.stake;
+
+            // Stop as soon as the accumulated stake exceeds the threshold.
+            if stake > (stake_threshold * context.committee.total_stake()) / 100 as Stake {
+                break;
+            }
+            filtered_authorities.push((authority_idx, authority.hostname.clone(), authority.stake));
+        }
+        filtered_authorities
+    }
+}
+
+impl Debug for LeaderSwapTable {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&format!(
+            "LeaderSwapTable for {:?}, good_nodes: {:?} with stake: {}, bad_nodes: {:?} with stake: {}",
+            self.reputation_scores.commit_range,
+            self.good_nodes
+                .iter()
+                .map(|(idx, _hostname, _stake)| idx.to_owned())
+                .collect::<Vec<AuthorityIndex>>(),
+            self.good_nodes
+                .iter()
+                .map(|(_idx, _hostname, stake)| stake)
+                .sum::<Stake>(),
+            self.bad_nodes.keys().copied().collect::<Vec<AuthorityIndex>>(),
+            self.bad_nodes
+                .values()
+                .map(|(_hostname, stake)| stake)
+                .sum::<Stake>(),
+        ))
+    }
 }
 
 #[cfg(test)]
@@ -71,7 +349,12 @@ mod tests {
     use iota_protocol_config::ProtocolConfig;
 
     use super::*;
-    use crate::metrics::test_metrics;
+    use crate::{
+        block::{BlockDigest, BlockRef},
+        commit::{CommitDigest, CommitRange, TrustedCommit},
+        metrics::test_metrics,
+        storage::{mem_store::MemStore, Store, WriteBatch},
+    };
 
     #[test]
     fn test_elect_leader() {
@@ -138,4 +421,79 @@ mod tests {
             leader_schedule.elect_leader_stake_based(1, 2)
         );
     }
+
+    #[test]
+    fn test_leader_swap_table() {
+        let context = Arc::new(Context::new_for_test(4).0);
+        let reputation_scores = ReputationScores::new(CommitRange::new(1, 300), vec![4, 3, 2, 1]);
+
+        // With a threshold of 33% a single authority of stake 1 can be swapped out.
+        let table = LeaderSwapTable::new(context.clone(), reputation_scores, 33);
+        assert_eq!(table.good_nodes.len(), 1);
+        assert_eq!(table.good_nodes[0].0, AuthorityIndex::new_for_test(0));
+        assert_eq!(
+            table.bad_nodes.keys().copied().collect::<Vec<_>>(),
+            vec![AuthorityIndex::new_for_test(3)]
+        );
+
+        assert_eq!(
+            table.swap(AuthorityIndex::new_for_test(3), 10, 0),
+            Some(AuthorityIndex::new_for_test(0))
+        );
+        assert_eq!(table.swap(AuthorityIndex::new_for_test(1), 10, 0), None);
+    }
+
+    #[test]
+    fn test_leader_swap_table_equal_scores() {
+        let context = Arc::new(Context::new_for_test(4).0);
+        let reputation_scores = ReputationScores::new(CommitRange::new(1, 300), vec![2, 2, 2, 2]);
+
+        let table = LeaderSwapTable::new(context, reputation_scores, 33);
+        assert!(table.good_nodes.is_empty());
+        assert!(table.bad_nodes.is_empty());
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "The swap_stake_threshold (34) should be in range [0 - 33], out of bounds parameter detected"
+    )]
+    fn test_leader_swap_table_invalid_threshold() {
+        let context = Arc::new(Context::new_for_test(4).0);
+        LeaderSwapTable::new(context, ReputationScores::default(), 34);
+    }
+
+    #[test]
+    fn test_leader_schedule_from_store() {
+        let context = Arc::new(Context::new_for_test(4).0);
+        let store = Arc::new(MemStore::new());
+
+        // Persist a commit together with the scores of the last schedule.
+        let reputation_scores = ReputationScores::new(CommitRange::new(1, 1), vec![4, 3, 2, 1]);
+        let commit = TrustedCommit::new_for_test(
+            1,
+            CommitDigest::MIN,
+            BlockRef::new(1, AuthorityIndex::new_for_test(0), BlockDigest::MIN),
+            vec![],
+        );
+        store
+            .write(WriteBatch::new(
+                vec![],
+                vec![commit],
+                vec![0; 4],
+                reputation_scores.clone(),
+            ))
+            .unwrap();
+
+        let dag_state = Arc::new(RwLock::new(DagState::new(context.clone(), store)));
+        assert_eq!(dag_state.read().last_reputation_scores(), reputation_scores);
+        assert_eq!(dag_state.read().unscored_committed_subdags_count(), 0);
+
+        let leader_schedule = LeaderSchedule::from_store(context, dag_state);
+        let table = leader_schedule.leader_swap_table.read();
+        assert_eq!(table.reputation_scores, reputation_scores);
+        assert_eq!(
+            table.bad_nodes.keys().copied().collect::<Vec<_>>(),
+            vec![AuthorityIndex::new_for_test(3)]
+        );
+    }
 }
@@ -0,0 +1,291 @@
+// Copyright (c) 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::{self, Debug, Formatter},
+    sync::Arc,
+};
+
+use consensus_config::AuthorityIndex;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    block::{BlockAPI, BlockRef},
+    commit::{CommitRange, CommittedSubDag},
+    context::Context,
+};
+
+/// The reputation scores of the authorities, computed over a range of
+/// commits. Scores are derived only from committed sub-dags, so every
+/// authority computes the same scores for the same commit range.
+#[derive(Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct ReputationScores {
+    /// Score per authority. Vec index is the `AuthorityIndex`.
+    pub(crate) scores_per_authority: Vec<u64>,
+    /// The range of commits these scores were calculated from.
+    pub(crate) commit_range: CommitRange,
+}
+
+impl ReputationScores {
+    pub(crate) fn new(commit_range: CommitRange, scores_per_authority: Vec<u64>) -> Self {
+        Self {
+            scores_per_authority,
+            commit_range,
+        }
+    }
+
+    /// Whether any scores have been calculated yet.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.scores_per_authority.is_empty()
+    }
+
+    pub(crate) fn highest_score(&self) -> u64 {
+        *self.scores_per_authority.iter().max().unwrap_or(&0)
+    }
+
+    /// Returns the authorities sorted by score in descending order. Ties are
+    /// broken by authority index, so the order is deterministic.
+    pub(crate) fn authorities_by_score_desc(
+        &self,
+        context: Arc<Context>,
+    ) -> Vec<(AuthorityIndex, u64)> {
+        let mut authorities: Vec<_> = self
+            .scores_per_authority
+            .iter()
+            .enumerate()
+            .map(|(index, score)| {
+                (
+                    context
+                        .committee
+                        .to_authority_index(index)
+                        .expect("Should be a valid AuthorityIndex"),
+                    *score,
+                )
+            })
+            .collect();
+
+        authorities.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        authorities
+    }
+
+    pub(crate) fn update_metrics(&self, context: Arc<Context>) {
+        for (index, score) in self.scores_per_authority.iter().enumerate() {
+            let authority_index = context
+                .committee
+                .to_authority_index(index)
+                .expect("Should be a valid AuthorityIndex");
+            let authority = context.committee.authority(authority_index);
+            context
+                .metrics
+                .node_metrics
+                .reputation_scores
+                .with_label_values(&[&authority.hostname])
+                .set(*score as i64);
+        }
+    }
+}
+
+impl Debug for ReputationScores {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "ReputationScores(commits={}..={}, scores={:?})",
+            self.commit_range.start, self.commit_range.end, self.scores_per_authority
+        )
+    }
+}
+
+/// Calculates the reputation scores of the authorities from a continuous
+/// range of committed sub-dags.
+///
+/// An authority earns one point for every committed leader that includes the
+/// authority's block of the previous round as a direct ancestor, i.e. every
+/// time the authority helped to form the quorum a committed leader was built
+/// on. Slow or faulty authorities miss these quorums and score lower.
+pub(crate) struct ReputationScoreCalculator<'a> {
+    context: Arc<Context>,
+    unscored_subdags: &'a [CommittedSubDag],
+    commit_range: CommitRange,
+}
+
+impl<'a> ReputationScoreCalculator<'a> {
+    pub(crate) fn new(context: Arc<Context>, unscored_subdags: &'a [CommittedSubDag]) -> Self {
+        assert!(
+            !unscored_subdags.is_empty(),
+            "Attempted to calculate scores with no unscored subdags"
+        );
+        let commit_indexes = unscored_subdags
+            .iter()
+            .map(|subdag| subdag.commit_index)
+            .collect::<Vec<_>>();
+        let min_commit_index = *commit_indexes.iter().min().unwrap();
+        let max_commit_index = *commit_indexes.iter().max().unwrap();
+        let commit_range = CommitRange::new(min_commit_index, max_commit_index);
+        assert_eq!(
+            commit_range.size(),
+            unscored_subdags.len(),
+            "Unscored subdags must cover a continuous range of commits"
+        );
+
+        Self {
+            context,
+            unscored_subdags,
+            commit_range,
+        }
+    }
+
+    pub(crate) fn calculate(&self) -> ReputationScores {
+        let mut scores_per_authority = vec![0_u64; self.context.committee.size()];
+        for subdag in self.unscored_subdags {
+            for (authority, score) in self.calculate_scores_for_subdag(subdag) {
+                scores_per_authority[authority] += score;
+            }
+        }
+        ReputationScores::new(self.commit_range.clone(), scores_per_authority)
+    }
+
+    fn calculate_scores_for_subdag(
+        &self,
+        subdag: &CommittedSubDag,
+    ) -> BTreeMap<AuthorityIndex, u64> {
+        let mut scores = BTreeMap::new();
+        let Some(leader_block) = subdag
+            .blocks
+            .iter()
+            .find(|block| block.reference() == subdag.leader)
+        else {
+            return scores;
+        };
+
+        let previous_round = subdag.leader.round.saturating_sub(1);
+        // Collecting into a set so that an equivocating authority is not rewarded
+        // twice.
+        let voters = leader_block
+            .ancestors()
+            .iter()
+            .filter(|ancestor| ancestor.round == previous_round)
+            .map(|ancestor: &BlockRef| ancestor.author)
+            .collect::<BTreeSet<_>>();
+        for author in voters {
+            *scores.entry(author).or_insert(0) += 1;
+        }
+        scores
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use parking_lot::RwLock;
+
+    use super::*;
+    use crate::{
+        block::{TestBlock, VerifiedBlock},
+        dag_state::DagState,
+        storage::mem_store::MemStore,
+        test_dag::{build_dag, build_dag_layer},
+    };
+
+    #[test]
+    fn test_reputation_scores_authorities_by_score_desc() {
+        let context = Arc::new(Context::new_for_test(4).0);
+        let scores = ReputationScores::new(CommitRange::new(1, 300), vec![4, 1, 1, 3]);
+
+        let authorities = scores.authorities_by_score_desc(context);
+        assert_eq!(
+            authorities,
+            vec![
+                (AuthorityIndex::new_for_test(0), 4),
+                (AuthorityIndex::new_for_test(3), 3),
+                (AuthorityIndex::new_for_test(1), 1),
+                (AuthorityIndex::new_for_test(2), 1),
+            ]
+        );
+        assert_eq!(scores.highest_score(), 4);
+    }
+
+    #[test]
+    fn test_reputation_score_calculator() {
+        let context = Arc::new(Context::new_for_test(4).0);
+        let store = Arc::new(MemStore::new());
+        let dag_state = Arc::new(RwLock::new(DagState::new(context.clone(), store)));
+
+        // Fully connected rounds 1 & 2.
+        let round_2 = build_dag(context.clone(), dag_state.clone(), None, 2);
+
+        // In round 3 authority 3 is slow: the leader of round 3 only links to the
+        // blocks of authorities 0, 1 and 2 of round 2.
+        let ancestors_without_3 = round_2
+            .iter()
+            .filter(|block_ref| block_ref.author != AuthorityIndex::new_for_test(3))
+            .cloned()
+            .collect::<Vec<_>>();
+        let round_3 = build_dag_layer(
+            vec![(AuthorityIndex::new_for_test(0), ancestors_without_3)],
+            dag_state.clone(),
+        );
+
+        let leader_2 = dag_state
+            .read()
+            .get_uncommitted_blocks_at_round(2)
+            .into_iter()
+            .find(|block| block.author() == AuthorityIndex::new_for_test(2))
+            .unwrap();
+        let leader_3 = dag_state.read().get_block(&round_3[0]).unwrap();
+
+        let subdags = vec![
+            CommittedSubDag::new(
+                leader_2.reference(),
+                vec![leader_2.clone()],
+                leader_2.timestamp_ms(),
+                1,
+            ),
+            CommittedSubDag::new(
+                leader_3.reference(),
+                vec![leader_3.clone()],
+                leader_3.timestamp_ms(),
+                2,
+            ),
+        ];
+
+        let scores = ReputationScoreCalculator::new(context, &subdags).calculate();
+        assert_eq!(scores.commit_range, CommitRange::new(1, 2));
+        assert_eq!(scores.scores_per_authority, vec![2, 2, 2, 1]);
+    }
+
+    #[test]
+    fn test_reputation_score_calculator_ignores_equivocations() {
+        let context = Arc::new(Context::new_for_test(4).0);
+        let ancestors = (0..4)
+            .map(|author| {
+                VerifiedBlock::new_for_test(
+                    TestBlock::new(1, author).set_timestamp_ms(1000).build(),
+                )
+                .reference()
+            })
+            .chain(std::iter::once(
+                // An equivocating block of authority 0.
+                VerifiedBlock::new_for_test(TestBlock::new(1, 0).set_timestamp_ms(1001).build())
+                    .reference(),
+            ))
+            .collect::<Vec<_>>();
+        let leader =
+            VerifiedBlock::new_for_test(TestBlock::new(2, 1).set_ancestors(ancestors).build());
+        let subdags = vec![CommittedSubDag::new(
+            leader.reference(),
+            vec![leader.clone()],
+            leader.timestamp_ms(),
+            1,
+        )];
+
+        let scores = ReputationScoreCalculator::new(context, &subdags).calculate();
+        assert_eq!(scores.scores_per_authority, vec![1, 1, 1, 1]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Attempted to calculate scores with no unscored subdags")]
+    fn test_reputation_score_calculator_no_subdags() {
+        let context = Arc::new(Context::new_for_test(4).0);
+        ReputationScoreCalculator::new(context, &[]).calculate();
+    }
+}
@@ -16,6 +16,7 @@ mod core_thread;
 mod dag_state;
 mod error;
 mod leader_schedule;
+mod leader_scoring;
 mod leader_timeout;
 mod linearizer;
 mod metrics;
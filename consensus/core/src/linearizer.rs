@@ -122,7 +122,12 @@ impl Linearizer {
                 .serialize()
                 .unwrap_or_else(|e| panic!("Failed to serialize commit: {}", e));
             let commit = TrustedCommit::new_trusted(commit, serialized);
-            self.dag_state.write().add_commit(commit.clone());
+            let mut dag_state = self.dag_state.write();
+            dag_state.add_commit(commit.clone());
+            // Keep the sub-dag around for the reputation scores of the next leader
+            // schedule.
+            dag_state.add_unscored_committed_subdags(vec![sub_dag.clone()]);
+            drop(dag_state);
             committed_sub_dags.push(sub_dag);
         }
         // Committed blocks must be persisted to storage before sending them to Iota and
@@ -97,7 +97,10 @@ pub(crate) struct NodeMetrics {
     pub leader_timeout_total: IntCounter,
     pub missing_blocks_total: IntGauge,
     pub quorum_receive_latency: Histogram,
+    pub reputation_scores: IntGaugeVec,
     pub scope_processing_time: HistogramVec,
+    pub leader_schedule_updates_total: IntCounter,
+    pub leader_swap_bad_nodes: IntGauge,
     pub sub_dags_per_commit_count: Histogram,
     pub suspended_blocks: IntCounterVec,
     pub threshold_clock_round: IntGauge,
@@ -228,6 +231,22 @@ impl NodeMetrics {
                 "The time it took to receive a new round quorum of blocks",
                 registry
             ).unwrap(),
+            reputation_scores: register_int_gauge_vec_with_registry!(
+                "reputation_scores",
+                "Reputation scores per authority, as of the last leader schedule update",
+                &["authority"],
+                registry,
+            ).unwrap(),
+            leader_schedule_updates_total: register_int_counter_with_registry!(
+                "leader_schedule_updates_total",
+                "Total number of leader schedule updates based on reputation scores",
+                registry,
+            ).unwrap(),
+            leader_swap_bad_nodes: register_int_gauge_with_registry!(
+                "leader_swap_bad_nodes",
+                "The number of authorities that are swapped out of the leader schedule",
+                registry,
+            ).unwrap(),
             scope_processing_time: register_histogram_vec_with_registry!(
                 "scope_processing_time",
                 "The processing time of a specific code scope",
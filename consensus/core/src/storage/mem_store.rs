@@ -18,6 +18,7 @@ use crate::{
     block::{BlockAPI as _, BlockDigest, BlockRef, Round, Slot, VerifiedBlock},
     commit::{CommitAPI as _, CommitDigest, CommitIndex, TrustedCommit},
     error::ConsensusResult,
+    leader_scoring::ReputationScores,
 };
 
 /// In-memory storage for testing.
@@ -31,6 +32,7 @@ struct Inner {
     commits: BTreeMap<(CommitIndex, CommitDigest), TrustedCommit>,
     commit_votes: BTreeSet<(CommitIndex, CommitDigest, BlockRef)>,
     commit_info: BTreeMap<(CommitIndex, CommitDigest), CommitInfo>,
+    reputation_scores: BTreeMap<(CommitIndex, CommitDigest), ReputationScores>,
 }
 
 impl MemStore {
@@ -43,6 +45,7 @@ impl MemStore {
                 commits: BTreeMap::new(),
                 commit_votes: BTreeSet::new(),
                 commit_info: BTreeMap::new(),
+                reputation_scores: BTreeMap::new(),
             }),
         }
     }
@@ -81,6 +84,10 @@ impl Store for MemStore {
             inner
                 .commit_info
                 .insert((last_commit.index(), last_commit.digest()), commit_info);
+            inner.reputation_scores.insert(
+                (last_commit.index(), last_commit.digest()),
+                write_batch.reputation_scores,
+            );
         }
         Ok(())
     }
@@ -196,4 +203,12 @@ impl Store for MemStore {
         let inner = self.inner.read();
         Ok(inner.commit_info.last_key_value().map(|(_k, v)| v.clone()))
     }
+
+    fn read_last_reputation_scores(&self) -> ConsensusResult<Option<ReputationScores>> {
+        let inner = self.inner.read();
+        Ok(inner
+            .reputation_scores
+            .last_key_value()
+            .map(|(_k, v)| v.clone()))
+    }
 }
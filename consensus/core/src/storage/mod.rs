@@ -17,6 +17,7 @@ use crate::{
     block::{BlockRef, Round, Slot, VerifiedBlock},
     commit::{CommitIndex, TrustedCommit},
     error::ConsensusResult,
+    leader_scoring::ReputationScores,
 };
 
 /// A common interface for consensus storage.
@@ -62,6 +63,11 @@ pub(crate) trait Store: Send + Sync {
     /// Reads the last commit info, including last committed round per
     /// authority.
     fn read_last_commit_info(&self) -> ConsensusResult<Option<CommitInfo>>;
+
+    /// Reads the reputation scores of the leader schedule, as written with the
+    /// last commit. Returns `None` if no scores have been written yet, e.g.
+    /// for data written before scores were persisted.
+    fn read_last_reputation_scores(&self) -> ConsensusResult<Option<ReputationScores>>;
 }
 
 /// Represents data to be written to the store together atomically.
@@ -70,6 +76,7 @@ pub(crate) struct WriteBatch {
     pub(crate) blocks: Vec<VerifiedBlock>,
     pub(crate) commits: Vec<TrustedCommit>,
     pub(crate) last_committed_rounds: Vec<Round>,
+    pub(crate) reputation_scores: ReputationScores,
 }
 
 impl WriteBatch {
@@ -77,11 +84,13 @@ impl WriteBatch {
         blocks: Vec<VerifiedBlock>,
         commits: Vec<TrustedCommit>,
         last_committed_rounds: Vec<Round>,
+        reputation_scores: ReputationScores,
     ) -> Self {
         WriteBatch {
             blocks,
             commits,
             last_committed_rounds,
+            reputation_scores,
         }
     }
 
@@ -25,6 +25,7 @@ use crate::{
     block::{BlockAPI as _, BlockDigest, BlockRef, Round, SignedBlock, Slot, VerifiedBlock},
     commit::{CommitAPI as _, CommitDigest, CommitIndex, TrustedCommit},
     error::{ConsensusError, ConsensusResult},
+    leader_scoring::ReputationScores,
 };
 
 /// Persistent storage with RocksDB.
@@ -40,6 +41,10 @@ pub(crate) struct RocksDBStore {
     commit_votes: DBMap<(CommitIndex, CommitDigest, BlockRef), ()>,
     /// Stores the latest values of a few properties.
     commit_info: DBMap<(CommitIndex, CommitDigest), CommitInfo>,
+    /// Stores the reputation scores of the leader schedule with the last
+    /// commit. Kept apart from `commit_info` so that commit infos written
+    /// before scores were persisted can still be read.
+    reputation_scores: DBMap<(CommitIndex, CommitDigest), ReputationScores>,
 }
 
 impl RocksDBStore {
@@ -48,6 +53,7 @@ impl RocksDBStore {
     const COMMITS_CF: &'static str = "commits";
     const COMMIT_VOTES_CF: &'static str = "commit_votes";
     const COMMIT_INFO_CF: &'static str = "commit_info";
+    const REPUTATION_SCORES_CF: &'static str = "reputation_scores";
 
     /// Creates a new instance of RocksDB storage.
     pub(crate) fn new(path: &str) -> Self {
@@ -71,6 +77,7 @@ impl RocksDBStore {
             (Self::COMMITS_CF, cf_options.clone()),
             (Self::COMMIT_VOTES_CF, cf_options.clone()),
             (Self::COMMIT_INFO_CF, cf_options.clone()),
+            (Self::REPUTATION_SCORES_CF, cf_options.clone()),
         ];
         let rocksdb = open_cf_opts(
             path,
@@ -80,12 +87,13 @@ impl RocksDBStore {
         )
         .expect("Cannot open database");
 
-        let (blocks, digests_by_authorities, commits, commit_votes, commit_info) = reopen!(&rocksdb,
+        let (blocks, digests_by_authorities, commits, commit_votes, commit_info, reputation_scores) = reopen!(&rocksdb,
             Self::BLOCKS_CF;<(Round, AuthorityIndex, BlockDigest), bytes::Bytes>,
             Self::DIGESTS_BY_AUTHORITIES_CF;<(AuthorityIndex, Round, BlockDigest), ()>,
             Self::COMMITS_CF;<(CommitIndex, CommitDigest), Bytes>,
             Self::COMMIT_VOTES_CF;<(CommitIndex, CommitDigest, BlockRef), ()>,
-            Self::COMMIT_INFO_CF;<(CommitIndex, CommitDigest), CommitInfo>
+            Self::COMMIT_INFO_CF;<(CommitIndex, CommitDigest), CommitInfo>,
+            Self::REPUTATION_SCORES_CF;<(CommitIndex, CommitDigest), ReputationScores>
         );
 
         Self {
@@ -94,6 +102,7 @@ impl RocksDBStore {
             commits,
             commit_votes,
             commit_info,
+            reputation_scores,
         }
     }
 }
@@ -145,6 +154,15 @@ impl Store for RocksDBStore {
                     [((last_commit.index(), last_commit.digest()), commit_info)],
                 )
                 .map_err(ConsensusError::RocksDBFailure)?;
+            batch
+                .insert_batch(
+                    &self.reputation_scores,
+                    [(
+                        (last_commit.index(), last_commit.digest()),
+                        write_batch.reputation_scores,
+                    )],
+                )
+                .map_err(ConsensusError::RocksDBFailure)?;
         }
         batch.write()?;
         Ok(())
@@ -289,4 +307,12 @@ impl Store for RocksDBStore {
         let (_, commit_info) = result.map_err(ConsensusError::RocksDBFailure)?;
         Ok(Some(commit_info))
     }
+
+    fn read_last_reputation_scores(&self) -> ConsensusResult<Option<ReputationScores>> {
+        let Some(result) = self.reputation_scores.safe_iter().skip_to_last().next() else {
+            return Ok(None);
+        };
+        let (_, reputation_scores) = result.map_err(ConsensusError::RocksDBFailure)?;
+        Ok(Some(reputation_scores))
+    }
 }
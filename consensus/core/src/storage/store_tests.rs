@@ -9,7 +9,8 @@ use tempfile::TempDir;
 use super::{mem_store::MemStore, rocksdb_store::RocksDBStore, Store, WriteBatch};
 use crate::{
     block::{BlockAPI, BlockDigest, BlockRef, Slot, TestBlock, VerifiedBlock},
-    commit::{CommitDigest, TrustedCommit},
+    commit::{CommitDigest, CommitRange, TrustedCommit},
+    leader_scoring::ReputationScores,
 };
 
 /// Test fixture for store tests. Wraps around various store implementations.
@@ -294,3 +295,58 @@ async fn read_and_scan_commits(
         assert_eq!(scanned_commits, written_commits,);
     }
 }
+
+#[rstest]
+#[tokio::test]
+async fn read_last_commit_info_and_reputation_scores(
+    #[values(new_rocksdb_teststore(), new_mem_teststore())] test_store: TestStore,
+) {
+    let store = test_store.store();
+
+    // Nothing written yet, as for stores written before scores were persisted.
+    assert!(store.read_last_commit_info().unwrap().is_none());
+    assert!(store.read_last_reputation_scores().unwrap().is_none());
+
+    let commit = |index| {
+        TrustedCommit::new_for_test(
+            index,
+            CommitDigest::MIN,
+            BlockRef::new(
+                index,
+                AuthorityIndex::new_for_test(0),
+                BlockDigest::default(),
+            ),
+            vec![],
+        )
+    };
+    store
+        .write(WriteBatch::default().commits(vec![commit(1)]))
+        .unwrap();
+    assert!(store.read_last_commit_info().unwrap().is_some());
+    assert_eq!(
+        store.read_last_reputation_scores().unwrap(),
+        Some(ReputationScores::default())
+    );
+
+    let reputation_scores = ReputationScores::new(CommitRange::new(1, 2), vec![4, 3, 2, 1]);
+    store
+        .write(WriteBatch::new(
+            vec![],
+            vec![commit(2)],
+            vec![2, 0, 0, 0],
+            reputation_scores.clone(),
+        ))
+        .unwrap();
+    assert_eq!(
+        store
+            .read_last_commit_info()
+            .unwrap()
+            .unwrap()
+            .last_committed_rounds,
+        vec![2, 0, 0, 0]
+    );
+    assert_eq!(
+        store.read_last_reputation_scores().unwrap(),
+        Some(reputation_scores)
+    );
+}
@@ -12,6 +12,7 @@ use crate::{
     commit::LeaderStatus,
     context::Context,
     dag_state::DagState,
+    leader_schedule::LeaderSchedule,
     storage::mem_store::MemStore,
     test_dag::{build_dag, build_dag_layer},
     universal_committer::universal_committer_builder::UniversalCommitterBuilder,
@@ -755,7 +756,9 @@ fn basic_test_setup() -> (
     )));
 
     // Create committer without pipelining and only 1 leader per leader round
-    let committer = UniversalCommitterBuilder::new(context.clone(), dag_state.clone()).build();
+    let leader_schedule = LeaderSchedule::new(context.clone());
+    let committer =
+        UniversalCommitterBuilder::new(context.clone(), leader_schedule, dag_state.clone()).build();
 
     // note: without pipelining or multi-leader enabled there should only be one
     // committer.
@@ -146,8 +146,11 @@ pub(crate) mod universal_committer_builder {
     }
 
     impl UniversalCommitterBuilder {
-        pub(crate) fn new(context: Arc<Context>, dag_state: Arc<RwLock<DagState>>) -> Self {
-            let leader_schedule = LeaderSchedule::new(context.clone());
+        pub(crate) fn new(
+            context: Arc<Context>,
+            leader_schedule: LeaderSchedule,
+            dag_state: Arc<RwLock<DagState>>,
+        ) -> Self {
             Self {
                 context,
                 leader_schedule,
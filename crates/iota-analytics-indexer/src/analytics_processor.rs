@@ -23,8 +23,8 @@ use tracing::{error, info};
 
 use crate::{
     analytics_metrics::AnalyticsMetrics, handlers::AnalyticsHandler, join_paths,
-    writers::AnalyticsWriter, AnalyticsIndexerConfig, FileMetadata, MaxCheckpointReader,
-    ParquetSchema, EPOCH_DIR_PREFIX,
+    local_store::LocalStore, writers::AnalyticsWriter, AnalyticsIndexerConfig, FileMetadata,
+    MaxCheckpointReader, ParquetSchema, EPOCH_DIR_PREFIX,
 };
 
 pub struct AnalyticsProcessor<S: Serialize + ParquetSchema> {
@@ -32,6 +32,9 @@ pub struct AnalyticsProcessor<S: Serialize + ParquetSchema> {
     writer: Box<dyn AnalyticsWriter<S>>,
     current_epoch: u64,
     current_checkpoint_range: Range<u64>,
+    current_min_timestamp_ms: u64,
+    current_max_timestamp_ms: u64,
+    current_num_rows: u64,
     last_commit_instant: Instant,
     metrics: AnalyticsMetrics,
     config: AnalyticsIndexerConfig,
@@ -87,6 +90,11 @@ impl<S: Serialize + ParquetSchema + 'static> Handler for AnalyticsProcessor<S> {
         self.handler.process_checkpoint(checkpoint_data).await?;
         let rows = self.handler.read()?;
         self.writer.write(&rows)?;
+        if self.current_checkpoint_range.is_empty() {
+            self.current_min_timestamp_ms = timestamp;
+        }
+        self.current_max_timestamp_ms = timestamp;
+        self.current_num_rows += rows.len() as u64;
         self.current_checkpoint_range.end = self
             .current_checkpoint_range
             .end
@@ -112,7 +120,21 @@ impl<S: Serialize + ParquetSchema + 'static> AnalyticsProcessor<S> {
             ..Default::default()
         };
         let local_object_store = local_store_config.make()?;
-        let remote_object_store = config.remote_store_config.make()?;
+        let (remote_object_store, output_store) = match &config.local_output_dir {
+            Some(local_output_dir) => {
+                fs::create_dir_all(local_output_dir)?;
+                let output_store_config = ObjectStoreConfig {
+                    directory: Some(local_output_dir.clone()),
+                    object_store: Some(ObjectStoreType::File),
+                    ..Default::default()
+                };
+                (
+                    output_store_config.make()?,
+                    Some(LocalStore::new(local_output_dir, config.file_type)),
+                )
+            }
+            None => (config.remote_store_config.make()?, None),
+        };
         let (kill_sender, kill_receiver) = oneshot::channel::<()>();
         let (sender, receiver) = mpsc::channel::<FileMetadata>(100);
         let name: String = handler.name().parse()?;
@@ -123,6 +145,7 @@ impl<S: Serialize + ParquetSchema + 'static> AnalyticsProcessor<S> {
             local_object_store.clone(),
             checkpoint_dir,
             config.remote_store_path_prefix.clone(),
+            output_store,
             receiver,
             kill_receiver,
             cloned_metrics,
@@ -140,6 +163,9 @@ impl<S: Serialize + ParquetSchema + 'static> AnalyticsProcessor<S> {
             writer,
             current_epoch: 0,
             current_checkpoint_range: next_checkpoint_seq_num..next_checkpoint_seq_num,
+            current_min_timestamp_ms: 0,
+            current_max_timestamp_ms: 0,
+            current_num_rows: 0,
             last_commit_instant: Instant::now(),
             kill_sender,
             sender,
@@ -163,6 +189,9 @@ impl<S: Serialize + ParquetSchema + 'static> AnalyticsProcessor<S> {
                 self.config.file_format,
                 self.current_epoch,
                 self.current_checkpoint_range.clone(),
+                self.current_min_timestamp_ms,
+                self.current_max_timestamp_ms,
+                self.current_num_rows,
             );
             self.sender.send(file_metadata).await?;
             tokio::task::yield_now().await;
@@ -194,6 +223,7 @@ impl<S: Serialize + ParquetSchema + 'static> AnalyticsProcessor<S> {
 
     fn reset(&mut self) -> Result<()> {
         self.reset_checkpoint_range();
+        self.current_num_rows = 0;
         self.writer
             .reset(self.current_epoch, self.current_checkpoint_range.start)?;
         self.reset_last_commit_ts();
@@ -214,6 +244,7 @@ impl<S: Serialize + ParquetSchema + 'static> AnalyticsProcessor<S> {
         local_object_store: Arc<DynObjectStore>,
         local_staging_root_dir: PathBuf,
         remote_store_path_prefix: Option<Path>,
+        output_store: Option<LocalStore>,
         mut file_recv: mpsc::Receiver<FileMetadata>,
         mut recv: oneshot::Receiver<()>,
         metrics: AnalyticsMetrics,
@@ -226,15 +257,24 @@ impl<S: Serialize + ParquetSchema + 'static> AnalyticsProcessor<S> {
                     if let Some(file_metadata) = file {
                         info!("Received {name} file with checkpoints: {:?}", &file_metadata.checkpoint_seq_range);
                         let checkpoint_seq_num = file_metadata.checkpoint_seq_range.end;
+                        let remote_dest = match &output_store {
+                            Some(_) => file_metadata.partitioned_file_path(),
+                            None => join_paths(remote_store_path_prefix.clone(), &file_metadata.file_path()),
+                        };
                         Self::sync_file_to_remote(
                                 local_staging_root_dir.clone(),
                                 file_metadata.file_path(),
-                                remote_store_path_prefix.clone(),
+                                remote_dest,
                                 local_object_store.clone(),
                                 remote_object_store.clone()
                             )
                             .await
                             .expect("Syncing checkpoint should not fail");
+                        if let Some(output_store) = &output_store {
+                            output_store
+                                .append(file_metadata)
+                                .expect("Updating the manifest should not fail");
+                        }
                         metrics.last_uploaded_checkpoint.with_label_values(&[&name]).set(checkpoint_seq_num as i64);
                     } else {
                         info!("Terminating upload sync loop");
@@ -276,11 +316,10 @@ impl<S: Serialize + ParquetSchema + 'static> AnalyticsProcessor<S> {
     async fn sync_file_to_remote(
         dir: PathBuf,
         path: Path,
-        prefix: Option<Path>,
+        remote_dest: Path,
         from: Arc<DynObjectStore>,
         to: Arc<DynObjectStore>,
     ) -> Result<()> {
-        let remote_dest = join_paths(prefix, &path);
         info!("Syncing file to remote: {:?}", &remote_dest);
         copy_file(&path, &remote_dest, &from, &to).await?;
         fs::remove_file(path_to_filesystem(dir, &path)?)?;
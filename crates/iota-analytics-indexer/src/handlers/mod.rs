@@ -271,6 +271,11 @@ fn parse_struct_field(
                 );
             }
         }
+        MoveValue::Variant(move_variant) => {
+            for (k, field) in move_variant.fields {
+                parse_struct_field(&format!("{}.{}", path, &k), field, curr_struct, all_structs);
+            }
+        }
         _ => {}
     }
 }
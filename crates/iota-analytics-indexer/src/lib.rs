@@ -6,6 +6,7 @@ use std::{ops::Range, path::PathBuf};
 
 use anyhow::{anyhow, Result};
 use arrow_array::Int32Array;
+use chrono::DateTime;
 use clap::*;
 use gcp_bigquery_client::{model::query_request::QueryRequest, Client};
 use iota_config::object_storage_config::ObjectStoreConfig;
@@ -36,23 +37,30 @@ use crate::{
         transaction_objects_handler::TransactionObjectsHandler,
         wrapped_object_handler::WrappedObjectHandler, AnalyticsHandler,
     },
+    local_store::LocalStore,
     tables::{
         CheckpointEntry, DynamicFieldEntry, EventEntry, InputObjectKind, MoveCallEntry,
         MovePackageEntry, ObjectEntry, ObjectStatus, OwnerType, TransactionEntry,
         TransactionObjectEntry, WrappedObjectEntry,
     },
-    writers::{csv_writer::CSVWriter, parquet_writer::ParquetWriter, AnalyticsWriter},
+    writers::{
+        arrow_ipc_writer::ArrowIpcWriter, csv_writer::CSVWriter, jsonl_writer::JsonLinesWriter,
+        parquet_writer::ParquetWriter, AnalyticsWriter,
+    },
 };
 
 pub mod analytics_metrics;
 pub mod analytics_processor;
 pub mod errors;
 mod handlers;
+pub mod local_store;
 mod package_store;
 pub mod tables;
 mod writers;
 
 const EPOCH_DIR_PREFIX: &str = "epoch_";
+const EPOCH_PARTITION_PREFIX: &str = "epoch=";
+const DATE_PARTITION_PREFIX: &str = "date=";
 const CHECKPOINT_DIR_PREFIX: &str = "checkpoints";
 const OBJECT_DIR_PREFIX: &str = "objects";
 const TRANSACTION_DIR_PREFIX: &str = "transactions";
@@ -101,12 +109,18 @@ pub struct AnalyticsIndexerConfig {
     // Remote object store path prefix to use while writing
     #[clap(long, default_value = None, global = true)]
     pub remote_store_path_prefix: Option<Path>,
-    // File format to store data in i.e. csv, parquet, etc
+    // File format to store data in i.e. csv, parquet, arrow, jsonl
     #[clap(long, value_enum, default_value = "csv", global = true)]
     pub file_format: FileFormat,
     // Type of data to write i.e. checkpoint, object, transaction, etc
     #[clap(long, value_enum, long, global = true)]
     pub file_type: FileType,
+    /// Local directory to write the data to instead of the remote object
+    /// store. Files are laid out in Hive style partitions
+    /// (`epoch=<n>/date=<yyyy-mm-dd>`) next to a manifest of their
+    /// checkpoint ranges, see `query-range`.
+    #[clap(long, default_value = None, global = true)]
+    pub local_output_dir: Option<PathBuf>,
     // Directory to contain the package cache for pipelines
     #[clap(
         long,
@@ -282,6 +296,8 @@ impl MaxCheckpointReader for NoOpCheckpointReader {
 pub enum FileFormat {
     CSV = 0,
     PARQUET = 1,
+    ARROW = 2,
+    JSONL = 3,
 }
 
 impl FileFormat {
@@ -289,6 +305,8 @@ impl FileFormat {
         match self {
             FileFormat::CSV => "csv",
             FileFormat::PARQUET => "parquet",
+            FileFormat::ARROW => "arrow",
+            FileFormat::JSONL => "jsonl",
         }
     }
 }
@@ -349,6 +367,29 @@ impl FileType {
                 file_format.file_suffix()
             ))
     }
+
+    /// The path of a file in a Hive style layout, partitioned by epoch and by
+    /// the UTC date of its first checkpoint.
+    pub fn partitioned_file_path(
+        &self,
+        file_format: FileFormat,
+        epoch_num: EpochId,
+        timestamp_ms: u64,
+        checkpoint_range: Range<u64>,
+    ) -> Path {
+        let date = DateTime::from_timestamp_millis(timestamp_ms as i64)
+            .unwrap_or_default()
+            .format("%Y-%m-%d");
+        self.dir_prefix()
+            .child(format!("{}{}", EPOCH_PARTITION_PREFIX, epoch_num))
+            .child(format!("{}{}", DATE_PARTITION_PREFIX, date))
+            .child(format!(
+                "{}_{}.{}",
+                checkpoint_range.start,
+                checkpoint_range.end,
+                file_format.file_suffix()
+            ))
+    }
 }
 
 pub enum ParquetValue {
@@ -450,6 +491,10 @@ pub struct FileMetadata {
     pub file_format: FileFormat,
     pub epoch_num: u64,
     pub checkpoint_seq_range: Range<u64>,
+    /// Timestamps of the first and the last checkpoint of the file.
+    pub min_timestamp_ms: u64,
+    pub max_timestamp_ms: u64,
+    pub num_rows: u64,
 }
 
 impl FileMetadata {
@@ -458,12 +503,18 @@ impl FileMetadata {
         file_format: FileFormat,
         epoch_num: u64,
         checkpoint_seq_range: Range<u64>,
+        min_timestamp_ms: u64,
+        max_timestamp_ms: u64,
+        num_rows: u64,
     ) -> FileMetadata {
         FileMetadata {
             file_type,
             file_format,
             epoch_num,
             checkpoint_seq_range,
+            min_timestamp_ms,
+            max_timestamp_ms,
+            num_rows,
         }
     }
 
@@ -474,6 +525,15 @@ impl FileMetadata {
             self.checkpoint_seq_range.clone(),
         )
     }
+
+    pub fn partitioned_file_path(&self) -> Path {
+        self.file_type.partitioned_file_path(
+            self.file_format,
+            self.epoch_num,
+            self.min_timestamp_ms,
+            self.checkpoint_seq_range.clone(),
+        )
+    }
 }
 
 pub struct Processor {
@@ -868,6 +928,16 @@ pub fn make_writer<S: Serialize + ParquetSchema>(
             file_type,
             starting_checkpoint_seq_num,
         )?),
+        FileFormat::ARROW => Box::new(ArrowIpcWriter::new(
+            &config.checkpoint_dir,
+            file_type,
+            starting_checkpoint_seq_num,
+        )?),
+        FileFormat::JSONL => Box::new(JsonLinesWriter::new(
+            &config.checkpoint_dir,
+            file_type,
+            starting_checkpoint_seq_num,
+        )?),
     })
 }
 
@@ -877,6 +947,8 @@ pub async fn get_starting_checkpoint_seq_num(
 ) -> Result<u64> {
     let checkpoint = if let Some(starting_checkpoint_seq_num) = config.starting_checkpoint_seq_num {
         starting_checkpoint_seq_num
+    } else if let Some(local_output_dir) = &config.local_output_dir {
+        LocalStore::new(local_output_dir, file_type).next_checkpoint_seq_num()?
     } else {
         read_store_for_checkpoint(
             config.remote_store_config.clone(),
@@ -0,0 +1,254 @@
+// Copyright (c) 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Local filesystem output of the analytics indexer.
+//!
+//! Files are written in Hive style partitions below the output directory,
+//! e.g. `transactions/epoch=3/date=2024-06-01/1000_2000.parquet`, so they can
+//! be read by query engines with partition pruning. Every file type directory
+//! holds a `_manifest.jsonl` listing the checkpoint range, timestamp range and
+//! row count of each written file, one JSON object per line. The leading
+//! underscore keeps the manifest hidden from most readers of the partitions.
+
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{BufRead, BufReader, Write},
+    ops::Range,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result};
+use iota_storage::object_store::util::path_to_filesystem;
+use serde::{Deserialize, Serialize};
+
+use crate::{FileMetadata, FileType};
+
+const MANIFEST_FILE_NAME: &str = "_manifest.jsonl";
+
+/// An entry of the manifest, describing a single file of the output.
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+pub struct ManifestEntry {
+    /// Path of the file, relative to the output directory.
+    pub path: String,
+    #[serde(flatten)]
+    pub metadata: FileMetadata,
+}
+
+/// A window to select files of the output by.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum QueryWindow {
+    /// Checkpoint sequence numbers, the end is exclusive.
+    Checkpoints(Range<u64>),
+    /// Checkpoint timestamps in milliseconds, the end is exclusive.
+    Timestamps(Range<u64>),
+}
+
+impl QueryWindow {
+    fn overlaps(&self, metadata: &FileMetadata) -> bool {
+        match self {
+            QueryWindow::Checkpoints(range) => {
+                metadata.checkpoint_seq_range.start < range.end
+                    && range.start < metadata.checkpoint_seq_range.end
+            }
+            QueryWindow::Timestamps(range) => {
+                metadata.min_timestamp_ms < range.end && range.start <= metadata.max_timestamp_ms
+            }
+        }
+    }
+}
+
+/// The files of a single file type in a local output directory.
+pub struct LocalStore {
+    root_dir_path: PathBuf,
+    file_type: FileType,
+}
+
+impl LocalStore {
+    pub fn new(root_dir_path: &Path, file_type: FileType) -> Self {
+        Self {
+            root_dir_path: root_dir_path.to_path_buf(),
+            file_type,
+        }
+    }
+
+    fn manifest_path(&self) -> Result<PathBuf> {
+        Ok(
+            path_to_filesystem(self.root_dir_path.clone(), &self.file_type.dir_prefix())?
+                .join(MANIFEST_FILE_NAME),
+        )
+    }
+
+    /// Records a file in the manifest. The file must already be written to
+    /// its partition.
+    pub fn append(&self, metadata: FileMetadata) -> Result<()> {
+        let entry = ManifestEntry {
+            path: metadata.partitioned_file_path().to_string(),
+            metadata,
+        };
+        fs::create_dir_all(&self.root_dir_path)?;
+        let manifest_path = self.manifest_path()?;
+        if let Some(parent) = manifest_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(manifest_path)?;
+        let mut line = serde_json::to_vec(&entry)?;
+        line.push(b'\n');
+        file.write_all(&line)?;
+        file.sync_all()?;
+        Ok(())
+    }
+
+    /// Reads all entries of the manifest, in the order they were written.
+    pub fn read_manifest(&self) -> Result<Vec<ManifestEntry>> {
+        if !self.root_dir_path.exists() {
+            return Ok(vec![]);
+        }
+        let manifest_path = self.manifest_path()?;
+        if !manifest_path.exists() {
+            return Ok(vec![]);
+        }
+        let reader = BufReader::new(File::open(&manifest_path)?);
+        let mut entries = vec![];
+        for (idx, line) in reader.lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let entry = serde_json::from_str(&line).with_context(|| {
+                format!(
+                    "Invalid manifest entry at {}:{}",
+                    manifest_path.display(),
+                    idx + 1
+                )
+            })?;
+            entries.push(entry);
+        }
+        Ok(entries)
+    }
+
+    /// The checkpoint to resume writing from, i.e. the end of the last file in
+    /// the manifest.
+    pub fn next_checkpoint_seq_num(&self) -> Result<u64> {
+        Ok(self
+            .read_manifest()?
+            .iter()
+            .map(|entry| entry.metadata.checkpoint_seq_range.end)
+            .max()
+            .unwrap_or(0))
+    }
+
+    /// Lists the entries of the files holding data of the given window, sorted
+    /// by checkpoint. A file written twice, e.g. after a restart, is listed
+    /// once.
+    pub fn query(&self, window: &QueryWindow) -> Result<Vec<ManifestEntry>> {
+        let mut entries = self
+            .read_manifest()?
+            .into_iter()
+            .filter(|entry| window.overlaps(&entry.metadata))
+            .collect::<Vec<_>>();
+        entries.sort_by_key(|entry| entry.metadata.checkpoint_seq_range.start);
+        entries.dedup_by(|a, b| a.path == b.path);
+        Ok(entries)
+    }
+
+    /// The absolute path of a file of the manifest.
+    pub fn file_path(&self, entry: &ManifestEntry) -> Result<PathBuf> {
+        path_to_filesystem(self.root_dir_path.clone(), &entry.path.as_str().into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::FileFormat;
+
+    const DAY_MS: u64 = 24 * 60 * 60 * 1000;
+
+    fn metadata(epoch: u64, range: Range<u64>, timestamps: Range<u64>) -> FileMetadata {
+        FileMetadata::new(
+            FileType::Transaction,
+            FileFormat::PARQUET,
+            epoch,
+            range.clone(),
+            timestamps.start,
+            timestamps.end,
+            range.end - range.start,
+        )
+    }
+
+    fn make_store(dir: &Path) -> LocalStore {
+        let store = LocalStore::new(dir, FileType::Transaction);
+        store.append(metadata(0, 0..10, 0..900)).unwrap();
+        store.append(metadata(0, 10..20, 1000..1900)).unwrap();
+        store
+            .append(metadata(1, 20..30, DAY_MS..DAY_MS + 900))
+            .unwrap();
+        store
+    }
+
+    fn checkpoint_ranges(entries: Vec<ManifestEntry>) -> Vec<Range<u64>> {
+        entries
+            .into_iter()
+            .map(|entry| entry.metadata.checkpoint_seq_range)
+            .collect()
+    }
+
+    #[test]
+    fn partitioned_paths() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = make_store(dir.path());
+        let paths = store
+            .read_manifest()
+            .unwrap()
+            .into_iter()
+            .map(|entry| entry.path)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            paths,
+            vec![
+                "transactions/epoch=0/date=1970-01-01/0_10.parquet",
+                "transactions/epoch=0/date=1970-01-01/10_20.parquet",
+                "transactions/epoch=1/date=1970-01-02/20_30.parquet",
+            ]
+        );
+        assert_eq!(store.next_checkpoint_seq_num().unwrap(), 30);
+    }
+
+    #[test]
+    fn query_by_checkpoints() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = make_store(dir.path());
+        let query =
+            |range| checkpoint_ranges(store.query(&QueryWindow::Checkpoints(range)).unwrap());
+        assert_eq!(query(0..10), vec![0..10]);
+        assert_eq!(query(9..11), vec![0..10, 10..20]);
+        assert_eq!(query(20..100), vec![20..30]);
+        assert!(query(30..100).is_empty());
+    }
+
+    #[test]
+    fn query_by_timestamps() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = make_store(dir.path());
+        let query =
+            |range| checkpoint_ranges(store.query(&QueryWindow::Timestamps(range)).unwrap());
+        assert_eq!(query(900..1000), vec![0..10]);
+        assert_eq!(query(901..1000), Vec::<Range<u64>>::new());
+        assert_eq!(query(0..DAY_MS), vec![0..10, 10..20]);
+        assert_eq!(query(DAY_MS..u64::MAX), vec![20..30]);
+    }
+
+    #[test]
+    fn empty_store() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = LocalStore::new(dir.path(), FileType::Transaction);
+        assert_eq!(store.next_checkpoint_seq_num().unwrap(), 0);
+        assert!(store
+            .query(&QueryWindow::Checkpoints(0..u64::MAX))
+            .unwrap()
+            .is_empty());
+    }
+}
@@ -2,17 +2,87 @@
 // Modifications Copyright (c) 2024 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+use std::path::PathBuf;
+
 use clap::*;
 use iota_analytics_indexer::{
-    analytics_metrics::AnalyticsMetrics, errors::AnalyticsIndexerError, make_analytics_processor,
-    AnalyticsIndexerConfig,
+    analytics_metrics::AnalyticsMetrics,
+    errors::AnalyticsIndexerError,
+    local_store::{LocalStore, QueryWindow},
+    make_analytics_processor, AnalyticsIndexerConfig, FileType,
 };
 use iota_indexer::{framework::IndexerBuilder, metrics::IndexerMetrics};
 use prometheus::Registry;
 use tracing::info;
 
+const QUERY_RANGE_COMMAND: &str = "query-range";
+
+#[derive(Parser, Clone, Debug)]
+#[clap(name = "Iota Analytics Indexer", rename_all = "kebab-case")]
+enum AnalyticsIndexerCommand {
+    /// List the files of a local output directory holding data of a checkpoint
+    /// or timestamp window.
+    QueryRange {
+        /// The local output directory the indexer writes to.
+        #[clap(long)]
+        local_output_dir: PathBuf,
+        #[clap(long, value_enum)]
+        file_type: FileType,
+        /// First checkpoint of the window.
+        #[clap(long, conflicts_with_all = ["start_timestamp_ms", "end_timestamp_ms"])]
+        start_checkpoint: Option<u64>,
+        /// Checkpoint after the window.
+        #[clap(long, conflicts_with_all = ["start_timestamp_ms", "end_timestamp_ms"])]
+        end_checkpoint: Option<u64>,
+        /// First timestamp of the window, in milliseconds.
+        #[clap(long)]
+        start_timestamp_ms: Option<u64>,
+        /// Timestamp after the window, in milliseconds.
+        #[clap(long)]
+        end_timestamp_ms: Option<u64>,
+        /// Print the manifest entries as JSON lines instead of the file paths.
+        #[clap(long)]
+        json: bool,
+    },
+}
+
+fn query_range(command: AnalyticsIndexerCommand) -> anyhow::Result<()> {
+    let AnalyticsIndexerCommand::QueryRange {
+        local_output_dir,
+        file_type,
+        start_checkpoint,
+        end_checkpoint,
+        start_timestamp_ms,
+        end_timestamp_ms,
+        json,
+    } = command;
+    let window = if start_timestamp_ms.is_some() || end_timestamp_ms.is_some() {
+        QueryWindow::Timestamps(
+            start_timestamp_ms.unwrap_or(0)..end_timestamp_ms.unwrap_or(u64::MAX),
+        )
+    } else {
+        QueryWindow::Checkpoints(start_checkpoint.unwrap_or(0)..end_checkpoint.unwrap_or(u64::MAX))
+    };
+    let store = LocalStore::new(&local_output_dir, file_type);
+    for entry in store.query(&window)? {
+        if json {
+            println!("{}", serde_json::to_string(&entry)?);
+        } else {
+            println!("{}", store.file_path(&entry)?.display());
+        }
+    }
+    Ok(())
+}
+
 #[tokio::main]
 async fn main() -> Result<(), AnalyticsIndexerError> {
+    // The indexer itself is configured by flags only, so the subcommand is
+    // told apart by its name.
+    if std::env::args().nth(1).as_deref() == Some(QUERY_RANGE_COMMAND) {
+        return query_range(AnalyticsIndexerCommand::parse())
+            .map_err(|e| AnalyticsIndexerError::GenericError(e.to_string()));
+    }
+
     let _guard = telemetry_subscribers::TelemetryConfig::new()
         .with_env()
         .init();
@@ -2,14 +2,21 @@
 // Modifications Copyright (c) 2024 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+use std::sync::Arc;
+
 use anyhow::Result;
+use arrow_array::{ArrayRef, BooleanArray, Int64Array, RecordBatch, StringArray, UInt64Array};
 use iota_types::base_types::EpochId;
 use serde::Serialize;
 
-use crate::{FileFormat, ParquetSchema};
+use crate::{FileFormat, ParquetSchema, ParquetValue};
 
+pub mod arrow_ipc_writer;
 pub mod csv_writer;
+pub mod jsonl_writer;
 pub mod parquet_writer;
+#[cfg(test)]
+mod tests;
 
 pub trait AnalyticsWriter<S: Serialize + ParquetSchema>: Send + Sync + 'static {
     /// File format i.e. csv, parquet, etc
@@ -23,3 +30,39 @@ pub trait AnalyticsWriter<S: Serialize + ParquetSchema>: Send + Sync + 'static {
     /// Approx size in bytes of the current staging file if available
     fn file_size(&self) -> Result<Option<u64>>;
 }
+
+macro_rules! convert_to_arrow_array {
+    ($column:ident, $target_vector:ident, $($variant:path => $types:ty),*) => {
+        match &$column[0] {
+            $(
+                $variant(_) => {
+                    let array = <$types>::from(
+                        $column
+                            .into_iter()
+                            .flat_map(|value| match value {
+                                $variant(value) => Some(value),
+                                _ => None,
+                            })
+                            .collect::<Vec<_>>(),
+                    );
+                    $target_vector.push(Arc::new(array) as ArrayRef);
+                }
+            )*
+        }
+    };
+}
+
+/// Converts the buffered columns of table entries into an arrow record batch.
+pub(crate) fn make_record_batch<S: ParquetSchema>(
+    data: Vec<Vec<ParquetValue>>,
+) -> Result<RecordBatch> {
+    let mut batch_data = vec![];
+    for column in data {
+        convert_to_arrow_array!(column, batch_data,
+            ParquetValue::U64 => UInt64Array, ParquetValue::Str => StringArray, ParquetValue::OptionU64 => UInt64Array, ParquetValue::OptionStr => StringArray, ParquetValue::Bool => BooleanArray, ParquetValue::I64 => Int64Array
+        );
+    }
+    Ok(RecordBatch::try_from_iter(
+        S::schema().iter().zip(batch_data.into_iter()),
+    )?)
+}
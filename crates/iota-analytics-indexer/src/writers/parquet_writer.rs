@@ -6,17 +6,17 @@ use std::{
     fs::{create_dir_all, remove_file, File},
     ops::Range,
     path::{Path, PathBuf},
-    sync::Arc,
 };
 
 use anyhow::{anyhow, Result};
-use arrow_array::{ArrayRef, BooleanArray, Int64Array, RecordBatch, StringArray, UInt64Array};
 use iota_storage::object_store::util::path_to_filesystem;
 use iota_types::base_types::EpochId;
 use parquet::{arrow::ArrowWriter, basic::Compression, file::properties::WriterProperties};
 use serde::Serialize;
 
-use crate::{AnalyticsWriter, FileFormat, FileType, ParquetSchema, ParquetValue};
+use crate::{
+    writers::make_record_batch, AnalyticsWriter, FileFormat, FileType, ParquetSchema, ParquetValue,
+};
 
 // Save table entries to parquet files.
 pub(crate) struct ParquetWriter {
@@ -60,27 +60,6 @@ impl ParquetWriter {
     }
 }
 
-macro_rules! convert_to_arrow_array {
-    ($column:ident, $target_vector:ident, $($variant:path => $types:ty),*) => {
-        match &$column[0] {
-            $(
-                $variant(_) => {
-                    let array = <$types>::from(
-                        $column
-                            .into_iter()
-                            .flat_map(|value| match value {
-                                $variant(value) => Some(value),
-                                _ => None,
-                            })
-                            .collect::<Vec<_>>(),
-                    );
-                    $target_vector.push(Arc::new(array) as ArrayRef);
-                }
-            )*
-        }
-    };
-}
-
 impl<S: Serialize + ParquetSchema> AnalyticsWriter<S> for ParquetWriter {
     fn file_format(&self) -> Result<FileFormat> {
         Ok(FileFormat::PARQUET)
@@ -103,13 +82,7 @@ impl<S: Serialize + ParquetSchema> AnalyticsWriter<S> for ParquetWriter {
             return Ok(false);
         }
         self.checkpoint_range.end = end_checkpoint_seq_num;
-        let mut batch_data = vec![];
-        for column in std::mem::take(&mut self.data) {
-            convert_to_arrow_array!(column, batch_data,
-                ParquetValue::U64 => UInt64Array, ParquetValue::Str => StringArray, ParquetValue::OptionU64 => UInt64Array, ParquetValue::OptionStr => StringArray, ParquetValue::Bool => BooleanArray, ParquetValue::I64 => Int64Array
-            );
-        }
-        let batch = RecordBatch::try_from_iter(S::schema().iter().zip(batch_data.into_iter()))?;
+        let batch = make_record_batch::<S>(std::mem::take(&mut self.data))?;
 
         let properties = WriterProperties::builder()
             .set_compression(Compression::SNAPPY)
@@ -0,0 +1,395 @@
+// Copyright (c) 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{fs, fs::File, path::Path};
+
+use arrow::{
+    array::{Array, StringArray, UInt64Array},
+    ipc::reader::FileReader,
+};
+use iota_indexer::framework::Handler;
+use iota_rest_api::CheckpointData;
+use iota_storage::object_store::util::path_to_filesystem;
+use iota_types::{base_types::IotaAddress, dynamic_field::DynamicFieldType, storage::ReadStore};
+use serde_json::Value;
+use simulacrum::Simulacrum;
+use strum::IntoEnumIterator;
+use tempfile::TempDir;
+
+use crate::{
+    handlers::{
+        checkpoint_handler::CheckpointHandler, transaction_handler::TransactionHandler,
+        AnalyticsHandler,
+    },
+    tables::{
+        CheckpointEntry, DynamicFieldEntry, EventEntry, InputObjectKind, MoveCallEntry,
+        MovePackageEntry, ObjectEntry, ObjectStatus, OwnerType, TransactionEntry,
+        TransactionObjectEntry, WrappedObjectEntry,
+    },
+    writers::{arrow_ipc_writer::ArrowIpcWriter, jsonl_writer::JsonLinesWriter, AnalyticsWriter},
+    FileFormat, FileType, ParquetSchema,
+};
+
+/// Executes a transfer and returns the data of the checkpoint including it.
+fn transfer_checkpoint() -> anyhow::Result<CheckpointData> {
+    let mut sim = Simulacrum::new();
+    let (transaction, _) = sim.transfer_txn(IotaAddress::random_for_testing_only());
+    let (_effects, err) = sim.execute_transaction(transaction)?;
+    assert!(err.is_none());
+    let checkpoint = sim.create_checkpoint();
+    Ok(sim.get_checkpoint_data(
+        checkpoint.clone(),
+        sim.get_checkpoint_contents_by_digest(&checkpoint.content_digest)?
+            .unwrap(),
+    )?)
+}
+
+async fn handler_rows<S, H: AnalyticsHandler<S>>(
+    mut handler: H,
+    checkpoint_data: &CheckpointData,
+) -> anyhow::Result<Vec<S>> {
+    handler.process_checkpoint(checkpoint_data).await?;
+    handler.read()
+}
+
+/// Writes the rows with the writer and returns the path of the flushed file.
+fn write_rows<S: serde::Serialize + ParquetSchema>(
+    mut writer: Box<dyn AnalyticsWriter<S>>,
+    root: &Path,
+    file_type: FileType,
+    rows: &[S],
+) -> anyhow::Result<std::path::PathBuf> {
+    writer.write(rows)?;
+    assert!(writer.flush(1)?);
+    path_to_filesystem(
+        root.to_path_buf(),
+        &file_type.file_path(writer.file_format()?, 0, 0..1),
+    )
+}
+
+fn read_arrow_file(path: &Path) -> anyhow::Result<Vec<arrow_array::RecordBatch>> {
+    Ok(FileReader::try_new(File::open(path)?, None)?.collect::<Result<Vec<_>, _>>()?)
+}
+
+fn read_jsonl_file(path: &Path) -> anyhow::Result<Vec<Value>> {
+    fs::read_to_string(path)?
+        .lines()
+        .map(|line| Ok(serde_json::from_str(line)?))
+        .collect()
+}
+
+#[tokio::test]
+async fn test_arrow_ipc_writer() -> anyhow::Result<()> {
+    let checkpoint_data = transfer_checkpoint()?;
+    let dir = TempDir::new()?;
+
+    let transactions = handler_rows(TransactionHandler::new(), &checkpoint_data).await?;
+    let writer = Box::new(ArrowIpcWriter::new(dir.path(), FileType::Transaction, 0)?);
+    let path = write_rows(writer, dir.path(), FileType::Transaction, &transactions)?;
+    assert_eq!(path.extension().unwrap(), "arrow");
+
+    let batches = read_arrow_file(&path)?;
+    assert_eq!(batches.len(), 1);
+    let batch = &batches[0];
+    assert_eq!(batch.num_rows(), transactions.len());
+    assert_eq!(
+        batch
+            .schema()
+            .fields()
+            .iter()
+            .map(|field| field.name().clone())
+            .collect::<Vec<_>>(),
+        TransactionEntry::schema()
+    );
+    let digests = batch
+        .column_by_name("transaction_digest")
+        .unwrap()
+        .as_any()
+        .downcast_ref::<StringArray>()
+        .unwrap();
+    assert_eq!(digests.value(0), transactions[0].transaction_digest);
+    let checkpoints = batch
+        .column_by_name("checkpoint")
+        .unwrap()
+        .as_any()
+        .downcast_ref::<UInt64Array>()
+        .unwrap();
+    assert_eq!(checkpoints.value(0), transactions[0].checkpoint);
+
+    let checkpoints = handler_rows(CheckpointHandler::new(), &checkpoint_data).await?;
+    let writer = Box::new(ArrowIpcWriter::new(dir.path(), FileType::Checkpoint, 0)?);
+    let path = write_rows(writer, dir.path(), FileType::Checkpoint, &checkpoints)?;
+    let batches = read_arrow_file(&path)?;
+    let previous_digests = batches[0]
+        .column_by_name("previous_checkpoint_digest")
+        .unwrap();
+    assert_eq!(
+        previous_digests.is_null(0),
+        checkpoints[0].previous_checkpoint_digest.is_none()
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_arrow_ipc_writer_without_rows() -> anyhow::Result<()> {
+    let dir = TempDir::new()?;
+    let mut writer: Box<dyn AnalyticsWriter<TransactionEntry>> =
+        Box::new(ArrowIpcWriter::new(dir.path(), FileType::Transaction, 0)?);
+    assert!(!writer.flush(1)?);
+    assert_eq!(writer.file_format()?, FileFormat::ARROW);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_jsonl_writer() -> anyhow::Result<()> {
+    let checkpoint_data = transfer_checkpoint()?;
+    let dir = TempDir::new()?;
+
+    let transactions = handler_rows(TransactionHandler::new(), &checkpoint_data).await?;
+    let writer = Box::new(JsonLinesWriter::new(dir.path(), FileType::Transaction, 0)?);
+    let path = write_rows(writer, dir.path(), FileType::Transaction, &transactions)?;
+    assert_eq!(path.extension().unwrap(), "jsonl");
+
+    let rows = read_jsonl_file(&path)?;
+    assert_eq!(rows.len(), transactions.len());
+    for (row, transaction) in rows.iter().zip(&transactions) {
+        assert_eq!(row, &serde_json::to_value(transaction)?);
+        assert_eq!(
+            row["transaction_digest"].as_str(),
+            Some(transaction.transaction_digest.as_str())
+        );
+    }
+
+    let checkpoints = handler_rows(CheckpointHandler::new(), &checkpoint_data).await?;
+    let writer = Box::new(JsonLinesWriter::new(dir.path(), FileType::Checkpoint, 0)?);
+    let path = write_rows(writer, dir.path(), FileType::Checkpoint, &checkpoints)?;
+    let rows = read_jsonl_file(&path)?;
+    assert_eq!(rows.len(), 1);
+    assert_eq!(
+        rows[0]["sequence_number"].as_u64(),
+        Some(checkpoints[0].sequence_number)
+    );
+    // Every column of the schema is written.
+    for column in CheckpointEntry::schema() {
+        assert!(rows[0].get(&column).is_some(), "missing column {column}");
+    }
+    Ok(())
+}
+
+/// Writes `rows` with the Arrow IPC and the JSON lines writer, and checks that
+/// every row and column is read back.
+fn check_writers<S: serde::Serialize + ParquetSchema>(
+    root: &Path,
+    file_type: FileType,
+    rows: Vec<S>,
+) -> anyhow::Result<()> {
+    let writer = Box::new(ArrowIpcWriter::new(root, file_type, 0)?);
+    let path = write_rows(writer, root, file_type, &rows)?;
+    let batches = read_arrow_file(&path)?;
+    assert_eq!(batches.len(), 1, "{file_type:?}");
+    assert_eq!(batches[0].num_rows(), rows.len(), "{file_type:?}");
+    assert_eq!(
+        batches[0]
+            .schema()
+            .fields()
+            .iter()
+            .map(|field| field.name().clone())
+            .collect::<Vec<_>>(),
+        S::schema(),
+        "{file_type:?}"
+    );
+
+    let writer = Box::new(JsonLinesWriter::new(root, file_type, 0)?);
+    let path = write_rows(writer, root, file_type, &rows)?;
+    let lines = read_jsonl_file(&path)?;
+    assert_eq!(lines.len(), rows.len(), "{file_type:?}");
+    for (line, row) in lines.iter().zip(&rows) {
+        for column in S::schema() {
+            assert!(
+                line.get(&column).is_some(),
+                "{file_type:?}: missing column {column}"
+            );
+        }
+        assert_eq!(line, &serde_json::to_value(row)?, "{file_type:?}");
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_writers_for_every_file_type() -> anyhow::Result<()> {
+    let dir = TempDir::new()?;
+    let digest = "4PbcrqWu6BpLU2jYKVZ3JMrE8FswUoTbq9JfhRt8Jt1V".to_string();
+    let address = IotaAddress::ZERO.to_string();
+    // Every variant is matched, so a new file type cannot be left untested.
+    for file_type in FileType::iter() {
+        match file_type {
+            FileType::Checkpoint => check_writers(
+                dir.path(),
+                file_type,
+                vec![CheckpointEntry {
+                    checkpoint_digest: digest.clone(),
+                    sequence_number: 1,
+                    epoch: 0,
+                    timestamp_ms: 1000,
+                    previous_checkpoint_digest: Some(digest.clone()),
+                    end_of_epoch: false,
+                    total_gas_cost: -5,
+                    computation_cost: 10,
+                    storage_cost: 20,
+                    storage_rebate: 35,
+                    non_refundable_storage_fee: 0,
+                    total_transaction_blocks: 1,
+                    total_transactions: 1,
+                    total_successful_transaction_blocks: 1,
+                    total_successful_transactions: 1,
+                    network_total_transaction: 2,
+                    validator_signature: "signature".to_string(),
+                }],
+            )?,
+            FileType::Object => check_writers(
+                dir.path(),
+                file_type,
+                vec![
+                    ObjectEntry {
+                        object_id: address.clone(),
+                        version: 2,
+                        digest: digest.clone(),
+                        type_: Some("0x2::coin::Coin<0x2::iota::IOTA>".to_string()),
+                        checkpoint: 1,
+                        epoch: 0,
+                        timestamp_ms: 1000,
+                        owner_type: Some(OwnerType::AddressOwner),
+                        owner_address: Some(address.clone()),
+                        object_status: ObjectStatus::Created,
+                        initial_shared_version: None,
+                        previous_transaction: digest.clone(),
+                        has_public_transfer: true,
+                        storage_rebate: Some(10),
+                        bcs: Some("AA==".to_string()),
+                        coin_type: Some("0x2::iota::IOTA".to_string()),
+                        coin_balance: Some(100),
+                        struct_tag: Some("0x2::coin::Coin".to_string()),
+                        object_json: None,
+                    },
+                    ObjectEntry {
+                        object_id: address.clone(),
+                        version: 3,
+                        digest: digest.clone(),
+                        type_: None,
+                        checkpoint: 1,
+                        epoch: 0,
+                        timestamp_ms: 1000,
+                        owner_type: None,
+                        owner_address: None,
+                        object_status: ObjectStatus::Deleted,
+                        initial_shared_version: None,
+                        previous_transaction: digest.clone(),
+                        has_public_transfer: false,
+                        storage_rebate: None,
+                        bcs: None,
+                        coin_type: None,
+                        coin_balance: None,
+                        struct_tag: None,
+                        object_json: None,
+                    },
+                ],
+            )?,
+            FileType::Transaction => check_writers(
+                dir.path(),
+                file_type,
+                handler_rows(TransactionHandler::new(), &transfer_checkpoint()?).await?,
+            )?,
+            FileType::TransactionObjects => check_writers(
+                dir.path(),
+                file_type,
+                vec![TransactionObjectEntry {
+                    object_id: address.clone(),
+                    version: Some(1),
+                    transaction_digest: digest.clone(),
+                    checkpoint: 1,
+                    epoch: 0,
+                    timestamp_ms: 1000,
+                    input_kind: Some(InputObjectKind::GasCoin),
+                    object_status: Some(ObjectStatus::Mutated),
+                }],
+            )?,
+            FileType::Event => check_writers(
+                dir.path(),
+                file_type,
+                vec![EventEntry {
+                    transaction_digest: digest.clone(),
+                    event_index: 0,
+                    checkpoint: 1,
+                    epoch: 0,
+                    timestamp_ms: 1000,
+                    sender: address.clone(),
+                    package: "0x2".to_string(),
+                    module: "coin".to_string(),
+                    event_type: "0x2::coin::CurrencyCreated".to_string(),
+                    bcs: "AA==".to_string(),
+                    event_json: "{}".to_string(),
+                }],
+            )?,
+            FileType::MoveCall => check_writers(
+                dir.path(),
+                file_type,
+                vec![MoveCallEntry {
+                    transaction_digest: digest.clone(),
+                    checkpoint: 1,
+                    epoch: 0,
+                    timestamp_ms: 1000,
+                    package: "0x2".to_string(),
+                    module: "pay".to_string(),
+                    function: "split".to_string(),
+                }],
+            )?,
+            FileType::MovePackage => check_writers(
+                dir.path(),
+                file_type,
+                vec![MovePackageEntry {
+                    package_id: "0x2".to_string(),
+                    checkpoint: 1,
+                    epoch: 0,
+                    timestamp_ms: 1000,
+                    bcs: "AA==".to_string(),
+                    transaction_digest: digest.clone(),
+                    package_version: Some(1),
+                    original_package_id: None,
+                }],
+            )?,
+            FileType::DynamicField => check_writers(
+                dir.path(),
+                file_type,
+                vec![DynamicFieldEntry {
+                    parent_object_id: address.clone(),
+                    transaction_digest: digest.clone(),
+                    checkpoint: 1,
+                    epoch: 0,
+                    timestamp_ms: 1000,
+                    name: "1".to_string(),
+                    bcs_name: "AQ==".to_string(),
+                    type_: DynamicFieldType::DynamicObject,
+                    object_id: address.clone(),
+                    version: 1,
+                    digest: digest.clone(),
+                    object_type: "0x2::coin::Coin<0x2::iota::IOTA>".to_string(),
+                }],
+            )?,
+            FileType::WrappedObject => check_writers(
+                dir.path(),
+                file_type,
+                vec![WrappedObjectEntry {
+                    object_id: None,
+                    root_object_id: address.clone(),
+                    root_object_version: 1,
+                    checkpoint: 1,
+                    epoch: 0,
+                    timestamp_ms: 1000,
+                    json_path: "$.balance".to_string(),
+                    struct_tag: Some("0x2::balance::Balance".to_string()),
+                }],
+            )?,
+        }
+    }
+    Ok(())
+}
@@ -15,15 +15,17 @@ use anyhow::Result;
 use iota_keys::keypair_file::{read_authority_keypair_from_file, read_keypair_from_file};
 use iota_protocol_config::{Chain, SupportedProtocolVersions};
 use iota_types::{
-    base_types::IotaAddress,
+    base_types::{IotaAddress, ObjectID},
     committee::EpochId,
     crypto::{
         get_key_pair_from_rng, AccountKeyPair, AuthorityKeyPair, AuthorityPublicKeyBytes,
         IotaKeyPair, KeypairTraits, NetworkKeyPair,
     },
+    iota_serde::IotaStructTag,
     messages_checkpoint::CheckpointSequenceNumber,
     multiaddr::Multiaddr,
 };
+use move_core_types::language_storage::StructTag;
 use narwhal_config::Parameters as ConsensusParameters;
 use once_cell::sync::OnceCell;
 use rand::rngs::OsRng;
@@ -534,7 +536,7 @@ impl Default for CheckpointExecutorConfig {
     }
 }
 
-#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "kebab-case")]
 pub struct AuthorityStorePruningConfig {
     /// number of the latest epoch dbs to retain
@@ -576,6 +578,39 @@ pub struct AuthorityStorePruningConfig {
     pub killswitch_tombstone_pruning: bool,
     #[serde(default, skip_serializing_if = "std::ops::Not::not")]
     pub smooth: bool,
+    /// object versions and transactions which are kept regardless of the
+    /// number of epochs to retain
+    #[serde(default, skip_serializing_if = "HistoryRetentionConfig::is_empty")]
+    pub history_retention: HistoryRetentionConfig,
+}
+
+/// Rules selecting history which the pruner keeps, e.g. for full nodes
+/// serving the complete history of a few applications. An object version is
+/// kept if it matches any rule, or if it is owned by an object whose latest
+/// version does, such as the dynamic fields of a kept object. A transaction is
+/// kept if it was sent by a retained owner, calls into a retained package or
+/// wrote a kept object version, along with the contents of its checkpoint.
+#[serde_as]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct HistoryRetentionConfig {
+    /// Packages whose objects, and objects of the types they define, are kept
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub packages: Vec<ObjectID>,
+    /// Move types whose objects are kept. A type given without type
+    /// parameters keeps all of its instantiations.
+    #[serde_as(as = "Vec<IotaStructTag>")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub move_types: Vec<StructTag>,
+    /// Addresses whose owned objects are kept
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub owners: Vec<IotaAddress>,
+}
+
+impl HistoryRetentionConfig {
+    pub fn is_empty(&self) -> bool {
+        self.packages.is_empty() && self.move_types.is_empty() && self.owners.is_empty()
+    }
 }
 
 fn default_num_latest_epoch_dbs_to_retain() -> usize {
@@ -607,6 +642,7 @@ impl Default for AuthorityStorePruningConfig {
             num_epochs_to_retain_for_checkpoints: if cfg!(msim) { Some(2) } else { None },
             killswitch_tombstone_pruning: false,
             smooth: false,
+            history_retention: HistoryRetentionConfig::default(),
         }
     }
 }
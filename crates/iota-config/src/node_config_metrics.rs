@@ -6,7 +6,7 @@ use std::sync::Arc;
 
 use prometheus::{register_int_gauge_with_registry, IntGauge, Registry};
 
-use crate::NodeConfig;
+use crate::{transaction_deny_config::TransactionDenyConfig, NodeConfig};
 
 pub struct NodeConfigMetrics {
     tx_deny_config_user_transaction_disabled: IntGauge,
@@ -16,6 +16,7 @@ pub struct NodeConfigMetrics {
     tx_deny_config_num_denied_objects: IntGauge,
     tx_deny_config_num_denied_packages: IntGauge,
     tx_deny_config_num_denied_addresses: IntGauge,
+    tx_deny_config_num_denied_types: IntGauge,
 }
 
 impl NodeConfigMetrics {
@@ -63,24 +64,38 @@ impl NodeConfigMetrics {
                 registry
             )
             .unwrap(),
+            tx_deny_config_num_denied_types: register_int_gauge_with_registry!(
+                "tx_deny_config_num_denied_types",
+                "Number of denied Move types",
+                registry
+            )
+            .unwrap(),
         };
         Arc::new(this)
     }
 
     pub fn record_metrics(&self, config: &NodeConfig) {
+        self.record_transaction_deny_config(&config.transaction_deny_config);
+    }
+
+    /// Records the transaction deny config in use. Called again whenever its
+    /// deny lists are changed at runtime.
+    pub fn record_transaction_deny_config(&self, config: &TransactionDenyConfig) {
         self.tx_deny_config_user_transaction_disabled
-            .set(config.transaction_deny_config.user_transaction_disabled() as i64);
+            .set(config.user_transaction_disabled() as i64);
         self.tx_deny_config_shared_object_disabled
-            .set(config.transaction_deny_config.shared_object_disabled() as i64);
+            .set(config.shared_object_disabled() as i64);
         self.tx_deny_config_package_publish_disabled
-            .set(config.transaction_deny_config.package_publish_disabled() as i64);
+            .set(config.package_publish_disabled() as i64);
         self.tx_deny_config_package_upgrade_disabled
-            .set(config.transaction_deny_config.package_upgrade_disabled() as i64);
+            .set(config.package_upgrade_disabled() as i64);
         self.tx_deny_config_num_denied_objects
-            .set(config.transaction_deny_config.get_object_deny_set().len() as i64);
+            .set(config.get_object_deny_set().len() as i64);
         self.tx_deny_config_num_denied_packages
-            .set(config.transaction_deny_config.get_package_deny_set().len() as i64);
+            .set(config.get_package_deny_set().len() as i64);
         self.tx_deny_config_num_denied_addresses
-            .set(config.transaction_deny_config.get_address_deny_set().len() as i64);
+            .set(config.get_address_deny_set().len() as i64);
+        self.tx_deny_config_num_denied_types
+            .set(config.get_type_deny_set().len() as i64);
     }
 }
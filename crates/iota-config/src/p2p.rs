@@ -192,6 +192,14 @@ pub struct StateSyncConfig {
     /// If unspecified, this will default to no limit.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub get_checkpoint_contents_per_checkpoint_limit: Option<usize>,
+
+    /// Number of checkpoints the highest synced checkpoint may lag behind the
+    /// highest checkpoint known from peers before checkpoint contents are
+    /// synced from the archive first, instead of from peers.
+    ///
+    /// If unspecified, this will default to `10,000`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub archive_sync_lag_threshold: Option<u64>,
 }
 
 impl StateSyncConfig {
@@ -250,6 +258,13 @@ impl StateSyncConfig {
             .map(Duration::from_millis)
             .unwrap_or(DEFAULT_TIMEOUT)
     }
+
+    pub fn archive_sync_lag_threshold(&self) -> u64 {
+        const ARCHIVE_SYNC_LAG_THRESHOLD: u64 = 10_000;
+
+        self.archive_sync_lag_threshold
+            .unwrap_or(ARCHIVE_SYNC_LAG_THRESHOLD)
+    }
 }
 
 /// Access Type of a node.
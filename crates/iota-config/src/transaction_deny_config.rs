@@ -2,12 +2,21 @@
 // Modifications Copyright (c) 2024 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use std::collections::HashSet;
-
-use iota_types::base_types::{IotaAddress, ObjectID};
+use std::{collections::HashSet, fmt, path::PathBuf};
+
+use iota_types::{
+    base_types::{IotaAddress, ObjectID},
+    iota_serde::IotaStructTag,
+    parse_iota_struct_tag,
+};
+use move_core_types::language_storage::StructTag;
 use once_cell::sync::OnceCell;
 use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+
+use crate::Config;
 
+#[serde_as]
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
 #[serde(rename_all = "kebab-case")]
 pub struct TransactionDenyConfig {
@@ -38,6 +47,22 @@ pub struct TransactionDenyConfig {
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     address_deny_list: Vec<IotaAddress>,
 
+    /// A list of Move types that are not allowed to be used as type arguments
+    /// of Move calls or `MakeMoveVec` commands, including when nested in
+    /// other type arguments, or as the types of input and receiving objects.
+    /// A type given without type parameters denies all of its instantiations.
+    /// Values of a denied type created or returned within a transaction are
+    /// not detected.
+    #[serde_as(as = "Vec<IotaStructTag>")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    type_deny_list: Vec<StructTag>,
+
+    /// Path of the file holding the object, package, address and type deny
+    /// lists changed at runtime through the admin server. When the file
+    /// exists on startup, its lists replace the ones of this config.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    deny_list_path: Option<PathBuf>,
+
     /// Whether publishing new packages is disabled.
     #[serde(default)]
     package_publish_disabled: bool,
@@ -66,6 +91,9 @@ pub struct TransactionDenyConfig {
     #[serde(skip)]
     address_deny_set: OnceCell<HashSet<IotaAddress>>,
 
+    #[serde(skip)]
+    type_deny_set: OnceCell<HashSet<StructTag>>,
+
     /// Whether receiving objects transferred to other objects is allowed
     #[serde(default)]
     receiving_objects_disabled: bool,
@@ -77,7 +105,6 @@ pub struct TransactionDenyConfig {
     /// A list of disabled OAuth providers for zkLogin
     #[serde(default)]
     zklogin_disabled_providers: HashSet<String>,
-    // TODO: We could consider add a deny list for types that we want to disable public transfer.
     // TODO: We could also consider disable more types of commands, such as transfer, split and
     // etc.
 }
@@ -98,6 +125,61 @@ impl TransactionDenyConfig {
             .get_or_init(|| self.address_deny_list.iter().cloned().collect())
     }
 
+    pub fn get_type_deny_set(&self) -> &HashSet<StructTag> {
+        self.type_deny_set
+            .get_or_init(|| self.type_deny_list.iter().cloned().collect())
+    }
+
+    pub fn deny_list_path(&self) -> Option<&PathBuf> {
+        self.deny_list_path.as_ref()
+    }
+
+    /// The object, package, address and type deny lists of this config.
+    pub fn deny_lists(&self) -> DenyLists {
+        DenyLists {
+            object_deny_list: self.object_deny_list.clone(),
+            package_deny_list: self.package_deny_list.clone(),
+            address_deny_list: self.address_deny_list.clone(),
+            type_deny_list: self.type_deny_list.clone(),
+        }
+    }
+
+    /// Returns a copy of this config with its deny lists replaced.
+    pub fn with_deny_lists(&self, lists: DenyLists) -> Self {
+        Self {
+            object_deny_list: lists.object_deny_list,
+            package_deny_list: lists.package_deny_list,
+            address_deny_list: lists.address_deny_list,
+            type_deny_list: lists.type_deny_list,
+            object_deny_set: OnceCell::new(),
+            package_deny_set: OnceCell::new(),
+            address_deny_set: OnceCell::new(),
+            type_deny_set: OnceCell::new(),
+            ..self.clone()
+        }
+    }
+
+    /// Replaces the deny lists of this config with the ones persisted at
+    /// `deny_list_path`, if that file exists.
+    pub fn with_persisted_deny_lists(self) -> anyhow::Result<Self> {
+        match &self.deny_list_path {
+            Some(path) if path.exists() => {
+                let lists = DenyLists::load(path)?;
+                Ok(self.with_deny_lists(lists))
+            }
+            _ => Ok(self),
+        }
+    }
+
+    /// Writes the deny lists of this config to `deny_list_path`. Without a
+    /// path, runtime changes only last until the node is restarted.
+    pub fn persist_deny_lists(&self) -> anyhow::Result<()> {
+        if let Some(path) = &self.deny_list_path {
+            self.deny_lists().save(path)?;
+        }
+        Ok(())
+    }
+
     pub fn package_publish_disabled(&self) -> bool {
         self.package_publish_disabled
     }
@@ -181,6 +263,16 @@ impl TransactionDenyConfigBuilder {
         self
     }
 
+    pub fn add_denied_type(mut self, tag: StructTag) -> Self {
+        self.config.type_deny_list.push(tag);
+        self
+    }
+
+    pub fn deny_list_path(mut self, path: PathBuf) -> Self {
+        self.config.deny_list_path = Some(path);
+        self
+    }
+
     pub fn disable_zklogin_sig(mut self) -> Self {
         self.config.zklogin_sig_disabled = true;
         self
@@ -191,3 +283,150 @@ impl TransactionDenyConfigBuilder {
         self
     }
 }
+
+/// The deny lists of a [`TransactionDenyConfig`] which can be changed at
+/// runtime.
+#[serde_as]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct DenyLists {
+    #[serde(default)]
+    pub object_deny_list: Vec<ObjectID>,
+    #[serde(default)]
+    pub package_deny_list: Vec<ObjectID>,
+    #[serde(default)]
+    pub address_deny_list: Vec<IotaAddress>,
+    #[serde_as(as = "Vec<IotaStructTag>")]
+    #[serde(default)]
+    pub type_deny_list: Vec<StructTag>,
+}
+
+impl Config for DenyLists {}
+
+impl DenyLists {
+    /// Adds an entry, returning whether it was not denied before.
+    pub fn add(&mut self, entry: DenyListEntry) -> bool {
+        fn push<T: PartialEq>(list: &mut Vec<T>, item: T) -> bool {
+            if list.contains(&item) {
+                return false;
+            }
+            list.push(item);
+            true
+        }
+        match entry {
+            DenyListEntry::Object(id) => push(&mut self.object_deny_list, id),
+            DenyListEntry::Package(id) => push(&mut self.package_deny_list, id),
+            DenyListEntry::Address(address) => push(&mut self.address_deny_list, address),
+            DenyListEntry::Type(tag) => push(&mut self.type_deny_list, tag),
+        }
+    }
+
+    /// Removes an entry, returning whether it was denied before.
+    pub fn remove(&mut self, entry: &DenyListEntry) -> bool {
+        fn retain<T: PartialEq>(list: &mut Vec<T>, item: &T) -> bool {
+            let len = list.len();
+            list.retain(|i| i != item);
+            list.len() != len
+        }
+        match entry {
+            DenyListEntry::Object(id) => retain(&mut self.object_deny_list, id),
+            DenyListEntry::Package(id) => retain(&mut self.package_deny_list, id),
+            DenyListEntry::Address(address) => retain(&mut self.address_deny_list, address),
+            DenyListEntry::Type(tag) => retain(&mut self.type_deny_list, tag),
+        }
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = DenyListEntry> + '_ {
+        self.object_deny_list
+            .iter()
+            .map(|id| DenyListEntry::Object(*id))
+            .chain(
+                self.package_deny_list
+                    .iter()
+                    .map(|id| DenyListEntry::Package(*id)),
+            )
+            .chain(
+                self.address_deny_list
+                    .iter()
+                    .map(|address| DenyListEntry::Address(*address)),
+            )
+            .chain(
+                self.type_deny_list
+                    .iter()
+                    .map(|tag| DenyListEntry::Type(tag.clone())),
+            )
+    }
+}
+
+/// A single entry of the [`DenyLists`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DenyListEntry {
+    Object(ObjectID),
+    Package(ObjectID),
+    Address(IotaAddress),
+    Type(StructTag),
+}
+
+impl DenyListEntry {
+    /// Parses an entry from its kind (`object`, `package`, `address` or
+    /// `type`) and value.
+    pub fn parse(kind: &str, value: &str) -> anyhow::Result<Self> {
+        Ok(match kind {
+            "object" => Self::Object(value.parse()?),
+            "package" => Self::Package(value.parse()?),
+            "address" => Self::Address(value.parse()?),
+            "type" => Self::Type(parse_iota_struct_tag(value)?),
+            _ => anyhow::bail!("Unknown deny list kind: {kind}"),
+        })
+    }
+}
+
+impl fmt::Display for DenyListEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DenyListEntry::Object(id) => write!(f, "object {id}"),
+            DenyListEntry::Package(id) => write!(f, "package {id}"),
+            DenyListEntry::Address(address) => write!(f, "address {address}"),
+            DenyListEntry::Type(tag) => write!(f, "type {}", tag.to_canonical_display(true)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deny_lists_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("deny-lists.yaml");
+        let config = TransactionDenyConfigBuilder::new()
+            .add_denied_object(ObjectID::random())
+            .deny_list_path(path.clone())
+            .build();
+        // Without a persisted file, the lists of the config are used.
+        let config = config.with_persisted_deny_lists().unwrap();
+        assert_eq!(config.get_object_deny_set().len(), 1);
+
+        let mut lists = config.deny_lists();
+        let tag = parse_iota_struct_tag("0x2::coin::Coin").unwrap();
+        assert!(lists.add(DenyListEntry::Type(tag.clone())));
+        assert!(!lists.add(DenyListEntry::Type(tag.clone())));
+        assert!(lists.remove(&DenyListEntry::Object(config.object_deny_list[0])));
+        let updated = config.with_deny_lists(lists.clone());
+        assert!(updated.get_object_deny_set().is_empty());
+        assert!(updated.get_type_deny_set().contains(&tag));
+        updated.persist_deny_lists().unwrap();
+
+        let reloaded = config.with_persisted_deny_lists().unwrap();
+        assert_eq!(reloaded.deny_lists(), lists);
+    }
+
+    #[test]
+    fn parse_entries() {
+        let entry = DenyListEntry::parse("type", "0x2::coin::Coin<0x2::iota::IOTA>").unwrap();
+        assert!(matches!(entry, DenyListEntry::Type(_)));
+        assert!(DenyListEntry::parse("address", "0x2").is_ok());
+        assert!(DenyListEntry::parse("coin", "0x2").is_err());
+    }
+}
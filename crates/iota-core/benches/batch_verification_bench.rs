@@ -82,6 +82,7 @@ fn async_verifier_bench(c: &mut Criterion) {
                         ZkLoginEnv::Test,
                         true,
                         true,
+                        true,
                     ));
 
                     b.iter(|| {
@@ -32,7 +32,7 @@ use iota_config::{
         AuthorityOverloadConfig, AuthorityStorePruningConfig, DBCheckpointConfig,
         ExpensiveSafetyCheckConfig, StateDebugDumpConfig,
     },
-    transaction_deny_config::TransactionDenyConfig,
+    transaction_deny_config::{DenyLists, TransactionDenyConfig},
     NodeConfig,
 };
 use iota_framework::{BuiltInFramework, SystemPackage};
@@ -780,7 +780,11 @@ pub struct AuthorityState {
     /// Config controlling what kind of expensive safety checks to perform.
     expensive_safety_check_config: ExpensiveSafetyCheckConfig,
 
-    transaction_deny_config: TransactionDenyConfig,
+    /// Swapped atomically when the deny lists are changed at runtime.
+    transaction_deny_config: ArcSwap<TransactionDenyConfig>,
+
+    /// Serializes runtime changes of the transaction deny lists.
+    transaction_deny_lists_update_lock: Mutex<()>,
 
     certificate_deny_config: CertificateDenyConfig,
 
@@ -868,7 +872,7 @@ impl AuthorityState {
             transaction.tx_signatures(),
             &input_object_kinds,
             &receiving_objects_refs,
-            &self.transaction_deny_config,
+            &self.transaction_deny_config.load(),
             self.get_backing_package_store().as_ref(),
         )?;
 
@@ -882,6 +886,12 @@ impl AuthorityState {
             )
             .await?;
 
+        iota_transaction_checks::deny::check_input_object_types(
+            &self.transaction_deny_config.load(),
+            &input_objects,
+            &receiving_objects,
+        )?;
+
         let (_gas_status, checked_input_objects) =
             iota_transaction_checks::check_transaction_input(
                 epoch_store.protocol_config(),
@@ -1694,7 +1704,7 @@ impl AuthorityState {
             &[],
             &input_object_kinds,
             &receiving_object_refs,
-            &self.transaction_deny_config,
+            &self.transaction_deny_config.load(),
             self.get_backing_package_store().as_ref(),
         )?;
 
@@ -1707,6 +1717,12 @@ impl AuthorityState {
             )
             .await?;
 
+        iota_transaction_checks::deny::check_input_object_types(
+            &self.transaction_deny_config.load(),
+            &input_objects,
+            &receiving_objects,
+        )?;
+
         // make a gas object if one was not provided
         let mut gas_object_refs = transaction.gas().to_vec();
         let ((gas_status, checked_input_objects), mock_gas) = if transaction.gas().is_empty() {
@@ -1911,7 +1927,7 @@ impl AuthorityState {
             &[],
             &input_object_kinds,
             &receiving_object_refs,
-            &self.transaction_deny_config,
+            &self.transaction_deny_config.load(),
             self.get_backing_package_store().as_ref(),
         )?;
 
@@ -1920,6 +1936,12 @@ impl AuthorityState {
             .read_objects_for_dev_inspect(&input_object_kinds, &receiving_object_refs)
             .await?;
 
+        iota_transaction_checks::deny::check_input_object_types(
+            &self.transaction_deny_config.load(),
+            &input_objects,
+            &receiving_objects,
+        )?;
+
         // Create and use a dummy gas object if there is no gas object provided.
         let dummy_gas_object = Object::new_gas_with_balance_and_owner_for_testing(
             DEV_INSPECT_GAS_COIN_VALUE,
@@ -2687,7 +2709,8 @@ impl AuthorityState {
             _authority_per_epoch_pruner,
             db_checkpoint_config: db_checkpoint_config.clone(),
             expensive_safety_check_config,
-            transaction_deny_config,
+            transaction_deny_config: ArcSwap::from_pointee(transaction_deny_config),
+            transaction_deny_lists_update_lock: Mutex::new(()),
             certificate_deny_config,
             debug_dump_config,
             authority_overload_config: authority_overload_config.clone(),
@@ -2773,7 +2796,7 @@ impl AuthorityState {
             &self.execution_cache.store_for_testing().perpetual_tables,
             &self.checkpoint_store,
             &self.execution_cache.store_for_testing().objects_lock_table,
-            config.authority_store_pruning_config,
+            config.authority_store_pruning_config.clone(),
             metrics,
             config.indirect_objects_threshold,
             archive_readers,
@@ -4108,6 +4131,27 @@ impl AuthorityState {
         epoch_store.clear_override_protocol_upgrade_buffer_stake()
     }
 
+    pub fn transaction_deny_config(&self) -> Arc<TransactionDenyConfig> {
+        self.transaction_deny_config.load_full()
+    }
+
+    /// Applies a change to the transaction deny lists. The changed lists are
+    /// persisted before they are used to check new transactions, so they
+    /// survive a restart of the node.
+    pub fn update_transaction_deny_lists<R>(
+        &self,
+        update: impl FnOnce(&mut DenyLists) -> R,
+    ) -> anyhow::Result<R> {
+        let _guard = self.transaction_deny_lists_update_lock.lock();
+        let config = self.transaction_deny_config.load();
+        let mut lists = config.deny_lists();
+        let result = update(&mut lists);
+        let config = config.with_deny_lists(lists);
+        config.persist_deny_lists()?;
+        self.transaction_deny_config.store(Arc::new(config));
+        Ok(result)
+    }
+
     /// Get the set of system packages that are compiled in to this build, if
     /// those packages are compatible with the current versions of those
     /// packages on-chain.
@@ -4,23 +4,27 @@
 
 use std::{
     cmp::{max, min},
-    collections::{BTreeSet, HashMap},
+    collections::{BTreeSet, HashMap, HashSet},
     sync::{Arc, Mutex},
     time::{Duration, SystemTime, UNIX_EPOCH},
 };
 
 use anyhow::anyhow;
 use iota_archival::reader::ArchiveReaderBalancer;
-use iota_config::node::AuthorityStorePruningConfig;
+use iota_config::node::{AuthorityStorePruningConfig, HistoryRetentionConfig};
 use iota_metrics::{monitored_scope, spawn_monitored_task};
 use iota_storage::mutex_table::RwLockTable;
 use iota_types::{
-    base_types::{ObjectID, SequenceNumber, VersionNumber},
+    base_types::{IotaAddress, ObjectID, SequenceNumber, VersionNumber},
+    digests::TransactionDigest,
     effects::{TransactionEffects, TransactionEffectsAPI},
     message_envelope::Message,
     messages_checkpoint::{CheckpointContents, CheckpointDigest, CheckpointSequenceNumber},
+    object::{Object, Owner},
     storage::ObjectKey,
+    transaction::TransactionDataAPI,
 };
+use move_core_types::language_storage::StructTag;
 use once_cell::sync::Lazy;
 use prometheus::{
     register_int_counter_with_registry, register_int_gauge_with_registry, IntCounter, IntGauge,
@@ -65,6 +69,8 @@ pub struct AuthorityStorePruningMetrics {
     pub last_pruned_effects_checkpoint: IntGauge,
     pub num_epochs_to_retain_for_objects: IntGauge,
     pub num_epochs_to_retain_for_checkpoints: IntGauge,
+    pub num_retained_objects: IntCounter,
+    pub num_retained_transactions: IntCounter,
 }
 
 impl AuthorityStorePruningMetrics {
@@ -106,6 +112,18 @@ impl AuthorityStorePruningMetrics {
                 registry
             )
             .unwrap(),
+            num_retained_objects: register_int_counter_with_registry!(
+                "num_retained_objects",
+                "Number of object versions kept by history retention rules",
+                registry
+            )
+            .unwrap(),
+            num_retained_transactions: register_int_counter_with_registry!(
+                "num_retained_transactions",
+                "Number of transactions kept by history retention rules",
+                registry
+            )
+            .unwrap(),
         };
         Arc::new(this)
     }
@@ -121,6 +139,117 @@ pub enum PruningMode {
     Checkpoints,
 }
 
+/// Matches object versions and transactions against the history retention
+/// rules of the pruning config
+struct HistoryRetention {
+    packages: HashSet<ObjectID>,
+    move_types: Vec<StructTag>,
+    owners: HashSet<IotaAddress>,
+}
+
+impl HistoryRetention {
+    fn new(config: &HistoryRetentionConfig) -> Option<Self> {
+        if config.is_empty() {
+            return None;
+        }
+        Some(Self {
+            packages: config.packages.iter().copied().collect(),
+            move_types: config.move_types.clone(),
+            owners: config.owners.iter().copied().collect(),
+        })
+    }
+
+    fn matches_object(&self, object: &Object) -> bool {
+        if self.packages.contains(&object.id()) {
+            return true;
+        }
+        if let Owner::AddressOwner(owner) = object.owner {
+            if self.owners.contains(&owner) {
+                return true;
+            }
+        }
+        let Some(tag) = object.struct_tag() else {
+            return false;
+        };
+        self.packages.contains(&ObjectID::from(tag.address))
+            || self.move_types.iter().any(|rule| {
+                rule.address == tag.address
+                    && rule.module == tag.module
+                    && rule.name == tag.name
+                    && (rule.type_params.is_empty() || rule.type_params == tag.type_params)
+            })
+    }
+
+    /// An object version is retained if it matches the rules, or if it is owned
+    /// by an object which does, such as the dynamic fields of a retained
+    /// object. Parent objects are matched at their latest version.
+    fn retains_object(
+        &self,
+        perpetual_db: &AuthorityPerpetualTables,
+        object: &Object,
+    ) -> anyhow::Result<bool> {
+        if self.matches_object(object) {
+            return Ok(true);
+        }
+        let mut owner = object.owner;
+        while let Owner::ObjectOwner(parent_id) = owner {
+            let Some((parent_key, store_object)) =
+                perpetual_db.get_latest_object_or_tombstone(parent_id.into())?
+            else {
+                return Ok(false);
+            };
+            let Some(parent) = perpetual_db.object(&parent_key, store_object)? else {
+                return Ok(false);
+            };
+            if self.matches_object(&parent) {
+                return Ok(true);
+            }
+            owner = parent.owner;
+        }
+        Ok(false)
+    }
+
+    fn retains_object_key(
+        &self,
+        perpetual_db: &AuthorityPerpetualTables,
+        object_key: &ObjectKey,
+    ) -> anyhow::Result<bool> {
+        let Some(store_object) = perpetual_db.objects.get(object_key)? else {
+            return Ok(false);
+        };
+        match perpetual_db.object(object_key, store_object)? {
+            Some(object) => self.retains_object(perpetual_db, &object),
+            None => Ok(false),
+        }
+    }
+
+    /// A transaction is retained if it was sent by a retained owner, calls
+    /// into a retained package or wrote a retained object version
+    fn retains_transaction(
+        &self,
+        perpetual_db: &AuthorityPerpetualTables,
+        effects: &TransactionEffects,
+    ) -> anyhow::Result<bool> {
+        if let Some(transaction) = perpetual_db.get_transaction(effects.transaction_digest())? {
+            let data = transaction.inner().data().transaction_data();
+            if self.owners.contains(&data.sender())
+                || data
+                    .move_calls()
+                    .into_iter()
+                    .any(|(package, _, _)| self.packages.contains(package))
+            {
+                return Ok(true);
+            }
+        }
+        for ((object_id, version, _), _, _) in effects.all_changed_objects() {
+            if self.retains_object_key(perpetual_db, &ObjectKey(object_id, version))? {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+}
+
 impl AuthorityStorePruner {
     /// prunes old versions of objects based on transaction effects
     async fn prune_objects(
@@ -131,6 +260,7 @@ impl AuthorityStorePruner {
         metrics: Arc<AuthorityStorePruningMetrics>,
         indirect_objects_threshold: usize,
         enable_pruning_tombstones: bool,
+        retention: Option<&HistoryRetention>,
     ) -> anyhow::Result<()> {
         let _scope = monitored_scope("ObjectsLivePruner");
         let mut wb = perpetual_db.objects.batch();
@@ -151,6 +281,25 @@ impl AuthorityStorePruner {
             }
         }
 
+        // Object versions matching the retention rules are kept, and their object
+        // ids are pruned with point deletes so that the kept versions are not
+        // covered by a range delete.
+        let mut retained_object_ids = HashSet::new();
+        if let Some(retention) = retention {
+            let mut retained_object_keys = HashSet::new();
+            for object_key in &live_object_keys_to_prune {
+                if retention.retains_object_key(perpetual_db, object_key)? {
+                    retained_object_keys.insert(*object_key);
+                }
+            }
+            live_object_keys_to_prune
+                .retain(|object_key| !retained_object_keys.contains(object_key));
+            retained_object_ids.extend(retained_object_keys.iter().map(|object_key| object_key.0));
+            metrics
+                .num_retained_objects
+                .inc_by(retained_object_keys.len() as u64);
+        }
+
         metrics
             .num_pruned_objects
             .inc_by(live_object_keys_to_prune.len() as u64);
@@ -175,7 +324,12 @@ impl AuthorityStorePruner {
         }
 
         let mut updates: HashMap<ObjectID, (VersionNumber, VersionNumber)> = HashMap::new();
+        let mut retained_object_id_keys_to_prune = vec![];
         for ObjectKey(object_id, seq_number) in live_object_keys_to_prune {
+            if retained_object_ids.contains(&object_id) {
+                retained_object_id_keys_to_prune.push(ObjectKey(object_id, seq_number));
+                continue;
+            }
             updates
                 .entry(object_id)
                 .and_modify(|range| *range = (min(range.0, seq_number), max(range.1, seq_number)))
@@ -191,6 +345,9 @@ impl AuthorityStorePruner {
             let end_range = ObjectKey(object_id, (max_version.value() + 1).into());
             wb.schedule_delete_range(&perpetual_db.objects, &start_range, &end_range)?;
         }
+        if !retained_object_id_keys_to_prune.is_empty() {
+            wb.delete_batch(&perpetual_db.objects, retained_object_id_keys_to_prune)?;
+        }
 
         // When enable_pruning_tombstones is enabled, instead of using range deletes, we
         // need to do a scan of all the keys for the deleted objects and then do
@@ -200,18 +357,35 @@ impl AuthorityStorePruner {
         // (imagine a tombstone is compacted away, but earlier version is still not).
         // Using point deletes guarantees that all earlier versions are deleted
         // in the database.
+        // A tombstone is kept as long as earlier versions of its object are
+        // retained.
         if !object_tombstones_to_prune.is_empty() {
             let mut object_keys_to_delete = vec![];
+            let mut num_retained_objects = 0;
             for ObjectKey(object_id, seq_number) in object_tombstones_to_prune {
+                let mut has_retained_versions = false;
                 for result in perpetual_db.objects.safe_iter_with_bounds(
                     Some(ObjectKey(object_id, VersionNumber::MIN)),
                     Some(ObjectKey(object_id, seq_number.next())),
                 ) {
-                    let (object_key, _) = result?;
+                    let (object_key, store_object) = result?;
                     assert_eq!(object_key.0, object_id);
+                    if let Some(retention) = retention {
+                        if let Some(object) = perpetual_db.object(&object_key, store_object)? {
+                            if retention.retains_object(perpetual_db, &object)? {
+                                has_retained_versions = true;
+                                num_retained_objects += 1;
+                                continue;
+                            }
+                        }
+                    }
+                    if has_retained_versions && object_key.1 == seq_number {
+                        continue;
+                    }
                     object_keys_to_delete.push(object_key);
                 }
             }
+            metrics.num_retained_objects.inc_by(num_retained_objects);
 
             wb.delete_batch(&perpetual_db.objects, object_keys_to_delete)?;
         }
@@ -240,14 +414,40 @@ impl AuthorityStorePruner {
         checkpoint_content_to_prune: Vec<CheckpointContents>,
         effects_to_prune: &Vec<TransactionEffects>,
         metrics: Arc<AuthorityStorePruningMetrics>,
+        retention: Option<&HistoryRetention>,
     ) -> anyhow::Result<()> {
         let _scope = monitored_scope("EffectsLivePruner");
 
+        let mut retained_transactions: HashSet<TransactionDigest> = HashSet::new();
+        if let Some(retention) = retention {
+            for effects in effects_to_prune {
+                if retention.retains_transaction(perpetual_db, effects)? {
+                    retained_transactions.insert(*effects.transaction_digest());
+                }
+            }
+            metrics
+                .num_retained_transactions
+                .inc_by(retained_transactions.len() as u64);
+        }
+
         let mut perpetual_batch = perpetual_db.objects.batch();
         let transactions: Vec<_> = checkpoint_content_to_prune
             .iter()
             .flat_map(|content| content.iter().map(|tx| tx.transaction))
+            .filter(|digest| !retained_transactions.contains(digest))
             .collect();
+        // Checkpoints holding retained transactions are kept, so that the
+        // transactions can still be resolved to their checkpoint contents
+        let (checkpoints_to_prune, checkpoint_content_to_prune): (Vec<_>, Vec<_>) =
+            checkpoints_to_prune
+                .into_iter()
+                .zip(checkpoint_content_to_prune)
+                .filter(|(_, content)| {
+                    !content
+                        .iter()
+                        .any(|tx| retained_transactions.contains(&tx.transaction))
+                })
+                .unzip();
 
         perpetual_batch.delete_batch(&perpetual_db.transactions, transactions.iter())?;
         perpetual_batch.delete_batch(&perpetual_db.executed_effects, transactions.iter())?;
@@ -258,6 +458,9 @@ impl AuthorityStorePruner {
 
         let mut effect_digests = vec![];
         for effects in effects_to_prune {
+            if retained_transactions.contains(effects.transaction_digest()) {
+                continue;
+            }
             let effects_digest = effects.digest();
             debug!("Pruning effects {:?}", effects_digest);
             effect_digests.push(effects_digest);
@@ -413,6 +616,7 @@ impl AuthorityStorePruner {
             .map(|c| c.epoch())
             .unwrap_or_default();
 
+        let retention = HistoryRetention::new(&config.history_retention);
         let mut checkpoints_to_prune = vec![];
         let mut checkpoint_content_to_prune = vec![];
         let mut effects_to_prune = vec![];
@@ -467,6 +671,7 @@ impl AuthorityStorePruner {
                             metrics.clone(),
                             indirect_objects_threshold,
                             !config.killswitch_tombstone_pruning,
+                            retention.as_ref(),
                         )
                         .await?
                     }
@@ -478,6 +683,7 @@ impl AuthorityStorePruner {
                         checkpoint_content_to_prune,
                         &effects_to_prune,
                         metrics.clone(),
+                        retention.as_ref(),
                     )?,
                 };
                 checkpoints_to_prune = vec![];
@@ -497,6 +703,7 @@ impl AuthorityStorePruner {
                         metrics.clone(),
                         indirect_objects_threshold,
                         !config.killswitch_tombstone_pruning,
+                        retention.as_ref(),
                     )
                     .await?
                 }
@@ -508,6 +715,7 @@ impl AuthorityStorePruner {
                     checkpoint_content_to_prune,
                     &effects_to_prune,
                     metrics.clone(),
+                    retention.as_ref(),
                 )?,
             };
         }
@@ -652,12 +860,12 @@ impl AuthorityStorePruner {
             loop {
                 tokio::select! {
                     _ = objects_prune_interval.tick(), if config.num_epochs_to_retain != u64::MAX => {
-                        if let Err(err) = Self::prune_objects_for_eligible_epochs(&perpetual_db, &checkpoint_store, &objects_lock_table, config, metrics.clone(), indirect_objects_threshold, epoch_duration_ms).await {
+                        if let Err(err) = Self::prune_objects_for_eligible_epochs(&perpetual_db, &checkpoint_store, &objects_lock_table, config.clone(), metrics.clone(), indirect_objects_threshold, epoch_duration_ms).await {
                             error!("Failed to prune objects: {:?}", err);
                         }
                     },
                     _ = checkpoints_prune_interval.tick(), if !matches!(config.num_epochs_to_retain_for_checkpoints(), None | Some(u64::MAX) | Some(0)) => {
-                        if let Err(err) = Self::prune_checkpoints_for_eligible_epochs(&perpetual_db, &checkpoint_store, &objects_lock_table, config, metrics.clone(), indirect_objects_threshold, archive_readers.clone(), epoch_duration_ms).await {
+                        if let Err(err) = Self::prune_checkpoints_for_eligible_epochs(&perpetual_db, &checkpoint_store, &objects_lock_table, config.clone(), metrics.clone(), indirect_objects_threshold, archive_readers.clone(), epoch_duration_ms).await {
                             error!("Failed to prune checkpoints: {:?}", err);
                         }
                     },
@@ -716,16 +924,32 @@ impl AuthorityStorePruner {
 
 #[cfg(test)]
 mod tests {
-    use std::{collections::HashSet, path::Path, sync::Arc, time::Duration};
+    use std::{
+        collections::{HashMap, HashSet},
+        path::Path,
+        sync::Arc,
+        time::Duration,
+    };
 
+    use iota_config::node::HistoryRetentionConfig;
     use iota_storage::mutex_table::RwLockTable;
     use iota_types::{
-        base_types::{ObjectDigest, ObjectID, SequenceNumber},
+        base_types::{
+            random_object_ref, ExecutionDigests, IotaAddress, ObjectDigest, ObjectID,
+            SequenceNumber,
+        },
+        digests::CheckpointDigest,
         effects::{TransactionEffects, TransactionEffectsAPI},
+        gas_coin::GasCoin,
+        message_envelope::Message,
+        messages_checkpoint::CheckpointContents,
         object::Object,
         storage::ObjectKey,
+        transaction::{Transaction, TransactionData, VerifiedTransaction},
+        IOTA_FRAMEWORK_PACKAGE_ID,
     };
     use more_asserts as ma;
+    use move_core_types::language_storage::TypeTag;
     use prometheus::Registry;
     use tracing::log::info;
     use typed_store::{
@@ -733,14 +957,17 @@ mod tests {
         Map,
     };
 
-    use super::AuthorityStorePruner;
-    use crate::authority::{
-        authority_store_pruner::AuthorityStorePruningMetrics,
-        authority_store_tables::AuthorityPerpetualTables,
-        authority_store_types::{
-            get_store_object_pair, ObjectContentDigest, StoreData, StoreObject, StoreObjectPair,
-            StoreObjectWrapper,
+    use super::{AuthorityStorePruner, HistoryRetention};
+    use crate::{
+        authority::{
+            authority_store_pruner::AuthorityStorePruningMetrics,
+            authority_store_tables::AuthorityPerpetualTables,
+            authority_store_types::{
+                get_store_object_pair, ObjectContentDigest, StoreData, StoreObject,
+                StoreObjectPair, StoreObjectWrapper,
+            },
         },
+        checkpoints::CheckpointStore,
     };
 
     fn get_keys_after_pruning(path: &Path) -> anyhow::Result<HashSet<ObjectKey>> {
@@ -845,6 +1072,34 @@ mod tests {
         Ok((to_keep, to_delete, tombstones))
     }
 
+    /// Writes `object` as an indirect object and returns the digest of its
+    /// contents.
+    fn insert_indirect_object(
+        db: &AuthorityPerpetualTables,
+        object_key: ObjectKey,
+        object: Object,
+    ) -> ObjectContentDigest {
+        let StoreObjectPair(store_object, indirect_object) = get_store_object_pair(object, 1);
+        let StoreObject::Value(value) = store_object.clone().into_inner() else {
+            panic!("expected an object value");
+        };
+        let StoreData::IndirectObject(metadata) = value.data else {
+            panic!("expected an indirect object");
+        };
+        let mut batch = db.objects.batch();
+        batch
+            .insert_batch(&db.objects, [(object_key, store_object)])
+            .unwrap();
+        batch
+            .merge_batch(
+                &db.indirect_move_objects,
+                [(metadata.digest, indirect_object.unwrap())],
+            )
+            .unwrap();
+        batch.write().unwrap();
+        metadata.digest
+    }
+
     pub(crate) fn lock_table() -> Arc<RwLockTable<ObjectContentDigest>> {
         Arc::new(RwLockTable::new(1))
     }
@@ -891,6 +1146,7 @@ mod tests {
                 metrics,
                 indirect_object_threshold,
                 true,
+                None,
             )
             .await
             .unwrap();
@@ -1017,6 +1273,7 @@ mod tests {
             metrics,
             0,
             true,
+            None,
         )
         .await;
         info!("Total pruned keys = {:?}", total_pruned);
@@ -1032,6 +1289,293 @@ mod tests {
         ma::assert_le!(after_compaction_size, before_compaction_size);
         Ok(())
     }
+
+    // Tests that retained object versions and the dynamic fields of retained
+    // objects survive pruning, along with the indirect objects they reference.
+    #[tokio::test]
+    async fn test_pruning_objects_with_history_retention() {
+        let path = tempfile::tempdir().unwrap().into_path();
+        let owner = IotaAddress::random_for_testing_only();
+        let retention = HistoryRetention::new(&HistoryRetentionConfig {
+            owners: vec![owner],
+            ..Default::default()
+        })
+        .unwrap();
+        let mut to_keep = HashMap::new();
+        {
+            let db = Arc::new(AuthorityPerpetualTables::open(&path, None));
+            let mut effects = TransactionEffects::default();
+            let ids = ObjectID::in_range(ObjectID::ZERO, 100).unwrap();
+            for (index, id) in ids.into_iter().enumerate() {
+                for seq in 0..4 {
+                    let object_key = ObjectKey(id, SequenceNumber::from_u64(seq));
+                    // Version 1 of every other object is owned by the retained owner,
+                    // the other objects are pruned with range deletes.
+                    let retained = index % 2 == 0 && seq == 1;
+                    let object_owner = if retained {
+                        owner
+                    } else {
+                        IotaAddress::random_for_testing_only()
+                    };
+                    let digest = insert_indirect_object(
+                        &db,
+                        object_key,
+                        Object::with_id_owner_gas_for_testing(id, object_owner, seq),
+                    );
+                    if retained || seq == 3 {
+                        to_keep.insert(object_key, digest);
+                    }
+                    if seq < 3 {
+                        effects.unsafe_add_deleted_live_object_for_testing((
+                            id,
+                            object_key.1,
+                            ObjectDigest::MIN,
+                        ));
+                    }
+                }
+            }
+
+            // The old version of a dynamic field is kept with its retained parent.
+            let parent_id = ObjectID::random();
+            let parent_key = ObjectKey(parent_id, SequenceNumber::from_u64(0));
+            let digest = insert_indirect_object(
+                &db,
+                parent_key,
+                Object::with_id_owner_for_testing(parent_id, owner),
+            );
+            to_keep.insert(parent_key, digest);
+            let child_id = ObjectID::random();
+            for seq in 0..2 {
+                let child_key = ObjectKey(child_id, SequenceNumber::from_u64(seq));
+                let digest = insert_indirect_object(
+                    &db,
+                    child_key,
+                    Object::with_object_owner_for_testing(child_id, parent_id),
+                );
+                to_keep.insert(child_key, digest);
+            }
+            effects.unsafe_add_deleted_live_object_for_testing((
+                child_id,
+                SequenceNumber::from_u64(0),
+                ObjectDigest::MIN,
+            ));
+
+            let registry = Registry::default();
+            let metrics = AuthorityStorePruningMetrics::new(&registry);
+            AuthorityStorePruner::prune_objects(
+                vec![effects],
+                &db,
+                &lock_table(),
+                0,
+                metrics,
+                1,
+                true,
+                Some(&retention),
+            )
+            .await
+            .unwrap();
+        }
+        tokio::time::sleep(Duration::from_secs(3)).await;
+
+        assert_eq!(
+            to_keep.keys().copied().collect::<HashSet<_>>(),
+            get_keys_after_pruning(&path).unwrap()
+        );
+        let perpetual_db = AuthorityPerpetualTables::open(&path, None);
+        perpetual_db.indirect_move_objects.flush().unwrap();
+        perpetual_db
+            .indirect_move_objects
+            .compact_range(&ObjectDigest::MIN, &ObjectDigest::MAX)
+            .unwrap();
+        perpetual_db
+            .indirect_move_objects
+            .compact_range(&ObjectDigest::MIN, &ObjectDigest::MAX)
+            .unwrap();
+        let indirect_objects: HashSet<_> = perpetual_db
+            .indirect_move_objects
+            .keys()
+            .map(Result::unwrap)
+            .collect();
+        assert_eq!(indirect_objects, to_keep.into_values().collect());
+    }
+
+    // Tests that retained transactions survive pruning along with the contents
+    // of their checkpoints.
+    #[tokio::test]
+    async fn test_pruning_checkpoints_with_history_retention() {
+        let path = tempfile::tempdir().unwrap().into_path();
+        let perpetual_db = Arc::new(AuthorityPerpetualTables::open(&path, None));
+        let checkpoint_store = CheckpointStore::new(&path.join("checkpoints"));
+        let owner = IotaAddress::random_for_testing_only();
+        let retention = HistoryRetention::new(&HistoryRetentionConfig {
+            owners: vec![owner],
+            ..Default::default()
+        })
+        .unwrap();
+
+        let mut effects_to_prune = vec![];
+        let mut checkpoint_content_to_prune = vec![];
+        for sender in [owner, IotaAddress::random_for_testing_only()] {
+            let transaction = VerifiedTransaction::new_unchecked(Transaction::from_data(
+                TransactionData::new_transfer_iota(
+                    IotaAddress::random_for_testing_only(),
+                    sender,
+                    None,
+                    random_object_ref(),
+                    1_000_000,
+                    1_000,
+                ),
+                vec![],
+            ));
+            let mut effects = TransactionEffects::default();
+            *effects.transaction_digest_mut_for_testing() = *transaction.digest();
+            let contents =
+                CheckpointContents::new_with_digests_only_for_tests([ExecutionDigests::new(
+                    *transaction.digest(),
+                    effects.digest(),
+                )]);
+            perpetual_db
+                .transactions
+                .insert(transaction.digest(), transaction.serializable_ref())
+                .unwrap();
+            perpetual_db
+                .effects
+                .insert(&effects.digest(), &effects)
+                .unwrap();
+            perpetual_db
+                .executed_effects
+                .insert(transaction.digest(), &effects.digest())
+                .unwrap();
+            perpetual_db
+                .executed_transactions_to_checkpoint
+                .insert(transaction.digest(), &(0, 0))
+                .unwrap();
+            checkpoint_store
+                .insert_checkpoint_contents(contents.clone())
+                .unwrap();
+            effects_to_prune.push(effects);
+            checkpoint_content_to_prune.push(contents);
+        }
+
+        let registry = Registry::default();
+        let metrics = AuthorityStorePruningMetrics::new(&registry);
+        AuthorityStorePruner::prune_checkpoints(
+            &perpetual_db,
+            &checkpoint_store,
+            0,
+            vec![CheckpointDigest::random(), CheckpointDigest::random()],
+            checkpoint_content_to_prune.clone(),
+            &effects_to_prune,
+            metrics,
+            Some(&retention),
+        )
+        .unwrap();
+
+        for (index, (effects, contents)) in effects_to_prune
+            .iter()
+            .zip(&checkpoint_content_to_prune)
+            .enumerate()
+        {
+            // Only the transaction sent by the retained owner is kept.
+            let retained = index == 0;
+            let transaction_digest = effects.transaction_digest();
+            assert_eq!(
+                perpetual_db
+                    .transactions
+                    .contains_key(transaction_digest)
+                    .unwrap(),
+                retained
+            );
+            assert_eq!(
+                perpetual_db
+                    .executed_effects
+                    .contains_key(transaction_digest)
+                    .unwrap(),
+                retained
+            );
+            assert_eq!(
+                perpetual_db
+                    .executed_transactions_to_checkpoint
+                    .contains_key(transaction_digest)
+                    .unwrap(),
+                retained
+            );
+            assert_eq!(
+                perpetual_db
+                    .effects
+                    .contains_key(&effects.digest())
+                    .unwrap(),
+                retained
+            );
+            assert_eq!(
+                checkpoint_store
+                    .checkpoint_content
+                    .contains_key(contents.digest())
+                    .unwrap(),
+                retained
+            );
+        }
+    }
+
+    #[test]
+    fn test_history_retention_matches_objects() {
+        let owner = IotaAddress::random_for_testing_only();
+        let object = Object::with_id_owner_for_testing(ObjectID::random(), owner);
+        let coin_type = GasCoin::type_();
+        let mut untyped_coin_type = coin_type.clone();
+        untyped_coin_type.type_params.clear();
+        let mut other_coin_type = coin_type.clone();
+        other_coin_type.type_params = vec![TypeTag::U64];
+
+        assert!(HistoryRetention::new(&HistoryRetentionConfig::default()).is_none());
+        for (config, retained) in [
+            (
+                HistoryRetentionConfig {
+                    owners: vec![owner],
+                    ..Default::default()
+                },
+                true,
+            ),
+            (
+                HistoryRetentionConfig {
+                    owners: vec![IotaAddress::random_for_testing_only()],
+                    ..Default::default()
+                },
+                false,
+            ),
+            (
+                HistoryRetentionConfig {
+                    packages: vec![IOTA_FRAMEWORK_PACKAGE_ID],
+                    ..Default::default()
+                },
+                true,
+            ),
+            (
+                HistoryRetentionConfig {
+                    move_types: vec![coin_type],
+                    ..Default::default()
+                },
+                true,
+            ),
+            (
+                HistoryRetentionConfig {
+                    move_types: vec![untyped_coin_type],
+                    ..Default::default()
+                },
+                true,
+            ),
+            (
+                HistoryRetentionConfig {
+                    move_types: vec![other_coin_type],
+                    ..Default::default()
+                },
+                false,
+            ),
+        ] {
+            let retention = HistoryRetention::new(&config).unwrap();
+            assert_eq!(retention.matches_object(&object), retained, "{config:?}");
+        }
+    }
 }
 
 #[cfg(test)]
@@ -1134,6 +1678,7 @@ mod pprof_tests {
             metrics,
             1,
             true,
+            None,
         )
         .await?;
         let guard = pprof::ProfilerGuardBuilder::default()
@@ -1173,6 +1718,7 @@ mod pprof_tests {
             metrics,
             1,
             true,
+            None,
         )
         .await?;
         if let Ok(()) = perpetual_db.objects.flush() {
@@ -14,8 +14,8 @@ use iota_types::{
     },
     crypto::{
         get_key_pair, get_key_pair_from_rng, AccountKeyPair, AuthorityKeyPair,
-        AuthorityPublicKeyBytes, AuthoritySignature, IotaKeyPair, KeypairTraits, PublicKey,
-        Signature, Signer, ZkLoginPublicIdentifier,
+        AuthorityPublicKeyBytes, AuthoritySignature, CompressedSignature, IotaKeyPair,
+        KeypairTraits, PublicKey, Signature, Signer, ZkLoginPublicIdentifier,
     },
     effects::{IDOperation, ObjectIn, ObjectOut, TransactionEffects, UnchangedSharedKind},
     execution_status::{
@@ -167,6 +167,8 @@ fn get_registry() -> Result<YamlRegistry> {
     tracer.trace_type::<PackageUpgradeError>(&samples)?;
     tracer.trace_type::<TransactionExpiration>(&samples)?;
     tracer.trace_type::<EndOfEpochTransactionKind>(&samples)?;
+    tracer.trace_type::<PublicKey>(&samples)?;
+    tracer.trace_type::<CompressedSignature>(&samples)?;
 
     tracer.trace_type::<IDOperation>(&samples)?;
     tracer.trace_type::<ObjectIn>(&samples)?;
@@ -110,6 +110,9 @@ pub struct SignatureVerifier {
     /// environment (prod/test) the code runs in.
     zk_login_params: ZkLoginParams,
 
+    /// Whether passkey signatures are accepted as members of a multisig.
+    accept_passkey_in_multisig: bool,
+
     queue: Mutex<CertBuffer>,
     pub metrics: Arc<SignatureVerifierMetrics>,
 }
@@ -135,6 +138,7 @@ impl SignatureVerifier {
         env: ZkLoginEnv,
         verify_legacy_zklogin_address: bool,
         accept_zklogin_in_multisig: bool,
+        accept_passkey_in_multisig: bool,
     ) -> Self {
         Self {
             committee,
@@ -162,6 +166,7 @@ impl SignatureVerifier {
                 verify_legacy_zklogin_address,
                 accept_zklogin_in_multisig,
             },
+            accept_passkey_in_multisig,
         }
     }
 
@@ -172,6 +177,7 @@ impl SignatureVerifier {
         zklogin_env: ZkLoginEnv,
         verify_legacy_zklogin_address: bool,
         accept_zklogin_in_multisig: bool,
+        accept_passkey_in_multisig: bool,
     ) -> Self {
         Self::new_with_batch_size(
             committee,
@@ -181,6 +187,7 @@ impl SignatureVerifier {
             zklogin_env,
             verify_legacy_zklogin_address,
             accept_zklogin_in_multisig,
+            accept_passkey_in_multisig,
         )
     }
 
@@ -368,6 +375,7 @@ impl SignatureVerifier {
                     self.zk_login_params.env,
                     self.zk_login_params.verify_legacy_zklogin_address,
                     self.zk_login_params.accept_zklogin_in_multisig,
+                    self.accept_passkey_in_multisig,
                 );
                 signed_tx.verify_message_signature(&verify_params)
             },
@@ -518,6 +526,7 @@ pub fn batch_verify_certificates(
         Default::default(),
         true,
         true,
+        true,
     );
     match batch_verify(committee, certs, &[]) {
         Ok(_) => vec![Ok(()); certs.len()],
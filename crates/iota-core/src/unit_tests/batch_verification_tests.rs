@@ -126,6 +126,7 @@ async fn test_async_verifier() {
         ZkLoginEnv::Test,
         true,
         true,
+        true,
     ));
 
     let tasks: Vec<_> = (0..32)
@@ -7,7 +7,7 @@ use std::{path::PathBuf, sync::Arc};
 use fastcrypto::{ed25519::Ed25519KeyPair, traits::KeyPair};
 use iota_config::{
     certificate_deny_config::CertificateDenyConfigBuilder,
-    transaction_deny_config::{TransactionDenyConfig, TransactionDenyConfigBuilder},
+    transaction_deny_config::{DenyListEntry, TransactionDenyConfig, TransactionDenyConfigBuilder},
 };
 use iota_swarm_config::{
     genesis_config::{AccountConfig, DEFAULT_GAS_AMOUNT},
@@ -20,8 +20,10 @@ use iota_types::{
     error::{IotaError, IotaResult, UserInputError},
     execution_status::{ExecutionFailureStatus, ExecutionStatus},
     messages_grpc::HandleTransactionResponse,
+    parse_iota_struct_tag, parse_iota_type_tag,
+    programmable_transaction_builder::ProgrammableTransactionBuilder,
     transaction::{
-        CallArg, CertifiedTransaction, Transaction, TransactionData, VerifiedCertificate,
+        CallArg, CertifiedTransaction, Command, Transaction, TransactionData, VerifiedCertificate,
         VerifiedTransaction, TEST_ONLY_GAS_UNIT_FOR_TRANSFER,
     },
     utils::{
@@ -446,6 +448,99 @@ async fn test_package_denied() {
     assert!(result.is_ok());
 }
 
+#[tokio::test]
+async fn test_deny_lists_updated_at_runtime() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("deny-lists.yaml");
+    let (network_config, state) = setup_test(
+        TransactionDenyConfigBuilder::new()
+            .deny_list_path(path.clone())
+            .build(),
+    )
+    .await;
+    let accounts = get_accounts_and_coins(&network_config, &state);
+
+    // Denying the sender applies to the next transaction and is persisted.
+    let entry = DenyListEntry::Address(accounts[0].0);
+    assert!(state
+        .update_transaction_deny_lists(|lists| lists.add(entry.clone()))
+        .unwrap());
+    assert_denied(&transfer_with_account(&accounts[0], &accounts[0], &state).await);
+    let persisted = TransactionDenyConfigBuilder::new()
+        .deny_list_path(path)
+        .build()
+        .with_persisted_deny_lists()
+        .unwrap();
+    assert!(persisted.get_address_deny_set().contains(&accounts[0].0));
+
+    // Allowing the sender again applies immediately too.
+    assert!(state
+        .update_transaction_deny_lists(|lists| lists.remove(&entry))
+        .unwrap());
+    assert!(transfer_with_account(&accounts[0], &accounts[0], &state)
+        .await
+        .is_ok());
+}
+
+#[tokio::test]
+async fn test_type_denied() {
+    let (network_config, state) = setup_test(
+        TransactionDenyConfigBuilder::new()
+            .add_denied_type(parse_iota_struct_tag("0x2::balance::Balance").unwrap())
+            .build(),
+    )
+    .await;
+    let accounts = get_accounts_and_coins(&network_config, &state);
+    let rgp = state.reference_gas_price_for_testing().unwrap();
+    let make_move_vec = |type_tag: &str, gas_payment_index: usize| {
+        let mut builder = ProgrammableTransactionBuilder::new();
+        builder.command(Command::MakeMoveVec(
+            Some(parse_iota_type_tag(type_tag).unwrap()),
+            vec![],
+        ));
+        let data = TransactionData::new_programmable(
+            accounts[0].0,
+            vec![accounts[0].2[gas_payment_index]],
+            builder.finish(),
+            TEST_ONLY_GAS_UNIT_FOR_TRANSFER * rgp,
+            rgp,
+        );
+        let epoch_store = state.epoch_store_for_testing();
+        let tx = to_sender_signed_transaction(data, &accounts[0].1);
+        let tx = epoch_store.verify_transaction(tx).unwrap();
+        let state = state.clone();
+        async move { state.handle_transaction(&epoch_store, tx).await }
+    };
+
+    // All instantiations of a denied type are denied, also when nested.
+    assert_denied(&make_move_vec("0x2::balance::Balance<0x2::iota::IOTA>", 0).await);
+    assert_denied(&make_move_vec("vector<0x2::balance::Balance<0x2::iota::IOTA>>", 1).await);
+    assert_denied(
+        &make_move_vec(
+            "0x1::option::Option<0x2::balance::Balance<0x2::iota::IOTA>>",
+            2,
+        )
+        .await,
+    );
+    assert!(make_move_vec("0x2::coin::Coin<0x2::iota::IOTA>", 3)
+        .await
+        .is_ok());
+}
+
+#[tokio::test]
+async fn test_input_object_type_denied() {
+    let (network_config, state) = setup_test(
+        TransactionDenyConfigBuilder::new()
+            .add_denied_type(parse_iota_struct_tag("0x2::coin::Coin<0x2::iota::IOTA>").unwrap())
+            .build(),
+    )
+    .await;
+    let accounts = get_accounts_and_coins(&network_config, &state);
+
+    // The transferred gas coin is an input object of the denied type.
+    assert_denied(&transfer_with_account(&accounts[0], &accounts[0], &state).await);
+}
+
 #[tokio::test]
 async fn test_certificate_deny() {
     let (network_config, state) = setup_test(TransactionDenyConfig::default()).await;
@@ -2,9 +2,9 @@
 // Modifications Copyright (c) 2024 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use std::{path::PathBuf, pin::Pin};
+use std::{ops::RangeInclusive, path::PathBuf, pin::Pin};
 
-use anyhow::Result;
+use anyhow::{ensure, Result};
 use futures::Future;
 use iota_metrics::spawn_monitored_task;
 use iota_types::{
@@ -29,6 +29,9 @@ pub struct IndexerExecutor<P> {
     pool_progress_sender: mpsc::Sender<(String, CheckpointSequenceNumber)>,
     pool_progress_receiver: mpsc::Receiver<(String, CheckpointSequenceNumber)>,
     metrics: DataIngestionMetrics,
+    /// The last checkpoint of a backfill. The executor exits once all the
+    /// worker pools have processed it.
+    backfill_end: Option<CheckpointSequenceNumber>,
 }
 
 impl<P: ProgressStore> IndexerExecutor<P> {
@@ -42,6 +45,7 @@ impl<P: ProgressStore> IndexerExecutor<P> {
             pool_progress_sender,
             pool_progress_receiver,
             metrics,
+            backfill_end: None,
         }
     }
 
@@ -67,6 +71,13 @@ impl<P: ProgressStore> IndexerExecutor<P> {
         reader_options: ReaderOptions,
         mut exit_receiver: oneshot::Receiver<()>,
     ) -> Result<ExecutorProgress> {
+        let mut reader_options = reader_options;
+        if let Some(backfill_end) = self.backfill_end {
+            // The local directory may be shared with a live pipeline, so a backfill
+            // must leave its files in place.
+            reader_options.upper_limit = Some(backfill_end);
+            reader_options.gc_checkpoint_files = false;
+        }
         let mut reader_checkpoint_number = self.progress_store.min_watermark()?;
         let (checkpoint_reader, mut checkpoint_recv, gc_sender, _exit_sender) =
             CheckpointReader::initialize(
@@ -85,13 +96,25 @@ impl<P: ProgressStore> IndexerExecutor<P> {
             tokio::select! {
                 _ = &mut exit_receiver => break,
                 Some((task_name, sequence_number)) = self.pool_progress_receiver.recv() => {
-                    self.progress_store.save(task_name.clone(), sequence_number).await?;
+                    // Persist all the pending updates at once, so the watermarks of
+                    // the tasks are updated atomically.
+                    let mut progress = ExecutorProgress::from([(task_name, sequence_number)]);
+                    while let Ok((task_name, sequence_number)) = self.pool_progress_receiver.try_recv() {
+                        let watermark = progress.entry(task_name).or_default();
+                        *watermark = (*watermark).max(sequence_number);
+                    }
+                    self.progress_store.save_many(progress.clone()).await?;
                     let seq_number = self.progress_store.min_watermark()?;
                     if seq_number > reader_checkpoint_number {
                         gc_sender.send(seq_number).await?;
                         reader_checkpoint_number = seq_number;
                     }
-                    self.metrics.data_ingestion_checkpoint.with_label_values(&[&task_name]).set(sequence_number as i64);
+                    for (task_name, sequence_number) in progress {
+                        self.metrics.data_ingestion_checkpoint.with_label_values(&[&task_name]).set(sequence_number as i64);
+                    }
+                    if self.backfill_end.is_some_and(|backfill_end| seq_number > backfill_end) {
+                        break;
+                    }
                 }
                 Some(checkpoint) = checkpoint_recv.recv() => {
                     for sender in &self.pool_senders {
@@ -104,6 +127,27 @@ impl<P: ProgressStore> IndexerExecutor<P> {
     }
 }
 
+impl IndexerExecutor<ShimProgressStore> {
+    /// Creates an executor that backfills the checkpoints of `range`, for
+    /// instance to re-run a worker after fixing a bug.
+    ///
+    /// The registered worker pools start from the beginning of the range with
+    /// a temporary watermark, so the progress of the live pipeline is neither
+    /// read nor updated. Processed checkpoint files are not removed from the
+    /// local directory, and [`IndexerExecutor::run`] returns once all worker
+    /// pools have processed the end of the range.
+    pub fn new_backfill(
+        range: RangeInclusive<CheckpointSequenceNumber>,
+        number_of_jobs: usize,
+        metrics: DataIngestionMetrics,
+    ) -> Result<Self> {
+        ensure!(!range.is_empty(), "Empty backfill range: {range:?}");
+        let mut executor = Self::new(ShimProgressStore(*range.start()), number_of_jobs, metrics);
+        executor.backfill_end = Some(*range.end());
+        Ok(executor)
+    }
+}
+
 pub async fn setup_single_workflow<W: Worker + 'static>(
     worker: W,
     remote_store_url: String,
@@ -18,6 +18,10 @@ use iota_types::{
     full_checkpoint_content::CheckpointData, messages_checkpoint::CheckpointSequenceNumber,
 };
 pub use metrics::DataIngestionMetrics;
+#[cfg(feature = "postgres")]
+pub use progress_store::PostgresProgressStore;
+#[cfg(feature = "rocksdb")]
+pub use progress_store::RocksDbProgressStore;
 pub use progress_store::{FileProgressStore, ProgressStore};
 pub use reader::ReaderOptions;
 pub use util::create_remote_store_client;
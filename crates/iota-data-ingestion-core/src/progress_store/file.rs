@@ -2,14 +2,14 @@
 // Modifications Copyright (c) 2024 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use std::path::PathBuf;
+use std::{fs, io::Write, path::PathBuf};
 
 use anyhow::Result;
 use async_trait::async_trait;
 use iota_types::messages_checkpoint::CheckpointSequenceNumber;
 use serde_json::{Number, Value};
 
-use crate::progress_store::ProgressStore;
+use crate::progress_store::{ExecutorProgress, ProgressStore};
 
 pub struct FileProgressStore {
     path: PathBuf,
@@ -19,12 +19,27 @@ impl FileProgressStore {
     pub fn new(path: PathBuf) -> Self {
         Self { path }
     }
+
+    /// Writes the content to a temporary file next to the progress file and
+    /// renames it over the progress file, so a crash never leaves a partially
+    /// written progress file behind.
+    fn write_atomically(&self, content: &Value) -> Result<()> {
+        let dir = match self.path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir.to_path_buf(),
+            _ => PathBuf::from("."),
+        };
+        let mut file = tempfile::NamedTempFile::new_in(dir)?;
+        file.write_all(serde_json::to_string_pretty(content)?.as_bytes())?;
+        file.as_file().sync_all()?;
+        file.persist(&self.path)?;
+        Ok(())
+    }
 }
 
 #[async_trait]
 impl ProgressStore for FileProgressStore {
     async fn load(&mut self, task_name: String) -> Result<CheckpointSequenceNumber> {
-        let content: Value = serde_json::from_slice(&std::fs::read(self.path.clone())?)?;
+        let content: Value = serde_json::from_slice(&fs::read(&self.path)?)?;
         Ok(content
             .get(&task_name)
             .and_then(|v| v.as_u64())
@@ -35,9 +50,15 @@ impl ProgressStore for FileProgressStore {
         task_name: String,
         checkpoint_number: CheckpointSequenceNumber,
     ) -> Result<()> {
-        let mut content: Value = serde_json::from_slice(&std::fs::read(self.path.clone())?)?;
-        content[task_name] = Value::Number(Number::from(checkpoint_number));
-        std::fs::write(self.path.clone(), serde_json::to_string_pretty(&content)?)?;
-        Ok(())
+        self.save_many(ExecutorProgress::from([(task_name, checkpoint_number)]))
+            .await
+    }
+
+    async fn save_many(&mut self, progress: ExecutorProgress) -> Result<()> {
+        let mut content: Value = serde_json::from_slice(&fs::read(&self.path)?)?;
+        for (task_name, checkpoint_number) in progress {
+            content[task_name] = Value::Number(Number::from(checkpoint_number));
+        }
+        self.write_atomically(&content)
     }
 }
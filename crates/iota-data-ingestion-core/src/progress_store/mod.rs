@@ -8,7 +8,15 @@ use anyhow::Result;
 use async_trait::async_trait;
 use iota_types::messages_checkpoint::CheckpointSequenceNumber;
 mod file;
+#[cfg(feature = "postgres")]
+mod postgres;
+#[cfg(feature = "rocksdb")]
+mod rocksdb;
 pub use file::FileProgressStore;
+#[cfg(feature = "postgres")]
+pub use postgres::PostgresProgressStore;
+#[cfg(feature = "rocksdb")]
+pub use rocksdb::RocksDbProgressStore;
 
 pub type ExecutorProgress = HashMap<String, CheckpointSequenceNumber>;
 
@@ -20,6 +28,15 @@ pub trait ProgressStore: Send {
         task_name: String,
         checkpoint_number: CheckpointSequenceNumber,
     ) -> Result<()>;
+    /// Saves the watermarks of several tasks at once. Stores overriding the
+    /// default implementation persist them atomically, so that a crash never
+    /// leaves only some of the watermarks updated.
+    async fn save_many(&mut self, progress: ExecutorProgress) -> Result<()> {
+        for (task_name, checkpoint_number) in progress {
+            self.save(task_name, checkpoint_number).await?;
+        }
+        Ok(())
+    }
 }
 
 pub struct ProgressStoreWrapper<P> {
@@ -46,6 +63,14 @@ impl<P: ProgressStore> ProgressStore for ProgressStoreWrapper<P> {
         self.pending_state.insert(task_name, checkpoint_number);
         Ok(())
     }
+
+    async fn save_many(&mut self, progress: ExecutorProgress) -> Result<()> {
+        self.progress_store.save_many(progress.clone()).await?;
+        // The pending state is only updated once the watermarks are persisted, so
+        // the min watermark never runs ahead of what is restored after a restart.
+        self.pending_state.extend(progress);
+        Ok(())
+    }
 }
 
 impl<P: ProgressStore> ProgressStoreWrapper<P> {
@@ -0,0 +1,191 @@
+// Copyright (c) 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{bail, Result};
+use async_trait::async_trait;
+use diesel::{
+    r2d2::{ConnectionManager, Pool},
+    sql_types::{BigInt, Text},
+    Connection, OptionalExtension, PgConnection, QueryableByName, RunQueryDsl,
+};
+use iota_types::messages_checkpoint::CheckpointSequenceNumber;
+
+use crate::progress_store::{ExecutorProgress, ProgressStore};
+
+type PgConnectionPool = Pool<ConnectionManager<PgConnection>>;
+
+#[derive(QueryableByName)]
+struct Watermark {
+    #[diesel(sql_type = BigInt)]
+    checkpoint_number: i64,
+}
+
+/// A progress store persisting the watermarks of the tasks in a Postgres
+/// table. The watermarks of several tasks are written in a single
+/// transaction.
+///
+/// Executors running in separate processes can share the table, as long as
+/// every task is run by a single executor.
+pub struct PostgresProgressStore {
+    pool: PgConnectionPool,
+    table_name: String,
+}
+
+impl PostgresProgressStore {
+    /// Connects to the database and creates the progress table if it does not
+    /// exist yet. The table name is interpolated into the queries, so it must
+    /// be a plain SQL identifier.
+    pub async fn new(database_url: &str, table_name: String) -> Result<Self> {
+        validate_table_name(&table_name)?;
+        let manager = ConnectionManager::<PgConnection>::new(database_url);
+        // Building the pool establishes the first connection, which blocks.
+        let pool = tokio::task::spawn_blocking(move || Pool::builder().max_size(1).build(manager))
+            .await??;
+        let store = Self { pool, table_name };
+        let query = format!(
+            "CREATE TABLE IF NOT EXISTS {} (
+                task_name TEXT PRIMARY KEY,
+                checkpoint_number BIGINT NOT NULL
+            )",
+            store.table_name
+        );
+        store
+            .run(move |conn| {
+                diesel::sql_query(query).execute(conn)?;
+                Ok(())
+            })
+            .await?;
+        Ok(store)
+    }
+
+    /// Runs the blocking database operation `f` on a connection of the pool.
+    async fn run<T, F>(&self, f: F) -> Result<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&mut PgConnection) -> Result<T> + Send + 'static,
+    {
+        let pool = self.pool.clone();
+        tokio::task::spawn_blocking(move || f(&mut pool.get()?)).await?
+    }
+}
+
+/// Accepts unquoted Postgres identifiers: ASCII letters, digits and
+/// underscores, not starting with a digit, of at most 63 bytes.
+fn validate_table_name(table_name: &str) -> Result<()> {
+    let valid = !table_name.is_empty()
+        && table_name.len() <= 63
+        && !table_name.starts_with(|c: char| c.is_ascii_digit())
+        && table_name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if !valid {
+        bail!("Invalid progress table name {table_name:?}, expected a plain SQL identifier");
+    }
+    Ok(())
+}
+
+#[async_trait]
+impl ProgressStore for PostgresProgressStore {
+    async fn load(&mut self, task_name: String) -> Result<CheckpointSequenceNumber> {
+        let query = format!(
+            "SELECT checkpoint_number FROM {} WHERE task_name = $1",
+            self.table_name
+        );
+        let watermark = self
+            .run(move |conn| {
+                Ok(diesel::sql_query(query)
+                    .bind::<Text, _>(task_name)
+                    .get_result::<Watermark>(conn)
+                    .optional()?)
+            })
+            .await?;
+        Ok(watermark
+            .map(|watermark| watermark.checkpoint_number as CheckpointSequenceNumber)
+            .unwrap_or_default())
+    }
+
+    async fn save(
+        &mut self,
+        task_name: String,
+        checkpoint_number: CheckpointSequenceNumber,
+    ) -> Result<()> {
+        self.save_many(ExecutorProgress::from([(task_name, checkpoint_number)]))
+            .await
+    }
+
+    async fn save_many(&mut self, progress: ExecutorProgress) -> Result<()> {
+        let query = format!(
+            "INSERT INTO {} (task_name, checkpoint_number) VALUES ($1, $2)
+            ON CONFLICT (task_name) DO UPDATE SET checkpoint_number = EXCLUDED.checkpoint_number",
+            self.table_name
+        );
+        self.run(move |conn| {
+            conn.transaction(|conn| {
+                for (task_name, checkpoint_number) in progress {
+                    diesel::sql_query(&query)
+                        .bind::<Text, _>(task_name)
+                        .bind::<BigInt, _>(checkpoint_number as i64)
+                        .execute(conn)?;
+                }
+                Ok::<_, diesel::result::Error>(())
+            })?;
+            Ok(())
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn table_name_must_be_an_identifier() {
+        validate_table_name("progress_store").unwrap();
+        validate_table_name("_progress2").unwrap();
+        validate_table_name("").unwrap_err();
+        validate_table_name("2progress").unwrap_err();
+        validate_table_name("progress; DROP TABLE users").unwrap_err();
+        validate_table_name("public.progress").unwrap_err();
+        validate_table_name(&"p".repeat(64)).unwrap_err();
+    }
+
+    /// Requires a running Postgres, e.g. the one used by the indexer tests.
+    #[tokio::test]
+    #[ignore]
+    async fn postgres_progress_store() {
+        let pg_host = std::env::var("POSTGRES_HOST").unwrap_or_else(|_| "localhost".into());
+        let pg_port = std::env::var("POSTGRES_PORT").unwrap_or_else(|_| "32770".into());
+        let pw = std::env::var("POSTGRES_PASSWORD").unwrap_or_else(|_| "postgrespw".into());
+        let database_url = format!("postgres://postgres:{pw}@{pg_host}:{pg_port}");
+        let table_name = format!("progress_store_test_{}", std::process::id());
+
+        let mut store = PostgresProgressStore::new(&database_url, table_name.clone())
+            .await
+            .unwrap();
+        assert_eq!(store.load("a".to_string()).await.unwrap(), 0);
+        store
+            .save_many(ExecutorProgress::from([
+                ("a".to_string(), 10),
+                ("b".to_string(), 4),
+            ]))
+            .await
+            .unwrap();
+        store.save("b".to_string(), 5).await.unwrap();
+
+        // The table is reused by a new store.
+        let mut store = PostgresProgressStore::new(&database_url, table_name.clone())
+            .await
+            .unwrap();
+        assert_eq!(store.load("a".to_string()).await.unwrap(), 10);
+        assert_eq!(store.load("b".to_string()).await.unwrap(), 5);
+
+        store
+            .run(move |conn| {
+                diesel::sql_query(format!("DROP TABLE {table_name}")).execute(conn)?;
+                Ok(())
+            })
+            .await
+            .unwrap();
+    }
+}
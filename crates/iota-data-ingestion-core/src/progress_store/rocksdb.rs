@@ -0,0 +1,69 @@
+// Copyright (c) 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::path::Path;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use iota_types::messages_checkpoint::CheckpointSequenceNumber;
+use typed_store::{
+    rocks::{DBMap, MetricConf},
+    traits::{TableSummary, TypedStoreDebug},
+    Map,
+};
+use typed_store_derive::DBMapUtils;
+
+use crate::progress_store::{ExecutorProgress, ProgressStore};
+
+#[derive(DBMapUtils)]
+struct ProgressTables {
+    /// The watermark of each task, keyed by task name.
+    watermarks: DBMap<String, CheckpointSequenceNumber>,
+}
+
+/// A progress store persisting the watermarks of the tasks in a RocksDB
+/// database. The watermarks of several tasks are written in a single batch.
+///
+/// The database can only be opened by a single process at a time, so it
+/// suits several pipelines run by the same executor.
+pub struct RocksDbProgressStore {
+    tables: ProgressTables,
+}
+
+impl RocksDbProgressStore {
+    pub fn new(path: &Path) -> Self {
+        Self {
+            tables: ProgressTables::open_tables_read_write(
+                path.to_path_buf(),
+                MetricConf::new("data_ingestion_progress"),
+                None,
+                None,
+            ),
+        }
+    }
+}
+
+#[async_trait]
+impl ProgressStore for RocksDbProgressStore {
+    async fn load(&mut self, task_name: String) -> Result<CheckpointSequenceNumber> {
+        Ok(self.tables.watermarks.get(&task_name)?.unwrap_or_default())
+    }
+
+    async fn save(
+        &mut self,
+        task_name: String,
+        checkpoint_number: CheckpointSequenceNumber,
+    ) -> Result<()> {
+        self.tables
+            .watermarks
+            .insert(&task_name, &checkpoint_number)?;
+        Ok(())
+    }
+
+    async fn save_many(&mut self, progress: ExecutorProgress) -> Result<()> {
+        let mut batch = self.tables.watermarks.batch();
+        batch.insert_batch(&self.tables.watermarks, progress)?;
+        batch.write()?;
+        Ok(())
+    }
+}
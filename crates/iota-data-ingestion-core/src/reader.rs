@@ -44,6 +44,12 @@ pub struct ReaderOptions {
     pub tick_interval_ms: u64,
     pub timeout_secs: u64,
     pub batch_size: usize,
+    /// The last checkpoint to read, if any. The reader stops once it has
+    /// forwarded it.
+    pub upper_limit: Option<CheckpointSequenceNumber>,
+    /// Whether processed checkpoint files are removed from the local
+    /// directory.
+    pub gc_checkpoint_files: bool,
 }
 
 impl Default for ReaderOptions {
@@ -52,6 +58,8 @@ impl Default for ReaderOptions {
             tick_interval_ms: 100,
             timeout_secs: 5,
             batch_size: 100,
+            upper_limit: None,
+            gc_checkpoint_files: true,
         }
     }
 }
@@ -83,6 +91,10 @@ impl CheckpointReader {
 
     fn exceeds_capacity(&self, checkpoint_number: CheckpointSequenceNumber) -> bool {
         (MAX_CHECKPOINTS_IN_PROGRESS as u64 + self.last_pruned_watermark) <= checkpoint_number
+            || self
+                .options
+                .upper_limit
+                .is_some_and(|upper_limit| checkpoint_number > upper_limit)
     }
 
     async fn remote_fetch_checkpoint_internal(
@@ -119,6 +131,10 @@ impl CheckpointReader {
     fn start_remote_fetcher(&mut self) -> mpsc::Receiver<Result<CheckpointData>> {
         let batch_size = self.options.batch_size;
         let start_checkpoint = self.current_checkpoint_number;
+        let end_checkpoint = self
+            .options
+            .upper_limit
+            .map_or(u64::MAX, |upper_limit| upper_limit.saturating_add(1));
         let (sender, receiver) = mpsc::channel(batch_size);
         let url = self
             .remote_store_url
@@ -132,7 +148,7 @@ impl CheckpointReader {
         .expect("failed to create remote store client");
 
         spawn_monitored_task!(async move {
-            let mut checkpoint_stream = (start_checkpoint..u64::MAX)
+            let mut checkpoint_stream = (start_checkpoint..end_checkpoint)
                 .map(|checkpoint_number| Self::remote_fetch_checkpoint(&store, checkpoint_number))
                 .pipe(futures::stream::iter)
                 .buffered(batch_size);
@@ -213,8 +229,11 @@ impl CheckpointReader {
 
     /// Cleans the local directory by removing all processed checkpoint files.
     fn gc_processed_files(&mut self, watermark: CheckpointSequenceNumber) -> Result<()> {
-        info!("cleaning processed files, watermark is {}", watermark);
         self.last_pruned_watermark = watermark;
+        if !self.options.gc_checkpoint_files {
+            return Ok(());
+        }
+        info!("cleaning processed files, watermark is {}", watermark);
         for entry in fs::read_dir(self.path.clone())? {
             let entry = entry?;
             let filename = entry.file_name();
@@ -23,8 +23,9 @@ use tempfile::NamedTempFile;
 use tokio::sync::oneshot;
 
 use crate::{
-    progress_store::ExecutorProgress, DataIngestionMetrics, FileProgressStore, IndexerExecutor,
-    ReaderOptions, Worker, WorkerPool,
+    progress_store::{ExecutorProgress, ProgressStoreWrapper},
+    DataIngestionMetrics, FileProgressStore, IndexerExecutor, ProgressStore, ReaderOptions, Worker,
+    WorkerPool,
 };
 
 async fn add_worker_pool<W: Worker + 'static>(
@@ -108,6 +109,96 @@ async fn basic_flow() {
     assert_eq!(result.unwrap().get("test"), Some(&20));
 }
 
+#[tokio::test]
+async fn backfill_range() {
+    let mut executor =
+        IndexerExecutor::new_backfill(5..=9, 1, DataIngestionMetrics::new(&Registry::new()))
+            .unwrap();
+    let worker_pool = WorkerPool::new(TestWorker, "test".to_string(), 5);
+    executor.register(worker_pool).await.unwrap();
+    let path = temp_dir();
+    for checkpoint_number in 0..20 {
+        let bytes = mock_checkpoint_data_bytes(checkpoint_number);
+        std::fs::write(path.join(format!("{}.chk", checkpoint_number)), bytes).unwrap();
+    }
+    let options = ReaderOptions {
+        tick_interval_ms: 10,
+        batch_size: 1,
+        ..Default::default()
+    };
+    let (_exit_sender, exit_receiver) = oneshot::channel();
+    // The executor exits by itself once the range has been processed.
+    let result = tokio::time::timeout(
+        Duration::from_secs(10),
+        executor.run(path.clone(), None, vec![], options, exit_receiver),
+    )
+    .await
+    .unwrap()
+    .unwrap();
+    assert_eq!(result.get("test"), Some(&10));
+    // The checkpoint files are left for the live pipeline.
+    assert_eq!(std::fs::read_dir(&path).unwrap().count(), 20);
+}
+
+#[test]
+fn backfill_empty_range() {
+    #[allow(clippy::reversed_empty_ranges)]
+    let result =
+        IndexerExecutor::new_backfill(9..=5, 1, DataIngestionMetrics::new(&Registry::new()));
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn file_progress_store_save_many() {
+    let progress_file = NamedTempFile::new().unwrap();
+    std::fs::write(progress_file.path(), "{}").unwrap();
+    let mut store = FileProgressStore::new(progress_file.path().to_path_buf());
+    store
+        .save_many(ExecutorProgress::from([
+            ("a".to_string(), 5),
+            ("b".to_string(), 7),
+        ]))
+        .await
+        .unwrap();
+    store.save("a".to_string(), 6).await.unwrap();
+
+    let mut store = FileProgressStore::new(progress_file.path().to_path_buf());
+    assert_eq!(store.load("a".to_string()).await.unwrap(), 6);
+    assert_eq!(store.load("b".to_string()).await.unwrap(), 7);
+    assert_eq!(store.load("c".to_string()).await.unwrap(), 0);
+}
+
+#[cfg(feature = "rocksdb")]
+#[tokio::test]
+async fn rocksdb_progress_store() {
+    use crate::RocksDbProgressStore;
+
+    let mut store = RocksDbProgressStore::new(&temp_dir());
+    assert_eq!(store.load("a".to_string()).await.unwrap(), 0);
+    store
+        .save_many(ExecutorProgress::from([
+            ("a".to_string(), 10),
+            ("b".to_string(), 4),
+        ]))
+        .await
+        .unwrap();
+    store.save("b".to_string(), 5).await.unwrap();
+    assert_eq!(store.load("a".to_string()).await.unwrap(), 10);
+    assert_eq!(store.load("b".to_string()).await.unwrap(), 5);
+
+    let mut wrapper = ProgressStoreWrapper::new(RocksDbProgressStore::new(&temp_dir()));
+    wrapper.load("a".to_string()).await.unwrap();
+    wrapper.load("b".to_string()).await.unwrap();
+    wrapper
+        .save_many(ExecutorProgress::from([
+            ("a".to_string(), 10),
+            ("b".to_string(), 4),
+        ]))
+        .await
+        .unwrap();
+    assert_eq!(wrapper.min_watermark().unwrap(), 4);
+}
+
 fn temp_dir() -> std::path::PathBuf {
     tempfile::tempdir()
         .expect("Failed to open temporary directory")
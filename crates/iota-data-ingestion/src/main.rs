@@ -2,18 +2,41 @@
 // Modifications Copyright (c) 2024 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use std::{env, path::PathBuf};
+use std::path::PathBuf;
 
-use anyhow::Result;
+use anyhow::{ensure, Result};
+use clap::Parser;
 use iota_data_ingestion::{
     ArchivalConfig, ArchivalWorker, BlobTaskConfig, BlobWorker, DynamoDBProgressStore,
     KVStoreTaskConfig, KVStoreWorker,
 };
-use iota_data_ingestion_core::{DataIngestionMetrics, IndexerExecutor, ReaderOptions, WorkerPool};
+use iota_data_ingestion_core::{
+    DataIngestionMetrics, IndexerExecutor, ProgressStore, ReaderOptions, WorkerPool,
+};
+use iota_types::messages_checkpoint::CheckpointSequenceNumber;
 use prometheus::Registry;
 use serde::{Deserialize, Serialize};
 use tokio::{signal, sync::oneshot};
 
+#[derive(Parser, Clone, Debug)]
+#[clap(name = "IOTA Data Ingestion", rename_all = "kebab-case")]
+struct Args {
+    /// Path to the configuration yaml file.
+    config: PathBuf,
+    /// Backfills the checkpoints from this one to `--backfill-end`, instead
+    /// of running the live pipeline. The progress of the live pipeline is
+    /// left untouched.
+    #[clap(long, requires = "backfill_end")]
+    backfill_start: Option<CheckpointSequenceNumber>,
+    /// The last checkpoint to backfill.
+    #[clap(long, requires = "backfill_start")]
+    backfill_end: Option<CheckpointSequenceNumber>,
+    /// The names of the tasks to backfill. All tasks of the configuration are
+    /// backfilled if none is given.
+    #[clap(long = "task", requires = "backfill_start")]
+    tasks: Vec<String>,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug)]
 #[serde(rename_all = "lowercase")]
 enum Task {
@@ -86,35 +109,11 @@ fn setup_env(exit_sender: oneshot::Sender<()>) {
     });
 }
 
-#[tokio::main]
-async fn main() -> Result<()> {
-    let (exit_sender, exit_receiver) = oneshot::channel();
-    setup_env(exit_sender);
-
-    let args: Vec<String> = env::args().collect();
-    assert_eq!(args.len(), 2, "configuration yaml file is required");
-    let config: IndexerConfig = serde_yaml::from_str(&std::fs::read_to_string(&args[1])?)?;
-
-    // setup metrics
-    let _guard = telemetry_subscribers::TelemetryConfig::new()
-        .with_env()
-        .init();
-    let registry_service = iota_metrics::start_prometheus_server(
-        format!("{}:{}", config.metrics_host, config.metrics_port).parse()?,
-    );
-    let registry: Registry = registry_service.default_registry();
-    iota_metrics::init_metrics(&registry);
-    let metrics = DataIngestionMetrics::new(&registry);
-
-    let progress_store = DynamoDBProgressStore::new(
-        &config.progress_store.aws_access_key_id,
-        &config.progress_store.aws_secret_access_key,
-        config.progress_store.aws_region,
-        config.progress_store.table_name,
-    )
-    .await;
-    let mut executor = IndexerExecutor::new(progress_store, config.tasks.len(), metrics);
-    for task_config in config.tasks {
+async fn register_tasks<P: ProgressStore>(
+    executor: &mut IndexerExecutor<P>,
+    tasks: Vec<TaskConfig>,
+) -> Result<()> {
+    for task_config in tasks {
         match task_config.task {
             Task::Archival(archival_config) => {
                 let worker_pool = WorkerPool::new(
@@ -142,10 +141,66 @@ async fn main() -> Result<()> {
             }
         };
     }
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let (exit_sender, exit_receiver) = oneshot::channel();
+    setup_env(exit_sender);
+
+    let args = Args::parse();
+    let config: IndexerConfig = serde_yaml::from_str(&std::fs::read_to_string(&args.config)?)?;
+
+    let _guard = telemetry_subscribers::TelemetryConfig::new()
+        .with_env()
+        .init();
     let reader_options = ReaderOptions {
         batch_size: config.remote_read_batch_size,
         ..Default::default()
     };
+
+    if let (Some(start), Some(end)) = (args.backfill_start, args.backfill_end) {
+        let tasks: Vec<_> = config
+            .tasks
+            .into_iter()
+            .filter(|task| args.tasks.is_empty() || args.tasks.contains(&task.name))
+            .collect();
+        ensure!(!tasks.is_empty(), "No task to backfill");
+        // The metrics are not served, so a backfill can run next to the live
+        // pipeline.
+        let metrics = DataIngestionMetrics::new(&Registry::new());
+        let mut executor = IndexerExecutor::new_backfill(start..=end, tasks.len(), metrics)?;
+        register_tasks(&mut executor, tasks).await?;
+        executor
+            .run(
+                config.path,
+                config.remote_store_url,
+                config.remote_store_options,
+                reader_options,
+                exit_receiver,
+            )
+            .await?;
+        return Ok(());
+    }
+
+    // setup metrics
+    let registry_service = iota_metrics::start_prometheus_server(
+        format!("{}:{}", config.metrics_host, config.metrics_port).parse()?,
+    );
+    let registry: Registry = registry_service.default_registry();
+    iota_metrics::init_metrics(&registry);
+    let metrics = DataIngestionMetrics::new(&registry);
+
+    let progress_store = DynamoDBProgressStore::new(
+        &config.progress_store.aws_access_key_id,
+        &config.progress_store.aws_secret_access_key,
+        config.progress_store.aws_region,
+        config.progress_store.table_name,
+    )
+    .await;
+    let mut executor = IndexerExecutor::new(progress_store, config.tasks.len(), metrics);
+    register_tasks(&mut executor, config.tasks).await?;
     executor
         .run(
             config.path,
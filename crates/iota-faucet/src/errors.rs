@@ -41,6 +41,12 @@ pub enum FaucetError {
     #[error("Coin amounts sent are incorrect:`{0}`")]
     CoinAmountTransferredIncorrect(String),
 
+    #[error("Daily request quota exceeded for {0}. Please try again later.")]
+    QuotaExceeded(String),
+
+    #[error("Cannot determine the client IP from the `{0}` header")]
+    InvalidClientIp(String),
+
     #[error("Internal error: {0}")]
     Internal(String),
 }
@@ -9,13 +9,20 @@ use uuid::Uuid;
 
 use crate::FaucetError;
 
+mod quota;
 mod simple_faucet;
 mod write_ahead_log;
-use std::{net::Ipv4Addr, path::PathBuf};
+use std::{
+    net::{IpAddr, Ipv4Addr},
+    path::PathBuf,
+};
 
 use clap::Parser;
 
-pub use self::simple_faucet::SimpleFaucet;
+pub use self::{
+    quota::{forwarded_client_ip, QuotaCharge, QuotaExceeded, QuotaKey, Quotas},
+    simple_faucet::SimpleFaucet,
+};
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct FaucetReceipt {
@@ -120,6 +127,59 @@ pub struct FaucetConfig {
 
     #[clap(long, action = clap::ArgAction::Set, default_value_t = false)]
     pub batch_enabled: bool,
+
+    /// Interval in seconds between rebalancings of the coin pools. Zero
+    /// disables rebalancing.
+    #[clap(long, default_value_t = 300)]
+    pub rebalance_interval: u64,
+
+    /// Number of failed WAL retries after which a coin is retired, i.e. no
+    /// longer retried until the retirement period has passed.
+    #[clap(long, default_value_t = 5)]
+    pub max_wal_retries: u64,
+
+    /// Time in seconds a coin stays retired before it is retried again.
+    #[clap(long, default_value_t = 3600)]
+    pub coin_retirement_period: u64,
+
+    /// Maximum number of requests served to a single client IP per UTC day.
+    /// Unlimited if not set.
+    #[clap(long)]
+    pub max_requests_per_ip_per_day: Option<u64>,
+
+    /// Maximum number of requests served to a single recipient address per
+    /// UTC day. Unlimited if not set.
+    #[clap(long)]
+    pub max_requests_per_recipient_per_day: Option<u64>,
+
+    /// Client IPs exempt from the daily quotas, e.g. CI runners.
+    #[clap(long, value_delimiter = ',')]
+    pub quota_allowlist: Vec<IpAddr>,
+
+    /// Header holding the client IP, e.g. `x-forwarded-for` when running
+    /// behind a proxy. Requests without a valid address in the header are
+    /// rejected. If not set, the address of the connection is used.
+    #[clap(long)]
+    pub client_ip_header: Option<String>,
+
+    /// Number of trusted proxies appending to the `client_ip_header` in front
+    /// of the faucet. The address appended by the outermost of them is used,
+    /// i.e. the rightmost address with a single proxy.
+    #[clap(long, default_value_t = 1)]
+    pub trusted_proxy_hops: usize,
+
+    /// Path of the store holding the usage of the daily quotas. Defaults to
+    /// the write ahead log path with a `quotas` extension.
+    #[clap(long)]
+    pub quota_store: Option<PathBuf>,
+}
+
+impl FaucetConfig {
+    pub fn quota_store_path(&self) -> PathBuf {
+        self.quota_store
+            .clone()
+            .unwrap_or_else(|| self.write_ahead_log.with_extension("quotas"))
+    }
 }
 
 impl Default for FaucetConfig {
@@ -138,6 +198,15 @@ impl Default for FaucetConfig {
             batch_request_size: 500,
             ttl_expiration: 300,
             batch_enabled: false,
+            rebalance_interval: 300,
+            max_wal_retries: 5,
+            coin_retirement_period: 3600,
+            max_requests_per_ip_per_day: None,
+            max_requests_per_recipient_per_day: None,
+            quota_allowlist: vec![],
+            client_ip_header: None,
+            trusted_proxy_hops: 1,
+            quota_store: None,
         }
     }
 }
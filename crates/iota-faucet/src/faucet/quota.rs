@@ -0,0 +1,497 @@
+// Copyright (c) 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    collections::HashSet,
+    fmt,
+    net::IpAddr,
+    path::Path,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use iota_types::base_types::IotaAddress;
+use prometheus::Registry;
+use serde::{Deserialize, Serialize};
+use tracing::info;
+use typed_store::{
+    rocks::DBMap,
+    traits::{TableSummary, TypedStoreDebug},
+    Map, TypedStoreError,
+};
+use typed_store_derive::DBMapUtils;
+
+use crate::{metrics::QuotaMetrics, FaucetConfig};
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// Persistent count of the requests served to each client IP and recipient
+/// address during the current UTC day. Entries of previous days are pruned
+/// once a new day starts.
+#[derive(DBMapUtils, Clone)]
+pub struct QuotaStore {
+    pub usage: DBMap<QuotaKey, QuotaUsage>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum QuotaKey {
+    Ip(IpAddr),
+    Recipient(IotaAddress),
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
+pub struct QuotaUsage {
+    /// Days since the unix epoch, in UTC.
+    pub day: u64,
+    pub requests: u64,
+}
+
+impl QuotaStore {
+    pub(crate) fn open(path: &Path) -> Self {
+        Self::open_tables_read_write(
+            path.to_path_buf(),
+            typed_store::rocks::MetricConf::new("faucet_quota_store"),
+            None,
+            None,
+        )
+    }
+}
+
+/// A request rejected because it exceeds one of the daily quotas.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct QuotaExceeded {
+    pub key: QuotaKey,
+    /// Time until the quotas are reset, at the start of the next UTC day.
+    pub retry_after: Duration,
+}
+
+/// The usage counted for a request. Given back with [`Quotas::refund`] if the
+/// request is not served.
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct QuotaCharge {
+    day: u64,
+    keys: Vec<QuotaKey>,
+}
+
+/// Daily request quotas per client IP and per recipient address. Requests from
+/// allowlisted IPs are exempt from both quotas.
+///
+/// A request is counted before it is served, so that concurrent requests
+/// cannot exceed the quotas, and refunded if serving it fails.
+pub struct Quotas {
+    store: QuotaStore,
+    max_requests_per_ip: Option<u64>,
+    max_requests_per_recipient: Option<u64>,
+    allowlist: HashSet<IpAddr>,
+    /// Serializes the check and update of the usage, and holds the day the
+    /// store was last pruned.
+    pruned_day: parking_lot::Mutex<u64>,
+    metrics: QuotaMetrics,
+}
+
+impl Quotas {
+    pub fn new(
+        store_path: &Path,
+        config: &FaucetConfig,
+        prometheus_registry: &Registry,
+    ) -> Result<Self, TypedStoreError> {
+        let quotas = Self {
+            store: QuotaStore::open(store_path),
+            max_requests_per_ip: config.max_requests_per_ip_per_day,
+            max_requests_per_recipient: config.max_requests_per_recipient_per_day,
+            allowlist: config.quota_allowlist.iter().copied().collect(),
+            pruned_day: parking_lot::Mutex::new(0),
+            metrics: QuotaMetrics::new(prometheus_registry),
+        };
+        quotas.prune(current_day(SystemTime::now()))?;
+        Ok(quotas)
+    }
+
+    /// Counts a request of `ip` for `recipient` against the quotas, unless it
+    /// would exceed one of them.
+    pub fn check_and_record(
+        &self,
+        ip: Option<IpAddr>,
+        recipient: IotaAddress,
+    ) -> Result<Result<QuotaCharge, QuotaExceeded>, TypedStoreError> {
+        self.check_and_record_at(ip, recipient, SystemTime::now())
+    }
+
+    fn check_and_record_at(
+        &self,
+        ip: Option<IpAddr>,
+        recipient: IotaAddress,
+        now: SystemTime,
+    ) -> Result<Result<QuotaCharge, QuotaExceeded>, TypedStoreError> {
+        if ip.is_some_and(|ip| self.allowlist.contains(&ip)) {
+            self.metrics.total_allowlisted_requests.inc();
+            return Ok(Ok(QuotaCharge::default()));
+        }
+
+        let day = current_day(now);
+        let mut pruned_day = self.pruned_day.lock();
+        if *pruned_day != day {
+            self.prune_locked(&mut pruned_day, day)?;
+        }
+
+        let limits = ip
+            .zip(self.max_requests_per_ip)
+            .map(|(ip, max)| (QuotaKey::Ip(ip), max))
+            .into_iter()
+            .chain(
+                self.max_requests_per_recipient
+                    .map(|max| (QuotaKey::Recipient(recipient), max)),
+            )
+            .collect::<Vec<_>>();
+
+        let mut usages = Vec::with_capacity(limits.len());
+        for (key, max) in limits {
+            let requests = self.requests_on(&key, day)?;
+            if requests >= max {
+                self.metrics
+                    .total_requests_over_quota
+                    .with_label_values(&[key.kind()])
+                    .inc();
+                let retry_after = (day + 1) * SECONDS_PER_DAY - unix_seconds(now);
+                return Ok(Err(QuotaExceeded {
+                    key,
+                    retry_after: Duration::from_secs(retry_after),
+                }));
+            }
+            usages.push((key, requests));
+        }
+
+        let mut charge = QuotaCharge {
+            day,
+            keys: Vec::with_capacity(usages.len()),
+        };
+        for (key, requests) in usages {
+            if requests == 0 {
+                self.metrics.tracked_keys(&key).inc();
+            }
+            self.store.usage.insert(
+                &key,
+                &QuotaUsage {
+                    day,
+                    requests: requests + 1,
+                },
+            )?;
+            charge.keys.push(key);
+        }
+        Ok(Ok(charge))
+    }
+
+    /// Gives back the usage counted for a request which was not served. Usage
+    /// of a previous day is already reset, so it is not refunded.
+    pub fn refund(&self, charge: QuotaCharge) -> Result<(), TypedStoreError> {
+        let _guard = self.pruned_day.lock();
+        for key in charge.keys {
+            let requests = self.requests_on(&key, charge.day)?;
+            if requests > 1 {
+                self.store.usage.insert(
+                    &key,
+                    &QuotaUsage {
+                        day: charge.day,
+                        requests: requests - 1,
+                    },
+                )?;
+            } else if requests == 1 {
+                self.store.usage.remove(&key)?;
+                self.metrics.tracked_keys(&key).dec();
+            }
+        }
+        Ok(())
+    }
+
+    fn requests_on(&self, key: &QuotaKey, day: u64) -> Result<u64, TypedStoreError> {
+        Ok(self
+            .store
+            .usage
+            .get(key)?
+            .filter(|usage| usage.day == day)
+            .map_or(0, |usage| usage.requests))
+    }
+
+    fn prune(&self, day: u64) -> Result<(), TypedStoreError> {
+        self.prune_locked(&mut self.pruned_day.lock(), day)
+    }
+
+    /// Removes the usage of days before `day`, and resets the metrics of the
+    /// tracked keys to the remaining ones.
+    fn prune_locked(&self, pruned_day: &mut u64, day: u64) -> Result<(), TypedStoreError> {
+        let mut expired = vec![];
+        let (mut ips, mut recipients) = (0, 0);
+        for item in self.store.usage.safe_iter() {
+            let (key, usage) = item?;
+            if usage.day < day {
+                expired.push(key);
+            } else if matches!(key, QuotaKey::Ip(_)) {
+                ips += 1;
+            } else {
+                recipients += 1;
+            }
+        }
+        if !expired.is_empty() {
+            info!("Pruning {} expired quota entries", expired.len());
+            self.store.usage.multi_remove(expired)?;
+        }
+        self.metrics.tracked_ips.set(ips);
+        self.metrics.tracked_recipients.set(recipients);
+        *pruned_day = day;
+        Ok(())
+    }
+}
+
+impl fmt::Display for QuotaKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QuotaKey::Ip(ip) => write!(f, "IP {ip}"),
+            QuotaKey::Recipient(recipient) => write!(f, "recipient {recipient}"),
+        }
+    }
+}
+
+impl QuotaKey {
+    fn kind(&self) -> &'static str {
+        match self {
+            QuotaKey::Ip(_) => "ip",
+            QuotaKey::Recipient(_) => "recipient",
+        }
+    }
+}
+
+impl QuotaMetrics {
+    fn tracked_keys(&self, key: &QuotaKey) -> &prometheus::IntGauge {
+        match key {
+            QuotaKey::Ip(_) => &self.tracked_ips,
+            QuotaKey::Recipient(_) => &self.tracked_recipients,
+        }
+    }
+}
+
+/// Returns the client IP from the value of a header like `x-forwarded-for`,
+/// to which every proxy appends the address it received the request from.
+/// Entries added by the client itself cannot be trusted, so the entry
+/// appended by the outermost of the `trusted_hops` proxies in front of the
+/// faucet is used, counting from the right.
+pub fn forwarded_client_ip(value: &str, trusted_hops: usize) -> Option<IpAddr> {
+    let entries = value.split(',').map(str::trim).collect::<Vec<_>>();
+    if trusted_hops == 0 || trusted_hops > entries.len() {
+        return None;
+    }
+    entries[entries.len() - trusted_hops].parse().ok()
+}
+
+fn unix_seconds(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .expect("System time is before the unix epoch")
+        .as_secs()
+}
+
+fn current_day(now: SystemTime) -> u64 {
+    unix_seconds(now) / SECONDS_PER_DAY
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::Ipv4Addr;
+
+    use super::*;
+
+    const IP: IpAddr = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1));
+    const OTHER_IP: IpAddr = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 2));
+
+    fn config() -> FaucetConfig {
+        FaucetConfig {
+            max_requests_per_ip_per_day: Some(2),
+            max_requests_per_recipient_per_day: Some(3),
+            ..Default::default()
+        }
+    }
+
+    fn at(day: u64, seconds: u64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(day * SECONDS_PER_DAY + seconds)
+    }
+
+    #[test]
+    fn ip_quota() {
+        let tmp = tempfile::tempdir().unwrap();
+        let quotas = Quotas::new(&tmp.path().join("quotas"), &config(), &Registry::new()).unwrap();
+        let now = at(100, 0);
+
+        for _ in 0..2 {
+            let recipient = IotaAddress::random_for_testing_only();
+            quotas
+                .check_and_record_at(Some(IP), recipient, now)
+                .unwrap()
+                .unwrap();
+        }
+        let recipient = IotaAddress::random_for_testing_only();
+        let exceeded = quotas
+            .check_and_record_at(Some(IP), recipient, at(100, 100))
+            .unwrap()
+            .unwrap_err();
+        assert_eq!(exceeded.key, QuotaKey::Ip(IP));
+        assert_eq!(
+            exceeded.retry_after,
+            Duration::from_secs(SECONDS_PER_DAY - 100)
+        );
+
+        // Other IPs and the next day are not affected.
+        assert!(quotas
+            .check_and_record_at(Some(OTHER_IP), recipient, now)
+            .unwrap()
+            .is_ok());
+        assert!(quotas
+            .check_and_record_at(Some(IP), recipient, at(101, 0))
+            .unwrap()
+            .is_ok());
+    }
+
+    #[test]
+    fn recipient_quota() {
+        let tmp = tempfile::tempdir().unwrap();
+        let quotas = Quotas::new(&tmp.path().join("quotas"), &config(), &Registry::new()).unwrap();
+        let now = at(100, 0);
+        let recipient = IotaAddress::random_for_testing_only();
+
+        // Rotating IPs does not help to exceed the recipient quota.
+        for i in 0..3 {
+            let ip = IpAddr::V4(Ipv4Addr::new(10, 0, 1, i));
+            quotas
+                .check_and_record_at(Some(ip), recipient, now)
+                .unwrap()
+                .unwrap();
+        }
+        let exceeded = quotas
+            .check_and_record_at(Some(OTHER_IP), recipient, now)
+            .unwrap()
+            .unwrap_err();
+        assert_eq!(exceeded.key, QuotaKey::Recipient(recipient));
+    }
+
+    #[test]
+    fn refunded_requests_are_not_counted() {
+        let tmp = tempfile::tempdir().unwrap();
+        let quotas = Quotas::new(&tmp.path().join("quotas"), &config(), &Registry::new()).unwrap();
+        let now = at(100, 0);
+        let recipient = IotaAddress::random_for_testing_only();
+
+        // Failed requests do not use up the quota.
+        for _ in 0..5 {
+            let charge = quotas
+                .check_and_record_at(Some(IP), recipient, now)
+                .unwrap()
+                .unwrap();
+            quotas.refund(charge).unwrap();
+        }
+        assert_eq!(quotas.metrics.tracked_ips.get(), 0);
+        assert_eq!(quotas.metrics.tracked_recipients.get(), 0);
+
+        for _ in 0..2 {
+            quotas
+                .check_and_record_at(Some(IP), recipient, now)
+                .unwrap()
+                .unwrap();
+        }
+        let charge = quotas
+            .check_and_record_at(Some(OTHER_IP), recipient, now)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            quotas
+                .requests_on(&QuotaKey::Recipient(recipient), 100)
+                .unwrap(),
+            3
+        );
+        quotas.refund(charge).unwrap();
+        assert_eq!(
+            quotas
+                .requests_on(&QuotaKey::Recipient(recipient), 100)
+                .unwrap(),
+            2
+        );
+        assert_eq!(quotas.requests_on(&QuotaKey::Ip(IP), 100).unwrap(), 2);
+        assert_eq!(quotas.metrics.tracked_ips.get(), 1);
+    }
+
+    #[test]
+    fn client_ip_from_forwarded_header() {
+        let value = "1.1.1.1, 10.0.0.1,10.0.0.2";
+        assert_eq!(
+            forwarded_client_ip(value, 1),
+            Some("10.0.0.2".parse().unwrap())
+        );
+        assert_eq!(
+            forwarded_client_ip(value, 3),
+            Some("1.1.1.1".parse().unwrap())
+        );
+        // Fails closed if there are fewer entries than trusted proxies.
+        assert_eq!(forwarded_client_ip(value, 4), None);
+        assert_eq!(forwarded_client_ip(value, 0), None);
+        assert_eq!(forwarded_client_ip("unknown", 1), None);
+        assert_eq!(forwarded_client_ip("", 1), None);
+    }
+
+    #[test]
+    fn allowlisted_ip() {
+        let tmp = tempfile::tempdir().unwrap();
+        let config = FaucetConfig {
+            quota_allowlist: vec![IP],
+            ..config()
+        };
+        let quotas = Quotas::new(&tmp.path().join("quotas"), &config, &Registry::new()).unwrap();
+        let recipient = IotaAddress::random_for_testing_only();
+        for _ in 0..10 {
+            assert!(quotas
+                .check_and_record_at(Some(IP), recipient, at(100, 0))
+                .unwrap()
+                .is_ok());
+        }
+    }
+
+    #[test]
+    fn usage_survives_restart() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("quotas");
+        let recipient = IotaAddress::random_for_testing_only();
+        // The quotas are checked against the current day, as the store is pruned
+        // on startup.
+        let now = SystemTime::now();
+        {
+            let quotas = Quotas::new(&path, &config(), &Registry::new()).unwrap();
+            for _ in 0..2 {
+                quotas
+                    .check_and_record_at(Some(IP), recipient, now)
+                    .unwrap()
+                    .unwrap();
+            }
+        }
+
+        let quotas = Quotas::new(&path, &config(), &Registry::new()).unwrap();
+        assert_eq!(quotas.metrics.tracked_ips.get(), 1);
+        assert_eq!(quotas.metrics.tracked_recipients.get(), 1);
+        assert!(quotas
+            .check_and_record_at(Some(IP), recipient, now)
+            .unwrap()
+            .is_err());
+    }
+
+    #[test]
+    fn expired_usage_is_pruned() {
+        let tmp = tempfile::tempdir().unwrap();
+        let quotas = Quotas::new(&tmp.path().join("quotas"), &config(), &Registry::new()).unwrap();
+        let recipient = IotaAddress::random_for_testing_only();
+        quotas
+            .check_and_record_at(Some(IP), recipient, at(100, 0))
+            .unwrap()
+            .unwrap();
+        assert_eq!(quotas.metrics.tracked_ips.get(), 1);
+
+        quotas
+            .check_and_record_at(Some(OTHER_IP), recipient, at(101, 0))
+            .unwrap()
+            .unwrap();
+        assert_eq!(quotas.metrics.tracked_ips.get(), 1);
+        assert!(quotas.store.usage.get(&QuotaKey::Ip(IP)).unwrap().is_none());
+    }
+}
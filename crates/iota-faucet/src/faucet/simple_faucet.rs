@@ -2,10 +2,9 @@
 // Modifications Copyright (c) 2024 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-#[cfg(test)]
-use std::collections::HashSet;
 use std::{
-    collections::HashMap,
+    cmp::Reverse,
+    collections::{HashMap, HashSet},
     fmt,
     path::Path,
     sync::{Arc, Weak},
@@ -14,8 +13,8 @@ use std::{
 use async_recursion::async_recursion;
 use async_trait::async_trait;
 use iota_json_rpc_types::{
-    IotaObjectDataOptions, IotaTransactionBlockEffectsAPI, IotaTransactionBlockResponse,
-    IotaTransactionBlockResponseOptions, OwnedObjectRef,
+    IotaExecutionStatus, IotaObjectDataOptions, IotaTransactionBlockEffectsAPI,
+    IotaTransactionBlockResponse, IotaTransactionBlockResponseOptions, OwnedObjectRef,
 };
 use iota_keys::keystore::AccountKeystore;
 use iota_metrics::spawn_monitored_task;
@@ -36,7 +35,7 @@ use tokio::{
         mpsc::{self, Receiver, Sender},
         oneshot, Mutex,
     },
-    time::{timeout, Duration},
+    time::{timeout, Duration, Instant},
 };
 use tracing::{error, info, warn};
 use ttl_cache::TtlCache;
@@ -63,6 +62,12 @@ pub struct SimpleFaucet {
     task_id_cache: Mutex<TtlCache<Uuid, BatchSendStatus>>,
     ttl_expiration: u64,
     coin_amount: u64,
+    num_coins: usize,
+    max_wal_retries: u64,
+    coin_retirement_period: Duration,
+    /// Coins failing repeatedly in the WAL retry loop, with the time they were
+    /// retired at.
+    retired_coins: parking_lot::Mutex<HashMap<ObjectID, Instant>>,
     /// Shuts down the batch transfer task. Used only in testing.
     #[allow(unused)]
     batch_transfer_shutdown: parking_lot::Mutex<Option<oneshot::Sender<()>>>,
@@ -96,6 +101,12 @@ const DEFAULT_GAS_COMPUTATION_BUCKET: u64 = 10_000_000;
 const LOCK_TIMEOUT: Duration = Duration::from_secs(10);
 const RECV_TIMEOUT: Duration = Duration::from_secs(5);
 const BATCH_TIMEOUT: Duration = Duration::from_secs(10);
+/// Maximum number of coins created by splitting a coin in one rebalancing.
+const MAX_SPLIT_COINS: usize = 256;
+/// Maximum number of coins merged into another coin in one rebalancing.
+const MAX_MERGE_COINS: usize = 255;
+/// Maximum number of coins taken out of a pool in one rebalancing.
+const REBALANCE_BATCH_SIZE: usize = MAX_MERGE_COINS + 1;
 
 impl SimpleFaucet {
     pub async fn new(
@@ -123,7 +134,8 @@ impl SimpleFaucet {
         let wal = WriteAheadLog::open(wal_path);
         let mut pending = vec![];
 
-        let (producer, consumer) = mpsc::channel(coins.len());
+        // Leave room for the coins split off when rebalancing the pool.
+        let (producer, consumer) = mpsc::channel(coins.len().max(config.num_coins));
         let (batch_producer, batch_consumer) = mpsc::channel(coins.len());
 
         let (sender, mut receiver) = mpsc::channel::<(Uuid, IotaAddress, Vec<u64>)>(
@@ -194,6 +206,10 @@ impl SimpleFaucet {
             task_id_cache: TtlCache::new(config.max_request_per_second as usize * 60 * 10).into(),
             ttl_expiration: config.ttl_expiration,
             coin_amount: config.amount,
+            num_coins: config.num_coins,
+            max_wal_retries: config.max_wal_retries,
+            coin_retirement_period: Duration::from_secs(config.coin_retirement_period),
+            retired_coins: Default::default(),
             batch_transfer_shutdown: parking_lot::Mutex::new(Some(batch_transfer_shutdown)),
         };
 
@@ -379,20 +395,53 @@ impl SimpleFaucet {
         }))
     }
 
-    /// Clear the WAL list in the faucet
+    /// Clear the WAL list in the faucet. Coins which failed `max_wal_retries`
+    /// times are retired, and only retried again after the retirement period.
     pub async fn retry_wal_coins(&self) -> Result<(), FaucetError> {
         let mut wal = self.wal.lock().await;
         let mut pending = vec![];
+        let mut reinstated = vec![];
 
+        let mut retired_coins = self.retired_coins.lock();
+        let mut logged_coins = HashSet::new();
         for item in wal.log.safe_iter() {
             // Safe unwrap as we are the only ones that ever add to the WAL.
             let (coin_id, entry) = item.unwrap();
+            logged_coins.insert(coin_id);
             let uuid = Uuid::from_bytes(entry.uuid);
-            if !entry.in_flight {
-                pending.push((uuid, entry.recipient, coin_id, entry.tx));
+            if entry.in_flight {
+                continue;
             }
+            if let Some(retired_at) = retired_coins.get(&coin_id) {
+                if retired_at.elapsed() < self.coin_retirement_period {
+                    continue;
+                }
+                info!(?uuid, ?coin_id, "Reinstating retired coin");
+                retired_coins.remove(&coin_id);
+                reinstated.push(coin_id);
+            } else if self.max_wal_retries > 0 && entry.retry_count >= self.max_wal_retries {
+                warn!(
+                    ?uuid,
+                    ?coin_id,
+                    retry_count = entry.retry_count,
+                    "Retiring coin failing in the WAL retry loop"
+                );
+                retired_coins.insert(coin_id, Instant::now());
+                continue;
+            }
+            pending.push((uuid, entry.recipient, coin_id, entry.tx));
+        }
+        // Forget coins whose transactions landed in the meantime.
+        retired_coins.retain(|coin_id, _| logged_coins.contains(coin_id));
+        self.metrics
+            .current_retired_coins
+            .set(retired_coins.len() as i64);
+        drop(retired_coins);
+
+        for coin_id in reinstated {
+            wal.reset_retry_count(coin_id)
+                .map_err(FaucetError::internal)?;
         }
-
         for (_, _, coin_id, _) in &pending {
             wal.increment_retry_count(*coin_id)
                 .map_err(FaucetError::internal)?;
@@ -412,6 +461,291 @@ impl SimpleFaucet {
         Ok(())
     }
 
+    /// Rebalances the coin pools. Coins too small to serve a request are merged
+    /// into the largest coin of their pool, and the largest coin of the
+    /// request pool is split while the pool holds fewer than `num_coins`
+    /// coins. At most `REBALANCE_BATCH_SIZE` coins are taken out of a pool at
+    /// once, coins in use by requests or in the WAL are left alone.
+    pub async fn rebalance_coin_pools(&self) -> Result<(), FaucetError> {
+        let gas_cost = self.get_gas_cost().await?;
+        self.rebalance_coin_pool(false, self.num_coins, gas_cost)
+            .await;
+        // Batch requests are served from few large coins, so the batch pool is not
+        // split.
+        self.rebalance_coin_pool(true, 0, gas_cost).await;
+        Ok(())
+    }
+
+    async fn rebalance_coin_pool(&self, for_batch: bool, pool_size: usize, gas_cost: u64) {
+        let uuid = Uuid::new_v4();
+        let (coin_ids, remaining) = self.drain_gas_pool(for_batch, REBALANCE_BATCH_SIZE).await;
+        let coins = futures::future::join_all(
+            coin_ids
+                .iter()
+                .map(|coin_id| self.get_gas_coin_and_check_faucet_owner(*coin_id)),
+        )
+        .await;
+
+        let min_value = self.coin_amount * self.num_coins as u64 + gas_cost;
+        let mut healthy = vec![];
+        let mut dust = vec![];
+        let mut unknown = vec![];
+        for (coin_id, coin) in coin_ids.into_iter().zip(coins) {
+            match coin {
+                Ok(Some(coin)) if coin.value() >= min_value => {
+                    healthy.push((coin_id, coin.value()))
+                }
+                Ok(Some(coin)) => dust.push((coin_id, coin.value())),
+                Ok(None) => {
+                    warn!(?uuid, ?coin_id, "Invalid, removing from pool");
+                    self.metrics.total_discarded_coins.inc();
+                }
+                Err(err) => {
+                    error!(?uuid, ?coin_id, "Fullnode read error: {err:?}");
+                    self.metrics.total_rebalance_read_errors.inc();
+                    unknown.push(coin_id);
+                }
+            }
+        }
+        healthy.sort_by_key(|(_, value)| Reverse(*value));
+        dust.sort_by_key(|(_, value)| Reverse(*value));
+
+        // Coins left in the pool are assumed to be healthy.
+        let split_count = pool_size.saturating_sub(healthy.len() + unknown.len() + remaining);
+        // The largest coin pays for the rebalancing, and receives the merged coins.
+        let target = if !healthy.is_empty() && (!dust.is_empty() || split_count > 0) {
+            Some(healthy.remove(0))
+        } else if healthy.is_empty() && dust.len() > 1 && dust[0].1 >= gas_cost {
+            Some(dust.remove(0))
+        } else {
+            None
+        };
+        let Some((target, mut target_value)) = target else {
+            self.return_coins_to_pool(
+                healthy
+                    .into_iter()
+                    .chain(dust)
+                    .map(|(coin_id, _)| coin_id)
+                    .chain(unknown),
+                for_batch,
+                uuid,
+            )
+            .await;
+            return;
+        };
+        // All other coins go back to the pool right away. Dust exceeding the
+        // limit of a merge is merged in a later rebalancing.
+        let excess_dust = dust.split_off(dust.len().min(MAX_MERGE_COINS));
+        self.return_coins_to_pool(
+            healthy
+                .into_iter()
+                .chain(excess_dust)
+                .map(|(coin_id, _)| coin_id)
+                .chain(unknown),
+            for_batch,
+            uuid,
+        )
+        .await;
+
+        let mut rebalanced = vec![target];
+        if !dust.is_empty() {
+            let dust = dust
+                .into_iter()
+                .map(|(coin_id, _)| coin_id)
+                .collect::<Vec<_>>();
+            match self.merge_coins(target, &dust, gas_cost).await {
+                Ok(value) => {
+                    info!(?uuid, coin_id = ?target, merged = dust.len(), "Merged coins");
+                    self.metrics.total_coins_merged.inc_by(dust.len() as u64);
+                    target_value = value;
+                }
+                Err(err) => {
+                    error!(?uuid, coin_id = ?target, "Failed to merge coins: {err}");
+                    self.metrics.total_rebalance_failures.inc();
+                    rebalanced.extend(dust);
+                }
+            }
+        }
+
+        // Split the target in coins of equal value, keeping a share for the target
+        // itself.
+        let available = target_value.saturating_sub(gas_cost);
+        let split_count = split_count
+            .min(MAX_SPLIT_COINS)
+            .min((available / min_value).saturating_sub(1) as usize);
+        if split_count > 0 {
+            let value = available / (split_count as u64 + 1);
+            match self.split_coin(target, split_count, value, gas_cost).await {
+                Ok(coins) => {
+                    info!(?uuid, coin_id = ?target, split = coins.len(), value, "Split coin");
+                    self.metrics.total_coins_split.inc_by(coins.len() as u64);
+                    rebalanced.extend(coins);
+                }
+                Err(err) => {
+                    error!(?uuid, coin_id = ?target, "Failed to split coin: {err}");
+                    self.metrics.total_rebalance_failures.inc();
+                }
+            }
+        }
+
+        // Coins which turn out to be unusable are discarded when they are pulled
+        // from the pool.
+        self.return_coins_to_pool(rebalanced, for_batch, uuid).await;
+    }
+
+    /// Takes up to `limit` coins out of a pool, without checking whether they
+    /// are valid. Returns the coins and the number of coins left in the pool.
+    async fn drain_gas_pool(&self, for_batch: bool, limit: usize) -> (Vec<ObjectID>, usize) {
+        let (mut consumer, producer) = if for_batch {
+            (self.batch_consumer.lock().await, &self.batch_producer)
+        } else {
+            (self.consumer.lock().await, &self.producer)
+        };
+        let mut coins = vec![];
+        while coins.len() < limit {
+            let Ok(coin_id) = consumer.try_recv() else {
+                break;
+            };
+            coins.push(coin_id);
+        }
+        self.metrics.total_available_coins.sub(coins.len() as i64);
+        drop(consumer);
+        let producer = producer.lock().await;
+        let remaining = producer.max_capacity() - producer.capacity();
+        (coins, remaining)
+    }
+
+    async fn return_coins_to_pool(
+        &self,
+        coins: impl IntoIterator<Item = ObjectID>,
+        for_batch: bool,
+        uuid: Uuid,
+    ) {
+        let producer = if for_batch {
+            self.batch_producer.lock().await
+        } else {
+            self.producer.lock().await
+        };
+        for coin_id in coins {
+            match producer.try_send(coin_id) {
+                Ok(()) => self.metrics.total_available_coins.inc(),
+                Err(err) => {
+                    error!(?uuid, ?coin_id, "Failed to return coin to the pool: {err}");
+                    self.metrics.total_discarded_coins.inc();
+                }
+            }
+        }
+    }
+
+    /// Merges `coins` into `target`, returning the new balance of `target`.
+    async fn merge_coins(
+        &self,
+        target: ObjectID,
+        coins: &[ObjectID],
+        gas_cost: u64,
+    ) -> Result<u64, FaucetError> {
+        let client = self
+            .wallet
+            .get_client()
+            .await
+            .map_err(|e| FaucetError::Wallet(e.to_string()))?;
+        let input_coins = std::iter::once(target)
+            .chain(coins.iter().copied())
+            .collect();
+        let tx_data = client
+            .transaction_builder()
+            .pay_all_iota(
+                self.active_address,
+                input_coins,
+                self.active_address,
+                gas_cost,
+            )
+            .await
+            .map_err(FaucetError::internal)?;
+        self.execute_rebalance_txn(tx_data).await?;
+        let coin = self
+            .get_gas_coin_and_check_faucet_owner(target)
+            .await
+            .map_err(|e| FaucetError::FullnodeReadingError(e.to_string()))?
+            .ok_or_else(|| FaucetError::InvalidGasCoin(target.to_string()))?;
+        Ok(coin.value())
+    }
+
+    /// Splits `count` coins of `value` off `coin`, returning the new coins.
+    async fn split_coin(
+        &self,
+        coin: ObjectID,
+        count: usize,
+        value: u64,
+        gas_cost: u64,
+    ) -> Result<Vec<ObjectID>, FaucetError> {
+        let gas_payment = self
+            .wallet
+            .get_object_ref(coin)
+            .await
+            .map_err(|e| FaucetError::Wallet(e.to_string()))?;
+        let gas_price = self.get_gas_price().await?;
+        let pt = {
+            let mut builder = ProgrammableTransactionBuilder::new();
+            builder
+                .pay_iota(vec![self.active_address; count], vec![value; count])
+                .map_err(FaucetError::internal)?;
+            builder.finish()
+        };
+        let tx_data = TransactionData::new_programmable(
+            self.active_address,
+            vec![gas_payment],
+            pt,
+            gas_cost,
+            gas_price,
+        );
+        let response = self.execute_rebalance_txn(tx_data).await?;
+        Ok(response
+            .effects
+            .map(|effects| {
+                effects
+                    .created()
+                    .iter()
+                    .map(|created| created.reference.object_id)
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+
+    async fn execute_rebalance_txn(
+        &self,
+        tx_data: TransactionData,
+    ) -> Result<IotaTransactionBlockResponse, FaucetError> {
+        let signature = self
+            .wallet
+            .config
+            .keystore
+            .sign_secure(&self.active_address, &tx_data, Intent::iota_transaction())
+            .map_err(FaucetError::internal)?;
+        let tx = Transaction::from_data(tx_data, vec![signature]);
+        let client = self
+            .wallet
+            .get_client()
+            .await
+            .map_err(|e| FaucetError::Wallet(e.to_string()))?;
+        let response = client
+            .quorum_driver_api()
+            .execute_transaction_block(
+                tx,
+                IotaTransactionBlockResponseOptions::new().with_effects(),
+                Some(ExecuteTransactionRequestType::WaitForLocalExecution),
+            )
+            .await
+            .map_err(|e| FaucetError::Transfer(e.to_string()))?;
+        match response.effects.as_ref().map(|effects| effects.status()) {
+            Some(IotaExecutionStatus::Success) => Ok(response),
+            status => Err(FaucetError::Transfer(format!(
+                "rebalancing transaction {} failed with status {status:?}",
+                response.digest
+            ))),
+        }
+    }
+
     /// Sign an already created transaction (in `tx_data`) and keep trying to
     /// execute it until fullnode returns a definite response or a timeout
     /// is hit.
@@ -1530,6 +1864,125 @@ mod tests {
         assert_eq!(discarded_coins, original_discarded);
     }
 
+    #[tokio::test]
+    async fn test_retire_failing_wal_coins() {
+        let test_cluster = TestClusterBuilder::new().build().await;
+        let context = test_cluster.wallet;
+        let tmp = tempfile::tempdir().unwrap();
+        let prom_registry = Registry::new();
+        let config = FaucetConfig {
+            max_wal_retries: 2,
+            coin_retirement_period: 0,
+            ..Default::default()
+        };
+        let faucet = SimpleFaucet::new(
+            context,
+            &prom_registry,
+            &tmp.path().join("faucet.wal"),
+            config,
+        )
+        .await
+        .unwrap();
+
+        let recipient = IotaAddress::random_for_testing_only();
+        let faucet_address = faucet.active_address;
+        let uuid = Uuid::new_v4();
+        let GasCoinResponse::ValidGasCoin(coin_id) =
+            faucet.prepare_gas_coin(100, uuid, false).await
+        else {
+            panic!("prepare_gas_coin did not give a valid coin.")
+        };
+        let tx_data = faucet
+            .build_pay_iota_txn(coin_id, faucet_address, recipient, &[100], 200_000_000)
+            .await
+            .unwrap();
+
+        // Pretend the transaction failed to execute twice already.
+        let mut wal = faucet.wal.lock().await;
+        wal.reserve(uuid, coin_id, recipient, tx_data).unwrap();
+        wal.set_in_flight(coin_id, false).unwrap();
+        wal.increment_retry_count(coin_id).unwrap();
+        wal.increment_retry_count(coin_id).unwrap();
+        drop(wal);
+
+        // The coin is retired instead of retried.
+        faucet.retry_wal_coins().await.unwrap();
+        assert_eq!(faucet.metrics.current_retired_coins.get(), 1);
+        let wal = faucet.wal.lock().await;
+        assert_eq!(wal.reclaim(coin_id).unwrap().unwrap().retry_count, 2);
+        drop(wal);
+
+        // After the retirement period it is retried again, and the WAL is cleared.
+        faucet.retry_wal_coins().await.unwrap();
+        assert_eq!(faucet.metrics.current_retired_coins.get(), 0);
+        assert!(faucet.wal.lock().await.log.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_rebalance_coin_pools() {
+        telemetry_subscribers::init_for_testing();
+        let test_cluster = TestClusterBuilder::new().build().await;
+        let address = test_cluster.get_address_0();
+        let mut context = test_cluster.wallet;
+        let gases = get_current_gases(address, &mut context).await;
+
+        // Split out coins which are too small to serve a request together with
+        // the gas costs.
+        let config = FaucetConfig::default();
+        let tiny_value = (config.num_coins as u64 * config.amount) + 1;
+        IotaClientCommands::SplitCoin {
+            coin_id: *gases[0].id(),
+            amounts: Some(vec![tiny_value, tiny_value]),
+            gas_budget: 50000000,
+            gas: None,
+            count: None,
+            serialize_unsigned_transaction: false,
+            serialize_signed_transaction: false,
+        }
+        .execute(&mut context)
+        .await
+        .unwrap();
+        let healthy_coins = gases.len();
+
+        let tmp = tempfile::tempdir().unwrap();
+        let prom_registry = Registry::new();
+        let config = FaucetConfig {
+            num_coins: healthy_coins + 3,
+            ..config
+        };
+        let faucet = SimpleFaucet::new(
+            context,
+            &prom_registry,
+            &tmp.path().join("faucet.wal"),
+            config,
+        )
+        .await
+        .unwrap();
+        faucet.shutdown_batch_send_task();
+        assert_eq!(
+            faucet.metrics.total_available_coins.get() as usize,
+            healthy_coins + 2
+        );
+
+        faucet.rebalance_coin_pools().await.unwrap();
+        assert_eq!(faucet.metrics.total_coins_merged.get(), 2);
+        assert_eq!(faucet.metrics.total_coins_split.get(), 3);
+        assert_eq!(faucet.metrics.total_rebalance_failures.get(), 0);
+        assert_eq!(
+            faucet.metrics.total_available_coins.get() as usize,
+            healthy_coins + 3
+        );
+
+        // All coins of the pool can serve a request.
+        let faucet: &mut SimpleFaucet = &mut Arc::try_unwrap(faucet).unwrap();
+        let candidates = faucet.drain_gas_queue(healthy_coins + 3).await;
+        let gases = get_current_gases(address, faucet.wallet_mut()).await;
+        let gas_cost = faucet.get_gas_cost().await.unwrap();
+        for gas in gases.iter().filter(|gas| candidates.contains(gas.id())) {
+            assert!(gas.value() >= faucet.coin_amount + gas_cost);
+        }
+    }
+
     #[tokio::test]
     async fn test_discard_smaller_amount_gas() {
         telemetry_subscribers::init_for_testing();
@@ -114,6 +114,14 @@ impl WriteAheadLog {
         Ok(())
     }
 
+    pub(crate) fn reset_retry_count(&mut self, coin: ObjectID) -> Result<(), TypedStoreError> {
+        if let Some(mut entry) = self.log.get(&coin)? {
+            entry.retry_count = 0;
+            self.log.insert(&coin, &entry)?;
+        }
+        Ok(())
+    }
+
     pub(crate) fn set_in_flight(
         &mut self,
         coin: ObjectID,
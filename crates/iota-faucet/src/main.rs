@@ -12,21 +12,23 @@ use std::{
 
 use axum::{
     error_handling::HandleErrorLayer,
-    extract::Path,
+    extract::{ConnectInfo, Path},
     http::StatusCode,
-    response::IntoResponse,
+    response::{IntoResponse, Response},
     routing::{get, post},
     BoxError, Extension, Json, Router,
 };
 use clap::Parser;
-use http::Method;
+use http::{header, HeaderMap, Method};
 use iota_config::{iota_config_dir, IOTA_CLIENT_CONFIG};
 use iota_faucet::{
-    BatchFaucetResponse, BatchStatusFaucetResponse, Faucet, FaucetConfig, FaucetError,
-    FaucetRequest, FaucetResponse, RequestMetricsLayer, SimpleFaucet,
+    forwarded_client_ip, BatchFaucetResponse, BatchStatusFaucetResponse, Faucet, FaucetConfig,
+    FaucetError, FaucetRequest, FaucetResponse, QuotaCharge, Quotas, RequestMetricsLayer,
+    SimpleFaucet,
 };
 use iota_metrics::spawn_monitored_task;
 use iota_sdk::wallet_context::WalletContext;
+use iota_types::base_types::IotaAddress;
 use tower::{limit::RateLimitLayer, ServiceBuilder};
 use tower_http::cors::{Any, CorsLayer};
 use tracing::{info, warn};
@@ -36,6 +38,7 @@ const CONCURRENCY_LIMIT: usize = 30;
 
 struct AppState<F = Arc<SimpleFaucet>> {
     faucet: F,
+    quotas: Quotas,
     config: FaucetConfig,
 }
 
@@ -63,6 +66,7 @@ async fn main() -> Result<(), anyhow::Error> {
         wallet_client_timeout_secs,
         ref write_ahead_log,
         wal_retry_interval,
+        rebalance_interval,
         ..
     } = config;
 
@@ -81,6 +85,7 @@ async fn main() -> Result<(), anyhow::Error> {
         )
         .await
         .unwrap(),
+        quotas: Quotas::new(&config.quota_store_path(), &config, &prometheus_registry)?,
         config,
     });
 
@@ -111,6 +116,19 @@ async fn main() -> Result<(), anyhow::Error> {
                 .into_inner(),
         );
 
+    if rebalance_interval > 0 {
+        let faucet = app_state.faucet.clone();
+        spawn_monitored_task!(async move {
+            info!("Starting task to rebalance the coin pools.");
+            loop {
+                tokio::time::sleep(Duration::from_secs(rebalance_interval)).await;
+                if let Err(err) = faucet.rebalance_coin_pools().await {
+                    warn!("Failed to rebalance the coin pools: {err}");
+                }
+            }
+        });
+    }
+
     spawn_monitored_task!(async move {
         info!("Starting task to clear WAL.");
         loop {
@@ -124,7 +142,11 @@ async fn main() -> Result<(), anyhow::Error> {
     let addr = SocketAddr::new(IpAddr::V4(host_ip), port);
     info!("listening on {}", addr);
     let listener = tokio::net::TcpListener::bind(addr).await?;
-    axum::serve(listener, app.into_make_service()).await?;
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .await?;
     Ok(())
 }
 
@@ -136,8 +158,10 @@ async fn health() -> &'static str {
 /// handler for batch_request_gas requests
 async fn batch_request_gas(
     Extension(state): Extension<Arc<AppState>>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
     Json(payload): Json<FaucetRequest>,
-) -> impl IntoResponse {
+) -> Response {
     let id = Uuid::new_v4();
     // ID for traceability
     info!(uuid = ?id, "Got new gas request.");
@@ -148,19 +172,31 @@ async fn batch_request_gas(
             Json(BatchFaucetResponse::from(FaucetError::Internal(
                 "Input Error.".to_string(),
             ))),
-        );
+        )
+            .into_response();
+    };
+
+    let charge = match check_quotas(&state, addr, &headers, request.recipient) {
+        Ok(charge) => charge,
+        Err((status, headers, err)) => {
+            return (status, headers, Json(BatchFaucetResponse::from(err))).into_response();
+        }
     };
 
     if state.config.batch_enabled {
         let result = spawn_monitored_task!(async move {
-            state
+            let result = state
                 .faucet
                 .batch_send(
                     id,
                     request.recipient,
                     &vec![state.config.amount; state.config.num_coins],
                 )
-                .await
+                .await;
+            if result.is_err() {
+                refund_quotas(&state, charge);
+            }
+            result
         })
         .await
         .unwrap();
@@ -168,7 +204,7 @@ async fn batch_request_gas(
         match result {
             Ok(v) => {
                 info!(uuid =?id, "Request is successfully served");
-                (StatusCode::ACCEPTED, Json(BatchFaucetResponse::from(v)))
+                (StatusCode::ACCEPTED, Json(BatchFaucetResponse::from(v))).into_response()
             }
             Err(v) => {
                 warn!(uuid =?id, "Failed to request gas: {:?}", v);
@@ -176,6 +212,7 @@ async fn batch_request_gas(
                     StatusCode::INTERNAL_SERVER_ERROR,
                     Json(BatchFaucetResponse::from(v)),
                 )
+                    .into_response()
             }
         }
     } else {
@@ -183,14 +220,18 @@ async fn batch_request_gas(
         // enough
         info!(uuid = ?id, "Falling back to v1 implementation");
         let result = spawn_monitored_task!(async move {
-            state
+            let result = state
                 .faucet
                 .send(
                     id,
                     request.recipient,
                     &vec![state.config.amount; state.config.num_coins],
                 )
-                .await
+                .await;
+            if result.is_err() {
+                refund_quotas(&state, charge);
+            }
+            result
         })
         .await
         .unwrap();
@@ -198,7 +239,7 @@ async fn batch_request_gas(
         match result {
             Ok(_) => {
                 info!(uuid =?id, "Request is successfully served");
-                (StatusCode::ACCEPTED, Json(BatchFaucetResponse::from(id)))
+                (StatusCode::ACCEPTED, Json(BatchFaucetResponse::from(id))).into_response()
             }
             Err(v) => {
                 warn!(uuid =?id, "Failed to request gas: {:?}", v);
@@ -206,6 +247,7 @@ async fn batch_request_gas(
                     StatusCode::INTERNAL_SERVER_ERROR,
                     Json(BatchFaucetResponse::from(v)),
                 )
+                    .into_response()
             }
         }
     }
@@ -242,24 +284,36 @@ async fn request_status(
 /// handler for all the request_gas requests
 async fn request_gas(
     Extension(state): Extension<Arc<AppState>>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
     Json(payload): Json<FaucetRequest>,
-) -> impl IntoResponse {
+) -> Response {
     // ID for traceability
     let id = Uuid::new_v4();
     info!(uuid = ?id, "Got new gas request.");
     let result = match payload {
         FaucetRequest::FixedAmountRequest(requests) => {
+            let charge = match check_quotas(&state, addr, &headers, requests.recipient) {
+                Ok(charge) => charge,
+                Err((status, headers, err)) => {
+                    return (status, headers, Json(FaucetResponse::from(err))).into_response();
+                }
+            };
             // We spawn a tokio task for this such that connection drop will not interrupt
             // it and impact the recycling of coins
             spawn_monitored_task!(async move {
-                state
+                let result = state
                     .faucet
                     .send(
                         id,
                         requests.recipient,
                         &vec![state.config.amount; state.config.num_coins],
                     )
-                    .await
+                    .await;
+                if result.is_err() {
+                    refund_quotas(&state, charge);
+                }
+                result
             })
             .await
             .unwrap()
@@ -270,13 +324,14 @@ async fn request_gas(
                 Json(FaucetResponse::from(FaucetError::Internal(
                     "Input Error.".to_string(),
                 ))),
-            );
+            )
+                .into_response();
         }
     };
     match result {
         Ok(v) => {
             info!(uuid =?id, "Request is successfully served");
-            (StatusCode::CREATED, Json(FaucetResponse::from(v)))
+            (StatusCode::CREATED, Json(FaucetResponse::from(v))).into_response()
         }
         Err(v) => {
             warn!(uuid =?id, "Failed to request gas: {:?}", v);
@@ -284,10 +339,64 @@ async fn request_gas(
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(FaucetResponse::from(v)),
             )
+                .into_response()
         }
     }
 }
 
+/// Counts a request against the daily quotas. If the request is rejected,
+/// returns the status, headers and error to respond with. Requests without a
+/// valid client IP in the configured header are rejected, as they could
+/// otherwise evade the IP quota.
+fn check_quotas(
+    state: &AppState,
+    addr: SocketAddr,
+    headers: &HeaderMap,
+    recipient: IotaAddress,
+) -> Result<QuotaCharge, (StatusCode, HeaderMap, FaucetError)> {
+    let ip = match &state.config.client_ip_header {
+        Some(name) => headers
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| forwarded_client_ip(value, state.config.trusted_proxy_hops))
+            .ok_or_else(|| {
+                warn!(header = %name, "Request without a valid client IP");
+                (
+                    StatusCode::BAD_REQUEST,
+                    HeaderMap::new(),
+                    FaucetError::InvalidClientIp(name.clone()),
+                )
+            })?,
+        None => addr.ip(),
+    };
+
+    match state.quotas.check_and_record(Some(ip), recipient) {
+        Ok(Ok(charge)) => Ok(charge),
+        Ok(Err(exceeded)) => {
+            warn!(key = %exceeded.key, "Request exceeds the daily quota");
+            let mut headers = HeaderMap::new();
+            headers.insert(header::RETRY_AFTER, exceeded.retry_after.as_secs().into());
+            Err((
+                StatusCode::TOO_MANY_REQUESTS,
+                headers,
+                FaucetError::QuotaExceeded(exceeded.key.to_string()),
+            ))
+        }
+        Err(err) => Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            HeaderMap::new(),
+            FaucetError::Internal(err.to_string()),
+        )),
+    }
+}
+
+/// Gives back the quotas counted for a request which was not served.
+fn refund_quotas(state: &AppState, charge: QuotaCharge) {
+    if let Err(err) = state.quotas.refund(charge) {
+        warn!("Failed to refund the quotas of a failed request: {err}");
+    }
+}
+
 fn create_wallet_context(timeout_secs: u64) -> Result<WalletContext, anyhow::Error> {
     let wallet_conf = iota_config_dir()?.join(IOTA_CLIENT_CONFIG);
     info!("Initialize wallet from config path: {:?}", wallet_conf);
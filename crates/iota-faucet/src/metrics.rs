@@ -4,8 +4,9 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use prometheus::{
-    register_histogram_with_registry, register_int_counter_with_registry,
-    register_int_gauge_with_registry, Histogram, IntCounter, IntGauge, Registry,
+    register_histogram_with_registry, register_int_counter_vec_with_registry,
+    register_int_counter_with_registry, register_int_gauge_with_registry, Histogram, IntCounter,
+    IntCounterVec, IntGauge, Registry,
 };
 
 /// Prometheus metrics which can be displayed in Grafana, queried and alerted on
@@ -29,6 +30,20 @@ pub struct FaucetMetrics {
     pub(crate) total_available_coins: IntGauge,
     pub(crate) total_discarded_coins: IntGauge,
     pub(crate) total_coin_requests_succeeded: IntGauge,
+    pub(crate) total_coins_split: IntCounter,
+    pub(crate) total_coins_merged: IntCounter,
+    pub(crate) total_rebalance_failures: IntCounter,
+    pub(crate) total_rebalance_read_errors: IntCounter,
+    pub(crate) current_retired_coins: IntGauge,
+}
+
+/// Metrics relevant to the daily request quotas
+#[derive(Clone, Debug)]
+pub struct QuotaMetrics {
+    pub(crate) total_requests_over_quota: IntCounterVec,
+    pub(crate) total_allowlisted_requests: IntCounter,
+    pub(crate) tracked_ips: IntGauge,
+    pub(crate) tracked_recipients: IntGauge,
 }
 
 const LATENCY_SEC_BUCKETS: &[f64] = &[
@@ -113,6 +128,70 @@ impl FaucetMetrics {
                 registry,
             )
             .unwrap(),
+            total_coins_split: register_int_counter_with_registry!(
+                "total_coins_split",
+                "Total number of coins split off large coins when rebalancing the coin pools",
+                registry,
+            )
+            .unwrap(),
+            total_coins_merged: register_int_counter_with_registry!(
+                "total_coins_merged",
+                "Total number of coins too small to serve a request merged when rebalancing the \
+                 coin pools",
+                registry,
+            )
+            .unwrap(),
+            total_rebalance_failures: register_int_counter_with_registry!(
+                "total_rebalance_failures",
+                "Total number of failed transactions when rebalancing the coin pools",
+                registry,
+            )
+            .unwrap(),
+            total_rebalance_read_errors: register_int_counter_with_registry!(
+                "total_rebalance_read_errors",
+                "Total number of coins which could not be read from the fullnode when \
+                 rebalancing the coin pools",
+                registry,
+            )
+            .unwrap(),
+            current_retired_coins: register_int_gauge_with_registry!(
+                "current_retired_coins",
+                "Current number of coins retired after failing repeatedly in the WAL retry loop",
+                registry,
+            )
+            .unwrap(),
+        }
+    }
+}
+
+impl QuotaMetrics {
+    pub fn new(registry: &Registry) -> Self {
+        Self {
+            total_requests_over_quota: register_int_counter_vec_with_registry!(
+                "total_requests_over_quota",
+                "Total number of requests rejected because they exceeded the daily quota, by quota",
+                &["quota"],
+                registry,
+            )
+            .unwrap(),
+            total_allowlisted_requests: register_int_counter_with_registry!(
+                "total_allowlisted_requests",
+                "Total number of requests from allowlisted IPs, which are exempt from quotas",
+                registry,
+            )
+            .unwrap(),
+            tracked_ips: register_int_gauge_with_registry!(
+                "quota_tracked_ips",
+                "Number of client IPs that made requests during the current day",
+                registry,
+            )
+            .unwrap(),
+            tracked_recipients: register_int_gauge_with_registry!(
+                "quota_tracked_recipients",
+                "Number of recipient addresses that received coins during the current day",
+                registry,
+            )
+            .unwrap(),
         }
     }
 }
@@ -6,3 +6,10 @@ use axum::http::HeaderName;
 
 pub static VERSION_HEADER: HeaderName = HeaderName::from_static("x-iota-rpc-version");
 pub static LIMITS_HEADER: HeaderName = HeaderName::from_static("x-iota-rpc-show-usage");
+pub static ADMIN_TOKEN_HEADER: HeaderName = HeaderName::from_static("x-iota-rpc-admin-token");
+pub static API_KEY_HEADER: HeaderName = HeaderName::from_static("x-iota-rpc-api-key");
+pub static QUERY_COST_HEADER: HeaderName = HeaderName::from_static("x-iota-rpc-query-cost");
+pub static BUDGET_CAPACITY_HEADER: HeaderName =
+    HeaderName::from_static("x-iota-rpc-budget-capacity");
+pub static BUDGET_REMAINING_HEADER: HeaderName =
+    HeaderName::from_static("x-iota-rpc-budget-remaining");
@@ -2,7 +2,7 @@
 // Modifications Copyright (c) 2024 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use std::{collections::BTreeSet, fmt::Display, time::Duration};
+use std::{collections::BTreeSet, fmt::Display, path::PathBuf, time::Duration};
 
 use async_graphql::*;
 use fastcrypto_zkp::bn254::zk_login_api::ZkLoginEnv;
@@ -22,6 +22,14 @@ const MAX_OUTPUT_NODES: u64 = 100_000; // Maximum number of output nodes allowed
 const MAX_DB_QUERY_COST: u64 = 20_000; // Max DB query cost (normally f64) truncated
 const DEFAULT_PAGE_SIZE: u64 = 20; // Default number of elements allowed on a page of a connection
 const MAX_PAGE_SIZE: u64 = 50; // Maximum number of elements allowed on a page of a connection
+const MAX_SUBSCRIPTION_CONNECTIONS: u32 = 100; // Maximum number of open subscription websockets
+
+/// Default budgets for the cost of queries (measured in output nodes) made by
+/// callers that did not send an API key, and callers that did.
+const ANONYMOUS_BUDGET_CAPACITY: u64 = 200_000;
+const ANONYMOUS_BUDGET_REFILL_PER_SECOND: u64 = 20_000;
+const AUTHENTICATED_BUDGET_CAPACITY: u64 = 2_000_000;
+const AUTHENTICATED_BUDGET_REFILL_PER_SECOND: u64 = 200_000;
 
 /// The following limits reflect the max values set in the ProtocolConfig.
 const MAX_TYPE_ARGUMENT_DEPTH: u32 = 16;
@@ -97,6 +105,12 @@ pub struct ServiceConfig {
 
     #[serde(default)]
     pub(crate) zklogin: ZkLoginConfig,
+
+    #[serde(default)]
+    pub(crate) persisted_queries: PersistedQueriesConfig,
+
+    #[serde(default)]
+    pub(crate) rate_limits: RateLimitsConfig,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, Copy)]
@@ -126,6 +140,8 @@ pub struct Limits {
     pub max_type_nodes: u32,
     #[serde(default)]
     pub max_move_value_depth: u32,
+    #[serde(default = "Limits::default_max_subscription_connections")]
+    pub max_subscription_connections: u32,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, Copy)]
@@ -216,6 +232,8 @@ pub struct InternalFeatureConfig {
     pub(crate) apollo_tracing: bool,
     #[serde(default)]
     pub(crate) open_telemetry: bool,
+    #[serde(default)]
+    pub(crate) persisted_queries: bool,
 }
 
 #[derive(Serialize, Clone, Deserialize, Debug, Eq, PartialEq, Default)]
@@ -230,6 +248,55 @@ pub struct ZkLoginConfig {
     pub env: ZkLoginEnv,
 }
 
+/// Configuration for queries that clients can refer to by hash.
+#[derive(Serialize, Clone, Deserialize, Debug, Eq, PartialEq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub struct PersistedQueriesConfig {
+    /// JSON file mapping the hex-encoded SHA-256 hashes of persisted queries
+    /// to their text. It is loaded on startup, and queries registered while
+    /// the service is running are written back to it.
+    #[serde(default)]
+    pub(crate) path: Option<PathBuf>,
+    /// Reject all queries that are not persisted.
+    #[serde(default)]
+    pub(crate) allowlist_only: bool,
+    /// Token that requests to register persisted queries must send in the
+    /// admin token header. Queries cannot be registered while the service
+    /// is running if this is not set.
+    #[serde(default)]
+    pub(crate) admin_token: Option<String>,
+}
+
+/// Configuration for budgeting the cost of the queries each caller can make.
+#[derive(Serialize, Clone, Deserialize, Debug, Eq, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub struct RateLimitsConfig {
+    /// Charge the cost of each query against its caller's budget.
+    #[serde(default)]
+    pub(crate) enabled: bool,
+    /// TOML file listing the API keys callers can authenticate with, the
+    /// names of the callers they identify, and optionally a budget for
+    /// each of them, overriding the authenticated tier's.
+    #[serde(default)]
+    pub(crate) api_keys_path: Option<PathBuf>,
+    /// Budget for each IP address that makes requests without an API key.
+    #[serde(default = "BudgetConfig::anonymous")]
+    pub(crate) anonymous: BudgetConfig,
+    /// Budget for each API key.
+    #[serde(default = "BudgetConfig::authenticated")]
+    pub(crate) authenticated: BudgetConfig,
+}
+
+/// A budget for the cost of queries, measured in output nodes. It holds up to
+/// `capacity` units, and is refilled by `refill_per_second` units every
+/// second.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub struct BudgetConfig {
+    pub(crate) capacity: u64,
+    pub(crate) refill_per_second: u64,
+}
+
 /// The enabled features and service limits configured by the server.
 #[Object]
 impl ServiceConfig {
@@ -324,6 +391,12 @@ impl ServiceConfig {
     async fn max_move_value_depth(&self) -> u32 {
         self.limits.max_move_value_depth
     }
+
+    /// Maximum number of websocket connections serving subscriptions that can
+    /// be open on this service at the same time.
+    async fn max_subscription_connections(&self) -> u32 {
+        self.limits.max_subscription_connections
+    }
 }
 
 impl TxExecFullNodeConfig {
@@ -415,6 +488,10 @@ impl Limits {
             max_move_value_depth: self.max_move_value_depth as usize,
         }
     }
+
+    fn default_max_subscription_connections() -> u32 {
+        MAX_SUBSCRIPTION_CONNECTIONS
+    }
 }
 
 impl Ide {
@@ -425,6 +502,22 @@ impl Ide {
     }
 }
 
+impl BudgetConfig {
+    fn anonymous() -> Self {
+        Self {
+            capacity: ANONYMOUS_BUDGET_CAPACITY,
+            refill_per_second: ANONYMOUS_BUDGET_REFILL_PER_SECOND,
+        }
+    }
+
+    fn authenticated() -> Self {
+        Self {
+            capacity: AUTHENTICATED_BUDGET_CAPACITY,
+            refill_per_second: AUTHENTICATED_BUDGET_REFILL_PER_SECOND,
+        }
+    }
+}
+
 impl BackgroundTasksConfig {
     pub fn test_defaults() -> Self {
         Self {
@@ -469,6 +562,7 @@ impl Default for Limits {
             max_type_argument_width: MAX_TYPE_ARGUMENT_WIDTH,
             max_type_nodes: MAX_TYPE_NODES,
             max_move_value_depth: MAX_MOVE_VALUE_DEPTH,
+            max_subscription_connections: MAX_SUBSCRIPTION_CONNECTIONS,
         }
     }
 }
@@ -484,6 +578,18 @@ impl Default for InternalFeatureConfig {
             tracing: false,
             apollo_tracing: false,
             open_telemetry: false,
+            persisted_queries: true,
+        }
+    }
+}
+
+impl Default for RateLimitsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            api_keys_path: None,
+            anonymous: BudgetConfig::anonymous(),
+            authenticated: BudgetConfig::authenticated(),
         }
     }
 }
@@ -523,6 +629,7 @@ mod tests {
                 max-type-argument-width = 64
                 max-type-nodes = 128
                 max-move-value-depth = 256
+                max-subscription-connections = 10
             "#,
         )
         .unwrap();
@@ -541,6 +648,7 @@ mod tests {
                 max_type_argument_width: 64,
                 max_type_nodes: 128,
                 max_move_value_depth: 256,
+                max_subscription_connections: 10,
             },
             ..Default::default()
         };
@@ -548,6 +656,21 @@ mod tests {
         assert_eq!(actual, expect)
     }
 
+    #[test]
+    fn test_read_default_subscription_connections_in_service_config() {
+        let actual = ServiceConfig::read(
+            r#" [limits]
+                max-query-depth = 100
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            actual.limits.max_subscription_connections,
+            MAX_SUBSCRIPTION_CONNECTIONS
+        );
+    }
+
     #[test]
     fn test_read_enabled_features_in_service_config() {
         let actual = ServiceConfig::read(
@@ -602,6 +725,7 @@ mod tests {
                 max-type-argument-width = 64
                 max-type-nodes = 128
                 max-move-value-depth = 256
+                max-subscription-connections = 10
 
                 [experiments]
                 test-flag = true
@@ -623,6 +747,7 @@ mod tests {
                 max_type_argument_width: 64,
                 max_type_nodes: 128,
                 max_move_value_depth: 256,
+                max_subscription_connections: 10,
             },
             disabled_features: BTreeSet::from([FunctionalGroup::Analytics]),
             experiments: Experiments { test_flag: true },
@@ -631,4 +756,57 @@ mod tests {
 
         assert_eq!(actual, expect);
     }
+
+    #[test]
+    fn test_read_persisted_queries_in_service_config() {
+        let actual = ServiceConfig::read(
+            r#" [persisted-queries]
+                path = "persisted-queries.json"
+                allowlist-only = true
+                admin-token = "secret"
+            "#,
+        )
+        .unwrap();
+
+        let expect = ServiceConfig {
+            persisted_queries: PersistedQueriesConfig {
+                path: Some(PathBuf::from("persisted-queries.json")),
+                allowlist_only: true,
+                admin_token: Some("secret".to_string()),
+            },
+            ..Default::default()
+        };
+
+        assert_eq!(actual, expect);
+    }
+
+    #[test]
+    fn test_read_rate_limits_in_service_config() {
+        let actual = ServiceConfig::read(
+            r#" [rate-limits]
+                enabled = true
+                api-keys-path = "api-keys.toml"
+
+                [rate-limits.anonymous]
+                capacity = 1000
+                refill-per-second = 100
+            "#,
+        )
+        .unwrap();
+
+        let expect = ServiceConfig {
+            rate_limits: RateLimitsConfig {
+                enabled: true,
+                api_keys_path: Some(PathBuf::from("api-keys.toml")),
+                anonymous: BudgetConfig {
+                    capacity: 1000,
+                    refill_per_second: 100,
+                },
+                authenticated: BudgetConfig::authenticated(),
+            },
+            ..Default::default()
+        };
+
+        assert_eq!(actual, expect);
+    }
 }
@@ -13,7 +13,10 @@ pub(crate) mod code {
     pub const BAD_REQUEST: &str = "BAD_REQUEST";
     pub const BAD_USER_INPUT: &str = "BAD_USER_INPUT";
     pub const INTERNAL_SERVER_ERROR: &str = "INTERNAL_SERVER_ERROR";
+    pub const PERSISTED_QUERY_NOT_FOUND: &str = "PERSISTED_QUERY_NOT_FOUND";
+    pub const RATE_LIMITED: &str = "RATE_LIMITED";
     pub const REQUEST_TIMEOUT: &str = "REQUEST_TIMEOUT";
+    pub const UNAUTHENTICATED: &str = "UNAUTHENTICATED";
     pub const UNKNOWN: &str = "UNKNOWN";
 }
 
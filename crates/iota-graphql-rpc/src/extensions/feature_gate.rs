@@ -39,9 +39,7 @@ impl Extension for FeatureGate {
             ..
         } = &info;
 
-        let ServiceConfig {
-            disabled_features, ..
-        } = ctx.data().map_err(|_| {
+        let config: &ServiceConfig = ctx.data().map_err(|_| {
             graphql_error(
                 code::INTERNAL_SERVER_ERROR,
                 "Unable to fetch service configuration",
@@ -53,30 +51,41 @@ impl Extension for FeatureGate {
         // `ServiceConfig` from the `Context`). This is (probably) required to
         // hide disabled types and interfaces in the schema.
 
-        if let Some(group) = functional_group(parent_type, name) {
-            if disabled_features.contains(&group) {
-                return if *is_for_introspection {
-                    Ok(None)
-                } else {
-                    Err(ServerError::new(
-                        format!(
-                            "Cannot query field \"{name}\" on type \"{parent_type}\". \
-                             Feature {} is disabled.",
-                            group.name(),
-                        ),
-                        // TODO: Fork `async-graphl` to add field position information to
-                        // `ResolveInfo`, so the error can take advantage of it.  Similarly for
-                        // utilising the `path_node` to set the error path.
-                        None,
-                    ))
-                };
-            }
+        if let Some(message) = disabled_feature_message(config, parent_type, name) {
+            return if *is_for_introspection {
+                Ok(None)
+            } else {
+                Err(ServerError::new(
+                    message,
+                    // TODO: Fork `async-graphl` to add field position information to
+                    // `ResolveInfo`, so the error can take advantage of it.  Similarly for
+                    // utilising the `path_node` to set the error path.
+                    None,
+                ))
+            };
         }
 
         next.run(ctx, info).await
     }
 }
 
+/// If field `name` on `parent_type` belongs to a functional group that is
+/// disabled in `config`, returns the message explaining why it cannot be
+/// queried.
+pub(crate) fn disabled_feature_message(
+    config: &ServiceConfig,
+    parent_type: &str,
+    name: &str,
+) -> Option<String> {
+    let group = functional_group(parent_type, name)?;
+    config.disabled_features.contains(&group).then(|| {
+        format!(
+            "Cannot query field \"{name}\" on type \"{parent_type}\". Feature {} is disabled.",
+            group.name(),
+        )
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::BTreeSet;
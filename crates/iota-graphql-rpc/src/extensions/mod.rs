@@ -4,5 +4,7 @@
 
 pub(crate) mod feature_gate;
 pub(crate) mod logger;
+pub(crate) mod persisted_queries;
 pub mod query_limits_checker;
+pub(crate) mod rate_limiter;
 pub(crate) mod timeout;
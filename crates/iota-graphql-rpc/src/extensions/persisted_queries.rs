@@ -0,0 +1,368 @@
+// Copyright (c) 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    any::TypeId,
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use async_graphql::{
+    extensions::{Extension, ExtensionContext, ExtensionFactory, NextPrepareRequest},
+    parser::{
+        self,
+        types::{OperationType, Selection},
+    },
+    Request, ServerResult,
+};
+use fastcrypto::hash::{HashFunction, Sha256};
+use serde::Deserialize;
+use tokio::sync::RwLock;
+use tracing::info;
+
+use crate::{
+    config::ServiceConfig,
+    error::{code, graphql_error, Error},
+    metrics::Metrics,
+};
+
+/// Key of the request extension that identifies a persisted query, following
+/// the format of Apollo's automatic persisted queries:
+///
+/// ```json
+/// { "persistedQuery": { "version": 1, "sha256Hash": "<hex digest>" } }
+/// ```
+const PERSISTED_QUERY_EXTENSION: &str = "persistedQuery";
+
+/// Queries that clients can refer to by hash, instead of sending their full
+/// text. The registry is loaded from a JSON file mapping each query's SHA-256
+/// hash (hex-encoded) to its text, and queries registered while the service
+/// is running are written back to the same file.
+#[derive(Debug, Default)]
+pub(crate) struct PersistedQueryRegistry {
+    path: Option<PathBuf>,
+    queries: RwLock<BTreeMap<String, String>>,
+}
+
+/// Token sent by a request in the admin token header, authorizing it to
+/// register persisted queries.
+pub(crate) struct AdminToken(pub String);
+
+/// Name of the mutation that registers persisted queries.
+const REGISTER_PERSISTED_QUERY: &str = "registerPersistedQuery";
+
+/// Extension factory for the `PersistedQueries` extension, which substitutes
+/// persisted queries into requests that refer to them by hash, and rejects
+/// requests for queries that are not persisted if the service only accepts
+/// persisted queries.
+pub(crate) struct PersistedQueries;
+
+#[derive(Debug, Default)]
+struct PersistedQueriesExt;
+
+#[derive(Deserialize)]
+struct PersistedQuery {
+    #[serde(rename = "sha256Hash")]
+    sha256_hash: String,
+}
+
+impl PersistedQueryRegistry {
+    /// Load the registry from the file at `path`, if there is one. A registry
+    /// without a path only holds queries registered while the service is
+    /// running.
+    pub(crate) fn load(path: Option<PathBuf>) -> Result<Self, Error> {
+        let queries = match &path {
+            Some(path) if path.exists() => read_queries(path)?,
+            _ => BTreeMap::new(),
+        };
+
+        if let Some(path) = &path {
+            info!(
+                "Loaded {} persisted queries from {}",
+                queries.len(),
+                path.display()
+            );
+        }
+
+        Ok(Self {
+            path,
+            queries: RwLock::new(queries),
+        })
+    }
+
+    /// The text of the query persisted under `hash`, if there is one.
+    pub(crate) async fn get(&self, hash: &str) -> Option<String> {
+        self.queries.read().await.get(hash).cloned()
+    }
+
+    /// Whether a query is persisted under `hash`.
+    pub(crate) async fn contains(&self, hash: &str) -> bool {
+        self.queries.read().await.contains_key(hash)
+    }
+
+    /// Persist `query`, returning the hash it can be referred to by. If the
+    /// registry was loaded from a file, the file is updated to include it
+    /// before the query becomes visible to other requests.
+    pub(crate) async fn register(&self, query: String) -> Result<String, Error> {
+        let hash = query_hash(&query);
+        let mut queries = self.queries.write().await;
+        if queries.contains_key(&hash) {
+            return Ok(hash);
+        }
+
+        queries.insert(hash.clone(), query);
+        if let Some(path) = self.path.clone() {
+            if let Err(e) = persist_queries(path, &queries).await {
+                queries.remove(&hash);
+                return Err(e);
+            }
+        }
+
+        Ok(hash)
+    }
+}
+
+impl AdminToken {
+    /// Whether this is the `expected` token. The comparison takes the same
+    /// time wherever the tokens differ, so it does not reveal how much of a
+    /// guessed token is correct.
+    pub(crate) fn matches(&self, expected: &str) -> bool {
+        let (actual, expected) = (self.0.as_bytes(), expected.as_bytes());
+        actual.len() == expected.len()
+            && actual
+                .iter()
+                .zip(expected)
+                .fold(0u8, |diff, (a, e)| diff | (a ^ e))
+                == 0
+    }
+}
+
+impl ExtensionFactory for PersistedQueries {
+    fn create(&self) -> Arc<dyn Extension> {
+        Arc::new(PersistedQueriesExt)
+    }
+}
+
+#[async_trait::async_trait]
+impl Extension for PersistedQueriesExt {
+    /// Replaces the query in requests that refer to a persisted query by hash,
+    /// before it is parsed. Requests for queries that are not persisted are
+    /// rejected if the service is configured to only accept persisted
+    /// queries.
+    async fn prepare_request(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        mut request: Request,
+        next: NextPrepareRequest<'_>,
+    ) -> ServerResult<Request> {
+        let cfg: &ServiceConfig = ctx
+            .data()
+            .expect("No service config provided in schema data");
+        let registry: &PersistedQueryRegistry = ctx.data_unchecked();
+        let metrics: &Metrics = ctx.data_unchecked();
+
+        let hash = match request.extensions.remove(PERSISTED_QUERY_EXTENSION) {
+            Some(value) => {
+                let persisted: PersistedQuery = async_graphql::from_value(value).map_err(|_| {
+                    graphql_error(
+                        code::BAD_REQUEST,
+                        "Invalid persisted query extension, expected an object containing \
+                         `sha256Hash`",
+                    )
+                })?;
+                Some(persisted.sha256_hash.to_ascii_lowercase())
+            }
+            None => None,
+        };
+
+        // Requests that only send a hash must refer to a persisted query.
+        if let Some(hash) = hash.as_ref().filter(|_| request.query.is_empty()) {
+            let Some(query) = registry.get(hash).await else {
+                metrics.request_metrics.persisted_query_misses.inc();
+                return Err(graphql_error(
+                    code::PERSISTED_QUERY_NOT_FOUND,
+                    "PersistedQueryNotFound",
+                ));
+            };
+
+            metrics.request_metrics.persisted_query_hits.inc();
+            request.query = query;
+            return next.run(ctx, request).await;
+        }
+
+        let actual = query_hash(&request.query);
+        if hash.as_ref().is_some_and(|hash| hash != &actual) {
+            return Err(graphql_error(
+                code::BAD_REQUEST,
+                "Persisted query hash does not match the query",
+            ));
+        }
+
+        if !cfg.persisted_queries.allowlist_only {
+            return next.run(ctx, request).await;
+        }
+
+        // Only queries in the registry are accepted, whether they are sent by hash
+        // or in full, except for the admin's requests to add queries to it.
+        if is_admin_registration(cfg, &request) {
+            return next.run(ctx, request).await;
+        }
+
+        if !registry.contains(&actual).await {
+            metrics.request_metrics.persisted_query_misses.inc();
+            return Err(graphql_error(
+                code::PERSISTED_QUERY_NOT_FOUND,
+                "This service only accepts persisted queries",
+            ));
+        }
+
+        metrics.request_metrics.persisted_query_hits.inc();
+        next.run(ctx, request).await
+    }
+}
+
+/// The hex-encoded SHA-256 hash of `query`, which clients use to refer to it
+/// once it has been persisted.
+pub(crate) fn query_hash(query: &str) -> String {
+    hex::encode(Sha256::digest(query.as_bytes()).digest)
+}
+
+/// Whether `request` only registers persisted queries, and carries the
+/// service's admin token authorizing it to.
+fn is_admin_registration(cfg: &ServiceConfig, request: &Request) -> bool {
+    let Some(expected) = &cfg.persisted_queries.admin_token else {
+        return false;
+    };
+
+    let authorized = request
+        .data
+        .get(&TypeId::of::<AdminToken>())
+        .and_then(|token| token.downcast_ref::<AdminToken>())
+        .is_some_and(|token| token.matches(expected));
+    if !authorized {
+        return false;
+    }
+
+    let Ok(doc) = parser::parse_query(&request.query) else {
+        return false;
+    };
+
+    doc.fragments.is_empty()
+        && doc.operations.iter().all(|(_, op)| {
+            op.node.ty == OperationType::Mutation
+                && op.node.selection_set.node.items.iter().all(|selection| {
+                    matches!(
+                        &selection.node,
+                        Selection::Field(field) if field.node.name.node == REGISTER_PERSISTED_QUERY
+                    )
+                })
+        })
+}
+
+fn read_queries(path: &Path) -> Result<BTreeMap<String, String>, Error> {
+    let contents = fs::read_to_string(path).map_err(|e| {
+        Error::Internal(format!(
+            "Failed to read persisted queries from {}: {e}",
+            path.display()
+        ))
+    })?;
+
+    let queries: BTreeMap<String, String> = serde_json::from_str(&contents).map_err(|e| {
+        Error::Internal(format!(
+            "Failed to parse persisted queries in {}: {e}",
+            path.display()
+        ))
+    })?;
+
+    // Normalize the hashes, and make sure they identify the queries they are
+    // persisted under, so that clients can compute them.
+    queries
+        .into_iter()
+        .map(|(hash, query)| {
+            let hash = hash.to_ascii_lowercase();
+            if hash != query_hash(&query) {
+                return Err(Error::Internal(format!(
+                    "Persisted query {hash} in {} does not match its hash",
+                    path.display()
+                )));
+            }
+            Ok((hash, query))
+        })
+        .collect()
+}
+
+/// Write `queries` to `path` from a blocking thread, so that filesystem IO
+/// does not hold up the runtime serving other requests.
+async fn persist_queries(path: PathBuf, queries: &BTreeMap<String, String>) -> Result<(), Error> {
+    let contents = serde_json::to_string_pretty(queries)
+        .map_err(|e| Error::Internal(format!("Failed to serialize persisted queries: {e}")))?;
+
+    tokio::task::spawn_blocking(move || write_queries(&path, contents))
+        .await
+        .map_err(|e| Error::Internal(format!("Failed to write persisted queries: {e}")))?
+}
+
+/// Write serialized queries to `path`, replacing its contents atomically so
+/// that a failed write does not lose previously persisted queries.
+fn write_queries(path: &Path, contents: String) -> Result<(), Error> {
+    let tmp = path.with_extension("tmp");
+    fs::write(&tmp, contents)
+        .and_then(|_| fs::rename(&tmp, path))
+        .map_err(|e| {
+            Error::Internal(format!(
+                "Failed to write persisted queries to {}: {e}",
+                path.display()
+            ))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const QUERY: &str = "{ chainIdentifier }";
+
+    #[tokio::test]
+    async fn test_register_in_memory() {
+        let registry = PersistedQueryRegistry::load(None).unwrap();
+        let hash = registry.register(QUERY.to_string()).await.unwrap();
+
+        assert_eq!(hash, query_hash(QUERY));
+        assert!(registry.contains(&hash).await);
+        assert_eq!(registry.get(&hash).await.as_deref(), Some(QUERY));
+        assert_eq!(registry.get(&query_hash("{ serviceConfig }")).await, None);
+    }
+
+    #[tokio::test]
+    async fn test_register_persists_to_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("persisted-queries.json");
+
+        let registry = PersistedQueryRegistry::load(Some(path.clone())).unwrap();
+        let hash = registry.register(QUERY.to_string()).await.unwrap();
+
+        let reloaded = PersistedQueryRegistry::load(Some(path)).unwrap();
+        assert_eq!(reloaded.get(&hash).await.as_deref(), Some(QUERY));
+    }
+
+    #[test]
+    fn test_admin_token_matches() {
+        let token = AdminToken("secret".to_string());
+        assert!(token.matches("secret"));
+        assert!(!token.matches("secreT"));
+        assert!(!token.matches("secret2"));
+        assert!(!token.matches(""));
+    }
+
+    #[test]
+    fn test_load_rejects_mismatched_hash() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("persisted-queries.json");
+        let queries = BTreeMap::from([(query_hash("{ serviceConfig }"), QUERY.to_string())]);
+        fs::write(&path, serde_json::to_string(&queries).unwrap()).unwrap();
+
+        assert!(PersistedQueryRegistry::load(Some(path)).is_err());
+    }
+}
@@ -28,6 +28,7 @@ use uuid::Uuid;
 use crate::{
     config::{Limits, ServiceConfig},
     error::{code, graphql_error, graphql_error_at_pos},
+    extensions::rate_limiter::RequestCost,
     metrics::Metrics,
 };
 
@@ -196,6 +197,10 @@ impl Extension for QueryLimitsChecker {
             )?;
             max_depth_seen = max_depth_seen.max(running_costs.depth);
         }
+        // The estimated number of output nodes is what the request costs its caller.
+        if let Some(request_cost) = ctx.data_opt::<RequestCost>() {
+            request_cost.set_cost(running_costs.output_nodes);
+        }
         if ctx.data_opt::<ShowUsage>().is_some() {
             *self.validation_result.lock().await = Some(ValidationRes {
                 input_nodes: running_costs.input_nodes,
@@ -0,0 +1,475 @@
+// Copyright (c) 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    collections::HashMap,
+    fs,
+    net::{IpAddr, SocketAddr},
+    num::NonZeroUsize,
+    path::Path,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use async_graphql::{
+    extensions::{Extension, ExtensionContext, ExtensionFactory, NextParseQuery},
+    parser::types::ExecutableDocument,
+    ServerResult, Variables,
+};
+use axum::http::{HeaderMap, HeaderValue};
+use iota_graphql_rpc_headers::{
+    BUDGET_CAPACITY_HEADER, BUDGET_REMAINING_HEADER, QUERY_COST_HEADER,
+};
+use lru::LruCache;
+use serde::Deserialize;
+use tracing::info;
+use uuid::Uuid;
+
+use crate::{
+    config::{BudgetConfig, RateLimitsConfig},
+    error::{code, graphql_error, Error},
+    metrics::Metrics,
+};
+
+/// Maximum number of anonymous clients whose budgets are tracked at the same
+/// time. The budgets of the least recently seen clients are forgotten first.
+const MAX_ANONYMOUS_CLIENTS: usize = 100_000;
+
+/// API key sent by a request in the API key header.
+pub(crate) struct ApiKey(pub String);
+
+/// The cost of a request, and what remains of its caller's budget after it
+/// has been charged. The query limits checker computes the cost, the rate
+/// limiter charges it, and the request handler reports both in the response
+/// headers.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct RequestCost(Arc<Mutex<CostState>>);
+
+#[derive(Debug, Default)]
+struct CostState {
+    cost: Option<u64>,
+    budget: Option<Budget>,
+}
+
+/// What remains of a caller's budget.
+#[derive(Copy, Clone, Debug, PartialEq)]
+struct Budget {
+    capacity: u64,
+    remaining: u64,
+}
+
+/// Budgets for the cost of the queries each caller can make, refilled over
+/// time. Callers that send a known API key are charged against that key's
+/// budget, and all other callers are charged against a budget for their IP
+/// address, in the (lower) anonymous tier.
+pub(crate) struct RateLimits {
+    /// Names of the callers identified by each API key.
+    keys: HashMap<String, String>,
+    /// Budgets for each API key, by the name of their caller.
+    authenticated: Mutex<HashMap<String, TokenBucket>>,
+    /// Budgets for anonymous callers, by their IP address.
+    anonymous: Mutex<LruCache<IpAddr, TokenBucket>>,
+    anonymous_tier: BudgetConfig,
+}
+
+/// Extension factory for the `RateLimiter` extension, which authenticates the
+/// caller of each request and charges the request's cost against their
+/// budget, rejecting requests that cost more than the caller has left.
+///
+/// It must be added to the schema before the `QueryLimitsChecker`, which
+/// computes the cost of the request.
+pub(crate) struct RateLimiter;
+
+#[derive(Debug, Default)]
+struct RateLimiterExt;
+
+/// The caller that a request is charged to.
+#[derive(Clone, Debug, PartialEq)]
+enum Caller {
+    Key(String),
+    Anonymous(IpAddr),
+}
+
+/// A budget that holds up to `capacity` units of cost, and is refilled at a
+/// constant rate.
+#[derive(Debug)]
+struct TokenBucket {
+    capacity: u64,
+    refill_per_second: u64,
+    tokens: f64,
+    last_refill: Instant,
+}
+
+/// Format of the API key file.
+#[derive(Deserialize, Debug, Default)]
+#[serde(rename_all = "kebab-case")]
+struct ApiKeysFile {
+    #[serde(default)]
+    keys: Vec<ApiKeyEntry>,
+}
+
+/// An API key, the name of the caller it identifies, and optionally the
+/// budget for that caller, if it differs from the authenticated tier's.
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "kebab-case")]
+struct ApiKeyEntry {
+    key: String,
+    name: String,
+    capacity: Option<u64>,
+    refill_per_second: Option<u64>,
+}
+
+impl RequestCost {
+    /// Record the cost of the request, as computed by the query limits
+    /// checker.
+    pub(crate) fn set_cost(&self, cost: u64) {
+        self.0.lock().unwrap().cost = Some(cost);
+    }
+
+    fn cost(&self) -> Option<u64> {
+        self.0.lock().unwrap().cost
+    }
+
+    fn set_budget(&self, budget: Budget) {
+        self.0.lock().unwrap().budget = Some(budget);
+    }
+
+    /// Headers reporting the cost charged for the request, and what remains of
+    /// the caller's budget.
+    pub(crate) fn headers(&self) -> HeaderMap {
+        let state = self.0.lock().unwrap();
+        let mut headers = HeaderMap::new();
+        let Some(budget) = state.budget else {
+            return headers;
+        };
+
+        if let Some(cost) = state.cost {
+            headers.insert(QUERY_COST_HEADER.clone(), HeaderValue::from(cost));
+        }
+        headers.insert(
+            BUDGET_CAPACITY_HEADER.clone(),
+            HeaderValue::from(budget.capacity),
+        );
+        headers.insert(
+            BUDGET_REMAINING_HEADER.clone(),
+            HeaderValue::from(budget.remaining),
+        );
+        headers
+    }
+}
+
+impl RateLimits {
+    /// Set up budgets for the tiers in `config`, and for the API keys in its
+    /// key file, if it has one.
+    pub(crate) fn load(config: &RateLimitsConfig) -> Result<Self, Error> {
+        let entries = match &config.api_keys_path {
+            Some(path) => read_api_keys(path)?,
+            None => vec![],
+        };
+
+        let now = Instant::now();
+        let mut keys = HashMap::new();
+        let mut authenticated = HashMap::new();
+        for entry in entries {
+            let bucket = TokenBucket::new(
+                entry.capacity.unwrap_or(config.authenticated.capacity),
+                entry
+                    .refill_per_second
+                    .unwrap_or(config.authenticated.refill_per_second),
+                now,
+            );
+
+            if authenticated.insert(entry.name.clone(), bucket).is_some() {
+                return Err(Error::Internal(format!(
+                    "API key name {} is used more than once",
+                    entry.name
+                )));
+            }
+            keys.insert(entry.key, entry.name);
+        }
+
+        if let Some(path) = &config.api_keys_path {
+            info!("Loaded {} API keys from {}", keys.len(), path.display());
+        }
+
+        Ok(Self {
+            keys,
+            authenticated: Mutex::new(authenticated),
+            anonymous: Mutex::new(LruCache::new(
+                NonZeroUsize::new(MAX_ANONYMOUS_CLIENTS).unwrap(),
+            )),
+            anonymous_tier: config.anonymous,
+        })
+    }
+
+    /// The caller identified by `key`, if it is a known API key.
+    fn authenticate(&self, key: &str) -> Option<Caller> {
+        self.keys.get(key).cloned().map(Caller::Key)
+    }
+
+    /// Charge `cost` to `caller`'s budget. Returns what remains of the budget
+    /// if the caller could afford it, and an error containing the budget
+    /// and how long until it can afford it otherwise. The budget is
+    /// unchanged if the caller could not afford the cost.
+    fn charge(
+        &self,
+        caller: &Caller,
+        cost: u64,
+        now: Instant,
+    ) -> Result<Budget, (Budget, Option<Duration>)> {
+        match caller {
+            Caller::Key(name) => {
+                let mut buckets = self.authenticated.lock().unwrap();
+                let bucket = buckets
+                    .get_mut(name)
+                    .expect("Authenticated callers have a budget");
+                bucket.charge(cost, now)
+            }
+
+            Caller::Anonymous(ip) => {
+                let mut buckets = self.anonymous.lock().unwrap();
+                let tier = self.anonymous_tier;
+                let bucket = buckets.get_or_insert_mut(*ip, || {
+                    TokenBucket::new(tier.capacity, tier.refill_per_second, now)
+                });
+                bucket.charge(cost, now)
+            }
+        }
+    }
+}
+
+impl TokenBucket {
+    /// A full bucket.
+    fn new(capacity: u64, refill_per_second: u64, now: Instant) -> Self {
+        Self {
+            capacity,
+            refill_per_second,
+            tokens: capacity as f64,
+            last_refill: now,
+        }
+    }
+
+    fn budget(&self) -> Budget {
+        Budget {
+            capacity: self.capacity,
+            remaining: self.tokens as u64,
+        }
+    }
+
+    /// Take `cost` tokens out of the bucket, if it holds enough of them after
+    /// being refilled for the time elapsed since it was last refilled.
+    /// Otherwise, returns how long it will take for the bucket to hold
+    /// enough tokens, or `None` if it never will.
+    fn charge(&mut self, cost: u64, now: Instant) -> Result<Budget, (Budget, Option<Duration>)> {
+        let elapsed = now.saturating_duration_since(self.last_refill);
+        self.tokens = (self.tokens + elapsed.as_secs_f64() * self.refill_per_second as f64)
+            .min(self.capacity as f64);
+        self.last_refill = now;
+
+        let cost = cost as f64;
+        if cost <= self.tokens {
+            self.tokens -= cost;
+            return Ok(self.budget());
+        }
+
+        let retry_after = (cost <= self.capacity as f64 && self.refill_per_second > 0)
+            .then(|| Duration::from_secs_f64((cost - self.tokens) / self.refill_per_second as f64));
+        Err((self.budget(), retry_after))
+    }
+}
+
+impl ExtensionFactory for RateLimiter {
+    fn create(&self) -> Arc<dyn Extension> {
+        Arc::new(RateLimiterExt)
+    }
+}
+
+#[async_trait::async_trait]
+impl Extension for RateLimiterExt {
+    /// Authenticates the caller before the query is parsed, and charges them
+    /// for the cost of the query once it has been validated. Only requests
+    /// that track their cost are rate limited.
+    async fn parse_query(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        query: &str,
+        variables: &Variables,
+        next: NextParseQuery<'_>,
+    ) -> ServerResult<ExecutableDocument> {
+        let Some(request_cost) = ctx.data_opt::<RequestCost>() else {
+            return next.run(ctx, query, variables).await;
+        };
+
+        let query_id: &Uuid = ctx.data_unchecked();
+        let session_id: &SocketAddr = ctx.data_unchecked();
+        let metrics: &Metrics = ctx.data_unchecked();
+        let limits: &RateLimits = ctx.data_unchecked();
+
+        let caller = match ctx.data_opt::<ApiKey>() {
+            Some(ApiKey(key)) => limits.authenticate(key).ok_or_else(|| {
+                info!(
+                    query_id = %query_id,
+                    session_id = %session_id,
+                    error_code = code::UNAUTHENTICATED,
+                    "Unknown API key"
+                );
+                graphql_error(code::UNAUTHENTICATED, "Unknown API key")
+            })?,
+            None => Caller::Anonymous(session_id.ip()),
+        };
+
+        let doc = next.run(ctx, query, variables).await?;
+
+        // Requests are charged for at least one unit of cost, even if the query
+        // limits checker did not compute one.
+        let cost = request_cost.cost().unwrap_or(1).max(1);
+        match limits.charge(&caller, cost, Instant::now()) {
+            Ok(budget) => {
+                request_cost.set_budget(budget);
+                Ok(doc)
+            }
+
+            Err((budget, retry_after)) => {
+                request_cost.set_budget(budget);
+                metrics
+                    .request_metrics
+                    .rate_limited_requests
+                    .with_label_values(&[caller.tier()])
+                    .inc();
+                info!(
+                    query_id = %query_id,
+                    session_id = %session_id,
+                    error_code = code::RATE_LIMITED,
+                    "Query cost of {cost} exceeds remaining budget of {}",
+                    budget.remaining,
+                );
+
+                let retry = match retry_after {
+                    Some(after) => format!("Try again in {:.1}s", after.as_secs_f32()),
+                    None => format!("The budget's capacity is {}", budget.capacity),
+                };
+                Err(graphql_error(
+                    code::RATE_LIMITED,
+                    format!(
+                        "Query cost of {cost} exceeds remaining budget of {}. {retry}",
+                        budget.remaining
+                    ),
+                ))
+            }
+        }
+    }
+}
+
+impl Caller {
+    /// The tier the caller's budget belongs to, for metrics.
+    fn tier(&self) -> &'static str {
+        match self {
+            Caller::Key(_) => "authenticated",
+            Caller::Anonymous(_) => "anonymous",
+        }
+    }
+}
+
+fn read_api_keys(path: &Path) -> Result<Vec<ApiKeyEntry>, Error> {
+    let contents = fs::read_to_string(path).map_err(|e| {
+        Error::Internal(format!(
+            "Failed to read API keys from {}: {e}",
+            path.display()
+        ))
+    })?;
+
+    let file: ApiKeysFile = toml::de::from_str(&contents).map_err(|e| {
+        Error::Internal(format!(
+            "Failed to parse API keys in {}: {e}",
+            path.display()
+        ))
+    })?;
+
+    Ok(file.keys)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn budget(capacity: u64, remaining: u64) -> Budget {
+        Budget {
+            capacity,
+            remaining,
+        }
+    }
+
+    #[test]
+    fn test_token_bucket_refills_over_time() {
+        let start = Instant::now();
+        let mut bucket = TokenBucket::new(100, 10, start);
+
+        assert_eq!(bucket.charge(60, start), Ok(budget(100, 40)));
+        assert_eq!(
+            bucket.charge(60, start),
+            Err((budget(100, 40), Some(Duration::from_secs(2))))
+        );
+
+        // The failed charge did not consume any tokens.
+        let later = start + Duration::from_secs(2);
+        assert_eq!(bucket.charge(60, later), Ok(budget(100, 0)));
+
+        // The bucket never holds more than its capacity.
+        let much_later = later + Duration::from_secs(60);
+        assert_eq!(bucket.charge(1, much_later), Ok(budget(100, 99)));
+
+        // Costs beyond the capacity can never be afforded.
+        assert_eq!(bucket.charge(101, much_later), Err((budget(100, 99), None)));
+    }
+
+    #[test]
+    fn test_rate_limits_by_tier() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("api-keys.toml");
+        fs::write(
+            &path,
+            r#" [[keys]]
+                key = "key-a"
+                name = "a"
+
+                [[keys]]
+                key = "key-b"
+                name = "b"
+                capacity = 5
+                refill-per-second = 0
+            "#,
+        )
+        .unwrap();
+
+        let config = RateLimitsConfig {
+            enabled: true,
+            api_keys_path: Some(path.clone()),
+            anonymous: BudgetConfig {
+                capacity: 10,
+                refill_per_second: 0,
+            },
+            authenticated: BudgetConfig {
+                capacity: 100,
+                refill_per_second: 0,
+            },
+        };
+
+        let limits = RateLimits::load(&config).unwrap();
+        let now = Instant::now();
+
+        let a = limits.authenticate("key-a").unwrap();
+        let b = limits.authenticate("key-b").unwrap();
+        assert_eq!(limits.authenticate("key-c"), None);
+
+        assert_eq!(limits.charge(&a, 50, now), Ok(budget(100, 50)));
+        assert_eq!(limits.charge(&b, 5, now), Ok(budget(5, 0)));
+        assert!(limits.charge(&b, 1, now).is_err());
+
+        // Anonymous callers have a budget per IP address.
+        let x = Caller::Anonymous("127.0.0.1".parse().unwrap());
+        let y = Caller::Anonymous("127.0.0.2".parse().unwrap());
+        assert_eq!(limits.charge(&x, 10, now), Ok(budget(10, 0)));
+        assert!(limits.charge(&x, 1, now).is_err());
+        assert_eq!(limits.charge(&y, 1, now), Ok(budget(10, 9)));
+    }
+}
@@ -16,5 +16,6 @@ mod metrics;
 mod mutation;
 pub(crate) mod raw_query;
 pub mod server;
+mod subscription;
 pub mod test_infra;
 mod types;
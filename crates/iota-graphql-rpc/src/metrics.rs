@@ -83,6 +83,16 @@ pub(crate) struct RequestMetrics {
     pub num_queries_top_level: IntCounterVec,
     /// Total inflight requests
     pub inflight_requests: Gauge,
+    /// Open websocket connections serving subscriptions
+    pub subscription_connections: Gauge,
+    /// Number of requests served from the persisted query registry
+    pub persisted_query_hits: IntCounter,
+    /// Number of requests for queries missing from the persisted query
+    /// registry
+    pub persisted_query_misses: IntCounter,
+    /// Number of requests rejected for exceeding their caller's budget, by
+    /// tier
+    pub rate_limited_requests: IntCounterVec,
 }
 
 impl Metrics {
@@ -261,6 +271,31 @@ impl RequestMetrics {
                 registry
             )
             .unwrap(),
+            subscription_connections: register_gauge_with_registry!(
+                "subscription_connections",
+                "Number of websocket connections serving subscriptions at a moment in time",
+                registry
+            )
+            .unwrap(),
+            persisted_query_hits: register_int_counter_with_registry!(
+                "persisted_query_hits",
+                "Number of requests served from the persisted query registry",
+                registry
+            )
+            .unwrap(),
+            persisted_query_misses: register_int_counter_with_registry!(
+                "persisted_query_misses",
+                "Number of requests for queries missing from the persisted query registry",
+                registry
+            )
+            .unwrap(),
+            rate_limited_requests: register_int_counter_vec_with_registry!(
+                "rate_limited_requests",
+                "Number of requests rejected for exceeding their caller's budget, by tier",
+                &["tier"],
+                registry,
+            )
+            .unwrap(),
         }
     }
 }
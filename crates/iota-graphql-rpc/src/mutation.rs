@@ -18,7 +18,9 @@ use iota_types::{
 };
 
 use crate::{
+    config::ServiceConfig,
     error::Error,
+    extensions::persisted_queries::{AdminToken, PersistedQueryRegistry},
     types::{
         execution_result::ExecutionResult,
         transaction_block_effects::{TransactionBlockEffects, TransactionBlockEffectsKind},
@@ -153,4 +155,35 @@ impl Mutation {
             },
         })
     }
+
+    /// Persist `query`, so that clients can send its hash instead of its text.
+    /// Returns the hex-encoded SHA-256 hash of the query, which clients send
+    /// in the `persistedQuery` extension of their requests.
+    ///
+    /// Requests to register a query must be authorized by sending the
+    /// service's admin token in the `x-iota-rpc-admin-token` header. They are
+    /// accepted even if the service only accepts persisted queries.
+    async fn register_persisted_query(&self, ctx: &Context<'_>, query: String) -> Result<String> {
+        let config: &ServiceConfig = ctx.data_unchecked();
+        let Some(admin_token) = &config.persisted_queries.admin_token else {
+            return Err(Error::Client(
+                "Registering persisted queries is not enabled on this service".to_string(),
+            ))
+            .extend();
+        };
+
+        match ctx.data_opt::<AdminToken>() {
+            Some(token) if token.matches(admin_token) => {}
+            _ => {
+                return Err(Error::Client("Invalid admin token".to_string())).extend();
+            }
+        }
+
+        parser::parse_query(&query)
+            .map_err(|e| Error::Client(format!("Unable to parse query: {e}")))
+            .extend()?;
+
+        let registry: &PersistedQueryRegistry = ctx.data_unchecked();
+        registry.register(query).await.extend()
+    }
 }
@@ -16,26 +16,34 @@ use std::{
 use async_graphql::{
     dataloader::DataLoader,
     extensions::{ApolloTracing, ExtensionFactory, Tracing},
-    EmptySubscription, Schema, SchemaBuilder, ServerError,
+    http::ALL_WEBSOCKET_PROTOCOLS,
+    Data, Schema, SchemaBuilder, ServerError,
 };
-use async_graphql_axum::{GraphQLRequest, GraphQLResponse};
+use async_graphql_axum::{GraphQLProtocol, GraphQLRequest, GraphQLResponse, GraphQLWebSocket};
 use axum::{
     body::Body,
-    extract::{ConnectInfo, FromRef, State},
+    extract::{ConnectInfo, FromRef, State, WebSocketUpgrade},
     http::{HeaderMap, StatusCode},
     middleware::{self},
-    response::IntoResponse,
+    response::{IntoResponse, Response},
     routing::{post, MethodRouter, Route},
     Router,
 };
 use axum_extra::headers::Header as _;
 use http::{HeaderValue, Method, Request};
-use iota_graphql_rpc_headers::{LIMITS_HEADER, VERSION_HEADER};
+use iota_graphql_rpc_headers::{
+    ADMIN_TOKEN_HEADER, API_KEY_HEADER, BUDGET_CAPACITY_HEADER, BUDGET_REMAINING_HEADER,
+    LIMITS_HEADER, QUERY_COST_HEADER, VERSION_HEADER,
+};
 use iota_metrics::spawn_monitored_task;
 use iota_network_stack::callback::{CallbackLayer, MakeCallbackHandler, ResponseHandler};
 use iota_package_resolver::{PackageStoreWithLruCache, Resolver};
 use iota_sdk::IotaClientBuilder;
-use tokio::{join, net::TcpListener, sync::OnceCell};
+use tokio::{
+    join,
+    net::TcpListener,
+    sync::{OnceCell, OwnedSemaphorePermit, Semaphore},
+};
 use tokio_util::sync::CancellationToken;
 use tower::{Layer, Service};
 use tower_http::cors::{AllowOrigin, CorsLayer};
@@ -54,12 +62,15 @@ use crate::{
     extensions::{
         feature_gate::FeatureGate,
         logger::Logger,
+        persisted_queries::{AdminToken, PersistedQueries, PersistedQueryRegistry},
         query_limits_checker::{QueryLimitsChecker, ShowUsage},
+        rate_limiter::{ApiKey, RateLimiter, RateLimits, RequestCost},
         timeout::Timeout,
     },
     metrics::Metrics,
     mutation::Mutation,
     server::version::{check_version_middleware, set_version_middleware},
+    subscription::Subscription,
     types::{
         checkpoint::Checkpoint,
         move_object::IMoveObject,
@@ -137,7 +148,7 @@ impl Server {
 
 pub(crate) struct ServerBuilder {
     state: AppState,
-    schema: SchemaBuilder<Query, Mutation, EmptySubscription>,
+    schema: SchemaBuilder<Query, Mutation, Subscription>,
     router: Option<Router>,
     db_reader: Option<Db>,
 }
@@ -156,6 +167,36 @@ pub(crate) struct AppState {
 #[derive(Clone)]
 pub(crate) struct CheckpointWatermark(pub Arc<AtomicU64>);
 
+/// Permits for the websocket connections serving subscriptions, bounding how
+/// many of them can be open at the same time.
+#[derive(Clone)]
+struct SubscriptionConnections(Arc<Semaphore>);
+
+/// An open subscription connection. Holds one of the service's permits and is
+/// counted in the connection gauge until it is dropped.
+struct SubscriptionConnection {
+    metrics: Metrics,
+    _permit: OwnedSemaphorePermit,
+}
+
+impl SubscriptionConnections {
+    /// Claim a permit for a new connection, or `None` if they are all taken.
+    fn acquire(&self, metrics: &Metrics) -> Option<SubscriptionConnection> {
+        let permit = self.0.clone().try_acquire_owned().ok()?;
+        metrics.request_metrics.subscription_connections.inc();
+        Some(SubscriptionConnection {
+            metrics: metrics.clone(),
+            _permit: permit,
+        })
+    }
+}
+
+impl Drop for SubscriptionConnection {
+    fn drop(&mut self) {
+        self.metrics.request_metrics.subscription_connections.dec();
+    }
+}
+
 impl AppState {
     pub(crate) fn new(
         connection: ConnectionConfig,
@@ -213,20 +254,13 @@ impl ServerBuilder {
         self
     }
 
-    fn build_schema(self) -> Schema<Query, Mutation, EmptySubscription> {
+    fn build_schema(self) -> Schema<Query, Mutation, Subscription> {
         self.schema.finish()
     }
 
     /// Prepares the components of the server to be run. Finalizes the graphql
     /// schema, and expects the `Db` and `Router` to have been initialized.
-    fn build_components(
-        self,
-    ) -> (
-        String,
-        Schema<Query, Mutation, EmptySubscription>,
-        Db,
-        Router,
-    ) {
+    fn build_components(self) -> (String, Schema<Query, Mutation, Subscription>, Db, Router) {
         let address = self.address();
         let ServerBuilder {
             schema,
@@ -247,6 +281,7 @@ impl ServerBuilder {
             let router: Router = Router::new()
                 .route("/", post(graphql_handler))
                 .route("/graphql", post(graphql_handler))
+                .route("/subscriptions", axum::routing::get(subscriptions_handler))
                 .route("/health", axum::routing::get(health_checks))
                 .with_state(self.state.clone())
                 .route_layer(middleware::from_fn_with_state(
@@ -310,6 +345,13 @@ impl ServerBuilder {
                 hyper::header::CONTENT_TYPE,
                 VERSION_HEADER.clone(),
                 LIMITS_HEADER.clone(),
+                API_KEY_HEADER.clone(),
+            ])
+            // Allow clients to read the cost of their requests, and their budget
+            .expose_headers([
+                QUERY_COST_HEADER.clone(),
+                BUDGET_CAPACITY_HEADER.clone(),
+                BUDGET_REMAINING_HEADER.clone(),
             ]);
         Ok(cors)
     }
@@ -317,14 +359,22 @@ impl ServerBuilder {
     /// Consumes the `ServerBuilder` to create a `Server` that can be run.
     pub fn build(self) -> Result<Server, Error> {
         let state = self.state.clone();
-        let (address, schema, db_reader, router) = self.build_components();
 
         // Initialize the checkpoint watermark for the background task to update.
+        // Subscriptions follow it to find out when new data is available.
         let checkpoint_watermark = CheckpointWatermark(Arc::new(AtomicU64::new(0)));
+        let subscription_connections = SubscriptionConnections(Arc::new(Semaphore::new(
+            state.service.limits.max_subscription_connections as usize,
+        )));
+
+        let (address, schema, db_reader, router) = self
+            .context_data(checkpoint_watermark.clone())
+            .build_components();
 
         let app = router
             .layer(axum::extract::Extension(schema))
             .layer(axum::extract::Extension(checkpoint_watermark.clone()))
+            .layer(axum::extract::Extension(subscription_connections))
             .layer(Self::cors()?);
 
         Ok(Server {
@@ -416,6 +466,13 @@ impl ServerBuilder {
             None
         };
 
+        // Persisted queries
+        let persisted_queries =
+            PersistedQueryRegistry::load(config.service.persisted_queries.path.clone())?;
+
+        // Rate limits
+        let rate_limits = RateLimits::load(&config.service.rate_limits)?;
+
         builder = builder
             .context_data(config.service.clone())
             .context_data(DataLoader::new(db.clone(), tokio::spawn))
@@ -428,14 +485,24 @@ impl ServerBuilder {
             .context_data(iota_sdk_client)
             .context_data(zklogin_config)
             .context_data(metrics.clone())
+            .context_data(persisted_queries)
+            .context_data(rate_limits)
             .context_data(config.clone());
 
+        if config.internal_features.persisted_queries {
+            builder = builder.extension(PersistedQueries);
+        }
         if config.internal_features.feature_gate {
             builder = builder.extension(FeatureGate);
         }
         if config.internal_features.logger {
             builder = builder.extension(Logger::default());
         }
+        // The rate limiter charges requests for the cost computed by the query limits
+        // checker, so it needs to be added first.
+        if config.service.rate_limits.enabled {
+            builder = builder.extension(RateLimiter);
+        }
         if config.internal_features.query_limits_checker {
             builder = builder.extension(QueryLimitsChecker::default());
         }
@@ -456,8 +523,8 @@ impl ServerBuilder {
     }
 }
 
-fn schema_builder() -> SchemaBuilder<Query, Mutation, EmptySubscription> {
-    async_graphql::Schema::build(Query, Mutation, EmptySubscription)
+fn schema_builder() -> SchemaBuilder<Query, Mutation, Subscription> {
+    async_graphql::Schema::build(Query, Mutation, Subscription)
         .register_output_type::<IMoveObject>()
         .register_output_type::<IObject>()
         .register_output_type::<IOwner>()
@@ -469,20 +536,36 @@ pub fn export_schema() -> String {
 }
 
 /// Entry point for graphql requests. Each request is stamped with a unique ID,
-/// a `ShowUsage` flag if set in the request headers, and the high checkpoint
-/// watermark as set by the background task.
+/// a `ShowUsage` flag, an `AdminToken` and an `ApiKey` if set in the request
+/// headers, and the high checkpoint watermark as set by the background task.
+/// The response reports the cost charged for the request, and what remains of
+/// the caller's budget, in its headers.
 async fn graphql_handler(
     ConnectInfo(addr): ConnectInfo<SocketAddr>,
     schema: axum::Extension<IotaGraphQLSchema>,
     watermark: axum::Extension<CheckpointWatermark>,
     headers: HeaderMap,
     req: GraphQLRequest,
-) -> (axum::http::Extensions, GraphQLResponse) {
+) -> (HeaderMap, axum::http::Extensions, GraphQLResponse) {
     let mut req = req.into_inner();
     req.data.insert(Uuid::new_v4());
     if headers.contains_key(ShowUsage::name()) {
         req.data.insert(ShowUsage)
     }
+    if let Some(token) = headers
+        .get(&ADMIN_TOKEN_HEADER)
+        .and_then(|token| token.to_str().ok())
+    {
+        req.data.insert(AdminToken(token.to_string()))
+    }
+    if let Some(key) = headers
+        .get(&API_KEY_HEADER)
+        .and_then(|key| key.to_str().ok())
+    {
+        req.data.insert(ApiKey(key.to_string()))
+    }
+    let request_cost = RequestCost::default();
+    req.data.insert(request_cost.clone());
     // Capture the IP address of the client
     // Note: if a load balancer is used it must be configured to forward the client
     // IP address
@@ -502,7 +585,68 @@ async fn graphql_handler(
     if result.is_err() {
         extensions.insert(GraphqlErrors(std::sync::Arc::new(result.errors.clone())));
     };
-    (extensions, result.into())
+    (request_cost.headers(), extensions, result.into())
+}
+
+/// Entry point for subscriptions, which are served over a websocket. Each
+/// connection is stamped with a unique ID and the client's IP address, and
+/// holds one of the service's subscription connection permits for as long as
+/// it stays open. Connections are rejected while no permits are available.
+///
+/// Each subscription is charged against its caller's budget when it is made.
+/// Callers authenticate with an `ApiKey` sent in the request headers, or in
+/// the payload of the connection's init message, under the same name.
+async fn subscriptions_handler(
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    State(metrics): State<Metrics>,
+    schema: axum::Extension<IotaGraphQLSchema>,
+    connections: axum::Extension<SubscriptionConnections>,
+    headers: HeaderMap,
+    protocol: GraphQLProtocol,
+    upgrade: WebSocketUpgrade,
+) -> Response {
+    let Some(connection) = connections.acquire(&metrics) else {
+        return (
+            StatusCode::SERVICE_UNAVAILABLE,
+            "Too many open subscription connections",
+        )
+            .into_response();
+    };
+
+    let api_key = headers
+        .get(&API_KEY_HEADER)
+        .and_then(|key| key.to_str().ok())
+        .map(str::to_string);
+
+    let axum::Extension(schema) = schema;
+    upgrade
+        .protocols(ALL_WEBSOCKET_PROTOCOLS)
+        .on_upgrade(move |stream| async move {
+            let mut data = Data::default();
+            data.insert(Uuid::new_v4());
+            data.insert(addr);
+
+            GraphQLWebSocket::new(stream, schema, protocol)
+                .with_data(data)
+                .on_connection_init(move |payload| async move {
+                    let mut data = Data::default();
+                    let api_key = api_key.or_else(|| {
+                        payload
+                            .get(API_KEY_HEADER.as_str())
+                            .and_then(|key| key.as_str())
+                            .map(str::to_string)
+                    });
+                    if let Some(key) = api_key {
+                        data.insert(ApiKey(key));
+                    }
+                    data.insert(RequestCost::default());
+                    Ok(data)
+                })
+                .serve()
+                .await;
+
+            drop(connection);
+        })
 }
 
 #[derive(Clone)]
@@ -622,15 +766,23 @@ pub mod tests {
 
     use async_graphql::{
         extensions::{Extension, ExtensionContext, NextExecute},
-        Response,
+        value, Request, Response,
+    };
+    use futures::StreamExt;
+    use iota_graphql_rpc_headers::{
+        BUDGET_CAPACITY_HEADER, BUDGET_REMAINING_HEADER, QUERY_COST_HEADER,
     };
     use uuid::Uuid;
 
     use super::*;
     use crate::{
-        config::{ConnectionConfig, Limits, ServiceConfig, Version},
+        config::{BudgetConfig, ConnectionConfig, Limits, ServiceConfig, Version},
         context_data::db_data_provider::PgManager,
-        extensions::{query_limits_checker::QueryLimitsChecker, timeout::Timeout},
+        extensions::{
+            persisted_queries::query_hash, query_limits_checker::QueryLimitsChecker,
+            timeout::Timeout,
+        },
+        types::checkpoint::Checkpoint,
     };
 
     /// Prepares a schema for tests dealing with extensions. Returns a
@@ -963,4 +1115,267 @@ pub mod tests {
         assert_eq!(req_metrics.output_nodes.get_sample_sum(), 2. + 4.);
         assert_eq!(req_metrics.query_depth.get_sample_sum(), 1. + 3.);
     }
+
+    pub async fn test_persisted_queries_impl() {
+        fn persisted(hash: &str) -> Request {
+            let mut request = Request::new("");
+            request.extensions.insert(
+                "persistedQuery".to_string(),
+                value!({ "version": 1, "sha256Hash": hash }),
+            );
+            request
+        }
+
+        let persisted_query = "{ chainIdentifier }";
+        let other_query = "{ serviceConfig { maxQueryDepth } }";
+        let registry = PersistedQueryRegistry::default();
+        let hash = registry
+            .register(persisted_query.to_string())
+            .await
+            .unwrap();
+        assert_eq!(hash, query_hash(persisted_query));
+
+        let mut cfg = ServiceConfig::default();
+        cfg.persisted_queries.allowlist_only = true;
+        cfg.persisted_queries.admin_token = Some("secret".to_string());
+        let server_builder = prep_schema(None, Some(cfg));
+        let metrics = server_builder.state.metrics.clone();
+        let schema = server_builder
+            .context_data(registry)
+            .extension(PersistedQueries)
+            .build_schema();
+
+        // Persisted queries can be sent by hash or in full.
+        schema
+            .execute(persisted(&hash))
+            .await
+            .into_result()
+            .expect("Should complete successfully");
+        schema
+            .execute(persisted_query)
+            .await
+            .into_result()
+            .expect("Should complete successfully");
+
+        // Other queries are rejected.
+        let errs: Vec<_> = schema
+            .execute(persisted(&query_hash(other_query)))
+            .await
+            .into_result()
+            .unwrap_err()
+            .into_iter()
+            .map(|e| e.message)
+            .collect();
+        assert_eq!(errs, vec!["PersistedQueryNotFound".to_string()]);
+
+        let errs: Vec<_> = schema
+            .execute(other_query)
+            .await
+            .into_result()
+            .unwrap_err()
+            .into_iter()
+            .map(|e| e.message)
+            .collect();
+        assert_eq!(
+            errs,
+            vec!["This service only accepts persisted queries".to_string()]
+        );
+
+        let req_metrics = metrics.request_metrics;
+        assert_eq!(req_metrics.persisted_query_hits.get(), 2);
+        assert_eq!(req_metrics.persisted_query_misses.get(), 2);
+
+        // Registering a query is only exempt from the allowlist with the admin token.
+        let register = format!(r#"mutation {{ registerPersistedQuery(query: "{other_query}") }}"#);
+        let errs: Vec<_> = schema
+            .execute(Request::new(&register).data(AdminToken("guess".to_string())))
+            .await
+            .into_result()
+            .unwrap_err()
+            .into_iter()
+            .map(|e| e.message)
+            .collect();
+        assert_eq!(
+            errs,
+            vec!["This service only accepts persisted queries".to_string()]
+        );
+
+        let response = schema
+            .execute(Request::new(&register).data(AdminToken("secret".to_string())))
+            .await;
+        assert_eq!(
+            response.data,
+            value!({ "registerPersistedQuery": query_hash(other_query) })
+        );
+
+        // Once registered, the query is accepted.
+        schema
+            .execute(other_query)
+            .await
+            .into_result()
+            .expect("Should complete successfully");
+    }
+
+    pub async fn test_rate_limits_impl() {
+        let mut cfg = ServiceConfig::default();
+        cfg.rate_limits.enabled = true;
+        cfg.rate_limits.anonymous = BudgetConfig {
+            capacity: 3,
+            refill_per_second: 0,
+        };
+
+        let rate_limits = RateLimits::load(&cfg.rate_limits).unwrap();
+        let server_builder = prep_schema(None, Some(cfg));
+        let metrics = server_builder.state.metrics.clone();
+        let schema = server_builder
+            .context_data(rate_limits)
+            .extension(RateLimiter)
+            .extension(QueryLimitsChecker::default())
+            .build_schema();
+
+        async fn exec(schema: &IotaGraphQLSchema, request: Request) -> (HeaderMap, Response) {
+            let request_cost = RequestCost::default();
+            let response = schema.execute(request.data(request_cost.clone())).await;
+            (request_cost.headers(), response)
+        }
+
+        // Anonymous requests are charged against the anonymous tier's budget.
+        let query = "{ chainIdentifier }";
+        for remaining in [2, 1, 0] {
+            let (headers, response) = exec(&schema, Request::new(query)).await;
+            response
+                .into_result()
+                .expect("Should complete successfully");
+            assert_eq!(headers[&QUERY_COST_HEADER], "1");
+            assert_eq!(headers[&BUDGET_CAPACITY_HEADER], "3");
+            assert_eq!(headers[&BUDGET_REMAINING_HEADER], remaining.to_string());
+        }
+
+        let (headers, response) = exec(&schema, Request::new(query)).await;
+        let errs: Vec<_> = response
+            .into_result()
+            .unwrap_err()
+            .into_iter()
+            .map(|e| e.message)
+            .collect();
+        assert_eq!(
+            errs,
+            vec![
+                "Query cost of 1 exceeds remaining budget of 0. The budget's capacity is 3"
+                    .to_string()
+            ]
+        );
+        assert_eq!(headers[&BUDGET_REMAINING_HEADER], "0");
+
+        // Requests with an unknown API key are rejected.
+        let (headers, response) =
+            exec(&schema, Request::new(query).data(ApiKey("key".to_string()))).await;
+        let errs: Vec<_> = response
+            .into_result()
+            .unwrap_err()
+            .into_iter()
+            .map(|e| e.message)
+            .collect();
+        assert_eq!(errs, vec!["Unknown API key".to_string()]);
+        assert!(headers.is_empty());
+
+        // Subscriptions are charged against the same budget, through the cost tracked
+        // for their connection.
+        let mut connection = Data::default();
+        connection.insert(RequestCost::default());
+        let response = schema
+            .execute_stream_with_session_data(
+                Request::new("subscription { checkpoints { sequenceNumber } }"),
+                Arc::new(connection),
+            )
+            .next()
+            .await
+            .expect("Should respond to the subscription");
+        let errs: Vec<_> = response.errors.into_iter().map(|e| e.message).collect();
+        assert_eq!(errs.len(), 1);
+        assert!(errs[0].ends_with("exceeds remaining budget of 0. The budget's capacity is 3"));
+
+        let req_metrics = metrics.request_metrics;
+        assert_eq!(
+            req_metrics
+                .rate_limited_requests
+                .with_label_values(&["anonymous"])
+                .get(),
+            2
+        );
+    }
+    pub async fn test_subscriptions_impl() {
+        let mut cfg = ServiceConfig::default();
+        cfg.background_tasks.watermark_update_ms = 10;
+
+        let server_builder = prep_schema(None, Some(cfg.clone()));
+        let reader = PgManager::reader(ConnectionConfig::ci_integration_test_cfg().db_url)
+            .expect("Failed to create pg connection pool");
+        let db = Db::new(reader, cfg.limits, server_builder.state.metrics.clone());
+        let latest = Checkpoint::query_latest_at(&db, None)
+            .await
+            .unwrap()
+            .expect("Should have indexed a checkpoint")
+            .sequence_number_impl();
+        assert!(latest >= 2, "Should have indexed at least 3 checkpoints");
+
+        // The subscription starts after the checkpoint at the watermark, even though
+        // later checkpoints have already been indexed.
+        let watermark = CheckpointWatermark(Arc::new(AtomicU64::new(latest - 2)));
+        let schema = server_builder
+            .context_data(watermark.clone())
+            .build_schema();
+
+        let mut stream = schema.execute_stream(Request::new(
+            "subscription { checkpoints { sequenceNumber } }",
+        ));
+        assert!(
+            tokio::time::timeout(Duration::from_millis(100), stream.next())
+                .await
+                .is_err()
+        );
+
+        // Moving the watermark releases the checkpoints up to it, in order.
+        watermark.0.store(latest, Relaxed);
+        for sequence_number in [latest - 1, latest] {
+            let response = tokio::time::timeout(Duration::from_secs(10), stream.next())
+                .await
+                .expect("Should receive a checkpoint")
+                .expect("Stream should stay open");
+            assert_eq!(
+                response.data,
+                value!({ "checkpoints": { "sequenceNumber": sequence_number } })
+            );
+        }
+    }
+
+    pub async fn test_subscription_connections_impl() {
+        let metrics = metrics();
+        let connections = SubscriptionConnections(Arc::new(Semaphore::new(2)));
+        let open = || metrics.request_metrics.subscription_connections.get();
+
+        let first = connections
+            .acquire(&metrics)
+            .expect("Should accept connection");
+        let second = connections
+            .acquire(&metrics)
+            .expect("Should accept connection");
+        assert_eq!(open(), 2.0);
+
+        // Connections beyond the limit are rejected without being counted.
+        assert!(connections.acquire(&metrics).is_none());
+        assert_eq!(open(), 2.0);
+
+        // Closing a connection frees its permit for a new one.
+        drop(first);
+        assert_eq!(open(), 1.0);
+        let third = connections
+            .acquire(&metrics)
+            .expect("Should accept connection");
+        assert_eq!(open(), 2.0);
+
+        drop(second);
+        drop(third);
+        assert_eq!(open(), 0.0);
+    }
 }
@@ -12,7 +12,9 @@ use crate::{
 };
 
 async fn graphiql(ide_title: axum::Extension<Option<String>>) -> impl axum::response::IntoResponse {
-    let gq = async_graphql::http::GraphiQLSource::build().endpoint("/");
+    let gq = async_graphql::http::GraphiQLSource::build()
+        .endpoint("/")
+        .subscription_endpoint("/subscriptions");
     if let axum::Extension(Some(title)) = ide_title {
         axum::response::Html(gq.title(&title).finish())
     } else {
@@ -0,0 +1,311 @@
+// Copyright (c) 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{collections::VecDeque, future::Future, sync::atomic::Ordering::Relaxed, time::Duration};
+
+use async_graphql::{
+    connection::{Connection, CursorType},
+    *,
+};
+use futures::{stream, Stream};
+
+use crate::{
+    config::ServiceConfig,
+    consistency::Checkpointed,
+    data::Db,
+    error::Error,
+    extensions::feature_gate::disabled_feature_message,
+    server::builder::CheckpointWatermark,
+    types::{
+        checkpoint::{self, Checkpoint, CheckpointCursor},
+        cursor::Page,
+        event::{self, Event, EventFilter, EventKey},
+        transaction_block::{
+            self, TransactionBlock, TransactionBlockCursor, TransactionBlockFilter,
+        },
+    },
+};
+
+pub struct Subscription;
+
+/// Subscriptions push data to the client as it is indexed. Each subscription
+/// starts after the latest checkpoint the service has indexed at the time it
+/// is made, and is served over a GraphQL-over-websocket connection.
+#[Subscription]
+impl Subscription {
+    /// Checkpoints, in order, as they are indexed.
+    async fn checkpoints(
+        &self,
+        ctx: &Context<'_>,
+    ) -> Result<impl Stream<Item = Result<Checkpoint>>> {
+        Tail::new(ctx, "checkpoints")?
+            .follow(|db, page, checkpoint_viewed_at| async move {
+                Checkpoint::paginate(&db, page, None, Some(checkpoint_viewed_at)).await
+            })
+            .await
+    }
+
+    /// Transaction blocks that match `filter`, in the order they were
+    /// executed, as they are indexed.
+    async fn transactions(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<TransactionBlockFilter>,
+    ) -> Result<impl Stream<Item = Result<TransactionBlock>>> {
+        let filter = filter.unwrap_or_default();
+        Tail::new(ctx, "transactions")?
+            .follow(move |db, page, checkpoint_viewed_at| {
+                let filter = filter.clone();
+                async move {
+                    TransactionBlock::paginate(&db, page, filter, Some(checkpoint_viewed_at)).await
+                }
+            })
+            .await
+    }
+
+    /// Events that match `filter`, in the order they were emitted, as they are
+    /// indexed.
+    async fn events(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<EventFilter>,
+    ) -> Result<impl Stream<Item = Result<Event>>> {
+        let filter = filter.unwrap_or_default();
+        Tail::new(ctx, "events")?
+            .follow(move |db, page, checkpoint_viewed_at| {
+                let filter = filter.clone();
+                async move { Event::paginate(&db, page, filter, Some(checkpoint_viewed_at)).await }
+            })
+            .await
+    }
+}
+
+/// Cursors that a subscription can resume from once the checkpoint watermark
+/// has moved past the checkpoint they were viewed at.
+trait Resumable: Checkpointed + Sized {
+    /// The same position in the results, viewed at `checkpoint_viewed_at`.
+    fn resume_at(&self, checkpoint_viewed_at: u64) -> Self;
+
+    /// The position after the last result of `checkpoint`, viewed at
+    /// `checkpoint_viewed_at`, or `None` if no result can precede it.
+    fn after_checkpoint(checkpoint: &Checkpoint, checkpoint_viewed_at: u64) -> Option<Self>;
+}
+
+/// Follows the service's checkpoint watermark on behalf of a subscription,
+/// fetching the results that were added each time it moves forward.
+struct Tail {
+    db: Db,
+    config: ServiceConfig,
+    watermark: CheckpointWatermark,
+    poll_interval: Duration,
+}
+
+/// Progress of a subscription's stream.
+struct TailState<C, T> {
+    tail: Tail,
+    /// Cursor of the last result fetched, if any.
+    cursor: Option<C>,
+    /// The checkpoint that results are currently fetched at.
+    checkpoint_viewed_at: u64,
+    /// Whether all results up to `checkpoint_viewed_at` have been fetched.
+    caught_up: bool,
+    /// Results fetched but not yet sent to the subscriber.
+    buffer: VecDeque<T>,
+}
+
+impl Tail {
+    fn new(ctx: &Context<'_>, field: &str) -> Result<Self> {
+        let config: &ServiceConfig = ctx.data_unchecked();
+        if let Some(message) = disabled_feature_message(config, "Subscription", field) {
+            return Err(Error::Client(message).extend());
+        }
+
+        let watermark: &CheckpointWatermark = ctx
+            .data()
+            .map_err(|_| Error::Internal("Unable to fetch checkpoint watermark".to_string()))
+            .extend()?;
+
+        Ok(Self {
+            db: ctx.data_unchecked::<Db>().clone(),
+            config: config.clone(),
+            watermark: watermark.clone(),
+            poll_interval: Duration::from_millis(config.background_tasks.watermark_update_ms),
+        })
+    }
+
+    /// The latest checkpoint the service can produce consistent results for.
+    fn checkpoint_viewed_at(&self) -> u64 {
+        self.watermark.0.load(Relaxed)
+    }
+
+    /// Stream the results of `fetch` that come after the current watermark.
+    /// `fetch` is called with a page of results to fetch, and the checkpoint
+    /// to fetch them at. The stream is positioned after the last checkpoint
+    /// indexed at the time of the call, and each subsequent page starts
+    /// after the last result of the previous one.
+    async fn follow<C, T, F, Fut>(self, fetch: F) -> Result<impl Stream<Item = Result<T>>>
+    where
+        C: Resumable + Clone + Send + Sync + 'static,
+        T: Send + 'static,
+        F: Fn(Db, Page<C>, u64) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<Connection<String, T>, Error>> + Send,
+    {
+        let checkpoint_viewed_at = self.checkpoint_viewed_at();
+        let latest = Checkpoint::query_latest_at(&self.db, Some(checkpoint_viewed_at))
+            .await
+            .extend()?;
+
+        let state = TailState {
+            cursor: latest.and_then(|cp| C::after_checkpoint(&cp, checkpoint_viewed_at)),
+            checkpoint_viewed_at,
+            caught_up: true,
+            buffer: VecDeque::new(),
+            tail: self,
+        };
+
+        Ok(stream::unfold(
+            (state, fetch),
+            |(mut state, fetch)| async move {
+                loop {
+                    if let Some(result) = state.buffer.pop_front() {
+                        return Some((Ok(result), (state, fetch)));
+                    }
+
+                    if state.caught_up {
+                        state.wait_for_checkpoint().await;
+                    }
+
+                    match state.fetch_next(&fetch).await {
+                        Ok(()) => continue,
+                        Err(e) => {
+                            // Retry from the same cursor once the watermark has moved again.
+                            state.caught_up = true;
+                            return Some((Err(e.extend()), (state, fetch)));
+                        }
+                    }
+                }
+            },
+        ))
+    }
+}
+
+impl<C, T> TailState<C, T>
+where
+    C: Resumable + Clone,
+{
+    /// Wait until the watermark moves past the checkpoint results have been
+    /// fetched at.
+    async fn wait_for_checkpoint(&mut self) {
+        loop {
+            tokio::time::sleep(self.tail.poll_interval).await;
+            let checkpoint_viewed_at = self.tail.checkpoint_viewed_at();
+            if checkpoint_viewed_at > self.checkpoint_viewed_at {
+                self.checkpoint_viewed_at = checkpoint_viewed_at;
+                self.caught_up = false;
+                return;
+            }
+        }
+    }
+
+    /// Fetch the next page of results after the cursor, up to the checkpoint
+    /// results are currently fetched at.
+    async fn fetch_next<F, Fut>(&mut self, fetch: &F) -> Result<(), Error>
+    where
+        F: Fn(Db, Page<C>, u64) -> Fut,
+        Fut: Future<Output = Result<Connection<String, T>, Error>>,
+    {
+        let after = self
+            .cursor
+            .as_ref()
+            .map(|c| c.resume_at(self.checkpoint_viewed_at));
+        let page = Page::from_params(
+            &self.tail.config,
+            Some(self.tail.config.limits.max_page_size),
+            after,
+            None,
+            None,
+        )
+        .map_err(|e| Error::Internal(e.message))?;
+
+        let conn = fetch(self.tail.db.clone(), page, self.checkpoint_viewed_at).await?;
+        if let Some(cursor) = last_cursor(&conn)? {
+            self.cursor = Some(cursor);
+        }
+
+        self.caught_up = !conn.has_next_page;
+        self.buffer
+            .extend(conn.edges.into_iter().map(|edge| edge.node));
+        Ok(())
+    }
+}
+
+/// The decoded cursor of the last result in `conn`, if it has any results.
+fn last_cursor<C: CursorType, T>(conn: &Connection<String, T>) -> Result<Option<C>, Error> {
+    conn.edges
+        .last()
+        .map(|edge| {
+            C::decode_cursor(&edge.cursor)
+                .map_err(|_| Error::Internal(format!("Invalid cursor: {}", edge.cursor)))
+        })
+        .transpose()
+}
+
+impl Resumable for checkpoint::Cursor {
+    fn resume_at(&self, checkpoint_viewed_at: u64) -> Self {
+        checkpoint::Cursor::new(CheckpointCursor {
+            checkpoint_viewed_at,
+            sequence_number: self.sequence_number,
+        })
+    }
+
+    fn after_checkpoint(checkpoint: &Checkpoint, checkpoint_viewed_at: u64) -> Option<Self> {
+        Some(checkpoint::Cursor::new(CheckpointCursor {
+            checkpoint_viewed_at,
+            sequence_number: checkpoint.sequence_number_impl(),
+        }))
+    }
+}
+
+impl Resumable for transaction_block::Cursor {
+    fn resume_at(&self, checkpoint_viewed_at: u64) -> Self {
+        transaction_block::Cursor::new(TransactionBlockCursor {
+            checkpoint_viewed_at,
+            tx_sequence_number: self.tx_sequence_number,
+            tx_checkpoint_number: self.tx_checkpoint_number,
+        })
+    }
+
+    fn after_checkpoint(checkpoint: &Checkpoint, checkpoint_viewed_at: u64) -> Option<Self> {
+        let tx_sequence_number = checkpoint
+            .network_total_transactions_impl()
+            .checked_sub(1)?;
+        Some(transaction_block::Cursor::new(TransactionBlockCursor {
+            checkpoint_viewed_at,
+            tx_sequence_number,
+            tx_checkpoint_number: checkpoint.sequence_number_impl(),
+        }))
+    }
+}
+
+impl Resumable for event::Cursor {
+    fn resume_at(&self, checkpoint_viewed_at: u64) -> Self {
+        event::Cursor::new(EventKey {
+            tx: self.tx,
+            e: self.e,
+            checkpoint_viewed_at,
+        })
+    }
+
+    fn after_checkpoint(checkpoint: &Checkpoint, checkpoint_viewed_at: u64) -> Option<Self> {
+        // Event sequence numbers are stored as `i64`, so this is past the last
+        // event of the checkpoint's last transaction.
+        let tx = checkpoint
+            .network_total_transactions_impl()
+            .checked_sub(1)?;
+        Some(event::Cursor::new(EventKey {
+            tx,
+            e: i64::MAX as u64,
+            checkpoint_viewed_at,
+        }))
+    }
+}
@@ -161,22 +161,23 @@ pub(crate) fn get_value_from_move_struct(
     // update this as we iterate through the parts
     let start_value = &MoveValue::Struct(move_struct.clone());
 
-    let result = parts
-        .iter()
-        .try_fold(start_value, |current_value, part| match current_value {
-            MoveValue::Struct(s) => s
-                .fields
-                .iter()
-                .find_map(|(id, value)| {
-                    if id.as_str() == *part {
-                        Some(value)
-                    } else {
-                        None
-                    }
-                })
-                .ok_or_else(|| DisplayRenderError::FieldNotFound(part.to_string())),
-            _ => Err(DisplayRenderError::UnexpectedMoveValue),
-        })?;
+    let result = parts.iter().try_fold(start_value, |current_value, part| {
+        let fields = match current_value {
+            MoveValue::Struct(s) => &s.fields,
+            MoveValue::Variant(v) => &v.fields,
+            _ => return Err(DisplayRenderError::UnexpectedMoveValue),
+        };
+        fields
+            .iter()
+            .find_map(|(id, value)| {
+                if id.as_str() == *part {
+                    Some(value)
+                } else {
+                    None
+                }
+            })
+            .ok_or_else(|| DisplayRenderError::FieldNotFound(part.to_string()))
+    })?;
 
     // TODO: move off dependency on IotaMoveValue
     let iota_move_value: IotaMoveValue = result.clone().into();
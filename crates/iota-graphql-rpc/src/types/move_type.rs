@@ -53,6 +53,15 @@ type MoveTypeLayout =
         type: string,
         fields: [{ name: string, layout: MoveTypeLayout }],
       }
+    }
+  | {
+      enum: {
+        type: string,
+        variants: [{
+          name: string,
+          fields: [{ name: string, layout: MoveTypeLayout }],
+        }]
+      }
     }"
 );
 
@@ -91,6 +100,7 @@ pub(crate) enum MoveTypeLayout {
     U256,
     Vector(Box<MoveTypeLayout>),
     Struct(MoveStructLayout),
+    Enum(MoveEnumLayout),
 }
 
 #[derive(Serialize, Deserialize)]
@@ -100,6 +110,19 @@ pub(crate) struct MoveStructLayout {
     fields: Vec<MoveFieldLayout>,
 }
 
+#[derive(Serialize, Deserialize)]
+pub(crate) struct MoveEnumLayout {
+    #[serde(rename = "type")]
+    type_: String,
+    variants: Vec<MoveVariantLayout>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub(crate) struct MoveVariantLayout {
+    name: String,
+    fields: Vec<MoveFieldLayout>,
+}
+
 #[derive(Serialize, Deserialize)]
 pub(crate) struct MoveFieldLayout {
     name: String,
@@ -241,6 +264,7 @@ impl TryFrom<A::MoveTypeLayout> for MoveTypeLayout {
 
             TL::Vector(v) => Self::Vector(Box::new(Self::try_from(*v)?)),
             TL::Struct(s) => Self::Struct(s.try_into()?),
+            TL::Enum(e) => Self::Enum(e.try_into()?),
         })
     }
 }
@@ -260,6 +284,36 @@ impl TryFrom<A::MoveStructLayout> for MoveStructLayout {
     }
 }
 
+impl TryFrom<A::MoveEnumLayout> for MoveEnumLayout {
+    type Error = Error;
+
+    fn try_from(layout: A::MoveEnumLayout) -> Result<Self, Error> {
+        Ok(Self {
+            type_: layout.type_.to_canonical_string(/* with_prefix */ true),
+            variants: layout
+                .variants
+                .into_iter()
+                .map(MoveVariantLayout::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl TryFrom<A::MoveVariantLayout> for MoveVariantLayout {
+    type Error = Error;
+
+    fn try_from(layout: A::MoveVariantLayout) -> Result<Self, Error> {
+        Ok(Self {
+            name: layout.name.to_string(),
+            fields: layout
+                .fields
+                .into_iter()
+                .map(MoveFieldLayout::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
 impl TryFrom<A::MoveFieldLayout> for MoveFieldLayout {
     type Error = Error;
 
@@ -57,7 +57,11 @@ type MoveData =
   | { String:  string }
   | { Vector:  [MoveData] }
   | { Option:   MoveData? }
-  | { Struct:  [{ name: string, value: MoveData }] }"
+  | { Struct:  [{ name: string, value: MoveData }] }
+  | { Variant: {
+      name: string,
+      fields: [{ name: string, value: MoveData }],
+  }"
 );
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -73,6 +77,13 @@ pub(crate) enum MoveData {
     Vector(Vec<MoveData>),
     Option(Option<Box<MoveData>>),
     Struct(Vec<MoveField>),
+    Variant(MoveVariant),
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub(crate) struct MoveVariant {
+    name: String,
+    fields: Vec<MoveField>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -106,6 +117,8 @@ impl MoveValue {
     /// - u64, u128, and u256 are represented as JSON strings.
     /// - Vectors are represented by JSON arrays.
     /// - Structs are represented by JSON objects.
+    /// - Enums are represented by JSON objects, with a field named `@variant`
+    ///   indicating the active variant.
     /// - Empty optional values are represented by `null`.
     ///
     /// This form is offered as a less verbose convenience in cases where the
@@ -199,6 +212,19 @@ impl TryFrom<A::MoveValue> for MoveData {
                 }
             }
 
+            V::Variant(A::MoveVariant {
+                variant_name,
+                fields,
+                ..
+            }) => {
+                let fields: Result<Vec<_>, _> =
+                    fields.into_iter().map(MoveField::try_from).collect();
+                Self::Variant(MoveVariant {
+                    name: variant_name.to_string(),
+                    fields: fields?,
+                })
+            }
+
             // Iota does not support `signer` as a type.
             V::Signer(_) => return Err(unexpected_signer_error()),
         })
@@ -271,6 +297,26 @@ fn try_to_json_value(value: A::MoveValue) -> Result<Value, Error> {
             }
         }
 
+        V::Variant(A::MoveVariant {
+            variant_name,
+            fields,
+            ..
+        }) => {
+            // Enum variants, with the name of the active variant alongside its fields
+            let variant = (
+                Name::new("@variant"),
+                Value::String(variant_name.to_string()),
+            );
+            let fields = fields
+                .into_iter()
+                .map(|(name, value)| Ok((Name::new(name.to_string()), try_to_json_value(value)?)));
+            Value::Object(
+                std::iter::once(Ok(variant))
+                    .chain(fields)
+                    .collect::<Result<_, Error>>()?,
+            )
+        }
+
         // Iota does not support `signer` as a type.
         V::Signer(_) => return Err(unexpected_signer_error()),
     })
@@ -43,6 +43,7 @@ use crate::{
     data::{Db, QueryExecutor},
     error::Error,
     mutation::Mutation,
+    subscription::Subscription,
     types::{
         base64::Base64 as GraphQLBase64,
         zklogin_verify_signature::{
@@ -52,7 +53,7 @@ use crate::{
 };
 
 pub(crate) struct Query;
-pub(crate) type IotaGraphQLSchema = async_graphql::Schema<Query, Mutation, EmptySubscription>;
+pub(crate) type IotaGraphQLSchema = async_graphql::Schema<Query, Mutation, Subscription>;
 
 #[Object]
 impl Query {
@@ -757,4 +757,46 @@ mod tests {
     async fn test_query_complexity_metrics() {
         test_query_complexity_metrics_impl().await;
     }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_persisted_queries() {
+        test_persisted_queries_impl().await;
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_rate_limits() {
+        test_rate_limits_impl().await;
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_subscriptions() {
+        let rng = StdRng::from_seed([12; 32]);
+        let mut sim = Simulacrum::new_with_rng(rng);
+        for _ in 0..3 {
+            sim.create_checkpoint();
+        }
+
+        let connection_config = ConnectionConfig::ci_integration_test_cfg();
+        let cluster = iota_graphql_rpc::test_infra::cluster::serve_executor(
+            connection_config,
+            DEFAULT_INTERNAL_DATA_SOURCE_PORT,
+            Arc::new(sim),
+            None,
+        )
+        .await;
+        cluster
+            .wait_for_checkpoint_catchup(3, Duration::from_secs(10))
+            .await;
+
+        test_subscriptions_impl().await;
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_subscription_connections() {
+        test_subscription_connections_impl().await;
+    }
 }
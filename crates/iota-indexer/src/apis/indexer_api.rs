@@ -2,7 +2,12 @@
 // Modifications Copyright (c) 2024 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+#[cfg(feature = "subscriptions")]
+use std::sync::Arc;
+
 use async_trait::async_trait;
+#[cfg(feature = "subscriptions")]
+use iota_core::subscription_handler::SubscriptionHandler;
 use iota_json_rpc::IotaRpcModule;
 use iota_json_rpc_api::{cap_page_limit, internal_error, IndexerApiServer};
 use iota_json_rpc_types::{
@@ -26,11 +31,28 @@ use crate::indexer_reader::IndexerReader;
 
 pub(crate) struct IndexerApi {
     inner: IndexerReader,
+    #[cfg(feature = "subscriptions")]
+    subscription_handler: Option<Arc<SubscriptionHandler>>,
 }
 
 impl IndexerApi {
     pub fn new(inner: IndexerReader) -> Self {
-        Self { inner }
+        Self {
+            inner,
+            #[cfg(feature = "subscriptions")]
+            subscription_handler: None,
+        }
+    }
+
+    /// Serves event and transaction subscriptions from `subscription_handler`.
+    /// Without a handler subscription requests are rejected.
+    #[cfg(feature = "subscriptions")]
+    pub fn with_subscription_handler(
+        mut self,
+        subscription_handler: Arc<SubscriptionHandler>,
+    ) -> Self {
+        self.subscription_handler = Some(subscription_handler);
+        self
     }
 
     async fn get_owned_objects_internal(
@@ -289,13 +311,34 @@ impl IndexerApiServer for IndexerApi {
         ))
     }
 
-    async fn subscribe_event(&self, _sink: PendingSubscriptionSink, _filter: EventFilter) {}
+    async fn subscribe_event(&self, sink: PendingSubscriptionSink, filter: EventFilter) {
+        #[cfg(feature = "subscriptions")]
+        if let Some(subscription_handler) = &self.subscription_handler {
+            iota_json_rpc::indexer_api::spawn_subscription(
+                sink,
+                subscription_handler.subscribe_events(filter),
+                None,
+            );
+        }
+        #[cfg(not(feature = "subscriptions"))]
+        let _ = (sink, filter);
+    }
 
     async fn subscribe_transaction(
         &self,
-        _sink: PendingSubscriptionSink,
-        _filter: TransactionFilter,
+        sink: PendingSubscriptionSink,
+        filter: TransactionFilter,
     ) {
+        #[cfg(feature = "subscriptions")]
+        if let Some(subscription_handler) = &self.subscription_handler {
+            iota_json_rpc::indexer_api::spawn_subscription(
+                sink,
+                subscription_handler.subscribe_transactions(filter),
+                None,
+            );
+        }
+        #[cfg(not(feature = "subscriptions"))]
+        let _ = (sink, filter);
     }
 }
 
@@ -163,6 +163,8 @@ async fn commit_checkpoints<S>(
         metrics.total_epoch_committed.inc();
     }
 
+    // Checkpoints must be persisted last: readers, e.g. the subscription
+    // dispatcher, rely on a visible checkpoint having all its data committed.
     state
         .persist_checkpoints(checkpoint_batch)
         .await
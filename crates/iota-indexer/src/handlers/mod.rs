@@ -14,6 +14,8 @@ use crate::{
 
 pub mod checkpoint_handler;
 pub mod committer;
+#[cfg(feature = "subscriptions")]
+pub mod subscription_dispatcher;
 pub mod tx_processor;
 
 #[derive(Debug)]
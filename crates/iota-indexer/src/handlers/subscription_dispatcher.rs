@@ -0,0 +1,196 @@
+// Copyright (c) 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{sync::Arc, time::Duration};
+
+use iota_core::subscription_handler::SubscriptionHandler;
+use iota_json_rpc_types::{IotaTransactionBlockEffects, IotaTransactionBlockEvents};
+use iota_types::{messages_checkpoint::CheckpointSequenceNumber, transaction::TransactionData};
+use tokio::time::MissedTickBehavior;
+use tracing::{error, info, warn};
+
+use crate::{indexer_reader::IndexerReader, types::IndexerResult};
+
+const SUBSCRIPTION_POLL_INTERVAL_MS: u64 = 100;
+
+/// Follows the checkpoints committed by the indexer writer and pushes their
+/// transactions and events to the json-rpc subscribers.
+///
+/// The committer persists a checkpoint row only after all of its
+/// transactions and events were written, so a checkpoint that is visible in
+/// the `checkpoints` table can be streamed in full. Subscribers only receive
+/// data committed after they subscribed.
+///
+/// Backpressure is handled by the [`SubscriptionHandler`]: dispatching waits
+/// while its queue is full, and subscribers that can't keep up are dropped.
+pub struct SubscriptionDispatcher {
+    reader: IndexerReader,
+    subscription_handler: Arc<SubscriptionHandler>,
+    poll_interval: Duration,
+}
+
+impl SubscriptionDispatcher {
+    pub fn new(reader: IndexerReader, subscription_handler: Arc<SubscriptionHandler>) -> Self {
+        let poll_interval_ms = std::env::var("SUBSCRIPTION_POLL_INTERVAL_MS")
+            .unwrap_or(SUBSCRIPTION_POLL_INTERVAL_MS.to_string())
+            .parse::<u64>()
+            .unwrap();
+        Self {
+            reader,
+            subscription_handler,
+            poll_interval: Duration::from_millis(poll_interval_ms),
+        }
+    }
+
+    pub async fn run(self) {
+        info!("Indexer subscription dispatcher started...");
+        let mut interval = tokio::time::interval(self.poll_interval);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        let mut next_checkpoint = None;
+        loop {
+            interval.tick().await;
+            let latest_checkpoint = match self.latest_committed_checkpoint().await {
+                Ok(latest_checkpoint) => latest_checkpoint,
+                Err(e) => {
+                    warn!("Failed to get latest committed checkpoint with error: {e}");
+                    continue;
+                }
+            };
+            // Start streaming right after the checkpoint that was committed when the
+            // dispatcher started, historical data is served by the query endpoints.
+            let next_checkpoint =
+                next_checkpoint.get_or_insert_with(|| latest_checkpoint.map_or(0, |seq| seq + 1));
+            let Some(latest_checkpoint) = latest_checkpoint else {
+                continue;
+            };
+
+            while *next_checkpoint <= latest_checkpoint {
+                if let Err(e) = self.dispatch_checkpoint(*next_checkpoint).await {
+                    // Retry the same checkpoint on the next tick so that subscribers
+                    // don't observe gaps.
+                    error!(
+                        "Failed to dispatch checkpoint {} to subscribers with error: {e}",
+                        next_checkpoint
+                    );
+                    break;
+                }
+                *next_checkpoint += 1;
+            }
+        }
+    }
+
+    async fn latest_committed_checkpoint(&self) -> IndexerResult<Option<CheckpointSequenceNumber>> {
+        let checkpoints = self
+            .reader
+            .spawn_blocking(|this| this.get_checkpoints(None, 1, true))
+            .await?;
+        Ok(checkpoints
+            .first()
+            .map(|checkpoint| checkpoint.sequence_number))
+    }
+
+    async fn dispatch_checkpoint(&self, checkpoint: CheckpointSequenceNumber) -> IndexerResult<()> {
+        let transactions = self
+            .reader
+            .get_checkpoint_transactions_for_streaming_in_blocking_task(checkpoint)
+            .await?;
+        dispatch_transactions(&self.subscription_handler, transactions).await
+    }
+}
+
+/// Pushes the transactions of a checkpoint and their events to the
+/// subscribers, in checkpoint order.
+async fn dispatch_transactions(
+    subscription_handler: &SubscriptionHandler,
+    transactions: Vec<(
+        TransactionData,
+        IotaTransactionBlockEffects,
+        IotaTransactionBlockEvents,
+    )>,
+) -> IndexerResult<()> {
+    for (transaction_data, effects, events) in transactions {
+        subscription_handler
+            .process_tx(&transaction_data, &effects, &events)
+            .await?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::{Stream, StreamExt};
+    use iota_json_rpc_types::{
+        EventFilter, IotaEvent, IotaTransactionBlockEffectsAPI, TransactionFilter,
+    };
+    use iota_types::{
+        base_types::{random_object_ref, IotaAddress},
+        digests::TransactionDigest,
+        effects::{TransactionEffects, TransactionEffectsAPI},
+    };
+    use prometheus::Registry;
+
+    use super::*;
+
+    fn transaction(
+        sender: IotaAddress,
+    ) -> (
+        TransactionData,
+        IotaTransactionBlockEffects,
+        IotaTransactionBlockEvents,
+    ) {
+        let data = TransactionData::new_transfer_iota(
+            IotaAddress::random_for_testing_only(),
+            sender,
+            None,
+            random_object_ref(),
+            1_000_000,
+            1_000,
+        );
+        let digest = TransactionDigest::random();
+        let mut effects = TransactionEffects::default();
+        *effects.transaction_digest_mut_for_testing() = digest;
+        let event = IotaEvent {
+            sender,
+            ..IotaEvent::random_for_testing()
+        };
+        (
+            data,
+            effects.try_into().unwrap(),
+            IotaTransactionBlockEvents { data: vec![event] },
+        )
+    }
+
+    async fn next_within_timeout<T>(stream: &mut (impl Stream<Item = T> + Unpin)) -> Option<T> {
+        tokio::time::timeout(Duration::from_millis(500), stream.next())
+            .await
+            .ok()
+            .flatten()
+    }
+
+    #[tokio::test]
+    async fn dispatches_only_matching_transactions_and_events() {
+        let subscription_handler = SubscriptionHandler::new(&Registry::new());
+        let sender = IotaAddress::random_for_testing_only();
+        let mut transactions = Box::pin(
+            subscription_handler.subscribe_transactions(TransactionFilter::FromAddress(sender)),
+        );
+        let mut events =
+            Box::pin(subscription_handler.subscribe_events(EventFilter::Sender(sender)));
+
+        let matching = transaction(sender);
+        let other = transaction(IotaAddress::random_for_testing_only());
+        let matching_digest = *matching.1.transaction_digest();
+        dispatch_transactions(&subscription_handler, vec![other, matching.clone()])
+            .await
+            .unwrap();
+
+        let effects = next_within_timeout(&mut transactions).await.unwrap();
+        assert_eq!(effects.transaction_digest(), &matching_digest);
+        let event = next_within_timeout(&mut events).await.unwrap();
+        assert_eq!(event, matching.2.data[0]);
+
+        assert!(next_within_timeout(&mut transactions).await.is_none());
+        assert!(next_within_timeout(&mut events).await.is_none());
+    }
+}
@@ -17,8 +17,8 @@ use fastcrypto::encoding::{Encoding, Hex};
 use iota_json_rpc_types::{
     AddressMetrics, Balance, CheckpointId, Coin as IotaCoin, DisplayFieldsResponse, EpochInfo,
     EventFilter, IotaCoinMetadata, IotaEvent, IotaObjectDataFilter, IotaTransactionBlockEffects,
-    IotaTransactionBlockEffectsAPI, IotaTransactionBlockResponse, MoveCallMetrics,
-    MoveFunctionName, NetworkMetrics, TransactionFilter,
+    IotaTransactionBlockEffectsAPI, IotaTransactionBlockEvents, IotaTransactionBlockResponse,
+    MoveCallMetrics, MoveFunctionName, NetworkMetrics, TransactionFilter,
 };
 use iota_types::{
     balance::Supply,
@@ -32,6 +32,7 @@ use iota_types::{
     is_system_package,
     move_package::MovePackage,
     object::{MoveObject, Object, ObjectRead},
+    transaction::{SenderSignedData, TransactionData},
 };
 use itertools::{any, Itertools};
 use move_core_types::{annotated_value::MoveStructLayout, language_storage::StructTag};
@@ -1056,6 +1057,15 @@ impl IndexerReader {
                 .first::<(i64, Vec<Option<Vec<u8>>>)>(conn)
         })?;
 
+        self.serialized_events_to_iota_events(digest, timestamp_ms, serialized_events)
+    }
+
+    fn serialized_events_to_iota_events(
+        &self,
+        digest: TransactionDigest,
+        timestamp_ms: i64,
+        serialized_events: Vec<Option<Vec<u8>>>,
+    ) -> Result<Vec<iota_json_rpc_types::IotaEvent>, IndexerError> {
         let events = serialized_events
             .into_iter()
             .flatten()
@@ -1269,6 +1279,74 @@ impl IndexerReader {
             .collect()
     }
 
+    /// Loads the transactions of a committed checkpoint in execution order,
+    /// together with their effects and events, in the shape consumed by the
+    /// subscription handler.
+    fn get_checkpoint_transactions_for_streaming_impl(
+        &self,
+        checkpoint_seq: u64,
+    ) -> IndexerResult<
+        Vec<(
+            TransactionData,
+            IotaTransactionBlockEffects,
+            IotaTransactionBlockEvents,
+        )>,
+    > {
+        let stored_txes = self
+            .run_query(|conn| {
+                transactions::table
+                    .filter(transactions::checkpoint_sequence_number.eq(checkpoint_seq as i64))
+                    .order(transactions::tx_sequence_number.asc())
+                    .load::<StoredTransaction>(conn)
+            })
+            .and_then(|transactions| {
+                transactions
+                    .into_iter()
+                    .map(|store| store.set_genesis_large_object_as_inner_data(&self.pool))
+                    .collect::<Result<Vec<_>, _>>()
+            })?;
+
+        stored_txes
+            .into_iter()
+            .map(|stored_tx| {
+                let sender_signed_data: SenderSignedData =
+                    bcs::from_bytes(&stored_tx.raw_transaction).map_err(|e| {
+                        IndexerError::PersistentStorageDataCorruptionError(format!(
+                            "Can't convert raw_transaction of {} into SenderSignedData. Error: {e}",
+                            stored_tx.tx_sequence_number
+                        ))
+                    })?;
+                let effects = stored_tx.try_into_iota_transaction_effects()?;
+                let events = self.serialized_events_to_iota_events(
+                    *effects.transaction_digest(),
+                    stored_tx.timestamp_ms,
+                    stored_tx.events,
+                )?;
+                Ok((
+                    sender_signed_data.transaction_data().clone(),
+                    effects,
+                    IotaTransactionBlockEvents { data: events },
+                ))
+            })
+            .collect()
+    }
+
+    pub async fn get_checkpoint_transactions_for_streaming_in_blocking_task(
+        &self,
+        checkpoint_seq: u64,
+    ) -> IndexerResult<
+        Vec<(
+            TransactionData,
+            IotaTransactionBlockEffects,
+            IotaTransactionBlockEvents,
+        )>,
+    > {
+        self.spawn_blocking(move |this| {
+            this.get_checkpoint_transactions_for_streaming_impl(checkpoint_seq)
+        })
+        .await
+    }
+
     pub async fn get_transaction_events_in_blocking_task(
         &self,
         digest: TransactionDigest,
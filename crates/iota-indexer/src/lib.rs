@@ -4,12 +4,24 @@
 #![recursion_limit = "256"]
 
 use std::net::SocketAddr;
+#[cfg(feature = "subscriptions")]
+use std::sync::Arc;
 
 use anyhow::{anyhow, Result};
 use clap::Parser;
 use errors::IndexerError;
-use iota_json_rpc::{JsonRpcServerBuilder, ServerHandle, ServerType};
+#[cfg(feature = "subscriptions")]
+use iota_core::subscription_handler::SubscriptionHandler;
+use iota_json_rpc::{
+    axum_router::{
+        DEFAULT_MAX_BATCH_RESPONSE_SIZE, DEFAULT_MAX_BATCH_SIZE,
+        DEFAULT_MAX_SUBSCRIPTIONS_PER_CONNECTION,
+    },
+    JsonRpcServerBuilder, ServerHandle,
+};
 use iota_json_rpc_api::CLIENT_SDK_TYPE_HEADER;
+#[cfg(feature = "subscriptions")]
+use iota_metrics::spawn_monitored_task;
 use jsonrpsee::http_client::{HeaderMap, HeaderValue, HttpClient, HttpClientBuilder};
 use metrics::IndexerMetrics;
 use prometheus::Registry;
@@ -17,6 +29,8 @@ use tokio::runtime::Handle;
 use tracing::warn;
 use url::Url;
 
+#[cfg(feature = "subscriptions")]
+use crate::handlers::subscription_dispatcher::SubscriptionDispatcher;
 use crate::{
     apis::{
         CoinReadApi, ExtendedApi, GovernanceReadApi, IndexerApi, MoveUtilsApi, ReadApi,
@@ -69,6 +83,17 @@ pub struct IndexerConfig {
     pub rpc_server_url: String,
     #[clap(long, default_value = "9000", global = true)]
     pub rpc_server_port: u16,
+    /// Maximum number of active event and transaction subscriptions per
+    /// websocket connection.
+    #[clap(long, default_value_t = DEFAULT_MAX_SUBSCRIPTIONS_PER_CONNECTION, global = true)]
+    pub max_subscriptions_per_connection: u32,
+    /// Maximum number of calls in a JSON-RPC batch request, zero disables
+    /// batch requests.
+    #[clap(long, default_value_t = DEFAULT_MAX_BATCH_SIZE, global = true)]
+    pub max_batch_size: u32,
+    /// Maximum size in bytes of the response to a JSON-RPC batch request.
+    #[clap(long, default_value_t = DEFAULT_MAX_BATCH_RESPONSE_SIZE, global = true)]
+    pub max_batch_response_size: u32,
     #[clap(long)]
     pub reset_db: bool,
     #[clap(long)]
@@ -136,6 +161,9 @@ impl Default for IndexerConfig {
             client_metric_port: 9184,
             rpc_server_url: "0.0.0.0".to_string(),
             rpc_server_port: 9000,
+            max_subscriptions_per_connection: DEFAULT_MAX_SUBSCRIPTIONS_PER_CONNECTION,
+            max_batch_size: DEFAULT_MAX_BATCH_SIZE,
+            max_batch_response_size: DEFAULT_MAX_BATCH_RESPONSE_SIZE,
             reset_db: false,
             fullnode_sync_worker: true,
             rpc_server_worker: true,
@@ -150,11 +178,24 @@ pub async fn build_json_rpc_server(
     config: &IndexerConfig,
     custom_runtime: Option<Handle>,
 ) -> Result<ServerHandle, IndexerError> {
-    let mut builder = JsonRpcServerBuilder::new(env!("CARGO_PKG_VERSION"), prometheus_registry);
+    let mut builder = JsonRpcServerBuilder::new(env!("CARGO_PKG_VERSION"), prometheus_registry)
+        .with_max_subscriptions_per_connection(config.max_subscriptions_per_connection)
+        .with_max_batch_size(config.max_batch_size)
+        .with_max_batch_response_size(config.max_batch_response_size);
     let http_client = crate::get_http_client(config.rpc_client_url.as_str())?;
 
+    let indexer_api = IndexerApi::new(reader.clone());
+    #[cfg(feature = "subscriptions")]
+    let indexer_api = {
+        let subscription_handler = Arc::new(SubscriptionHandler::new(prometheus_registry));
+        let subscription_dispatcher =
+            SubscriptionDispatcher::new(reader.clone(), subscription_handler.clone());
+        spawn_monitored_task!(subscription_dispatcher.run());
+        indexer_api.with_subscription_handler(subscription_handler)
+    };
+
     builder.register_module(WriteApi::new(http_client.clone()))?;
-    builder.register_module(IndexerApi::new(reader.clone()))?;
+    builder.register_module(indexer_api)?;
     builder.register_module(TransactionBuilderApi::new(reader.clone()))?;
     builder.register_module(MoveUtilsApi::new(reader.clone()))?;
     builder.register_module(GovernanceReadApi::new(reader.clone()))?;
@@ -167,8 +208,10 @@ pub async fn build_json_rpc_server(
         config.rpc_server_url.as_str().parse().unwrap(),
         config.rpc_server_port,
     );
+    // Serve both http and websocket requests, the latter are needed for
+    // subscriptions.
     Ok(builder
-        .start(default_socket_addr, custom_runtime, Some(ServerType::Http))
+        .start(default_socket_addr, custom_runtime, None)
         .await?)
 }
 
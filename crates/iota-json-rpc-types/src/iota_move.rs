@@ -23,7 +23,7 @@ use move_binary_format::{
     },
 };
 use move_core_types::{
-    annotated_value::{MoveStruct, MoveValue},
+    annotated_value::{MoveStruct, MoveValue, MoveVariant},
     identifier::Identifier,
     language_storage::StructTag,
 };
@@ -318,6 +318,7 @@ pub enum IotaMoveValue {
     UID { id: ObjectID },
     Struct(IotaMoveStruct),
     Option(Box<Option<IotaMoveValue>>),
+    Variant(IotaMoveVariant),
 }
 
 impl IotaMoveValue {
@@ -332,6 +333,7 @@ impl IotaMoveValue {
             IotaMoveValue::String(v) => json!(v),
             IotaMoveValue::UID { id } => json!({ "id": id }),
             IotaMoveValue::Option(v) => json!(v),
+            IotaMoveValue::Variant(v) => v.to_json_value(),
         }
     }
 }
@@ -347,6 +349,7 @@ impl Display for IotaMoveValue {
             IotaMoveValue::UID { id } => write!(writer, "{id}")?,
             IotaMoveValue::Struct(value) => write!(writer, "{value}")?,
             IotaMoveValue::Option(value) => write!(writer, "{value:?}")?,
+            IotaMoveValue::Variant(value) => write!(writer, "{value}")?,
             IotaMoveValue::Vector(vec) => {
                 write!(
                     writer,
@@ -383,6 +386,7 @@ impl From<MoveValue> for IotaMoveValue {
             MoveValue::Signer(value) | MoveValue::Address(value) => {
                 IotaMoveValue::Address(IotaAddress::from(ObjectID::from(value)))
             }
+            MoveValue::Variant(value) => IotaMoveValue::Variant(value.into()),
         }
     }
 }
@@ -481,6 +485,72 @@ impl Display for IotaMoveStruct {
     }
 }
 
+#[serde_as]
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Eq, PartialEq)]
+#[serde(rename = "MoveVariant")]
+pub struct IotaMoveVariant {
+    #[schemars(with = "String")]
+    #[serde(rename = "type")]
+    #[serde_as(as = "IotaStructTag")]
+    pub type_: StructTag,
+    pub variant: String,
+    pub fields: BTreeMap<String, IotaMoveValue>,
+}
+
+impl IotaMoveVariant {
+    /// Extract the variant name and field values from MoveVariant without
+    /// type information in json format
+    pub fn to_json_value(self) -> Value {
+        let fields = self
+            .fields
+            .into_iter()
+            .map(|(key, value)| (key, value.to_json_value()))
+            .collect::<BTreeMap<_, _>>();
+        json!({
+            "variant": self.variant,
+            "fields": fields,
+        })
+    }
+}
+
+impl Display for IotaMoveVariant {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut writer = String::new();
+        let IotaMoveVariant {
+            type_,
+            variant,
+            fields,
+        } = self;
+        writeln!(writer)?;
+        writeln!(writer, "  {}: {type_}", "type".bold().bright_black())?;
+        writeln!(writer, "  {}: {variant}", "variant".bold().bright_black())?;
+        for (name, value) in fields {
+            let value = format!("{value}");
+            let value = if value.starts_with('\n') {
+                indent(&value, 2)
+            } else {
+                value
+            };
+            writeln!(writer, "  {}: {value}", name.bold().bright_black())?;
+        }
+        write!(f, "{}", writer.trim_end_matches('\n'))
+    }
+}
+
+impl From<MoveVariant> for IotaMoveVariant {
+    fn from(value: MoveVariant) -> Self {
+        IotaMoveVariant {
+            type_: value.type_,
+            variant: value.variant_name.into_string(),
+            fields: value
+                .fields
+                .into_iter()
+                .map(|(id, value)| (id.into_string(), value.into()))
+                .collect(),
+        }
+    }
+}
+
 fn indent<T: Display>(d: &T, indent: usize) -> String {
     d.to_string()
         .lines()
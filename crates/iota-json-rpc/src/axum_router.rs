@@ -2,20 +2,24 @@
 // Modifications Copyright (c) 2024 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use std::sync::Arc;
+use std::{future::Future, sync::Arc};
 
 use axum::{
     body::Body,
     extract::{Json, State},
 };
+use futures::StreamExt;
 use hyper::HeaderMap;
 use iota_json_rpc_api::CLIENT_TARGET_API_VERSION_HEADER;
 use jsonrpsee::{
     core::server::{helpers::MethodSink, Methods},
     server::RandomIntegerIdProvider,
     types::{
-        error::{ErrorCode, BATCHES_NOT_SUPPORTED_CODE, BATCHES_NOT_SUPPORTED_MSG},
-        ErrorObject, Id, InvalidRequest, Params, Request,
+        error::{
+            reject_too_big_batch_request, reject_too_big_batch_response, ErrorCode,
+            BATCHES_NOT_SUPPORTED_CODE, BATCHES_NOT_SUPPORTED_MSG,
+        },
+        ErrorObject, Id, InvalidRequest, Notification, Params, Request,
     },
     BoundedSubscriptions, ConnectionId, Extensions, MethodCallback, MethodKind, MethodResponse,
 };
@@ -28,6 +32,16 @@ use crate::{
 
 pub const MAX_RESPONSE_SIZE: u32 = 2 << 30;
 
+/// Default number of active subscriptions a single websocket connection may
+/// hold at once.
+pub const DEFAULT_MAX_SUBSCRIPTIONS_PER_CONNECTION: u32 = 100;
+
+/// Default maximum number of calls in a batch request.
+pub const DEFAULT_MAX_BATCH_SIZE: u32 = 50;
+
+/// Default maximum size in bytes of the response to a batch request.
+pub const DEFAULT_MAX_BATCH_RESPONSE_SIZE: u32 = 10 << 20;
+
 #[derive(Clone, Debug)]
 pub struct JsonRpcService<L> {
     logger: L,
@@ -38,6 +52,15 @@ pub struct JsonRpcService<L> {
     methods: Methods,
     extensions: Extensions,
     rpc_router: RpcRouter,
+
+    /// Maximum number of active subscriptions per websocket connection.
+    max_subscriptions_per_connection: u32,
+
+    /// Maximum number of calls in a batch request, batches are rejected if
+    /// zero.
+    max_batch_size: u32,
+    /// Maximum size in bytes of the response to a batch request.
+    max_batch_response_size: u32,
 }
 
 impl<L> JsonRpcService<L> {
@@ -48,8 +71,26 @@ impl<L> JsonRpcService<L> {
             logger,
             extensions,
             id_provider: Arc::new(RandomIntegerIdProvider),
+            max_subscriptions_per_connection: DEFAULT_MAX_SUBSCRIPTIONS_PER_CONNECTION,
+            max_batch_size: DEFAULT_MAX_BATCH_SIZE,
+            max_batch_response_size: DEFAULT_MAX_BATCH_RESPONSE_SIZE,
         }
     }
+
+    pub fn with_max_subscriptions_per_connection(mut self, max: u32) -> Self {
+        self.max_subscriptions_per_connection = max;
+        self
+    }
+
+    pub fn with_max_batch_size(mut self, max: u32) -> Self {
+        self.max_batch_size = max;
+        self
+    }
+
+    pub fn with_max_batch_response_size(mut self, max: u32) -> Self {
+        self.max_batch_response_size = max;
+        self
+    }
 }
 
 impl<L: Logger> JsonRpcService<L> {
@@ -117,27 +158,139 @@ pub async fn json_rpc_handler<L: Logger>(
         .and_then(|h| h.to_str().ok());
     let response = process_raw_request(&service, api_version, raw_request.get()).await;
 
-    ok_response(response.into_result())
+    // A batch of notifications only is answered with an empty body.
+    ok_response(response.unwrap_or_default())
 }
 
 async fn process_raw_request<L: Logger>(
     service: &JsonRpcService<L>,
     api_version: Option<&str>,
     raw_request: &str,
-) -> MethodResponse {
+) -> Option<String> {
     if let Ok(request) = serde_json::from_str::<Request>(raw_request) {
-        process_request(request, api_version, service.call_data()).await
-    } else if let Ok(_batch) = serde_json::from_str::<Vec<&RawValue>>(raw_request) {
-        MethodResponse::error(
-            Id::Null,
-            ErrorObject::borrowed(BATCHES_NOT_SUPPORTED_CODE, BATCHES_NOT_SUPPORTED_MSG, None),
+        Some(
+            process_request(request, api_version, service.call_data())
+                .await
+                .into_result(),
         )
+    } else if let Ok(batch) = serde_json::from_str::<Vec<&RawValue>>(raw_request) {
+        // All calls of the batch share the version header and the start of the
+        // request.
+        let call_data = service.call_data();
+        process_batch(
+            batch,
+            service.max_batch_size,
+            service.max_batch_response_size,
+            |request| {
+                let call_data = call_data.clone();
+                async move { Some(process_request(request, api_version, call_data).await) }
+            },
+        )
+        .await
     } else {
         let (id, code) = prepare_error(raw_request);
-        MethodResponse::error(id, ErrorObject::from(code))
+        Some(MethodResponse::error(id, ErrorObject::from(code)).into_result())
     }
 }
 
+/// Processes the calls of a batch request concurrently and joins their
+/// responses in the order of the calls.
+///
+/// Notifications are executed like the other calls but not answered, so
+/// `None` is returned if the batch only holds notifications. Batches with more
+/// than `max_batch_size` calls, or whose responses exceed `max_response_size`
+/// bytes, are rejected as a whole with a single error response.
+async fn process_batch<'a, F, Fut>(
+    batch: Vec<&'a RawValue>,
+    max_batch_size: u32,
+    max_response_size: u32,
+    process: F,
+) -> Option<String>
+where
+    F: Fn(Request<'a>) -> Fut,
+    Fut: Future<Output = Option<MethodResponse>>,
+{
+    if max_batch_size == 0 {
+        return Some(
+            MethodResponse::error(
+                Id::Null,
+                ErrorObject::borrowed(BATCHES_NOT_SUPPORTED_CODE, BATCHES_NOT_SUPPORTED_MSG, None),
+            )
+            .into_result(),
+        );
+    }
+    if batch.is_empty() {
+        return Some(
+            MethodResponse::error(Id::Null, ErrorObject::from(ErrorCode::InvalidRequest))
+                .into_result(),
+        );
+    }
+    if batch.len() > max_batch_size as usize {
+        return Some(
+            MethodResponse::error(
+                Id::Null,
+                reject_too_big_batch_request(max_batch_size as usize),
+            )
+            .into_result(),
+        );
+    }
+
+    let concurrency = batch.len();
+    let calls = batch.into_iter().map(|raw_call| {
+        let call = match serde_json::from_str::<Request>(raw_call.get()) {
+            Ok(request) => Ok((process(request), true)),
+            Err(_) => {
+                match serde_json::from_str::<Notification<Option<&RawValue>>>(raw_call.get()) {
+                    Ok(notification) => {
+                        let request =
+                            Request::new(notification.method, notification.params, Id::Null);
+                        Ok((process(request), false))
+                    }
+                    Err(_) => Err(prepare_error(raw_call.get())),
+                }
+            }
+        };
+        async move {
+            match call {
+                Ok((response, true)) => response.await,
+                // Notifications are valid calls whose response is dropped.
+                Ok((response, false)) => {
+                    response.await;
+                    None
+                }
+                Err((id, code)) => Some(MethodResponse::error(id, ErrorObject::from(code))),
+            }
+        }
+    });
+    let mut responses = futures::stream::iter(calls).buffered(concurrency);
+
+    let mut batch_response = String::from("[");
+    while let Some(response) = responses.next().await {
+        let Some(response) = response else {
+            continue;
+        };
+        // Account for the separator and the closing bracket.
+        if batch_response.len() + response.as_result().len() + 2 > max_response_size as usize {
+            return Some(
+                MethodResponse::error(
+                    Id::Null,
+                    reject_too_big_batch_response(max_response_size as usize),
+                )
+                .into_result(),
+            );
+        }
+        if batch_response.len() > 1 {
+            batch_response.push(',');
+        }
+        batch_response.push_str(response.as_result());
+    }
+    if batch_response.len() == 1 {
+        return None;
+    }
+    batch_response.push(']');
+    Some(batch_response)
+}
+
 async fn process_request<L: Logger>(
     req: Request<'_>,
     api_version: Option<&str>,
@@ -289,7 +442,8 @@ pub mod ws {
         #[allow(clippy::disallowed_methods)]
         let (tx, mut rx) = mpsc::channel::<String>(MAX_WS_MESSAGE_BUFFER);
         let sink = MethodSink::new_with_limit(tx, MAX_RESPONSE_SIZE);
-        let bounded_subscriptions = BoundedSubscriptions::new(100);
+        let bounded_subscriptions =
+            BoundedSubscriptions::new(service.max_subscriptions_per_connection);
 
         loop {
             tokio::select! {
@@ -299,7 +453,7 @@ pub mod ws {
                             let response =
                                 process_raw_request(&service, &msg, bounded_subscriptions.clone(), &sink).await;
                             if let Some(response) = response {
-                                sink.send(response.into_result()).await.ok();
+                                sink.send(response).await.ok();
                             }
                         }
                     } else {
@@ -320,17 +474,23 @@ pub mod ws {
         raw_request: &str,
         bounded_subscriptions: BoundedSubscriptions,
         sink: &MethodSink,
-    ) -> Option<MethodResponse> {
+    ) -> Option<String> {
         if let Ok(request) = serde_json::from_str::<Request>(raw_request) {
-            process_request(request, service.ws_call_data(bounded_subscriptions, sink)).await
-        } else if let Ok(_batch) = serde_json::from_str::<Vec<&RawValue>>(raw_request) {
-            Some(MethodResponse::error(
-                Id::Null,
-                ErrorObject::borrowed(BATCHES_NOT_SUPPORTED_CODE, BATCHES_NOT_SUPPORTED_MSG, None),
-            ))
+            process_request(request, service.ws_call_data(bounded_subscriptions, sink))
+                .await
+                .map(MethodResponse::into_result)
+        } else if let Ok(batch) = serde_json::from_str::<Vec<&RawValue>>(raw_request) {
+            let call_data = service.ws_call_data(bounded_subscriptions, sink);
+            process_batch(
+                batch,
+                service.max_batch_size,
+                service.max_batch_response_size,
+                |request| process_request(request, call_data.clone()),
+            )
+            .await
         } else {
             let (id, code) = prepare_error(raw_request);
-            Some(MethodResponse::error(id, ErrorObject::from(code)))
+            Some(MethodResponse::error(id, ErrorObject::from(code)).into_result())
         }
     }
 
@@ -468,3 +628,158 @@ pub mod ws {
         response
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::HashMap,
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    use jsonrpsee::{types::ErrorObjectOwned, RpcModule};
+    use serde_json::{json, Value};
+
+    use super::*;
+
+    fn test_service() -> JsonRpcService<()> {
+        let mut module = RpcModule::new(());
+        module
+            .register_method("test_echo", |params, _, _| params.one::<String>())
+            .unwrap();
+        JsonRpcService::new(
+            module.into(),
+            RpcRouter::new(HashMap::new(), false),
+            (),
+            Extensions::new(),
+        )
+    }
+
+    async fn call(service: &JsonRpcService<()>, request: Value) -> Option<Value> {
+        process_raw_request(service, None, &request.to_string())
+            .await
+            .map(|response| serde_json::from_str(&response).unwrap())
+    }
+
+    fn echo(id: u64, value: &str) -> Value {
+        json!({ "jsonrpc": "2.0", "id": id, "method": "test_echo", "params": [value] })
+    }
+
+    fn error_code(response: &Value) -> i64 {
+        response["error"]["code"].as_i64().unwrap()
+    }
+
+    #[tokio::test]
+    async fn batch_request() {
+        let service = test_service();
+        let response = call(
+            &service,
+            json!([
+                echo(1, "a"),
+                { "jsonrpc": "2.0", "method": "test_echo", "params": ["notification"] },
+                { "jsonrpc": "2.0", "id": 2, "method": "unknown_method" },
+                echo(3, "c"),
+                { "foo": "bar" },
+            ]),
+        )
+        .await
+        .unwrap();
+
+        let responses = response.as_array().unwrap();
+        assert_eq!(responses.len(), 4);
+        assert_eq!(responses[0]["id"], 1);
+        assert_eq!(responses[0]["result"], "a");
+        assert_eq!(responses[1]["id"], 2);
+        assert_eq!(
+            error_code(&responses[1]),
+            ErrorCode::MethodNotFound.code() as i64
+        );
+        assert_eq!(responses[2]["id"], 3);
+        assert_eq!(responses[2]["result"], "c");
+        assert_eq!(
+            error_code(&responses[3]),
+            ErrorCode::InvalidRequest.code() as i64
+        );
+    }
+
+    #[tokio::test]
+    async fn batch_of_notifications() {
+        let service = test_service();
+        let notification = json!({ "jsonrpc": "2.0", "method": "test_echo", "params": ["a"] });
+        assert!(call(&service, json!([notification.clone(), notification]))
+            .await
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn batch_notifications_are_executed() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let mut module = RpcModule::new(calls.clone());
+        module
+            .register_method("test_count", |_, calls, _| {
+                Ok::<_, ErrorObjectOwned>(calls.fetch_add(1, Ordering::SeqCst) + 1)
+            })
+            .unwrap();
+        let service = JsonRpcService::new(
+            module.into(),
+            RpcRouter::new(HashMap::new(), false),
+            (),
+            Extensions::new(),
+        );
+
+        let notification = json!({ "jsonrpc": "2.0", "method": "test_count" });
+        let response = call(
+            &service,
+            json!([
+                notification.clone(),
+                { "jsonrpc": "2.0", "id": 1, "method": "test_count" },
+                notification.clone(),
+            ]),
+        )
+        .await
+        .unwrap();
+        let responses = response.as_array().unwrap();
+        assert_eq!(responses.len(), 1);
+        assert_eq!(responses[0]["id"], 1);
+        // Only the call is answered, but the notifications are executed too.
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+
+        assert!(call(&service, json!([notification])).await.is_none());
+        assert_eq!(calls.load(Ordering::SeqCst), 4);
+    }
+
+    #[tokio::test]
+    async fn empty_batch() {
+        let service = test_service();
+        let response = call(&service, json!([])).await.unwrap();
+        assert_eq!(
+            error_code(&response),
+            ErrorCode::InvalidRequest.code() as i64
+        );
+    }
+
+    #[tokio::test]
+    async fn batch_limits() {
+        let service = test_service().with_max_batch_size(2);
+        let response = call(&service, json!([echo(1, "a"), echo(2, "b"), echo(3, "c")]))
+            .await
+            .unwrap();
+        assert_eq!(
+            error_code(&response),
+            reject_too_big_batch_request(2).code() as i64
+        );
+
+        let service = test_service().with_max_batch_response_size(64);
+        let long_value = "a".repeat(64);
+        let response = call(&service, json!([echo(1, "a"), echo(2, &long_value)]))
+            .await
+            .unwrap();
+        assert_eq!(
+            error_code(&response),
+            reject_too_big_batch_response(64).code() as i64
+        );
+
+        let service = test_service().with_max_batch_size(0);
+        let response = call(&service, json!([echo(1, "a")])).await.unwrap();
+        assert_eq!(error_code(&response), BATCHES_NOT_SUPPORTED_CODE as i64);
+    }
+}
@@ -28,7 +28,10 @@ use tower_http::{
 use tracing::{debug, info};
 
 use crate::{
-    axum_router::{json_rpc_handler, ws::ws_json_rpc_upgrade},
+    axum_router::{
+        json_rpc_handler, ws::ws_json_rpc_upgrade, DEFAULT_MAX_BATCH_RESPONSE_SIZE,
+        DEFAULT_MAX_BATCH_SIZE, DEFAULT_MAX_SUBSCRIPTIONS_PER_CONNECTION,
+    },
     error::Error,
     metrics::MetricsLogger,
     routing_layer::RpcRouter,
@@ -58,6 +61,9 @@ pub struct JsonRpcServerBuilder {
     module: RpcModule<()>,
     rpc_doc: Project,
     registry: Registry,
+    max_subscriptions_per_connection: u32,
+    max_batch_size: u32,
+    max_batch_response_size: u32,
 }
 
 pub fn iota_rpc_doc(version: &str) -> Project {
@@ -84,9 +90,32 @@ impl JsonRpcServerBuilder {
             module: RpcModule::new(()),
             rpc_doc: iota_rpc_doc(version),
             registry: prometheus_registry.clone(),
+            max_subscriptions_per_connection: DEFAULT_MAX_SUBSCRIPTIONS_PER_CONNECTION,
+            max_batch_size: DEFAULT_MAX_BATCH_SIZE,
+            max_batch_response_size: DEFAULT_MAX_BATCH_RESPONSE_SIZE,
         }
     }
 
+    /// Limits the number of subscriptions a single websocket connection can
+    /// hold open at the same time.
+    pub fn with_max_subscriptions_per_connection(mut self, max: u32) -> Self {
+        self.max_subscriptions_per_connection = max;
+        self
+    }
+
+    /// Limits the number of calls in a batch request. Batch requests are
+    /// rejected if the limit is zero.
+    pub fn with_max_batch_size(mut self, max: u32) -> Self {
+        self.max_batch_size = max;
+        self
+    }
+
+    /// Limits the size in bytes of the response to a batch request.
+    pub fn with_max_batch_response_size(mut self, max: u32) -> Self {
+        self.max_batch_response_size = max;
+        self
+    }
+
     pub fn register_module<T: IotaRpcModule>(&mut self, module: T) -> Result<(), Error> {
         self.rpc_doc.add_module(T::rpc_doc_module());
         Ok(self.module.merge(module.rpc())?)
@@ -170,7 +199,10 @@ impl JsonRpcServerBuilder {
             rpc_router,
             metrics_logger,
             Extensions::new(),
-        );
+        )
+        .with_max_subscriptions_per_connection(self.max_subscriptions_per_connection)
+        .with_max_batch_size(self.max_batch_size)
+        .with_max_batch_response_size(self.max_batch_response_size);
 
         let mut router = axum::Router::new();
 
@@ -1306,6 +1306,13 @@ fn get_value_from_move_struct(
                     )))?;
                 }
             }
+            IotaMoveValue::Variant(move_variant) => {
+                if let Some(value) = move_variant.fields.get(part) {
+                    current_value = value;
+                } else {
+                    Err(anyhow!("Field value {var_name} cannot be found in variant"))?;
+                }
+            }
             _ => {
                 Err(Error::UnexpectedError(format!(
                     "Unexpected move value type for field {var_name}"
@@ -30,7 +30,7 @@ use move_bytecode_utils::resolve_struct;
 pub use move_core_types::annotated_value::MoveTypeLayout;
 use move_core_types::{
     account_address::AccountAddress,
-    annotated_value::{MoveFieldLayout, MoveStruct, MoveStructLayout, MoveValue},
+    annotated_value::{MoveFieldLayout, MoveStruct, MoveStructLayout, MoveValue, MoveVariant},
     ident_str,
     identifier::{IdentStr, Identifier},
     language_storage::{StructTag, TypeTag},
@@ -451,6 +451,20 @@ fn move_value_to_json(move_value: &MoveValue) -> Option<JsonValue> {
                 json!(fields)
             }
         },
+        MoveValue::Variant(MoveVariant {
+            variant_name,
+            fields,
+            ..
+        }) => {
+            let fields = fields
+                .iter()
+                .map(|(key, value)| (key, move_value_to_json(value)))
+                .collect::<BTreeMap<_, _>>();
+            json!({
+                "variant": variant_name,
+                "fields": fields,
+            })
+        }
     })
 }
 
@@ -0,0 +1,121 @@
+// Copyright (c) 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Passphrase based encryption of keystore files.
+//!
+//! The content of an encrypted keystore file is encrypted with AES-256-GCM,
+//! using a key derived from the passphrase with scrypt. The file stores the
+//! scrypt parameters, the salt and the nonce next to the ciphertext:
+//!
+//! ```json
+//! {
+//!   "version": 1,
+//!   "kdf": { "logN": 15, "r": 8, "p": 1, "salt": "<base64>" },
+//!   "nonce": "<base64>",
+//!   "ciphertext": "<base64>"
+//! }
+//! ```
+
+use aes_gcm::{
+    aead::{Aead, KeyInit},
+    Aes256Gcm, Nonce,
+};
+use anyhow::{anyhow, ensure};
+use fastcrypto::encoding::{Base64, Encoding};
+use rand::{rngs::OsRng, RngCore};
+use serde::{Deserialize, Serialize};
+
+/// The environment variable holding the passphrase of an encrypted keystore.
+/// If it is set, the keystore is unlocked without prompting.
+pub const IOTA_KEYSTORE_PASSPHRASE_ENV: &str = "IOTA_KEYSTORE_PASSPHRASE";
+
+const ENCRYPTED_KEYSTORE_VERSION: u8 = 1;
+const KEY_LENGTH: usize = 32;
+const SALT_LENGTH: usize = 32;
+const NONCE_LENGTH: usize = 12;
+
+/// The scrypt parameters used to derive the encryption key from the
+/// passphrase.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct KdfParams {
+    pub log_n: u8,
+    pub r: u32,
+    pub p: u32,
+    pub salt: String,
+}
+
+impl KdfParams {
+    /// The recommended scrypt parameters for interactive use, with a fresh
+    /// random salt.
+    fn generate() -> Self {
+        let mut salt = [0u8; SALT_LENGTH];
+        OsRng.fill_bytes(&mut salt);
+        Self {
+            log_n: 15,
+            r: 8,
+            p: 1,
+            salt: Base64::encode(salt),
+        }
+    }
+
+    fn derive_key(&self, passphrase: &str) -> Result<Aes256Gcm, anyhow::Error> {
+        let params = scrypt::Params::new(self.log_n, self.r, self.p, KEY_LENGTH)
+            .map_err(|e| anyhow!("Invalid key derivation parameters: {e}"))?;
+        let salt = Base64::decode(&self.salt).map_err(|e| anyhow!("Invalid salt: {e}"))?;
+        let mut key = [0u8; KEY_LENGTH];
+        scrypt::scrypt(passphrase.as_bytes(), &salt, &params, &mut key)
+            .map_err(|e| anyhow!("Cannot derive the encryption key: {e}"))?;
+        Aes256Gcm::new_from_slice(&key).map_err(|e| anyhow!("Invalid encryption key: {e}"))
+    }
+}
+
+/// The content of an encrypted keystore file.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct EncryptedKeystore {
+    pub version: u8,
+    pub kdf: KdfParams,
+    pub nonce: String,
+    pub ciphertext: String,
+}
+
+impl EncryptedKeystore {
+    /// Encrypts `plaintext` with a key derived from `passphrase`, using a
+    /// fresh salt and nonce.
+    pub fn encrypt(plaintext: &[u8], passphrase: &str) -> Result<Self, anyhow::Error> {
+        ensure!(!passphrase.is_empty(), "The passphrase must not be empty");
+        let kdf = KdfParams::generate();
+        let cipher = kdf.derive_key(passphrase)?;
+
+        let mut nonce = [0u8; NONCE_LENGTH];
+        OsRng.fill_bytes(&mut nonce);
+        let ciphertext = cipher
+            .encrypt(Nonce::from_slice(&nonce), plaintext)
+            .map_err(|e| anyhow!("Cannot encrypt the keystore: {e}"))?;
+
+        Ok(Self {
+            version: ENCRYPTED_KEYSTORE_VERSION,
+            kdf,
+            nonce: Base64::encode(nonce),
+            ciphertext: Base64::encode(ciphertext),
+        })
+    }
+
+    /// Decrypts the content with a key derived from `passphrase`. Fails if the
+    /// passphrase is wrong or the content has been tampered with.
+    pub fn decrypt(&self, passphrase: &str) -> Result<Vec<u8>, anyhow::Error> {
+        ensure!(
+            self.version == ENCRYPTED_KEYSTORE_VERSION,
+            "Unsupported encrypted keystore version: {}",
+            self.version
+        );
+        let cipher = self.kdf.derive_key(passphrase)?;
+        let nonce = Base64::decode(&self.nonce).map_err(|e| anyhow!("Invalid nonce: {e}"))?;
+        ensure!(nonce.len() == NONCE_LENGTH, "Invalid nonce length");
+        let ciphertext =
+            Base64::decode(&self.ciphertext).map_err(|e| anyhow!("Invalid ciphertext: {e}"))?;
+        cipher
+            .decrypt(Nonce::from_slice(&nonce), ciphertext.as_slice())
+            .map_err(|_| anyhow!("Cannot decrypt the keystore, the passphrase may be wrong"))
+    }
+}
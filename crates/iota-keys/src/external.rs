@@ -0,0 +1,564 @@
+// Copyright (c) 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! A keystore whose private keys are held by an external signer, e.g. a
+//! hardware wallet or a remote signing service.
+//!
+//! The keystore only stores the key ids, public keys and aliases of the
+//! registered keys. Signing requests are forwarded to the signer, using the
+//! following protocol. Every request is a single JSON object, answered by a
+//! single JSON object:
+//!
+//! - `{"method": "keys"}` lists the keys held by the signer, answered by
+//!   `{"result": [{"key_id": "...", "public_key": "..."}]}`, where `public_key`
+//!   is the Base64 encoded `flag || pk`.
+//! - `{"method": "sign", "params": {"key_id": "...", "public_key": "...",
+//!   "message": "..."}}` signs the Base64 encoded `message` with the given key,
+//!   answered by `{"result": {"signature": "..."}}`, where `signature` is the
+//!   Base64 encoded `flag || sig || pk`. The message must be signed the same
+//!   way as with an [`IotaKeyPair`] of the key's scheme. For transactions and
+//!   personal messages, `message` is already the Blake2b256 digest of the
+//!   intent message.
+//!
+//! Errors are reported as `{"error": "..."}`.
+//!
+//! A [`ExternalSigner::Command`] signer is spawned for every request, which
+//! is written to its stdin. The response is read from its stdout. A
+//! [`ExternalSigner::UnixSocket`] signer is connected to for every request,
+//! and requests and responses are terminated by a newline. A request fails if
+//! the signer does not respond within its `timeout_secs`, and a command
+//! signer is killed then.
+//!
+//! [`IotaKeyPair`]: iota_types::crypto::IotaKeyPair
+
+use std::{
+    collections::{BTreeMap, HashSet},
+    fs,
+    fs::File,
+    io::{BufReader, Read, Write},
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    sync::mpsc,
+    time::Duration,
+};
+
+use anyhow::{anyhow, bail, Context};
+use fastcrypto::{
+    encoding::{Base64, Encoding},
+    hash::HashFunction,
+    traits::ToFromBytes,
+};
+use iota_types::{
+    base_types::IotaAddress,
+    crypto::{DefaultHash, EncodeDecodeBase64, IotaKeyPair, IotaSignature, PublicKey, Signature},
+};
+use serde::{de::DeserializeOwned, Deserialize, Deserializer, Serialize, Serializer};
+use shared_crypto::intent::{Intent, IntentMessage};
+
+use crate::{
+    keystore::{validate_alias, AccountKeystore, Alias},
+    random_names::random_name,
+};
+
+/// The default time the signer has to answer a request. Hardware wallets
+/// wait for the user to confirm, so this is generous.
+pub const DEFAULT_EXTERNAL_SIGNER_TIMEOUT_SECS: u64 = 120;
+
+fn default_timeout_secs() -> u64 {
+    DEFAULT_EXTERNAL_SIGNER_TIMEOUT_SECS
+}
+
+/// How to reach the external signer.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub enum ExternalSigner {
+    /// A program that is spawned for every request.
+    Command {
+        program: PathBuf,
+        #[serde(default)]
+        args: Vec<String>,
+        #[serde(default = "default_timeout_secs")]
+        timeout_secs: u64,
+    },
+    /// A signer listening on a Unix domain socket.
+    UnixSocket {
+        path: PathBuf,
+        #[serde(default = "default_timeout_secs")]
+        timeout_secs: u64,
+    },
+}
+
+/// A key held by the external signer.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ExternalKey {
+    /// The signer specific id of the key.
+    pub key_id: String,
+    /// Base64 encoded `flag || pk`.
+    pub public_key: String,
+}
+
+impl ExternalKey {
+    pub fn public_key(&self) -> Result<PublicKey, anyhow::Error> {
+        PublicKey::decode_base64(&self.public_key).map_err(|e| {
+            anyhow!(
+                "Invalid public key {} of external key: {e}",
+                self.public_key
+            )
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = "method", content = "params", rename_all = "snake_case")]
+enum SignerRequest {
+    Keys,
+    Sign {
+        key_id: String,
+        public_key: String,
+        message: String,
+    },
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "snake_case")]
+enum SignerResponse<T> {
+    Result(T),
+    Error(String),
+}
+
+#[derive(Deserialize, Debug)]
+struct SignResult {
+    signature: String,
+}
+
+impl ExternalSigner {
+    fn request<T: DeserializeOwned>(&self, request: &SignerRequest) -> Result<T, anyhow::Error> {
+        let mut request = serde_json::to_vec(request)?;
+        request.push(b'\n');
+
+        let response = match self {
+            ExternalSigner::Command {
+                program,
+                args,
+                timeout_secs,
+            } => {
+                let mut child = Command::new(program)
+                    .args(args)
+                    .stdin(Stdio::piped())
+                    .stdout(Stdio::piped())
+                    .spawn()
+                    .with_context(|| {
+                        format!("Cannot start the external signer {}", program.display())
+                    })?;
+                let mut stdin = child.stdin.take().expect("stdin is piped");
+                let mut stdout = child.stdout.take().expect("stdout is piped");
+                // Reading and writing may block on a stuck signer, so both
+                // happen on a separate thread while we wait for the response.
+                let (sender, receiver) = mpsc::channel();
+                std::thread::spawn(move || {
+                    let result = stdin.write_all(&request).and_then(|_| {
+                        drop(stdin);
+                        let mut stdout_bytes = Vec::new();
+                        stdout.read_to_end(&mut stdout_bytes).map(|_| stdout_bytes)
+                    });
+                    // The receiver is gone if the request timed out.
+                    sender.send(result).ok();
+                });
+                let stdout = match receiver.recv_timeout(Duration::from_secs(*timeout_secs)) {
+                    Ok(stdout) => stdout,
+                    Err(_) => {
+                        child.kill().ok();
+                        child.wait().ok();
+                        bail!("The external signer did not respond within {timeout_secs}s");
+                    }
+                };
+                let status = child.wait()?;
+                if !status.success() {
+                    bail!("The external signer exited with {status}");
+                }
+                stdout?
+            }
+            #[cfg(unix)]
+            ExternalSigner::UnixSocket { path, timeout_secs } => {
+                use std::io::{BufRead, ErrorKind};
+
+                let stream = std::os::unix::net::UnixStream::connect(path).with_context(|| {
+                    format!(
+                        "Cannot connect to the external signer at {}",
+                        path.display()
+                    )
+                })?;
+                let timeout = Some(Duration::from_secs(*timeout_secs));
+                stream.set_read_timeout(timeout)?;
+                stream.set_write_timeout(timeout)?;
+                let timed_out = |e: std::io::Error| match e.kind() {
+                    ErrorKind::WouldBlock | ErrorKind::TimedOut => {
+                        anyhow!("The external signer did not respond within {timeout_secs}s")
+                    }
+                    _ => e.into(),
+                };
+                (&stream).write_all(&request).map_err(timed_out)?;
+                let mut response = Vec::new();
+                BufReader::new(&stream)
+                    .read_until(b'\n', &mut response)
+                    .map_err(timed_out)?;
+                response
+            }
+            #[cfg(not(unix))]
+            ExternalSigner::UnixSocket { .. } => {
+                bail!("Unix socket signers are only supported on Unix")
+            }
+        };
+
+        match serde_json::from_slice(&response)
+            .context("Cannot deserialize the response of the external signer")?
+        {
+            SignerResponse::Result(result) => Ok(result),
+            SignerResponse::Error(e) => bail!("The external signer failed: {e}"),
+        }
+    }
+
+    /// Lists the keys held by the signer.
+    pub fn keys(&self) -> Result<Vec<ExternalKey>, anyhow::Error> {
+        self.request(&SignerRequest::Keys)
+    }
+
+    /// Signs `message` with the given key, and checks that the returned
+    /// signature is by that key.
+    pub fn sign(&self, key: &ExternalKey, message: &[u8]) -> Result<Signature, anyhow::Error> {
+        let result: SignResult = self.request(&SignerRequest::Sign {
+            key_id: key.key_id.clone(),
+            public_key: key.public_key.clone(),
+            message: Base64::encode(message),
+        })?;
+
+        let signature = Signature::from_bytes(
+            &Base64::decode(&result.signature)
+                .map_err(|e| anyhow!("Invalid signature encoding: {e}"))?,
+        )
+        .map_err(|e| anyhow!("Invalid signature: {e}"))?;
+        let public_key = key.public_key()?;
+        if signature.scheme().flag() != public_key.scheme().flag()
+            || signature.public_key_bytes() != public_key.as_ref()
+        {
+            bail!(
+                "The external signer signed with a different key than {}",
+                key.key_id
+            );
+        }
+        Ok(signature)
+    }
+}
+
+/// A keystore delegating all signing to an [`ExternalSigner`].
+///
+/// The registered keys are saved to the keystore file, and their aliases to
+/// the file with the `aliases` extension next to it, like for the
+/// [`FileBasedKeystore`](crate::keystore::FileBasedKeystore).
+pub struct ExternalKeystore {
+    signer: ExternalSigner,
+    keys: BTreeMap<IotaAddress, ExternalKey>,
+    aliases: BTreeMap<IotaAddress, Alias>,
+    path: Option<PathBuf>,
+}
+
+/// The representation of an [`ExternalKeystore`] in the client config.
+#[derive(Serialize, Deserialize)]
+struct ExternalKeystoreConfig {
+    path: PathBuf,
+    signer: ExternalSigner,
+}
+
+impl Serialize for ExternalKeystore {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        ExternalKeystoreConfig {
+            path: self.path.clone().unwrap_or_default(),
+            signer: self.signer.clone(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for ExternalKeystore {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        use serde::de::Error;
+        let config = ExternalKeystoreConfig::deserialize(deserializer)?;
+        ExternalKeystore::new(&config.path, config.signer).map_err(D::Error::custom)
+    }
+}
+
+impl ExternalKeystore {
+    pub fn new(path: &PathBuf, signer: ExternalSigner) -> Result<Self, anyhow::Error> {
+        let keys = if path.exists() {
+            let reader = BufReader::new(File::open(path).with_context(|| {
+                format!("Cannot open the external keystore file: {}", path.display())
+            })?);
+            let keys: Vec<ExternalKey> = serde_json::from_reader(reader).with_context(|| {
+                format!(
+                    "Cannot deserialize the external keystore file: {}",
+                    path.display()
+                )
+            })?;
+            keys.into_iter()
+                .map(|key| Ok((IotaAddress::from(&key.public_key()?), key)))
+                .collect::<Result<BTreeMap<_, _>, anyhow::Error>>()?
+        } else {
+            BTreeMap::new()
+        };
+
+        let mut aliases_path = path.clone();
+        aliases_path.set_extension("aliases");
+        let aliases = if aliases_path.exists() {
+            let reader = BufReader::new(File::open(&aliases_path).with_context(|| {
+                format!(
+                    "Cannot open aliases file in keystore: {}",
+                    aliases_path.display()
+                )
+            })?);
+            let aliases: Vec<Alias> = serde_json::from_reader(reader).with_context(|| {
+                format!(
+                    "Cannot deserialize aliases file in keystore: {}",
+                    aliases_path.display(),
+                )
+            })?;
+            aliases
+                .into_iter()
+                .map(|alias| {
+                    let key = PublicKey::decode_base64(&alias.public_key_base64);
+                    key.map(|k| (Into::<IotaAddress>::into(&k), alias))
+                })
+                .collect::<Result<BTreeMap<_, _>, _>>()
+                .map_err(|e| {
+                    anyhow!(
+                        "Invalid aliases file in keystore: {}. {}",
+                        aliases_path.display(),
+                        e
+                    )
+                })?
+        } else {
+            BTreeMap::new()
+        };
+
+        Ok(Self {
+            signer,
+            keys,
+            aliases,
+            path: Some(path.to_path_buf()),
+        })
+    }
+
+    /// Creates a keystore that is not persisted.
+    pub fn new_in_mem(signer: ExternalSigner) -> Self {
+        Self {
+            signer,
+            keys: BTreeMap::new(),
+            aliases: BTreeMap::new(),
+            path: None,
+        }
+    }
+
+    pub fn signer(&self) -> &ExternalSigner {
+        &self.signer
+    }
+
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// Registers a key held by the signer under `alias`, so it can be used
+    /// for signing. Returns the address of the key.
+    pub fn register_key(
+        &mut self,
+        key_id: &str,
+        alias: Option<String>,
+    ) -> Result<IotaAddress, anyhow::Error> {
+        let key = self
+            .signer
+            .keys()?
+            .into_iter()
+            .find(|key| key.key_id == key_id)
+            .ok_or_else(|| anyhow!("The external signer has no key with id {key_id}"))?;
+        let public_key = key.public_key()?;
+        let address = IotaAddress::from(&public_key);
+        if self.keys.contains_key(&address) {
+            bail!("The key for address {address} is already registered");
+        }
+
+        let alias = self.create_alias(alias)?;
+        self.aliases.insert(
+            address,
+            Alias {
+                alias,
+                public_key_base64: public_key.encode_base64(),
+            },
+        );
+        self.keys.insert(address, key);
+        self.save()?;
+        Ok(address)
+    }
+
+    /// Returns the registered key of `address`.
+    pub fn external_key(&self, address: &IotaAddress) -> Result<&ExternalKey, anyhow::Error> {
+        self.keys
+            .get(address)
+            .ok_or_else(|| anyhow!("Cannot find key for address: [{address}]"))
+    }
+
+    fn sign(&self, address: &IotaAddress, message: &[u8]) -> Result<Signature, signature::Error> {
+        let key = self
+            .external_key(address)
+            .map_err(|e| signature::Error::from_source(e.to_string()))?;
+        self.signer
+            .sign(key, message)
+            .map_err(|e| signature::Error::from_source(e.to_string()))
+    }
+
+    pub fn save_aliases(&self) -> Result<(), anyhow::Error> {
+        if let Some(path) = &self.path {
+            let aliases_store =
+                serde_json::to_string_pretty(&self.aliases.values().collect::<Vec<_>>())
+                    .with_context(|| {
+                        format!(
+                            "Cannot serialize aliases to file in keystore: {}",
+                            path.display()
+                        )
+                    })?;
+
+            let mut aliases_path = path.clone();
+            aliases_path.set_extension("aliases");
+            fs::write(aliases_path, aliases_store)?
+        }
+        Ok(())
+    }
+
+    pub fn save_keystore(&self) -> Result<(), anyhow::Error> {
+        if let Some(path) = &self.path {
+            let store = serde_json::to_string_pretty(&self.keys.values().collect::<Vec<_>>())
+                .with_context(|| {
+                    format!("Cannot serialize keystore to file: {}", path.display())
+                })?;
+            fs::write(path, store)?;
+        }
+        Ok(())
+    }
+
+    pub fn save(&self) -> Result<(), anyhow::Error> {
+        self.save_aliases()?;
+        self.save_keystore()?;
+        Ok(())
+    }
+}
+
+impl AccountKeystore for ExternalKeystore {
+    fn sign_hashed(
+        &self,
+        address: &IotaAddress,
+        msg: &[u8],
+    ) -> Result<Signature, signature::Error> {
+        self.sign(address, msg)
+    }
+
+    fn sign_secure<T>(
+        &self,
+        address: &IotaAddress,
+        msg: &T,
+        intent: Intent,
+    ) -> Result<Signature, signature::Error>
+    where
+        T: Serialize,
+    {
+        let intent_msg = IntentMessage::new(intent, msg);
+        let mut hasher = DefaultHash::default();
+        hasher.update(bcs::to_bytes(&intent_msg).map_err(signature::Error::from_source)?);
+        let signature = self.sign(address, &hasher.finalize().digest)?;
+
+        signature
+            .verify_secure(&intent_msg, *address, signature.scheme())
+            .map_err(|e| signature::Error::from_source(e.to_string()))?;
+        Ok(signature)
+    }
+
+    fn add_key(
+        &mut self,
+        _alias: Option<String>,
+        _keypair: IotaKeyPair,
+    ) -> Result<(), anyhow::Error> {
+        bail!("Private keys cannot be added to an external signer keystore")
+    }
+
+    fn keys(&self) -> Vec<PublicKey> {
+        self.keys
+            .values()
+            .filter_map(|key| key.public_key().ok())
+            .collect()
+    }
+
+    fn get_key(&self, address: &IotaAddress) -> Result<&IotaKeyPair, anyhow::Error> {
+        bail!("The private key for address {address} is held by the external signer")
+    }
+
+    fn addresses_with_alias(&self) -> Vec<(&IotaAddress, &Alias)> {
+        self.aliases.iter().collect::<Vec<_>>()
+    }
+
+    fn aliases(&self) -> Vec<&Alias> {
+        self.aliases.values().collect()
+    }
+
+    fn aliases_mut(&mut self) -> Vec<&mut Alias> {
+        self.aliases.values_mut().collect()
+    }
+
+    /// Get alias of address
+    fn get_alias_by_address(&self, address: &IotaAddress) -> Result<String, anyhow::Error> {
+        match self.aliases.get(address) {
+            Some(alias) => Ok(alias.alias.clone()),
+            None => bail!("Cannot find alias for address {address}"),
+        }
+    }
+
+    /// Get the address by its alias
+    fn get_address_by_alias(&self, alias: String) -> Result<&IotaAddress, anyhow::Error> {
+        self.addresses_with_alias()
+            .iter()
+            .find(|x| x.1.alias == alias)
+            .ok_or_else(|| anyhow!("Cannot resolve alias {alias} to an address"))
+            .map(|x| x.0)
+    }
+
+    /// This function returns an error if the provided alias already exists. If
+    /// the alias has not already been used, then it returns the alias.
+    /// If no alias has been passed, it will generate a new alias.
+    fn create_alias(&self, alias: Option<String>) -> Result<String, anyhow::Error> {
+        match alias {
+            Some(a) if self.alias_exists(&a) => {
+                bail!("Alias {a} already exists. Please choose another alias.")
+            }
+            Some(a) => validate_alias(&a),
+            None => Ok(random_name(
+                &self
+                    .alias_names()
+                    .into_iter()
+                    .map(|x| x.to_string())
+                    .collect::<HashSet<_>>(),
+            )),
+        }
+    }
+
+    /// Updates an old alias to the new alias and saves it to the alias file.
+    /// If the new_alias is None, it will generate a new random alias.
+    fn update_alias(
+        &mut self,
+        old_alias: &str,
+        new_alias: Option<&str>,
+    ) -> Result<String, anyhow::Error> {
+        let new_alias_name = self.update_alias_value(old_alias, new_alias)?;
+        self.save_aliases()?;
+        Ok(new_alias_name)
+    }
+}
@@ -64,7 +64,8 @@ pub fn derive_key_pair_from_path(
         }
         SignatureScheme::BLS12381
         | SignatureScheme::MultiSig
-        | SignatureScheme::ZkLoginAuthenticator => Err(IotaError::UnsupportedFeature {
+        | SignatureScheme::ZkLoginAuthenticator
+        | SignatureScheme::PasskeyAuthenticator => Err(IotaError::UnsupportedFeature {
             error: format!("key derivation not supported {:?}", key_scheme),
         }),
     }
@@ -168,7 +169,8 @@ pub fn validate_path(
         }
         SignatureScheme::BLS12381
         | SignatureScheme::MultiSig
-        | SignatureScheme::ZkLoginAuthenticator => Err(IotaError::UnsupportedFeature {
+        | SignatureScheme::ZkLoginAuthenticator
+        | SignatureScheme::PasskeyAuthenticator => Err(IotaError::UnsupportedFeature {
             error: format!("key derivation not supported {:?}", key_scheme),
         }),
     }
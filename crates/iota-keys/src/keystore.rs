@@ -27,6 +27,8 @@ use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use shared_crypto::intent::{Intent, IntentMessage};
 
 use crate::{
+    encryption::EncryptedKeystore,
+    external::ExternalKeystore,
     key_derive::{derive_key_pair_from_path, generate_new_key},
     random_names::{random_name, random_names},
 };
@@ -36,6 +38,7 @@ use crate::{
 pub enum Keystore {
     File(FileBasedKeystore),
     InMem(InMemKeystore),
+    External(ExternalKeystore),
 }
 #[enum_dispatch]
 pub trait AccountKeystore: Send + Sync {
@@ -147,19 +150,43 @@ pub trait AccountKeystore: Send + Sync {
     }
 }
 
+impl Keystore {
+    /// Whether the keys of the keystore are encrypted and have not been
+    /// unlocked yet.
+    pub fn is_locked(&self) -> bool {
+        matches!(self, Keystore::File(file) if file.is_locked())
+    }
+
+    /// Unlocks an encrypted keystore with its passphrase. Does nothing for
+    /// keystores that are not locked.
+    pub fn unlock(&mut self, passphrase: &str) -> Result<(), anyhow::Error> {
+        match self {
+            Keystore::File(file) => file.unlock(passphrase),
+            Keystore::InMem(_) | Keystore::External(_) => Ok(()),
+        }
+    }
+}
+
 impl Display for Keystore {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let mut writer = String::new();
         match self {
             Keystore::File(file) => {
                 writeln!(writer, "Keystore Type: File")?;
-                write!(writer, "Keystore Path : {:?}", file.path)?;
+                writeln!(writer, "Keystore Path : {:?}", file.path)?;
+                write!(writer, "Encrypted : {}", file.is_encrypted())?;
                 write!(f, "{}", writer)
             }
             Keystore::InMem(_) => {
                 writeln!(writer, "Keystore Type: InMem")?;
                 write!(f, "{}", writer)
             }
+            Keystore::External(external) => {
+                writeln!(writer, "Keystore Type: External")?;
+                writeln!(writer, "Keystore Path : {:?}", external.path())?;
+                write!(writer, "Signer : {:?}", external.signer())?;
+                write!(f, "{}", writer)
+            }
         }
     }
 }
@@ -170,11 +197,26 @@ pub struct Alias {
     pub public_key_base64: String,
 }
 
+/// The content of a keystore file, either a plaintext list of Base64 encoded
+/// `flag || privkey` or its encryption.
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum KeystoreFile {
+    Plaintext(Vec<String>),
+    Encrypted(EncryptedKeystore),
+}
+
 #[derive(Default)]
 pub struct FileBasedKeystore {
     keys: BTreeMap<IotaAddress, IotaKeyPair>,
     aliases: BTreeMap<IotaAddress, Alias>,
     path: Option<PathBuf>,
+    /// The passphrase the keystore file is encrypted with, if it is encrypted
+    /// and has been unlocked.
+    passphrase: Option<String>,
+    /// The content of an encrypted keystore file that has not been unlocked
+    /// yet. The keys are not available until then.
+    locked: Option<EncryptedKeystore>,
 }
 
 impl Serialize for FileBasedKeystore {
@@ -211,9 +253,8 @@ impl AccountKeystore for FileBasedKeystore {
     ) -> Result<Signature, signature::Error> {
         Ok(Signature::new_hashed(
             msg,
-            self.keys.get(address).ok_or_else(|| {
-                signature::Error::from_source(format!("Cannot find key for address: [{address}]"))
-            })?,
+            self.get_key(address)
+                .map_err(|e| signature::Error::from_source(e.to_string()))?,
         ))
     }
     fn sign_secure<T>(
@@ -227,9 +268,8 @@ impl AccountKeystore for FileBasedKeystore {
     {
         Ok(Signature::new_secure(
             &IntentMessage::new(intent, msg),
-            self.keys.get(address).ok_or_else(|| {
-                signature::Error::from_source(format!("Cannot find key for address: [{address}]"))
-            })?,
+            self.get_key(address)
+                .map_err(|e| signature::Error::from_source(e.to_string()))?,
         ))
     }
 
@@ -238,6 +278,7 @@ impl AccountKeystore for FileBasedKeystore {
         alias: Option<String>,
         keypair: IotaKeyPair,
     ) -> Result<(), anyhow::Error> {
+        self.ensure_unlocked()?;
         let address: IotaAddress = (&keypair.public()).into();
         let alias = self.create_alias(alias)?;
         self.aliases.insert(
@@ -268,7 +309,16 @@ impl AccountKeystore for FileBasedKeystore {
         self.aliases.values_mut().collect()
     }
 
+    /// Return the public keys of the keystore. The public keys of a locked
+    /// keystore are taken from its aliases.
     fn keys(&self) -> Vec<PublicKey> {
+        if self.is_locked() {
+            return self
+                .aliases
+                .values()
+                .filter_map(|alias| PublicKey::decode_base64(&alias.public_key_base64).ok())
+                .collect();
+        }
         self.keys.values().map(|key| key.public()).collect()
     }
 
@@ -309,6 +359,7 @@ impl AccountKeystore for FileBasedKeystore {
     }
 
     fn get_key(&self, address: &IotaAddress) -> Result<&IotaKeyPair, anyhow::Error> {
+        self.ensure_unlocked()?;
         match self.keys.get(address) {
             Some(key) => Ok(key),
             None => Err(anyhow!("Cannot find key for address: [{address}]")),
@@ -330,24 +381,20 @@ impl AccountKeystore for FileBasedKeystore {
 
 impl FileBasedKeystore {
     pub fn new(path: &PathBuf) -> Result<Self, anyhow::Error> {
-        let keys = if path.exists() {
+        let (keys, locked) = if path.exists() {
             let reader =
                 BufReader::new(File::open(path).with_context(|| {
                     format!("Cannot open the keystore file: {}", path.display())
                 })?);
-            let kp_strings: Vec<String> = serde_json::from_reader(reader).with_context(|| {
+            let file: KeystoreFile = serde_json::from_reader(reader).with_context(|| {
                 format!("Cannot deserialize the keystore file: {}", path.display(),)
             })?;
-            kp_strings
-                .iter()
-                .map(|kpstr| {
-                    let key = IotaKeyPair::decode_base64(kpstr);
-                    key.map(|k| (IotaAddress::from(&k.public()), k))
-                })
-                .collect::<Result<BTreeMap<_, _>, _>>()
-                .map_err(|e| anyhow!("Invalid keystore file: {}. {}", path.display(), e))?
+            match file {
+                KeystoreFile::Plaintext(kp_strings) => (decode_keys(&kp_strings, path)?, None),
+                KeystoreFile::Encrypted(encrypted) => (BTreeMap::new(), Some(encrypted)),
+            }
         } else {
-            BTreeMap::new()
+            (BTreeMap::new(), None)
         };
 
         // check aliases
@@ -384,6 +431,11 @@ impl FileBasedKeystore {
                     )
                 })?
         } else if keys.is_empty() {
+            ensure!(
+                locked.is_none(),
+                "Missing aliases file of the encrypted keystore: {}",
+                aliases_path.display()
+            );
             BTreeMap::new()
         } else {
             let names: Vec<String> = random_names(HashSet::new(), keys.len());
@@ -416,9 +468,56 @@ impl FileBasedKeystore {
             keys,
             aliases,
             path: Some(path.to_path_buf()),
+            passphrase: None,
+            locked,
         })
     }
 
+    /// Whether the keystore file is encrypted with a passphrase.
+    pub fn is_encrypted(&self) -> bool {
+        self.passphrase.is_some() || self.locked.is_some()
+    }
+
+    /// Whether the keystore file is encrypted and has not been unlocked yet.
+    /// Only the public keys and aliases of a locked keystore are available.
+    pub fn is_locked(&self) -> bool {
+        self.locked.is_some()
+    }
+
+    fn ensure_unlocked(&self) -> Result<(), anyhow::Error> {
+        ensure!(
+            !self.is_locked(),
+            "The keystore is locked, unlock it with its passphrase first"
+        );
+        Ok(())
+    }
+
+    /// Decrypts the keys of an encrypted keystore with its passphrase. Does
+    /// nothing if the keystore is not locked.
+    pub fn unlock(&mut self, passphrase: &str) -> Result<(), anyhow::Error> {
+        let Some(encrypted) = &self.locked else {
+            return Ok(());
+        };
+        let path = self.path.clone().unwrap_or_default();
+        let kp_strings: Vec<String> = serde_json::from_slice(&encrypted.decrypt(passphrase)?)
+            .with_context(|| format!("Cannot deserialize the keystore file: {}", path.display()))?;
+        let keys = decode_keys(&kp_strings, &path)?;
+        self.keys = keys;
+        self.passphrase = Some(passphrase.to_string());
+        self.locked = None;
+        Ok(())
+    }
+
+    /// Encrypts the keystore file with `passphrase`. A plaintext keystore is
+    /// migrated to an encrypted one, an encrypted keystore is re-encrypted
+    /// with the new passphrase. The keystore must be unlocked.
+    pub fn set_passphrase(&mut self, passphrase: &str) -> Result<(), anyhow::Error> {
+        self.ensure_unlocked()?;
+        ensure!(!passphrase.is_empty(), "The passphrase must not be empty");
+        self.passphrase = Some(passphrase.to_string());
+        self.save_keystore()
+    }
+
     pub fn set_path(&mut self, path: &Path) {
         self.path = Some(path.to_path_buf());
     }
@@ -442,6 +541,22 @@ impl FileBasedKeystore {
     }
 
     pub fn save_keystore(&self) -> Result<(), anyhow::Error> {
+        self.ensure_unlocked()?;
+        if let Some(passphrase) = &self.passphrase {
+            if let Some(path) = &self.path {
+                let plaintext = serde_json::to_vec(&self.encoded_keys()).with_context(|| {
+                    format!("Cannot serialize keystore to file: {}", path.display())
+                })?;
+                let file =
+                    KeystoreFile::Encrypted(EncryptedKeystore::encrypt(&plaintext, passphrase)?);
+                let store = serde_json::to_string_pretty(&file).with_context(|| {
+                    format!("Cannot serialize keystore to file: {}", path.display())
+                })?;
+                fs::write(path, store)?;
+            }
+            return Ok(());
+        }
+
         println!(
             "Keys saved as Base64 with 33 bytes `flag || privkey` ($BASE64_STR). 
         To see Bech32 format encoding, use `iota keytool export $IOTA_ADDRESS` where 
@@ -449,14 +564,9 @@ impl FileBasedKeystore {
         );
 
         if let Some(path) = &self.path {
-            let store = serde_json::to_string_pretty(
-                &self
-                    .keys
-                    .values()
-                    .map(|k| k.encode_base64())
-                    .collect::<Vec<_>>(),
-            )
-            .with_context(|| format!("Cannot serialize keystore to file: {}", path.display()))?;
+            let store = serde_json::to_string_pretty(&self.encoded_keys()).with_context(|| {
+                format!("Cannot serialize keystore to file: {}", path.display())
+            })?;
             fs::write(path, store)?;
         }
         Ok(())
@@ -468,11 +578,29 @@ impl FileBasedKeystore {
         Ok(())
     }
 
+    fn encoded_keys(&self) -> Vec<String> {
+        self.keys.values().map(|k| k.encode_base64()).collect()
+    }
+
     pub fn key_pairs(&self) -> Vec<&IotaKeyPair> {
         self.keys.values().collect()
     }
 }
 
+fn decode_keys(
+    kp_strings: &[String],
+    path: &Path,
+) -> Result<BTreeMap<IotaAddress, IotaKeyPair>, anyhow::Error> {
+    kp_strings
+        .iter()
+        .map(|kpstr| {
+            let key = IotaKeyPair::decode_base64(kpstr);
+            key.map(|k| (IotaAddress::from(&k.public()), k))
+        })
+        .collect::<Result<BTreeMap<_, _>, _>>()
+        .map_err(|e| anyhow!("Invalid keystore file: {}. {}", path.display(), e))
+}
+
 #[derive(Default, Serialize, Deserialize)]
 pub struct InMemKeystore {
     aliases: BTreeMap<IotaAddress, Alias>,
@@ -633,7 +761,7 @@ impl InMemKeystore {
     }
 }
 
-fn validate_alias(alias: &str) -> Result<String, anyhow::Error> {
+pub(crate) fn validate_alias(alias: &str) -> Result<String, anyhow::Error> {
     let re = Regex::new(r"^[A-Za-z][A-Za-z0-9-_\.]*$")
         .map_err(|_| anyhow!("Cannot build the regex needed to validate the alias naming"))?;
     let alias = alias.trim();
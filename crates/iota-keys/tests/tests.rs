@@ -6,13 +6,18 @@ use std::{fs, str::FromStr};
 
 use fastcrypto::{hash::HashFunction, traits::EncodeDecodeBase64};
 use iota_keys::{
+    external::{ExternalKeystore, ExternalSigner, DEFAULT_EXTERNAL_SIGNER_TIMEOUT_SECS},
     key_derive::generate_new_key,
     keystore::{AccountKeystore, FileBasedKeystore, InMemKeystore, Keystore},
 };
 use iota_types::{
     base_types::{IotaAddress, IOTA_ADDRESS_LENGTH},
-    crypto::{DefaultHash, Ed25519IotaSignature, IotaSignatureInner, SignatureScheme},
+    crypto::{
+        DefaultHash, Ed25519IotaSignature, IotaKeyPair, IotaSignature, IotaSignatureInner,
+        Signature, SignatureScheme,
+    },
 };
+use shared_crypto::intent::{Intent, IntentMessage};
 use tempfile::TempDir;
 
 #[test]
@@ -267,3 +272,233 @@ fn get_alias_by_address_test() {
     let address = generate_new_key(SignatureScheme::ED25519, None, None).unwrap();
     assert!(keystore.get_alias_by_address(&address.0).is_err())
 }
+
+/// Serves the external signer protocol on a Unix socket, signing with
+/// `keypair`.
+#[cfg(unix)]
+fn spawn_mock_signer(socket_path: std::path::PathBuf, keypair: IotaKeyPair) {
+    use std::{
+        io::{BufRead, BufReader, Write},
+        os::unix::net::UnixListener,
+    };
+
+    use fastcrypto::encoding::{Base64, Encoding};
+
+    let listener = UnixListener::bind(socket_path).unwrap();
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut request = String::new();
+            BufReader::new(&stream).read_line(&mut request).unwrap();
+            let request: serde_json::Value = serde_json::from_str(&request).unwrap();
+
+            let response = match request["method"].as_str().unwrap() {
+                "keys" => serde_json::json!({
+                    "result": [{
+                        "key_id": "key-0",
+                        "public_key": keypair.public().encode_base64(),
+                    }]
+                }),
+                "sign" => {
+                    let message =
+                        Base64::decode(request["params"]["message"].as_str().unwrap()).unwrap();
+                    let signature = Signature::new_hashed(&message, &keypair);
+                    serde_json::json!({
+                        "result": { "signature": Base64::encode(signature.as_ref()) }
+                    })
+                }
+                method => serde_json::json!({ "error": format!("unknown method {method}") }),
+            };
+            let mut response = serde_json::to_vec(&response).unwrap();
+            response.push(b'\n');
+            stream.write_all(&response).unwrap();
+        }
+    });
+}
+
+#[cfg(unix)]
+#[test]
+fn external_keystore_unix_socket_test() {
+    let temp_dir = TempDir::new().unwrap();
+    let socket_path = temp_dir.path().join("signer.sock");
+    let (address, keypair, _, _) = generate_new_key(SignatureScheme::ED25519, None, None).unwrap();
+    spawn_mock_signer(socket_path.clone(), keypair);
+
+    let signer = ExternalSigner::UnixSocket {
+        path: socket_path,
+        timeout_secs: DEFAULT_EXTERNAL_SIGNER_TIMEOUT_SECS,
+    };
+    let keystore_path = temp_dir.path().join("external.keystore");
+    let mut keystore = ExternalKeystore::new(&keystore_path, signer.clone()).unwrap();
+    assert_eq!(
+        keystore
+            .register_key("key-0", Some("hardware".to_string()))
+            .unwrap(),
+        address
+    );
+    assert!(keystore.register_key("key-1", None).is_err());
+
+    // The key is registered under its alias, but the private key is not available.
+    let keystore = Keystore::from(ExternalKeystore::new(&keystore_path, signer).unwrap());
+    assert_eq!(keystore.addresses(), vec![address]);
+    assert_eq!(keystore.get_alias_by_address(&address).unwrap(), "hardware");
+    assert!(keystore.get_key(&address).is_err());
+
+    let signature = keystore
+        .sign_secure(&address, &"hello", Intent::personal_message())
+        .unwrap();
+    signature
+        .verify_secure(
+            &IntentMessage::new(Intent::personal_message(), "hello"),
+            address,
+            SignatureScheme::ED25519,
+        )
+        .unwrap();
+
+    // Unknown addresses cannot be signed for.
+    let other = generate_new_key(SignatureScheme::ED25519, None, None)
+        .unwrap()
+        .0;
+    assert!(keystore
+        .sign_secure(&other, &"hello", Intent::personal_message())
+        .is_err());
+}
+
+#[cfg(unix)]
+#[test]
+fn external_keystore_command_test() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let temp_dir = TempDir::new().unwrap();
+    let program = temp_dir.path().join("signer.sh");
+    fs::write(
+        &program,
+        "#!/bin/sh\nread request\necho '{\"error\": \"device locked\"}'\n",
+    )
+    .unwrap();
+    fs::set_permissions(&program, fs::Permissions::from_mode(0o755)).unwrap();
+
+    let signer = ExternalSigner::Command {
+        program,
+        args: vec![],
+        timeout_secs: DEFAULT_EXTERNAL_SIGNER_TIMEOUT_SECS,
+    };
+    let error = signer.keys().unwrap_err();
+    assert!(error.to_string().contains("device locked"));
+
+    let mut keystore = ExternalKeystore::new_in_mem(signer);
+    assert!(keystore.register_key("key-0", None).is_err());
+    assert!(keystore
+        .add_key(
+            None,
+            generate_new_key(SignatureScheme::ED25519, None, None)
+                .unwrap()
+                .1
+        )
+        .is_err());
+}
+
+#[cfg(unix)]
+#[test]
+fn external_signer_timeout_test() {
+    use std::os::unix::{fs::PermissionsExt, net::UnixListener};
+
+    let temp_dir = TempDir::new().unwrap();
+    let program = temp_dir.path().join("signer.sh");
+    fs::write(&program, "#!/bin/sh\nsleep 30\n").unwrap();
+    fs::set_permissions(&program, fs::Permissions::from_mode(0o755)).unwrap();
+    let signer = ExternalSigner::Command {
+        program,
+        args: vec![],
+        timeout_secs: 1,
+    };
+    let start = std::time::Instant::now();
+    let error = signer.keys().unwrap_err();
+    assert!(error.to_string().contains("did not respond"));
+    assert!(start.elapsed() < std::time::Duration::from_secs(10));
+
+    // A socket signer that accepts the connection but never responds.
+    let socket_path = temp_dir.path().join("signer.sock");
+    let _listener = UnixListener::bind(&socket_path).unwrap();
+    let signer = ExternalSigner::UnixSocket {
+        path: socket_path,
+        timeout_secs: 1,
+    };
+    let error = signer.keys().unwrap_err();
+    assert!(error.to_string().contains("did not respond"));
+}
+
+#[test]
+fn encrypted_keystore_migration_test() {
+    let temp_dir = TempDir::new().unwrap();
+    let keystore_path = temp_dir.path().join("iota.keystore");
+    let mut keystore = FileBasedKeystore::new(&keystore_path).unwrap();
+    let (address, _, _) = keystore
+        .generate_and_add_new_key(
+            SignatureScheme::ED25519,
+            Some("my_alias".to_string()),
+            None,
+            None,
+        )
+        .unwrap();
+    assert!(!keystore.is_encrypted());
+
+    // Migrate the plaintext keystore to an encrypted one.
+    keystore.set_passphrase("correct horse").unwrap();
+    assert!(keystore.is_encrypted());
+    let content = fs::read_to_string(&keystore_path).unwrap();
+    assert!(content.contains("ciphertext"));
+    let encoded_key = keystore.get_key(&address).unwrap().encode_base64();
+    assert!(!content.contains(&encoded_key));
+
+    // A reloaded keystore is locked, but its addresses and aliases are known.
+    let mut keystore = FileBasedKeystore::new(&keystore_path).unwrap();
+    assert!(keystore.is_locked());
+    assert_eq!(keystore.addresses(), vec![address]);
+    assert_eq!(keystore.get_alias_by_address(&address).unwrap(), "my_alias");
+    assert!(keystore.get_key(&address).is_err());
+    assert!(keystore
+        .sign_secure(&address, &"hello", Intent::iota_transaction())
+        .is_err());
+    assert!(keystore
+        .add_key(
+            None,
+            generate_new_key(SignatureScheme::ED25519, None, None)
+                .unwrap()
+                .1
+        )
+        .is_err());
+
+    assert!(keystore.unlock("wrong passphrase").is_err());
+    assert!(keystore.is_locked());
+    keystore.unlock("correct horse").unwrap();
+    assert!(!keystore.is_locked());
+    assert_eq!(
+        keystore.get_key(&address).unwrap().encode_base64(),
+        encoded_key
+    );
+    keystore
+        .sign_secure(&address, &"hello", Intent::iota_transaction())
+        .unwrap();
+}
+
+#[test]
+fn encrypted_keystore_change_passphrase_test() {
+    let temp_dir = TempDir::new().unwrap();
+    let keystore_path = temp_dir.path().join("iota.keystore");
+    let mut keystore = FileBasedKeystore::new(&keystore_path).unwrap();
+    keystore.set_passphrase("old passphrase").unwrap();
+    keystore
+        .generate_and_add_new_key(SignatureScheme::Secp256k1, None, None, None)
+        .unwrap();
+    // An empty passphrase is rejected.
+    assert!(keystore.set_passphrase("").is_err());
+    keystore.set_passphrase("new passphrase").unwrap();
+
+    let mut keystore = Keystore::from(FileBasedKeystore::new(&keystore_path).unwrap());
+    assert!(keystore.is_locked());
+    assert!(keystore.unlock("old passphrase").is_err());
+    keystore.unlock("new passphrase").unwrap();
+    assert!(!keystore.is_locked());
+    assert_eq!(keystore.keys().len(), 1);
+}
@@ -0,0 +1,270 @@
+// Copyright (c) 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::anyhow;
+use iota_config::genesis::Genesis;
+use iota_json_rpc_types::IotaTransactionBlockResponseOptions;
+use iota_rest_api::{CheckpointData, Client};
+use iota_sdk::{IotaClient, IotaClientBuilder};
+use iota_types::{
+    base_types::{EpochId, ObjectID},
+    committee::Committee,
+    digests::TransactionDigest,
+    effects::{TransactionEffects, TransactionEffectsAPI, TransactionEvents},
+    messages_checkpoint::{CertifiedCheckpointSummary, CheckpointSequenceNumber},
+    object::Object,
+};
+use tokio::sync::{Mutex, OnceCell};
+use tracing::info;
+
+use crate::{
+    config::Config,
+    store::{next_epoch_committee, CheckpointStore},
+    verifier::{extract_verified_transaction, VerifiedTransactionData},
+};
+
+/// A light client for the IOTA blockchain.
+///
+/// The client keeps the chain of committees, as end-of-epoch checkpoints
+/// verified from genesis, in a [`CheckpointStore`]. Anything read from the
+/// full node is only returned after it has been verified against a certified
+/// checkpoint of the committee of its epoch.
+pub struct LightClient {
+    config: Config,
+    rest_client: Client,
+    /// Connected on first use and shared by all requests afterwards.
+    iota_client: OnceCell<IotaClient>,
+    store: CheckpointStore,
+    genesis_committee: Committee,
+    sync_lock: Mutex<()>,
+}
+
+impl LightClient {
+    pub async fn new(config: Config) -> anyhow::Result<Self> {
+        let genesis_committee = Genesis::load(config.genesis_path())?.committee()?;
+
+        Ok(Self {
+            rest_client: Client::new(config.rest_url()),
+            iota_client: OnceCell::new(),
+            store: CheckpointStore::new(config.checkpoint_summary_dir.clone()),
+            genesis_committee,
+            sync_lock: Mutex::new(()),
+            config,
+        })
+    }
+
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    pub fn rest_client(&self) -> &Client {
+        &self.rest_client
+    }
+
+    pub fn store(&self) -> &CheckpointStore {
+        &self.store
+    }
+
+    async fn iota_client(&self) -> anyhow::Result<&IotaClient> {
+        self.iota_client
+            .get_or_try_init(|| {
+                IotaClientBuilder::default().build(self.config.full_node_url.as_str())
+            })
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn download_checkpoint_summary(
+        &self,
+        seq: CheckpointSequenceNumber,
+    ) -> anyhow::Result<CertifiedCheckpointSummary> {
+        self.rest_client.get_checkpoint_summary(seq).await
+    }
+
+    /// Syncs the committee chain to the latest finished epoch.
+    ///
+    /// Only the end-of-epoch checkpoints after the last verified one are
+    /// downloaded and verified, so regular syncs are cheap.
+    pub async fn sync(&self) -> anyhow::Result<()> {
+        let _guard = self.sync_lock.lock().await;
+
+        self.sync_checkpoint_list_to_latest().await?;
+        self.verify_checkpoint_list().await
+    }
+
+    /// Run binary search to for each end of epoch checkpoint that is missing
+    /// between the latest on the list and the latest checkpoint.
+    async fn sync_checkpoint_list_to_latest(&self) -> anyhow::Result<()> {
+        // Get the local checkpoint list
+        let mut checkpoints_list = self.store.read_checkpoint_list()?;
+
+        let (mut last_epoch, mut last_checkpoint_seq) = match checkpoints_list.checkpoints.last() {
+            Some(latest_in_list) => {
+                // Download the latest in list checkpoint
+                let summary = self.download_checkpoint_summary(*latest_in_list).await?;
+                (Some(summary.epoch()), summary.sequence_number)
+            }
+            None => (None, 0),
+        };
+
+        // Download the very latest checkpoint
+        let latest = self.rest_client.get_latest_checkpoint().await?;
+
+        // Binary search to find missing checkpoints
+        loop {
+            let target_epoch = last_epoch.map_or(0, |epoch| epoch + 1);
+            // Only finished epochs have an end-of-epoch checkpoint.
+            if target_epoch >= latest.epoch() {
+                break;
+            }
+            info!("Target Epoch: {target_epoch}");
+
+            let mut start = last_checkpoint_seq;
+            let mut end = latest.sequence_number;
+            let mut found_summary = None;
+
+            while start < end {
+                let mid = (start + end) / 2;
+                let summary = self.download_checkpoint_summary(mid).await?;
+
+                if summary.epoch() == target_epoch && summary.end_of_epoch_data.is_some() {
+                    found_summary = Some(summary);
+                    break;
+                }
+
+                if summary.epoch() <= target_epoch {
+                    start = mid + 1;
+                } else {
+                    end = mid;
+                }
+            }
+
+            let summary = found_summary.ok_or_else(|| {
+                anyhow!("End-of-epoch checkpoint of epoch {target_epoch} not found")
+            })?;
+
+            // Note: Do not write summary to file, since we must only persist
+            //       checkpoints that have been verified by the previous committee
+
+            // Add to the list
+            checkpoints_list.checkpoints.push(summary.sequence_number);
+            self.store.write_checkpoint_list(&checkpoints_list)?;
+
+            // Update
+            last_epoch = Some(summary.epoch());
+            last_checkpoint_seq = summary.sequence_number;
+        }
+
+        Ok(())
+    }
+
+    /// Verifies and stores the checkpoints of the list that have not been
+    /// verified yet, starting from the committee of the last verified one.
+    async fn verify_checkpoint_list(&self) -> anyhow::Result<()> {
+        let checkpoints_list = self.store.read_checkpoint_list()?;
+
+        // Stored checkpoints have been verified already, so continue from the last
+        // one of the verified prefix of the list.
+        let verified = checkpoints_list
+            .checkpoints
+            .iter()
+            .take_while(|seq| self.store.has_checkpoint(**seq))
+            .count();
+        let mut prev_committee = match verified.checked_sub(1) {
+            Some(last_verified) => next_epoch_committee(
+                &self
+                    .store
+                    .read_checkpoint(checkpoints_list.checkpoints[last_verified])?,
+            )?,
+            None => self.genesis_committee.clone(),
+        };
+
+        for seq in &checkpoints_list.checkpoints[verified..] {
+            // Download the checkpoint from the server
+            let summary = self.download_checkpoint_summary(*seq).await?;
+            summary.clone().verify(&prev_committee)?;
+            // Extract the new committee information before storing the summary
+            let next_committee = next_epoch_committee(&summary)?;
+            // Write the checkpoint summary to a file
+            self.store.write_checkpoint(&summary)?;
+
+            info!(
+                "Epoch: {} Checkpoint ID: {}",
+                summary.epoch(),
+                summary.digest()
+            );
+
+            prev_committee = next_committee;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the verified committee of `epoch`. The committee chain must
+    /// have been synced up to that epoch.
+    pub fn committee_for_epoch(&self, epoch: EpochId) -> anyhow::Result<Committee> {
+        self.store
+            .committee_for_epoch(epoch, &self.genesis_committee)
+    }
+
+    async fn get_full_checkpoint(
+        &self,
+        seq: CheckpointSequenceNumber,
+    ) -> anyhow::Result<CheckpointData> {
+        // Downloading the checkpoint from the server
+        self.rest_client.get_full_checkpoint(seq).await
+    }
+
+    /// Fetches a transaction with its effects and events, and verifies them
+    /// against the certified checkpoint it was executed in.
+    pub async fn get_verified_transaction(
+        &self,
+        tid: TransactionDigest,
+    ) -> anyhow::Result<VerifiedTransactionData> {
+        // Lookup the transaction id and get the checkpoint sequence number. The full
+        // node is not trusted here, a wrong checkpoint just fails verification.
+        let options = IotaTransactionBlockResponseOptions::new();
+        let seq = self
+            .iota_client()
+            .await?
+            .read_api()
+            .get_transaction_with_options(tid, options)
+            .await?
+            .checkpoint
+            .ok_or(anyhow!("Transaction not found"))?;
+
+        // Download the full checkpoint for this sequence number
+        let full_checkpoint = self.get_full_checkpoint(seq).await?;
+        let committee = self.committee_for_epoch(full_checkpoint.checkpoint_summary.epoch())?;
+
+        extract_verified_transaction(&full_checkpoint, &committee, tid)
+    }
+
+    pub async fn get_verified_effects_and_events(
+        &self,
+        tid: TransactionDigest,
+    ) -> anyhow::Result<(TransactionEffects, Option<TransactionEvents>)> {
+        let verified = self.get_verified_transaction(tid).await?;
+        Ok((verified.effects, verified.events))
+    }
+
+    /// Fetches the latest version of an object, and verifies it against the
+    /// certified effects of the transaction that wrote it.
+    pub async fn get_verified_object(&self, id: ObjectID) -> anyhow::Result<Object> {
+        let object = self.rest_client.get_object(id).await?;
+
+        // Need to authenticate this object
+        let (effects, _) = self
+            .get_verified_effects_and_events(object.previous_transaction)
+            .await?;
+
+        // check that this object ID, version and hash is in the effects
+        effects
+            .all_changed_objects()
+            .iter()
+            .find(|object_ref| object_ref.0 == object.compute_object_reference())
+            .ok_or(anyhow!("Object not found"))?;
+
+        Ok(object)
+    }
+}
@@ -0,0 +1,37 @@
+// Copyright (c) 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{fs, path::PathBuf};
+
+use anyhow::Context;
+
+// The config file for the light client including the root of trust genesis
+// digest
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+pub struct Config {
+    /// Full node url
+    pub full_node_url: String,
+
+    /// Checkpoint summary directory
+    pub checkpoint_summary_dir: PathBuf,
+
+    //  Genesis file name
+    pub genesis_filename: PathBuf,
+}
+
+impl Config {
+    /// Loads the config from a yaml file.
+    pub fn load(path: &PathBuf) -> anyhow::Result<Self> {
+        let reader = fs::File::open(path)
+            .with_context(|| format!("Unable to load config from {}", path.display()))?;
+        Ok(serde_yaml::from_reader(reader)?)
+    }
+
+    pub fn rest_url(&self) -> String {
+        format!("{}/rest", self.full_node_url)
+    }
+
+    pub fn genesis_path(&self) -> PathBuf {
+        self.checkpoint_summary_dir.join(&self.genesis_filename)
+    }
+}
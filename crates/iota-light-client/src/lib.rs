@@ -0,0 +1,15 @@
+// Copyright (c) 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! A light client for the IOTA blockchain. See the README of this crate for
+//! how the committee chain is synced and how data is verified.
+
+mod client;
+pub mod config;
+pub mod package_store;
+pub mod proxy;
+pub mod store;
+pub mod verifier;
+
+pub use client::LightClient;
+pub use config::Config;
@@ -0,0 +1,48 @@
+// Copyright (c) Mysten Labs, Inc.
+// Modifications Copyright (c) 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use iota_package_resolver::{Package, PackageStore, Result as ResolverResult};
+use iota_types::base_types::SequenceNumber;
+use move_core_types::account_address::AccountAddress;
+
+use crate::LightClient;
+
+/// A package store that only resolves packages verified by the light client.
+pub struct RemotePackageStore {
+    light_client: Arc<LightClient>,
+}
+
+impl RemotePackageStore {
+    pub fn new(light_client: Arc<LightClient>) -> Self {
+        Self { light_client }
+    }
+}
+
+#[async_trait]
+impl PackageStore for RemotePackageStore {
+    /// Latest version of the object at `id`.
+    async fn version(&self, id: AccountAddress) -> ResolverResult<SequenceNumber> {
+        Ok(self
+            .light_client
+            .rest_client()
+            .get_object(id.into())
+            .await
+            .unwrap()
+            .version())
+    }
+    /// Read package contents. Fails if `id` is not an object, not a package, or
+    /// is malformed in some way.
+    async fn fetch(&self, id: AccountAddress) -> ResolverResult<Arc<Package>> {
+        let object = self
+            .light_client
+            .get_verified_object(id.into())
+            .await
+            .unwrap();
+        let package = Package::read(&object).unwrap();
+        Ok(Arc::new(package))
+    }
+}
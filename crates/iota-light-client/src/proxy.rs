@@ -0,0 +1,224 @@
+// Copyright (c) 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{net::SocketAddr, sync::Arc, time::Duration};
+
+use axum::{
+    extract::{Path, State},
+    http::{header, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+    routing::get,
+    Router,
+};
+use iota_rest_api::{
+    accept::AcceptFormat, types::JsonObject, TransactionResponse, APPLICATION_BCS,
+};
+use iota_types::{base_types::ObjectID, digests::TransactionDigest};
+use tracing::{info, warn};
+
+use crate::LightClient;
+
+pub const GET_OBJECT_PATH: &str = "/objects/:object_id";
+pub const GET_TRANSACTION_PATH: &str = "/transactions/:transaction";
+
+/// The default interval at which the proxy syncs the committee chain, so it
+/// can verify data of new epochs.
+pub const DEFAULT_SYNC_INTERVAL: Duration = Duration::from_secs(600);
+
+/// Returns a router that serves objects and transactions from the full node
+/// only after verifying them against certified checkpoints. Responses use
+/// the same content negotiation as the REST API of the full node.
+pub fn router(light_client: Arc<LightClient>) -> Router {
+    Router::new()
+        .route(GET_OBJECT_PATH, get(get_object))
+        .route(GET_TRANSACTION_PATH, get(get_transaction))
+        .with_state(light_client)
+}
+
+/// Serves the verifying proxy on `address`, syncing the committee chain
+/// every `sync_interval` in the background.
+pub async fn serve(
+    light_client: Arc<LightClient>,
+    address: SocketAddr,
+    sync_interval: Duration,
+) -> anyhow::Result<()> {
+    let sync_client = light_client.clone();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(sync_interval);
+        loop {
+            interval.tick().await;
+            if let Err(e) = sync_client.sync().await {
+                warn!("Failed to sync checkpoints: {e}");
+            }
+        }
+    });
+
+    let listener = tokio::net::TcpListener::bind(address).await?;
+    info!("Light client proxy listening on {address}");
+    axum::serve(listener, router(light_client).into_make_service()).await?;
+    Ok(())
+}
+
+async fn get_object(
+    Path(object_id): Path<ObjectID>,
+    accept: AcceptFormat,
+    State(light_client): State<Arc<LightClient>>,
+) -> Result<Response, ProxyError> {
+    let object = light_client.get_verified_object(object_id).await?;
+
+    Ok(match accept {
+        AcceptFormat::Json => axum::Json(JsonObject::from_object(&object)).into_response(),
+        AcceptFormat::Bcs => bcs_response(&object)?,
+    })
+}
+
+async fn get_transaction(
+    Path(transaction_digest): Path<TransactionDigest>,
+    accept: AcceptFormat,
+    State(light_client): State<Arc<LightClient>>,
+) -> Result<Response, ProxyError> {
+    let verified = light_client
+        .get_verified_transaction(transaction_digest)
+        .await?;
+    let response = TransactionResponse {
+        digest: transaction_digest,
+        transaction: verified.transaction,
+        effects: verified.effects,
+        events: verified.events,
+    };
+
+    Ok(match accept {
+        AcceptFormat::Json => axum::Json(response).into_response(),
+        AcceptFormat::Bcs => bcs_response(&response)?,
+    })
+}
+
+fn bcs_response<T: serde::Serialize>(value: &T) -> Result<Response, ProxyError> {
+    let bytes = bcs::to_bytes(value).map_err(anyhow::Error::from)?;
+    Ok((
+        [(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static(APPLICATION_BCS),
+        )],
+        bytes,
+    )
+        .into_response())
+}
+
+/// Data that could not be fetched or verified. Unverified data is never
+/// returned to the caller.
+struct ProxyError(anyhow::Error);
+
+impl From<anyhow::Error> for ProxyError {
+    fn from(value: anyhow::Error) -> Self {
+        Self(value)
+    }
+}
+
+impl IntoResponse for ProxyError {
+    fn into_response(self) -> Response {
+        (
+            StatusCode::BAD_GATEWAY,
+            format!("Unable to verify response: {}", self.0),
+        )
+            .into_response()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{path::Path as FsPath, sync::Mutex};
+
+    use axum::{
+        body::Body,
+        http::{Request, Uri},
+    };
+    use iota_swarm_config::network_config_builder::ConfigBuilder;
+    use iota_types::object::Object;
+    use tower::ServiceExt;
+
+    use super::*;
+    use crate::Config;
+
+    /// Starts a stand-in for the full node, which records the paths it is
+    /// asked for. Objects are answered with `object` if one is given, all other
+    /// requests fail.
+    async fn start_full_node(object: Option<Object>) -> (SocketAddr, Arc<Mutex<Vec<String>>>) {
+        let requests = Arc::new(Mutex::new(vec![]));
+        let recorded = requests.clone();
+        let app = Router::new().fallback(move |uri: Uri| {
+            let recorded = recorded.clone();
+            let object = object.clone();
+            async move {
+                recorded.lock().unwrap().push(uri.path().to_owned());
+                match object {
+                    Some(object) if uri.path().starts_with("/rest/objects/") => {
+                        bcs_response(&object).into_response()
+                    }
+                    _ => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+                }
+            }
+        });
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        (address, requests)
+    }
+
+    async fn light_client(full_node: SocketAddr, dir: &FsPath) -> Arc<LightClient> {
+        let genesis = ConfigBuilder::new_with_temp_dir().build().genesis;
+        genesis.save(dir.join("genesis.blob")).unwrap();
+        let config = Config {
+            full_node_url: format!("http://{full_node}"),
+            checkpoint_summary_dir: dir.to_path_buf(),
+            genesis_filename: "genesis.blob".into(),
+        };
+        Arc::new(LightClient::new(config).await.unwrap())
+    }
+
+    async fn get(light_client: Arc<LightClient>, uri: String) -> (StatusCode, String) {
+        let response = router(light_client)
+            .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, String::from_utf8(body.to_vec()).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_full_node_error_is_reported_as_bad_gateway() {
+        let dir = tempfile::tempdir().unwrap();
+        let (full_node, requests) = start_full_node(None).await;
+        let light_client = light_client(full_node, dir.path()).await;
+        let object_id = ObjectID::random();
+
+        let (status, body) = get(light_client, format!("/objects/{object_id}")).await;
+        assert_eq!(status, StatusCode::BAD_GATEWAY);
+        assert!(body.starts_with("Unable to verify response"), "{body}");
+        assert!(body.contains("500"), "{body}");
+        // The request was forwarded to the REST API of the full node.
+        assert_eq!(
+            *requests.lock().unwrap(),
+            vec![format!("/rest/objects/{object_id}")]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_unverified_object_is_not_returned() {
+        let dir = tempfile::tempdir().unwrap();
+        let object = Object::immutable_with_id_for_testing(ObjectID::random());
+        let (full_node, requests) = start_full_node(Some(object.clone())).await;
+        let light_client = light_client(full_node, dir.path()).await;
+
+        // The full node serves the object, but fails to serve the transaction
+        // needed to verify it.
+        let (status, _) = get(light_client, format!("/objects/{}", object.id())).await;
+        assert_eq!(status, StatusCode::BAD_GATEWAY);
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests[0], format!("/rest/objects/{}", object.id()));
+        assert!(requests.len() > 1);
+    }
+}
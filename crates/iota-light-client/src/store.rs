@@ -0,0 +1,174 @@
+// Copyright (c) 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    fs,
+    io::{Read, Write},
+    path::PathBuf,
+};
+
+use anyhow::anyhow;
+use iota_types::{
+    base_types::EpochId,
+    committee::Committee,
+    messages_checkpoint::{CertifiedCheckpointSummary, CheckpointSequenceNumber, EndOfEpochData},
+};
+
+const CHECKPOINTS_LIST_FILENAME: &str = "checkpoints.yaml";
+
+// The list of checkpoints at the end of each epoch
+#[derive(Debug, Clone, Default, serde::Deserialize, serde::Serialize)]
+pub struct CheckpointsList {
+    // List of end of epoch checkpoints
+    pub checkpoints: Vec<CheckpointSequenceNumber>,
+}
+
+/// Persists the chain of committees as the list of end-of-epoch checkpoints
+/// and their certified summaries.
+///
+/// A summary is only written once it has been verified against the
+/// committee of its epoch, so every stored summary can be trusted without
+/// verifying it again. The list may contain checkpoints that have been
+/// discovered but not verified (and stored) yet.
+#[derive(Debug, Clone)]
+pub struct CheckpointStore {
+    dir: PathBuf,
+}
+
+impl CheckpointStore {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    pub fn read_checkpoint_list(&self) -> anyhow::Result<CheckpointsList> {
+        let checkpoints_path = self.dir.join(CHECKPOINTS_LIST_FILENAME);
+        if !checkpoints_path.exists() {
+            return Ok(CheckpointsList::default());
+        }
+        // Read the resulting file and parse the yaml checkpoint list
+        let reader = fs::File::open(checkpoints_path)?;
+        Ok(serde_yaml::from_reader(reader)?)
+    }
+
+    pub fn write_checkpoint_list(&self, checkpoints_list: &CheckpointsList) -> anyhow::Result<()> {
+        // Write the checkpoint list to a file
+        let checkpoints_path = self.dir.join(CHECKPOINTS_LIST_FILENAME);
+        let mut writer = fs::File::create(checkpoints_path)?;
+        let mut bytes = Vec::new();
+        serde_yaml::to_writer(&mut bytes, &checkpoints_list)?;
+        writer
+            .write_all(&bytes)
+            .map_err(|_| anyhow!("Unable to serialize checkpoint list"))
+    }
+
+    fn checkpoint_path(&self, seq: CheckpointSequenceNumber) -> PathBuf {
+        self.dir.join(format!("{seq}.yaml"))
+    }
+
+    /// Whether the verified summary of the checkpoint is stored.
+    pub fn has_checkpoint(&self, seq: CheckpointSequenceNumber) -> bool {
+        self.checkpoint_path(seq).exists()
+    }
+
+    pub fn read_checkpoint(
+        &self,
+        seq: CheckpointSequenceNumber,
+    ) -> anyhow::Result<CertifiedCheckpointSummary> {
+        let checkpoint_path = self.checkpoint_path(seq);
+        let mut reader = fs::File::open(&checkpoint_path)?;
+        let metadata = fs::metadata(&checkpoint_path)?;
+        let mut buffer = vec![0; metadata.len() as usize];
+        reader.read_exact(&mut buffer)?;
+        bcs::from_bytes(&buffer).map_err(|_| anyhow!("Unable to parse checkpoint file"))
+    }
+
+    /// Stores a summary. It must have been verified by the caller.
+    pub fn write_checkpoint(&self, summary: &CertifiedCheckpointSummary) -> anyhow::Result<()> {
+        let mut writer = fs::File::create(self.checkpoint_path(summary.sequence_number))?;
+        let bytes = bcs::to_bytes(&summary)
+            .map_err(|_| anyhow!("Unable to serialize checkpoint summary"))?;
+        writer.write_all(&bytes)?;
+        Ok(())
+    }
+
+    /// Returns the committee of `epoch`, taken from the stored end-of-epoch
+    /// checkpoint of the previous epoch. The genesis committee is used for
+    /// epoch 0.
+    pub fn committee_for_epoch(
+        &self,
+        epoch: EpochId,
+        genesis_committee: &Committee,
+    ) -> anyhow::Result<Committee> {
+        if epoch == genesis_committee.epoch {
+            return Ok(genesis_committee.clone());
+        }
+
+        let checkpoints_list = self.read_checkpoint_list()?;
+        for seq in checkpoints_list.checkpoints.iter().rev() {
+            if !self.has_checkpoint(*seq) {
+                continue;
+            }
+            let summary = self.read_checkpoint(*seq)?;
+            if summary.epoch() + 1 == epoch {
+                return next_epoch_committee(&summary);
+            }
+            if summary.epoch() < epoch {
+                break;
+            }
+        }
+
+        Err(anyhow!(
+            "No verified committee for epoch {epoch}. Need to Sync."
+        ))
+    }
+}
+
+/// Extracts the committee of the next epoch from an end-of-epoch checkpoint.
+pub fn next_epoch_committee(summary: &CertifiedCheckpointSummary) -> anyhow::Result<Committee> {
+    let Some(EndOfEpochData {
+        next_epoch_committee,
+        ..
+    }) = &summary.end_of_epoch_data
+    else {
+        return Err(anyhow!(
+            "Expected all checkpoints to be end-of-epoch checkpoints"
+        ));
+    };
+
+    Ok(Committee::new(
+        summary.epoch().checked_add(1).unwrap(),
+        next_epoch_committee.iter().cloned().collect(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checkpoint_list_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = CheckpointStore::new(dir.path().to_path_buf());
+
+        // A missing list is an empty one.
+        assert!(store.read_checkpoint_list().unwrap().checkpoints.is_empty());
+
+        let list = CheckpointsList {
+            checkpoints: vec![9769, 85168, 161191],
+        };
+        store.write_checkpoint_list(&list).unwrap();
+        assert_eq!(
+            store.read_checkpoint_list().unwrap().checkpoints,
+            list.checkpoints
+        );
+        assert!(!store.has_checkpoint(9769));
+    }
+
+    #[test]
+    fn test_read_example_checkpoint_list() {
+        let store =
+            CheckpointStore::new(PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("example_config"));
+        let list = store.read_checkpoint_list().unwrap();
+        assert_eq!(list.checkpoints.first(), Some(&9769));
+    }
+}
@@ -0,0 +1,314 @@
+// Copyright (c) Mysten Labs, Inc.
+// Modifications Copyright (c) 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::anyhow;
+use iota_rest_api::CheckpointData;
+use iota_types::{
+    committee::Committee,
+    digests::TransactionDigest,
+    effects::{TransactionEffects, TransactionEffectsAPI, TransactionEvents},
+    messages_checkpoint::CheckpointSequenceNumber,
+    transaction::SenderSignedData,
+};
+
+/// A transaction, its effects and events, authenticated by the certified
+/// checkpoint it was executed in.
+#[derive(Debug, Clone)]
+pub struct VerifiedTransactionData {
+    pub checkpoint: CheckpointSequenceNumber,
+    pub transaction: SenderSignedData,
+    pub effects: TransactionEffects,
+    pub events: Option<TransactionEvents>,
+}
+
+/// Verifies the checkpoint against the committee of its epoch, and extracts
+/// the transaction `tid` from it. The transaction, effects and events are
+/// checked against the digests certified in the checkpoint contents.
+pub fn extract_verified_transaction(
+    checkpoint: &CheckpointData,
+    committee: &Committee,
+    tid: TransactionDigest,
+) -> anyhow::Result<VerifiedTransactionData> {
+    let summary = &checkpoint.checkpoint_summary;
+
+    // Verify the checkpoint summary using the committee
+    summary.verify_with_contents(committee, Some(&checkpoint.checkpoint_contents))?;
+
+    // Check the validity of the transaction
+    let contents = &checkpoint.checkpoint_contents;
+    let (matching_tx, _) = checkpoint
+        .transactions
+        .iter()
+        .zip(contents.iter())
+        // Note that we get the digest of the effects to ensure this is
+        // indeed the correct effects that are authenticated in the contents.
+        .find(|(tx, digest)| {
+            tx.effects.execution_digests() == **digest && digest.transaction == tid
+        })
+        .ok_or(anyhow!("Transaction not found in checkpoint contents"))?;
+
+    // Check the transaction data is the one certified.
+    anyhow::ensure!(
+        *matching_tx.transaction.digest() == tid,
+        "Transaction digest does not match"
+    );
+
+    // Check the events are all correct.
+    let events_digest = matching_tx.events.as_ref().map(|events| events.digest());
+    anyhow::ensure!(
+        events_digest.as_ref() == matching_tx.effects.events_digest(),
+        "Events digest does not match"
+    );
+
+    Ok(VerifiedTransactionData {
+        checkpoint: summary.sequence_number,
+        transaction: matching_tx.transaction.data().clone(),
+        effects: matching_tx.effects.clone(),
+        events: matching_tx.events.clone(),
+    })
+}
+
+pub fn extract_verified_effects_and_events(
+    checkpoint: &CheckpointData,
+    committee: &Committee,
+    tid: TransactionDigest,
+) -> anyhow::Result<(TransactionEffects, Option<TransactionEvents>)> {
+    let verified = extract_verified_transaction(checkpoint, committee, tid)?;
+
+    // Since we do not check objects we do not return them
+    Ok((verified.effects, verified.events))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, io::Read, path::PathBuf, str::FromStr};
+
+    use iota_types::{
+        base_types::IotaAddress,
+        crypto::AuthorityQuorumSignInfo,
+        message_envelope::Envelope,
+        messages_checkpoint::{CheckpointSummary, FullCheckpointContents},
+        storage::ReadStore,
+    };
+    use simulacrum::Simulacrum;
+
+    use super::*;
+
+    async fn read_full_checkpoint(checkpoint_path: &PathBuf) -> anyhow::Result<CheckpointData> {
+        let mut reader = fs::File::open(checkpoint_path.clone())?;
+        let metadata = fs::metadata(checkpoint_path)?;
+        let mut buffer = vec![0; metadata.len() as usize];
+        reader.read_exact(&mut buffer)?;
+        bcs::from_bytes(&buffer).map_err(|_| anyhow!("Unable to parse checkpoint file"))
+    }
+
+    async fn read_data() -> (Committee, CheckpointData) {
+        let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        d.push("example_config/20873329.yaml");
+
+        let mut reader = fs::File::open(d.clone()).unwrap();
+        let metadata = fs::metadata(&d).unwrap();
+        let mut buffer = vec![0; metadata.len() as usize];
+        reader.read_exact(&mut buffer).unwrap();
+        let checkpoint: Envelope<CheckpointSummary, AuthorityQuorumSignInfo<true>> =
+            bcs::from_bytes(&buffer)
+                .map_err(|_| anyhow!("Unable to parse checkpoint file"))
+                .unwrap();
+
+        let prev_committee = checkpoint
+            .end_of_epoch_data
+            .as_ref()
+            .ok_or(anyhow!(
+                "Expected all checkpoints to be end-of-epoch checkpoints"
+            ))
+            .unwrap()
+            .next_epoch_committee
+            .iter()
+            .cloned()
+            .collect();
+
+        // Make a committee object using this
+        let committee = Committee::new(checkpoint.epoch().checked_add(1).unwrap(), prev_committee);
+
+        let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        d.push("example_config/20958462.bcs");
+
+        let full_checkpoint = read_full_checkpoint(&d).await.unwrap();
+
+        (committee, full_checkpoint)
+    }
+
+    #[tokio::test]
+    #[ignore = "waiting for upstream changes"]
+    async fn test_checkpoint_all_good() {
+        let (committee, full_checkpoint) = read_data().await;
+
+        extract_verified_effects_and_events(
+            &full_checkpoint,
+            &committee,
+            TransactionDigest::from_str("8RiKBwuAbtu8zNCtz8SrcfHyEUzto6zi6cMVA9t4WhWk").unwrap(),
+        )
+        .unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "waiting for upstream changes"]
+    async fn test_checkpoint_bad_committee() {
+        let (mut committee, full_checkpoint) = read_data().await;
+
+        // Change committee
+        committee.epoch += 10;
+
+        assert!(
+            extract_verified_effects_and_events(
+                &full_checkpoint,
+                &committee,
+                TransactionDigest::from_str("8RiKBwuAbtu8zNCtz8SrcfHyEUzto6zi6cMVA9t4WhWk")
+                    .unwrap(),
+            )
+            .is_err()
+        );
+    }
+
+    #[tokio::test]
+    #[ignore = "waiting for upstream changes"]
+    async fn test_checkpoint_no_transaction() {
+        let (committee, full_checkpoint) = read_data().await;
+
+        assert!(
+            extract_verified_effects_and_events(
+                &full_checkpoint,
+                &committee,
+                TransactionDigest::from_str("8RiKBwuAbtu8zNCtz8SrcfHyEUzto6zj6cMVA9t4WhWk")
+                    .unwrap(),
+            )
+            .is_err()
+        );
+    }
+
+    #[tokio::test]
+    #[ignore = "waiting for upstream changes"]
+    async fn test_checkpoint_bad_contents() {
+        let (committee, mut full_checkpoint) = read_data().await;
+
+        // Change contents
+        let random_contents = FullCheckpointContents::random_for_testing();
+        full_checkpoint.checkpoint_contents = random_contents.checkpoint_contents();
+
+        assert!(
+            extract_verified_effects_and_events(
+                &full_checkpoint,
+                &committee,
+                TransactionDigest::from_str("8RiKBwuAbtu8zNCtz8SrcfHyEUzto6zj6cMVA9t4WhWk")
+                    .unwrap(),
+            )
+            .is_err()
+        );
+    }
+
+    #[tokio::test]
+    #[ignore = "waiting for upstream changes"]
+    async fn test_checkpoint_bad_events() {
+        let (committee, mut full_checkpoint) = read_data().await;
+
+        let event = full_checkpoint.transactions[4]
+            .events
+            .as_ref()
+            .unwrap()
+            .data[0]
+            .clone();
+
+        for t in &mut full_checkpoint.transactions {
+            if let Some(events) = &mut t.events {
+                events.data.push(event.clone());
+            }
+        }
+
+        assert!(
+            extract_verified_effects_and_events(
+                &full_checkpoint,
+                &committee,
+                TransactionDigest::from_str("8RiKBwuAbtu8zNCtz8SrcfHyEUzto6zj6cMVA9t4WhWk")
+                    .unwrap(),
+            )
+            .is_err()
+        );
+    }
+
+    /// Executes a transfer and returns the certified checkpoint that includes
+    /// it, the committee that certified it and the transaction digest.
+    fn simulated_checkpoint() -> (CheckpointData, Committee, TransactionDigest) {
+        let mut sim = Simulacrum::new();
+        let (transaction, _) = sim.transfer_txn(IotaAddress::random_for_testing_only());
+        let tid = *transaction.digest();
+        sim.execute_transaction(transaction).unwrap();
+        let checkpoint = sim.create_checkpoint();
+
+        let contents = sim
+            .get_checkpoint_contents_by_digest(&checkpoint.content_digest)
+            .unwrap()
+            .unwrap();
+        let committee = sim.get_committee(checkpoint.epoch()).unwrap().unwrap();
+        let checkpoint = sim.get_checkpoint_data(checkpoint, contents).unwrap();
+        (checkpoint, committee.as_ref().clone(), tid)
+    }
+
+    #[test]
+    fn test_simulated_checkpoint_all_good() {
+        let (checkpoint, committee, tid) = simulated_checkpoint();
+
+        let verified = extract_verified_transaction(&checkpoint, &committee, tid).unwrap();
+        assert_eq!(
+            verified.checkpoint,
+            checkpoint.checkpoint_summary.sequence_number
+        );
+        assert_eq!(*verified.effects.transaction_digest(), tid);
+        assert_eq!(verified.transaction.digest(), tid);
+    }
+
+    #[test]
+    fn test_simulated_checkpoint_bad_committee() {
+        let (checkpoint, mut committee, tid) = simulated_checkpoint();
+
+        committee.epoch += 10;
+
+        assert!(extract_verified_transaction(&checkpoint, &committee, tid).is_err());
+    }
+
+    #[test]
+    fn test_simulated_checkpoint_no_transaction() {
+        let (checkpoint, committee, _) = simulated_checkpoint();
+
+        assert!(
+            extract_verified_transaction(&checkpoint, &committee, TransactionDigest::random())
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_simulated_checkpoint_bad_contents() {
+        let (mut checkpoint, committee, tid) = simulated_checkpoint();
+
+        checkpoint.checkpoint_contents =
+            FullCheckpointContents::random_for_testing().checkpoint_contents();
+
+        assert!(extract_verified_transaction(&checkpoint, &committee, tid).is_err());
+    }
+
+    #[test]
+    fn test_simulated_checkpoint_bad_events() {
+        let (mut checkpoint, committee, tid) = simulated_checkpoint();
+
+        // Events that were not certified by the effects.
+        let transaction = checkpoint
+            .transactions
+            .iter_mut()
+            .find(|transaction| *transaction.transaction.digest() == tid)
+            .unwrap();
+        assert!(transaction.effects.events_digest().is_none());
+        transaction.events = Some(TransactionEvents::default());
+
+        assert!(extract_verified_transaction(&checkpoint, &committee, tid).is_err());
+    }
+}
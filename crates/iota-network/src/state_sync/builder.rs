@@ -15,7 +15,7 @@ use iota_config::p2p::StateSyncConfig;
 use iota_types::{messages_checkpoint::VerifiedCheckpoint, storage::WriteStore};
 use tap::Pipe;
 use tokio::{
-    sync::{broadcast, mpsc},
+    sync::{broadcast, mpsc, watch},
     task::JoinSet,
 };
 
@@ -23,6 +23,7 @@ use super::{
     metrics::Metrics,
     server::{CheckpointContentsDownloadLimitLayer, Server},
     Handle, PeerHeights, StateSync, StateSyncEventLoop, StateSyncMessage, StateSyncServer,
+    SyncMode,
 };
 
 pub struct Builder<S> {
@@ -138,18 +139,22 @@ where
         let (checkpoint_event_sender, _receiver) =
             broadcast::channel(config.synced_checkpoint_broadcast_channel_capacity());
         let weak_sender = sender.downgrade();
-        let handle = Handle {
-            sender,
-            checkpoint_event_sender: checkpoint_event_sender.clone(),
-        };
         let peer_heights = PeerHeights {
             peers: HashMap::new(),
+            scores: HashMap::new(),
             unprocessed_checkpoints: HashMap::new(),
             sequence_number_to_digest: HashMap::new(),
             wait_interval_when_no_peer_to_sync_content: Duration::from_secs(10),
         }
         .pipe(RwLock::new)
         .pipe(Arc::new);
+        let (sync_mode_sender, sync_mode) = watch::channel(SyncMode::Peers);
+        let handle = Handle {
+            sender,
+            checkpoint_event_sender: checkpoint_event_sender.clone(),
+            peer_heights: peer_heights.clone(),
+            sync_mode,
+        };
 
         let server = Server {
             store: store.clone(),
@@ -168,6 +173,7 @@ where
                 checkpoint_event_sender,
                 metrics,
                 archive_readers,
+                sync_mode_sender,
             },
             server,
         )
@@ -184,6 +190,7 @@ pub struct UnstartedStateSync<S> {
     pub(super) checkpoint_event_sender: broadcast::Sender<VerifiedCheckpoint>,
     pub(super) metrics: Metrics,
     pub(super) archive_readers: ArchiveReaderBalancer,
+    pub(super) sync_mode_sender: watch::Sender<SyncMode>,
 }
 
 impl<S> UnstartedStateSync<S>
@@ -201,6 +208,7 @@ where
             checkpoint_event_sender,
             metrics,
             archive_readers,
+            sync_mode_sender,
         } = self;
 
         (
@@ -219,6 +227,7 @@ where
                 metrics,
                 archive_readers,
                 sync_checkpoint_from_archive_task: None,
+                sync_mode_sender,
             },
             handle,
         )
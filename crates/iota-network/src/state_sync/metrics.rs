@@ -4,11 +4,17 @@
 
 use std::sync::Arc;
 
+use anemo::PeerId;
 use iota_metrics::histogram::Histogram;
 use iota_types::messages_checkpoint::CheckpointSequenceNumber;
-use prometheus::{register_int_gauge_with_registry, IntGauge, Registry};
+use prometheus::{
+    register_gauge_vec_with_registry, register_int_gauge_with_registry, GaugeVec, IntGauge,
+    Registry,
+};
 use tap::Pipe;
 
+use super::SyncMode;
+
 #[derive(Clone)]
 pub(super) struct Metrics(Option<Arc<Inner>>);
 
@@ -47,6 +53,25 @@ impl Metrics {
         }
     }
 
+    pub fn set_sync_mode(&self, mode: SyncMode) {
+        if let Some(inner) = &self.0 {
+            inner.sync_mode.set(mode as i64);
+        }
+    }
+
+    pub fn set_peer_scores(&self, scores: impl IntoIterator<Item = (PeerId, f64)>) {
+        if let Some(inner) = &self.0 {
+            // Drop the scores of peers we are no longer connected to
+            inner.peer_score.reset();
+            for (peer_id, score) in scores {
+                inner
+                    .peer_score
+                    .with_label_values(&[&peer_id.to_string()])
+                    .set(score);
+            }
+        }
+    }
+
     pub fn checkpoint_summary_age_metric(&self) -> Option<&Histogram> {
         if let Some(inner) = &self.0 {
             return Some(&inner.checkpoint_summary_age_ms);
@@ -60,6 +85,8 @@ struct Inner {
     highest_verified_checkpoint: IntGauge,
     highest_synced_checkpoint: IntGauge,
     checkpoint_summary_age_ms: Histogram,
+    sync_mode: IntGauge,
+    peer_score: GaugeVec,
 }
 
 impl Inner {
@@ -91,6 +118,21 @@ impl Inner {
                 "Age of checkpoints summaries when they arrive and are verified.",
                 registry,
             ),
+
+            sync_mode: register_int_gauge_with_registry!(
+                "state_sync_mode",
+                "Where checkpoint contents are synced from: 0 for peers, 1 for the archive",
+                registry
+            )
+            .unwrap(),
+
+            peer_score: register_gauge_vec_with_registry!(
+                "state_sync_peer_score",
+                "Score of a peer by its observed latency, throughput and failure rate",
+                &["peer_id"],
+                registry
+            )
+            .unwrap(),
         }
         .pipe(Arc::new)
     }
@@ -34,7 +34,9 @@
 //!   of data availability.
 //!
 //! The `PeerHeights` struct is used to track the highest_synced_checkpoint
-//! watermark for all of our peers.
+//! watermark for all of our peers, along with a `PeerScore` of their observed
+//! latency, throughput and failure rate which is used to prefer the best peers
+//! when requesting checkpoints.
 //!
 //! When a new checkpoint is discovered, and we've determined that it is higher
 //! than our highest_verified_checkpoint, then StateSync will kick off a task to
@@ -57,6 +59,11 @@
 //! downloaded. Notifications on this broadcast channel will always be made in
 //! order. StateSync will also send out a notification to its peers of the newly
 //! synchronized checkpoint so that it can help other peers synchronize.
+//!
+//! Checkpoint contents which none of our peers have anymore are read from the
+//! archive. When our highest_synced_checkpoint falls too far behind the highest
+//! checkpoint known from our peers, StateSync switches to reading checkpoint
+//! contents from the archive first (see [SyncMode]).
 
 use std::{
     collections::{HashMap, VecDeque},
@@ -64,7 +71,7 @@ use std::{
         atomic::{AtomicU64, Ordering},
         Arc, RwLock,
     },
-    time::Duration,
+    time::{Duration, Instant},
 };
 
 use anemo::{types::PeerEvent, PeerId, Request, Response, Result};
@@ -92,6 +99,7 @@ mod generated {
 }
 mod builder;
 mod metrics;
+mod peer_score;
 mod server;
 #[cfg(test)]
 mod tests;
@@ -103,6 +111,7 @@ pub use generated::{
 };
 use iota_archival::reader::ArchiveReaderBalancer;
 use iota_storage::verify_checkpoint;
+pub use peer_score::PeerScore;
 pub use server::{GetCheckpointAvailabilityResponse, GetCheckpointSummaryRequest};
 
 use self::{metrics::Metrics, server::CheckpointContentsDownloadLimitLayer};
@@ -116,6 +125,8 @@ use self::{metrics::Metrics, server::CheckpointContentsDownloadLimitLayer};
 pub struct Handle {
     sender: mpsc::Sender<StateSyncMessage>,
     checkpoint_event_sender: broadcast::Sender<VerifiedCheckpoint>,
+    peer_heights: Arc<RwLock<PeerHeights>>,
+    sync_mode: watch::Receiver<SyncMode>,
 }
 
 impl Handle {
@@ -140,11 +151,36 @@ impl Handle {
     pub fn subscribe_to_synced_checkpoints(&self) -> broadcast::Receiver<VerifiedCheckpoint> {
         self.checkpoint_event_sender.subscribe()
     }
+
+    /// Returns the observed scores of the peers we have requested checkpoints
+    /// from.
+    pub fn peer_scores(&self) -> HashMap<PeerId, PeerScore> {
+        self.peer_heights.read().unwrap().scores.clone()
+    }
+
+    /// Returns where checkpoint contents are currently synced from.
+    pub fn sync_mode(&self) -> SyncMode {
+        *self.sync_mode.borrow()
+    }
+}
+
+/// Where StateSync syncs checkpoint contents from.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SyncMode {
+    /// Checkpoint contents are downloaded from peers, and only read from the
+    /// archive if none of our peers have them anymore.
+    Peers,
+    /// We are too far behind our peers, so checkpoint contents are read from
+    /// the archive first.
+    Archive,
 }
 
+#[derive(Debug)]
 struct PeerHeights {
     /// Table used to track the highest checkpoint for each of our peers.
     peers: HashMap<PeerId, PeerStateSyncInfo>,
+    /// Observed quality of our peers when serving checkpoint requests.
+    scores: HashMap<PeerId, PeerScore>,
     unprocessed_checkpoints: HashMap<CheckpointDigest, Checkpoint>,
     sequence_number_to_digest: HashMap<CheckpointSequenceNumber, CheckpointDigest>,
 
@@ -236,6 +272,34 @@ impl PeerHeights {
         }
     }
 
+    pub fn remove_peer(&mut self, peer_id: &PeerId) {
+        self.peers.remove(peer_id);
+        self.scores.remove(peer_id);
+    }
+
+    pub fn record_peer_summary_success(&mut self, peer_id: PeerId, latency: Duration) {
+        self.scores
+            .entry(peer_id)
+            .or_default()
+            .record_summary_success(latency);
+    }
+
+    pub fn record_peer_content_success(
+        &mut self,
+        peer_id: PeerId,
+        latency: Duration,
+        transactions: usize,
+    ) {
+        self.scores
+            .entry(peer_id)
+            .or_default()
+            .record_content_success(latency, transactions);
+    }
+
+    pub fn record_peer_failure(&mut self, peer_id: PeerId) {
+        self.scores.entry(peer_id).or_default().record_failure();
+    }
+
     pub fn cleanup_old_checkpoints(&mut self, sequence_number: CheckpointSequenceNumber) {
         self.unprocessed_checkpoints
             .retain(|_digest, checkpoint| *checkpoint.sequence_number() > sequence_number);
@@ -283,11 +347,12 @@ impl PeerHeights {
     }
 }
 
-// PeerBalancer is an Iterator that selects peers based on RTT with some added
-// randomness.
+// PeerBalancer is an Iterator that selects peers based on their score with some
+// added randomness. Demoted peers are only selected once all others were.
 #[derive(Clone)]
 struct PeerBalancer {
     peers: VecDeque<(anemo::Peer, PeerStateSyncInfo)>,
+    demoted_peers: VecDeque<(anemo::Peer, PeerStateSyncInfo)>,
     requested_checkpoint: Option<CheckpointSequenceNumber>,
     request_type: PeerCheckpointRequestType,
 }
@@ -304,18 +369,45 @@ impl PeerBalancer {
         peer_heights: Arc<RwLock<PeerHeights>>,
         request_type: PeerCheckpointRequestType,
     ) -> Self {
+        let peer_heights = peer_heights.read().unwrap();
         let mut peers: Vec<_> = peer_heights
-            .read()
-            .unwrap()
             .peers_on_same_chain()
             // Filter out any peers who we aren't connected with.
-            .filter_map(|(peer_id, info)| network.peer(*peer_id).map(|peer| (peer, *info)))
+            .filter_map(|(peer_id, info)| {
+                network.peer(*peer_id).map(|peer| {
+                    let score = peer_heights
+                        .scores
+                        .get(peer_id)
+                        .copied()
+                        .unwrap_or_default();
+                    (peer, *info, score)
+                })
+            })
             .collect();
-        peers.sort_by(|(peer_a, _), (peer_b, _)| {
-            peer_a.connection_rtt().cmp(&peer_b.connection_rtt())
+        // Best scored peers first, peers which were not scored yet by their RTT.
+        peers.sort_by(|(peer_a, _, score_a), (peer_b, _, score_b)| {
+            score_b
+                .value(&request_type, peer_b.connection_rtt())
+                .total_cmp(&score_a.value(&request_type, peer_a.connection_rtt()))
         });
+        // Peers are only demoted for being slow against the latencies observed for
+        // the same type of requests.
+        let fastest_latency = peers
+            .iter()
+            .filter_map(|(_, _, score)| score.latency(&request_type))
+            .min();
+        let (demoted_peers, peers): (Vec<_>, Vec<_>) = peers
+            .into_iter()
+            .partition(|(_, _, score)| score.is_demoted(&request_type, fastest_latency));
         Self {
-            peers: peers.into(),
+            peers: peers
+                .into_iter()
+                .map(|(peer, info, _)| (peer, info))
+                .collect(),
+            demoted_peers: demoted_peers
+                .into_iter()
+                .map(|(peer, info, _)| (peer, info))
+                .collect(),
             requested_checkpoint: None,
             request_type,
         }
@@ -331,12 +423,24 @@ impl Iterator for PeerBalancer {
     type Item = StateSyncClient<anemo::Peer>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        while !self.peers.is_empty() {
+        while !self.peers.is_empty() || !self.demoted_peers.is_empty() {
             const SELECTION_WINDOW: usize = 2;
-            let idx =
-                rand::thread_rng().gen_range(0..std::cmp::min(SELECTION_WINDOW, self.peers.len()));
-            let (peer, info) = self.peers.remove(idx).unwrap();
+            const CONTENT_SELECTION_WINDOW: usize = 4;
+            if self.peers.is_empty() {
+                self.peers.append(&mut self.demoted_peers);
+            }
             let requested_checkpoint = self.requested_checkpoint.unwrap_or(0);
+            let idx = match &self.request_type {
+                PeerCheckpointRequestType::Summary => rand::thread_rng()
+                    .gen_range(0..std::cmp::min(SELECTION_WINDOW, self.peers.len())),
+                // Spread the contents of consecutive checkpoints across our best peers so
+                // that they are downloaded from them in parallel.
+                PeerCheckpointRequestType::Content => {
+                    requested_checkpoint as usize
+                        % std::cmp::min(CONTENT_SELECTION_WINDOW, self.peers.len())
+                }
+            };
+            let (peer, info) = self.peers.remove(idx).unwrap();
             match &self.request_type {
                 // Summary will never be pruned
                 PeerCheckpointRequestType::Summary if info.height >= requested_checkpoint => {
@@ -391,6 +495,7 @@ struct StateSyncEventLoop<S> {
 
     archive_readers: ArchiveReaderBalancer,
     sync_checkpoint_from_archive_task: Option<AbortHandle>,
+    sync_mode_sender: watch::Sender<SyncMode>,
 }
 
 impl<S> StateSyncEventLoop<S>
@@ -424,7 +529,9 @@ where
         let (_sender, receiver) = oneshot::channel();
         tokio::spawn(update_checkpoint_watermark_metrics(
             receiver,
+            self.network.clone(),
             self.store.clone(),
+            self.peer_heights.clone(),
             self.metrics.clone(),
         ));
 
@@ -439,23 +546,22 @@ where
             self.config.checkpoint_content_download_tx_concurrency(),
             self.config.checkpoint_content_timeout(),
             target_checkpoint_contents_sequence_receiver,
+            self.sync_mode_sender.subscribe(),
         );
         let task_handle = self.tasks.spawn(task);
         self.sync_checkpoint_contents_task = Some(task_handle);
 
-        // Start archive based checkpoint content sync loop.
-        // TODO: Consider switching to sync from archive only on startup.
-        // Right now because the peer set is fixed at startup, a node may eventually
-        // end up with peers who have all purged their local state. In such a scenario
-        // it will be stuck until restart when it ends up with a different set
-        // of peers. Once the discovery mechanism can dynamically identify and
-        // connect to other peers on the network, we will rely on sync from
-        // archive as a fall back.
+        // Start archive based checkpoint content sync loop. It syncs the contents
+        // which none of our peers have anymore, and switches to syncing from the
+        // archive first while we are far behind our peers.
         let task = sync_checkpoint_contents_from_archive(
             self.network.clone(),
             self.archive_readers.clone(),
             self.store.clone(),
             self.peer_heights.clone(),
+            self.metrics.clone(),
+            self.sync_mode_sender.clone(),
+            self.config.archive_sync_lag_threshold(),
         );
         let task_handle = self.tasks.spawn(task);
         self.sync_checkpoint_from_archive_task = Some(task_handle);
@@ -645,7 +751,7 @@ where
                 self.spawn_get_latest_from_peer(peer_id);
             }
             Ok(PeerEvent::LostPeer(peer_id, _)) => {
-                self.peer_heights.write().unwrap().peers.remove(&peer_id);
+                self.peer_heights.write().unwrap().remove_peer(&peer_id);
             }
 
             Err(RecvError::Closed) => {
@@ -1042,8 +1148,10 @@ where
                 // Iterate through peers trying each one in turn until we're able to
                 // successfully get the target checkpoint
                 for mut peer in peers {
+                    let peer_id = peer.inner().peer_id();
                     let request = Request::new(GetCheckpointSummaryRequest::BySequenceNumber(next))
                         .with_timeout(timeout);
+                    let start = Instant::now();
                     if let Some(checkpoint) = peer
                         .get_checkpoint_summary(request)
                         .await
@@ -1058,6 +1166,7 @@ where
                                 "peer returned checkpoint with wrong sequence number: expected {next}, got {}",
                                 checkpoint.sequence_number()
                             );
+                            peer_heights.write().unwrap().record_peer_failure(peer_id);
                             continue;
                         }
 
@@ -1073,17 +1182,18 @@ where
                                     pinned_checkpoints[pinned_digest_index].1,
                                     checkpoint_digest
                                 );
+                                peer_heights.write().unwrap().record_peer_failure(peer_id);
                                 continue;
                             }
                         }
 
+                        let mut peer_heights = peer_heights.write().unwrap();
+                        peer_heights.record_peer_summary_success(peer_id, start.elapsed());
                         // Insert in our store in the event that things fail and we need to retry
-                        peer_heights
-                            .write()
-                            .unwrap()
-                            .insert_checkpoint(checkpoint.clone());
-                        return (Some(checkpoint), next, Some(peer.inner().peer_id()));
+                        peer_heights.insert_checkpoint(checkpoint.clone());
+                        return (Some(checkpoint), next, Some(peer_id));
                     }
+                    peer_heights.write().unwrap().record_peer_failure(peer_id);
                 }
                 (None, next, None)
             }
@@ -1156,11 +1266,19 @@ where
 /// Syncs checkpoint contents from one of the archive_readers if the
 /// highest_synced_checkpoint < lowest_checkpoint among peers. The requesting
 /// checkpoint range is from highest_synced_checkpoint+1 to lowest_checkpoint.
+///
+/// If the highest_synced_checkpoint lags more than `archive_sync_lag_threshold`
+/// behind the highest checkpoint known from peers, and an archive has the next
+/// checkpoints, it switches to [SyncMode::Archive] and syncs up to the highest
+/// known checkpoint, while syncing contents from peers is paused.
 async fn sync_checkpoint_contents_from_archive<S>(
     network: anemo::Network,
     archive_readers: ArchiveReaderBalancer,
     store: S,
     peer_heights: Arc<RwLock<PeerHeights>>,
+    metrics: Metrics,
+    sync_mode_sender: watch::Sender<SyncMode>,
+    archive_sync_lag_threshold: u64,
 ) where
     S: WriteStore + Clone + Send + Sync + 'static,
 {
@@ -1177,6 +1295,10 @@ async fn sync_checkpoint_contents_from_archive<S>(
             .iter()
             .map(|(_p, state_sync_info)| state_sync_info.lowest)
             .min();
+        let highest_known_checkpoint = peer_heights
+            .read()
+            .unwrap()
+            .highest_known_checkpoint_sequence_number();
         let highest_synced = store
             .get_highest_synced_checkpoint()
             .expect("store operation should not fail")
@@ -1187,15 +1309,29 @@ async fn sync_checkpoint_contents_from_archive<S>(
         } else {
             false
         };
-        if sync_from_archive {
+        let far_behind = highest_known_checkpoint.is_some_and(|highest_known_checkpoint| {
+            highest_known_checkpoint.saturating_sub(highest_synced) > archive_sync_lag_threshold
+        });
+        let mut sync_mode = SyncMode::Peers;
+        if sync_from_archive || far_behind {
             let start = highest_synced
                 .checked_add(1)
                 .expect("Checkpoint seq num overflow");
-            let checkpoint_range = start..lowest_checkpoint_on_peers.unwrap();
+            let end = if far_behind {
+                // The if condition should ensure that this is Some
+                highest_known_checkpoint.unwrap().saturating_add(1)
+            } else {
+                lowest_checkpoint_on_peers.unwrap()
+            };
+            let checkpoint_range = start..end;
             if let Some(archive_reader) = archive_readers
                 .pick_one_random(checkpoint_range.clone())
                 .await
             {
+                if far_behind {
+                    sync_mode = SyncMode::Archive;
+                    set_sync_mode(&sync_mode_sender, &metrics, sync_mode);
+                }
                 let txn_counter = Arc::new(AtomicU64::new(0));
                 let checkpoint_counter = Arc::new(AtomicU64::new(0));
                 if let Err(err) = archive_reader
@@ -1220,10 +1356,27 @@ async fn sync_checkpoint_contents_from_archive<S>(
                 warn!("Failed to find an archive reader to complete the state sync request");
             }
         }
+        if sync_mode == SyncMode::Peers {
+            set_sync_mode(&sync_mode_sender, &metrics, sync_mode);
+        }
         tokio::time::sleep(Duration::from_secs(5)).await;
     }
 }
 
+fn set_sync_mode(sender: &watch::Sender<SyncMode>, metrics: &Metrics, sync_mode: SyncMode) {
+    let changed = sender.send_if_modified(|current| {
+        if *current == sync_mode {
+            return false;
+        }
+        *current = sync_mode;
+        true
+    });
+    if changed {
+        info!("Syncing checkpoint contents from {:?}", sync_mode);
+    }
+    metrics.set_sync_mode(sync_mode);
+}
+
 /// Syncs checkpoint contents from peers if the target sequence cursor, which is
 /// changed via target_sequence_channel, is greater than the current one. The
 /// requesting checkpoint range is from current_sequence+1 to
@@ -1239,6 +1392,7 @@ async fn sync_checkpoint_contents<S>(
     checkpoint_content_download_tx_concurrency: u64,
     timeout: Duration,
     mut target_sequence_channel: watch::Receiver<CheckpointSequenceNumber>,
+    mut sync_mode: watch::Receiver<SyncMode>,
 ) where
     S: WriteStore + Clone,
 {
@@ -1266,6 +1420,18 @@ async fn sync_checkpoint_contents<S>(
                     }
                 }
             },
+            Ok(()) = sync_mode.changed() => {
+                sync_mode.borrow_and_update();
+                // Contents in flight are dropped, and syncing restarts from the highest
+                // synced checkpoint, which the archive may have advanced meanwhile.
+                checkpoint_contents_tasks = FuturesOrdered::new();
+                highest_synced = store
+                    .get_highest_synced_checkpoint()
+                    .expect("store operation should not fail");
+                current_sequence = highest_synced.sequence_number().checked_add(1).unwrap();
+                highest_started_network_total_transactions = highest_synced.network_total_transactions;
+                tx_concurrency_remaining = checkpoint_content_download_tx_concurrency;
+            },
             Some(maybe_checkpoint) = checkpoint_contents_tasks.next() => {
                 match maybe_checkpoint {
                     Ok(checkpoint) => {
@@ -1304,9 +1470,11 @@ async fn sync_checkpoint_contents<S>(
             },
         }
 
-        // Start syncing tasks up to configured concurrency limits.
+        // Start syncing tasks up to configured concurrency limits, unless the
+        // contents are synced from the archive first.
         while current_sequence < target_sequence_cursor
             && checkpoint_contents_tasks.len() < checkpoint_content_download_concurrency
+            && *sync_mode.borrow() == SyncMode::Peers
         {
             let next_checkpoint = store
                 .get_checkpoint_by_sequence_number(current_sequence)
@@ -1381,7 +1549,8 @@ where
         PeerCheckpointRequestType::Content,
     )
     .with_checkpoint(*checkpoint.sequence_number());
-    let Some(_contents) = get_full_checkpoint_contents(peers, &store, &checkpoint, timeout).await
+    let Some(_contents) =
+        get_full_checkpoint_contents(peers, &store, &peer_heights, &checkpoint, timeout).await
     else {
         // Delay completion in case of error so we don't hammer the network with
         // retries.
@@ -1404,6 +1573,7 @@ where
 async fn get_full_checkpoint_contents<S>(
     peers: PeerBalancer,
     store: S,
+    peer_heights: &RwLock<PeerHeights>,
     checkpoint: &VerifiedCheckpoint,
     timeout: Duration,
 ) -> Option<FullCheckpointContents>
@@ -1427,12 +1597,10 @@ where
     // Iterate through our selected peers trying each one in turn until we're able
     // to successfully get the target checkpoint
     for mut peer in peers {
-        debug!(
-            ?timeout,
-            "requesting checkpoint contents from {}",
-            peer.inner().peer_id(),
-        );
+        let peer_id = peer.inner().peer_id();
+        debug!(?timeout, "requesting checkpoint contents from {}", peer_id);
         let request = Request::new(digest).with_timeout(timeout);
+        let start = Instant::now();
         if let Some(contents) = peer
             .get_checkpoint_contents(request)
             .await
@@ -1442,6 +1610,11 @@ where
             .tap_none(|| trace!("peer unable to help sync"))
         {
             if contents.verify_digests(digest).is_ok() {
+                peer_heights.write().unwrap().record_peer_content_success(
+                    peer_id,
+                    start.elapsed(),
+                    contents.size(),
+                );
                 let verified_contents = VerifiedCheckpointContents::new_unchecked(contents.clone());
                 store
                     .insert_checkpoint_contents(checkpoint, verified_contents)
@@ -1449,6 +1622,7 @@ where
                 return Some(contents);
             }
         }
+        peer_heights.write().unwrap().record_peer_failure(peer_id);
     }
     debug!("no peers had checkpoint contents");
     None
@@ -1456,7 +1630,9 @@ where
 
 async fn update_checkpoint_watermark_metrics<S>(
     mut recv: oneshot::Receiver<()>,
+    network: anemo::Network,
     store: S,
+    peer_heights: Arc<RwLock<PeerHeights>>,
     metrics: Metrics,
 ) -> Result<()>
 where
@@ -1472,6 +1648,14 @@ where
                 let highest_synced_checkpoint = store.get_highest_synced_checkpoint()
                     .expect("store operation should not fail");
                 metrics.set_highest_synced_checkpoint(highest_synced_checkpoint.sequence_number);
+                let peer_scores: Vec<_> = peer_heights.read().unwrap().scores.iter()
+                    .filter_map(|(peer_id, score)| {
+                        network.peer(*peer_id).map(|peer| {
+                            (*peer_id, score.value(&PeerCheckpointRequestType::Content, peer.connection_rtt()))
+                        })
+                    })
+                    .collect();
+                metrics.set_peer_scores(peer_scores);
              },
             _ = &mut recv => break,
         }
@@ -0,0 +1,116 @@
+// Copyright (c) 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::Duration;
+
+use super::PeerCheckpointRequestType;
+
+/// Weight of a new observation in the moving averages of a score.
+const SMOOTHING_FACTOR: f64 = 0.2;
+/// Number of observed requests before a peer can be demoted.
+const MIN_OBSERVATIONS: u64 = 5;
+/// Failure rate above which a peer is demoted.
+const MAX_FAILURE_RATE: f64 = 0.5;
+/// A peer is demoted if its latency is this many times the latency of our
+/// fastest peer.
+const SLOW_PEER_FACTOR: u32 = 4;
+/// Peers with a lower latency are never demoted for being slow.
+const SLOW_PEER_MIN_LATENCY: Duration = Duration::from_millis(200);
+
+/// The quality of a peer as observed from its responses to our checkpoint
+/// requests.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct PeerScore {
+    /// Moving average of the latency of successful checkpoint summary
+    /// requests.
+    pub summary_latency: Option<Duration>,
+    /// Moving average of the latency of successful checkpoint contents
+    /// requests.
+    pub content_latency: Option<Duration>,
+    /// Moving average of the number of transactions per second received in
+    /// checkpoint contents.
+    pub throughput: f64,
+    /// Moving average of the share of failed requests.
+    pub failure_rate: f64,
+    /// Number of observed requests.
+    pub observations: u64,
+}
+
+impl PeerScore {
+    /// Records a successful checkpoint summary request.
+    pub fn record_summary_success(&mut self, latency: Duration) {
+        self.summary_latency = Some(moving_average_latency(self.summary_latency, latency));
+        self.record_success();
+    }
+
+    /// Records a successful checkpoint contents request, with the number of
+    /// transactions received.
+    pub fn record_content_success(&mut self, latency: Duration, transactions: usize) {
+        self.content_latency = Some(moving_average_latency(self.content_latency, latency));
+        let throughput = transactions as f64 / latency.as_secs_f64().max(0.001);
+        self.throughput = moving_average(self.throughput, throughput);
+        self.record_success();
+    }
+
+    /// Records a request which failed, timed out or returned invalid data.
+    pub fn record_failure(&mut self) {
+        self.failure_rate = moving_average(self.failure_rate, 1.0);
+        self.observations += 1;
+    }
+
+    /// Higher is better. Peers without observed latency for the requests of
+    /// `request_type` are scored by `rtt`.
+    pub(super) fn value(&self, request_type: &PeerCheckpointRequestType, rtt: Duration) -> f64 {
+        (1.0 - self.failure_rate) * (1.0 + self.throughput.ln_1p())
+            / (1.0 + self.latency(request_type).unwrap_or(rtt).as_secs_f64())
+    }
+
+    /// The observed latency of the requests of `request_type`.
+    pub(super) fn latency(&self, request_type: &PeerCheckpointRequestType) -> Option<Duration> {
+        match request_type {
+            PeerCheckpointRequestType::Summary => self.summary_latency,
+            PeerCheckpointRequestType::Content => self.content_latency,
+        }
+    }
+
+    /// Returns true if the peer should only be tried after all others, either
+    /// because it fails most requests or because its observed latency for the
+    /// requests of `request_type` is much higher than `fastest_latency`, the
+    /// lowest observed latency of our peers for them.
+    pub(super) fn is_demoted(
+        &self,
+        request_type: &PeerCheckpointRequestType,
+        fastest_latency: Option<Duration>,
+    ) -> bool {
+        if self.observations < MIN_OBSERVATIONS {
+            return false;
+        }
+        if self.failure_rate > MAX_FAILURE_RATE {
+            return true;
+        }
+        match (self.latency(request_type), fastest_latency) {
+            (Some(latency), Some(fastest_latency)) => {
+                latency > std::cmp::max(fastest_latency * SLOW_PEER_FACTOR, SLOW_PEER_MIN_LATENCY)
+            }
+            _ => false,
+        }
+    }
+
+    fn record_success(&mut self) {
+        self.failure_rate = moving_average(self.failure_rate, 0.0);
+        self.observations += 1;
+    }
+}
+
+fn moving_average(average: f64, observation: f64) -> f64 {
+    average * (1.0 - SMOOTHING_FACTOR) + observation * SMOOTHING_FACTOR
+}
+
+fn moving_average_latency(average: Option<Duration>, observation: Duration) -> Duration {
+    match average {
+        Some(average) => {
+            average.mul_f64(1.0 - SMOOTHING_FACTOR) + observation.mul_f64(SMOOTHING_FACTOR)
+        }
+        None => observation,
+    }
+}
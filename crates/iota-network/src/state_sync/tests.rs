@@ -10,6 +10,7 @@ use iota_archival::{reader::ArchiveReaderBalancer, writer::ArchiveWriter};
 use iota_config::{
     node::ArchiveReaderConfig,
     object_storage_config::{ObjectStoreConfig, ObjectStoreType},
+    p2p::StateSyncConfig,
 };
 use iota_storage::{FileCompression, StorageFormat};
 use iota_swarm_config::test_utils::{empty_contents, CommitteeFixture};
@@ -23,8 +24,8 @@ use tokio::time::{timeout, Instant};
 
 use crate::{
     state_sync::{
-        Builder, GetCheckpointSummaryRequest, PeerStateSyncInfo, StateSync, StateSyncMessage,
-        UnstartedStateSync,
+        Builder, GetCheckpointSummaryRequest, PeerCheckpointRequestType, PeerScore,
+        PeerStateSyncInfo, StateSync, StateSyncMessage, SyncMode, UnstartedStateSync,
     },
     utils::build_network,
 };
@@ -928,3 +929,246 @@ async fn sync_with_checkpoints_watermark() {
         &last_checkpoint_seq
     );
 }
+
+#[test]
+fn peer_score_prefers_fast_and_reliable_peers() {
+    let content = PeerCheckpointRequestType::Content;
+    let summary = PeerCheckpointRequestType::Summary;
+    let rtt = Duration::from_millis(50);
+    let mut fast = PeerScore::default();
+    let mut slow = PeerScore::default();
+    let mut failing = PeerScore::default();
+    for _ in 0..10 {
+        fast.record_content_success(Duration::from_millis(100), 1_000);
+        slow.record_content_success(Duration::from_secs(2), 1_000);
+        failing.record_failure();
+    }
+    let fastest_latency = fast.latency(&content);
+
+    assert!(fast.value(&content, rtt) > slow.value(&content, rtt));
+    assert!(slow.value(&content, rtt) > failing.value(&content, rtt));
+    assert!(!fast.is_demoted(&content, fastest_latency));
+    assert!(slow.is_demoted(&content, fastest_latency));
+    assert!(failing.is_demoted(&content, fastest_latency));
+    // Content latencies are not compared against summary latencies
+    assert_eq!(slow.latency(&summary), None);
+    assert!(!slow.is_demoted(&summary, Some(Duration::from_millis(1))));
+    // Peers without observations are scored by their RTT, and never demoted
+    assert!(!PeerScore::default().is_demoted(&content, fastest_latency));
+
+    // Peers are not demoted before enough requests were observed
+    let mut new_peer = PeerScore::default();
+    new_peer.record_summary_success(Duration::from_secs(2));
+    new_peer.record_failure();
+    assert!(!new_peer.is_demoted(&summary, Some(Duration::from_millis(100))));
+    for _ in 0..3 {
+        new_peer.record_summary_success(Duration::from_secs(2));
+    }
+    assert!(new_peer.is_demoted(&summary, Some(Duration::from_millis(100))));
+    assert_eq!(new_peer.content_latency, None);
+}
+
+#[tokio::test]
+async fn handle_reports_sync_mode_and_peer_scores() {
+    let committee = CommitteeFixture::generate(rand::rngs::OsRng, 0, 4);
+    let (ordered_checkpoints, _, _sequence_number_to_digest, _checkpoints) =
+        committee.make_empty_checkpoints(1, None);
+    let store = SharedInMemoryStore::default();
+    store.inner_mut().insert_genesis_state(
+        ordered_checkpoints.first().cloned().unwrap(),
+        empty_contents(),
+        committee.committee().to_owned(),
+    );
+
+    let (
+        UnstartedStateSync {
+            handle,
+            peer_heights,
+            sync_mode_sender,
+            ..
+        },
+        _server,
+    ) = Builder::new().store(store).build_internal();
+    let peer_id = PeerId([9; 32]); // fake PeerId
+
+    assert_eq!(handle.sync_mode(), SyncMode::Peers);
+    sync_mode_sender.send_replace(SyncMode::Archive);
+    assert_eq!(handle.sync_mode(), SyncMode::Archive);
+
+    peer_heights.write().unwrap().record_peer_content_success(
+        peer_id,
+        Duration::from_millis(100),
+        10,
+    );
+    peer_heights
+        .write()
+        .unwrap()
+        .record_peer_summary_success(peer_id, Duration::from_millis(10));
+    peer_heights.write().unwrap().record_peer_failure(peer_id);
+    let score = handle.peer_scores()[&peer_id];
+    assert_eq!(score.observations, 3);
+    assert_eq!(score.content_latency, Some(Duration::from_millis(100)));
+    assert_eq!(score.summary_latency, Some(Duration::from_millis(10)));
+    assert!(score.failure_rate > 0.0);
+
+    peer_heights.write().unwrap().remove_peer(&peer_id);
+    assert!(handle.peer_scores().is_empty());
+}
+
+#[tokio::test]
+// Test that a node far behind its peers syncs checkpoint contents from the
+// archive first, without requesting them from peers, and switches back to
+// syncing from peers once the archive has no newer checkpoints.
+async fn test_state_sync_switches_to_archive_when_far_behind() -> anyhow::Result<()> {
+    let committee = CommitteeFixture::generate(rand::rngs::OsRng, 0, 4);
+    // Build mock data
+    let (ordered_checkpoints, _, _sequence_number_to_digest, _checkpoints) =
+        committee.make_empty_checkpoints(100, None);
+    // Initialize archive store with the first half of the checkpoints
+    let temp_dir = tempdir()?.into_path();
+    let local_store_config = ObjectStoreConfig {
+        object_store: Some(ObjectStoreType::File),
+        directory: Some(temp_dir.join("local_dir")),
+        ..Default::default()
+    };
+    let remote_store_config = ObjectStoreConfig {
+        object_store: Some(ObjectStoreType::File),
+        directory: Some(temp_dir.join("remote_dir")),
+        ..Default::default()
+    };
+    let archive_writer = ArchiveWriter::new(
+        local_store_config,
+        remote_store_config.clone(),
+        FileCompression::Zstd,
+        StorageFormat::Blob,
+        Duration::from_secs(10),
+        20,
+        &Registry::default(),
+    )
+    .await?;
+    let test_store = SharedInMemoryStore::default();
+    test_store.inner_mut().insert_genesis_state(
+        ordered_checkpoints.first().cloned().unwrap(),
+        empty_contents(),
+        committee.committee().to_owned(),
+    );
+    let last_archived_checkpoint = 49;
+    for checkpoint in &ordered_checkpoints[0..=last_archived_checkpoint as usize] {
+        test_store.inner_mut().insert_checkpoint(checkpoint);
+    }
+    let kill = archive_writer.start(test_store).await?;
+    let archive_reader_config = ArchiveReaderConfig {
+        remote_store_config,
+        download_concurrency: NonZeroUsize::new(1).unwrap(),
+        use_for_pruning_watermark: false,
+    };
+    let archive_readers =
+        ArchiveReaderBalancer::new(vec![archive_reader_config], &Registry::default())?;
+    let archive_reader = archive_readers.pick_one_random(0..u64::MAX).await.unwrap();
+    let archive_sync_lag_threshold = 20;
+    loop {
+        archive_reader.sync_manifest_once().await?;
+        if let Ok(latest_available_checkpoint_in_archive) =
+            archive_reader.latest_available_checkpoint().await
+        {
+            if latest_available_checkpoint_in_archive >= archive_sync_lag_threshold {
+                break;
+            }
+        }
+        tokio::time::sleep(Duration::from_secs(1)).await;
+    }
+
+    // Build and connect two nodes where Node 1 is far behind Node 2 and has
+    // access to the archive store
+    let (builder, server) = Builder::new()
+        .config(StateSyncConfig {
+            archive_sync_lag_threshold: Some(archive_sync_lag_threshold),
+            ..Default::default()
+        })
+        .store(SharedInMemoryStore::default())
+        .archive_readers(archive_readers)
+        .build();
+    let network_1 = build_network(|router| router.add_rpc_service(server));
+    let (event_loop_1, handle_1) = builder.build(network_1.clone());
+    let (builder, server) = Builder::new().store(SharedInMemoryStore::default()).build();
+    let network_2 = build_network(|router| router.add_rpc_service(server));
+    let (event_loop_2, _handle_2) = builder.build(network_2.clone());
+    network_1.connect(network_2.local_addr()).await.unwrap();
+
+    // Init the root committee in both nodes
+    event_loop_1.store.inner_mut().insert_genesis_state(
+        ordered_checkpoints.first().cloned().unwrap(),
+        empty_contents(),
+        committee.committee().to_owned(),
+    );
+    event_loop_2.store.inner_mut().insert_genesis_state(
+        ordered_checkpoints.first().cloned().unwrap(),
+        empty_contents(),
+        committee.committee().to_owned(),
+    );
+
+    // Node 2 has all the data
+    {
+        let mut store = event_loop_2.store.inner_mut();
+        for checkpoint in ordered_checkpoints.clone() {
+            store.insert_checkpoint(&checkpoint);
+            store.insert_checkpoint_contents(&checkpoint, empty_contents());
+            store.update_highest_synced_checkpoint(&checkpoint);
+        }
+    }
+
+    // Node 1 will know that Node 2 has all the data
+    event_loop_1.peer_heights.write().unwrap().peers.insert(
+        network_2.peer_id(),
+        PeerStateSyncInfo {
+            genesis_checkpoint_digest: *ordered_checkpoints[0].digest(),
+            on_same_chain_as_us: true,
+            height: *ordered_checkpoints.last().unwrap().sequence_number(),
+            lowest: 0,
+        },
+    );
+
+    let store_1 = event_loop_1.store.clone();
+    let mut sync_mode = event_loop_1.sync_mode_sender.subscribe();
+
+    tokio::spawn(event_loop_1.start());
+    tokio::spawn(event_loop_2.start());
+
+    // Node 1 switches to syncing from the archive first
+    timeout(
+        Duration::from_secs(60),
+        sync_mode.wait_for(|mode| *mode == SyncMode::Archive),
+    )
+    .await??;
+
+    // Node 1 switches back to syncing from peers once it synced all checkpoints
+    // in the archive, without having synced the newer ones from Node 2
+    timeout(
+        Duration::from_secs(120),
+        sync_mode.wait_for(|mode| *mode == SyncMode::Peers),
+    )
+    .await??;
+    assert!(store_1.get_highest_synced_checkpoint()?.sequence_number <= last_archived_checkpoint);
+
+    // The remaining checkpoint contents are synced from Node 2
+    let total_time = Instant::now();
+    loop {
+        if store_1.get_highest_synced_checkpoint()?.sequence_number
+            == ordered_checkpoints.last().unwrap().sequence_number
+        {
+            break;
+        }
+        if total_time.elapsed() > Duration::from_secs(120) {
+            return Err(anyhow!("Test timed out"));
+        }
+        tokio::time::sleep(Duration::from_secs(1)).await;
+    }
+    assert_eq!(handle_1.sync_mode(), SyncMode::Peers);
+    assert!(
+        handle_1.peer_scores()[&network_2.peer_id()]
+            .content_latency
+            .is_some()
+    );
+    kill.send(())?;
+    Ok(())
+}
@@ -14,6 +14,7 @@ use axum::{
     Router,
 };
 use humantime::parse_duration;
+use iota_config::transaction_deny_config::DenyListEntry;
 use iota_types::error::IotaError;
 use serde::Deserialize;
 use telemetry_subscribers::TracingHandle;
@@ -53,6 +54,24 @@ use crate::IotaNode;
 // Reset tracing to the TRACE_FILTER env var.
 //
 //   $ curl -X POST 'http://127.0.0.1:1337/reset-tracing'
+//
+// View the objects, packages, addresses and Move types currently denied in
+// transactions:
+//
+//   $ curl 'http://127.0.0.1:1337/deny-list'
+//
+// Deny a package, or allow it again. The kind is one of `object`, `package`,
+// `address` or `type`. Changes apply immediately and are written to the
+// `deny-list-path` of the transaction deny config, if set:
+//
+//   $ curl -X POST 'http://127.0.0.1:1337/deny-list/add?kind=package&value=0x42'
+//   $ curl -X POST 'http://127.0.0.1:1337/deny-list/remove?kind=package&value=0x42'
+//
+// Deny all instantiations of a Move type as type argument:
+//
+//   $ curl -X POST -G 'http://127.0.0.1:1337/deny-list/add' \
+//       --data-urlencode 'kind=type' \
+//       --data-urlencode 'value=0x42::token::Token'
 
 const LOGGING_ROUTE: &str = "/logging";
 const TRACING_ROUTE: &str = "/enable-tracing";
@@ -62,6 +81,9 @@ const CLEAR_BUFFER_STAKE_ROUTE: &str = "/clear-override-buffer-stake";
 const FORCE_CLOSE_EPOCH: &str = "/force-close-epoch";
 const CAPABILITIES: &str = "/capabilities";
 const NODE_CONFIG: &str = "/node-config";
+const DENY_LIST: &str = "/deny-list";
+const DENY_LIST_ADD: &str = "/deny-list/add";
+const DENY_LIST_REMOVE: &str = "/deny-list/remove";
 
 struct AppState {
     node: Arc<IotaNode>,
@@ -80,6 +102,7 @@ pub async fn run_admin_server(node: Arc<IotaNode>, port: u16, tracing_handle: Tr
         .route(LOGGING_ROUTE, get(get_filter))
         .route(CAPABILITIES, get(capabilities))
         .route(NODE_CONFIG, get(node_config))
+        .route(DENY_LIST, get(deny_list))
         .route(LOGGING_ROUTE, post(set_filter))
         .route(
             SET_BUFFER_STAKE_ROUTE,
@@ -92,6 +115,8 @@ pub async fn run_admin_server(node: Arc<IotaNode>, port: u16, tracing_handle: Tr
         .route(FORCE_CLOSE_EPOCH, post(force_close_epoch))
         .route(TRACING_ROUTE, post(enable_tracing))
         .route(TRACING_RESET_ROUTE, post(reset_tracing))
+        .route(DENY_LIST_ADD, post(add_to_deny_list))
+        .route(DENY_LIST_REMOVE, post(remove_from_deny_list))
         .with_state(Arc::new(app_state));
 
     let socket_address = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), port);
@@ -294,3 +319,64 @@ async fn force_close_epoch(
         Err(err) => (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()),
     }
 }
+
+async fn deny_list(State(state): State<Arc<AppState>>) -> (StatusCode, String) {
+    let mut output = String::new();
+    for entry in state.node.transaction_deny_lists().entries() {
+        output.push_str(&format!("{}\n", entry));
+    }
+
+    (StatusCode::OK, output)
+}
+
+#[derive(Deserialize)]
+struct DenyListUpdate {
+    kind: String,
+    value: String,
+}
+
+async fn add_to_deny_list(
+    State(state): State<Arc<AppState>>,
+    update: Query<DenyListUpdate>,
+) -> (StatusCode, String) {
+    let Query(DenyListUpdate { kind, value }) = update;
+    let entry = match DenyListEntry::parse(&kind, &value) {
+        Ok(entry) => entry,
+        Err(err) => return (StatusCode::BAD_REQUEST, err.to_string()),
+    };
+
+    match state
+        .node
+        .update_transaction_deny_lists(|lists| lists.add(entry.clone()))
+    {
+        Ok(true) => {
+            info!(%entry, "Added to transaction deny list");
+            (StatusCode::OK, format!("{} denied\n", entry))
+        }
+        Ok(false) => (StatusCode::OK, format!("{} is already denied\n", entry)),
+        Err(err) => (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()),
+    }
+}
+
+async fn remove_from_deny_list(
+    State(state): State<Arc<AppState>>,
+    update: Query<DenyListUpdate>,
+) -> (StatusCode, String) {
+    let Query(DenyListUpdate { kind, value }) = update;
+    let entry = match DenyListEntry::parse(&kind, &value) {
+        Ok(entry) => entry,
+        Err(err) => return (StatusCode::BAD_REQUEST, err.to_string()),
+    };
+
+    match state
+        .node
+        .update_transaction_deny_lists(|lists| lists.remove(&entry))
+    {
+        Ok(true) => {
+            info!(%entry, "Removed from transaction deny list");
+            (StatusCode::OK, format!("{} allowed\n", entry))
+        }
+        Ok(false) => (StatusCode::OK, format!("{} is not denied\n", entry)),
+        Err(err) => (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()),
+    }
+}
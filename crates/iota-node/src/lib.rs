@@ -29,6 +29,7 @@ use iota_config::{
     node::{ConsensusProtocol, DBCheckpointConfig, RunWithRange},
     node_config_metrics::NodeConfigMetrics,
     object_storage_config::{ObjectStoreConfig, ObjectStoreType},
+    transaction_deny_config::DenyLists,
     ConsensusConfig, NodeConfig,
 };
 use iota_core::{
@@ -211,6 +212,7 @@ pub struct IotaNode {
     transaction_orchestrator: Option<Arc<TransactionOrchestrator<NetworkAuthorityClient>>>,
     registry_service: RegistryService,
     metrics: Arc<IotaNodeMetrics>,
+    node_config_metrics: Arc<NodeConfigMetrics>,
 
     _discovery: discovery::Handle,
     state_sync_handle: state_sync::Handle,
@@ -401,7 +403,8 @@ impl IotaNode {
         custom_rpc_runtime: Option<Handle>,
         software_version: &'static str,
     ) -> Result<Arc<IotaNode>> {
-        NodeConfigMetrics::new(&registry_service.default_registry()).record_metrics(&config);
+        let node_config_metrics = NodeConfigMetrics::new(&registry_service.default_registry());
+        node_config_metrics.record_metrics(&config);
         let mut config = config.clone();
         if config.supported_protocol_versions.is_none() {
             info!(
@@ -588,7 +591,7 @@ impl IotaNode {
             state_snapshot_handle.is_some(),
         )?;
 
-        let mut pruning_config = config.authority_store_pruning_config;
+        let mut pruning_config = config.authority_store_pruning_config.clone();
         if !epoch_store
             .protocol_config()
             .simplified_unwrap_then_delete()
@@ -612,7 +615,10 @@ impl IotaNode {
             genesis.objects(),
             &db_checkpoint_config,
             config.expensive_safety_check_config.clone(),
-            config.transaction_deny_config.clone(),
+            config
+                .transaction_deny_config
+                .clone()
+                .with_persisted_deny_lists()?,
             config.certificate_deny_config.clone(),
             config.indirect_objects_threshold,
             config.state_debug_dump_config.clone(),
@@ -620,6 +626,8 @@ impl IotaNode {
             archive_readers,
         )
         .await;
+        // The persisted deny lists may differ from the ones of the config.
+        node_config_metrics.record_transaction_deny_config(&state.transaction_deny_config());
         // ensure genesis txn was executed
         if epoch_store.epoch() == 0 {
             let txn = &genesis.transaction();
@@ -742,6 +750,7 @@ impl IotaNode {
             transaction_orchestrator,
             registry_service,
             metrics: iota_node_metrics,
+            node_config_metrics,
 
             _discovery: discovery_handle,
             state_sync_handle,
@@ -818,6 +827,20 @@ impl IotaNode {
             .set_override_protocol_upgrade_buffer_stake(epoch, buffer_stake_bps)
     }
 
+    pub fn transaction_deny_lists(&self) -> DenyLists {
+        self.state.transaction_deny_config().deny_lists()
+    }
+
+    pub fn update_transaction_deny_lists<R>(
+        &self,
+        update: impl FnOnce(&mut DenyLists) -> R,
+    ) -> Result<R> {
+        let result = self.state.update_transaction_deny_lists(update)?;
+        self.node_config_metrics
+            .record_transaction_deny_config(&self.state.transaction_deny_config());
+        Ok(result)
+    }
+
     // Testing-only API to start epoch close process.
     // For production code, please use the non-testing version.
     pub async fn close_epoch_for_testing(&self) -> IotaResult {
@@ -919,7 +942,7 @@ impl IotaNode {
                         .prune_and_compact_before_upload
                         .unwrap_or(true),
                     config.indirect_objects_threshold,
-                    config.authority_store_pruning_config,
+                    config.authority_store_pruning_config.clone(),
                     prometheus_registry,
                     state_snapshot_enabled,
                 )?;
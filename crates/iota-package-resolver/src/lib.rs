@@ -23,15 +23,17 @@ use move_binary_format::{
     access::ModuleAccess,
     errors::Location,
     file_format::{
-        AbilitySet, FunctionDefinitionIndex, Signature as MoveSignature, SignatureIndex,
-        SignatureToken, StructDefinitionIndex, StructFieldInformation, StructHandleIndex,
-        StructTypeParameter, TableIndex, Visibility,
+        AbilitySet, EnumDefinitionIndex, FieldDefinition, FunctionDefinitionIndex,
+        Signature as MoveSignature, SignatureIndex, SignatureToken, StructDefinitionIndex,
+        StructFieldInformation, StructHandleIndex, StructTypeParameter, TableIndex, Visibility,
     },
     CompiledModule,
 };
 use move_core_types::{
     account_address::AccountAddress,
-    annotated_value::{MoveFieldLayout, MoveStructLayout, MoveTypeLayout},
+    annotated_value::{
+        MoveEnumLayout, MoveFieldLayout, MoveStructLayout, MoveTypeLayout, MoveVariantLayout,
+    },
     language_storage::{StructTag, TypeTag},
 };
 
@@ -109,6 +111,10 @@ pub struct Module {
     /// definition in the bytecode, to speed up definition lookups.
     struct_index: BTreeMap<String, (AccountAddress, StructDefinitionIndex)>,
 
+    /// Index mapping enum names to their defining ID, and the index for their
+    /// definition in the bytecode, to speed up definition lookups.
+    enum_index: BTreeMap<String, (AccountAddress, EnumDefinitionIndex)>,
+
     /// Index mapping function names to the index for their definition in the
     /// bytecode, to speed up definition lookups.
     function_index: BTreeMap<String, FunctionDefinitionIndex>,
@@ -132,6 +138,49 @@ pub struct StructDef {
     pub fields: Vec<(String, OpenSignatureBody)>,
 }
 
+/// Deserialized representation of an enum definition.
+#[derive(Debug)]
+pub struct EnumDef {
+    /// The storage ID of the package that first introduced this type.
+    pub defining_id: AccountAddress,
+
+    /// This type's abilities.
+    pub abilities: AbilitySet,
+
+    /// Ability constraints and phantom status for type parameters
+    pub type_params: Vec<StructTypeParameter>,
+
+    /// The enum's variants, in tag order.
+    pub variants: Vec<VariantDef>,
+}
+
+/// Deserialized representation of an enum variant.
+#[derive(Debug)]
+pub struct VariantDef {
+    /// The variant's name.
+    pub name: String,
+
+    /// Serialized representation of the variant's fields (names and
+    /// deserialized signatures), with the same conventions as
+    /// `StructDef::fields`.
+    pub signatures: Vec<(String, OpenSignatureBody)>,
+}
+
+/// The definition of a struct or an enum, as needed for type resolution.
+#[derive(Debug)]
+struct DataDef {
+    defining_id: AccountAddress,
+    abilities: AbilitySet,
+    type_params: Vec<StructTypeParameter>,
+    data: MoveData,
+}
+
+#[derive(Debug)]
+enum MoveData {
+    Struct(Vec<(String, OpenSignatureBody)>),
+    Enum(Vec<VariantDef>),
+}
+
 /// Deserialized representation of a function definition
 #[derive(Debug)]
 pub struct FunctionDef {
@@ -206,9 +255,9 @@ pub enum OpenSignatureBody {
 /// Information necessary to convert a type tag into a type layout.
 #[derive(Debug, Default)]
 struct ResolutionContext<'l> {
-    /// Definitions (field information) for structs referred to by types added
-    /// to this context.
-    structs: BTreeMap<DatatypeKey, StructDef>,
+    /// Definitions (field information) for structs and enums referred to by
+    /// types added to this context.
+    datatypes: BTreeMap<DatatypeKey, DataDef>,
     /// Limits configuration from the calling resolver.
     limits: Option<&'l Limits>,
 }
@@ -586,17 +635,21 @@ impl Package {
         &self.modules
     }
 
-    fn struct_def(&self, module_name: &str, struct_name: &str) -> Result<StructDef> {
+    fn data_def(&self, module_name: &str, datatype_name: &str) -> Result<DataDef> {
         let module = self.module(module_name)?;
-        let Some(struct_def) = module.struct_def(struct_name)? else {
+        if let Some(struct_def) = module.struct_def(datatype_name)? {
+            return Ok(struct_def.into());
+        }
+
+        let Some(enum_def) = module.enum_def(datatype_name)? else {
             return Err(Error::StructNotFound(
                 self.storage_id,
                 module_name.to_string(),
-                struct_name.to_string(),
+                datatype_name.to_string(),
             ));
         };
 
-        Ok(struct_def)
+        Ok(enum_def.into())
     }
 
     /// Translate the `runtime_id` of a package to a specific storage ID using
@@ -638,6 +691,19 @@ impl Module {
             struct_index.insert(struct_, (defining_id, index));
         }
 
+        let mut enum_index = BTreeMap::new();
+        for (index, def) in bytecode.enum_defs.iter().enumerate() {
+            let eh = bytecode.struct_handle_at(def.enum_handle);
+            let enum_ = bytecode.identifier_at(eh.name).to_string();
+            let index = EnumDefinitionIndex::new(index as TableIndex);
+
+            let Some(defining_id) = origins.remove(&enum_) else {
+                return Err(enum_);
+            };
+
+            enum_index.insert(enum_, (defining_id, index));
+        }
+
         let mut function_index = BTreeMap::new();
         for (index, def) in bytecode.function_defs.iter().enumerate() {
             let fh = bytecode.function_handle_at(def.function);
@@ -650,6 +716,7 @@ impl Module {
         Ok(Module {
             bytecode,
             struct_index,
+            enum_index,
             function_index,
         })
     }
@@ -697,15 +764,7 @@ impl Module {
 
         let fields = match &struct_def.field_information {
             StructFieldInformation::Native => vec![],
-            StructFieldInformation::Declared(fields) => fields
-                .iter()
-                .map(|f| {
-                    Ok((
-                        self.bytecode.identifier_at(f.name).to_string(),
-                        OpenSignatureBody::read(&f.signature.0, &self.bytecode)?,
-                    ))
-                })
-                .collect::<Result<_>>()?,
+            StructFieldInformation::Declared(fields) => self.read_fields(fields)?,
         };
 
         Ok(Some(StructDef {
@@ -716,6 +775,70 @@ impl Module {
         }))
     }
 
+    /// Iterate over the enums with names strictly after `after` (or from the
+    /// beginning), and strictly before `before` (or to the end).
+    pub fn enums(
+        &self,
+        after: Option<&str>,
+        before: Option<&str>,
+    ) -> impl DoubleEndedIterator<Item = &str> + Clone {
+        use std::ops::Bound as B;
+        self.enum_index
+            .range::<str, _>((
+                after.map_or(B::Unbounded, B::Excluded),
+                before.map_or(B::Unbounded, B::Excluded),
+            ))
+            .map(|(name, _)| name.as_str())
+    }
+
+    /// Get the enum definition corresponding to the enum with name `name` in
+    /// this module. Returns `Ok(None)` if the enum cannot be found in this
+    /// module, `Err(...)` if there was an error deserializing it, and
+    /// `Ok(Some(def))` on success.
+    pub fn enum_def(&self, name: &str) -> Result<Option<EnumDef>> {
+        let Some(&(defining_id, index)) = self.enum_index.get(name) else {
+            return Ok(None);
+        };
+
+        let enum_def = self.bytecode.enum_def_at(index);
+        let enum_handle = self.bytecode.struct_handle_at(enum_def.enum_handle);
+        let abilities = enum_handle.abilities;
+        let type_params = enum_handle.type_parameters.clone();
+
+        let variants = enum_def
+            .variants
+            .iter()
+            .map(|variant| {
+                Ok(VariantDef {
+                    name: self
+                        .bytecode
+                        .identifier_at(variant.variant_name)
+                        .to_string(),
+                    signatures: self.read_fields(&variant.fields)?,
+                })
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(Some(EnumDef {
+            defining_id,
+            abilities,
+            type_params,
+            variants,
+        }))
+    }
+
+    fn read_fields(&self, fields: &[FieldDefinition]) -> Result<Vec<(String, OpenSignatureBody)>> {
+        fields
+            .iter()
+            .map(|f| {
+                Ok((
+                    self.bytecode.identifier_at(f.name).to_string(),
+                    OpenSignatureBody::read(&f.signature.0, &self.bytecode)?,
+                ))
+            })
+            .collect()
+    }
+
     /// Iterate over the functions with names strictly after `after` (or from
     /// the beginning), and strictly before `before` (or to the end).
     pub fn functions(
@@ -887,7 +1010,7 @@ impl DatatypeKey {
 impl<'l> ResolutionContext<'l> {
     fn new(limits: Option<&'l Limits>) -> Self {
         ResolutionContext {
-            structs: BTreeMap::new(),
+            datatypes: BTreeMap::new(),
             limits,
         }
     }
@@ -952,12 +1075,12 @@ impl<'l> ResolutionContext<'l> {
                     let context = store.fetch(s.address).await?;
                     let def = context
                         .clone()
-                        .struct_def(s.module.as_str(), s.name.as_str())?;
+                        .data_def(s.module.as_str(), s.name.as_str())?;
 
                     // Normalize `address` (the ID of a package that contains the definition of this
                     // struct) to be a runtime ID, because that's what the resolution context uses
                     // for keys.  Take care to do this before generating the key that is used to
-                    // query and/or write into `self.structs.
+                    // query and/or write into `self.datatypes.
                     s.address = context.runtime_id;
                     let key = DatatypeRef::from(s.as_ref()).as_key();
 
@@ -979,12 +1102,12 @@ impl<'l> ResolutionContext<'l> {
                         }
                     }
 
-                    if self.structs.contains_key(&key) {
+                    if self.datatypes.contains_key(&key) {
                         continue;
                     }
 
                     if visit_fields {
-                        for (_, sig) in &def.fields {
+                        for sig in def.field_signatures() {
                             self.add_signature(sig.clone(), store, &context, visit_fields)
                                 .await?;
                         }
@@ -992,10 +1115,10 @@ impl<'l> ResolutionContext<'l> {
 
                     check_max_limit!(
                         TooManyTypeNodes, self.limits;
-                        max_type_nodes > self.structs.len()
+                        max_type_nodes > self.datatypes.len()
                     );
 
-                    self.structs.insert(key, def);
+                    self.datatypes.insert(key, def);
                 }
             }
         }
@@ -1038,24 +1161,24 @@ impl<'l> ResolutionContext<'l> {
                     );
 
                     let params_count = params.len();
-                    let struct_count = self.structs.len();
+                    let datatype_count = self.datatypes.len();
                     frontier.extend(params.into_iter());
 
-                    let def = match self.structs.entry(key.clone()) {
+                    let def = match self.datatypes.entry(key.clone()) {
                         Entry::Occupied(e) => e.into_mut(),
 
                         Entry::Vacant(e) => {
                             let storage_id = context.relocate(key.package)?;
                             let package = store.fetch(storage_id).await?;
-                            let def = package.struct_def(&key.module, &key.name)?;
+                            let def = package.data_def(&key.module, &key.name)?;
 
                             if visit_fields {
-                                frontier.extend(def.fields.iter().map(|f| &f.1).cloned());
+                                frontier.extend(def.field_signatures().cloned());
                             }
 
                             check_max_limit!(
                                 TooManyTypeNodes, self.limits;
-                                max_type_nodes > struct_count
+                                max_type_nodes > datatype_count
                             );
 
                             e.insert(def)
@@ -1122,9 +1245,9 @@ impl<'l> ResolutionContext<'l> {
                 // these layouts are naturally keyed based on defining ID, but during
                 // resolution, they are keyed by runtime IDs.
 
-                // SAFETY: `add_type_tag` ensures `structs` has an element with this key.
+                // SAFETY: `add_type_tag` ensures `datatypes` has an element with this key.
                 let key = DatatypeRef::from(s.as_ref());
-                let def = &self.structs[&key];
+                let def = &self.datatypes[&key];
 
                 let StructTag {
                     module,
@@ -1160,26 +1283,57 @@ impl<'l> ResolutionContext<'l> {
                     type_params,
                 };
 
-                let mut fields = Vec::with_capacity(def.fields.len());
-                let mut field_depth = 0;
+                self.resolve_datatype_layout(def, type_, &param_layouts, max_depth)?
+            }
+        })
+    }
 
-                for (name, sig) in &def.fields {
-                    let (layout, depth) =
-                        self.resolve_signature_layout(sig, &param_layouts, max_depth - 1)?;
+    /// Layout of the struct or enum `def`, instantiated as `type_`, given the
+    /// layouts of its type parameters. Shared between `resolve_type_layout`
+    /// and `resolve_signature_layout`.
+    fn resolve_datatype_layout(
+        &self,
+        def: &DataDef,
+        type_: StructTag,
+        param_layouts: &[(MoveTypeLayout, usize)],
+        max_depth: usize,
+    ) -> Result<(MoveTypeLayout, usize)> {
+        let mut field_depth = 0;
+        let mut resolve_fields = |signatures: &[(String, OpenSignatureBody)]| {
+            let mut fields = Vec::with_capacity(signatures.len());
+            for (name, sig) in signatures {
+                let (layout, depth) =
+                    self.resolve_signature_layout(sig, param_layouts, max_depth - 1)?;
 
-                    field_depth = field_depth.max(depth);
-                    fields.push(MoveFieldLayout {
-                        name: ident(name.as_str())?,
-                        layout,
-                    })
-                }
+                field_depth = field_depth.max(depth);
+                fields.push(MoveFieldLayout {
+                    name: ident(name.as_str())?,
+                    layout,
+                })
+            }
+            Ok::<_, Error>(fields)
+        };
 
-                (
-                    L::Struct(MoveStructLayout { type_, fields }),
-                    field_depth + 1,
-                )
+        let layout = match &def.data {
+            MoveData::Struct(signatures) => {
+                let fields = resolve_fields(signatures)?;
+                MoveTypeLayout::Struct(MoveStructLayout { type_, fields })
             }
-        })
+            MoveData::Enum(variants) => {
+                let variants = variants
+                    .iter()
+                    .map(|variant| {
+                        Ok(MoveVariantLayout {
+                            name: ident(variant.name.as_str())?,
+                            fields: resolve_fields(&variant.signatures)?,
+                        })
+                    })
+                    .collect::<Result<_>>()?;
+                MoveTypeLayout::Enum(MoveEnumLayout { type_, variants })
+            }
+        };
+
+        Ok((layout, field_depth + 1))
     }
 
     /// Like `resolve_type_tag` but for signatures.  Needs to be provided the
@@ -1240,8 +1394,8 @@ impl<'l> ResolutionContext<'l> {
             }
 
             O::Datatype(key, params) => {
-                // SAFETY: `add_signature` ensures `structs` has an element with this key.
-                let def = &self.structs[key];
+                // SAFETY: `add_signature` ensures `datatypes` has an element with this key.
+                let def = &self.datatypes[key];
 
                 let param_layouts = params
                     .iter()
@@ -1261,23 +1415,7 @@ impl<'l> ResolutionContext<'l> {
                     type_params,
                 };
 
-                let mut fields = Vec::with_capacity(def.fields.len());
-                let mut field_depth = 0;
-                for (name, sig) in &def.fields {
-                    let (layout, depth) =
-                        self.resolve_signature_layout(sig, &param_layouts, max_depth - 1)?;
-
-                    field_depth = field_depth.max(depth);
-                    fields.push(MoveFieldLayout {
-                        name: ident(name.as_str())?,
-                        layout,
-                    });
-                }
-
-                (
-                    L::Struct(MoveStructLayout { type_, fields }),
-                    field_depth + 1,
-                )
+                self.resolve_datatype_layout(def, type_, &param_layouts, max_depth)?
             }
         })
     }
@@ -1297,9 +1435,9 @@ impl<'l> ResolutionContext<'l> {
             T::Vector(tag) => self.resolve_abilities(tag)?.intersect(AbilitySet::VECTOR),
 
             T::Struct(s) => {
-                // SAFETY: `add_type_tag` ensures `structs` has an element with this key.
+                // SAFETY: `add_type_tag` ensures `datatypes` has an element with this key.
                 let key = DatatypeRef::from(s.as_ref());
-                let def = &self.structs[&key];
+                let def = &self.datatypes[&key];
 
                 if def.type_params.len() != s.type_params.len() {
                     return Err(Error::TypeArityMismatch(
@@ -1350,8 +1488,8 @@ impl<'l> ResolutionContext<'l> {
             O::Vector(sig) => self.relocate_signature(sig.as_mut())?,
 
             O::Datatype(key, params) => {
-                // SAFETY: `add_signature` ensures `structs` has an element with this key.
-                let def = &self.structs[key];
+                // SAFETY: `add_signature` ensures `datatypes` has an element with this key.
+                let def = &self.datatypes[key];
                 for param in params {
                     self.relocate_signature(param)?;
                 }
@@ -1364,6 +1502,43 @@ impl<'l> ResolutionContext<'l> {
     }
 }
 
+impl DataDef {
+    /// The signatures of all fields of this type, including the fields of every
+    /// variant for enums.
+    fn field_signatures(&self) -> Box<dyn Iterator<Item = &OpenSignatureBody> + '_> {
+        match &self.data {
+            MoveData::Struct(fields) => Box::new(fields.iter().map(|(_, sig)| sig)),
+            MoveData::Enum(variants) => Box::new(
+                variants
+                    .iter()
+                    .flat_map(|v| v.signatures.iter().map(|(_, sig)| sig)),
+            ),
+        }
+    }
+}
+
+impl From<StructDef> for DataDef {
+    fn from(def: StructDef) -> Self {
+        DataDef {
+            defining_id: def.defining_id,
+            abilities: def.abilities,
+            type_params: def.type_params,
+            data: MoveData::Struct(def.fields),
+        }
+    }
+}
+
+impl From<EnumDef> for DataDef {
+    fn from(def: EnumDef) -> Self {
+        DataDef {
+            defining_id: def.defining_id,
+            abilities: def.abilities,
+            type_params: def.type_params,
+            data: MoveData::Enum(def.variants),
+        }
+    }
+}
+
 impl<'s> From<&'s StructTag> for DatatypeRef<'s, 's> {
     fn from(tag: &'s StructTag) -> Self {
         DatatypeRef {
@@ -301,6 +301,23 @@ struct FeatureFlags {
     // Reject functions with mutable Random.
     #[serde(skip_serializing_if = "is_false")]
     reject_mutable_random_on_entry_functions: bool,
+
+    // Enable enum types in Move bytecode.
+    #[serde(skip_serializing_if = "is_false")]
+    enable_move_enums: bool,
+
+    // Enable passkey (WebAuthn) authenticator signatures.
+    #[serde(skip_serializing_if = "is_false")]
+    passkey_auth: bool,
+
+    // Whether passkey signatures are accepted as members of a multisig.
+    #[serde(skip_serializing_if = "is_false")]
+    accept_passkey_in_multisig: bool,
+
+    // Use the reputation scores of the authorities to swap out bad leaders in
+    // the Mysticeti leader schedule.
+    #[serde(skip_serializing_if = "is_false")]
+    mysticeti_leader_scoring_and_schedule: bool,
 }
 
 fn is_false(b: &bool) -> bool {
@@ -436,6 +453,10 @@ pub struct ProtocolConfig {
     binary_field_handles: Option<u16>,
     binary_field_instantiations: Option<u16>,
     binary_friend_decls: Option<u16>,
+    binary_enum_defs: Option<u16>,
+    binary_enum_def_instantiations: Option<u16>,
+    binary_variant_handles: Option<u16>,
+    binary_variant_instantiation_handles: Option<u16>,
 
     /// Maximum size of the `contents` part of an object, in bytes. Enforced by
     /// the Iota adapter when effects are produced.
@@ -1146,6 +1167,22 @@ impl ProtocolConfig {
     pub fn reject_mutable_random_on_entry_functions(&self) -> bool {
         self.feature_flags.reject_mutable_random_on_entry_functions
     }
+
+    pub fn enable_move_enums(&self) -> bool {
+        self.feature_flags.enable_move_enums
+    }
+
+    pub fn passkey_auth(&self) -> bool {
+        self.feature_flags.passkey_auth
+    }
+
+    pub fn accept_passkey_in_multisig(&self) -> bool {
+        self.feature_flags.accept_passkey_in_multisig
+    }
+
+    pub fn mysticeti_leader_scoring_and_schedule(&self) -> bool {
+        self.feature_flags.mysticeti_leader_scoring_and_schedule
+    }
 }
 
 #[cfg(not(msim))]
@@ -1303,6 +1340,10 @@ impl ProtocolConfig {
             binary_field_handles: Some(500),
             binary_field_instantiations: Some(250),
             binary_friend_decls: Some(100),
+            binary_enum_defs: None,
+            binary_enum_def_instantiations: None,
+            binary_variant_handles: None,
+            binary_variant_instantiation_handles: None,
             max_move_object_size: Some(250 * 1024),
             max_move_package_size: Some(100 * 1024),
             max_publish_or_upgrade_per_ptb: Some(5),
@@ -1693,6 +1734,19 @@ impl ProtocolConfig {
             cfg.poseidon_bn254_cost_per_block = Some(10);
 
             cfg.feature_flags.enable_group_ops_native_function_msm = true;
+
+            // Enums require version 7 of the Move binary format.
+            cfg.feature_flags.enable_move_enums = true;
+            cfg.move_binary_format_version = Some(7);
+            cfg.binary_enum_defs = Some(200);
+            cfg.binary_enum_def_instantiations = Some(100);
+            cfg.binary_variant_handles = Some(1024);
+            cfg.binary_variant_instantiation_handles = Some(1024);
+
+            cfg.feature_flags.passkey_auth = true;
+            cfg.feature_flags.accept_passkey_in_multisig = true;
+
+            cfg.feature_flags.mysticeti_leader_scoring_and_schedule = true;
         }
 
         // TODO: remove the never_loop attribute when the version 2 is added.
@@ -1779,6 +1833,18 @@ impl ProtocolConfig {
     pub fn set_consensus_bad_nodes_stake_threshold(&mut self, val: u64) {
         self.consensus_bad_nodes_stake_threshold = Some(val);
     }
+    pub fn set_enable_move_enums_for_testing(&mut self, val: bool) {
+        self.feature_flags.enable_move_enums = val
+    }
+    pub fn set_passkey_auth_for_testing(&mut self, val: bool) {
+        self.feature_flags.passkey_auth = val
+    }
+    pub fn set_accept_passkey_in_multisig_for_testing(&mut self, val: bool) {
+        self.feature_flags.accept_passkey_in_multisig = val
+    }
+    pub fn set_mysticeti_leader_scoring_and_schedule_for_testing(&mut self, val: bool) {
+        self.feature_flags.mysticeti_leader_scoring_and_schedule = val
+    }
     pub fn set_receive_object_for_testing(&mut self, val: bool) {
         self.feature_flags.receive_objects = val
     }
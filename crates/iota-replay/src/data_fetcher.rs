@@ -2,22 +2,46 @@
 // Modifications Copyright (c) 2024 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use std::{collections::BTreeMap, num::NonZeroUsize, str::FromStr};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs,
+    num::NonZeroUsize,
+    path::Path,
+    str::FromStr,
+    sync::{atomic::AtomicU64, Arc},
+};
 
 use async_trait::async_trait;
+use bytes::Bytes;
 use futures::future::join_all;
-use iota_core::authority::NodeStateDump;
+use iota_archival::reader::{ArchiveReader, ArchiveReaderMetrics};
+use iota_config::{
+    node::ArchiveReaderConfig,
+    object_storage_config::{ObjectStoreConfig, ObjectStoreType},
+};
+use iota_core::authority::{authority_store_tables::LiveObject, NodeStateDump};
+use iota_framework::BuiltInFramework;
 use iota_json_rpc_api::QUERY_MAX_RESULT_LIMIT;
 use iota_json_rpc_types::{
     EventFilter, IotaEvent, IotaGetPastObjectRequest, IotaObjectData, IotaObjectDataOptions,
-    IotaObjectResponse, IotaPastObjectResponse, IotaTransactionBlockResponse,
-    IotaTransactionBlockResponseOptions,
+    IotaObjectResponse, IotaPastObjectResponse, IotaTransactionBlockEffects,
+    IotaTransactionBlockResponse, IotaTransactionBlockResponseOptions,
 };
 use iota_sdk::IotaClient;
+use iota_snapshot::{
+    reader::{LiveObjectIter, StateSnapshotReaderV1},
+    FileType as SnapshotFileType,
+};
 use iota_types::{
     base_types::{ObjectID, SequenceNumber, VersionNumber},
-    digests::TransactionDigest,
+    committee::EpochId,
+    digests::{ChainIdentifier, TransactionDigest},
+    effects::{TransactionEffects, TransactionEffectsAPI},
+    inner_temporary_store::WrittenObjects,
+    iota_system_state::{get_iota_system_state, IotaSystemStateTrait},
+    messages_checkpoint::CheckpointSequenceNumber,
     object::Object,
+    storage::{ObjectStore, ReadStore, SharedInMemoryStore},
     transaction::{
         EndOfEpochTransactionKind, SenderSignedData, TransactionDataAPI, TransactionKind,
     },
@@ -26,7 +50,7 @@ use lru::LruCache;
 use move_core_types::parser::parse_struct_tag;
 use parking_lot::RwLock;
 use rand::Rng;
-use tracing::error;
+use tracing::{error, info};
 
 use crate::types::{ReplayEngineError, EPOCH_CHANGE_STRUCT_TAG};
 
@@ -90,29 +114,37 @@ pub(crate) trait DataFetcher {
 pub enum Fetchers {
     Remote(RemoteFetcher),
     NodeStateDump(NodeStateDumpFetcher),
+    Archive(ArchiveFetcher),
 }
 
 impl Fetchers {
     pub fn as_remote(&self) -> &RemoteFetcher {
         match self {
             Fetchers::Remote(q) => q,
-            Fetchers::NodeStateDump(_) => panic!("not a remote fetcher"),
+            Fetchers::NodeStateDump(_) | Fetchers::Archive(_) => panic!("not a remote fetcher"),
         }
     }
 
     pub fn into_remote(self) -> RemoteFetcher {
         match self {
             Fetchers::Remote(q) => q,
-            Fetchers::NodeStateDump(_) => panic!("not a remote fetcher"),
+            Fetchers::NodeStateDump(_) | Fetchers::Archive(_) => panic!("not a remote fetcher"),
         }
     }
 
     pub fn as_node_state_dump(&self) -> &NodeStateDumpFetcher {
         match self {
-            Fetchers::Remote(_) => panic!("not a node state dump fetcher"),
+            Fetchers::Remote(_) | Fetchers::Archive(_) => panic!("not a node state dump fetcher"),
             Fetchers::NodeStateDump(q) => q,
         }
     }
+
+    pub fn as_archive(&self) -> &ArchiveFetcher {
+        match self {
+            Fetchers::Remote(_) | Fetchers::NodeStateDump(_) => panic!("not an archive fetcher"),
+            Fetchers::Archive(q) => q,
+        }
+    }
 }
 
 #[async_trait]
@@ -125,6 +157,7 @@ impl DataFetcher for Fetchers {
         match self {
             Fetchers::Remote(q) => q.multi_get_versioned(objects).await,
             Fetchers::NodeStateDump(q) => q.multi_get_versioned(objects).await,
+            Fetchers::Archive(q) => q.multi_get_versioned(objects).await,
         }
     }
 
@@ -135,6 +168,7 @@ impl DataFetcher for Fetchers {
         match self {
             Fetchers::Remote(q) => q.multi_get_latest(objects).await,
             Fetchers::NodeStateDump(q) => q.multi_get_latest(objects).await,
+            Fetchers::Archive(q) => q.multi_get_latest(objects).await,
         }
     }
 
@@ -145,6 +179,7 @@ impl DataFetcher for Fetchers {
         match self {
             Fetchers::Remote(q) => q.get_checkpoint_txs(id).await,
             Fetchers::NodeStateDump(q) => q.get_checkpoint_txs(id).await,
+            Fetchers::Archive(q) => q.get_checkpoint_txs(id).await,
         }
     }
 
@@ -155,6 +190,7 @@ impl DataFetcher for Fetchers {
         match self {
             Fetchers::Remote(q) => q.get_transaction(tx_digest).await,
             Fetchers::NodeStateDump(q) => q.get_transaction(tx_digest).await,
+            Fetchers::Archive(q) => q.get_transaction(tx_digest).await,
         }
     }
 
@@ -165,6 +201,7 @@ impl DataFetcher for Fetchers {
         match self {
             Fetchers::Remote(q) => q.get_loaded_child_objects(tx_digest).await,
             Fetchers::NodeStateDump(q) => q.get_loaded_child_objects(tx_digest).await,
+            Fetchers::Archive(q) => q.get_loaded_child_objects(tx_digest).await,
         }
     }
 
@@ -172,6 +209,7 @@ impl DataFetcher for Fetchers {
         match self {
             Fetchers::Remote(q) => q.get_latest_checkpoint_sequence_number().await,
             Fetchers::NodeStateDump(q) => q.get_latest_checkpoint_sequence_number().await,
+            Fetchers::Archive(q) => q.get_latest_checkpoint_sequence_number().await,
         }
     }
 
@@ -189,6 +227,10 @@ impl DataFetcher for Fetchers {
                 q.fetch_random_transaction(checkpoint_id_start, checkpoint_id_end)
                     .await
             }
+            Fetchers::Archive(q) => {
+                q.fetch_random_transaction(checkpoint_id_start, checkpoint_id_end)
+                    .await
+            }
         }
     }
 
@@ -199,6 +241,7 @@ impl DataFetcher for Fetchers {
         match self {
             Fetchers::Remote(q) => q.get_epoch_start_timestamp_and_rgp(epoch_id).await,
             Fetchers::NodeStateDump(q) => q.get_epoch_start_timestamp_and_rgp(epoch_id).await,
+            Fetchers::Archive(q) => q.get_epoch_start_timestamp_and_rgp(epoch_id).await,
         }
     }
 
@@ -209,12 +252,14 @@ impl DataFetcher for Fetchers {
         match self {
             Fetchers::Remote(q) => q.get_epoch_change_events(reverse).await,
             Fetchers::NodeStateDump(q) => q.get_epoch_change_events(reverse).await,
+            Fetchers::Archive(q) => q.get_epoch_change_events(reverse).await,
         }
     }
     async fn get_chain_id(&self) -> Result<String, ReplayEngineError> {
         match self {
             Fetchers::Remote(q) => q.get_chain_id().await,
             Fetchers::NodeStateDump(q) => q.get_chain_id().await,
+            Fetchers::Archive(q) => q.get_chain_id().await,
         }
     }
 }
@@ -800,3 +845,596 @@ impl DataFetcher for NodeStateDumpFetcher {
         unimplemented!("get_chain_id for state dump is not implemented")
     }
 }
+
+/// Number of archive files read concurrently from the local archive
+const ARCHIVE_READ_CONCURRENCY: usize = 5;
+
+/// An epoch covered by a local checkpoint archive
+#[derive(Clone, Debug)]
+pub struct ArchivedEpoch {
+    /// The protocol version in effect during the epoch
+    pub protocol_version: u64,
+    /// The first checkpoint of the epoch
+    pub first_checkpoint: CheckpointSequenceNumber,
+    /// The last checkpoint of the epoch, if the archive covers its end
+    pub last_checkpoint: Option<CheckpointSequenceNumber>,
+    /// The transaction which ended the previous epoch
+    pub epoch_change_tx: TransactionDigest,
+}
+
+/// How far to read a local checkpoint archive. Transactions are executed in
+/// order from the snapshot, so checkpoints after the last one replayed are
+/// never needed.
+#[derive(Clone, Copy, Debug, Default)]
+pub enum ArchiveEnd {
+    /// Up to the last archived checkpoint
+    #[default]
+    Latest,
+    /// Up to and including a checkpoint
+    Checkpoint(CheckpointSequenceNumber),
+    /// Up to and including the last checkpoint of an epoch
+    Epoch(EpochId),
+}
+
+/// Fetches data from a local checkpoint archive and a formal snapshot,
+/// without any RPC. Objects are seeded from the live object set of the
+/// snapshot, and their later versions are produced by executing the archived
+/// transactions in order and recording the objects they write with
+/// `record_execution`.
+#[derive(Clone)]
+pub struct ArchiveFetcher {
+    /// Checkpoints, transactions and effects read from the archive
+    pub archive_store: SharedInMemoryStore,
+    /// Objects as of the last executed transaction. Older versions are not
+    /// kept, as transactions are executed in order and only read the latest
+    /// version of each object.
+    pub latest_object_version_pool: Arc<RwLock<BTreeMap<ObjectID, Object>>>,
+    /// Start timestamp and reference gas price of the epochs reached so far
+    pub epoch_info: Arc<RwLock<BTreeMap<EpochId, (u64, u64)>>>,
+    /// Epochs covered by the archive after the snapshot
+    pub epochs: Arc<BTreeMap<EpochId, ArchivedEpoch>>,
+    /// Transactions covered by the archive after the snapshot, in execution
+    /// order
+    pub transactions: Arc<Vec<(CheckpointSequenceNumber, TransactionDigest)>>,
+    /// Position of each transaction in `transactions`
+    pub transaction_positions: Arc<HashMap<TransactionDigest, usize>>,
+    /// Number of transactions in `transactions` whose outputs were recorded
+    pub num_executed: Arc<RwLock<usize>>,
+    pub chain_id: ChainIdentifier,
+}
+
+impl ArchiveFetcher {
+    /// Load the formal snapshot of `snapshot_epoch` from `snapshot_path`, or
+    /// the latest snapshot in it if no epoch is given, and the checkpoints
+    /// archived in `archive_path` after it, up to `end`.
+    pub async fn new(
+        archive_path: &Path,
+        snapshot_path: &Path,
+        snapshot_epoch: Option<EpochId>,
+        end: ArchiveEnd,
+    ) -> Result<Self, ReplayEngineError> {
+        let snapshot_epoch = match snapshot_epoch {
+            Some(epoch) => epoch,
+            None => latest_snapshot_epoch(snapshot_path)?,
+        };
+        let objects = read_snapshot_objects(snapshot_path, snapshot_epoch)?;
+        info!(
+            "Loaded {} objects from the snapshot of epoch {}",
+            objects.len(),
+            snapshot_epoch
+        );
+
+        let config = ArchiveReaderConfig {
+            remote_store_config: ObjectStoreConfig {
+                object_store: Some(ObjectStoreType::File),
+                directory: Some(archive_path.to_path_buf()),
+                ..Default::default()
+            },
+            download_concurrency: NonZeroUsize::new(ARCHIVE_READ_CONCURRENCY)
+                .expect("Concurrency must be non zero"),
+            use_for_pruning_watermark: false,
+        };
+        let metrics = ArchiveReaderMetrics::new(&prometheus::Registry::new());
+        let archive_reader = ArchiveReader::new(config, &metrics).map_err(archive_error)?;
+        archive_reader
+            .sync_manifest_once()
+            .await
+            .map_err(archive_error)?;
+        let manifest = archive_reader.get_manifest().await.map_err(archive_error)?;
+
+        // The snapshot holds the state at the end of its epoch, so execution
+        // resumes from the first checkpoint of the next one
+        let first_checkpoint = manifest.next_checkpoint_after_epoch(snapshot_epoch);
+        let archived_end = manifest.next_checkpoint_seq_num();
+        let end_checkpoint = match end {
+            ArchiveEnd::Latest => archived_end,
+            ArchiveEnd::Checkpoint(checkpoint) => checkpoint.saturating_add(1).min(archived_end),
+            ArchiveEnd::Epoch(epoch) => manifest
+                .next_checkpoint_after_epoch(epoch)
+                .min(archived_end),
+        };
+        if first_checkpoint >= end_checkpoint {
+            return Err(ReplayEngineError::UnableToReadArchive {
+                err: format!("no checkpoints archived after epoch {snapshot_epoch} up to {end:?}"),
+            });
+        }
+
+        let archive_store = SharedInMemoryStore::default();
+        // The genesis checkpoint identifies the chain
+        archive_reader
+            .read_summaries(
+                archive_store.clone(),
+                0..1,
+                Arc::new(AtomicU64::new(0)),
+                false,
+            )
+            .await
+            .map_err(archive_error)?;
+        // The last checkpoint of the snapshot's epoch is only read for the
+        // transaction which started the next epoch
+        archive_reader
+            .read(
+                archive_store.clone(),
+                first_checkpoint - 1..end_checkpoint,
+                Arc::new(AtomicU64::new(0)),
+                Arc::new(AtomicU64::new(0)),
+                false,
+            )
+            .await
+            .map_err(archive_error)?;
+        info!(
+            "Loaded checkpoints {} to {} from the archive",
+            first_checkpoint,
+            end_checkpoint - 1
+        );
+
+        let genesis = archive_store
+            .get_checkpoint_by_sequence_number(0)
+            .map_err(archive_error)?
+            .ok_or_else(|| archive_error("genesis checkpoint is missing"))?;
+
+        let latest_object_version_pool = objects
+            .into_iter()
+            .map(|object| (object.id(), object))
+            .collect();
+
+        let mut fetcher = Self {
+            archive_store,
+            latest_object_version_pool: Arc::new(RwLock::new(latest_object_version_pool)),
+            epoch_info: Arc::new(RwLock::new(BTreeMap::new())),
+            epochs: Arc::new(BTreeMap::new()),
+            transactions: Arc::new(vec![]),
+            transaction_positions: Arc::new(HashMap::new()),
+            num_executed: Arc::new(RwLock::new(0)),
+            chain_id: ChainIdentifier::from(*genesis.digest()),
+        };
+
+        // The snapshot's system state was set up for the epoch after it by the
+        // epoch change transaction
+        let system_state = get_iota_system_state(&fetcher)?;
+        fetcher.epoch_info.write().insert(
+            system_state.epoch(),
+            (
+                system_state.epoch_start_timestamp_ms(),
+                system_state.reference_gas_price(),
+            ),
+        );
+
+        let mut epochs = BTreeMap::new();
+        let mut transactions = vec![];
+        let mut protocol_version = system_state.protocol_version();
+        let mut epoch_change_tx = *fetcher
+            .checkpoint_transactions(first_checkpoint - 1)?
+            .last()
+            .ok_or_else(|| missing_epoch_change_tx(first_checkpoint - 1))?;
+        for sequence_number in first_checkpoint..end_checkpoint {
+            let checkpoint = fetcher
+                .archive_store
+                .get_checkpoint_by_sequence_number(sequence_number)
+                .map_err(archive_error)?
+                .ok_or_else(|| archive_error(format!("checkpoint {sequence_number} is missing")))?;
+            let tx_digests = fetcher.checkpoint_transactions(sequence_number)?;
+            transactions.extend(tx_digests.iter().map(|digest| (sequence_number, *digest)));
+
+            let epoch = epochs
+                .entry(checkpoint.epoch())
+                .or_insert_with(|| ArchivedEpoch {
+                    protocol_version,
+                    first_checkpoint: sequence_number,
+                    last_checkpoint: None,
+                    epoch_change_tx,
+                });
+            if let Some(end_of_epoch_data) = &checkpoint.data().end_of_epoch_data {
+                epoch.last_checkpoint = Some(sequence_number);
+                protocol_version = end_of_epoch_data.next_epoch_protocol_version.as_u64();
+                epoch_change_tx = *tx_digests
+                    .last()
+                    .ok_or_else(|| missing_epoch_change_tx(sequence_number))?;
+            }
+        }
+
+        fetcher.transaction_positions = Arc::new(
+            transactions
+                .iter()
+                .enumerate()
+                .map(|(position, (_, digest))| (*digest, position))
+                .collect(),
+        );
+        fetcher.transactions = Arc::new(transactions);
+        fetcher.epochs = Arc::new(epochs);
+        Ok(fetcher)
+    }
+
+    /// The latest version of an object, as of the last executed transaction
+    pub fn latest_object(&self, object_id: &ObjectID) -> Option<Object> {
+        self.latest_object_version_pool
+            .read()
+            .get(object_id)
+            .cloned()
+    }
+
+    /// An object at `version`, if it is the latest version as of the last
+    /// executed transaction
+    pub fn object_at_version(
+        &self,
+        object_id: &ObjectID,
+        version: SequenceNumber,
+    ) -> Option<Object> {
+        self.latest_object(object_id)
+            .filter(|object| object.version() == version)
+    }
+
+    /// The protocol version in effect at an archived checkpoint
+    pub fn protocol_version_at(&self, checkpoint: CheckpointSequenceNumber) -> Option<u64> {
+        self.epochs
+            .values()
+            .rev()
+            .find(|epoch| epoch.first_checkpoint <= checkpoint)
+            .map(|epoch| epoch.protocol_version)
+    }
+
+    /// The last checkpoint read from the archive
+    pub fn last_checkpoint(&self) -> Result<CheckpointSequenceNumber, ReplayEngineError> {
+        Ok(*self
+            .archive_store
+            .get_highest_synced_checkpoint()
+            .map_err(archive_error)?
+            .sequence_number())
+    }
+
+    /// The versions of the system packages as of the last executed
+    /// transaction
+    pub fn system_package_refs(&self) -> Vec<(ObjectID, SequenceNumber)> {
+        let latest = self.latest_object_version_pool.read();
+        BuiltInFramework::all_package_ids()
+            .into_iter()
+            .filter_map(|id| latest.get(&id).map(|package| (id, package.version())))
+            .collect()
+    }
+
+    /// The first and last archived checkpoints of an epoch
+    pub fn checkpoints_for_epoch(
+        &self,
+        epoch_id: EpochId,
+    ) -> Result<(u64, u64), ReplayEngineError> {
+        let epoch = self
+            .epochs
+            .get(&epoch_id)
+            .ok_or(ReplayEngineError::UnableToDetermineCheckpoint { epoch: epoch_id })?;
+        let last_checkpoint = match epoch.last_checkpoint {
+            Some(checkpoint) => checkpoint,
+            None => self.last_checkpoint()?,
+        };
+        Ok((epoch.first_checkpoint, last_checkpoint))
+    }
+
+    /// The transaction data and on-chain effects of an archived transaction
+    pub fn transaction_and_effects(
+        &self,
+        tx_digest: &TransactionDigest,
+    ) -> Result<(SenderSignedData, TransactionEffects), ReplayEngineError> {
+        let transaction = self
+            .archive_store
+            .get_transaction(tx_digest)
+            .map_err(archive_error)?
+            .ok_or(ReplayEngineError::TransactionNotInArchive { digest: *tx_digest })?;
+        let effects = self
+            .archive_store
+            .get_transaction_effects(tx_digest)
+            .map_err(archive_error)?
+            .ok_or(ReplayEngineError::TransactionNotInArchive { digest: *tx_digest })?;
+        Ok((transaction.data().clone(), effects))
+    }
+
+    /// The transactions before `tx_digest` whose outputs were not recorded
+    /// yet, which must be executed before it. Transactions whose outputs were
+    /// recorded already cannot be executed again, as the objects they load
+    /// dynamically would be read at later versions.
+    pub fn unexecuted_transactions_before(
+        &self,
+        tx_digest: &TransactionDigest,
+    ) -> Result<Vec<TransactionDigest>, ReplayEngineError> {
+        let position = self.transaction_position(tx_digest)?;
+        let num_executed = *self.num_executed.read();
+        if position < num_executed {
+            return Err(ReplayEngineError::TransactionAlreadyReplayed { digest: *tx_digest });
+        }
+        Ok(self.transactions[num_executed..position]
+            .iter()
+            .map(|(_, digest)| *digest)
+            .collect())
+    }
+
+    /// Record the objects written by `tx_digest`, which must be the next
+    /// transaction to execute, so that the transactions after it can read
+    /// them. Transactions which were recorded already are ignored.
+    pub fn record_execution(
+        &self,
+        tx_digest: &TransactionDigest,
+        written: &WrittenObjects,
+    ) -> Result<(), ReplayEngineError> {
+        let mut num_executed = self.num_executed.write();
+        let position = self.transaction_position(tx_digest)?;
+        if position < *num_executed {
+            return Ok(());
+        }
+        if position > *num_executed {
+            return Err(ReplayEngineError::GeneralError {
+                err: format!("Transaction {tx_digest} was executed out of order"),
+            });
+        }
+
+        let (transaction, effects) = self.transaction_and_effects(tx_digest)?;
+        {
+            let mut latest_object_version_pool = self.latest_object_version_pool.write();
+            for object in written.values() {
+                latest_object_version_pool.insert(object.id(), object.clone());
+            }
+            for (id, _, _) in effects
+                .deleted()
+                .into_iter()
+                .chain(effects.unwrapped_then_deleted())
+                .chain(effects.wrapped())
+            {
+                latest_object_version_pool.remove(&id);
+            }
+        }
+        *num_executed += 1;
+
+        // The epoch change transaction sets up the next epoch in the system state
+        if transaction.transaction_data().kind().is_end_of_epoch_tx() {
+            let system_state = get_iota_system_state(self)?;
+            self.epoch_info.write().insert(
+                system_state.epoch(),
+                (
+                    system_state.epoch_start_timestamp_ms(),
+                    system_state.reference_gas_price(),
+                ),
+            );
+        }
+        Ok(())
+    }
+
+    fn transaction_position(
+        &self,
+        tx_digest: &TransactionDigest,
+    ) -> Result<usize, ReplayEngineError> {
+        self.transaction_positions
+            .get(tx_digest)
+            .copied()
+            .ok_or(ReplayEngineError::TransactionNotInArchive { digest: *tx_digest })
+    }
+
+    fn checkpoint_transactions(
+        &self,
+        sequence_number: CheckpointSequenceNumber,
+    ) -> Result<Vec<TransactionDigest>, ReplayEngineError> {
+        Ok(self
+            .archive_store
+            .get_full_checkpoint_contents_by_sequence_number(sequence_number)
+            .map_err(archive_error)?
+            .ok_or_else(|| archive_error(format!("checkpoint {sequence_number} is missing")))?
+            .iter()
+            .map(|data| *data.transaction.digest())
+            .collect())
+    }
+}
+
+#[async_trait]
+impl DataFetcher for ArchiveFetcher {
+    async fn multi_get_versioned(
+        &self,
+        objects: &[(ObjectID, SequenceNumber)],
+    ) -> Result<Vec<Object>, ReplayEngineError> {
+        objects
+            .iter()
+            .map(|(id, version)| {
+                self.object_at_version(id, *version).ok_or(
+                    ReplayEngineError::ObjectVersionNotFound {
+                        id: *id,
+                        version: *version,
+                    },
+                )
+            })
+            .collect()
+    }
+
+    async fn multi_get_latest(
+        &self,
+        objects: &[ObjectID],
+    ) -> Result<Vec<Object>, ReplayEngineError> {
+        objects
+            .iter()
+            .map(|id| {
+                self.latest_object(id)
+                    .ok_or(ReplayEngineError::ObjectNotExist { id: *id })
+            })
+            .collect()
+    }
+
+    async fn get_checkpoint_txs(
+        &self,
+        id: u64,
+    ) -> Result<Vec<TransactionDigest>, ReplayEngineError> {
+        self.checkpoint_transactions(id)
+    }
+
+    async fn get_transaction(
+        &self,
+        tx_digest: &TransactionDigest,
+    ) -> Result<IotaTransactionBlockResponse, ReplayEngineError> {
+        // The transaction itself is only provided in its raw form, as rendering
+        // it requires resolving the packages it calls
+        let (transaction, effects) = self.transaction_and_effects(tx_digest)?;
+        let mut response = IotaTransactionBlockResponse::new(*tx_digest);
+        response.raw_transaction =
+            bcs::to_bytes(&transaction).expect("Transaction serialization should not fail");
+        response.effects = Some(IotaTransactionBlockEffects::try_from(effects)?);
+        response.checkpoint = self
+            .transaction_positions
+            .get(tx_digest)
+            .map(|position| self.transactions[*position].0);
+        Ok(response)
+    }
+
+    async fn get_loaded_child_objects(
+        &self,
+        _tx_digest: &TransactionDigest,
+    ) -> Result<Vec<(ObjectID, SequenceNumber)>, ReplayEngineError> {
+        // The archive does not record them, they are read from the latest objects
+        // instead
+        Ok(vec![])
+    }
+
+    async fn get_latest_checkpoint_sequence_number(&self) -> Result<u64, ReplayEngineError> {
+        self.last_checkpoint()
+    }
+
+    async fn fetch_random_transaction(
+        &self,
+        checkpoint_id_start: Option<u64>,
+        checkpoint_id_end: Option<u64>,
+    ) -> Result<TransactionDigest, ReplayEngineError> {
+        let checkpoints = checkpoint_id_start.unwrap_or(0)..=checkpoint_id_end.unwrap_or(u64::MAX);
+        let txs: Vec<_> = self
+            .transactions
+            .iter()
+            .filter(|(checkpoint, _)| checkpoints.contains(checkpoint))
+            .collect();
+        if txs.is_empty() {
+            return Err(archive_error(format!(
+                "no transactions archived in checkpoints {checkpoints:?}"
+            )));
+        }
+        Ok(txs[rand::thread_rng().gen_range(0..txs.len())].1)
+    }
+
+    async fn get_epoch_start_timestamp_and_rgp(
+        &self,
+        epoch_id: u64,
+    ) -> Result<(u64, u64), ReplayEngineError> {
+        self.epoch_info
+            .read()
+            .get(&epoch_id)
+            .copied()
+            .ok_or(ReplayEngineError::EpochNotSupported { epoch: epoch_id })
+    }
+
+    async fn get_epoch_change_events(
+        &self,
+        _reverse: bool,
+    ) -> Result<Vec<IotaEvent>, ReplayEngineError> {
+        Err(ReplayEngineError::NotSupportedByArchive {
+            what: "epoch change events".to_string(),
+        })
+    }
+
+    async fn get_chain_id(&self) -> Result<String, ReplayEngineError> {
+        Ok(self.chain_id.to_string())
+    }
+}
+
+impl ObjectStore for ArchiveFetcher {
+    fn get_object(
+        &self,
+        object_id: &ObjectID,
+    ) -> iota_types::storage::error::Result<Option<Object>> {
+        Ok(self.latest_object(object_id))
+    }
+
+    fn get_object_by_key(
+        &self,
+        object_id: &ObjectID,
+        version: VersionNumber,
+    ) -> iota_types::storage::error::Result<Option<Object>> {
+        Ok(self.object_at_version(object_id, version))
+    }
+}
+
+/// The epoch of the latest formal snapshot in `snapshot_path`, which holds a
+/// directory `epoch_<N>` per snapshot
+fn latest_snapshot_epoch(snapshot_path: &Path) -> Result<EpochId, ReplayEngineError> {
+    fs::read_dir(snapshot_path)
+        .map_err(archive_error)?
+        .filter_map(|entry| {
+            let entry = entry.ok()?;
+            let epoch = entry
+                .file_name()
+                .to_str()?
+                .strip_prefix("epoch_")?
+                .parse()
+                .ok()?;
+            entry.path().join("MANIFEST").exists().then_some(epoch)
+        })
+        .max()
+        .ok_or_else(|| archive_error(format!("no snapshot found in {}", snapshot_path.display())))
+}
+
+/// The live objects in the formal snapshot of `epoch`
+fn read_snapshot_objects(
+    snapshot_path: &Path,
+    epoch: EpochId,
+) -> Result<Vec<Object>, ReplayEngineError> {
+    let epoch_dir = format!("epoch_{epoch}");
+    let manifest =
+        StateSnapshotReaderV1::read_manifest(snapshot_path.join(&epoch_dir).join("MANIFEST"))
+            .map_err(archive_error)?;
+    if manifest.epoch() != epoch {
+        return Err(archive_error(format!(
+            "snapshot in {epoch_dir} is for epoch {}",
+            manifest.epoch()
+        )));
+    }
+
+    let epoch_dir = object_store::path::Path::from(epoch_dir);
+    let mut objects = vec![];
+    for file_metadata in manifest
+        .file_metadata()
+        .iter()
+        .filter(|file_metadata| matches!(file_metadata.file_type, SnapshotFileType::Object))
+    {
+        let path = file_metadata
+            .local_file_path(snapshot_path, &epoch_dir)
+            .map_err(archive_error)?;
+        let bytes = fs::read(path).map_err(archive_error)?;
+        let live_objects =
+            LiveObjectIter::new(file_metadata, Bytes::from(bytes)).map_err(archive_error)?;
+        objects.extend(live_objects.filter_map(|object| match object {
+            LiveObject::Normal(object) => Some(object),
+            LiveObject::Wrapped(_) => None,
+        }));
+    }
+    Ok(objects)
+}
+
+fn archive_error(err: impl std::fmt::Display) -> ReplayEngineError {
+    ReplayEngineError::UnableToReadArchive {
+        err: err.to_string(),
+    }
+}
+
+fn missing_epoch_change_tx(checkpoint: CheckpointSequenceNumber) -> ReplayEngineError {
+    archive_error(format!(
+        "checkpoint {checkpoint} does not contain the epoch change transaction"
+    ))
+}
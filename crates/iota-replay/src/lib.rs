@@ -2,10 +2,15 @@
 // Modifications Copyright (c) 2024 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use std::{env, io::BufRead, path::PathBuf, str::FromStr};
+use std::{
+    env,
+    io::BufRead,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
 
 use async_recursion::async_recursion;
-use clap::Parser;
+use clap::{Args, Parser};
 use config::ReplayableNetworkConfigSet;
 use fuzz::{ReplayFuzzer, ReplayFuzzerConfig};
 use fuzz_mutations::base_fuzzers;
@@ -16,15 +21,20 @@ use iota_types::{
     message_envelope::Message,
 };
 use move_vm_config::runtime::get_default_output_filepath;
+use protocol_diff::{DiffReport, DiffTarget};
 use tracing::{error, info, warn};
 use transaction_provider::{FuzzStartPoint, TransactionSource};
 
-use crate::replay::{ExecutionSandboxState, LocalExec, ProtocolVersionSummary};
+use crate::{
+    data_fetcher::ArchiveEnd,
+    replay::{ExecutionSandboxState, LocalExec, ProtocolVersionSummary},
+};
 pub mod config;
 mod data_fetcher;
 mod displays;
 pub mod fuzz;
 pub mod fuzz_mutations;
+pub mod protocol_diff;
 mod replay;
 pub mod transaction_provider;
 pub mod types;
@@ -35,6 +45,33 @@ static DEFAULT_SANDBOX_BASE_PATH: &str =
 #[cfg(test)]
 mod tests;
 
+/// Replays from a local checkpoint archive instead of an RPC node. The objects
+/// are read from a formal snapshot, and brought up to date by executing the
+/// archived checkpoints after it in order.
+#[derive(Args, Clone, Debug, Default)]
+pub struct ArchiveReplayArgs {
+    /// Directory of the local checkpoint archive
+    #[arg(long, requires = "snapshot_path")]
+    pub archive_path: Option<PathBuf>,
+    /// Directory of the local formal snapshots
+    #[arg(long, requires = "archive_path")]
+    pub snapshot_path: Option<PathBuf>,
+    /// Epoch of the snapshot to start from, defaults to the latest snapshot
+    #[arg(long)]
+    pub snapshot_epoch: Option<u64>,
+    /// Last archived checkpoint to read, defaults to the last one
+    #[arg(long)]
+    pub end_checkpoint: Option<u64>,
+}
+
+impl ArchiveReplayArgs {
+    fn paths(&self) -> Option<(&Path, &Path)> {
+        self.archive_path
+            .as_deref()
+            .zip(self.snapshot_path.as_deref())
+    }
+}
+
 #[derive(Parser, Clone)]
 #[command(rename_all = "kebab-case")]
 pub enum ReplayToolCommand {
@@ -97,6 +134,8 @@ pub enum ReplayToolCommand {
         /// one originally used for the transaction.
         #[arg(long, short, allow_hyphen_values = true)]
         protocol_version: Option<i64>,
+        #[command(flatten)]
+        archive: ArchiveReplayArgs,
     },
 
     /// Replay transactions listed in a file
@@ -130,6 +169,8 @@ pub enum ReplayToolCommand {
         terminate_early: bool,
         #[arg(long, short, default_value = "16")]
         max_tasks: u64,
+        #[command(flatten)]
+        archive: ArchiveReplayArgs,
     },
 
     /// Replay all transactions in an epoch
@@ -141,6 +182,35 @@ pub enum ReplayToolCommand {
         terminate_early: bool,
         #[arg(long, short, default_value = "16")]
         max_tasks: u64,
+        /// When replaying from an archive, the snapshot epoch defaults to the
+        /// epoch before this one
+        #[command(flatten)]
+        archive: ArchiveReplayArgs,
+    },
+
+    /// Replay all transactions in a range of checkpoints both as they
+    /// originally ran and under another protocol version, and report where
+    /// they differ
+    #[command(name = "df")]
+    DiffCheckpoints {
+        #[arg(long, short)]
+        start: u64,
+        #[arg(long, short)]
+        end: u64,
+        /// Protocol version to compare against, which the chain must have run
+        #[arg(long, short)]
+        protocol_version: u64,
+        /// Optional version of the executor to compare against, if not
+        /// specified defaults to the one of the target protocol version.
+        #[arg(long, allow_hyphen_values = true)]
+        executor_version: Option<i64>,
+        /// Path to write the JSON report of divergences to
+        #[arg(long, short, default_value = "replay_diff_report.json")]
+        output: PathBuf,
+        #[arg(long, short)]
+        terminate_early: bool,
+        #[arg(long, short, default_value = "16")]
+        max_tasks: u64,
     },
 
     /// Run the replay based fuzzer
@@ -399,20 +469,42 @@ pub async fn execute_replay_command(
             diag,
             executor_version,
             protocol_version,
+            archive,
         } => {
             let tx_digest = TransactionDigest::from_str(&tx_digest)?;
             info!("Executing tx: {}", tx_digest);
-            let sandbox_state = LocalExec::replay_with_network_config(
-                rpc_url,
-                cfg_path.map(|p| p.to_str().unwrap().to_string()),
-                tx_digest,
-                safety,
-                use_authority,
-                executor_version,
-                protocol_version,
-                None,
-            )
-            .await?;
+            let sandbox_state = if let Some((archive_path, snapshot_path)) = archive.paths() {
+                LocalExec::new_for_archive(
+                    archive_path,
+                    snapshot_path,
+                    archive.snapshot_epoch,
+                    archive
+                        .end_checkpoint
+                        .map_or(ArchiveEnd::Latest, ArchiveEnd::Checkpoint),
+                )
+                .await?
+                .execute_transaction(
+                    &tx_digest,
+                    safety,
+                    use_authority,
+                    executor_version,
+                    protocol_version,
+                    None,
+                )
+                .await?
+            } else {
+                LocalExec::replay_with_network_config(
+                    rpc_url,
+                    cfg_path.map(|p| p.to_str().unwrap().to_string()),
+                    tx_digest,
+                    safety,
+                    use_authority,
+                    executor_version,
+                    protocol_version,
+                    None,
+                )
+                .await?
+            };
 
             if diag {
                 println!("{:#?}", sandbox_state.pre_exec_diag);
@@ -480,8 +572,36 @@ pub async fn execute_replay_command(
             end,
             terminate_early,
             max_tasks,
+            archive,
         } => {
             assert!(start <= end, "Start checkpoint must be <= end checkpoint");
+            if let Some((archive_path, snapshot_path)) = archive.paths() {
+                // Each transaction reads the objects written by the ones before it, so
+                // they cannot be split across tasks
+                info!(
+                    "Executing checkpoints {} to {} in order from the local archive, ignoring max tasks",
+                    start, end
+                );
+                let time = std::time::Instant::now();
+                let checkpoints: Vec<_> = (start..=end).collect();
+                let (succeeded, total) = LocalExec::new_for_archive(
+                    archive_path,
+                    snapshot_path,
+                    archive.snapshot_epoch,
+                    ArchiveEnd::Checkpoint(end),
+                )
+                .await?
+                .execute_all_in_checkpoints(&checkpoints, &safety, terminate_early, use_authority)
+                .await?;
+                info!(
+                    "Executed {} checkpoints @ {}/{} total TXs succeeded in {} ms",
+                    end - start + 1,
+                    succeeded,
+                    total,
+                    time.elapsed().as_millis()
+                );
+                return Ok(Some((succeeded, total)));
+            }
             assert!(max_tasks > 0, "Max tasks must be > 0");
             let checkpoints_per_task = ((end - start + max_tasks) / max_tasks) as usize;
             let mut handles = vec![];
@@ -542,11 +662,103 @@ pub async fn execute_replay_command(
             );
             Some((total_succeeded, total_tx))
         }
+        ReplayToolCommand::DiffCheckpoints {
+            start,
+            end,
+            protocol_version,
+            executor_version,
+            output,
+            terminate_early,
+            max_tasks,
+        } => {
+            assert!(start <= end, "Start checkpoint must be <= end checkpoint");
+            assert!(max_tasks > 0, "Max tasks must be > 0");
+            let checkpoints_per_task = ((end - start + max_tasks) / max_tasks) as usize;
+            let target = DiffTarget {
+                protocol_version,
+                executor_version,
+            };
+            info!(
+                "Diffing checkpoints {} to {} against protocol version {} with at most {} tasks",
+                start, end, protocol_version, max_tasks
+            );
+
+            let mut handles = vec![];
+            let range: Vec<_> = (start..=end).collect();
+            for checkpoints in range.chunks(checkpoints_per_task) {
+                let checkpoints = checkpoints.to_vec();
+                let rpc_url = rpc_url.clone();
+                let safety = safety.clone();
+                let target = target.clone();
+                handles.push(tokio::spawn(async move {
+                    let mut lx =
+                        LocalExec::new_from_fn_url(&rpc_url.expect("Url must be provided"))
+                            .await?
+                            .init_for_execution()
+                            .await?;
+                    protocol_diff::diff_checkpoints(
+                        &mut lx,
+                        &checkpoints,
+                        &target,
+                        &safety,
+                        terminate_early,
+                    )
+                    .await
+                }));
+            }
+
+            let mut report = DiffReport::default();
+            for task_report in futures::future::join_all(handles).await {
+                report.merge(task_report.expect("Join all failed")?);
+            }
+            std::fs::write(&output, serde_json::to_string_pretty(&report)?)?;
+            info!(
+                "{} out of {} TXs diverged, {} could not be executed. Report written to {}",
+                report.transactions_diverged,
+                report.transactions_compared,
+                report.transactions_failed.len(),
+                output.display()
+            );
+            Some((
+                report.transactions_compared - report.transactions_diverged,
+                report.transactions_compared,
+            ))
+        }
         ReplayToolCommand::ReplayEpoch {
             epoch,
             terminate_early,
             max_tasks,
+            archive,
         } => {
+            if let Some((archive_path, snapshot_path)) = archive.paths() {
+                let snapshot_epoch = archive.snapshot_epoch.or(epoch.checked_sub(1));
+                let mut lx = LocalExec::new_for_archive(
+                    archive_path,
+                    snapshot_path,
+                    snapshot_epoch,
+                    ArchiveEnd::Epoch(epoch),
+                )
+                .await?;
+                let (start, end) = lx.checkpoints_for_epoch(epoch).await?;
+                info!(
+                    "Executing epoch {} (checkpoint range {}-{}) from the local archive",
+                    epoch, start, end
+                );
+                let checkpoints: Vec<_> = (start..=end).collect();
+                let (succeeded, total) = lx
+                    .execute_all_in_checkpoints(
+                        &checkpoints,
+                        &safety,
+                        terminate_early,
+                        use_authority,
+                    )
+                    .await?;
+                info!(
+                    "Epoch {} replay finished {} out of {} TXs",
+                    epoch, succeeded, total
+                );
+                return Ok(Some((succeeded, total)));
+            }
             let lx =
                 LocalExec::new_from_fn_url(&rpc_url.clone().expect("Url must be provided")).await?;
 
@@ -566,6 +778,7 @@ pub async fn execute_replay_command(
                     end,
                     terminate_early,
                     max_tasks,
+                    archive: Default::default(),
                 },
             )
             .await;
@@ -0,0 +1,639 @@
+// Copyright (c) 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use iota_config::node::ExpensiveSafetyCheckConfig;
+use iota_json_rpc_types::{IotaExecutionStatus, IotaTransactionBlockEffectsAPI, OwnedObjectRef};
+use iota_types::{
+    base_types::ObjectID,
+    digests::TransactionDigest,
+    execution_status::ExecutionFailureStatus,
+    gas::GasCostSummary,
+    transaction::{Command, TransactionKind},
+};
+use serde::{Deserialize, Serialize};
+use tracing::{error, info};
+
+use crate::{
+    replay::{ExecutionSandboxState, LocalExec},
+    types::ReplayEngineError,
+};
+
+/// The protocol version and executor which the original execution of
+/// transactions is compared against
+#[derive(Clone, Debug)]
+pub struct DiffTarget {
+    /// Must be a protocol version the chain has run, as the framework packages
+    /// of the target are read from the chain
+    pub protocol_version: u64,
+    /// Defaults to the executor of the target protocol version, -1 implies the
+    /// latest executor
+    pub executor_version: Option<i64>,
+}
+
+/// One way in which executing a transaction under the target differs from
+/// executing it as it originally ran
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Divergence {
+    Status {
+        original: IotaExecutionStatus,
+        target: IotaExecutionStatus,
+    },
+    Gas {
+        original: GasCostSummary,
+        target: GasCostSummary,
+    },
+    Events {
+        original: usize,
+        target: usize,
+    },
+    /// The events emitted at `index` differ in type or contents
+    EventContents {
+        index: usize,
+    },
+    ObjectsWritten {
+        only_original: Vec<ObjectID>,
+        only_target: Vec<ObjectID>,
+    },
+    ObjectsCreated {
+        only_original: Vec<ObjectID>,
+        only_target: Vec<ObjectID>,
+    },
+    ObjectsDeleted {
+        only_original: Vec<ObjectID>,
+        only_target: Vec<ObjectID>,
+    },
+    SharedObjects {
+        only_original: Vec<ObjectID>,
+        only_target: Vec<ObjectID>,
+    },
+    ObjectContents {
+        id: ObjectID,
+    },
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TransactionDivergence {
+    pub tx_digest: TransactionDigest,
+    /// The first Move function called by the transaction, or its kind if it
+    /// calls none
+    pub function: String,
+    /// The error the transaction failed with under the target, or originally
+    /// if it only failed then
+    pub error_kind: String,
+    pub divergences: Vec<Divergence>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct DiffReport {
+    pub transactions_compared: u64,
+    pub transactions_diverged: u64,
+    /// Transactions which could not be executed, with the error
+    pub transactions_failed: Vec<(TransactionDigest, String)>,
+    /// Divergent transactions by Move function and then by error kind
+    pub divergences: BTreeMap<String, BTreeMap<String, Vec<TransactionDivergence>>>,
+}
+
+impl DiffReport {
+    pub fn insert(&mut self, divergence: TransactionDivergence) {
+        self.transactions_diverged += 1;
+        self.divergences
+            .entry(divergence.function.clone())
+            .or_default()
+            .entry(divergence.error_kind.clone())
+            .or_default()
+            .push(divergence);
+    }
+
+    pub fn merge(&mut self, other: DiffReport) {
+        self.transactions_compared += other.transactions_compared;
+        self.transactions_failed.extend(other.transactions_failed);
+        for divergence in other.divergences.into_values().flat_map(|by_error| {
+            by_error
+                .into_values()
+                .flat_map(|divergences| divergences.into_iter())
+        }) {
+            self.insert(divergence);
+        }
+    }
+}
+
+/// Executes all transactions in `checkpoints` as they originally ran and under
+/// `target`, and reports where the two differ
+pub async fn diff_checkpoints(
+    lx: &mut LocalExec,
+    checkpoints: &[u64],
+    target: &DiffTarget,
+    expensive_safety_check_config: &ExpensiveSafetyCheckConfig,
+    terminate_early: bool,
+) -> Result<DiffReport, ReplayEngineError> {
+    // Transactions are executed under the target with the framework packages the
+    // chain ran at that protocol version
+    if !lx
+        .protocol_version_system_package_table
+        .contains_key(&target.protocol_version)
+    {
+        return Err(ReplayEngineError::FrameworkObjectVersionTableNotPopulated {
+            protocol_version: target.protocol_version,
+        });
+    }
+    let mut report = DiffReport::default();
+    for checkpoint in checkpoints {
+        for tx_digest in lx.get_checkpoint_txs(*checkpoint).await? {
+            match diff_transaction(lx, &tx_digest, target, expensive_safety_check_config).await {
+                Ok(divergence) => {
+                    report.transactions_compared += 1;
+                    if let Some(divergence) = divergence {
+                        info!("Transaction {} diverged under the target", tx_digest);
+                        report.insert(divergence);
+                    }
+                }
+                Err(e) => {
+                    if terminate_early {
+                        return Err(e);
+                    }
+                    error!("Error diffing tx: {},  {:#?}", tx_digest, e);
+                    report.transactions_failed.push((tx_digest, e.to_string()));
+                }
+            }
+        }
+    }
+    Ok(report)
+}
+
+/// Executes a transaction as it originally ran and under `target`, from the
+/// same input objects. Returns how the two differ, if they do.
+pub async fn diff_transaction(
+    lx: &mut LocalExec,
+    tx_digest: &TransactionDigest,
+    target: &DiffTarget,
+    expensive_safety_check_config: &ExpensiveSafetyCheckConfig,
+) -> Result<Option<TransactionDivergence>, ReplayEngineError> {
+    let original = lx
+        .execute_transaction(
+            tx_digest,
+            expensive_safety_check_config.clone(),
+            false,
+            None,
+            None,
+            None,
+        )
+        .await?;
+    // Genesis and system transactions are not executed locally
+    if original.local_exec_temporary_store.is_none() {
+        return Ok(None);
+    }
+
+    lx.executor_version = target.executor_version;
+    lx.protocol_version = Some(target.protocol_version as i64);
+    let mut tx_info = original.transaction_info.clone();
+    tx_info.protocol_version = lx
+        .get_protocol_config(tx_info.executed_epoch, tx_info.chain)
+        .await?
+        .version;
+    let target_state = lx
+        .execution_engine_execute_with_tx_info_impl(
+            &tx_info,
+            None,
+            expensive_safety_check_config.clone(),
+        )
+        .await?;
+
+    Ok(compare_executions(&original, &target_state))
+}
+
+fn compare_executions(
+    original: &ExecutionSandboxState,
+    target: &ExecutionSandboxState,
+) -> Option<TransactionDivergence> {
+    let (Some(original_store), Some(target_store)) = (
+        &original.local_exec_temporary_store,
+        &target.local_exec_temporary_store,
+    ) else {
+        return None;
+    };
+    let (original_effects, target_effects) =
+        (&original.local_exec_effects, &target.local_exec_effects);
+
+    let mut divergences = vec![];
+    if original_effects.status() != target_effects.status() {
+        divergences.push(Divergence::Status {
+            original: original_effects.status().clone(),
+            target: target_effects.status().clone(),
+        });
+    }
+    if original_effects.gas_cost_summary() != target_effects.gas_cost_summary() {
+        divergences.push(Divergence::Gas {
+            original: original_effects.gas_cost_summary().clone(),
+            target: target_effects.gas_cost_summary().clone(),
+        });
+    }
+    let (original_events, target_events) = (&original_store.events.data, &target_store.events.data);
+    if original_events.len() != target_events.len() {
+        divergences.push(Divergence::Events {
+            original: original_events.len(),
+            target: target_events.len(),
+        });
+    }
+    for (index, (event, target_event)) in original_events.iter().zip(target_events).enumerate() {
+        if event != target_event {
+            divergences.push(Divergence::EventContents { index });
+        }
+    }
+
+    if let Some((only_original, only_target)) = id_difference(
+        original_store.written.keys().copied(),
+        target_store.written.keys().copied(),
+    ) {
+        divergences.push(Divergence::ObjectsWritten {
+            only_original,
+            only_target,
+        });
+    }
+    if let Some((only_original, only_target)) = id_difference(
+        original_effects
+            .created()
+            .iter()
+            .map(OwnedObjectRef::object_id),
+        target_effects
+            .created()
+            .iter()
+            .map(OwnedObjectRef::object_id),
+    ) {
+        divergences.push(Divergence::ObjectsCreated {
+            only_original,
+            only_target,
+        });
+    }
+    if let Some((only_original, only_target)) = id_difference(
+        original_effects
+            .deleted()
+            .iter()
+            .chain(original_effects.unwrapped_then_deleted())
+            .map(|object_ref| object_ref.object_id),
+        target_effects
+            .deleted()
+            .iter()
+            .chain(target_effects.unwrapped_then_deleted())
+            .map(|object_ref| object_ref.object_id),
+    ) {
+        divergences.push(Divergence::ObjectsDeleted {
+            only_original,
+            only_target,
+        });
+    }
+    if let Some((only_original, only_target)) = id_difference(
+        original_effects
+            .shared_objects()
+            .iter()
+            .map(|object_ref| object_ref.object_id),
+        target_effects
+            .shared_objects()
+            .iter()
+            .map(|object_ref| object_ref.object_id),
+    ) {
+        divergences.push(Divergence::SharedObjects {
+            only_original,
+            only_target,
+        });
+    }
+    // Storage rebates follow the gas charged, which is compared above
+    for (id, object) in &original_store.written {
+        if let Some(target_object) = target_store.written.get(id) {
+            if object.data != target_object.data || object.owner != target_object.owner {
+                divergences.push(Divergence::ObjectContents { id: *id });
+            }
+        }
+    }
+
+    if divergences.is_empty() {
+        return None;
+    }
+    Some(TransactionDivergence {
+        tx_digest: original.transaction_info.tx_digest,
+        function: called_function(&original.transaction_info.kind),
+        error_kind: [target, original]
+            .iter()
+            .find_map(|state| match &state.local_exec_status {
+                Some(Err(e)) => Some(variant_name(e.kind()).to_string()),
+                _ => None,
+            })
+            .unwrap_or_else(|| "None".to_string()),
+        divergences,
+    })
+}
+
+/// The ids only in `original` and the ones only in `target`, if the two differ
+fn id_difference(
+    original: impl Iterator<Item = ObjectID>,
+    target: impl Iterator<Item = ObjectID>,
+) -> Option<(Vec<ObjectID>, Vec<ObjectID>)> {
+    let original: BTreeSet<_> = original.collect();
+    let target: BTreeSet<_> = target.collect();
+    if original == target {
+        return None;
+    }
+    Some((
+        original.difference(&target).copied().collect(),
+        target.difference(&original).copied().collect(),
+    ))
+}
+
+fn called_function(kind: &TransactionKind) -> String {
+    if let TransactionKind::ProgrammableTransaction(pt) = kind {
+        if let Some(call) = pt.commands.iter().find_map(|command| match command {
+            Command::MoveCall(call) => Some(call),
+            _ => None,
+        }) {
+            return format!("{}::{}::{}", call.package, call.module, call.function);
+        }
+    }
+    kind.name().to_string()
+}
+
+/// The name of the failure status variant, without its details
+fn variant_name(status: &ExecutionFailureStatus) -> &'static str {
+    use ExecutionFailureStatus::*;
+    match status {
+        InsufficientGas => "InsufficientGas",
+        InvalidGasObject => "InvalidGasObject",
+        InvariantViolation => "InvariantViolation",
+        FeatureNotYetSupported => "FeatureNotYetSupported",
+        MoveObjectTooBig { .. } => "MoveObjectTooBig",
+        MovePackageTooBig { .. } => "MovePackageTooBig",
+        CircularObjectOwnership { .. } => "CircularObjectOwnership",
+        InsufficientCoinBalance => "InsufficientCoinBalance",
+        CoinBalanceOverflow => "CoinBalanceOverflow",
+        PublishErrorNonZeroAddress => "PublishErrorNonZeroAddress",
+        IotaMoveVerificationError => "IotaMoveVerificationError",
+        MovePrimitiveRuntimeError(_) => "MovePrimitiveRuntimeError",
+        MoveAbort(..) => "MoveAbort",
+        VMVerificationOrDeserializationError => "VMVerificationOrDeserializationError",
+        VMInvariantViolation => "VMInvariantViolation",
+        FunctionNotFound => "FunctionNotFound",
+        ArityMismatch => "ArityMismatch",
+        TypeArityMismatch => "TypeArityMismatch",
+        NonEntryFunctionInvoked => "NonEntryFunctionInvoked",
+        CommandArgumentError { .. } => "CommandArgumentError",
+        TypeArgumentError { .. } => "TypeArgumentError",
+        UnusedValueWithoutDrop { .. } => "UnusedValueWithoutDrop",
+        InvalidPublicFunctionReturnType { .. } => "InvalidPublicFunctionReturnType",
+        InvalidTransferObject => "InvalidTransferObject",
+        EffectsTooLarge { .. } => "EffectsTooLarge",
+        PublishUpgradeMissingDependency => "PublishUpgradeMissingDependency",
+        PublishUpgradeDependencyDowngrade => "PublishUpgradeDependencyDowngrade",
+        PackageUpgradeError { .. } => "PackageUpgradeError",
+        WrittenObjectsTooLarge { .. } => "WrittenObjectsTooLarge",
+        CertificateDenied => "CertificateDenied",
+        IotaMoveVerificationTimedout => "IotaMoveVerificationTimedout",
+        SharedObjectOperationNotAllowed => "SharedObjectOperationNotAllowed",
+        InputObjectDeleted => "InputObjectDeleted",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use iota_json_rpc_types::IotaTransactionBlockEffects;
+    use iota_protocol_config::{Chain, ProtocolVersion};
+    use iota_types::{
+        base_types::{random_object_ref, IotaAddress, ObjectRef, SequenceNumber},
+        effects::{TransactionEffects, TransactionEvents},
+        error::ExecutionError,
+        event::Event,
+        execution_status::{ExecutionStatus, MoveLocationOpt},
+        inner_temporary_store::InnerTemporaryStore,
+        object::{Object, Owner},
+        transaction::{SenderSignedData, TransactionData, TransactionDataAPI},
+    };
+    use move_binary_format::binary_config::BinaryConfig;
+    use shared_crypto::intent::Intent;
+
+    use super::*;
+    use crate::types::{DiagInfo, OnChainTransactionInfo};
+
+    /// A transfer which executed successfully, wrote `written` and emitted
+    /// `events`
+    fn executed_transfer(written: &[Object], events: &[Event]) -> ExecutionSandboxState {
+        let sender = IotaAddress::random_for_testing_only();
+        let gas = random_object_ref();
+        let data = TransactionData::new_transfer_iota(
+            IotaAddress::random_for_testing_only(),
+            sender,
+            None,
+            gas,
+            1_000_000,
+            1_000,
+        );
+        let tx_digest = TransactionDigest::random();
+        let effects =
+            IotaTransactionBlockEffects::try_from(TransactionEffects::new_from_execution_v1(
+                ExecutionStatus::Success,
+                0,
+                GasCostSummary::default(),
+                vec![],
+                vec![],
+                tx_digest,
+                vec![],
+                vec![],
+                vec![],
+                vec![],
+                vec![],
+                vec![],
+                (gas, Owner::AddressOwner(sender)),
+                None,
+                vec![],
+            ))
+            .unwrap();
+        ExecutionSandboxState {
+            transaction_info: OnChainTransactionInfo {
+                tx_digest,
+                sender_signed_data: SenderSignedData::new(
+                    data.clone(),
+                    Intent::iota_transaction(),
+                    vec![],
+                ),
+                sender,
+                input_objects: vec![],
+                kind: data.kind().clone(),
+                modified_at_versions: vec![],
+                shared_object_refs: vec![],
+                gas: vec![gas],
+                gas_budget: 1_000_000,
+                gas_price: 1_000,
+                executed_epoch: 0,
+                dependencies: vec![],
+                effects: effects.clone(),
+                protocol_version: ProtocolVersion::MAX,
+                epoch_start_timestamp: 0,
+                reference_gas_price: 1_000,
+                chain: Chain::Unknown,
+            },
+            required_objects: vec![],
+            local_exec_temporary_store: Some(InnerTemporaryStore {
+                input_objects: BTreeMap::new(),
+                mutable_inputs: BTreeMap::new(),
+                written: written
+                    .iter()
+                    .map(|object| (object.id(), object.clone()))
+                    .collect(),
+                loaded_runtime_objects: BTreeMap::new(),
+                events: TransactionEvents {
+                    data: events.to_vec(),
+                },
+                binary_config: BinaryConfig::standard(),
+                runtime_packages_loaded_from_db: BTreeMap::new(),
+                lamport_version: SequenceNumber::new(),
+            }),
+            local_exec_effects: effects,
+            local_exec_status: Some(Ok(())),
+            pre_exec_diag: DiagInfo::default(),
+        }
+    }
+
+    fn divergence(function: &str, error_kind: &str) -> TransactionDivergence {
+        TransactionDivergence {
+            tx_digest: TransactionDigest::random(),
+            function: function.to_string(),
+            error_kind: error_kind.to_string(),
+            divergences: vec![],
+        }
+    }
+
+    #[test]
+    fn test_compare_identical_executions() {
+        let written = [Object::immutable_with_id_for_testing(ObjectID::random())];
+        let events = [Event::random_for_testing()];
+        let original = executed_transfer(&written, &events);
+        let target = executed_transfer(&written, &events);
+        assert!(compare_executions(&original, &target).is_none());
+    }
+
+    #[test]
+    fn test_compare_diverging_executions() {
+        let event = Event::random_for_testing();
+        let written = Object::immutable_with_id_for_testing(ObjectID::random());
+        let original = executed_transfer(&[written.clone()], &[event.clone()]);
+
+        let mut changed_event = event.clone();
+        changed_event.contents = vec![1];
+        let mut target = executed_transfer(&[], &[changed_event, event]);
+        target.transaction_info = original.transaction_info.clone();
+        target.local_exec_status = Some(Err(ExecutionError::from_kind(
+            ExecutionFailureStatus::InsufficientCoinBalance,
+        )));
+        let (created, deleted, shared): (ObjectRef, ObjectRef, ObjectRef) = (
+            random_object_ref(),
+            random_object_ref(),
+            random_object_ref(),
+        );
+        let IotaTransactionBlockEffects::V1(effects) = &mut target.local_exec_effects;
+        effects.created.push(OwnedObjectRef {
+            owner: Owner::Immutable,
+            reference: created.into(),
+        });
+        effects.deleted.push(deleted.into());
+        effects.shared_objects.push(shared.into());
+
+        let divergence = compare_executions(&original, &target).unwrap();
+        assert_eq!(divergence.tx_digest, original.transaction_info.tx_digest);
+        assert_eq!(divergence.error_kind, "InsufficientCoinBalance");
+        let divergences = &divergence.divergences;
+        assert_eq!(divergences.len(), 6);
+        assert!(matches!(
+            divergences[0],
+            Divergence::Events {
+                original: 1,
+                target: 2
+            }
+        ));
+        assert!(matches!(
+            divergences[1],
+            Divergence::EventContents { index: 0 }
+        ));
+        assert!(matches!(
+            &divergences[2],
+            Divergence::ObjectsWritten { only_original, only_target }
+                if only_original == &[written.id()] && only_target.is_empty()
+        ));
+        assert!(matches!(
+            &divergences[3],
+            Divergence::ObjectsCreated { only_original, only_target }
+                if only_original.is_empty() && only_target == &[created.0]
+        ));
+        assert!(matches!(
+            &divergences[4],
+            Divergence::ObjectsDeleted { only_original, only_target }
+                if only_original.is_empty() && only_target == &[deleted.0]
+        ));
+        assert!(matches!(
+            &divergences[5],
+            Divergence::SharedObjects { only_original, only_target }
+                if only_original.is_empty() && only_target == &[shared.0]
+        ));
+    }
+
+    #[test]
+    fn test_compare_object_contents() {
+        let id = ObjectID::random();
+        let original = executed_transfer(&[Object::immutable_with_id_for_testing(id)], &[]);
+        let object = Object::with_id_owner_for_testing(id, IotaAddress::random_for_testing_only());
+        let target = executed_transfer(&[object], &[]);
+
+        let divergence = compare_executions(&original, &target).unwrap();
+        assert_eq!(divergence.error_kind, "None");
+        assert!(matches!(
+            divergence.divergences[..],
+            [Divergence::ObjectContents { id: diverged }] if diverged == id
+        ));
+    }
+
+    #[test]
+    fn test_merge_reports() {
+        let mut report = DiffReport {
+            transactions_compared: 2,
+            ..Default::default()
+        };
+        report.insert(divergence("0x2::coin::split", "InsufficientCoinBalance"));
+
+        let mut other = DiffReport {
+            transactions_compared: 3,
+            transactions_failed: vec![(TransactionDigest::random(), "error".to_string())],
+            ..Default::default()
+        };
+        other.insert(divergence("0x2::coin::split", "InsufficientCoinBalance"));
+        other.insert(divergence("0x2::coin::split", "None"));
+        other.insert(divergence("TransferObjects", "None"));
+        report.merge(other);
+
+        assert_eq!(report.transactions_compared, 5);
+        assert_eq!(report.transactions_diverged, 4);
+        assert_eq!(report.transactions_failed.len(), 1);
+        let split = &report.divergences["0x2::coin::split"];
+        assert_eq!(split["InsufficientCoinBalance"].len(), 2);
+        assert_eq!(split["None"].len(), 1);
+        assert_eq!(report.divergences["TransferObjects"]["None"].len(), 1);
+    }
+
+    #[test]
+    fn test_variant_name() {
+        assert_eq!(
+            variant_name(&ExecutionFailureStatus::InsufficientGas),
+            "InsufficientGas"
+        );
+        assert_eq!(
+            variant_name(&ExecutionFailureStatus::MovePrimitiveRuntimeError(
+                MoveLocationOpt(None)
+            )),
+            "MovePrimitiveRuntimeError"
+        );
+        assert_eq!(
+            variant_name(&ExecutionFailureStatus::EffectsTooLarge {
+                current_size: 2,
+                max_size: 1,
+            }),
+            "EffectsTooLarge"
+        );
+    }
+}
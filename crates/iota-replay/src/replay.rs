@@ -4,7 +4,7 @@
 
 use std::{
     collections::{BTreeMap, HashSet},
-    path::PathBuf,
+    path::{Path, PathBuf},
     sync::{Arc, Mutex},
 };
 
@@ -64,7 +64,8 @@ use crate::{
     chain_from_chain_id,
     config::ReplayableNetworkConfigSet,
     data_fetcher::{
-        extract_epoch_and_version, DataFetcher, Fetchers, NodeStateDumpFetcher, RemoteFetcher,
+        extract_epoch_and_version, ArchiveEnd, ArchiveFetcher, DataFetcher, Fetchers,
+        NodeStateDumpFetcher, RemoteFetcher,
     },
     displays::{
         transaction_displays::{transform_command_results_to_annotated, FullPTB},
@@ -505,6 +506,65 @@ impl LocalExec {
         })
     }
 
+    pub async fn new_for_archive(
+        archive_path: &Path,
+        snapshot_path: &Path,
+        snapshot_epoch: Option<EpochId>,
+        end: ArchiveEnd,
+    ) -> Result<Self, ReplayEngineError> {
+        // Use a throwaway metrics registry for local execution.
+        let registry = prometheus::Registry::new();
+        let metrics = Arc::new(LimitsMetrics::new(&registry));
+
+        let fetcher = ArchiveFetcher::new(archive_path, snapshot_path, snapshot_epoch, end).await?;
+
+        // The archive already tells the protocol version of every epoch it covers
+        let mut protocol_version_epoch_table = BTreeMap::new();
+        for (epoch, archived) in fetcher.epochs.iter() {
+            protocol_version_epoch_table
+                .entry(archived.protocol_version)
+                .and_modify(|summary: &mut ProtocolVersionSummary| {
+                    summary.epoch_end = *epoch;
+                    summary.checkpoint_end = archived.last_checkpoint;
+                })
+                .or_insert(ProtocolVersionSummary {
+                    protocol_version: archived.protocol_version,
+                    epoch_start: *epoch,
+                    epoch_end: *epoch,
+                    checkpoint_start: Some(archived.first_checkpoint),
+                    checkpoint_end: archived.last_checkpoint,
+                    epoch_change_tx: archived.epoch_change_tx,
+                });
+        }
+        // Until a transaction sets it, the protocol version is the one in effect at
+        // the last checkpoint read, up to which transactions are replayed
+        let last_checkpoint = fetcher.last_checkpoint()?;
+        let current_protocol_version =
+            fetcher
+                .protocol_version_at(last_checkpoint)
+                .ok_or_else(|| ReplayEngineError::UnableToReadArchive {
+                    err: format!("no epoch archived for checkpoint {last_checkpoint}"),
+                })?;
+
+        Ok(Self {
+            client: None,
+            protocol_version_epoch_table,
+            protocol_version_system_package_table: BTreeMap::new(),
+            current_protocol_version,
+            exec_store_events: Arc::new(Mutex::new(Vec::new())),
+            metrics,
+            storage: Storage::default(),
+            fetcher: Fetchers::Archive(fetcher),
+            // TODO: make these configurable
+            num_retries_for_timeout: RPC_TIMEOUT_ERR_NUM_RETRIES,
+            sleep_period_for_timeout: RPC_TIMEOUT_ERR_SLEEP_RETRY_PERIOD,
+            diag: Default::default(),
+            executor_version: None,
+            protocol_version: None,
+            enable_profiler: None,
+        })
+    }
+
     pub async fn multi_download_and_store(
         &mut self,
         objs: &[(ObjectID, SequenceNumber)],
@@ -714,7 +774,10 @@ impl LocalExec {
     ) -> Result<ExecutionSandboxState, ReplayEngineError> {
         let tx_digest = &tx_info.tx_digest;
         // A lot of the logic here isnt designed for genesis
-        if *tx_digest == TransactionDigest::genesis_marker() || tx_info.sender == IotaAddress::ZERO
+        // System transactions can only be replayed from an archive, which has the
+        // objects they read at the right versions
+        if *tx_digest == TransactionDigest::genesis_marker()
+            || (tx_info.sender == IotaAddress::ZERO && !self.is_archive_replay())
         {
             // Genesis.
             warn!(
@@ -772,9 +835,12 @@ impl LocalExec {
         let expensive_checks = true;
         let transaction_kind = override_transaction_kind.unwrap_or(tx_info.kind.clone());
         let certificate_deny_set = HashSet::new();
-        let (inner_store, gas_status, effects, result) = if let Ok(gas_status) =
+        let gas_status = if tx_info.kind.is_system_tx() {
+            Ok(IotaGasStatus::new_unmetered())
+        } else {
             IotaGasStatus::new(tx_info.gas_budget, tx_info.gas_price, rgp, protocol_config)
-        {
+        };
+        let (inner_store, gas_status, effects, result) = if let Ok(gas_status) = gas_status {
             executor.execute_transaction_to_effects(
                 &self,
                 protocol_config,
@@ -850,10 +916,10 @@ impl LocalExec {
             );
         }
 
-        let tx_info = if self.is_remote_replay() {
-            self.resolve_tx_components(tx_digest).await?
-        } else {
-            self.resolve_tx_components_from_dump(tx_digest).await?
+        let tx_info = match self.fetcher {
+            Fetchers::Remote(_) => self.resolve_tx_components(tx_digest).await?,
+            Fetchers::NodeStateDump(_) => self.resolve_tx_components_from_dump(tx_digest).await?,
+            Fetchers::Archive(_) => self.resolve_tx_components_from_archive(tx_digest).await?,
         };
         self.execution_engine_execute_with_tx_info_impl(
             &tx_info,
@@ -1048,9 +1114,59 @@ impl LocalExec {
         protocol_version: Option<i64>,
         enable_profiler: Option<PathBuf>,
     ) -> Result<ExecutionSandboxState, ReplayEngineError> {
+        if self.is_archive_replay() {
+            // The transactions before this one must run first, with no overrides, to
+            // produce the objects it reads
+            let preceding = self
+                .fetcher
+                .as_archive()
+                .unexecuted_transactions_before(tx_digest)?;
+            if !preceding.is_empty() {
+                info!(
+                    "Executing {} archived transactions before {}",
+                    preceding.len(),
+                    tx_digest
+                );
+            }
+            (
+                self.executor_version,
+                self.protocol_version,
+                self.enable_profiler,
+            ) = (None, None, None);
+            for digest in preceding {
+                let sandbox_state = self
+                    .execute_archived_transaction(&digest, expensive_safety_check_config.clone())
+                    .await?;
+                sandbox_state.check_effects()?;
+                self.record_archived_execution(&sandbox_state)?;
+            }
+        }
+
         self.executor_version = executor_version;
         self.protocol_version = protocol_version;
         self.enable_profiler = enable_profiler;
+        if self.is_archive_replay() {
+            let sandbox_state = self
+                .execute_archived_transaction(tx_digest, expensive_safety_check_config)
+                .await?;
+            // Only the outputs of a faithful execution may be read by later transactions
+            if executor_version.is_none()
+                && protocol_version.is_none()
+                && sandbox_state.check_effects().is_ok()
+            {
+                self.record_archived_execution(&sandbox_state)?;
+            }
+            if use_authority {
+                return Self::certificate_execute_with_sandbox_state(
+                    &sandbox_state,
+                    None,
+                    &self.diag,
+                )
+                .await;
+            }
+            return Ok(sandbox_state);
+        }
+
         if use_authority {
             self.certificate_execute(tx_digest, expensive_safety_check_config.clone())
                 .await
@@ -1059,6 +1175,36 @@ impl LocalExec {
                 .await
         }
     }
+
+    /// Executes an archived transaction on the execution engine, reading
+    /// objects afresh from the archive
+    async fn execute_archived_transaction(
+        &mut self,
+        tx_digest: &TransactionDigest,
+        expensive_safety_check_config: ExpensiveSafetyCheckConfig,
+    ) -> Result<ExecutionSandboxState, ReplayEngineError> {
+        // Objects stored for earlier transactions may have been modified since
+        self.storage = Storage::default();
+        self.execution_engine_execute_impl(tx_digest, expensive_safety_check_config)
+            .await
+    }
+
+    /// Makes the objects written by an archived transaction available to the
+    /// transactions after it
+    fn record_archived_execution(
+        &self,
+        sandbox_state: &ExecutionSandboxState,
+    ) -> Result<(), ReplayEngineError> {
+        let written = sandbox_state
+            .local_exec_temporary_store
+            .as_ref()
+            .map(|store| store.written.clone())
+            .unwrap_or_default();
+        self.fetcher
+            .as_archive()
+            .record_execution(&sandbox_state.transaction_info.tx_digest, &written)
+    }
+
     fn system_package_ids(_protocol_version: u64) -> Vec<ObjectID> {
         BuiltInFramework::all_package_ids()
     }
@@ -1118,6 +1264,10 @@ impl LocalExec {
         matches!(self.fetcher, Fetchers::Remote(_))
     }
 
+    pub fn is_archive_replay(&self) -> bool {
+        matches!(self.fetcher, Fetchers::Archive(_))
+    }
+
     /// Must be called after `populate_protocol_version_tables`
     pub fn system_package_versions_for_protocol_version(
         &self,
@@ -1141,6 +1291,8 @@ impl LocalExec {
                 .map(|w| (w.id, w.version, w.digest))
                 .map(|q| (q.0, q.1))
                 .collect()),
+
+            Fetchers::Archive(a) => Ok(a.system_package_refs()),
         }
     }
 
@@ -1406,6 +1558,10 @@ impl LocalExec {
         &self,
         epoch_id: u64,
     ) -> Result<(u64, u64), ReplayEngineError> {
+        // Events are not archived, but the checkpoints which end each epoch are
+        if let Fetchers::Archive(a) = &self.fetcher {
+            return a.checkpoints_for_epoch(epoch_id);
+        }
         let epoch_change_events = self
             .fetcher
             .get_epoch_change_events(true)
@@ -1622,6 +1778,70 @@ impl LocalExec {
         })
     }
 
+    async fn resolve_tx_components_from_archive(
+        &self,
+        tx_digest: &TransactionDigest,
+    ) -> Result<OnChainTransactionInfo, ReplayEngineError> {
+        let (orig_tx, effects) = self
+            .fetcher
+            .as_archive()
+            .transaction_and_effects(tx_digest)?;
+        let effects = IotaTransactionBlockEffects::try_from(effects)?;
+        let sender = orig_tx.transaction_data().sender();
+
+        let input_objs = orig_tx
+            .transaction_data()
+            .input_objects()
+            .map_err(|e| ReplayEngineError::UserInputError { err: e })?;
+        let tx_kind_orig = orig_tx.transaction_data().kind();
+
+        // Download the objects at the version right before the execution of this TX
+        let modified_at_versions: Vec<(ObjectID, SequenceNumber)> = effects.modified_at_versions();
+
+        let shared_object_refs: Vec<ObjectRef> = effects
+            .shared_objects()
+            .iter()
+            .map(|so_ref| {
+                if so_ref.digest == ObjectDigest::OBJECT_DIGEST_DELETED {
+                    Err(ReplayEngineError::NotSupportedByArchive {
+                        what: "transactions reading deleted shared objects".to_string(),
+                    })
+                } else {
+                    Ok(so_ref.to_object_ref())
+                }
+            })
+            .collect::<Result<_, _>>()?;
+        let gas_data = orig_tx.transaction_data().gas_data();
+        let gas_object_refs: Vec<_> = gas_data.clone().payment.into_iter().collect();
+
+        let epoch_id = effects.executed_epoch();
+        let chain = chain_from_chain_id(self.fetcher.get_chain_id().await?.as_str());
+
+        // Extract the epoch start timestamp
+        let (epoch_start_timestamp, reference_gas_price) =
+            self.get_epoch_start_timestamp_and_rgp(epoch_id).await?;
+
+        Ok(OnChainTransactionInfo {
+            kind: tx_kind_orig.clone(),
+            sender,
+            modified_at_versions,
+            input_objects: input_objs,
+            shared_object_refs,
+            gas: gas_object_refs,
+            gas_budget: gas_data.budget,
+            gas_price: gas_data.price,
+            executed_epoch: epoch_id,
+            dependencies: effects.dependencies().to_vec(),
+            effects,
+            protocol_version: self.get_protocol_config(epoch_id, chain).await?.version,
+            tx_digest: *tx_digest,
+            epoch_start_timestamp,
+            sender_signed_data: orig_tx.clone(),
+            reference_gas_price,
+            chain,
+        })
+    }
+
     async fn resolve_download_input_objects(
         &mut self,
         tx_info: &OnChainTransactionInfo,
@@ -1638,7 +1858,11 @@ impl LocalExec {
         // object.
         if !deleted_shared_objects.is_empty() {
             for tx_digest in tx_info.dependencies.iter() {
-                let tx_info = self.resolve_tx_components(tx_digest).await?;
+                let tx_info = if self.is_archive_replay() {
+                    self.resolve_tx_components_from_archive(tx_digest).await?
+                } else {
+                    self.resolve_tx_components(tx_digest).await?
+                };
                 for (obj_id, version, _) in tx_info.shared_object_refs.iter() {
                     deleted_shared_info_map.insert(*obj_id, (tx_info.tx_digest, *version));
                 }
@@ -1776,9 +2000,11 @@ impl LocalExec {
         self.multi_download_and_store(&shared_refs).await?;
 
         // Download gas (although this should already be in cache from modified at
-        // versions?)
-        let gas_refs: Vec<_> = tx_info.gas.iter().map(|w| (w.0, w.1)).collect();
-        self.multi_download_and_store(&gas_refs).await?;
+        // versions?) System transactions are not paid for with gas objects
+        if !tx_info.kind.is_system_tx() {
+            let gas_refs: Vec<_> = tx_info.gas.iter().map(|w| (w.0, w.1)).collect();
+            self.multi_download_and_store(&gas_refs).await?;
+        }
 
         // Fetch the input objects we know from the raw transaction
         let input_objs = self
@@ -2040,7 +2266,17 @@ impl ObjectStore for LocalExec {
         &self,
         object_id: &ObjectID,
     ) -> iota_types::storage::error::Result<Option<Object>> {
-        let res = self.storage.live_objects_store.get(object_id).cloned();
+        let res = self
+            .storage
+            .live_objects_store
+            .get(object_id)
+            .cloned()
+            .or_else(|| match &self.fetcher {
+                // Replays from an archive do not know which child objects are loaded up
+                // front, so they are read at their latest version
+                Fetchers::Archive(a) => a.latest_object(object_id),
+                _ => None,
+            });
         self.exec_store_events
             .lock()
             .expect("Unable to lock events list")
@@ -2,14 +2,37 @@
 // Modifications Copyright (c) 2024 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use iota_config::node::ExpensiveSafetyCheckConfig;
+use std::{collections::BTreeSet, num::NonZeroUsize, path::PathBuf, sync::Arc, time::Duration};
+
+use iota_archival::{
+    reader::{ArchiveReader, ArchiveReaderMetrics},
+    writer::ArchiveWriter,
+};
+use iota_config::{
+    node::{ArchiveReaderConfig, ExpensiveSafetyCheckConfig},
+    object_storage_config::{ObjectStoreConfig, ObjectStoreType},
+};
+use iota_core::authority::{authority_store_tables::AuthorityPerpetualTables, CHAIN_IDENTIFIER};
 use iota_json_rpc_api::QUERY_MAX_RESULT_LIMIT;
 use iota_json_rpc_types::IotaTransactionBlockResponseOptions;
 use iota_sdk::{IotaClient, IotaClientBuilder};
-use iota_types::{base_types::IotaAddress, digests::TransactionDigest};
+use iota_snapshot::writer::StateSnapshotWriterV1;
+use iota_storage::{FileCompression, StorageFormat};
+use iota_types::{
+    base_types::IotaAddress,
+    digests::{ChainIdentifier, TransactionDigest},
+    effects::TransactionEffectsAPI,
+    messages_checkpoint::{FullCheckpointContents, VerifiedCheckpointContents},
+    storage::{ObjectStore, ReadStore, SharedInMemoryStore},
+};
+use prometheus::Registry;
+use rand::{rngs::StdRng, SeedableRng};
+use simulacrum::Simulacrum;
+use tempfile::tempdir;
 
 use crate::{
     config::ReplayableNetworkConfigSet,
+    data_fetcher::{ArchiveEnd, ArchiveFetcher},
     types::{ReplayEngineError, MAX_CONCURRENT_REQUESTS, RPC_TIMEOUT_ERR_SLEEP_RETRY_PERIOD},
     LocalExec,
 };
@@ -173,3 +196,183 @@ async fn execute_replay(url: &str, tx: &TransactionDigest) -> Result<(), ReplayE
 
     Ok(())
 }
+
+fn temp_dir() -> PathBuf {
+    tempdir()
+        .expect("Failed to open temporary directory")
+        .into_path()
+}
+
+fn file_store(directory: PathBuf) -> ObjectStoreConfig {
+    ObjectStoreConfig {
+        object_store: Some(ObjectStoreType::File),
+        directory: Some(directory),
+        ..Default::default()
+    }
+}
+
+/// Checks that an epoch replays from a local archive and the formal snapshot
+/// of the epoch before it, without any RPC
+#[tokio::test]
+async fn test_replay_from_local_archive() -> anyhow::Result<()> {
+    let mut sim = Simulacrum::new_with_rng(StdRng::from_seed([9; 32]));
+    let genesis = sim.get_checkpoint_by_sequence_number(0)?.unwrap();
+    let chain_id = ChainIdentifier::from(*genesis.digest());
+    // The snapshot writer reads the chain from it
+    let _ = CHAIN_IDENTIFIER.set(chain_id);
+
+    sim.create_checkpoint();
+    sim.advance_epoch(false);
+    let end_of_epoch_0 = sim.get_latest_checkpoint()?;
+
+    // The snapshot holds the objects live at the end of epoch 0
+    let mut object_ids = BTreeSet::new();
+    for sequence_number in 0..=end_of_epoch_0.sequence_number {
+        let checkpoint = sim
+            .get_checkpoint_by_sequence_number(sequence_number)?
+            .unwrap();
+        let contents = sim
+            .get_checkpoint_contents_by_digest(&checkpoint.content_digest)?
+            .unwrap();
+        for digests in contents.iter() {
+            let effects = sim.get_transaction_effects(&digests.transaction)?.unwrap();
+            object_ids.extend(
+                effects
+                    .all_changed_objects()
+                    .into_iter()
+                    .map(|((id, _, _), _, _)| id),
+            );
+        }
+    }
+    let perpetual_db = Arc::new(AuthorityPerpetualTables::open(&temp_dir(), None));
+    for id in object_ids {
+        if let Some(object) = sim.get_object(&id)? {
+            perpetual_db.insert_object_test_only(object)?;
+        }
+    }
+    let snapshot_path = temp_dir();
+    StateSnapshotWriterV1::new(
+        &file_store(temp_dir()),
+        &file_store(snapshot_path.clone()),
+        FileCompression::Zstd,
+        NonZeroUsize::new(1).unwrap(),
+    )
+    .await?
+    .write(0, perpetual_db)
+    .await?;
+
+    for _ in 0..3 {
+        let (transaction, _) = sim.transfer_txn(IotaAddress::random_for_testing_only());
+        sim.execute_transaction(transaction)?;
+        sim.create_checkpoint();
+    }
+    sim.advance_epoch(false);
+    let end_of_epoch_1 = sim.get_latest_checkpoint()?;
+    // A checkpoint of epoch 2 which must not be read when replaying epoch 1
+    let last_checkpoint = sim.create_checkpoint();
+
+    let archive_store = SharedInMemoryStore::default();
+    let committee = sim.get_committee(0)?.unwrap();
+    for sequence_number in 0..=last_checkpoint.sequence_number {
+        let checkpoint = sim
+            .get_checkpoint_by_sequence_number(sequence_number)?
+            .unwrap();
+        let contents = sim
+            .get_checkpoint_contents_by_digest(&checkpoint.content_digest)?
+            .unwrap();
+        let contents = VerifiedCheckpointContents::new_unchecked(
+            FullCheckpointContents::from_checkpoint_contents(&sim, contents)?.unwrap(),
+        );
+        let mut store = archive_store.inner_mut();
+        if sequence_number == 0 {
+            store.insert_genesis_state(checkpoint, contents, committee.as_ref().clone());
+        } else {
+            store.insert_checkpoint(&checkpoint);
+            store.insert_checkpoint_contents(&checkpoint, contents);
+            store.update_highest_synced_checkpoint(&checkpoint);
+        }
+    }
+
+    let archive_path = temp_dir();
+    let archive_writer = ArchiveWriter::new(
+        file_store(temp_dir()),
+        file_store(archive_path.clone()),
+        FileCompression::Zstd,
+        StorageFormat::Blob,
+        Duration::from_secs(1),
+        20,
+        &Registry::default(),
+    )
+    .await?;
+    let kill = archive_writer.start(archive_store).await?;
+    let archive_reader = ArchiveReader::new(
+        ArchiveReaderConfig {
+            remote_store_config: file_store(archive_path.clone()),
+            download_concurrency: NonZeroUsize::new(1).unwrap(),
+            use_for_pruning_watermark: false,
+        },
+        &ArchiveReaderMetrics::new(&Registry::default()),
+    )?;
+    tokio::time::timeout(Duration::from_secs(60), async {
+        loop {
+            if archive_reader.sync_manifest_once().await.is_ok() {
+                if let Ok(manifest) = archive_reader.get_manifest().await {
+                    if manifest.next_checkpoint_seq_num() > end_of_epoch_1.sequence_number {
+                        break;
+                    }
+                }
+            }
+            tokio::time::sleep(Duration::from_millis(500)).await;
+        }
+    })
+    .await?;
+    kill.send(())?;
+
+    let fetcher =
+        ArchiveFetcher::new(&archive_path, &snapshot_path, Some(0), ArchiveEnd::Epoch(1)).await?;
+    assert_eq!(fetcher.chain_id, chain_id);
+    assert_eq!(fetcher.last_checkpoint()?, end_of_epoch_1.sequence_number);
+    assert_eq!(
+        fetcher.checkpoints_for_epoch(1)?,
+        (
+            end_of_epoch_0.sequence_number + 1,
+            end_of_epoch_1.sequence_number
+        )
+    );
+
+    let mut exec =
+        LocalExec::new_for_archive(&archive_path, &snapshot_path, Some(0), ArchiveEnd::Epoch(1))
+            .await?;
+    let (start, end) = exec.checkpoints_for_epoch(1).await?;
+    let checkpoints: Vec<_> = (start..=end).collect();
+    let (succeeded, total) = exec
+        .execute_all_in_checkpoints(
+            &checkpoints,
+            &ExpensiveSafetyCheckConfig::default(),
+            true,
+            false,
+        )
+        .await?;
+    assert!(total > 0);
+    assert_eq!(succeeded, total);
+
+    // Only the latest version of each object is kept, so a transaction cannot be
+    // replayed once the ones after it were
+    let first_tx = fetcher.transactions[0].1;
+    let err = exec
+        .execute_transaction(
+            &first_tx,
+            ExpensiveSafetyCheckConfig::default(),
+            false,
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        ReplayEngineError::TransactionAlreadyReplayed { digest } if digest == first_tx
+    ));
+    Ok(())
+}
@@ -194,6 +194,20 @@ pub enum ReplayEngineError {
 
     #[error("Unable to get chain id: {}", err)]
     UnableToGetChainId { err: String },
+
+    #[error("Unable to read local archive: {}", err)]
+    UnableToReadArchive { err: String },
+
+    #[error("Transaction {digest} not found in local archive")]
+    TransactionNotInArchive { digest: TransactionDigest },
+
+    #[error(
+        "Transaction {digest} was already replayed from the local archive, replaying it again would read the objects it loads dynamically at later versions"
+    )]
+    TransactionAlreadyReplayed { digest: TransactionDigest },
+
+    #[error("Replaying from a local archive does not support {what}")]
+    NotSupportedByArchive { what: String },
 }
 
 impl From<IotaObjectResponseError> for ReplayEngineError {
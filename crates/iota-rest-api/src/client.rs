@@ -4,12 +4,17 @@
 
 use anyhow::Result;
 use iota_types::{
-    base_types::{ObjectID, SequenceNumber},
+    base_types::{EpochId, ObjectID, SequenceNumber},
+    committee::Committee,
+    digests::TransactionDigest,
     full_checkpoint_content::CheckpointData,
+    iota_system_state::iota_system_state_summary::IotaSystemStateSummary,
     messages_checkpoint::{CertifiedCheckpointSummary, CheckpointSequenceNumber},
     object::Object,
 };
 
+use crate::{transactions::TransactionResponse, types::X_IOTA_CURSOR};
+
 #[derive(Clone)]
 pub struct Client {
     inner: reqwest::Client,
@@ -102,6 +107,99 @@ impl Client {
         self.bcs(response).await
     }
 
+    /// Fetches up to `limit` versions of an object, newest first, starting
+    /// from `start` or the latest version. Returns the version to start the
+    /// next page from, if there are older versions left.
+    pub async fn get_object_history(
+        &self,
+        object_id: ObjectID,
+        start: Option<SequenceNumber>,
+        limit: Option<u32>,
+    ) -> Result<(Vec<Object>, Option<SequenceNumber>)> {
+        let url = format!("{}/objects/{object_id}/history", self.base_url);
+
+        let mut query = Vec::new();
+        if let Some(start) = start {
+            query.push(("start", start.value()));
+        }
+        if let Some(limit) = limit {
+            query.push(("limit", limit as u64));
+        }
+
+        let response = self
+            .inner
+            .get(url)
+            .query(&query)
+            .header(reqwest::header::ACCEPT, crate::APPLICATION_BCS)
+            .send()
+            .await?;
+
+        let cursor = response
+            .headers()
+            .get(X_IOTA_CURSOR)
+            .map(|cursor| -> Result<SequenceNumber> {
+                Ok(SequenceNumber::from_u64(cursor.to_str()?.parse()?))
+            })
+            .transpose()?;
+
+        Ok((self.bcs(response).await?, cursor))
+    }
+
+    pub async fn get_transaction(
+        &self,
+        transaction_digest: &TransactionDigest,
+    ) -> Result<TransactionResponse> {
+        let url = format!("{}/transactions/{transaction_digest}", self.base_url);
+
+        let response = self
+            .inner
+            .get(url)
+            .header(reqwest::header::ACCEPT, crate::APPLICATION_BCS)
+            .send()
+            .await?;
+
+        self.bcs(response).await
+    }
+
+    pub async fn get_system_state_summary(&self) -> Result<IotaSystemStateSummary> {
+        let url = format!("{}/system", self.base_url);
+
+        let response = self
+            .inner
+            .get(url)
+            .header(reqwest::header::ACCEPT, crate::APPLICATION_JSON)
+            .send()
+            .await?;
+
+        self.json(response).await
+    }
+
+    pub async fn get_current_committee(&self) -> Result<Committee> {
+        let url = format!("{}/system/committee", self.base_url);
+
+        let response = self
+            .inner
+            .get(url)
+            .header(reqwest::header::ACCEPT, crate::APPLICATION_BCS)
+            .send()
+            .await?;
+
+        self.bcs(response).await
+    }
+
+    pub async fn get_committee(&self, epoch: EpochId) -> Result<Committee> {
+        let url = format!("{}/system/committee/{epoch}", self.base_url);
+
+        let response = self
+            .inner
+            .get(url)
+            .header(reqwest::header::ACCEPT, crate::APPLICATION_BCS)
+            .send()
+            .await?;
+
+        self.bcs(response).await
+    }
+
     fn check_response(&self, response: reqwest::Response) -> Result<reqwest::Response> {
         if !response.status().is_success() {
             let status = response.status();
@@ -6,6 +6,7 @@ use axum::http::StatusCode;
 
 pub type Result<T, E = RestError> = std::result::Result<T, E>;
 
+#[derive(Debug)]
 pub struct RestError {
     status: StatusCode,
     message: Option<String>,
@@ -39,6 +40,15 @@ impl From<iota_types::storage::error::Error> for RestError {
     }
 }
 
+impl From<iota_types::error::IotaError> for RestError {
+    fn from(value: iota_types::error::IotaError) -> Self {
+        Self {
+            status: StatusCode::INTERNAL_SERVER_ERROR,
+            message: Some(value.to_string()),
+        }
+    }
+}
+
 impl From<anyhow::Error> for RestError {
     fn from(value: anyhow::Error) -> Self {
         Self {
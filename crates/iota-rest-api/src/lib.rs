@@ -12,12 +12,15 @@ mod health;
 mod info;
 mod objects;
 mod response;
+mod system;
+mod transactions;
 pub mod types;
 
 pub use client::Client;
 pub use error::{RestError, Result};
 pub use iota_types::full_checkpoint_content::{CheckpointData, CheckpointTransaction};
 use iota_types::storage::ReadStore;
+pub use transactions::TransactionResponse;
 
 pub const TEXT_PLAIN_UTF_8: &str = "text/plain; charset=utf-8";
 pub const APPLICATION_BCS: &str = "application/bcs";
@@ -108,5 +111,22 @@ where
             objects::GET_OBJECT_WITH_VERSION_PATH,
             get(objects::get_object_with_version::<S>),
         )
+        .route(
+            objects::GET_OBJECT_HISTORY_PATH,
+            get(objects::get_object_history::<S>),
+        )
+        .route(
+            transactions::GET_TRANSACTION_PATH,
+            get(transactions::get_transaction::<S>),
+        )
+        .route(
+            system::GET_SYSTEM_STATE_PATH,
+            get(system::get_system_state::<S>),
+        )
+        .route(
+            system::GET_CURRENT_COMMITTEE_PATH,
+            get(system::get_current_committee::<S>),
+        )
+        .route(system::GET_COMMITTEE_PATH, get(system::get_committee::<S>))
         .with_state(state)
 }
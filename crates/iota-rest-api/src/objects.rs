@@ -2,15 +2,24 @@
 // Modifications Copyright (c) 2024 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use axum::extract::{Path, State};
+use axum::{
+    extract::{Path, Query, State},
+    http::HeaderMap,
+};
 use iota_types::{
     base_types::{ObjectID, SequenceNumber},
+    effects::TransactionEffectsAPI,
     object::Object,
     storage::ReadStore,
 };
 use tap::Pipe;
 
-use crate::{accept::AcceptFormat, response::ResponseContent, types::JsonObject, Result};
+use crate::{
+    accept::AcceptFormat,
+    response::ResponseContent,
+    types::{JsonObject, X_IOTA_CURSOR},
+    Result,
+};
 
 pub const GET_OBJECT_PATH: &str = "/objects/:object_id";
 
@@ -48,6 +57,121 @@ pub async fn get_object_with_version<S: ReadStore>(
     .pipe(Ok)
 }
 
+pub const GET_OBJECT_HISTORY_PATH: &str = "/objects/:object_id/history";
+
+/// Returns the versions of an object, newest first, starting from the
+/// `start` version or the latest one. The history is followed backwards
+/// through the transactions that modified the object, so it ends at the
+/// version the object was created (or unwrapped) at, or at the first version
+/// that was pruned. If there are more versions, the `x-iota-cursor` header
+/// holds the version to pass as `start` to fetch the next page.
+///
+/// Pruning removes the oldest versions first, so a `start` version older than
+/// the latest version of the object that is no longer available returns an
+/// empty history rather than an error.
+pub async fn get_object_history<S: ReadStore>(
+    Path(object_id): Path<ObjectID>,
+    Query(parameters): Query<ObjectHistoryQueryParameters>,
+    accept: AcceptFormat,
+    State(state): State<S>,
+) -> Result<(HeaderMap, ResponseContent<Vec<Object>, Vec<JsonObject>>)> {
+    let limit = parameters.limit();
+
+    let start_object = match parameters.start {
+        Some(version) => match state.get_object_by_key(&object_id, version)? {
+            Some(object) => Some(object),
+            None if state
+                .get_object(&object_id)?
+                .is_some_and(|latest| latest.version() > version) =>
+            {
+                None
+            }
+            None => return Err(ObjectNotFoundError::new_with_version(object_id, version).into()),
+        },
+        None => Some(
+            state
+                .get_object(&object_id)?
+                .ok_or_else(|| ObjectNotFoundError::new(object_id))?,
+        ),
+    };
+
+    let mut history = Vec::new();
+    let mut cursor = None;
+    let mut next_object = start_object;
+    while let Some(object) = next_object.take() {
+        let previous_version = previous_object_version(&state, &object)?;
+        history.push(object);
+
+        let Some(previous_version) = previous_version else {
+            break;
+        };
+        // The previous version may have been pruned already, in which case the
+        // history ends here and no cursor is returned.
+        let Some(previous_object) = state.get_object_by_key(&object_id, previous_version)? else {
+            break;
+        };
+        if history.len() >= limit {
+            cursor = Some(previous_version);
+            break;
+        }
+        next_object = Some(previous_object);
+    }
+
+    let mut headers = HeaderMap::new();
+    if let Some(cursor) = cursor {
+        headers.insert(
+            X_IOTA_CURSOR,
+            cursor.value().to_string().try_into().unwrap(),
+        );
+    }
+
+    let history = match accept {
+        AcceptFormat::Json => {
+            ResponseContent::Json(history.iter().map(JsonObject::from_object).collect())
+        }
+        AcceptFormat::Bcs => ResponseContent::Bcs(history),
+    };
+
+    Ok((headers, history))
+}
+
+/// Looks up the version of `object` that was used as input by the
+/// transaction that produced it, if any.
+fn previous_object_version<S: ReadStore>(
+    state: &S,
+    object: &Object,
+) -> Result<Option<SequenceNumber>> {
+    let Some(effects) = state.get_transaction_effects(&object.previous_transaction)? else {
+        return Ok(None);
+    };
+
+    effects
+        .modified_at_versions()
+        .into_iter()
+        .find(|(id, _)| *id == object.id())
+        .map(|(_, version)| version)
+        .pipe(Ok)
+}
+
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+pub struct ObjectHistoryQueryParameters {
+    pub limit: Option<u32>,
+    /// The version to start the history from, inclusive. Defaults to the
+    /// latest version of the object.
+    pub start: Option<SequenceNumber>,
+}
+
+impl ObjectHistoryQueryParameters {
+    const DEFAULT_LIMIT: u32 = 50;
+    const MAX_LIMIT: u32 = 100;
+
+    pub fn limit(&self) -> usize {
+        self.limit
+            .map(|limit| limit.clamp(1, Self::MAX_LIMIT))
+            .unwrap_or(Self::DEFAULT_LIMIT) as usize
+    }
+}
+
 #[derive(Debug)]
 pub struct ObjectNotFoundError {
     object_id: ObjectID,
@@ -89,3 +213,253 @@ impl From<ObjectNotFoundError> for crate::RestError {
         Self::new(axum::http::StatusCode::NOT_FOUND, value.to_string())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use axum::response::IntoResponse;
+    use iota_types::{
+        base_types::{EpochId, IotaAddress},
+        committee::Committee,
+        digests::{
+            CheckpointContentsDigest, CheckpointDigest, TransactionDigest, TransactionEventsDigest,
+        },
+        effects::{TransactionEffects, TransactionEvents},
+        messages_checkpoint::{
+            CheckpointContents, CheckpointSequenceNumber, FullCheckpointContents,
+            VerifiedCheckpoint,
+        },
+        storage::{error::Result as StorageResult, ObjectStore},
+        transaction::VerifiedTransaction,
+    };
+    use simulacrum::Simulacrum;
+
+    use super::*;
+
+    /// Executes `count` transfers and returns the id of the gas coin used by
+    /// the last one, together with the number of versions it has.
+    fn simulacrum_with_transfers(count: usize) -> (Simulacrum, ObjectID, usize) {
+        let mut sim = Simulacrum::new();
+        let mut gas_ids = Vec::new();
+        for _ in 0..count {
+            let (transaction, _) = sim.transfer_txn(IotaAddress::random_for_testing_only());
+            let (effects, error) = sim.execute_transaction(transaction).unwrap();
+            assert!(error.is_none(), "{error:?}");
+            gas_ids.push(effects.gas_object().0.0);
+        }
+        sim.create_checkpoint();
+
+        let gas_id = *gas_ids.last().unwrap();
+        // Every transaction paying with the coin created a new version of it.
+        let versions = 1 + gas_ids.iter().filter(|id| **id == gas_id).count();
+        (sim, gas_id, versions)
+    }
+
+    async fn object_history(
+        state: impl ReadStore,
+        object_id: ObjectID,
+        start: Option<SequenceNumber>,
+        limit: u32,
+    ) -> Result<(Vec<Object>, Option<SequenceNumber>)> {
+        let (headers, history) = get_object_history(
+            Path(object_id),
+            Query(ObjectHistoryQueryParameters {
+                limit: Some(limit),
+                start,
+            }),
+            AcceptFormat::Bcs,
+            State(state),
+        )
+        .await?;
+        let ResponseContent::Bcs(history) = history else {
+            panic!("expected a BCS response");
+        };
+        let cursor = headers
+            .get(X_IOTA_CURSOR)
+            .map(|cursor| SequenceNumber::from_u64(cursor.to_str().unwrap().parse().unwrap()));
+        Ok((history, cursor))
+    }
+
+    #[tokio::test]
+    async fn object_history_pages_through_all_versions() {
+        let (sim, gas_id, versions) = simulacrum_with_transfers(3);
+
+        let (full_history, cursor) = object_history(&sim, gas_id, None, 100).await.unwrap();
+        assert_eq!(full_history.len(), versions);
+        assert!(cursor.is_none());
+        assert_eq!(full_history[0], sim.get_object(&gas_id).unwrap().unwrap());
+        assert!(
+            full_history
+                .windows(2)
+                .all(|pair| pair[0].version() > pair[1].version())
+        );
+
+        let mut paged_history = Vec::new();
+        let mut start = None;
+        loop {
+            let (page, cursor) = object_history(&sim, gas_id, start, 1).await.unwrap();
+            assert_eq!(page.len(), 1);
+            paged_history.extend(page);
+            if cursor.is_none() {
+                break;
+            }
+            start = cursor;
+        }
+        assert_eq!(paged_history, full_history);
+
+        let response = object_history(&sim, ObjectID::random(), None, 1)
+            .await
+            .unwrap_err()
+            .into_response();
+        assert_eq!(response.status(), axum::http::StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn object_history_ends_at_pruned_versions() {
+        let (sim, gas_id, versions) = simulacrum_with_transfers(3);
+        assert!(
+            versions >= 3,
+            "the gas coin should have at least 3 versions"
+        );
+        let (full_history, _) = object_history(&sim, gas_id, None, 100).await.unwrap();
+        let oldest_version = full_history.last().unwrap().version();
+
+        // Without pruning, a page ending right before the oldest version points to
+        // it.
+        let (page, cursor) = object_history(&sim, gas_id, None, versions as u32 - 1)
+            .await
+            .unwrap();
+        assert_eq!(page, full_history[..versions - 1]);
+        assert_eq!(cursor, Some(oldest_version));
+
+        // Once the oldest version is pruned, no cursor is returned for it ...
+        let pruned = PrunedStore {
+            inner: &sim,
+            pruned_below: full_history[versions - 2].version(),
+        };
+        let (page, cursor) = object_history(&pruned, gas_id, None, versions as u32 - 1)
+            .await
+            .unwrap();
+        assert_eq!(page, full_history[..versions - 1]);
+        assert!(cursor.is_none());
+
+        // ... and a cursor handed out before pruning yields an empty page.
+        let (page, cursor) = object_history(&pruned, gas_id, Some(oldest_version), 1)
+            .await
+            .unwrap();
+        assert!(page.is_empty());
+        assert!(cursor.is_none());
+    }
+
+    /// Hides the object versions below `pruned_below`, as if they were pruned.
+    struct PrunedStore<'a> {
+        inner: &'a Simulacrum,
+        pruned_below: SequenceNumber,
+    }
+
+    impl ObjectStore for PrunedStore<'_> {
+        fn get_object(&self, object_id: &ObjectID) -> StorageResult<Option<Object>> {
+            self.inner.get_object(object_id)
+        }
+
+        fn get_object_by_key(
+            &self,
+            object_id: &ObjectID,
+            version: SequenceNumber,
+        ) -> StorageResult<Option<Object>> {
+            if version < self.pruned_below {
+                return Ok(None);
+            }
+            self.inner.get_object_by_key(object_id, version)
+        }
+    }
+
+    impl ReadStore for PrunedStore<'_> {
+        fn get_committee(&self, epoch: EpochId) -> StorageResult<Option<Arc<Committee>>> {
+            self.inner.get_committee(epoch)
+        }
+
+        fn get_latest_checkpoint(&self) -> StorageResult<VerifiedCheckpoint> {
+            self.inner.get_latest_checkpoint()
+        }
+
+        fn get_highest_verified_checkpoint(&self) -> StorageResult<VerifiedCheckpoint> {
+            self.inner.get_highest_verified_checkpoint()
+        }
+
+        fn get_highest_synced_checkpoint(&self) -> StorageResult<VerifiedCheckpoint> {
+            self.inner.get_highest_synced_checkpoint()
+        }
+
+        fn get_lowest_available_checkpoint(&self) -> StorageResult<CheckpointSequenceNumber> {
+            self.inner.get_lowest_available_checkpoint()
+        }
+
+        fn get_checkpoint_by_digest(
+            &self,
+            digest: &CheckpointDigest,
+        ) -> StorageResult<Option<VerifiedCheckpoint>> {
+            self.inner.get_checkpoint_by_digest(digest)
+        }
+
+        fn get_checkpoint_by_sequence_number(
+            &self,
+            sequence_number: CheckpointSequenceNumber,
+        ) -> StorageResult<Option<VerifiedCheckpoint>> {
+            self.inner
+                .get_checkpoint_by_sequence_number(sequence_number)
+        }
+
+        fn get_checkpoint_contents_by_digest(
+            &self,
+            digest: &CheckpointContentsDigest,
+        ) -> StorageResult<Option<CheckpointContents>> {
+            self.inner.get_checkpoint_contents_by_digest(digest)
+        }
+
+        fn get_checkpoint_contents_by_sequence_number(
+            &self,
+            sequence_number: CheckpointSequenceNumber,
+        ) -> StorageResult<Option<CheckpointContents>> {
+            self.inner
+                .get_checkpoint_contents_by_sequence_number(sequence_number)
+        }
+
+        fn get_transaction(
+            &self,
+            tx_digest: &TransactionDigest,
+        ) -> StorageResult<Option<Arc<VerifiedTransaction>>> {
+            self.inner.get_transaction(tx_digest)
+        }
+
+        fn get_transaction_effects(
+            &self,
+            tx_digest: &TransactionDigest,
+        ) -> StorageResult<Option<TransactionEffects>> {
+            self.inner.get_transaction_effects(tx_digest)
+        }
+
+        fn get_events(
+            &self,
+            event_digest: &TransactionEventsDigest,
+        ) -> StorageResult<Option<TransactionEvents>> {
+            self.inner.get_events(event_digest)
+        }
+
+        fn get_full_checkpoint_contents_by_sequence_number(
+            &self,
+            sequence_number: CheckpointSequenceNumber,
+        ) -> StorageResult<Option<FullCheckpointContents>> {
+            self.inner
+                .get_full_checkpoint_contents_by_sequence_number(sequence_number)
+        }
+
+        fn get_full_checkpoint_contents(
+            &self,
+            digest: &CheckpointContentsDigest,
+        ) -> StorageResult<Option<FullCheckpointContents>> {
+            self.inner.get_full_checkpoint_contents(digest)
+        }
+    }
+}
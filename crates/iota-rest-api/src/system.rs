@@ -0,0 +1,144 @@
+// Copyright (c) 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use axum::extract::{Path, State};
+use iota_types::{
+    base_types::EpochId,
+    committee::Committee,
+    iota_system_state::{
+        get_iota_system_state, iota_system_state_summary::IotaSystemStateSummary, IotaSystemState,
+        IotaSystemStateTrait,
+    },
+    storage::ReadStore,
+};
+use tap::Pipe;
+
+use crate::{accept::AcceptFormat, response::ResponseContent, Result};
+
+pub const GET_SYSTEM_STATE_PATH: &str = "/system";
+
+/// Returns the current system state. JSON responses carry the
+/// [`IotaSystemStateSummary`], BCS responses the raw [`IotaSystemState`].
+pub async fn get_system_state<S: ReadStore>(
+    accept: AcceptFormat,
+    State(state): State<S>,
+) -> Result<ResponseContent<IotaSystemState, IotaSystemStateSummary>> {
+    let system_state = get_iota_system_state(&state)?;
+
+    match accept {
+        AcceptFormat::Json => ResponseContent::Json(system_state.into_iota_system_state_summary()),
+        AcceptFormat::Bcs => ResponseContent::Bcs(system_state),
+    }
+    .pipe(Ok)
+}
+
+pub const GET_CURRENT_COMMITTEE_PATH: &str = "/system/committee";
+
+pub async fn get_current_committee<S: ReadStore>(
+    accept: AcceptFormat,
+    State(state): State<S>,
+) -> Result<ResponseContent<Committee>> {
+    let current_epoch = state.get_latest_epoch_id()?;
+    get_committee_for_epoch(&state, current_epoch, accept)
+}
+
+pub const GET_COMMITTEE_PATH: &str = "/system/committee/:epoch";
+
+pub async fn get_committee<S: ReadStore>(
+    Path(epoch): Path<EpochId>,
+    accept: AcceptFormat,
+    State(state): State<S>,
+) -> Result<ResponseContent<Committee>> {
+    get_committee_for_epoch(&state, epoch, accept)
+}
+
+fn get_committee_for_epoch<S: ReadStore>(
+    state: &S,
+    epoch: EpochId,
+    accept: AcceptFormat,
+) -> Result<ResponseContent<Committee>> {
+    let committee = state
+        .get_committee(epoch)?
+        .ok_or(CommitteeNotFoundError(epoch))?
+        .as_ref()
+        .clone();
+
+    match accept {
+        AcceptFormat::Json => ResponseContent::Json(committee),
+        AcceptFormat::Bcs => ResponseContent::Bcs(committee),
+    }
+    .pipe(Ok)
+}
+
+#[derive(Debug)]
+pub struct CommitteeNotFoundError(EpochId);
+
+impl std::fmt::Display for CommitteeNotFoundError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Committee for epoch {} not found", self.0)
+    }
+}
+
+impl std::error::Error for CommitteeNotFoundError {}
+
+impl From<CommitteeNotFoundError> for crate::RestError {
+    fn from(value: CommitteeNotFoundError) -> Self {
+        Self::new(axum::http::StatusCode::NOT_FOUND, value.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::response::IntoResponse;
+    use simulacrum::Simulacrum;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn get_system_state_and_committees() {
+        let sim = Simulacrum::new();
+        let expected_system_state = get_iota_system_state(&sim).unwrap();
+
+        let ResponseContent::Json(summary) = get_system_state(AcceptFormat::Json, State(&sim))
+            .await
+            .unwrap()
+        else {
+            panic!("expected a JSON response");
+        };
+        assert_eq!(summary.epoch, expected_system_state.epoch());
+        let ResponseContent::Bcs(system_state) = get_system_state(AcceptFormat::Bcs, State(&sim))
+            .await
+            .unwrap()
+        else {
+            panic!("expected a BCS response");
+        };
+        assert_eq!(system_state.epoch(), expected_system_state.epoch());
+        assert_eq!(
+            system_state.protocol_version(),
+            expected_system_state.protocol_version()
+        );
+
+        let expected_committee = sim.get_committee(0).unwrap().unwrap();
+        let ResponseContent::Bcs(committee) = get_current_committee(AcceptFormat::Bcs, State(&sim))
+            .await
+            .unwrap()
+        else {
+            panic!("expected a BCS response");
+        };
+        assert_eq!(committee, *expected_committee);
+        let ResponseContent::Bcs(committee) =
+            get_committee(Path(0), AcceptFormat::Bcs, State(&sim))
+                .await
+                .unwrap()
+        else {
+            panic!("expected a BCS response");
+        };
+        assert_eq!(committee, *expected_committee);
+
+        let response = get_committee(Path(1), AcceptFormat::Bcs, State(&sim))
+            .await
+            .unwrap_err()
+            .into_response();
+        assert_eq!(response.status(), axum::http::StatusCode::NOT_FOUND);
+    }
+}
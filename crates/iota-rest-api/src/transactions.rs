@@ -0,0 +1,280 @@
+// Copyright (c) 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+};
+use iota_types::{
+    digests::TransactionDigest,
+    effects::{TransactionEffects, TransactionEffectsAPI, TransactionEvents},
+    storage::ReadStore,
+    transaction::SenderSignedData,
+};
+use tap::Pipe;
+
+use crate::{accept::AcceptFormat, response::ResponseContent, RestError, Result};
+
+pub const GET_TRANSACTION_PATH: &str = "/transactions/:transaction";
+
+pub async fn get_transaction<S: ReadStore>(
+    Path(transaction_digest): Path<TransactionDigest>,
+    accept: AcceptFormat,
+    State(state): State<S>,
+) -> Result<ResponseContent<TransactionResponse>> {
+    let transaction = state
+        .get_transaction(&transaction_digest)?
+        .ok_or(TransactionNotFoundError(transaction_digest))?;
+    let effects = state
+        .get_transaction_effects(&transaction_digest)?
+        .ok_or(TransactionNotFoundError(transaction_digest))?;
+    // The events of an executed transaction are stored along with its effects,
+    // so missing events point to a corrupted store rather than an unknown
+    // transaction.
+    let events = if let Some(events_digest) = effects.events_digest() {
+        Some(state.get_events(events_digest)?.ok_or_else(|| {
+            RestError::new(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Events {events_digest} of transaction {transaction_digest} are missing"),
+            )
+        })?)
+    } else {
+        None
+    };
+
+    let response = TransactionResponse {
+        digest: transaction_digest,
+        transaction: transaction.data().clone(),
+        effects,
+        events,
+    };
+
+    match accept {
+        AcceptFormat::Json => ResponseContent::Json(response),
+        AcceptFormat::Bcs => ResponseContent::Bcs(response),
+    }
+    .pipe(Ok)
+}
+
+/// An executed transaction together with its effects and the events it
+/// emitted.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct TransactionResponse {
+    pub digest: TransactionDigest,
+    pub transaction: SenderSignedData,
+    pub effects: TransactionEffects,
+    pub events: Option<TransactionEvents>,
+}
+
+#[derive(Debug)]
+pub struct TransactionNotFoundError(TransactionDigest);
+
+impl std::fmt::Display for TransactionNotFoundError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Transaction {} not found", self.0)
+    }
+}
+
+impl std::error::Error for TransactionNotFoundError {}
+
+impl From<TransactionNotFoundError> for crate::RestError {
+    fn from(value: TransactionNotFoundError) -> Self {
+        Self::new(StatusCode::NOT_FOUND, value.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use axum::response::IntoResponse;
+    use iota_types::{
+        base_types::{EpochId, IotaAddress, ObjectID, SequenceNumber},
+        committee::Committee,
+        digests::{CheckpointContentsDigest, CheckpointDigest, TransactionEventsDigest},
+        messages_checkpoint::{
+            CheckpointContents, CheckpointSequenceNumber, FullCheckpointContents,
+            VerifiedCheckpoint,
+        },
+        object::Object,
+        storage::{error::Result as StorageResult, ObjectStore},
+        transaction::VerifiedTransaction,
+    };
+    use simulacrum::Simulacrum;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn get_transaction_returns_transaction_and_effects() {
+        let mut sim = Simulacrum::new();
+        let (transaction, _) = sim.transfer_txn(IotaAddress::random_for_testing_only());
+        let digest = *transaction.digest();
+        let (effects, _) = sim.execute_transaction(transaction.clone()).unwrap();
+        sim.create_checkpoint();
+
+        let ResponseContent::Bcs(response) =
+            get_transaction(Path(digest), AcceptFormat::Bcs, State(&sim))
+                .await
+                .unwrap()
+        else {
+            panic!("expected a BCS response");
+        };
+        assert_eq!(response.digest, digest);
+        assert_eq!(&response.transaction, transaction.data());
+        assert_eq!(response.effects, effects);
+        assert_eq!(response.events.is_some(), effects.events_digest().is_some());
+
+        let response = get_transaction(
+            Path(TransactionDigest::random()),
+            AcceptFormat::Bcs,
+            State(&sim),
+        )
+        .await
+        .unwrap_err()
+        .into_response();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn get_transaction_with_missing_events_is_an_internal_error() {
+        let mut sim = Simulacrum::new();
+        // The epoch change transaction emits the epoch info event.
+        sim.advance_epoch(false);
+        let checkpoint = sim.get_latest_checkpoint().unwrap();
+        let digest = sim
+            .get_checkpoint_contents_by_digest(&checkpoint.content_digest)
+            .unwrap()
+            .unwrap()
+            .iter()
+            .last()
+            .unwrap()
+            .transaction;
+
+        let ResponseContent::Bcs(response) =
+            get_transaction(Path(digest), AcceptFormat::Bcs, State(&sim))
+                .await
+                .unwrap()
+        else {
+            panic!("expected a BCS response");
+        };
+        assert!(response.events.is_some());
+
+        // A known transaction whose events are missing is not reported as not found.
+        let response = get_transaction(
+            Path(digest),
+            AcceptFormat::Bcs,
+            State(MissingEventsStore { inner: &sim }),
+        )
+        .await
+        .unwrap_err()
+        .into_response();
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
+    /// Hides all events, as if they were lost from the store.
+    struct MissingEventsStore<'a> {
+        inner: &'a Simulacrum,
+    }
+
+    impl ObjectStore for MissingEventsStore<'_> {
+        fn get_object(&self, object_id: &ObjectID) -> StorageResult<Option<Object>> {
+            self.inner.get_object(object_id)
+        }
+
+        fn get_object_by_key(
+            &self,
+            object_id: &ObjectID,
+            version: SequenceNumber,
+        ) -> StorageResult<Option<Object>> {
+            self.inner.get_object_by_key(object_id, version)
+        }
+    }
+
+    impl ReadStore for MissingEventsStore<'_> {
+        fn get_committee(&self, epoch: EpochId) -> StorageResult<Option<Arc<Committee>>> {
+            self.inner.get_committee(epoch)
+        }
+
+        fn get_latest_checkpoint(&self) -> StorageResult<VerifiedCheckpoint> {
+            self.inner.get_latest_checkpoint()
+        }
+
+        fn get_highest_verified_checkpoint(&self) -> StorageResult<VerifiedCheckpoint> {
+            self.inner.get_highest_verified_checkpoint()
+        }
+
+        fn get_highest_synced_checkpoint(&self) -> StorageResult<VerifiedCheckpoint> {
+            self.inner.get_highest_synced_checkpoint()
+        }
+
+        fn get_lowest_available_checkpoint(&self) -> StorageResult<CheckpointSequenceNumber> {
+            self.inner.get_lowest_available_checkpoint()
+        }
+
+        fn get_checkpoint_by_digest(
+            &self,
+            digest: &CheckpointDigest,
+        ) -> StorageResult<Option<VerifiedCheckpoint>> {
+            self.inner.get_checkpoint_by_digest(digest)
+        }
+
+        fn get_checkpoint_by_sequence_number(
+            &self,
+            sequence_number: CheckpointSequenceNumber,
+        ) -> StorageResult<Option<VerifiedCheckpoint>> {
+            self.inner
+                .get_checkpoint_by_sequence_number(sequence_number)
+        }
+
+        fn get_checkpoint_contents_by_digest(
+            &self,
+            digest: &CheckpointContentsDigest,
+        ) -> StorageResult<Option<CheckpointContents>> {
+            self.inner.get_checkpoint_contents_by_digest(digest)
+        }
+
+        fn get_checkpoint_contents_by_sequence_number(
+            &self,
+            sequence_number: CheckpointSequenceNumber,
+        ) -> StorageResult<Option<CheckpointContents>> {
+            self.inner
+                .get_checkpoint_contents_by_sequence_number(sequence_number)
+        }
+
+        fn get_transaction(
+            &self,
+            tx_digest: &TransactionDigest,
+        ) -> StorageResult<Option<Arc<VerifiedTransaction>>> {
+            self.inner.get_transaction(tx_digest)
+        }
+
+        fn get_transaction_effects(
+            &self,
+            tx_digest: &TransactionDigest,
+        ) -> StorageResult<Option<TransactionEffects>> {
+            self.inner.get_transaction_effects(tx_digest)
+        }
+
+        fn get_events(
+            &self,
+            _event_digest: &TransactionEventsDigest,
+        ) -> StorageResult<Option<TransactionEvents>> {
+            Ok(None)
+        }
+
+        fn get_full_checkpoint_contents_by_sequence_number(
+            &self,
+            sequence_number: CheckpointSequenceNumber,
+        ) -> StorageResult<Option<FullCheckpointContents>> {
+            self.inner
+                .get_full_checkpoint_contents_by_sequence_number(sequence_number)
+        }
+
+        fn get_full_checkpoint_contents(
+            &self,
+            digest: &CheckpointContentsDigest,
+        ) -> StorageResult<Option<FullCheckpointContents>> {
+            self.inner.get_full_checkpoint_contents(digest)
+        }
+    }
+}
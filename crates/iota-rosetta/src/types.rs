@@ -341,6 +341,10 @@ impl From<IotaPublicKey> for PublicKey {
                 hex_bytes: Hex::from_bytes(&k.0),
                 curve_type: CurveType::ZkLogin, // inaccurate but added for completeness.
             },
+            IotaPublicKey::Passkey(k) => PublicKey {
+                hex_bytes: Hex::from_bytes(&k.0),
+                curve_type: CurveType::Passkey,
+            },
         }
     }
 }
@@ -362,6 +366,7 @@ pub enum CurveType {
     Edwards25519,
     Secp256r1,
     ZkLogin,
+    Passkey,
 }
 
 impl From<CurveType> for SignatureScheme {
@@ -371,6 +376,7 @@ impl From<CurveType> for SignatureScheme {
             CurveType::Edwards25519 => SignatureScheme::ED25519,
             CurveType::Secp256r1 => SignatureScheme::Secp256r1,
             CurveType::ZkLogin => SignatureScheme::ZkLoginAuthenticator,
+            CurveType::Passkey => SignatureScheme::PasskeyAuthenticator,
         }
     }
 }
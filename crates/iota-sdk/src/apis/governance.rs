@@ -5,7 +5,7 @@
 use std::sync::Arc;
 
 use iota_json_rpc_api::GovernanceReadApiClient;
-use iota_json_rpc_types::{DelegatedStake, IotaCommittee};
+use iota_json_rpc_types::{DelegatedStake, DelegatedTimelockedStake, IotaCommittee, ValidatorApys};
 use iota_types::{
     base_types::IotaAddress, iota_serde::BigInt,
     iota_system_state::iota_system_state_summary::IotaSystemStateSummary,
@@ -30,6 +30,15 @@ impl GovernanceApi {
         Ok(self.api.http.get_stakes(owner).await?)
     }
 
+    /// Return a list of [DelegatedTimelockedStake] objects for the given
+    /// address, or an error upon failure.
+    pub async fn get_timelocked_stakes(
+        &self,
+        owner: IotaAddress,
+    ) -> IotaRpcResult<Vec<DelegatedTimelockedStake>> {
+        Ok(self.api.http.get_timelocked_stakes(owner).await?)
+    }
+
     /// Return the [IotaCommittee] information for the given `epoch`, or an
     /// error upon failure.
     ///
@@ -71,4 +80,10 @@ impl GovernanceApi {
     pub async fn get_reference_gas_price(&self) -> IotaRpcResult<u64> {
         Ok(*self.api.http.get_reference_gas_price().await?)
     }
+
+    /// Return the [ValidatorApys] of the active validators for the current
+    /// epoch, or an error upon failure.
+    pub async fn get_validators_apy(&self) -> IotaRpcResult<ValidatorApys> {
+        Ok(self.api.http.get_validators_apy().await?)
+    }
 }
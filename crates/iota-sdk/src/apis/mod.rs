@@ -7,8 +7,13 @@ mod event;
 mod governance;
 mod quorum_driver;
 mod read;
+mod stardust;
 
 pub use self::{
-    coin_read::CoinReadApi, event::EventApi, governance::GovernanceApi,
-    quorum_driver::QuorumDriverApi, read::ReadApi,
+    coin_read::CoinReadApi,
+    event::EventApi,
+    governance::GovernanceApi,
+    quorum_driver::QuorumDriverApi,
+    read::ReadApi,
+    stardust::{claim_outputs_pt, StardustApi, StardustOutput, StardustOutputKind, UnlockStatus},
 };
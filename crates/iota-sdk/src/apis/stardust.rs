@@ -0,0 +1,474 @@
+// Copyright (c) 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    fmt::{Display, Formatter},
+    str::FromStr,
+    sync::Arc,
+};
+
+use iota_json_rpc_api::{GovernanceReadApiClient, IndexerApiClient, ReadApiClient};
+use iota_json_rpc_types::{
+    IotaObjectData, IotaObjectDataFilter, IotaObjectDataOptions, IotaObjectResponseQuery,
+};
+use iota_types::{
+    balance::BALANCE_MODULE_NAME,
+    base_types::{IotaAddress, ObjectID, ObjectRef},
+    coin::COIN_MODULE_NAME,
+    object::Owner,
+    programmable_transaction_builder::ProgrammableTransactionBuilder,
+    stardust::output::{
+        unlock_conditions::{
+            ExpirationUnlockCondition, StorageDepositReturnUnlockCondition, TimelockUnlockCondition,
+        },
+        AliasOutput, BasicOutput, NftOutput, ALIAS_OUTPUT_MODULE_NAME, ALIAS_OUTPUT_STRUCT_NAME,
+        BASIC_OUTPUT_MODULE_NAME, BASIC_OUTPUT_STRUCT_NAME, NFT_OUTPUT_MODULE_NAME,
+        NFT_OUTPUT_STRUCT_NAME,
+    },
+    transaction::{Argument, ObjectArg, ProgrammableTransaction},
+    TypeTag, IOTA_FRAMEWORK_PACKAGE_ID, STARDUST_ADDRESS, STARDUST_PACKAGE_ID,
+};
+use move_core_types::{ident_str, identifier::IdentStr, language_storage::StructTag};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::{Error, IotaRpcResult},
+    RpcClient,
+};
+
+const BAG_MODULE_NAME: &IdentStr = ident_str!("bag");
+const UTILITIES_MODULE_NAME: &IdentStr = ident_str!("utilities");
+const EXTRACT_ASSETS_FUN_NAME: &IdentStr = ident_str!("extract_assets");
+const EXTRACT_AND_SEND_TO_FUN_NAME: &IdentStr = ident_str!("extract_and_send_to");
+const DESTROY_EMPTY_FUN_NAME: &IdentStr = ident_str!("destroy_empty");
+const DESTROY_ZERO_FUN_NAME: &IdentStr = ident_str!("destroy_zero");
+const JOIN_FUN_NAME: &IdentStr = ident_str!("join");
+const FROM_BALANCE_FUN_NAME: &IdentStr = ident_str!("from_balance");
+
+/// Stardust API provides functions to find and claim the outputs migrated
+/// from the Stardust ledger.
+#[derive(Debug, Clone)]
+pub struct StardustApi {
+    api: Arc<RpcClient>,
+}
+
+impl StardustApi {
+    pub(crate) fn new(api: Arc<RpcClient>) -> Self {
+        Self { api }
+    }
+
+    /// Return all [StardustOutput]s owned by the given address, or an error
+    /// upon failure.
+    ///
+    /// Outputs with an expiration unlock condition are shared objects and are
+    /// therefore not returned. They can be fetched with [Self::get_output].
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use std::str::FromStr;
+    ///
+    /// use iota_sdk::IotaClientBuilder;
+    /// use iota_types::base_types::IotaAddress;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), anyhow::Error> {
+    ///     let iota = IotaClientBuilder::default().build_localnet().await?;
+    ///     let address = IotaAddress::from_str("0x0000....0000")?;
+    ///     let timestamp_ms = iota.stardust_api().get_unlock_timestamp_ms().await?;
+    ///     for output in iota.stardust_api().get_owned_outputs(address).await? {
+    ///         println!("{:?}", output.unlock_status(address, timestamp_ms));
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn get_owned_outputs(
+        &self,
+        owner: IotaAddress,
+    ) -> IotaRpcResult<Vec<StardustOutput>> {
+        let filter = IotaObjectDataFilter::MatchAny(
+            [
+                (BASIC_OUTPUT_MODULE_NAME, BASIC_OUTPUT_STRUCT_NAME),
+                (NFT_OUTPUT_MODULE_NAME, NFT_OUTPUT_STRUCT_NAME),
+                (ALIAS_OUTPUT_MODULE_NAME, ALIAS_OUTPUT_STRUCT_NAME),
+            ]
+            .into_iter()
+            // Without type parameters, the filter matches the outputs of any coin type.
+            .map(|(module, name)| {
+                IotaObjectDataFilter::StructType(StructTag {
+                    address: STARDUST_ADDRESS,
+                    module: module.to_owned(),
+                    name: name.to_owned(),
+                    type_params: vec![],
+                })
+            })
+            .collect(),
+        );
+        let query = IotaObjectResponseQuery::new(
+            Some(filter),
+            Some(IotaObjectDataOptions::new().with_owner().with_bcs()),
+        );
+
+        let mut outputs = vec![];
+        let mut cursor = None;
+        loop {
+            let page = self
+                .api
+                .http
+                .get_owned_objects(owner, Some(query.clone()), cursor, None)
+                .await?;
+            for response in page.data {
+                let object = response.into_object().map_err(|e| {
+                    Error::Data(format!("Can't get owned output of {owner}: {e:?}"))
+                })?;
+                outputs.push(self.to_output(object).await?);
+            }
+            if !page.has_next_page {
+                break;
+            }
+            cursor = page.next_cursor;
+        }
+        Ok(outputs)
+    }
+
+    /// Return the [StardustOutput] with the given [ObjectID], or an error if
+    /// the object can't be fetched or is not a Stardust output.
+    pub async fn get_output(&self, object_id: ObjectID) -> IotaRpcResult<StardustOutput> {
+        let object = self
+            .api
+            .http
+            .get_object(
+                object_id,
+                Some(IotaObjectDataOptions::new().with_owner().with_bcs()),
+            )
+            .await?
+            .into_object()
+            .map_err(|e| Error::Data(format!("Can't get output {object_id}: {e:?}")))?;
+        self.to_output(object).await
+    }
+
+    /// Return the timestamp in milliseconds the unlock conditions are
+    /// evaluated against when a transaction is executed, or an error upon
+    /// failure.
+    ///
+    /// The Move unlock conditions compare their unix time with the start of
+    /// the current epoch, so a timelock only unlocks in the first epoch
+    /// starting after it.
+    pub async fn get_unlock_timestamp_ms(&self) -> IotaRpcResult<u64> {
+        Ok(self
+            .api
+            .http
+            .get_latest_iota_system_state()
+            .await?
+            .epoch_start_timestamp_ms)
+    }
+
+    async fn to_output(&self, object: IotaObjectData) -> IotaRpcResult<StardustOutput> {
+        let object_id = object.object_id;
+        let object_ref = object.object_ref();
+        let owner = object
+            .owner
+            .ok_or_else(|| Error::Data(format!("Missing owner of output {object_id}")))?;
+        let move_object = object
+            .bcs
+            .and_then(|bcs| bcs.try_into_move())
+            .ok_or_else(|| Error::Data(format!("Object {object_id} is not a Move object")))?;
+
+        let type_ = move_object.type_;
+        let not_an_output = || {
+            Error::Data(format!(
+                "Object {object_id} of type {type_} is not a Stardust output"
+            ))
+        };
+        let coin_type = type_
+            .type_params
+            .first()
+            .cloned()
+            .filter(|_| type_.address == STARDUST_ADDRESS)
+            .ok_or_else(not_an_output)?;
+        let bytes = &move_object.bcs_bytes;
+        let is_type = |module: &IdentStr, name: &IdentStr| {
+            type_.module.as_ident_str() == module && type_.name.as_ident_str() == name
+        };
+        let output = if is_type(BASIC_OUTPUT_MODULE_NAME, BASIC_OUTPUT_STRUCT_NAME) {
+            StardustOutputKind::Basic(bcs::from_bytes(bytes)?)
+        } else if is_type(NFT_OUTPUT_MODULE_NAME, NFT_OUTPUT_STRUCT_NAME) {
+            StardustOutputKind::Nft(bcs::from_bytes(bytes)?)
+        } else if is_type(ALIAS_OUTPUT_MODULE_NAME, ALIAS_OUTPUT_STRUCT_NAME) {
+            StardustOutputKind::Alias(bcs::from_bytes(bytes)?)
+        } else {
+            return Err(not_an_output());
+        };
+
+        let bag = match &output {
+            StardustOutputKind::Basic(output) => &output.native_tokens,
+            StardustOutputKind::Nft(output) => &output.native_tokens,
+            StardustOutputKind::Alias(output) => &output.native_tokens,
+        };
+        let mut native_tokens = vec![];
+        if bag.size > 0 {
+            let mut cursor = None;
+            loop {
+                let page = self
+                    .api
+                    .http
+                    .get_dynamic_fields(*bag.id.object_id(), cursor, None)
+                    .await?;
+                for field in page.data {
+                    // The bag is keyed by the type name of the native tokens, which has no
+                    // leading `0x`.
+                    let key = bcs::from_bytes::<String>(&field.bcs_name)?;
+                    let type_tag =
+                        TypeTag::from_str(&format!("0x{}", key.trim_start_matches("0x"))).map_err(
+                            |e| Error::Data(format!("Invalid native token type {key}: {e}")),
+                        )?;
+                    native_tokens.push(type_tag);
+                }
+                if !page.has_next_page {
+                    break;
+                }
+                cursor = page.next_cursor;
+            }
+        }
+
+        Ok(StardustOutput {
+            object_ref,
+            owner,
+            coin_type,
+            output,
+            native_tokens,
+        })
+    }
+}
+
+/// The Rust representation of a Stardust output object.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum StardustOutputKind {
+    Basic(BasicOutput),
+    Nft(NftOutput),
+    Alias(AliasOutput),
+}
+
+/// A Stardust output object together with the data needed to claim it.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct StardustOutput {
+    pub object_ref: ObjectRef,
+    pub owner: Owner,
+    /// The coin type of the output balance, e.g. `0x2::iota::IOTA`.
+    pub coin_type: TypeTag,
+    pub output: StardustOutputKind,
+    /// The types of the native tokens held by the output.
+    pub native_tokens: Vec<TypeTag>,
+}
+
+/// Whether an address can claim a [StardustOutput] at a given time.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum UnlockStatus {
+    /// The output can be claimed.
+    Unlocked,
+    /// The output can't be claimed before the given unix time in seconds.
+    Timelocked { unix_time: u32 },
+    /// The output can only be claimed by the given address.
+    UnlockableBy(IotaAddress),
+}
+
+impl Display for UnlockStatus {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UnlockStatus::Unlocked => write!(f, "Unlocked"),
+            UnlockStatus::Timelocked { unix_time } => write!(f, "Timelocked until {unix_time}"),
+            UnlockStatus::UnlockableBy(address) => write!(f, "Unlockable by {address}"),
+        }
+    }
+}
+
+impl StardustOutput {
+    pub fn object_id(&self) -> ObjectID {
+        self.object_ref.0
+    }
+
+    /// The name of the output type, e.g. `BasicOutput`.
+    pub fn type_name(&self) -> &'static str {
+        match self.output {
+            StardustOutputKind::Basic(_) => BASIC_OUTPUT_STRUCT_NAME.as_str(),
+            StardustOutputKind::Nft(_) => NFT_OUTPUT_STRUCT_NAME.as_str(),
+            StardustOutputKind::Alias(_) => ALIAS_OUTPUT_STRUCT_NAME.as_str(),
+        }
+    }
+
+    /// The amount of coins held by the output.
+    pub fn balance(&self) -> u64 {
+        match &self.output {
+            StardustOutputKind::Basic(output) => output.balance.value(),
+            StardustOutputKind::Nft(output) => output.balance.value(),
+            StardustOutputKind::Alias(output) => output.balance.value(),
+        }
+    }
+
+    pub fn storage_deposit_return(&self) -> Option<&StorageDepositReturnUnlockCondition> {
+        match &self.output {
+            StardustOutputKind::Basic(output) => output.storage_deposit_return.as_ref(),
+            StardustOutputKind::Nft(output) => output.storage_deposit_return.as_ref(),
+            StardustOutputKind::Alias(_) => None,
+        }
+    }
+
+    pub fn timelock(&self) -> Option<&TimelockUnlockCondition> {
+        match &self.output {
+            StardustOutputKind::Basic(output) => output.timelock.as_ref(),
+            StardustOutputKind::Nft(output) => output.timelock.as_ref(),
+            StardustOutputKind::Alias(_) => None,
+        }
+    }
+
+    pub fn expiration(&self) -> Option<&ExpirationUnlockCondition> {
+        match &self.output {
+            StardustOutputKind::Basic(output) => output.expiration.as_ref(),
+            StardustOutputKind::Nft(output) => output.expiration.as_ref(),
+            StardustOutputKind::Alias(_) => None,
+        }
+    }
+
+    /// The amount of coins the claimer receives, i.e. the balance without the
+    /// storage deposit that is returned when claiming.
+    pub fn claimable_balance(&self) -> u64 {
+        let deposit = self
+            .storage_deposit_return()
+            .map_or(0, |deposit| deposit.return_amount);
+        self.balance().saturating_sub(deposit)
+    }
+
+    /// Evaluate the unlock conditions of the output for the `claimer` at the
+    /// given timestamp in milliseconds, the same way the `stardust` package
+    /// does on execution.
+    pub fn unlock_status(&self, claimer: IotaAddress, timestamp_ms: u64) -> UnlockStatus {
+        let unlockable_by = match (&self.owner, self.expiration()) {
+            (_, Some(expiration)) => Some(expiration.can_be_unlocked_by(timestamp_ms)),
+            (Owner::AddressOwner(owner), None) => Some(*owner),
+            _ => None,
+        };
+        if let Some(address) = unlockable_by.filter(|address| *address != claimer) {
+            return UnlockStatus::UnlockableBy(address);
+        }
+        match self.timelock() {
+            Some(timelock) if timelock.is_timelocked(timestamp_ms) => UnlockStatus::Timelocked {
+                unix_time: timelock.unix_time,
+            },
+            _ => UnlockStatus::Unlocked,
+        }
+    }
+
+    fn object_arg(&self) -> ObjectArg {
+        match self.owner {
+            Owner::Shared {
+                initial_shared_version,
+            } => ObjectArg::SharedObject {
+                id: self.object_id(),
+                initial_shared_version,
+                mutable: true,
+            },
+            _ => ObjectArg::ImmOrOwnedObject(self.object_ref),
+        }
+    }
+}
+
+/// Build a [ProgrammableTransaction] claiming the given outputs.
+///
+/// The assets of every output are extracted, which returns the storage
+/// deposits to their return addresses. The native tokens, the NFTs, the
+/// aliases and the remaining balance of each coin type are sent to the
+/// `recipient`.
+pub fn claim_outputs_pt(
+    outputs: &[StardustOutput],
+    recipient: IotaAddress,
+) -> anyhow::Result<ProgrammableTransaction> {
+    let mut builder = ProgrammableTransactionBuilder::new();
+    // The joined balance and the claimable amount of every coin type.
+    let mut balances: Vec<(TypeTag, Argument, u64)> = vec![];
+    let recipient_arg = builder.pure(recipient)?;
+
+    for output in outputs {
+        let module = match output.output {
+            StardustOutputKind::Basic(_) => BASIC_OUTPUT_MODULE_NAME,
+            StardustOutputKind::Nft(_) => NFT_OUTPUT_MODULE_NAME,
+            StardustOutputKind::Alias(_) => ALIAS_OUTPUT_MODULE_NAME,
+        };
+        let output_arg = builder.obj(output.object_arg())?;
+        let Argument::Result(extracted) = builder.programmable_move_call(
+            STARDUST_PACKAGE_ID,
+            module.to_owned(),
+            EXTRACT_ASSETS_FUN_NAME.to_owned(),
+            vec![output.coin_type.clone()],
+            vec![output_arg],
+        ) else {
+            unreachable!("a move call always returns a result");
+        };
+        let balance = Argument::NestedResult(extracted, 0);
+        let mut bag = Argument::NestedResult(extracted, 1);
+        if !matches!(output.output, StardustOutputKind::Basic(_)) {
+            // The `Nft` or `Alias` object.
+            builder.transfer_arg(recipient, Argument::NestedResult(extracted, 2));
+        }
+
+        for native_token in &output.native_tokens {
+            bag = builder.programmable_move_call(
+                STARDUST_PACKAGE_ID,
+                UTILITIES_MODULE_NAME.to_owned(),
+                EXTRACT_AND_SEND_TO_FUN_NAME.to_owned(),
+                vec![native_token.clone()],
+                vec![bag, recipient_arg],
+            );
+        }
+        builder.programmable_move_call(
+            IOTA_FRAMEWORK_PACKAGE_ID,
+            BAG_MODULE_NAME.to_owned(),
+            DESTROY_EMPTY_FUN_NAME.to_owned(),
+            vec![],
+            vec![bag],
+        );
+
+        match balances
+            .iter_mut()
+            .find(|(coin_type, ..)| *coin_type == output.coin_type)
+        {
+            Some((coin_type, joined, amount)) => {
+                builder.programmable_move_call(
+                    IOTA_FRAMEWORK_PACKAGE_ID,
+                    BALANCE_MODULE_NAME.to_owned(),
+                    JOIN_FUN_NAME.to_owned(),
+                    vec![coin_type.clone()],
+                    vec![*joined, balance],
+                );
+                *amount += output.claimable_balance();
+            }
+            None => balances.push((
+                output.coin_type.clone(),
+                balance,
+                output.claimable_balance(),
+            )),
+        }
+    }
+
+    for (coin_type, balance, amount) in balances {
+        if amount == 0 {
+            builder.programmable_move_call(
+                IOTA_FRAMEWORK_PACKAGE_ID,
+                BALANCE_MODULE_NAME.to_owned(),
+                DESTROY_ZERO_FUN_NAME.to_owned(),
+                vec![coin_type],
+                vec![balance],
+            );
+        } else {
+            let coin = builder.programmable_move_call(
+                IOTA_FRAMEWORK_PACKAGE_ID,
+                COIN_MODULE_NAME.to_owned(),
+                FROM_BALANCE_FUN_NAME.to_owned(),
+                vec![coin_type],
+                vec![balance],
+            );
+            builder.transfer_arg(recipient, coin);
+        }
+    }
+    Ok(builder.finish())
+}
@@ -19,6 +19,8 @@
 //! block and submit it to the fullnode(s)
 //! * [ReadApi] - provides functions for retrieving data about different
 //! objects and transactions
+//! * [StardustApi] - provides functions to find and claim the outputs
+//! migrated from the Stardust ledger
 //! * <a href="../iota_transaction_builder/struct.TransactionBuilder.html"
 //!   title="struct
 //!   iota_transaction_builder::TransactionBuilder">TransactionBuilder</a> -
@@ -109,7 +111,7 @@ use rustls::crypto::{ring, CryptoProvider};
 use serde_json::Value;
 
 use crate::{
-    apis::{CoinReadApi, EventApi, GovernanceApi, QuorumDriverApi, ReadApi},
+    apis::{CoinReadApi, EventApi, GovernanceApi, QuorumDriverApi, ReadApi, StardustApi},
     error::{Error, IotaRpcResult},
 };
 
@@ -253,6 +255,7 @@ impl IotaClientBuilder {
         let transaction_builder = TransactionBuilder::new(read_api.clone());
         let coin_read_api = CoinReadApi::new(api.clone());
         let governance_api = GovernanceApi::new(api.clone());
+        let stardust_api = StardustApi::new(api.clone());
 
         Ok(IotaClient {
             api,
@@ -262,6 +265,7 @@ impl IotaClientBuilder {
             event_api,
             quorum_driver_api,
             governance_api,
+            stardust_api,
         })
     }
 
@@ -421,6 +425,7 @@ pub struct IotaClient {
     event_api: EventApi,
     quorum_driver_api: QuorumDriverApi,
     governance_api: GovernanceApi,
+    stardust_api: StardustApi,
 }
 
 pub(crate) struct RpcClient {
@@ -508,6 +513,11 @@ impl IotaClient {
         &self.read_api
     }
 
+    /// Returns a reference to the Stardust API.
+    pub fn stardust_api(&self) -> &StardustApi {
+        &self.stardust_api
+    }
+
     /// Returns a reference to the transaction builder API.
     pub fn transaction_builder(&self) -> &TransactionBuilder {
         &self.transaction_builder
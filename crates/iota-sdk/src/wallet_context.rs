@@ -11,7 +11,7 @@ use iota_json_rpc_types::{
     IotaObjectData, IotaObjectDataFilter, IotaObjectDataOptions, IotaObjectResponse,
     IotaObjectResponseQuery, IotaTransactionBlockResponse, IotaTransactionBlockResponseOptions,
 };
-use iota_keys::keystore::AccountKeystore;
+use iota_keys::{encryption::IOTA_KEYSTORE_PASSPHRASE_ENV, keystore::AccountKeystore};
 use iota_types::{
     base_types::{IotaAddress, ObjectID, ObjectRef},
     gas_coin::GasCoin,
@@ -36,12 +36,18 @@ impl WalletContext {
         request_timeout: Option<std::time::Duration>,
         max_concurrent_requests: Option<u64>,
     ) -> Result<Self, anyhow::Error> {
-        let config: IotaClientConfig = PersistedConfig::read(config_path).map_err(|err| {
+        let mut config: IotaClientConfig = PersistedConfig::read(config_path).map_err(|err| {
             anyhow!(
                 "Cannot open wallet config file at {:?}. Err: {err}",
                 config_path
             )
         })?;
+        // An encrypted keystore is unlocked right away if its passphrase is
+        // provided by the environment, otherwise it stays locked until
+        // `unlock_keystore` is called.
+        if let Ok(passphrase) = std::env::var(IOTA_KEYSTORE_PASSPHRASE_ENV) {
+            config.keystore.unlock(&passphrase)?;
+        }
 
         let config = config.persisted(config_path);
         let context = Self {
@@ -53,6 +59,16 @@ impl WalletContext {
         Ok(context)
     }
 
+    /// Whether the keystore is encrypted and has not been unlocked yet.
+    pub fn is_keystore_locked(&self) -> bool {
+        self.config.keystore.is_locked()
+    }
+
+    /// Unlocks an encrypted keystore with its passphrase.
+    pub fn unlock_keystore(&mut self, passphrase: &str) -> Result<(), anyhow::Error> {
+        self.config.keystore.unlock(passphrase)
+    }
+
     pub fn get_addresses(&self) -> Vec<IotaAddress> {
         self.config.keystore.addresses()
     }
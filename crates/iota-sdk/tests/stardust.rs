@@ -0,0 +1,155 @@
+// Copyright (c) 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::str::FromStr;
+
+use iota_sdk::apis::{claim_outputs_pt, StardustOutput, StardustOutputKind, UnlockStatus};
+use iota_types::{
+    balance::Balance,
+    base_types::{IotaAddress, ObjectID, SequenceNumber},
+    digests::ObjectDigest,
+    gas_coin::GAS,
+    id::UID,
+    object::Owner,
+    stardust::output::{
+        unlock_conditions::{
+            ExpirationUnlockCondition, StorageDepositReturnUnlockCondition, TimelockUnlockCondition,
+        },
+        BasicOutput, NftOutput,
+    },
+    transaction::Command,
+    TypeTag,
+};
+
+fn basic_output(balance: u64) -> BasicOutput {
+    BasicOutput {
+        id: UID::new(ObjectID::random()),
+        balance: Balance::new(balance),
+        native_tokens: Default::default(),
+        storage_deposit_return: None,
+        timelock: None,
+        expiration: None,
+        metadata: None,
+        tag: None,
+        sender: None,
+    }
+}
+
+fn stardust_output(owner: Owner, output: StardustOutputKind) -> StardustOutput {
+    StardustOutput {
+        object_ref: (ObjectID::random(), SequenceNumber::new(), ObjectDigest::MIN),
+        owner,
+        coin_type: GAS::type_tag(),
+        output,
+        native_tokens: vec![],
+    }
+}
+
+#[test]
+fn unlock_status_of_owned_output() {
+    let owner = IotaAddress::from(ObjectID::random());
+    let other = IotaAddress::from(ObjectID::random());
+    let mut output = basic_output(1_000);
+    output.timelock = Some(TimelockUnlockCondition { unix_time: 100 });
+    let output = stardust_output(
+        Owner::AddressOwner(owner),
+        StardustOutputKind::Basic(output),
+    );
+
+    assert_eq!(
+        output.unlock_status(owner, 99_999),
+        UnlockStatus::Timelocked { unix_time: 100 }
+    );
+    assert_eq!(output.unlock_status(owner, 100_000), UnlockStatus::Unlocked);
+    assert_eq!(
+        output.unlock_status(other, 100_000),
+        UnlockStatus::UnlockableBy(owner)
+    );
+}
+
+#[test]
+fn unlock_status_of_expiring_output() {
+    let owner = IotaAddress::from(ObjectID::random());
+    let return_address = IotaAddress::from(ObjectID::random());
+    let shared = Owner::Shared {
+        initial_shared_version: SequenceNumber::new(),
+    };
+    let mut output = basic_output(1_000);
+    output.expiration = Some(ExpirationUnlockCondition {
+        owner,
+        return_address,
+        unix_time: 100,
+    });
+    let output = stardust_output(shared, StardustOutputKind::Basic(output));
+
+    assert_eq!(output.unlock_status(owner, 99_999), UnlockStatus::Unlocked);
+    assert_eq!(
+        output.unlock_status(return_address, 99_999),
+        UnlockStatus::UnlockableBy(owner)
+    );
+    assert_eq!(
+        output.unlock_status(owner, 100_000),
+        UnlockStatus::UnlockableBy(return_address)
+    );
+    assert_eq!(
+        output.unlock_status(return_address, 100_000),
+        UnlockStatus::Unlocked
+    );
+}
+
+#[test]
+fn claimable_balance() {
+    let owner = Owner::AddressOwner(IotaAddress::from(ObjectID::random()));
+    let mut output = basic_output(1_000);
+    output.storage_deposit_return = Some(StorageDepositReturnUnlockCondition {
+        return_address: IotaAddress::from(ObjectID::random()),
+        return_amount: 400,
+    });
+    let output = stardust_output(owner, StardustOutputKind::Basic(output));
+    assert_eq!(output.balance(), 1_000);
+    assert_eq!(output.claimable_balance(), 600);
+}
+
+#[test]
+fn claim_outputs_transaction() {
+    let recipient = IotaAddress::from(ObjectID::random());
+    let owner = Owner::AddressOwner(recipient);
+    let mut basic = stardust_output(owner, StardustOutputKind::Basic(basic_output(1_000)));
+    basic.native_tokens = vec![TypeTag::from_str("0xabc::soon::SOON").unwrap()];
+    let nft = stardust_output(
+        owner,
+        StardustOutputKind::Nft(NftOutput {
+            id: UID::new(ObjectID::random()),
+            balance: Balance::new(500),
+            native_tokens: Default::default(),
+            storage_deposit_return: None,
+            timelock: None,
+            expiration: None,
+        }),
+    );
+
+    let pt = claim_outputs_pt(&[basic, nft], recipient).unwrap();
+    let commands = pt
+        .commands
+        .iter()
+        .map(|command| match command {
+            Command::MoveCall(call) => format!("{}::{}", call.module, call.function),
+            Command::TransferObjects(..) => "transfer".to_string(),
+            command => panic!("Unexpected command {command}"),
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        commands,
+        vec![
+            "basic_output::extract_assets",
+            "utilities::extract_and_send_to",
+            "bag::destroy_empty",
+            "nft_output::extract_assets",
+            "transfer",
+            "bag::destroy_empty",
+            "balance::join",
+            "coin::from_balance",
+            "transfer",
+        ]
+    );
+}
@@ -2,6 +2,8 @@
 // Modifications Copyright (c) 2024 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+use std::collections::HashSet;
+
 use fastcrypto_zkp::bn254::zk_login::OIDCProvider;
 use iota_config::transaction_deny_config::TransactionDenyConfig;
 use iota_types::{
@@ -9,8 +11,14 @@ use iota_types::{
     error::{IotaError, IotaResult, UserInputError},
     signature::GenericSignature,
     storage::BackingPackageStore,
-    transaction::{Command, InputObjectKind, TransactionData, TransactionDataAPI},
+    transaction::{
+        Command, InputObjectKind, InputObjects, ReceivingObjects, TransactionData,
+        TransactionDataAPI,
+    },
+    TypeTag,
 };
+use move_core_types::language_storage::StructTag;
+
 macro_rules! deny_if_true {
     ($cond:expr, $msg:expr) => {
         if ($cond) {
@@ -41,6 +49,8 @@ pub fn check_transaction_for_signing(
 
     check_package_dependencies(filter_config, tx_data, package_store)?;
 
+    check_type_arguments(filter_config, tx_data)?;
+
     check_receiving_objects(filter_config, receiving_objects)?;
 
     Ok(())
@@ -210,3 +220,71 @@ fn check_package_dependencies(
     }
     Ok(())
 }
+
+fn check_type_arguments(
+    filter_config: &TransactionDenyConfig,
+    tx_data: &TransactionData,
+) -> IotaResult {
+    let deny_set = filter_config.get_type_deny_set();
+    if deny_set.is_empty() {
+        return Ok(());
+    }
+    for command in tx_data.kind().iter_commands() {
+        let type_arguments = match command {
+            Command::MoveCall(call) => call.type_arguments.as_slice(),
+            Command::MakeMoveVec(Some(type_argument), _) => std::slice::from_ref(type_argument),
+            _ => continue,
+        };
+        for type_argument in type_arguments {
+            check_type_tag(deny_set, type_argument)?;
+        }
+    }
+    Ok(())
+}
+
+/// Checks the types of the input and receiving objects against the type deny
+/// list. Unlike the other checks, this needs the loaded objects, so it runs
+/// after them.
+pub fn check_input_object_types(
+    filter_config: &TransactionDenyConfig,
+    input_objects: &InputObjects,
+    receiving_objects: &ReceivingObjects,
+) -> IotaResult {
+    let deny_set = filter_config.get_type_deny_set();
+    if deny_set.is_empty() {
+        return Ok(());
+    }
+    for object in input_objects
+        .iter_objects()
+        .chain(receiving_objects.iter_objects())
+    {
+        if let Some(type_) = object.type_() {
+            check_type_tag(deny_set, &TypeTag::from(type_.clone()))?;
+        }
+    }
+    Ok(())
+}
+
+fn check_type_tag(deny_set: &HashSet<StructTag>, type_tag: &TypeTag) -> IotaResult {
+    match type_tag {
+        TypeTag::Vector(inner) => check_type_tag(deny_set, inner),
+        TypeTag::Struct(tag) => {
+            // A denied type without type parameters matches all of its instantiations.
+            let generic = StructTag {
+                type_params: vec![],
+                ..tag.as_ref().clone()
+            };
+            deny_if_true!(
+                deny_set.contains(tag) || deny_set.contains(&generic),
+                format!(
+                    "Usage of type {} is temporarily disabled",
+                    tag.to_canonical_display(true)
+                )
+            );
+            tag.type_params
+                .iter()
+                .try_for_each(|type_param| check_type_tag(deny_set, type_param))
+        }
+        _ => Ok(()),
+    }
+}
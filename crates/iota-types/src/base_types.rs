@@ -784,6 +784,9 @@ impl TryFrom<&GenericSignature> for IotaAddress {
             GenericSignature::ZkLoginAuthenticator(zklogin) => {
                 IotaAddress::try_from_unpadded(&zklogin.inputs)
             }
+            GenericSignature::PasskeyAuthenticator(passkey) => {
+                Ok(IotaAddress::from(&passkey.get_pk()?))
+            }
         }
     }
 }
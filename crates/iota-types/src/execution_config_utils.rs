@@ -53,6 +53,18 @@ pub fn to_binary_config(protocol_config: &ProtocolConfig) -> BinaryConfig {
             friend_decls: protocol_config
                 .binary_friend_decls_as_option()
                 .unwrap_or(u16::MAX),
+            enum_defs: protocol_config
+                .binary_enum_defs_as_option()
+                .unwrap_or(u16::MAX),
+            enum_def_instantiations: protocol_config
+                .binary_enum_def_instantiations_as_option()
+                .unwrap_or(u16::MAX),
+            variant_handles: protocol_config
+                .binary_variant_handles_as_option()
+                .unwrap_or(u16::MAX),
+            variant_instantiation_handles: protocol_config
+                .binary_variant_instantiation_handles_as_option()
+                .unwrap_or(u16::MAX),
         },
     )
 }
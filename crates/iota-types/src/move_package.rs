@@ -8,8 +8,11 @@ use derive_more::Display;
 use fastcrypto::hash::HashFunction;
 use iota_protocol_config::ProtocolConfig;
 use move_binary_format::{
-    access::ModuleAccess, binary_config::BinaryConfig, binary_views::BinaryIndexedView,
-    file_format::CompiledModule, normalized,
+    access::ModuleAccess,
+    binary_config::BinaryConfig,
+    binary_views::BinaryIndexedView,
+    file_format::{CompiledModule, StructHandleIndex},
+    normalized,
 };
 use move_core_types::{
     account_address::AccountAddress,
@@ -694,12 +697,21 @@ fn build_linkage_table<'p>(
     Ok(linkage_table)
 }
 
+/// The handles of all the types (structs and enums) defined in `module`.
+fn datatype_handles(module: &CompiledModule) -> impl Iterator<Item = StructHandleIndex> + '_ {
+    module
+        .struct_defs()
+        .iter()
+        .map(|def| def.struct_handle)
+        .chain(module.enum_defs().iter().map(|def| def.enum_handle))
+}
+
 fn build_initial_type_origin_table(modules: &[CompiledModule]) -> Vec<TypeOrigin> {
     modules
         .iter()
         .flat_map(|m| {
-            m.struct_defs().iter().map(|struct_def| {
-                let struct_handle = m.struct_handle_at(struct_def.struct_handle);
+            datatype_handles(m).map(|handle| {
+                let struct_handle = m.struct_handle_at(handle);
                 let module_name = m.name().to_string();
                 let struct_name = m.identifier_at(struct_handle.name).to_string();
                 let package: ObjectID = (*m.self_id().address()).into();
@@ -722,8 +734,8 @@ fn build_upgraded_type_origin_table(
     let mut new_table = vec![];
     let mut existing_table = predecessor.type_origin_map();
     for m in modules {
-        for struct_def in m.struct_defs() {
-            let struct_handle = m.struct_handle_at(struct_def.struct_handle);
+        for handle in datatype_handles(m) {
+            let struct_handle = m.struct_handle_at(handle);
             let module_name = m.name().to_string();
             let struct_name = m.identifier_at(struct_handle.name).to_string();
             let mod_key = (module_name.clone(), struct_name.clone());
@@ -27,6 +27,7 @@ use crate::{
     base_types::{EpochId, IotaAddress},
     crypto::{CompressedSignature, DefaultHash, PublicKey, SignatureScheme},
     error::IotaError,
+    passkey_authenticator::PasskeyAuthenticator,
     signature::{AuthenticatorTrait, GenericSignature, VerifyParams},
     zk_login_authenticator::ZkLoginAuthenticator,
 };
@@ -125,6 +126,12 @@ impl AuthenticatorTrait for MultiSig {
             });
         }
 
+        if self.has_passkey_sigs() && !verify_params.accept_passkey_in_multisig {
+            return Err(IotaError::InvalidSignature {
+                error: "Passkey sig not supported inside multisig".to_string(),
+            });
+        }
+
         let mut weight_sum: u16 = 0;
         let message = bcs::to_bytes(&value).expect("Message serialization should not fail");
         let mut hasher = DefaultHash::default();
@@ -194,6 +201,16 @@ impl AuthenticatorTrait for MultiSig {
                         .verify_claims(value, IotaAddress::from(subsig_pubkey), verify_params)
                         .map_err(|e| FastCryptoError::GeneralError(e.to_string()))
                 }
+                CompressedSignature::Passkey(p) => {
+                    let authenticator = PasskeyAuthenticator::from_bytes(&p.0).map_err(|_| {
+                        IotaError::InvalidSignature {
+                            error: "Invalid passkey authenticator bytes".to_string(),
+                        }
+                    })?;
+                    authenticator
+                        .verify_claims(value, IotaAddress::from(subsig_pubkey), verify_params)
+                        .map_err(|e| FastCryptoError::GeneralError(e.to_string()))
+                }
             };
             if res.is_ok() {
                 weight_sum += *weight as u16;
@@ -336,6 +353,12 @@ impl MultiSig {
             .collect()
     }
 
+    pub fn has_passkey_sigs(&self) -> bool {
+        self.sigs
+            .iter()
+            .any(|s| matches!(s, CompressedSignature::Passkey(_)))
+    }
+
     pub fn get_indices(&self) -> Result<Vec<u8>, IotaError> {
         as_indices(self.bitmap)
     }
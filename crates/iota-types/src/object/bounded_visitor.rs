@@ -6,7 +6,7 @@ use anyhow::bail;
 use move_core_types::{
     account_address::AccountAddress,
     annotated_value as A,
-    annotated_visitor::{self, StructDriver, VecDriver, Visitor},
+    annotated_visitor::{self, StructDriver, VariantDriver, VecDriver, Visitor},
     language_storage::TypeTag,
     u256::U256,
 };
@@ -192,6 +192,32 @@ impl Visitor for BoundedVisitor {
             fields,
         }))
     }
+
+    fn visit_variant(
+        &mut self,
+        driver: &mut VariantDriver<'_, '_, '_>,
+    ) -> Result<Self::Value, Self::Error> {
+        let type_ = driver.enum_layout().type_.clone();
+        let variant = driver.variant_layout();
+
+        self.debit_type_size(&TypeTag::Struct(Box::new(type_.clone())))?;
+        self.debit(variant.name.len())?;
+        for field in &variant.fields {
+            self.debit(field.name.len())?;
+        }
+
+        let mut fields = vec![];
+        while let Some((field, elem)) = driver.next_field(self)? {
+            fields.push((field.name.clone(), elem));
+        }
+
+        Ok(A::MoveValue::Variant(A::MoveVariant {
+            type_,
+            variant_name: variant.name.clone(),
+            tag: driver.tag(),
+            fields,
+        }))
+    }
 }
 
 impl Default for BoundedVisitor {
@@ -0,0 +1,276 @@
+// Copyright (c) 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::hash::{Hash, Hasher};
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use fastcrypto::{
+    error::FastCryptoError,
+    hash::{HashFunction, Sha256},
+    secp256r1::{Secp256r1PublicKey, Secp256r1Signature},
+    traits::{ToFromBytes, VerifyingKey},
+};
+use once_cell::sync::OnceCell;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use shared_crypto::intent::IntentMessage;
+
+use crate::{
+    base_types::{EpochId, IotaAddress},
+    crypto::{DefaultHash, IotaSignature, PublicKey, Signature, SignatureScheme},
+    error::{IotaError, IotaResult},
+    signature::{AuthenticatorTrait, VerifyParams},
+};
+
+#[cfg(test)]
+#[path = "unit_tests/passkey_authenticator_test.rs"]
+mod passkey_authenticator_test;
+
+/// The `type` of the client data for a WebAuthn assertion, as opposed to a
+/// credential creation.
+const WEBAUTHN_GET_TYPE: &str = "webauthn.get";
+
+/// The minimum length of the authenticator data: the 32-byte RP ID hash, the
+/// flags byte and the 4-byte signature counter.
+const MIN_AUTHENTICATOR_DATA_LENGTH: usize = 37;
+
+/// The offset of the flags byte in the authenticator data, after the RP ID
+/// hash.
+const AUTHENTICATOR_DATA_FLAGS_OFFSET: usize = 32;
+
+/// The User Present (UP) flag, set by the authenticator when the user
+/// interacted with it to produce the assertion.
+const USER_PRESENT_FLAG: u8 = 0x01;
+
+/// A passkey (WebAuthn) authenticator with all the necessary fields. The
+/// authenticator signs `authenticator_data || sha256(client_data_json)` with a
+/// secp256r1 key, where the client data embeds the digest of the intent
+/// message as its challenge.
+#[derive(Debug, Clone, JsonSchema, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PasskeyAuthenticator {
+    /// The authenticator data returned by the authenticator, see
+    /// <https://www.w3.org/TR/webauthn-2/#sctn-authenticator-data>.
+    authenticator_data: Vec<u8>,
+    /// The client data JSON serialized by the client, see
+    /// <https://www.w3.org/TR/webauthn-2/#dictdef-collectedclientdata>.
+    client_data_json: String,
+    /// The secp256r1 signature, encoded as `flag || sig || pk`.
+    user_signature: Signature,
+    #[serde(skip)]
+    bytes: OnceCell<Vec<u8>>,
+}
+
+/// The fields of the WebAuthn client data that are checked when verifying a
+/// passkey authenticator.
+#[derive(Deserialize)]
+struct ClientData {
+    #[serde(rename = "type")]
+    ty: String,
+    challenge: String,
+}
+
+impl PasskeyAuthenticator {
+    /// Create a new [struct PasskeyAuthenticator], checking that the client
+    /// data is a WebAuthn assertion made with the user present and that the
+    /// signature is a secp256r1 signature.
+    pub fn new(
+        authenticator_data: Vec<u8>,
+        client_data_json: String,
+        user_signature: Signature,
+    ) -> IotaResult<Self> {
+        let authenticator = Self {
+            authenticator_data,
+            client_data_json,
+            user_signature,
+            bytes: OnceCell::new(),
+        };
+        authenticator.validate()?;
+        Ok(authenticator)
+    }
+
+    pub fn authenticator_data(&self) -> &[u8] {
+        &self.authenticator_data
+    }
+
+    pub fn client_data_json(&self) -> &str {
+        &self.client_data_json
+    }
+
+    pub fn get_pk(&self) -> IotaResult<PublicKey> {
+        Ok(PublicKey::Passkey(
+            (&self.get_verification_inputs()?.1).into(),
+        ))
+    }
+
+    /// The challenge the authenticator must sign over for `intent_msg`, i.e.
+    /// the digest of the intent message.
+    pub fn challenge_for<T: Serialize>(intent_msg: &IntentMessage<T>) -> Vec<u8> {
+        let mut hasher = DefaultHash::default();
+        hasher.update(bcs::to_bytes(intent_msg).expect("Message serialization should not fail"));
+        hasher.finalize().digest.to_vec()
+    }
+
+    /// The challenge the authenticator signed over, decoded from the client
+    /// data.
+    pub fn challenge(&self) -> IotaResult<Vec<u8>> {
+        let client_data: ClientData =
+            serde_json::from_str(&self.client_data_json).map_err(|e| {
+                IotaError::InvalidSignature {
+                    error: format!("Invalid passkey client data: {e}"),
+                }
+            })?;
+
+        if client_data.ty != WEBAUTHN_GET_TYPE {
+            return Err(IotaError::InvalidSignature {
+                error: format!("Invalid passkey client data type: {}", client_data.ty),
+            });
+        }
+
+        URL_SAFE_NO_PAD
+            .decode(&client_data.challenge)
+            .map_err(|_| IotaError::InvalidSignature {
+                error: "Invalid passkey challenge encoding".to_string(),
+            })
+    }
+
+    /// The message the authenticator signs: `authenticator_data ||
+    /// sha256(client_data_json)`.
+    fn signed_message(&self) -> Vec<u8> {
+        let mut message = self.authenticator_data.clone();
+        message.extend(Sha256::digest(self.client_data_json.as_bytes()).digest);
+        message
+    }
+
+    fn get_verification_inputs(&self) -> IotaResult<(Secp256r1Signature, Secp256r1PublicKey)> {
+        if self.user_signature.scheme() != SignatureScheme::Secp256r1 {
+            return Err(IotaError::InvalidSignature {
+                error: "Passkey signature must be a secp256r1 signature".to_string(),
+            });
+        }
+
+        let pk = Secp256r1PublicKey::from_bytes(self.user_signature.public_key_bytes())
+            .map_err(|_| IotaError::KeyConversion("Invalid passkey public key".to_string()))?;
+        let sig = Secp256r1Signature::from_bytes(self.user_signature.signature_bytes()).map_err(
+            |_| IotaError::InvalidSignature {
+                error: "Invalid passkey signature bytes".to_string(),
+            },
+        )?;
+        Ok((sig, pk))
+    }
+
+    fn validate(&self) -> IotaResult {
+        if self.authenticator_data.len() < MIN_AUTHENTICATOR_DATA_LENGTH {
+            return Err(IotaError::InvalidSignature {
+                error: "Invalid passkey authenticator data length".to_string(),
+            });
+        }
+        if self.authenticator_data[AUTHENTICATOR_DATA_FLAGS_OFFSET] & USER_PRESENT_FLAG == 0 {
+            return Err(IotaError::InvalidSignature {
+                error: "Passkey authenticator data does not have the user present flag set"
+                    .to_string(),
+            });
+        }
+        self.challenge()?;
+        self.get_verification_inputs()?;
+        Ok(())
+    }
+}
+
+/// Necessary trait for [struct SenderSignedData].
+impl PartialEq for PasskeyAuthenticator {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_ref() == other.as_ref()
+    }
+}
+
+/// Necessary trait for [struct SenderSignedData].
+impl Eq for PasskeyAuthenticator {}
+
+/// Necessary trait for [struct SenderSignedData].
+impl Hash for PasskeyAuthenticator {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_ref().hash(state);
+    }
+}
+
+impl AuthenticatorTrait for PasskeyAuthenticator {
+    fn verify_user_authenticator_epoch(&self, _: EpochId) -> IotaResult {
+        Ok(())
+    }
+
+    fn verify_uncached_checks<T>(
+        &self,
+        _value: &IntentMessage<T>,
+        _author: IotaAddress,
+        _aux_verify_data: &VerifyParams,
+    ) -> IotaResult
+    where
+        T: Serialize,
+    {
+        Ok(())
+    }
+
+    /// Verify an intent message of a transaction with a passkey
+    /// authenticator: the challenge must be the digest of the intent message,
+    /// the address must be derived from the passkey's public key, and the
+    /// signature must be valid over the authenticator and client data.
+    fn verify_claims<T>(
+        &self,
+        intent_msg: &IntentMessage<T>,
+        author: IotaAddress,
+        _aux_verify_data: &VerifyParams,
+    ) -> IotaResult
+    where
+        T: Serialize,
+    {
+        if self.challenge()? != Self::challenge_for(intent_msg) {
+            return Err(IotaError::InvalidSignature {
+                error: "Passkey challenge does not match the intent message".to_string(),
+            });
+        }
+
+        let address = IotaAddress::from(&self.get_pk()?);
+        if author != address {
+            return Err(IotaError::IncorrectSigner {
+                error: format!("Incorrect signer, expected {:?}, got {:?}", author, address),
+            });
+        }
+
+        let (sig, pk) = self.get_verification_inputs()?;
+        pk.verify(&self.signed_message(), &sig)
+            .map_err(|e| IotaError::InvalidSignature {
+                error: format!("Fail to verify passkey sig {}", e),
+            })
+    }
+}
+
+impl ToFromBytes for PasskeyAuthenticator {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, FastCryptoError> {
+        if bytes.first().ok_or(FastCryptoError::InvalidInput)?
+            != &SignatureScheme::PasskeyAuthenticator.flag()
+        {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        let passkey: PasskeyAuthenticator =
+            bcs::from_bytes(&bytes[1..]).map_err(|_| FastCryptoError::InvalidSignature)?;
+        passkey
+            .validate()
+            .map_err(|_| FastCryptoError::InvalidSignature)?;
+        Ok(passkey)
+    }
+}
+
+impl AsRef<[u8]> for PasskeyAuthenticator {
+    fn as_ref(&self) -> &[u8] {
+        self.bytes
+            .get_or_try_init::<_, eyre::Report>(|| {
+                let as_bytes = bcs::to_bytes(self).expect("BCS serialization should not fail");
+                let mut bytes = Vec::with_capacity(1 + as_bytes.len());
+                bytes.push(SignatureScheme::PasskeyAuthenticator.flag());
+                bytes.extend_from_slice(as_bytes.as_slice());
+                Ok(bytes)
+            })
+            .expect("OnceCell invariant violated")
+    }
+}
@@ -25,12 +25,13 @@ use crate::{
     base_types::IotaAddress,
     committee::EpochId,
     crypto::{
-        CompressedSignature, IotaSignature, PublicKey, Signature, SignatureScheme,
-        ZkLoginAuthenticatorAsBytes,
+        CompressedSignature, IotaSignature, PasskeyAuthenticatorAsBytes, PublicKey, Signature,
+        SignatureScheme, ZkLoginAuthenticatorAsBytes,
     },
     error::{IotaError, IotaResult},
     multisig::MultiSig,
     multisig_legacy::MultiSigLegacy,
+    passkey_authenticator::PasskeyAuthenticator,
     zk_login_authenticator::ZkLoginAuthenticator,
 };
 #[derive(Default, Debug, Clone)]
@@ -41,6 +42,7 @@ pub struct VerifyParams {
     pub zk_login_env: ZkLoginEnv,
     pub verify_legacy_zklogin_address: bool,
     pub accept_zklogin_in_multisig: bool,
+    pub accept_passkey_in_multisig: bool,
 }
 
 impl VerifyParams {
@@ -50,6 +52,7 @@ impl VerifyParams {
         zk_login_env: ZkLoginEnv,
         verify_legacy_zklogin_address: bool,
         accept_zklogin_in_multisig: bool,
+        accept_passkey_in_multisig: bool,
     ) -> Self {
         Self {
             oidc_provider_jwks,
@@ -57,6 +60,7 @@ impl VerifyParams {
             zk_login_env,
             verify_legacy_zklogin_address,
             accept_zklogin_in_multisig,
+            accept_passkey_in_multisig,
         }
     }
 }
@@ -113,6 +117,7 @@ pub enum GenericSignature {
     MultiSigLegacy,
     Signature,
     ZkLoginAuthenticator,
+    PasskeyAuthenticator,
 }
 
 impl GenericSignature {
@@ -120,6 +125,10 @@ impl GenericSignature {
         matches!(self, GenericSignature::ZkLoginAuthenticator(_))
     }
 
+    pub fn is_passkey(&self) -> bool {
+        matches!(self, GenericSignature::PasskeyAuthenticator(_))
+    }
+
     pub fn is_upgraded_multisig(&self) -> bool {
         matches!(self, GenericSignature::MultiSig(_))
     }
@@ -164,6 +173,9 @@ impl GenericSignature {
             GenericSignature::ZkLoginAuthenticator(s) => Ok(CompressedSignature::ZkLogin(
                 ZkLoginAuthenticatorAsBytes(s.as_ref().to_vec()),
             )),
+            GenericSignature::PasskeyAuthenticator(s) => Ok(CompressedSignature::Passkey(
+                PasskeyAuthenticatorAsBytes(s.as_ref().to_vec()),
+            )),
             _ => Err(IotaError::UnsupportedFeature {
                 error: "Unsupported signature scheme".to_string(),
             }),
@@ -202,6 +214,7 @@ impl GenericSignature {
                 }
             }
             GenericSignature::ZkLoginAuthenticator(s) => s.get_pk(),
+            GenericSignature::PasskeyAuthenticator(s) => s.get_pk(),
             _ => Err(IotaError::UnsupportedFeature {
                 error: "Unsupported signature scheme".to_string(),
             }),
@@ -214,7 +227,9 @@ impl GenericSignature {
 /// flag (0x03) concat with the bcs serializedbytes of [struct MultiSigLegacy]
 /// i.e. `flag || bcs_bytes(MultiSigLegacy)`. [struct Multisig] is encodede as
 /// the MultiSig flag (0x03) concat with the bcs serializedbytes of [struct
-/// Multisig] i.e. `flag || bcs_bytes(Multisig)`.
+/// Multisig] i.e. `flag || bcs_bytes(Multisig)`. [struct
+/// PasskeyAuthenticator] is encoded as the passkey flag (0x06) concat with its
+/// bcs serialized bytes.
 impl ToFromBytes for GenericSignature {
     fn from_bytes(bytes: &[u8]) -> Result<Self, FastCryptoError> {
         match SignatureScheme::from_flag_byte(
@@ -237,6 +252,10 @@ impl ToFromBytes for GenericSignature {
                     let zk_login = ZkLoginAuthenticator::from_bytes(bytes)?;
                     Ok(GenericSignature::ZkLoginAuthenticator(zk_login))
                 }
+                SignatureScheme::PasskeyAuthenticator => {
+                    let passkey = PasskeyAuthenticator::from_bytes(bytes)?;
+                    Ok(GenericSignature::PasskeyAuthenticator(passkey))
+                }
                 _ => Err(FastCryptoError::InvalidInput),
             },
             Err(_) => Err(FastCryptoError::InvalidInput),
@@ -252,6 +271,7 @@ impl AsRef<[u8]> for GenericSignature {
             GenericSignature::MultiSigLegacy(s) => s.as_ref(),
             GenericSignature::Signature(s) => s.as_ref(),
             GenericSignature::ZkLoginAuthenticator(s) => s.as_ref(),
+            GenericSignature::PasskeyAuthenticator(s) => s.as_ref(),
         }
     }
 }
@@ -38,6 +38,16 @@ impl ExpirationUnlockCondition {
             unix_time,
         })
     }
+
+    /// Returns the address that can unlock the related output at the given
+    /// timestamp in milliseconds.
+    pub fn can_be_unlocked_by(&self, timestamp_ms: u64) -> IotaAddress {
+        if self.unix_time <= to_unix_time(timestamp_ms) {
+            self.return_address
+        } else {
+            self.owner
+        }
+    }
 }
 
 /// Rust version of the stardust storage deposit return unlock condition.
@@ -89,3 +99,44 @@ impl From<&iota_stardust_sdk::types::block::output::unlock_condition::TimelockUn
         }
     }
 }
+
+impl TimelockUnlockCondition {
+    /// Checks if the related output is still locked at the given timestamp in
+    /// milliseconds.
+    pub fn is_timelocked(&self, timestamp_ms: u64) -> bool {
+        self.unix_time > to_unix_time(timestamp_ms)
+    }
+}
+
+/// Converts a timestamp in milliseconds to the unix time in seconds of the
+/// unlock conditions, the same way as the `stardust` Move package.
+fn to_unix_time(timestamp_ms: u64) -> u32 {
+    (timestamp_ms / 1000) as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expiration_unlock_condition() {
+        let owner = IotaAddress::random_for_testing_only();
+        let return_address = IotaAddress::random_for_testing_only();
+        let expiration = ExpirationUnlockCondition {
+            owner,
+            return_address,
+            unix_time: 100,
+        };
+        assert_eq!(expiration.can_be_unlocked_by(0), owner);
+        assert_eq!(expiration.can_be_unlocked_by(99_999), owner);
+        assert_eq!(expiration.can_be_unlocked_by(100_000), return_address);
+    }
+
+    #[test]
+    fn timelock_unlock_condition() {
+        let timelock = TimelockUnlockCondition { unix_time: 100 };
+        assert!(timelock.is_timelocked(0));
+        assert!(timelock.is_timelocked(99_999));
+        assert!(!timelock.is_timelocked(100_000));
+    }
+}
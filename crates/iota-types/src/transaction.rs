@@ -2290,6 +2290,13 @@ impl VersionedProtocolMessage for SenderSignedData {
                         });
                     }
                 }
+                GenericSignature::PasskeyAuthenticator(_) => {
+                    if !protocol_config.passkey_auth() {
+                        return Err(IotaError::UnsupportedFeature {
+                            error: "passkey not enabled on this network".to_string(),
+                        });
+                    }
+                }
                 GenericSignature::Signature(_)
                 | GenericSignature::MultiSigLegacy(_)
                 | GenericSignature::ZkLoginAuthenticator(_) => (),
@@ -441,7 +441,7 @@ fn zklogin_in_multisig_works_with_both_addresses() {
         .into_iter()
         .collect();
 
-    let aux_verify_data = VerifyParams::new(parsed, vec![], ZkLoginEnv::Test, true, true);
+    let aux_verify_data = VerifyParams::new(parsed, vec![], ZkLoginEnv::Test, true, true, true);
     let res = multisig.verify_claims(intent_msg, multisig_address, &aux_verify_data);
     // since the zklogin inputs is crafted, it is expected that the proof verify
     // failed, but all checks before passes.
@@ -0,0 +1,237 @@
+// Copyright (c) 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use fastcrypto::{
+    secp256r1::Secp256r1KeyPair,
+    traits::{KeyPair, ToFromBytes},
+};
+use iota_protocol_config::ProtocolConfig;
+use shared_crypto::intent::{Intent, IntentMessage, PersonalMessage};
+
+use super::PasskeyAuthenticator;
+use crate::{
+    base_types::IotaAddress,
+    crypto::{get_key_pair, IotaKeyPair, PublicKey, Signature, SignatureScheme},
+    message_envelope::AuthenticatedMessage,
+    multisig::{MultiSig, MultiSigPublicKey},
+    signature::{AuthenticatorTrait, GenericSignature, VerifyParams},
+    transaction::{TransactionDataAPI, VersionedProtocolMessage},
+    utils::{
+        get_passkey_user_address, get_passkey_user_key, make_passkey_tx,
+        passkey_authenticator_data, passkey_client_data_json, sign_passkey_payload,
+        sign_with_passkey,
+    },
+};
+
+fn personal_message(message: &str) -> IntentMessage<PersonalMessage> {
+    IntentMessage::new(
+        Intent::iota_transaction(),
+        PersonalMessage {
+            message: message.as_bytes().to_vec(),
+        },
+    )
+}
+
+#[test]
+fn test_serde_passkey_signature() {
+    let kp = get_passkey_user_key();
+    let authenticator = sign_with_passkey(&kp, &personal_message("Hello"));
+    let sig = GenericSignature::PasskeyAuthenticator(authenticator);
+
+    let serialized = sig.as_ref();
+    assert_eq!(serialized[0], SignatureScheme::PasskeyAuthenticator.flag());
+
+    let deserialized = GenericSignature::from_bytes(serialized).unwrap();
+    assert_eq!(deserialized, sig);
+
+    let addr: IotaAddress = (&sig).try_into().unwrap();
+    assert_eq!(addr, get_passkey_user_address());
+}
+
+#[test]
+fn test_passkey_address_differs_from_secp256r1() {
+    let kp = get_passkey_user_key();
+    let secp256r1_address = IotaAddress::from(&PublicKey::Secp256r1(kp.public().into()));
+    assert_ne!(get_passkey_user_address(), secp256r1_address);
+}
+
+#[test]
+fn test_verify_passkey_signature() {
+    let kp = get_passkey_user_key();
+    let msg = personal_message("Hello");
+    let authenticator = sign_with_passkey(&kp, &msg);
+    let verify_params = VerifyParams::default();
+
+    assert!(
+        authenticator
+            .verify_authenticator(&msg, get_passkey_user_address(), Some(0), &verify_params)
+            .is_ok()
+    );
+
+    // Fails for an address that is not derived from the passkey.
+    let (other_address, _): (_, Secp256r1KeyPair) = get_key_pair();
+    assert!(
+        authenticator
+            .verify_authenticator(&msg, other_address, Some(0), &verify_params)
+            .is_err()
+    );
+
+    // Fails for a message other than the one in the challenge.
+    assert!(
+        authenticator
+            .verify_authenticator(
+                &personal_message("Goodbye"),
+                get_passkey_user_address(),
+                Some(0),
+                &verify_params
+            )
+            .is_err()
+    );
+}
+
+#[test]
+fn test_verify_tampered_passkey_signature() {
+    let kp = get_passkey_user_key();
+    let msg = personal_message("Hello");
+    let challenge = PasskeyAuthenticator::challenge_for(&msg);
+    let client_data_json = passkey_client_data_json("webauthn.get", &challenge);
+    let user_signature =
+        sign_passkey_payload(&kp, &passkey_authenticator_data(), &client_data_json);
+
+    // The signature does not cover different authenticator data.
+    let mut tampered_data = passkey_authenticator_data();
+    tampered_data[32] = 0x01;
+    let authenticator =
+        PasskeyAuthenticator::new(tampered_data, client_data_json.clone(), user_signature).unwrap();
+    assert!(
+        authenticator
+            .verify_claims(&msg, get_passkey_user_address(), &VerifyParams::default())
+            .is_err()
+    );
+
+    // The signature must be a secp256r1 signature.
+    let ed25519: IotaKeyPair = IotaKeyPair::Ed25519(get_key_pair().1);
+    let user_signature = Signature::new_secure(&msg, &ed25519);
+    assert!(
+        PasskeyAuthenticator::new(
+            passkey_authenticator_data(),
+            client_data_json,
+            user_signature
+        )
+        .is_err()
+    );
+}
+
+#[test]
+fn test_invalid_passkey_client_data() {
+    let kp = get_passkey_user_key();
+    let challenge = PasskeyAuthenticator::challenge_for(&personal_message("Hello"));
+
+    // Credential creation, rather than an assertion.
+    let client_data_json = passkey_client_data_json("webauthn.create", &challenge);
+    let user_signature =
+        sign_passkey_payload(&kp, &passkey_authenticator_data(), &client_data_json);
+    assert!(
+        PasskeyAuthenticator::new(
+            passkey_authenticator_data(),
+            client_data_json,
+            user_signature
+        )
+        .is_err()
+    );
+
+    // Truncated authenticator data.
+    let client_data_json = passkey_client_data_json("webauthn.get", &challenge);
+    let user_signature =
+        sign_passkey_payload(&kp, &passkey_authenticator_data()[..32], &client_data_json);
+    assert!(
+        PasskeyAuthenticator::new(
+            passkey_authenticator_data()[..32].to_vec(),
+            client_data_json,
+            user_signature
+        )
+        .is_err()
+    );
+
+    // Signed without the user present.
+    let client_data_json = passkey_client_data_json("webauthn.get", &challenge);
+    let mut absent_data = passkey_authenticator_data();
+    absent_data[32] = 0x04;
+    let user_signature = sign_passkey_payload(&kp, &absent_data, &client_data_json);
+    assert!(PasskeyAuthenticator::new(absent_data, client_data_json, user_signature).is_err());
+
+    // Not JSON.
+    let user_signature = sign_passkey_payload(&kp, &passkey_authenticator_data(), "webauthn.get");
+    assert!(
+        PasskeyAuthenticator::new(
+            passkey_authenticator_data(),
+            "webauthn.get".to_string(),
+            user_signature
+        )
+        .is_err()
+    );
+}
+
+#[test]
+fn test_passkey_in_multisig() {
+    let passkey = get_passkey_user_key();
+    let kp: IotaKeyPair = IotaKeyPair::Ed25519(get_key_pair().1);
+    let multisig_pk = MultiSigPublicKey::new(
+        vec![kp.public(), PublicKey::Passkey(passkey.public().into())],
+        vec![1, 1],
+        2,
+    )
+    .unwrap();
+    let address = IotaAddress::from(&multisig_pk);
+
+    let msg = personal_message("Hello");
+    let sig1: GenericSignature = Signature::new_secure(&msg, &kp).into();
+    let sig2 = GenericSignature::PasskeyAuthenticator(sign_with_passkey(&passkey, &msg));
+    let multisig = MultiSig::combine(vec![sig1, sig2], multisig_pk).unwrap();
+    assert!(multisig.has_passkey_sigs());
+
+    let mut verify_params = VerifyParams::default();
+    verify_params.accept_passkey_in_multisig = true;
+    assert!(
+        multisig
+            .verify_claims(&msg, address, &verify_params)
+            .is_ok()
+    );
+
+    // The multisig round trips through its bytes.
+    let generic = GenericSignature::MultiSig(multisig.clone());
+    assert_eq!(
+        GenericSignature::from_bytes(generic.as_ref()).unwrap(),
+        generic
+    );
+
+    verify_params.accept_passkey_in_multisig = false;
+    assert!(
+        multisig
+            .verify_claims(&msg, address, &verify_params)
+            .is_err()
+    );
+}
+
+#[test]
+fn test_passkey_feature_gate() {
+    let (_, tx, _) = make_passkey_tx();
+    let mut config = ProtocolConfig::get_for_max_version_UNSAFE();
+
+    config.set_passkey_auth_for_testing(false);
+    assert!(tx.data().check_version_supported(&config).is_err());
+
+    config.set_passkey_auth_for_testing(true);
+    assert!(tx.data().check_version_supported(&config).is_ok());
+}
+
+#[test]
+fn test_verify_passkey_transaction() {
+    let (address, tx, _) = make_passkey_tx();
+    assert_eq!(tx.transaction_data().sender(), address);
+    assert!(
+        tx.data()
+            .verify_message_signature(&VerifyParams::default())
+            .is_ok()
+    );
+}
@@ -319,3 +319,85 @@ mod zk_login {
     }
 }
 pub use zk_login::*;
+
+mod passkey {
+    use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+    use fastcrypto::{hash::Sha256, secp256r1::Secp256r1KeyPair};
+    use serde::Serialize;
+
+    use super::*;
+    use crate::{
+        crypto::{IotaSignatureInner, PublicKey, Secp256r1IotaSignature},
+        passkey_authenticator::PasskeyAuthenticator,
+    };
+
+    /// The key of a software passkey authenticator, generated from a fixed
+    /// seed.
+    pub fn get_passkey_user_key() -> Secp256r1KeyPair {
+        get_key_pair_from_rng(&mut StdRng::from_seed([0; 32])).1
+    }
+
+    pub fn get_passkey_user_address() -> IotaAddress {
+        IotaAddress::from(&PublicKey::Passkey(get_passkey_user_key().public().into()))
+    }
+
+    /// Authenticator data with a fixed RP ID hash, the user present and user
+    /// verified flags set and a zero signature counter.
+    pub fn passkey_authenticator_data() -> Vec<u8> {
+        let mut data = Sha256::digest(b"www.iota.org").digest.to_vec();
+        data.push(0x05);
+        data.extend([0; 4]);
+        data
+    }
+
+    pub fn passkey_client_data_json(ty: &str, challenge: &[u8]) -> String {
+        format!(
+            r#"{{"type":"{ty}","challenge":"{}","origin":"https://www.iota.org","crossOrigin":false}}"#,
+            URL_SAFE_NO_PAD.encode(challenge)
+        )
+    }
+
+    /// Sign `authenticator_data || sha256(client_data_json)` the way a passkey
+    /// authenticator does.
+    pub fn sign_passkey_payload(
+        kp: &Secp256r1KeyPair,
+        authenticator_data: &[u8],
+        client_data_json: &str,
+    ) -> Signature {
+        let mut message = authenticator_data.to_vec();
+        message.extend(Sha256::digest(client_data_json.as_bytes()).digest);
+        <Secp256r1IotaSignature as IotaSignatureInner>::new(kp, &message).into()
+    }
+
+    /// Sign an intent message with a software passkey authenticator.
+    pub fn sign_with_passkey<T: Serialize>(
+        kp: &Secp256r1KeyPair,
+        intent_msg: &IntentMessage<T>,
+    ) -> PasskeyAuthenticator {
+        let authenticator_data = passkey_authenticator_data();
+        let client_data_json = passkey_client_data_json(
+            "webauthn.get",
+            &PasskeyAuthenticator::challenge_for(intent_msg),
+        );
+        let user_signature = sign_passkey_payload(kp, &authenticator_data, &client_data_json);
+        PasskeyAuthenticator::new(authenticator_data, client_data_json, user_signature)
+            .expect("Passkey authenticator should be valid")
+    }
+
+    pub fn make_passkey_tx() -> (IotaAddress, Transaction, GenericSignature) {
+        let address = get_passkey_user_address();
+        let data = make_transaction_data(address);
+        let msg = IntentMessage::new(Intent::iota_transaction(), data.clone());
+        let authenticator = GenericSignature::PasskeyAuthenticator(sign_with_passkey(
+            &get_passkey_user_key(),
+            &msg,
+        ));
+        let tx = Transaction::new(SenderSignedData::new(
+            data,
+            Intent::iota_transaction(),
+            vec![authenticator.clone()],
+        ));
+        (address, tx, authenticator)
+    }
+}
+pub use passkey::*;
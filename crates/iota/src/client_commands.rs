@@ -3,7 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use std::{
-    collections::{btree_map::Entry, BTreeMap},
+    collections::{btree_map::Entry, BTreeMap, BTreeSet},
     fmt::{Debug, Display, Formatter, Write},
     fs,
     path::PathBuf,
@@ -22,10 +22,10 @@ use fastcrypto::{
 use iota_execution::verifier::VerifierOverrides;
 use iota_json::IotaJsonValue;
 use iota_json_rpc_types::{
-    Coin, DynamicFieldPage, IotaCoinMetadata, IotaData, IotaExecutionStatus, IotaObjectData,
-    IotaObjectDataOptions, IotaObjectResponse, IotaObjectResponseQuery, IotaParsedData,
-    IotaRawData, IotaTransactionBlockEffectsAPI, IotaTransactionBlockResponse,
-    IotaTransactionBlockResponseOptions,
+    Coin, DelegatedStake, DelegatedTimelockedStake, DynamicFieldPage, IotaCoinMetadata, IotaData,
+    IotaExecutionStatus, IotaObjectData, IotaObjectDataOptions, IotaObjectResponse,
+    IotaObjectResponseQuery, IotaParsedData, IotaRawData, IotaTransactionBlockEffectsAPI,
+    IotaTransactionBlockResponse, IotaTransactionBlockResponseOptions, StakeStatus,
 };
 use iota_keys::keystore::AccountKeystore;
 use iota_move::build::resolve_lock_file_path;
@@ -36,14 +36,14 @@ use iota_move_build::{
 use iota_protocol_config::{Chain, ProtocolConfig, ProtocolVersion};
 use iota_replay::ReplayToolCommand;
 use iota_sdk::{
-    apis::ReadApi,
+    apis::{claim_outputs_pt, ReadApi, UnlockStatus},
     iota_client_config::{IotaClientConfig, IotaEnv},
     wallet_context::WalletContext,
     IOTA_COIN_TYPE, IOTA_DEVNET_URL, IOTA_LOCAL_NETWORK_URL, IOTA_TESTNET_URL,
 };
 use iota_source_validation::{BytecodeSourceVerifier, SourceMode};
 use iota_types::{
-    base_types::{IotaAddress, ObjectID, SequenceNumber},
+    base_types::{EpochId, IotaAddress, ObjectID, ObjectType, SequenceNumber},
     crypto::{EmptySignInfo, SignatureScheme},
     digests::TransactionDigest,
     dynamic_field::DynamicFieldInfo,
@@ -55,6 +55,9 @@ use iota_types::{
     object::Owner,
     parse_iota_type_tag,
     signature::GenericSignature,
+    stardust::output::unlock_conditions::{
+        ExpirationUnlockCondition, StorageDepositReturnUnlockCondition, TimelockUnlockCondition,
+    },
     transaction::{SenderSignedData, Transaction, TransactionData, TransactionDataAPI},
 };
 use json_to_table::json_to_table;
@@ -214,6 +217,44 @@ pub enum IotaClientCommands {
     #[clap(name = "chain-identifier")]
     ChainIdentifier,
 
+    /// Claim Stardust outputs with the active address. The IOTA balance,
+    /// native tokens, NFTs and aliases of the outputs are sent to the active
+    /// address, storage deposits are returned to their return addresses.
+    #[clap(name = "claim-stardust-outputs")]
+    ClaimStardustOutputs {
+        /// Outputs to claim, in 20 bytes Hex string. If not specified, all
+        /// unlocked outputs owned by the active address are claimed.
+        #[clap(long, num_args(1..))]
+        outputs: Option<Vec<ObjectID>>,
+
+        /// ID of the gas object for gas payment, in 20 bytes Hex string
+        /// If not provided, a gas object with at least gas_budget value will be
+        /// selected
+        #[clap(long)]
+        gas: Option<ObjectID>,
+
+        /// Gas budget for this call
+        #[clap(long)]
+        gas_budget: u64,
+
+        /// Instead of executing the transaction, serialize the bcs bytes of the
+        /// unsigned transaction data (TransactionData) using base64
+        /// encoding, and print out the string <TX_BYTES>. The string can
+        /// be used to execute transaction with `iota client execute-signed-tx
+        /// --tx-bytes <TX_BYTES>`.
+        #[clap(long, required = false)]
+        serialize_unsigned_transaction: bool,
+
+        /// Instead of executing the transaction, serialize the bcs bytes of the
+        /// signed transaction data (SenderSignedData) using base64
+        /// encoding, and print out the string <SIGNED_TX_BYTES>. The string
+        /// can be used to execute transaction with `iota client
+        /// execute-combined-signed-tx --signed-tx-bytes
+        /// <SIGNED_TX_BYTES>`.
+        #[clap(long, required = false)]
+        serialize_signed_transaction: bool,
+    },
+
     /// Query a dynamic field by its address.
     #[clap(name = "dynamic-field")]
     DynamicFieldQuery {
@@ -576,6 +617,79 @@ pub enum IotaClientCommands {
         serialize_signed_transaction: bool,
     },
 
+    /// Stake IOTA coins or a timelocked balance with a validator.
+    #[clap(name = "stake")]
+    #[clap(group(ArgGroup::new("stake").required(true).args(&["coins", "timelocked_balance"])))]
+    Stake {
+        /// Address of the validator to stake with
+        #[clap(long)]
+        validator: IotaAddress,
+
+        /// IOTA coins to stake, in 20 bytes Hex string. The coins are merged
+        /// before staking.
+        #[clap(long, num_args(1..))]
+        coins: Option<Vec<ObjectID>>,
+
+        /// The amount to stake in NANOS, if not specified, the whole value of
+        /// the coins is staked.
+        #[clap(long, requires = "coins")]
+        amount: Option<u64>,
+
+        /// Timelocked balance to stake instead of coins, in 20 bytes Hex
+        /// string.
+        #[clap(long)]
+        timelocked_balance: Option<ObjectID>,
+
+        /// ID of the gas object for gas payment, in 20 bytes Hex string
+        /// If not provided, a gas object with at least gas_budget value will be
+        /// selected
+        #[clap(long)]
+        gas: Option<ObjectID>,
+
+        /// Gas budget for this call
+        #[clap(long)]
+        gas_budget: u64,
+
+        /// Instead of executing the transaction, serialize the bcs bytes of the
+        /// unsigned transaction data (TransactionData) using base64
+        /// encoding, and print out the string <TX_BYTES>. The string can
+        /// be used to execute transaction with `iota client execute-signed-tx
+        /// --tx-bytes <TX_BYTES>`.
+        #[clap(long, required = false)]
+        serialize_unsigned_transaction: bool,
+
+        /// Instead of executing the transaction, serialize the bcs bytes of the
+        /// signed transaction data (SenderSignedData) using base64
+        /// encoding, and print out the string <SIGNED_TX_BYTES>. The string
+        /// can be used to execute transaction with `iota client
+        /// execute-combined-signed-tx --signed-tx-bytes
+        /// <SIGNED_TX_BYTES>`.
+        #[clap(long, required = false)]
+        serialize_signed_transaction: bool,
+    },
+
+    /// List the stakes and timelocked stakes of an address, with their
+    /// estimated rewards.
+    #[clap(name = "stakes")]
+    Stakes {
+        /// Address (or its alias) owning the stakes, the active address if not
+        /// specified.
+        #[arg(value_parser)]
+        address: Option<KeyIdentity>,
+    },
+
+    /// List the Stardust outputs owned by an address, with their unlock
+    /// conditions evaluated against the current epoch start timestamp.
+    /// Outputs with an expiration unlock condition are shared objects and are
+    /// not listed.
+    #[clap(name = "stardust-outputs")]
+    StardustOutputs {
+        /// Address (or its alias) owning the outputs, the active address if
+        /// not specified.
+        #[arg(value_parser)]
+        address: Option<KeyIdentity>,
+    },
+
     /// Switch active address and network(e.g., devnet, local rpc server).
     #[clap(name = "switch")]
     Switch {
@@ -678,6 +792,42 @@ pub enum IotaClientCommands {
         serialize_signed_transaction: bool,
     },
 
+    /// Withdraw a stake or a timelocked stake, together with its rewards.
+    #[clap(name = "unstake")]
+    Unstake {
+        /// ID of the StakedIota or TimelockedStakedIota object to withdraw, in
+        /// 20 bytes Hex string
+        #[clap(long)]
+        staked_iota_id: ObjectID,
+
+        /// ID of the gas object for gas payment, in 20 bytes Hex string
+        /// If not provided, a gas object with at least gas_budget value will be
+        /// selected
+        #[clap(long)]
+        gas: Option<ObjectID>,
+
+        /// Gas budget for this call
+        #[clap(long)]
+        gas_budget: u64,
+
+        /// Instead of executing the transaction, serialize the bcs bytes of the
+        /// unsigned transaction data (TransactionData) using base64
+        /// encoding, and print out the string <TX_BYTES>. The string can
+        /// be used to execute transaction with `iota client execute-signed-tx
+        /// --tx-bytes <TX_BYTES>`.
+        #[clap(long, required = false)]
+        serialize_unsigned_transaction: bool,
+
+        /// Instead of executing the transaction, serialize the bcs bytes of the
+        /// signed transaction data (SenderSignedData) using base64
+        /// encoding, and print out the string <SIGNED_TX_BYTES>. The string
+        /// can be used to execute transaction with `iota client
+        /// execute-combined-signed-tx --signed-tx-bytes
+        /// <SIGNED_TX_BYTES>`.
+        #[clap(long, required = false)]
+        serialize_signed_transaction: bool,
+    },
+
     /// Upgrade Move modules
     #[clap(name = "upgrade")]
     Upgrade {
@@ -732,6 +882,11 @@ pub enum IotaClientCommands {
         serialize_signed_transaction: bool,
     },
 
+    /// List the active validators with their APY, commission rate and gas
+    /// price.
+    #[clap(name = "validators")]
+    Validators,
+
     /// Run the bytecode verifier on the package
     #[clap(name = "verify-bytecode-meter")]
     VerifyBytecodeMeter {
@@ -910,6 +1065,7 @@ impl IotaClientCommands {
                     diag: false,
                     executor_version,
                     protocol_version,
+                    archive: Default::default(),
                 };
 
                 let rpc = context.config.get_active_env()?.rpc.clone();
@@ -943,6 +1099,7 @@ impl IotaClientCommands {
                     end,
                     terminate_early,
                     max_tasks: 16,
+                    archive: Default::default(),
                 };
                 let rpc = context.config.get_active_env()?.rpc.clone();
                 let _command_result =
@@ -1544,6 +1701,69 @@ impl IotaClientCommands {
                     .await?;
                 IotaClientCommandResult::ChainIdentifier(ci)
             }
+            IotaClientCommands::ClaimStardustOutputs {
+                outputs,
+                gas,
+                gas_budget,
+                serialize_unsigned_transaction,
+                serialize_signed_transaction,
+            } => {
+                let signer = context.active_address()?;
+                let client = context.get_client().await?;
+                let stardust_api = client.stardust_api();
+                let timestamp_ms = stardust_api.get_unlock_timestamp_ms().await?;
+                let outputs = match outputs {
+                    Some(output_ids) => {
+                        let mut outputs = vec![];
+                        for output_id in output_ids {
+                            let output = stardust_api.get_output(output_id).await?;
+                            let status = output.unlock_status(signer, timestamp_ms);
+                            ensure!(
+                                status == UnlockStatus::Unlocked,
+                                "Output [{output_id}] can't be claimed by {signer}: {status}"
+                            );
+                            outputs.push(output);
+                        }
+                        outputs
+                    }
+                    None => stardust_api
+                        .get_owned_outputs(signer)
+                        .await?
+                        .into_iter()
+                        .filter(|output| {
+                            output.unlock_status(signer, timestamp_ms) == UnlockStatus::Unlocked
+                        })
+                        .collect(),
+                };
+                ensure!(
+                    !outputs.is_empty(),
+                    "No unlocked Stardust outputs found for {signer}"
+                );
+
+                let gas = match gas {
+                    Some(gas) => context.get_object_ref(gas).await?,
+                    None => context
+                        .gas_for_owner_budget(signer, gas_budget, BTreeSet::new())
+                        .await?
+                        .1
+                        .object_ref(),
+                };
+                let gas_price = context.get_reference_gas_price().await?;
+                let data = TransactionData::new_programmable(
+                    signer,
+                    vec![gas],
+                    claim_outputs_pt(&outputs, signer)?,
+                    gas_budget,
+                    gas_price,
+                );
+                serialize_or_execute!(
+                    data,
+                    serialize_unsigned_transaction,
+                    serialize_signed_transaction,
+                    context,
+                    ClaimStardustOutputs
+                )
+            }
             IotaClientCommands::SplitCoin {
                 coin_id,
                 amounts,
@@ -1585,6 +1805,193 @@ impl IotaClientCommands {
                     SplitCoin
                 )
             }
+            IotaClientCommands::Stake {
+                validator,
+                coins,
+                amount,
+                timelocked_balance,
+                gas,
+                gas_budget,
+                serialize_unsigned_transaction,
+                serialize_signed_transaction,
+            } => {
+                let client = context.get_client().await?;
+                let data = match (coins, timelocked_balance) {
+                    (Some(coins), None) => {
+                        let first_coin = coins
+                            .first()
+                            .ok_or_else(|| anyhow!("At least one coin must be staked"))?;
+                        let signer = context.get_object_owner(first_coin).await?;
+                        client
+                            .transaction_builder()
+                            .request_add_stake(signer, coins, amount, validator, gas, gas_budget)
+                            .await?
+                    }
+                    (None, Some(timelocked_balance)) => {
+                        let signer = context.get_object_owner(&timelocked_balance).await?;
+                        let gas = match gas {
+                            Some(gas) => gas,
+                            None => {
+                                context
+                                    .gas_for_owner_budget(signer, gas_budget, BTreeSet::new())
+                                    .await?
+                                    .1
+                                    .object_id
+                            }
+                        };
+                        client
+                            .transaction_builder()
+                            .request_add_timelocked_stake(
+                                signer,
+                                timelocked_balance,
+                                validator,
+                                gas,
+                                gas_budget,
+                            )
+                            .await?
+                    }
+                    _ => {
+                        return Err(anyhow!(
+                            "Exactly one of `coins` and `timelocked-balance` must be present for stake command."
+                        ));
+                    }
+                };
+                serialize_or_execute!(
+                    data,
+                    serialize_unsigned_transaction,
+                    serialize_signed_transaction,
+                    context,
+                    Stake
+                )
+            }
+            IotaClientCommands::Stakes { address } => {
+                let address = get_identity_address(address, context)?;
+                let client = context.get_client().await?;
+                let governance_api = client.governance_api();
+                IotaClientCommandResult::Stakes(StakesOutput {
+                    stakes: governance_api.get_stakes(address).await?,
+                    timelocked_stakes: governance_api.get_timelocked_stakes(address).await?,
+                })
+            }
+            IotaClientCommands::StardustOutputs { address } => {
+                let address = get_identity_address(address, context)?;
+                let client = context.get_client().await?;
+                let stardust_api = client.stardust_api();
+                let timestamp_ms = stardust_api.get_unlock_timestamp_ms().await?;
+                let outputs = stardust_api
+                    .get_owned_outputs(address)
+                    .await?
+                    .into_iter()
+                    .map(|output| StardustOutputSummary {
+                        object_id: output.object_id(),
+                        type_: output.type_name().to_string(),
+                        coin_type: output.coin_type.to_canonical_string(true),
+                        balance: output.balance(),
+                        claimable_balance: output.claimable_balance(),
+                        native_tokens: output
+                            .native_tokens
+                            .iter()
+                            .map(|native_token| native_token.to_canonical_string(true))
+                            .collect(),
+                        storage_deposit_return: output.storage_deposit_return().cloned(),
+                        timelock: output.timelock().cloned(),
+                        expiration: output.expiration().cloned(),
+                        status: output.unlock_status(address, timestamp_ms),
+                    })
+                    .collect();
+                IotaClientCommandResult::StardustOutputs(StardustOutputsOutput {
+                    address,
+                    timestamp_ms,
+                    outputs,
+                })
+            }
+            IotaClientCommands::Unstake {
+                staked_iota_id,
+                gas,
+                gas_budget,
+                serialize_unsigned_transaction,
+                serialize_signed_transaction,
+            } => {
+                let signer = context.get_object_owner(&staked_iota_id).await?;
+                let client = context.get_client().await?;
+                let object_type = client
+                    .read_api()
+                    .get_object_with_options(
+                        staked_iota_id,
+                        IotaObjectDataOptions::new().with_type(),
+                    )
+                    .await?
+                    .into_object()?
+                    .object_type()?;
+                let data = match object_type {
+                    ObjectType::Struct(type_) if type_.is_staked_iota() => {
+                        client
+                            .transaction_builder()
+                            .request_withdraw_stake(signer, staked_iota_id, gas, gas_budget)
+                            .await?
+                    }
+                    ObjectType::Struct(type_) if type_.is_timelocked_staked_iota() => {
+                        let gas = match gas {
+                            Some(gas) => gas,
+                            None => {
+                                context
+                                    .gas_for_owner_budget(signer, gas_budget, BTreeSet::new())
+                                    .await?
+                                    .1
+                                    .object_id
+                            }
+                        };
+                        client
+                            .transaction_builder()
+                            .request_withdraw_timelocked_stake(
+                                signer,
+                                staked_iota_id,
+                                gas,
+                                gas_budget,
+                            )
+                            .await?
+                    }
+                    _ => bail!(
+                        "Object [{staked_iota_id}] is neither a StakedIota nor a TimelockedStakedIota object."
+                    ),
+                };
+                serialize_or_execute!(
+                    data,
+                    serialize_unsigned_transaction,
+                    serialize_signed_transaction,
+                    context,
+                    Unstake
+                )
+            }
+            IotaClientCommands::Validators => {
+                let client = context.get_client().await?;
+                let governance_api = client.governance_api();
+                let system_state = governance_api.get_latest_iota_system_state().await?;
+                let apys = governance_api
+                    .get_validators_apy()
+                    .await?
+                    .apys
+                    .into_iter()
+                    .map(|apy| (apy.address, apy.apy))
+                    .collect::<BTreeMap<_, _>>();
+                let validators = system_state
+                    .active_validators
+                    .into_iter()
+                    .map(|validator| ValidatorOutput {
+                        apy: apys.get(&validator.iota_address).copied(),
+                        name: validator.name,
+                        address: validator.iota_address,
+                        commission_rate: validator.commission_rate,
+                        gas_price: validator.gas_price,
+                        stake: validator.staking_pool_iota_balance,
+                        voting_power: validator.voting_power,
+                    })
+                    .collect();
+                IotaClientCommandResult::Validators(ValidatorsOutput {
+                    epoch: system_state.epoch,
+                    validators,
+                })
+            }
             IotaClientCommands::MergeCoin {
                 primary_coin,
                 coin_to_merge,
@@ -2130,9 +2537,93 @@ impl Display for IotaClientCommandResult {
             IotaClientCommandResult::ChainIdentifier(ci) => {
                 writeln!(writer, "{}", ci)?;
             }
+            IotaClientCommandResult::ClaimStardustOutputs(response) => {
+                write!(writer, "{}", response)?;
+            }
             IotaClientCommandResult::SplitCoin(response) => {
                 write!(writer, "{}", response)?;
             }
+            IotaClientCommandResult::Stake(response)
+            | IotaClientCommandResult::Unstake(response) => {
+                write!(writer, "{}", response)?;
+            }
+            IotaClientCommandResult::Stakes(stakes) => {
+                if stakes.stakes.is_empty() && stakes.timelocked_stakes.is_empty() {
+                    return write!(f, "No stakes found for this address.");
+                }
+                let mut builder = TableBuilder::default();
+                builder.set_header(vec![
+                    "stakedIotaId",
+                    "validator",
+                    "principal (NANOS)",
+                    "status",
+                    "estimatedReward (NANOS)",
+                    "activeEpoch",
+                    "expirationTimestampMs",
+                ]);
+                for delegated in &stakes.stakes {
+                    for stake in &delegated.stakes {
+                        builder.push_record(stake_record(
+                            stake.staked_iota_id,
+                            delegated.validator_address,
+                            stake.principal,
+                            &stake.status,
+                            stake.stake_active_epoch,
+                            None,
+                        ));
+                    }
+                }
+                for delegated in &stakes.timelocked_stakes {
+                    for stake in &delegated.stakes {
+                        builder.push_record(stake_record(
+                            stake.timelocked_staked_iota_id,
+                            delegated.validator_address,
+                            stake.principal,
+                            &stake.status,
+                            stake.stake_active_epoch,
+                            Some(stake.expiration_timestamp_ms),
+                        ));
+                    }
+                }
+                let mut table = builder.build();
+                table.with(TableStyle::rounded());
+                write!(f, "{}", table)?
+            }
+            IotaClientCommandResult::StardustOutputs(outputs) => {
+                if outputs.outputs.is_empty() {
+                    return write!(f, "No Stardust outputs found for this address.");
+                }
+                let mut builder = TableBuilder::default();
+                builder.set_header(vec![
+                    "objectId",
+                    "type",
+                    "balance (NANOS)",
+                    "claimable (NANOS)",
+                    "nativeTokens",
+                    "status",
+                ]);
+                for output in &outputs.outputs {
+                    builder.push_record(vec![
+                        output.object_id.to_string(),
+                        output.type_.clone(),
+                        output.balance.to_string(),
+                        output.claimable_balance.to_string(),
+                        output.native_tokens.len().to_string(),
+                        output.status.to_string(),
+                    ]);
+                }
+                let mut table = builder.build();
+                table.with(TablePanel::header(format!(
+                    "Stardust outputs of {} at {}",
+                    outputs.address, outputs.timestamp_ms
+                )));
+                table.with(TableStyle::rounded().horizontals([HorizontalLine::new(
+                    1,
+                    TableStyle::modern().get_horizontal(),
+                )]));
+                table.with(tabled::settings::style::BorderSpanCorrection);
+                write!(f, "{}", table)?
+            }
             IotaClientCommandResult::MergeCoin(response) => {
                 write!(writer, "{}", response)?;
             }
@@ -2170,6 +2661,44 @@ impl Display for IotaClientCommandResult {
                 table.with(TableStyle::rounded());
                 write!(f, "{}", table)?
             }
+            IotaClientCommandResult::Validators(validators) => {
+                let mut builder = TableBuilder::default();
+                builder.set_header(vec![
+                    "name",
+                    "address",
+                    "apy",
+                    "commissionRate",
+                    "gasPrice",
+                    "stake (NANOS)",
+                    "votingPower",
+                ]);
+                for validator in &validators.validators {
+                    builder.push_record(vec![
+                        validator.name.clone(),
+                        validator.address.to_string(),
+                        validator
+                            .apy
+                            .map(|apy| format!("{:.2}%", apy * 100.0))
+                            .unwrap_or_else(|| "-".to_string()),
+                        // The commission rate is in basis points.
+                        format!("{:.2}%", validator.commission_rate as f64 / 100.0),
+                        validator.gas_price.to_string(),
+                        validator.stake.to_string(),
+                        validator.voting_power.to_string(),
+                    ]);
+                }
+                let mut table = builder.build();
+                table.with(TablePanel::header(format!(
+                    "Active validators in epoch {}",
+                    validators.epoch
+                )));
+                table.with(TableStyle::rounded().horizontals([HorizontalLine::new(
+                    1,
+                    TableStyle::modern().get_horizontal(),
+                )]));
+                table.with(tabled::settings::style::BorderSpanCorrection);
+                write!(f, "{}", table)?
+            }
             IotaClientCommandResult::VerifySource => {
                 writeln!(writer, "Source verification succeeded!")?;
             }
@@ -2316,9 +2845,21 @@ impl IotaClientCommandResult {
     pub fn tx_block_response(&self) -> Option<&IotaTransactionBlockResponse> {
         use IotaClientCommandResult::*;
         match self {
-            Upgrade(b) | Publish(b) | TransactionBlock(b) | Call(b) | Transfer(b)
-            | TransferIota(b) | Pay(b) | PayIota(b) | PayAllIota(b) | SplitCoin(b)
-            | MergeCoin(b) | ExecuteSignedTx(b) => Some(b),
+            Upgrade(b)
+            | Publish(b)
+            | TransactionBlock(b)
+            | Call(b)
+            | Transfer(b)
+            | TransferIota(b)
+            | Pay(b)
+            | PayIota(b)
+            | PayAllIota(b)
+            | SplitCoin(b)
+            | MergeCoin(b)
+            | ExecuteSignedTx(b)
+            | Stake(b)
+            | Unstake(b)
+            | ClaimStardustOutputs(b) => Some(b),
             _ => None,
         }
     }
@@ -2453,6 +2994,7 @@ pub enum IotaClientCommandResult {
     Balance(Vec<(Option<IotaCoinMetadata>, Vec<Coin>)>, bool),
     Call(IotaTransactionBlockResponse),
     ChainIdentifier(String),
+    ClaimStardustOutputs(IotaTransactionBlockResponse),
     DynamicFieldQuery(DynamicFieldPage),
     Envs(Vec<IotaEnv>, Option<String>),
     ExecuteSignedTx(IotaTransactionBlockResponse),
@@ -2472,12 +3014,17 @@ pub enum IotaClientCommandResult {
     SerializedSignedTransaction(SenderSignedData),
     SerializedUnsignedTransaction(TransactionData),
     SplitCoin(IotaTransactionBlockResponse),
+    Stake(IotaTransactionBlockResponse),
+    Stakes(StakesOutput),
+    StardustOutputs(StardustOutputsOutput),
     Switch(SwitchResponse),
     SyncClientState,
     TransactionBlock(IotaTransactionBlockResponse),
     Transfer(IotaTransactionBlockResponse),
     TransferIota(IotaTransactionBlockResponse),
+    Unstake(IotaTransactionBlockResponse),
     Upgrade(IotaTransactionBlockResponse),
+    Validators(ValidatorsOutput),
     VerifyBytecodeMeter {
         max_module_ticks: u128,
         max_function_ticks: u128,
@@ -2487,6 +3034,62 @@ pub enum IotaClientCommandResult {
     VerifySource,
 }
 
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StakesOutput {
+    pub stakes: Vec<DelegatedStake>,
+    pub timelocked_stakes: Vec<DelegatedTimelockedStake>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StardustOutputsOutput {
+    pub address: IotaAddress,
+    /// The timestamp in milliseconds the unlock conditions are evaluated
+    /// against.
+    pub timestamp_ms: u64,
+    pub outputs: Vec<StardustOutputSummary>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StardustOutputSummary {
+    pub object_id: ObjectID,
+    #[serde(rename = "type")]
+    pub type_: String,
+    pub coin_type: String,
+    pub balance: u64,
+    /// The balance without the storage deposit to return.
+    pub claimable_balance: u64,
+    pub native_tokens: Vec<String>,
+    pub storage_deposit_return: Option<StorageDepositReturnUnlockCondition>,
+    pub timelock: Option<TimelockUnlockCondition>,
+    pub expiration: Option<ExpirationUnlockCondition>,
+    pub status: UnlockStatus,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidatorsOutput {
+    pub epoch: EpochId,
+    pub validators: Vec<ValidatorOutput>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidatorOutput {
+    pub name: String,
+    pub address: IotaAddress,
+    /// The APY of the validator in the current epoch, if known.
+    pub apy: Option<f64>,
+    /// The commission rate in basis points.
+    pub commission_rate: u64,
+    pub gas_price: u64,
+    /// The IOTA balance of the staking pool in NANOS.
+    pub stake: u64,
+    pub voting_power: u64,
+}
+
 #[derive(Serialize, Clone)]
 pub struct SwitchResponse {
     /// Active address
@@ -2545,6 +3148,32 @@ pub async fn request_tokens_from_faucet(
     Ok(())
 }
 
+fn stake_record(
+    staked_iota_id: ObjectID,
+    validator: IotaAddress,
+    principal: u64,
+    status: &StakeStatus,
+    active_epoch: EpochId,
+    expiration_timestamp_ms: Option<u64>,
+) -> Vec<String> {
+    let (status, estimated_reward) = match status {
+        StakeStatus::Pending => ("Pending", "-".to_string()),
+        StakeStatus::Active { estimated_reward } => ("Active", estimated_reward.to_string()),
+        StakeStatus::Unstaked => ("Unstaked", "-".to_string()),
+    };
+    vec![
+        staked_iota_id.to_string(),
+        validator.to_string(),
+        principal.to_string(),
+        status.to_string(),
+        estimated_reward,
+        active_epoch.to_string(),
+        expiration_timestamp_ms
+            .map(|timestamp| timestamp.to_string())
+            .unwrap_or_else(|| "-".to_string()),
+    ]
+}
+
 fn pretty_print_balance(
     coins_by_type: &Vec<(Option<IotaCoinMetadata>, Vec<Coin>)>,
     builder: &mut TableBuilder,
@@ -42,7 +42,7 @@ use crate::{
     console::start_console,
     fire_drill::{run_fire_drill, FireDrill},
     genesis_ceremony::{run, Ceremony},
-    keytool::KeyToolCommand,
+    keytool::{read_keystore_passphrase, KeyToolCommand},
     validator_commands::IotaValidatorCommand,
 };
 
@@ -304,7 +304,7 @@ impl IotaCommand {
             IotaCommand::Console { config } => {
                 let config = config.unwrap_or(iota_config_dir()?.join(IOTA_CLIENT_CONFIG));
                 prompt_if_no_config(&config, false).await?;
-                let context = WalletContext::new(&config, None, None)?;
+                let context = get_wallet_context(&config)?;
                 start_console(context, &mut stdout(), &mut stderr()).await
             }
             IotaCommand::Client {
@@ -315,7 +315,7 @@ impl IotaCommand {
             } => {
                 let config_path = config.unwrap_or(iota_config_dir()?.join(IOTA_CLIENT_CONFIG));
                 prompt_if_no_config(&config_path, accept_defaults).await?;
-                let mut context = WalletContext::new(&config_path, None, None)?;
+                let mut context = get_wallet_context(&config_path)?;
                 if let Some(cmd) = cmd {
                     cmd.execute(&mut context).await?.print(!json);
                 } else {
@@ -334,7 +334,7 @@ impl IotaCommand {
             } => {
                 let config_path = config.unwrap_or(iota_config_dir()?.join(IOTA_CLIENT_CONFIG));
                 prompt_if_no_config(&config_path, accept_defaults).await?;
-                let mut context = WalletContext::new(&config_path, None, None)?;
+                let mut context = get_wallet_context(&config_path)?;
                 if let Some(cmd) = cmd {
                     cmd.execute(&mut context).await?.print(!json);
                 } else {
@@ -708,6 +708,16 @@ async fn prompt_if_no_config(
     Ok(())
 }
 
+/// Creates the wallet context, prompting for the passphrase of an encrypted
+/// keystore unless it is provided by the environment.
+fn get_wallet_context(config_path: &Path) -> Result<WalletContext, anyhow::Error> {
+    let mut context = WalletContext::new(config_path, None, None)?;
+    if context.is_keystore_locked() {
+        context.unlock_keystore(&read_keystore_passphrase()?)?;
+    }
+    Ok(context)
+}
+
 fn read_line() -> Result<String, anyhow::Error> {
     let mut s = String::new();
     let _ = stdout().flush();
@@ -23,7 +23,9 @@ use fastcrypto::{
     secp256k1::recoverable::Secp256k1Sig,
     traits::{KeyPair, ToFromBytes},
 };
+use inquire::Password;
 use iota_keys::{
+    encryption::IOTA_KEYSTORE_PASSPHRASE_ENV,
     key_derive::generate_new_key,
     keypair_file::{
         read_authority_keypair_from_file, read_keypair_from_file, write_authority_keypair_to_file,
@@ -45,7 +47,7 @@ use iota_types::{
 use json_to_table::{json_to_table, Orientation};
 use serde::Serialize;
 use serde_json::json;
-use shared_crypto::intent::{Intent, IntentMessage};
+use shared_crypto::intent::{Intent, IntentMessage, IntentScope, PersonalMessage};
 use tabled::{
     builder::Builder,
     settings::{object::Rows, Modify, Rotate, Width},
@@ -70,6 +72,11 @@ pub enum KeyToolCommand {
         /// digits, dots, hyphens (-), or underscores (_).
         new_alias: Option<String>,
     },
+    /// Encrypt the keystore file with a new passphrase, which is prompted for.
+    /// A plaintext keystore is migrated to an encrypted one, the passphrase
+    /// of an encrypted keystore is changed. The current passphrase can be
+    /// provided with the `IOTA_KEYSTORE_PASSPHRASE` environment variable.
+    ChangePassphrase,
     /// Convert private key in Hex or Base64 to new format (Bech32
     /// encoded 33 byte flag || private key starting with "iotaprivkey").
     /// Hex private key format import and export are both deprecated in
@@ -179,6 +186,33 @@ pub enum KeyToolCommand {
         #[clap(long)]
         threshold: ThresholdUnit,
     },
+    /// Given a Base64 encoded passkey signature, decode its components. If
+    /// `bytes` is provided, parse it as either TransactionData or
+    /// PersonalMessage based on `intent_scope` and verify the signature
+    /// against it. Example request: iota keytool passkey-sig-verify --sig
+    /// $SERIALIZED_PASSKEY_SIG --bytes $BYTES --intent-scope 3
+    PasskeySigVerify {
+        /// The Base64 of the serialized passkey signature.
+        #[clap(long)]
+        sig: GenericSignature,
+        /// The Base64 of the BCS encoded TransactionData or PersonalMessage.
+        #[clap(long)]
+        bytes: Option<String>,
+        /// Either 0 for TransactionData or 3 for PersonalMessage.
+        #[clap(long, default_value = "0")]
+        intent_scope: u8,
+    },
+    /// Register a key held by the external signer of the keystore, given its
+    /// key id on the signer. Only available when the keystore is backed by an
+    /// external signer.
+    RegisterExternalKey {
+        /// Sets an alias for this address. The alias must start with a letter
+        /// and can contain only letters, digits, hyphens (-), or underscores
+        /// (_).
+        #[clap(long)]
+        alias: Option<String>,
+        key_id: String,
+    },
 
     /// Read the content at the provided file path. The accepted format can be
     /// [enum IotaKeyPair] (Base64 encoded of 33-byte `flag || privkey`) or
@@ -381,6 +415,20 @@ pub struct MultiSigOutput {
     weight: u8,
 }
 
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PasskeySigVerifyOutput {
+    iota_address: IotaAddress,
+    public_base64_key: String,
+    // Hex encoded authenticator data returned by the authenticator.
+    authenticator_data: String,
+    client_data_json: String,
+    // Hex encoded challenge from the client data, this is the digest of the
+    // intent message the passkey signed.
+    challenge: String,
+    result: Option<IotaResult>,
+}
+
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ConvertOutput {
@@ -446,6 +494,7 @@ pub struct SignData {
 #[serde(untagged)]
 pub enum CommandOutput {
     Alias(AliasUpdate),
+    ChangePassphrase(String),
     Convert(ConvertOutput),
     DecodeMultiSig(DecodedMultiSigOutput),
     DecodeOrVerifyTx(DecodeOrVerifyTxOutput),
@@ -457,7 +506,9 @@ pub enum CommandOutput {
     LoadKeypair(KeypairData),
     MultiSigAddress(MultiSigAddress),
     MultiSigCombinePartialSig(MultiSigCombinePartialSig),
+    PasskeySigVerify(PasskeySigVerifyOutput),
     PrivateKeyBase64(PrivateKeyBase64),
+    RegisterExternalKey(Key),
     Show(Key),
     Sign(SignData),
     SignKMS(SerializedSig),
@@ -468,6 +519,9 @@ pub enum CommandOutput {
 
 impl KeyToolCommand {
     pub async fn execute(self, keystore: &mut Keystore) -> Result<CommandOutput, anyhow::Error> {
+        if keystore.is_locked() && self.requires_private_keys() {
+            keystore.unlock(&read_keystore_passphrase()?)?;
+        }
         let cmd_result = Ok(match self {
             KeyToolCommand::Alias {
                 old_alias,
@@ -479,6 +533,18 @@ impl KeyToolCommand {
                     new_alias,
                 })
             }
+            KeyToolCommand::ChangePassphrase => {
+                let Keystore::File(file) = keystore else {
+                    return Err(anyhow!("Only file based keystores can be encrypted"));
+                };
+                let was_encrypted = file.is_encrypted();
+                file.set_passphrase(&read_new_keystore_passphrase()?)?;
+                CommandOutput::ChangePassphrase(if was_encrypted {
+                    "The passphrase of the keystore was changed".to_string()
+                } else {
+                    "The keystore was encrypted with the passphrase".to_string()
+                })
+            }
             KeyToolCommand::Convert { value } => {
                 let result = convert_private_key_to_bech32(value)?;
                 CommandOutput::Convert(result)
@@ -529,7 +595,10 @@ impl KeyToolCommand {
                         &IntentMessage::new(Intent::iota_transaction(), tx_data),
                         address,
                         None,
-                        &VerifyParams::default(),
+                        &VerifyParams {
+                            accept_passkey_in_multisig: true,
+                            ..Default::default()
+                        },
                     );
                     output.transaction_result = format!("{:?}", res);
                 };
@@ -732,6 +801,73 @@ impl KeyToolCommand {
                 })
             }
 
+            KeyToolCommand::PasskeySigVerify {
+                sig,
+                bytes,
+                intent_scope,
+            } => {
+                let iota_address = IotaAddress::try_from(&sig)?;
+                let GenericSignature::PasskeyAuthenticator(passkey) = sig else {
+                    return Err(anyhow!("Not a passkey signature"));
+                };
+
+                let result = match bytes {
+                    None => None,
+                    Some(bytes) => {
+                        let bytes = Base64::decode(&bytes)
+                            .map_err(|e| anyhow!("Invalid base64 bytes: {:?}", e))?;
+                        let res = match IntentScope::try_from(intent_scope)
+                            .map_err(|_| anyhow!("Invalid scope"))?
+                        {
+                            IntentScope::TransactionData => {
+                                let tx_data: TransactionData = bcs::from_bytes(&bytes)?;
+                                passkey.verify_authenticator(
+                                    &IntentMessage::new(
+                                        Intent::iota_transaction(),
+                                        tx_data.clone(),
+                                    ),
+                                    tx_data.sender(),
+                                    None,
+                                    &VerifyParams::default(),
+                                )
+                            }
+                            IntentScope::PersonalMessage => {
+                                let data: PersonalMessage = bcs::from_bytes(&bytes)?;
+                                passkey.verify_authenticator(
+                                    &IntentMessage::new(Intent::personal_message(), data),
+                                    iota_address,
+                                    None,
+                                    &VerifyParams::default(),
+                                )
+                            }
+                            _ => return Err(anyhow!("Unsupported scope {intent_scope}")),
+                        };
+                        Some(res)
+                    }
+                };
+
+                CommandOutput::PasskeySigVerify(PasskeySigVerifyOutput {
+                    iota_address,
+                    public_base64_key: passkey.get_pk()?.encode_base64(),
+                    authenticator_data: Hex::encode(passkey.authenticator_data()),
+                    client_data_json: passkey.client_data_json().to_string(),
+                    challenge: Hex::encode(passkey.challenge()?),
+                    result,
+                })
+            }
+
+            KeyToolCommand::RegisterExternalKey { alias, key_id } => {
+                let Keystore::External(external) = keystore else {
+                    return Err(anyhow!(
+                        "The keystore is not backed by an external signer"
+                    ));
+                };
+                info!("Registering key {key_id} of the external signer");
+                let address = external.register_key(&key_id, alias)?;
+                let mut key = Key::from(external.external_key(&address)?.public_key()?);
+                key.alias = Some(external.get_alias_by_address(&address)?);
+                CommandOutput::RegisterExternalKey(key)
+            }
             KeyToolCommand::Show { file } => {
                 let res = read_keypair_from_file(&file);
                 match res {
@@ -1117,6 +1253,18 @@ impl KeyToolCommand {
 
         cmd_result
     }
+
+    /// Whether the command needs the private keys of the keystore, so an
+    /// encrypted keystore must be unlocked.
+    fn requires_private_keys(&self) -> bool {
+        matches!(
+            self,
+            KeyToolCommand::ChangePassphrase
+                | KeyToolCommand::Export { .. }
+                | KeyToolCommand::Import { .. }
+                | KeyToolCommand::Sign { .. }
+        )
+    }
 }
 
 // pub async fn fetch_jwks(
@@ -1174,6 +1322,7 @@ impl Display for CommandOutput {
                     update.old_alias, update.new_alias
                 )
             }
+            CommandOutput::ChangePassphrase(message) => write!(formatter, "{message}"),
             // Sign needs to be manually built because we need to wrap the very long
             // rawTxData string and rawIntentMsg strings into multiple rows due to
             // their lengths, which we cannot do with a JsonTable
@@ -1246,6 +1395,24 @@ impl Debug for CommandOutput {
     }
 }
 
+/// Reads the passphrase of an encrypted keystore from the
+/// `IOTA_KEYSTORE_PASSPHRASE` environment variable, or prompts for it.
+pub fn read_keystore_passphrase() -> Result<String, anyhow::Error> {
+    if let Ok(passphrase) = std::env::var(IOTA_KEYSTORE_PASSPHRASE_ENV) {
+        return Ok(passphrase);
+    }
+    Ok(Password::new("Keystore passphrase:")
+        .without_confirmation()
+        .prompt()?)
+}
+
+fn read_new_keystore_passphrase() -> Result<String, anyhow::Error> {
+    Ok(Password::new("New keystore passphrase:")
+        .with_custom_confirmation_message("Confirm the new keystore passphrase:")
+        .with_custom_confirmation_error_message("The passphrases don't match")
+        .prompt()?)
+}
+
 /// Converts legacy formatted private key to 33 bytes bech32 encoded private key
 /// or vice versa. It can handle:
 /// 1) Hex encoded 32 byte private key (assumes scheme is Ed25519), this is the
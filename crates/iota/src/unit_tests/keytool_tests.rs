@@ -18,10 +18,12 @@ use iota_types::{
         EncodeDecodeBase64, IotaKeyPair, IotaSignatureInner, Secp256k1IotaSignature,
         Secp256r1IotaSignature, Signature, SignatureScheme,
     },
+    signature::GenericSignature,
     transaction::{TransactionData, TEST_ONLY_GAS_UNIT_FOR_TRANSFER},
+    utils::{get_passkey_user_key, make_passkey_tx, sign_with_passkey},
 };
 use rand::{rngs::StdRng, SeedableRng};
-use shared_crypto::intent::{Intent, IntentScope};
+use shared_crypto::intent::{Intent, IntentMessage, IntentScope, PersonalMessage};
 use tempfile::TempDir;
 use tokio::test;
 
@@ -614,3 +616,60 @@ async fn test_sign_command() -> Result<(), anyhow::Error> {
     .await?;
     Ok(())
 }
+
+#[test]
+async fn test_passkey_sig_verify() -> Result<(), anyhow::Error> {
+    let mut keystore = Keystore::from(InMemKeystore::new_insecure_for_tests(0));
+
+    // Decode and verify a passkey signature over a transaction.
+    let (address, tx, sig) = make_passkey_tx();
+    let output = KeyToolCommand::PasskeySigVerify {
+        sig,
+        bytes: Some(Base64::encode(bcs::to_bytes(tx.transaction_data())?)),
+        intent_scope: IntentScope::TransactionData as u8,
+    }
+    .execute(&mut keystore)
+    .await?;
+    let CommandOutput::PasskeySigVerify(output) = output else {
+        panic!("Expected a passkey signature verification output");
+    };
+    assert_eq!(output.iota_address, address);
+    assert!(output.result.unwrap().is_ok());
+
+    // Verify a passkey signature over a personal message.
+    let message = PersonalMessage {
+        message: b"hello".to_vec(),
+    };
+    let sig = GenericSignature::PasskeyAuthenticator(sign_with_passkey(
+        &get_passkey_user_key(),
+        &IntentMessage::new(Intent::personal_message(), message.clone()),
+    ));
+    let output = KeyToolCommand::PasskeySigVerify {
+        sig: sig.clone(),
+        bytes: Some(Base64::encode(bcs::to_bytes(&message)?)),
+        intent_scope: IntentScope::PersonalMessage as u8,
+    }
+    .execute(&mut keystore)
+    .await?;
+    let CommandOutput::PasskeySigVerify(output) = output else {
+        panic!("Expected a passkey signature verification output");
+    };
+    assert!(output.result.unwrap().is_ok());
+
+    // The signature does not verify against a different message.
+    let other = PersonalMessage {
+        message: b"goodbye".to_vec(),
+    };
+    let output = KeyToolCommand::PasskeySigVerify {
+        sig,
+        bytes: Some(Base64::encode(bcs::to_bytes(&other)?)),
+        intent_scope: IntentScope::PersonalMessage as u8,
+    }
+    .execute(&mut keystore)
+    .await?;
+    let CommandOutput::PasskeySigVerify(output) = output else {
+        panic!("Expected a passkey signature verification output");
+    };
+    assert!(output.result.unwrap().is_err());
+    Ok(())
+}
@@ -48,7 +48,7 @@ use iota_types::{
     transaction::{
         TEST_ONLY_GAS_UNIT_FOR_GENERIC, TEST_ONLY_GAS_UNIT_FOR_OBJECT_BASICS,
         TEST_ONLY_GAS_UNIT_FOR_PUBLISH, TEST_ONLY_GAS_UNIT_FOR_SPLIT_COIN,
-        TEST_ONLY_GAS_UNIT_FOR_TRANSFER,
+        TEST_ONLY_GAS_UNIT_FOR_STAKING, TEST_ONLY_GAS_UNIT_FOR_TRANSFER,
     },
 };
 use move_package::BuildConfig as MoveBuildConfig;
@@ -2794,6 +2794,136 @@ async fn test_stake_with_u64_amount() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+#[sim_test]
+async fn test_stake_and_unstake_commands() -> Result<(), anyhow::Error> {
+    let mut test_cluster = TestClusterBuilder::new().build().await;
+    let rgp = test_cluster.get_reference_gas_price().await;
+    let address = test_cluster.get_address_0();
+    let context = &mut test_cluster.wallet;
+
+    let client = context.get_client().await?;
+    let coins = client
+        .coin_read_api()
+        .get_coins(address, None, None, None)
+        .await?
+        .data;
+
+    let validators = IotaClientCommands::Validators.execute(context).await?;
+    let IotaClientCommandResult::Validators(validators) = validators else {
+        panic!("Unexpected result for validators command: {validators:?}");
+    };
+    assert!(!validators.validators.is_empty());
+    let validator_addr = validators.validators[0].address;
+
+    let resp = IotaClientCommands::Stake {
+        validator: validator_addr,
+        coins: Some(vec![coins.first().unwrap().coin_object_id]),
+        amount: Some(1_000_000_000),
+        timelocked_balance: None,
+        gas: Some(coins.get(1).unwrap().coin_object_id),
+        gas_budget: rgp * TEST_ONLY_GAS_UNIT_FOR_STAKING,
+        serialize_unsigned_transaction: false,
+        serialize_signed_transaction: false,
+    }
+    .execute(context)
+    .await?;
+    let IotaClientCommandResult::Stake(response) = resp else {
+        panic!("Unexpected result for stake command: {resp:?}");
+    };
+    assert!(response.status_ok().unwrap());
+
+    let resp = IotaClientCommands::Stakes { address: None }
+        .execute(context)
+        .await?;
+    let IotaClientCommandResult::Stakes(stakes) = resp else {
+        panic!("Unexpected result for stakes command: {resp:?}");
+    };
+    assert_eq!(1, stakes.stakes.len());
+    assert!(stakes.timelocked_stakes.is_empty());
+    let stake = stakes.stakes[0].stakes.first().unwrap();
+    assert_eq!(validator_addr, stakes.stakes[0].validator_address);
+    assert_eq!(1_000_000_000, stake.principal);
+
+    let resp = IotaClientCommands::Unstake {
+        staked_iota_id: stake.staked_iota_id,
+        gas: None,
+        gas_budget: rgp * TEST_ONLY_GAS_UNIT_FOR_STAKING,
+        serialize_unsigned_transaction: false,
+        serialize_signed_transaction: false,
+    }
+    .execute(context)
+    .await?;
+    let IotaClientCommandResult::Unstake(response) = resp else {
+        panic!("Unexpected result for unstake command: {resp:?}");
+    };
+    assert!(response.status_ok().unwrap());
+
+    assert!(client
+        .governance_api()
+        .get_stakes(address)
+        .await?
+        .is_empty());
+    Ok(())
+}
+
+#[sim_test]
+async fn test_stardust_outputs_commands() -> Result<(), anyhow::Error> {
+    let mut test_cluster = TestClusterBuilder::new().build().await;
+    let rgp = test_cluster.get_reference_gas_price().await;
+    let address = test_cluster.get_address_0();
+    let context = &mut test_cluster.wallet;
+
+    // The test cluster genesis holds no migrated outputs.
+    let resp = IotaClientCommands::StardustOutputs { address: None }
+        .execute(context)
+        .await?;
+    let IotaClientCommandResult::StardustOutputs(outputs) = resp else {
+        panic!("Unexpected result for stardust-outputs command: {resp:?}");
+    };
+    assert_eq!(outputs.address, address);
+    assert!(outputs.outputs.is_empty());
+
+    let err = IotaClientCommands::ClaimStardustOutputs {
+        outputs: None,
+        gas: None,
+        gas_budget: rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER,
+        serialize_unsigned_transaction: false,
+        serialize_signed_transaction: false,
+    }
+    .execute(context)
+    .await
+    .unwrap_err();
+    assert!(
+        err.to_string().contains("No unlocked Stardust outputs"),
+        "{err}"
+    );
+
+    // A coin is not a Stardust output.
+    let coin = context
+        .get_client()
+        .await?
+        .coin_read_api()
+        .get_coins(address, None, None, None)
+        .await?
+        .data[0]
+        .coin_object_id;
+    let err = IotaClientCommands::ClaimStardustOutputs {
+        outputs: Some(vec![coin]),
+        gas: None,
+        gas_budget: rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER,
+        serialize_unsigned_transaction: false,
+        serialize_signed_transaction: false,
+    }
+    .execute(context)
+    .await
+    .unwrap_err();
+    assert!(
+        err.to_string().contains("is not a Stardust output"),
+        "{err}"
+    );
+    Ok(())
+}
+
 async fn test_with_iota_binary(args: &[&str]) -> Result<(), anyhow::Error> {
     let mut cmd = assert_cmd::Command::cargo_bin("iota").unwrap();
     let args = args.iter().map(|s| s.to_string()).collect::<Vec<_>>();
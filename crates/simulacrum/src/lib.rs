@@ -414,9 +414,9 @@ impl<T, V: store::SimulatorStore> ObjectStore for Simulacrum<T, V> {
 impl<T, V: store::SimulatorStore> ReadStore for Simulacrum<T, V> {
     fn get_committee(
         &self,
-        _epoch: iota_types::committee::EpochId,
+        epoch: iota_types::committee::EpochId,
     ) -> iota_types::storage::error::Result<Option<std::sync::Arc<Committee>>> {
-        todo!()
+        Ok(self.store().get_committee_by_epoch(epoch).map(Arc::new))
     }
 
     fn get_latest_checkpoint(&self) -> iota_types::storage::error::Result<VerifiedCheckpoint> {
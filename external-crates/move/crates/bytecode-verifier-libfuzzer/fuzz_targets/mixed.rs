@@ -83,6 +83,7 @@ fuzz_target!(|mix: Mixed| {
     let code_unit = CodeUnit {
         code: mix.code,
         locals: SignatureIndex(0),
+        jump_tables: vec![],
     };
 
     let fun_def = FunctionDefinition {
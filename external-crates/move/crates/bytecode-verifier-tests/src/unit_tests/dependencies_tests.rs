@@ -55,6 +55,7 @@ fn mk_script_function_module() -> CompiledModule {
                 code: Some(CodeUnit {
                     locals: SignatureIndex(0),
                     code: vec![Bytecode::Ret],
+                    jump_tables: vec![],
                 }),
             },
             // public(script) fun g_fn<T>() { return; }
@@ -66,6 +67,7 @@ fn mk_script_function_module() -> CompiledModule {
                 code: Some(CodeUnit {
                     locals: SignatureIndex(0),
                     code: vec![Bytecode::Ret],
+                    jump_tables: vec![],
                 }),
             },
         ],
@@ -81,6 +83,10 @@ fn mk_script_function_module() -> CompiledModule {
         struct_def_instantiations: vec![],
         function_instantiations: vec![],
         field_instantiations: vec![],
+        enum_defs: vec![],
+        enum_def_instantiations: vec![],
+        variant_handles: vec![],
+        variant_instantiation_handles: vec![],
     };
     move_bytecode_verifier::verify_module_unmetered(&m).unwrap();
     m
@@ -158,6 +164,7 @@ fn mk_invoking_module(use_generic: bool, valid: bool) -> CompiledModule {
                 code: Some(CodeUnit {
                     locals: SignatureIndex(0),
                     code: vec![call, Bytecode::Ret],
+                    jump_tables: vec![],
                 }),
             },
         ],
@@ -173,6 +180,10 @@ fn mk_invoking_module(use_generic: bool, valid: bool) -> CompiledModule {
         friend_decls: vec![],
         struct_def_instantiations: vec![],
         field_instantiations: vec![],
+        enum_defs: vec![],
+        enum_def_instantiations: vec![],
+        variant_handles: vec![],
+        variant_instantiation_handles: vec![],
     };
     move_bytecode_verifier::verify_module_unmetered(&m).unwrap();
     m
@@ -229,6 +240,7 @@ fn mk_invoking_script(use_generic: bool) -> CompiledScript {
         code: CodeUnit {
             locals: SignatureIndex(0),
             code: vec![call, Bytecode::Ret],
+            jump_tables: vec![],
         },
         signatures: vec![
             Signature(vec![]),                    // void
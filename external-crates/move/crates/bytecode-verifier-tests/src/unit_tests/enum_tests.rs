@@ -0,0 +1,89 @@
+// Copyright (c) 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use move_binary_format::file_format::{
+    basic_enum_test_module, Bytecode, CompiledModule, EnumDefinitionIndex, JumpTableInner,
+    VariantJumpTable,
+};
+use move_bytecode_verifier::verify_module_with_config_unmetered;
+use move_core_types::vm_status::StatusCode;
+
+use crate::unit_tests::production_config;
+
+fn verify(module: &CompiledModule) -> Result<(), StatusCode> {
+    verify_module_with_config_unmetered(&production_config(), module)
+        .map_err(|err| err.major_status())
+}
+
+fn code_mut(module: &mut CompiledModule, fun: usize) -> &mut Vec<Bytecode> {
+    &mut module.function_defs[fun].code.as_mut().unwrap().code
+}
+
+#[test]
+fn test_enum_module() {
+    assert_eq!(verify(&basic_enum_test_module()), Ok(()));
+}
+
+#[test]
+fn test_enums_disabled() {
+    let mut config = production_config();
+    config.enable_enums = false;
+    let status = verify_module_with_config_unmetered(&config, &basic_enum_test_module())
+        .unwrap_err()
+        .major_status();
+    assert_eq!(status, StatusCode::FEATURE_UNDER_GATING);
+}
+
+#[test]
+fn test_variant_tag_out_of_bounds() {
+    let mut m = basic_enum_test_module();
+    m.variant_handles[1].variant = 2;
+    assert_eq!(verify(&m), Err(StatusCode::INDEX_OUT_OF_BOUNDS));
+}
+
+#[test]
+fn test_jump_table_missing_variant() {
+    let mut m = basic_enum_test_module();
+    m.function_defs[0].code.as_mut().unwrap().jump_tables[0].jump_table =
+        JumpTableInner::Full(vec![10]);
+    assert_eq!(verify(&m), Err(StatusCode::INVALID_ENUM_SWITCH));
+}
+
+#[test]
+fn test_unused_jump_table() {
+    let mut m = basic_enum_test_module();
+    m.function_defs[1]
+        .code
+        .as_mut()
+        .unwrap()
+        .jump_tables
+        .push(VariantJumpTable {
+            head_enum: EnumDefinitionIndex(1),
+            jump_table: JumpTableInner::Full(vec![5]),
+        });
+    assert_eq!(verify(&m), Err(StatusCode::UNUSED_JUMP_TABLE));
+}
+
+#[test]
+fn test_pack_variant_type_mismatch() {
+    let mut m = basic_enum_test_module();
+    // `E::A { x: true }`
+    code_mut(&mut m, 0)[2] = Bytecode::LdTrue;
+    assert_eq!(verify(&m), Err(StatusCode::PACK_TYPE_MISMATCH_ERROR));
+}
+
+#[test]
+fn test_variant_switch_on_value() {
+    let mut m = basic_enum_test_module();
+    // the switch needs a reference to the enum
+    code_mut(&mut m, 0)[8] = Bytecode::CopyLoc(2);
+    assert_eq!(verify(&m), Err(StatusCode::ENUM_SWITCH_BAD_OPERAND));
+}
+
+#[test]
+fn test_unpack_variant_by_value_from_reference() {
+    let mut m = basic_enum_test_module();
+    // `UnpackVariant` needs the enum by value
+    code_mut(&mut m, 0)[10] = Bytecode::ImmBorrowLoc(2);
+    assert_eq!(verify(&m), Err(StatusCode::UNPACK_TYPE_MISMATCH_ERROR));
+}
@@ -140,6 +140,7 @@ fn make_module() -> CompiledModule {
                 code: Some(CodeUnit {
                     locals: SignatureIndex(0),
                     code: vec![Bytecode::Ret],
+                    jump_tables: vec![],
                 }),
             },
             // fun g_fn<T>() { return; }
@@ -151,6 +152,7 @@ fn make_module() -> CompiledModule {
                 code: Some(CodeUnit {
                     locals: SignatureIndex(0),
                     code: vec![Bytecode::Ret],
+                    jump_tables: vec![],
                 }),
             },
             // fun test_fn() { ... } - tests will fill up the code
@@ -162,6 +164,7 @@ fn make_module() -> CompiledModule {
                 code: Some(CodeUnit {
                     locals: SignatureIndex(0),
                     code: vec![],
+                    jump_tables: vec![],
                 }),
             },
         ],
@@ -182,6 +185,10 @@ fn make_module() -> CompiledModule {
         struct_def_instantiations: vec![],
         function_instantiations: vec![],
         field_instantiations: vec![],
+        enum_defs: vec![],
+        enum_def_instantiations: vec![],
+        variant_handles: vec![],
+        variant_instantiation_handles: vec![],
     }
 }
 
@@ -195,6 +202,7 @@ fn generic_call_to_non_generic_func() {
             Bytecode::CallGeneric(FunctionInstantiationIndex(0)),
             Bytecode::Ret,
         ],
+        jump_tables: vec![],
     });
     module.function_instantiations.push(FunctionInstantiation {
         handle: FunctionHandleIndex(0),
@@ -216,6 +224,7 @@ fn non_generic_call_to_generic_func() {
     module.function_defs[2].code = Some(CodeUnit {
         locals: SignatureIndex(0),
         code: vec![Bytecode::Call(FunctionHandleIndex(1)), Bytecode::Ret],
+        jump_tables: vec![],
     });
     let err = InstructionConsistency::verify_module(&module)
         .expect_err("Call to generic function must fail");
@@ -237,6 +246,7 @@ fn generic_pack_on_non_generic_struct() {
             Bytecode::Pop,
             Bytecode::Ret,
         ],
+        jump_tables: vec![],
     });
     module
         .struct_def_instantiations
@@ -265,6 +275,7 @@ fn non_generic_pack_on_generic_struct() {
             Bytecode::Pop,
             Bytecode::Ret,
         ],
+        jump_tables: vec![],
     });
     let err = InstructionConsistency::verify_module(&module)
         .expect_err("Pack to generic struct must fail");
@@ -287,6 +298,7 @@ fn generic_unpack_on_non_generic_struct() {
             Bytecode::Pop,
             Bytecode::Ret,
         ],
+        jump_tables: vec![],
     });
     module
         .struct_def_instantiations
@@ -316,6 +328,7 @@ fn non_generic_unpack_on_generic_struct() {
             Bytecode::Pop,
             Bytecode::Ret,
         ],
+        jump_tables: vec![],
     });
     module
         .struct_def_instantiations
@@ -345,6 +358,7 @@ fn generic_mut_borrow_field_on_non_generic_struct() {
             Bytecode::Pop,
             Bytecode::Ret,
         ],
+        jump_tables: vec![],
     });
     module.field_instantiations.push(FieldInstantiation {
         handle: FieldHandleIndex(0),
@@ -376,6 +390,7 @@ fn non_generic_mut_borrow_field_on_generic_struct() {
             Bytecode::Pop,
             Bytecode::Ret,
         ],
+        jump_tables: vec![],
     });
     module
         .struct_def_instantiations
@@ -409,6 +424,7 @@ fn generic_borrow_field_on_non_generic_struct() {
             Bytecode::Pop,
             Bytecode::Ret,
         ],
+        jump_tables: vec![],
     });
     module.field_instantiations.push(FieldInstantiation {
         handle: FieldHandleIndex(0),
@@ -440,6 +456,7 @@ fn non_generic_borrow_field_on_generic_struct() {
             Bytecode::Pop,
             Bytecode::Ret,
         ],
+        jump_tables: vec![],
     });
     module
         .struct_def_instantiations
@@ -240,8 +240,13 @@ fn big_vec_unpacks() {
             code: Some(CodeUnit {
                 locals: SignatureIndex(0),
                 code,
+                jump_tables: vec![],
             }),
         }],
+        enum_defs: vec![],
+        enum_def_instantiations: vec![],
+        variant_handles: vec![],
+        variant_instantiation_handles: vec![],
     };
 
     // save module and verify that it can ser/de
@@ -883,6 +888,7 @@ fn multi_functions(module: &mut CompiledModule, count: usize) {
             code: Some(CodeUnit {
                 locals: SignatureIndex((module.signatures.len() - 1) as u16),
                 code: vec![Bytecode::Ret],
+                jump_tables: vec![],
             }),
         });
     }
@@ -15,6 +15,7 @@ pub mod constants_tests;
 pub mod control_flow_tests;
 pub mod dependencies_tests;
 pub mod duplication_tests;
+pub mod enum_tests;
 pub mod generic_ops_tests;
 pub mod large_type_test;
 pub mod limit_tests;
@@ -55,5 +56,6 @@ pub(crate) fn production_config() -> VerifierConfig {
         max_identifier_len: Some(DEFAULT_MAX_IDENTIFIER_LENGTH),
         allow_receiving_object_id: true,
         reject_mutable_random_on_entry_functions: true,
+        enable_enums: true,
     }
 }
@@ -38,6 +38,7 @@ fn test_bicliques() {
         code: Some(CodeUnit {
             locals: SignatureIndex(0),
             code: vec![Bytecode::Call(FunctionHandleIndex(0)), Bytecode::Ret],
+            jump_tables: vec![],
         }),
     });
 
@@ -64,6 +65,7 @@ fn test_bicliques() {
         code: Some(CodeUnit {
             locals: SignatureIndex(0),
             code: vec![],
+            jump_tables: vec![],
         }),
     });
     let code = &mut m.function_defs[1].code.as_mut().unwrap().code;
@@ -90,6 +92,7 @@ fn test_bicliques() {
         code: Some(CodeUnit {
             locals: SignatureIndex(0),
             code: vec![Bytecode::Ret],
+            jump_tables: vec![],
         }),
     });
 
@@ -112,6 +115,7 @@ fn test_bicliques() {
             code: Some(CodeUnit {
                 locals: SignatureIndex(0),
                 code: vec![],
+                jump_tables: vec![],
             }),
         });
         let code = &mut m.function_defs[i as usize + 2].code.as_mut().unwrap().code;
@@ -164,6 +168,7 @@ fn test_merge_state_large_graph() {
         code: Some(CodeUnit {
             locals: SignatureIndex(0),
             code: vec![Bytecode::Call(FunctionHandleIndex(0)), Bytecode::Ret],
+            jump_tables: vec![],
         }),
     });
 
@@ -189,6 +194,7 @@ fn test_merge_state_large_graph() {
         code: Some(CodeUnit {
             locals: SignatureIndex(0),
             code: vec![Bytecode::Call(FunctionHandleIndex(1)), Bytecode::Ret],
+            jump_tables: vec![],
         }),
     });
 
@@ -209,6 +215,7 @@ fn test_merge_state_large_graph() {
         code: Some(CodeUnit {
             locals: SignatureIndex(0),
             code: vec![Bytecode::Call(FunctionHandleIndex(1)), Bytecode::Ret],
+            jump_tables: vec![],
         }),
     });
 
@@ -230,6 +237,7 @@ fn test_merge_state_large_graph() {
             code: Some(CodeUnit {
                 locals: SignatureIndex(1),
                 code: vec![],
+                jump_tables: vec![],
             }),
         });
         let code = &mut m.function_defs[i as usize + 3].code.as_mut().unwrap().code;
@@ -287,6 +295,7 @@ fn test_merge_state() {
         code: Some(CodeUnit {
             locals: SignatureIndex(0),
             code: vec![Bytecode::Call(FunctionHandleIndex(0)), Bytecode::Ret],
+            jump_tables: vec![],
         }),
     });
 
@@ -318,6 +327,7 @@ fn test_merge_state() {
             code: Some(CodeUnit {
                 locals: SignatureIndex(2),
                 code: vec![],
+                jump_tables: vec![],
             }),
         });
         let code = &mut m.function_defs[i as usize + 1].code.as_mut().unwrap().code;
@@ -403,6 +413,7 @@ fn test_copyloc_pop() {
             code: Some(CodeUnit {
                 locals: SignatureIndex(2),
                 code: vec![],
+                jump_tables: vec![],
             }),
         });
         let code = &mut m.function_defs[i as usize].code.as_mut().unwrap().code;
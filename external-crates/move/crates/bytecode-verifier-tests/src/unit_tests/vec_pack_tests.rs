@@ -34,6 +34,7 @@ fn test_vec_pack() {
         code: Some(CodeUnit {
             locals: SignatureIndex(0),
             code: vec![],
+            jump_tables: vec![],
         }),
     });
 
@@ -413,6 +413,17 @@ impl<'a> ApplyCodeUnitBoundsContext<'a> {
                     | MoveToGenericDeprecated(_) => {
                         panic!("Bytecode deprecated: {:?}", code[bytecode_idx])
                     }
+                    PackVariant(_)
+                    | PackVariantGeneric(_)
+                    | UnpackVariant(_)
+                    | UnpackVariantImmRef(_)
+                    | UnpackVariantMutRef(_)
+                    | UnpackVariantGeneric(_)
+                    | UnpackVariantGenericImmRef(_)
+                    | UnpackVariantGenericMutRef(_)
+                    | VariantSwitch(_) => {
+                        panic!("Enum bytecode not supported: {:?}", code[bytecode_idx])
+                    }
                     FreezeRef | Pop | Ret | LdU8(_) | LdU16(_) | LdU32(_) | LdU64(_)
                     | LdU128(_) | LdU256(_) | CastU8 | CastU16 | CastU32 | CastU64 | CastU128
                     | CastU256 | LdTrue | LdFalse | ReadRef | WriteRef | Add | Sub | Mul | Mod
@@ -472,6 +483,16 @@ fn is_interesting(bytecode: &Bytecode) -> bool {
         | MoveFromGenericDeprecated(_)
         | MoveToDeprecated(_)
         | MoveToGenericDeprecated(_) => false,
+        // Enum bytecodes are not mutated yet
+        PackVariant(_)
+        | PackVariantGeneric(_)
+        | UnpackVariant(_)
+        | UnpackVariantImmRef(_)
+        | UnpackVariantMutRef(_)
+        | UnpackVariantGeneric(_)
+        | UnpackVariantGenericImmRef(_)
+        | UnpackVariantGenericMutRef(_)
+        | VariantSwitch(_) => false,
         // List out the other options explicitly so there's a compile error if a new
         // bytecode gets added.
         FreezeRef | Pop | Ret | LdU8(_) | LdU16(_) | LdU32(_) | LdU64(_) | LdU128(_)
@@ -49,6 +49,12 @@ pub trait ModuleAccess: Sync {
         self.identifier_at(handle.name)
     }
 
+    fn enum_name(&self, idx: EnumDefinitionIndex) -> &IdentStr {
+        let enum_def = self.enum_def_at(idx);
+        let handle = self.struct_handle_at(enum_def.enum_handle);
+        self.identifier_at(handle.name)
+    }
+
     fn module_handle_at(&self, idx: ModuleHandleIndex) -> &ModuleHandle {
         let handle = &self.as_module().module_handles[idx.into_index()];
         debug_assert!(handle.address.into_index() < self.as_module().address_identifiers.len()); // invariant
@@ -83,6 +89,23 @@ pub trait ModuleAccess: Sync {
         &self.as_module().function_instantiations[idx.into_index()]
     }
 
+    fn enum_instantiation_at(&self, idx: EnumDefInstantiationIndex) -> &EnumDefInstantiation {
+        &self.as_module().enum_def_instantiations[idx.into_index()]
+    }
+
+    fn variant_handle_at(&self, idx: VariantHandleIndex) -> &VariantHandle {
+        let handle = &self.as_module().variant_handles[idx.into_index()];
+        debug_assert!(handle.enum_def.into_index() < self.as_module().enum_defs.len()); // invariant
+        handle
+    }
+
+    fn variant_instantiation_handle_at(
+        &self,
+        idx: VariantInstantiationHandleIndex,
+    ) -> &VariantInstantiationHandle {
+        &self.as_module().variant_instantiation_handles[idx.into_index()]
+    }
+
     fn field_instantiation_at(&self, idx: FieldInstantiationIndex) -> &FieldInstantiation {
         &self.as_module().field_instantiations[idx.into_index()]
     }
@@ -107,6 +130,10 @@ pub trait ModuleAccess: Sync {
         &self.as_module().struct_defs[idx.into_index()]
     }
 
+    fn enum_def_at(&self, idx: EnumDefinitionIndex) -> &EnumDefinition {
+        &self.as_module().enum_defs[idx.into_index()]
+    }
+
     fn function_def_at(&self, idx: FunctionDefinitionIndex) -> &FunctionDefinition {
         let result = &self.as_module().function_defs[idx.into_index()];
         debug_assert!(result.function.into_index() < self.function_handles().len()); // invariant
@@ -169,6 +196,22 @@ pub trait ModuleAccess: Sync {
         &self.as_module().function_defs
     }
 
+    fn enum_defs(&self) -> &[EnumDefinition] {
+        &self.as_module().enum_defs
+    }
+
+    fn enum_instantiations(&self) -> &[EnumDefInstantiation] {
+        &self.as_module().enum_def_instantiations
+    }
+
+    fn variant_handles(&self) -> &[VariantHandle] {
+        &self.as_module().variant_handles
+    }
+
+    fn variant_instantiation_handles(&self) -> &[VariantInstantiationHandle] {
+        &self.as_module().variant_instantiation_handles
+    }
+
     fn friend_decls(&self) -> &[ModuleHandle] {
         &self.as_module().friend_decls
     }
@@ -211,6 +254,17 @@ pub trait ModuleAccess: Sync {
             name == self.identifier_at(handle.name)
         })
     }
+
+    fn find_enum_def(&self, idx: StructHandleIndex) -> Option<&EnumDefinition> {
+        self.enum_defs().iter().find(|d| d.enum_handle == idx)
+    }
+
+    fn find_enum_def_by_name(&self, name: &IdentStr) -> Option<&EnumDefinition> {
+        self.enum_defs().iter().find(|def| {
+            let handle = self.struct_handle_at(def.enum_handle);
+            name == self.identifier_at(handle.name)
+        })
+    }
 }
 
 /// Represents accessors for a compiled script.
@@ -22,6 +22,10 @@ pub struct TableConfig {
     pub field_handles: u16,
     pub field_instantiations: u16,
     pub friend_decls: u16,
+    pub enum_defs: u16,
+    pub enum_def_instantiations: u16,
+    pub variant_handles: u16,
+    pub variant_instantiation_handles: u16,
 }
 
 impl TableConfig {
@@ -44,6 +48,10 @@ impl TableConfig {
             field_handles: u16::MAX,
             field_instantiations: u16::MAX,
             friend_decls: u16::MAX,
+            enum_defs: u16::MAX,
+            enum_def_instantiations: u16::MAX,
+            variant_handles: u16::MAX,
+            variant_instantiation_handles: u16::MAX,
         }
     }
 }
@@ -16,12 +16,14 @@ use crate::{
     errors::{PartialVMError, PartialVMResult},
     file_format::{
         AbilitySet, AddressIdentifierIndex, CodeUnit, CompiledScript, Constant, ConstantPoolIndex,
+        EnumDefInstantiation, EnumDefInstantiationIndex, EnumDefinition, EnumDefinitionIndex,
         FieldHandle, FieldHandleIndex, FieldInstantiation, FieldInstantiationIndex,
         FunctionDefinition, FunctionDefinitionIndex, FunctionHandle, FunctionHandleIndex,
         FunctionInstantiation, FunctionInstantiationIndex, IdentifierIndex, ModuleHandle,
         ModuleHandleIndex, Signature, SignatureIndex, SignatureToken, StructDefInstantiation,
         StructDefInstantiationIndex, StructDefinition, StructDefinitionIndex, StructHandle,
-        StructHandleIndex,
+        StructHandleIndex, VariantHandle, VariantHandleIndex, VariantInstantiationHandle,
+        VariantInstantiationHandleIndex,
     },
     CompiledModule,
 };
@@ -230,6 +232,76 @@ impl<'a> BinaryIndexedView<'a> {
         }
     }
 
+    pub fn enum_defs(&self) -> Option<&[EnumDefinition]> {
+        match self {
+            BinaryIndexedView::Module(module) => Some(module.enum_defs()),
+            BinaryIndexedView::Script(_) => None,
+        }
+    }
+
+    pub fn enum_def_at(&self, idx: EnumDefinitionIndex) -> PartialVMResult<&EnumDefinition> {
+        match self {
+            BinaryIndexedView::Module(module) => Ok(module.enum_def_at(idx)),
+            BinaryIndexedView::Script(_) => {
+                Err(PartialVMError::new(StatusCode::INVALID_OPERATION_IN_SCRIPT))
+            }
+        }
+    }
+
+    pub fn enum_instantiations(&self) -> Option<&[EnumDefInstantiation]> {
+        match self {
+            BinaryIndexedView::Module(module) => Some(module.enum_instantiations()),
+            BinaryIndexedView::Script(_) => None,
+        }
+    }
+
+    pub fn enum_instantiation_at(
+        &self,
+        idx: EnumDefInstantiationIndex,
+    ) -> PartialVMResult<&EnumDefInstantiation> {
+        match self {
+            BinaryIndexedView::Module(module) => Ok(module.enum_instantiation_at(idx)),
+            BinaryIndexedView::Script(_) => {
+                Err(PartialVMError::new(StatusCode::INVALID_OPERATION_IN_SCRIPT))
+            }
+        }
+    }
+
+    pub fn variant_handles(&self) -> Option<&[VariantHandle]> {
+        match self {
+            BinaryIndexedView::Module(module) => Some(module.variant_handles()),
+            BinaryIndexedView::Script(_) => None,
+        }
+    }
+
+    pub fn variant_handle_at(&self, idx: VariantHandleIndex) -> PartialVMResult<&VariantHandle> {
+        match self {
+            BinaryIndexedView::Module(module) => Ok(module.variant_handle_at(idx)),
+            BinaryIndexedView::Script(_) => {
+                Err(PartialVMError::new(StatusCode::INVALID_OPERATION_IN_SCRIPT))
+            }
+        }
+    }
+
+    pub fn variant_instantiation_handles(&self) -> Option<&[VariantInstantiationHandle]> {
+        match self {
+            BinaryIndexedView::Module(module) => Some(module.variant_instantiation_handles()),
+            BinaryIndexedView::Script(_) => None,
+        }
+    }
+
+    pub fn variant_instantiation_handle_at(
+        &self,
+        idx: VariantInstantiationHandleIndex,
+    ) -> PartialVMResult<&VariantInstantiationHandle> {
+        match self {
+            BinaryIndexedView::Module(module) => Ok(module.variant_instantiation_handle_at(idx)),
+            BinaryIndexedView::Script(_) => {
+                Err(PartialVMError::new(StatusCode::INVALID_OPERATION_IN_SCRIPT))
+            }
+        }
+    }
+
     pub fn function_defs(&self) -> Option<&[FunctionDefinition]> {
         match self {
             BinaryIndexedView::Module(module) => Some(module.function_defs()),
@@ -354,7 +426,7 @@ impl<'a> FunctionView<'a> {
             return_: module.signature_at(function_handle.return_),
             locals: module.signature_at(code.locals),
             type_parameters: &function_handle.type_parameters,
-            cfg: VMControlFlowGraph::new(&code.code),
+            cfg: VMControlFlowGraph::new(&code.code, &code.jump_tables),
         }
     }
 
@@ -371,7 +443,7 @@ impl<'a> FunctionView<'a> {
             return_: EMPTY_SIGNATURE,
             locals,
             type_parameters,
-            cfg: VMControlFlowGraph::new(&code.code),
+            cfg: VMControlFlowGraph::new(&code.code, &code.jump_tables),
         }
     }
 
@@ -13,9 +13,11 @@ use crate::{
     },
     file_format::{
         AbilitySet, Bytecode, CodeOffset, CodeUnit, CompiledModule, CompiledScript, Constant,
-        FieldHandle, FieldInstantiation, FunctionDefinition, FunctionDefinitionIndex,
-        FunctionHandle, FunctionInstantiation, LocalIndex, ModuleHandle, Signature, SignatureToken,
-        StructDefInstantiation, StructDefinition, StructFieldInformation, StructHandle, TableIndex,
+        EnumDefInstantiation, EnumDefinition, FieldHandle, FieldInstantiation, FunctionDefinition,
+        FunctionDefinitionIndex, FunctionHandle, FunctionInstantiation, JumpTableInner, LocalIndex,
+        ModuleHandle, Signature, SignatureIndex, SignatureToken, StructDefInstantiation,
+        StructDefinition, StructFieldInformation, StructHandle, TableIndex, VariantHandle,
+        VariantInstantiationHandle,
     },
     internals::ModuleIndex,
     IndexKind,
@@ -93,6 +95,10 @@ impl<'a> BoundsChecker<'a> {
         self.check_function_instantiations()?;
         self.check_field_instantiations()?;
         self.check_struct_defs()?;
+        self.check_enum_defs()?;
+        self.check_enum_instantiations()?;
+        self.check_variant_handles()?;
+        self.check_variant_instantiation_handles()?;
         self.check_function_defs()
     }
 
@@ -173,6 +179,39 @@ impl<'a> BoundsChecker<'a> {
         Ok(())
     }
 
+    fn check_enum_defs(&self) -> PartialVMResult<()> {
+        for enum_def in self.view.enum_defs().into_iter().flatten() {
+            self.check_enum_def(enum_def)?
+        }
+        Ok(())
+    }
+
+    fn check_enum_instantiations(&self) -> PartialVMResult<()> {
+        for enum_instantiation in self.view.enum_instantiations().into_iter().flatten() {
+            self.check_enum_instantiation(enum_instantiation)?
+        }
+        Ok(())
+    }
+
+    fn check_variant_handles(&self) -> PartialVMResult<()> {
+        for variant_handle in self.view.variant_handles().into_iter().flatten() {
+            self.check_variant_handle(variant_handle)?
+        }
+        Ok(())
+    }
+
+    fn check_variant_instantiation_handles(&self) -> PartialVMResult<()> {
+        for variant_inst_handle in self
+            .view
+            .variant_instantiation_handles()
+            .into_iter()
+            .flatten()
+        {
+            self.check_variant_instantiation_handle(variant_inst_handle)?
+        }
+        Ok(())
+    }
+
     fn check_function_defs(&mut self) -> PartialVMResult<()> {
         let view = self.view;
         for (function_def_idx, function_def) in
@@ -310,6 +349,83 @@ impl<'a> BoundsChecker<'a> {
         Ok(())
     }
 
+    fn check_enum_def(&self, enum_def: &EnumDefinition) -> PartialVMResult<()> {
+        check_bounds_impl(self.view.struct_handles(), enum_def.enum_handle)?;
+        let type_param_count = self
+            .view
+            .struct_handles()
+            .get(enum_def.enum_handle.into_index())
+            .map_or(0, |sh| sh.type_parameters.len());
+        for variant in &enum_def.variants {
+            check_bounds_impl(self.view.identifiers(), variant.variant_name)?;
+            // field signatures are inlined
+            for field in &variant.fields {
+                check_bounds_impl(self.view.identifiers(), field.name)?;
+                self.check_type(&field.signature.0)?;
+                self.check_type_parameter(&field.signature.0, type_param_count)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn check_enum_instantiation(
+        &self,
+        enum_instantiation: &EnumDefInstantiation,
+    ) -> PartialVMResult<()> {
+        check_bounds_impl_opt(&self.view.enum_defs(), enum_instantiation.def)?;
+        check_bounds_impl(self.view.signatures(), enum_instantiation.type_parameters)
+    }
+
+    fn check_variant_handle(&self, variant_handle: &VariantHandle) -> PartialVMResult<()> {
+        check_bounds_impl_opt(&self.view.enum_defs(), variant_handle.enum_def)?;
+        // variant tag must be in bounds, enum def just checked above must exist
+        if let Some(enum_def) = self
+            .view
+            .enum_defs()
+            .and_then(|d| d.get(variant_handle.enum_def.into_index()))
+        {
+            self.check_variant_tag(enum_def, variant_handle.variant)?;
+        }
+        Ok(())
+    }
+
+    fn check_variant_instantiation_handle(
+        &self,
+        variant_inst_handle: &VariantInstantiationHandle,
+    ) -> PartialVMResult<()> {
+        check_bounds_impl_opt(
+            &self.view.enum_instantiations(),
+            variant_inst_handle.enum_def,
+        )?;
+        // variant tag must be in bounds of the instantiated enum definition
+        if let Some(enum_def) = self
+            .view
+            .enum_instantiations()
+            .and_then(|i| i.get(variant_inst_handle.enum_def.into_index()))
+            .and_then(|inst| {
+                self.view
+                    .enum_defs()
+                    .and_then(|d| d.get(inst.def.into_index()))
+            })
+        {
+            self.check_variant_tag(enum_def, variant_inst_handle.variant)?;
+        }
+        Ok(())
+    }
+
+    fn check_variant_tag(&self, enum_def: &EnumDefinition, tag: u16) -> PartialVMResult<()> {
+        let variant_count = enum_def.variants.len();
+        if tag as usize >= variant_count {
+            return Err(bounds_error(
+                StatusCode::INDEX_OUT_OF_BOUNDS,
+                IndexKind::VariantTag,
+                tag,
+                variant_count,
+            ));
+        }
+        Ok(())
+    }
+
     fn check_function_def(
         &mut self,
         function_def_idx: usize,
@@ -381,8 +497,29 @@ impl<'a> BoundsChecker<'a> {
             self.check_type_parameter(local, type_param_count)?
         }
 
-        // check bytecodes
+        // check jump tables: the head enum must exist and every target must be in the
+        // code
         let code_len = code_unit.code.len();
+        for (jt_idx, jump_table) in code_unit.jump_tables.iter().enumerate() {
+            check_bounds_impl_opt(&self.view.enum_defs(), jump_table.head_enum)?;
+            match &jump_table.jump_table {
+                JumpTableInner::Full(offsets) => {
+                    for offset in offsets {
+                        if *offset as usize >= code_len {
+                            return Err(self.offset_out_of_bounds(
+                                StatusCode::INDEX_OUT_OF_BOUNDS,
+                                IndexKind::CodeDefinition,
+                                *offset as usize,
+                                code_len,
+                                jt_idx as CodeOffset,
+                            ));
+                        }
+                    }
+                }
+            }
+        }
+
+        // check bytecodes
         for (bytecode_offset, bytecode) in code_unit.code.iter().enumerate() {
             use self::Bytecode::*;
 
@@ -487,6 +624,44 @@ impl<'a> BoundsChecker<'a> {
                         }
                     }
                 }
+                PackVariant(idx)
+                | UnpackVariant(idx)
+                | UnpackVariantImmRef(idx)
+                | UnpackVariantMutRef(idx) => self.check_code_unit_bounds_impl_opt(
+                    &self.view.variant_handles(),
+                    *idx,
+                    bytecode_offset,
+                )?,
+                PackVariantGeneric(idx)
+                | UnpackVariantGeneric(idx)
+                | UnpackVariantGenericImmRef(idx)
+                | UnpackVariantGenericMutRef(idx) => {
+                    self.check_code_unit_bounds_impl_opt(
+                        &self.view.variant_instantiation_handles(),
+                        *idx,
+                        bytecode_offset,
+                    )?;
+                    // check type parameters in variant operations are bound to the function
+                    // type parameters
+                    if let Some(enum_inst) = self
+                        .view
+                        .variant_instantiation_handles()
+                        .and_then(|v| v.get(idx.into_index()))
+                        .and_then(|handle| {
+                            self.view
+                                .enum_instantiations()
+                                .and_then(|i| i.get(handle.enum_def.into_index()))
+                        })
+                    {
+                        self.check_type_parameters_in_signature(
+                            enum_inst.type_parameters,
+                            type_param_count,
+                        )?;
+                    }
+                }
+                VariantSwitch(idx) => {
+                    self.check_code_unit_bounds_impl(&code_unit.jump_tables, *idx, bytecode_offset)?
+                }
                 // Instructions that refer to this code block.
                 BrTrue(offset) | BrFalse(offset) | Branch(offset) => {
                     let offset = *offset as usize;
@@ -548,6 +723,19 @@ impl<'a> BoundsChecker<'a> {
         Ok(())
     }
 
+    fn check_type_parameters_in_signature(
+        &self,
+        idx: SignatureIndex,
+        type_param_count: usize,
+    ) -> PartialVMResult<()> {
+        if let Some(sig) = self.view.signatures().get(idx.into_index()) {
+            for ty in &sig.0 {
+                self.check_type_parameter(ty, type_param_count)?
+            }
+        }
+        Ok(())
+    }
+
     fn check_type(&self, ty: &SignatureToken) -> PartialVMResult<()> {
         use self::SignatureToken::*;
 
@@ -143,6 +143,33 @@ impl Compatibility {
             }
         }
 
+        // old module's enums are a subset of the new module's enums
+        for (name, old_enum) in &old_module.enums {
+            let Some(new_enum) = new_module.enums.get(name) else {
+                // Enum not present in new. Same reasoning as for removed structs.
+                struct_and_function_linking = false;
+                struct_layout = false;
+                break;
+            };
+
+            if !struct_abilities_compatible(
+                self.disallowed_new_abilities,
+                old_enum.abilities,
+                new_enum.abilities,
+            ) || !struct_type_parameters_compatible(
+                self.disallow_change_struct_type_params,
+                &old_enum.type_parameters,
+                &new_enum.type_parameters,
+            ) {
+                struct_and_function_linking = false;
+            }
+            if new_enum.variants != old_enum.variants {
+                // Variants changed. Since a variant switch must cover every variant of the
+                // enum, adding a variant is as breaking as removing or changing one.
+                struct_layout = false
+            }
+        }
+
         // The modules are considered as compatible function-wise when all the
         // conditions are met:
         //
@@ -366,6 +393,7 @@ impl InclusionCheck {
         // size of all of the tables are the exact same except for constants.
         if (self == &Self::Equal)
             && (old_module.structs.len() != new_module.structs.len()
+                || old_module.enums.len() != new_module.enums.len()
                 || old_module.functions.len() != new_module.functions.len()
                 || old_module.friends.len() != new_module.friends.len())
         {
@@ -382,6 +410,16 @@ impl InclusionCheck {
             };
         }
 
+        // Enum checks
+        for (name, old_enum) in &old_module.enums {
+            match new_module.enums.get(name) {
+                Some(new_enum) if old_enum == new_enum => (),
+                _ => {
+                    return err;
+                }
+            };
+        }
+
         // Function checks
         for (name, old_func) in &old_module.functions {
             match new_module
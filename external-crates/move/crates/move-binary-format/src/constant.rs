@@ -38,7 +38,7 @@ fn ty_to_sig(ty: &MoveTypeLayout) -> Option<SignatureToken> {
         MoveTypeLayout::U128 => Some(SignatureToken::U128),
         MoveTypeLayout::U256 => Some(SignatureToken::U256),
         MoveTypeLayout::Vector(v) => Some(SignatureToken::Vector(Box::new(ty_to_sig(v.as_ref())?))),
-        MoveTypeLayout::Struct(_) => None,
+        MoveTypeLayout::Struct(_) | MoveTypeLayout::Enum(_) => None,
         MoveTypeLayout::Bool => Some(SignatureToken::Bool),
     }
 }
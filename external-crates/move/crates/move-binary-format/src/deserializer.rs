@@ -241,6 +241,49 @@ fn load_struct_def_inst_index(
     )?))
 }
 
+fn load_enum_def_index(cursor: &mut VersionedCursor) -> BinaryLoaderResult<EnumDefinitionIndex> {
+    Ok(EnumDefinitionIndex(read_uleb_internal(
+        cursor,
+        ENUM_DEF_INDEX_MAX,
+    )?))
+}
+
+fn load_enum_def_inst_index(
+    cursor: &mut VersionedCursor,
+) -> BinaryLoaderResult<EnumDefInstantiationIndex> {
+    Ok(EnumDefInstantiationIndex(read_uleb_internal(
+        cursor,
+        ENUM_DEF_INST_INDEX_MAX,
+    )?))
+}
+
+fn load_variant_handle_index(
+    cursor: &mut VersionedCursor,
+) -> BinaryLoaderResult<VariantHandleIndex> {
+    Ok(VariantHandleIndex(read_uleb_internal(
+        cursor,
+        VARIANT_HANDLE_INDEX_MAX,
+    )?))
+}
+
+fn load_variant_inst_handle_index(
+    cursor: &mut VersionedCursor,
+) -> BinaryLoaderResult<VariantInstantiationHandleIndex> {
+    Ok(VariantInstantiationHandleIndex(read_uleb_internal(
+        cursor,
+        VARIANT_INST_HANDLE_INDEX_MAX,
+    )?))
+}
+
+fn load_variant_jump_table_index(
+    cursor: &mut VersionedCursor,
+) -> BinaryLoaderResult<VariantJumpTableIndex> {
+    Ok(VariantJumpTableIndex(read_uleb_internal(
+        cursor,
+        VARIANT_JUMP_TABLE_INDEX_MAX,
+    )?))
+}
+
 fn load_constant_pool_index(cursor: &mut VersionedCursor) -> BinaryLoaderResult<ConstantPoolIndex> {
     Ok(ConstantPoolIndex(read_uleb_internal(
         cursor,
@@ -264,6 +307,18 @@ fn load_field_count(cursor: &mut VersionedCursor) -> BinaryLoaderResult<u64> {
     read_uleb_internal(cursor, FIELD_COUNT_MAX)
 }
 
+fn load_variant_count(cursor: &mut VersionedCursor) -> BinaryLoaderResult<u64> {
+    read_uleb_internal(cursor, VARIANT_COUNT_MAX)
+}
+
+fn load_variant_tag(cursor: &mut VersionedCursor) -> BinaryLoaderResult<VariantTag> {
+    read_uleb_internal(cursor, VARIANT_TAG_MAX)
+}
+
+fn load_jump_table_count(cursor: &mut VersionedCursor) -> BinaryLoaderResult<u64> {
+    read_uleb_internal(cursor, JUMP_TABLE_COUNT_MAX)
+}
+
 fn load_type_parameter_count(cursor: &mut VersionedCursor) -> BinaryLoaderResult<usize> {
     read_uleb_internal(cursor, TYPE_PARAMETER_COUNT_MAX)
 }
@@ -559,6 +614,10 @@ fn build_common_tables(
         field_handles: _,
         field_instantiations: _,
         friend_decls: _,
+        enum_defs: _,
+        enum_def_instantiations: _,
+        variant_handles: _,
+        variant_instantiation_handles: _,
     } = &binary.binary_config.table_config;
     for table in tables {
         // minimize code that checks limits with a local macro that knows the context
@@ -636,6 +695,20 @@ fn build_common_tables(
             | TableType::STRUCT_DEF_INST
             | TableType::FIELD_HANDLE
             | TableType::FIELD_INST => (),
+            TableType::ENUM_DEFS
+            | TableType::ENUM_DEF_INST
+            | TableType::VARIANT_HANDLES
+            | TableType::VARIANT_INST_HANDLES => {
+                // enums do not exist before VERSION_7
+                if binary.version() < VERSION_7 {
+                    return Err(
+                        PartialVMError::new(StatusCode::MALFORMED).with_message(format!(
+                            "Enum declarations not applicable in bytecode version {}",
+                            binary.version()
+                        )),
+                    );
+                }
+            }
             TableType::FRIEND_DECLS => {
                 // friend declarations do not exist before VERSION_2
                 if binary.version() < VERSION_2 {
@@ -672,6 +745,10 @@ fn build_module_tables(
         field_handles: field_handles_max,
         field_instantiations: field_instantiations_max,
         friend_decls: friend_decls_max,
+        enum_defs: enum_defs_max,
+        enum_def_instantiations: enum_def_instantiations_max,
+        variant_handles: variant_handles_max,
+        variant_instantiation_handles: variant_instantiation_handles_max,
     } = &binary.binary_config.table_config;
     for table in tables {
         // minimize code that checks limits bu a local macro that know the context
@@ -718,6 +795,32 @@ fn build_module_tables(
                 load_module_handles(binary, table, &mut module.friend_decls)?;
                 check_table_size!(&module.friend_decls, *friend_decls_max);
             }
+            TableType::ENUM_DEFS => {
+                load_enum_defs(binary, table, &mut module.enum_defs)?;
+                check_table_size!(&module.enum_defs, *enum_defs_max);
+            }
+            TableType::ENUM_DEF_INST => {
+                load_enum_instantiations(binary, table, &mut module.enum_def_instantiations)?;
+                check_table_size!(
+                    &module.enum_def_instantiations,
+                    *enum_def_instantiations_max
+                );
+            }
+            TableType::VARIANT_HANDLES => {
+                load_variant_handles(binary, table, &mut module.variant_handles)?;
+                check_table_size!(&module.variant_handles, *variant_handles_max);
+            }
+            TableType::VARIANT_INST_HANDLES => {
+                load_variant_instantiation_handles(
+                    binary,
+                    table,
+                    &mut module.variant_instantiation_handles,
+                )?;
+                check_table_size!(
+                    &module.variant_instantiation_handles,
+                    *variant_instantiation_handles_max
+                );
+            }
             TableType::MODULE_HANDLES
             | TableType::STRUCT_HANDLES
             | TableType::FUNCTION_HANDLES
@@ -756,7 +859,11 @@ fn build_script_tables(
             | TableType::FUNCTION_DEFS
             | TableType::FIELD_INST
             | TableType::FIELD_HANDLE
-            | TableType::FRIEND_DECLS => {
+            | TableType::FRIEND_DECLS
+            | TableType::ENUM_DEFS
+            | TableType::ENUM_DEF_INST
+            | TableType::VARIANT_HANDLES
+            | TableType::VARIANT_INST_HANDLES => {
                 return Err(PartialVMError::new(StatusCode::MALFORMED)
                     .with_message("Bad table in Script".to_string()));
             }
@@ -1343,6 +1450,89 @@ fn load_field_def(cursor: &mut VersionedCursor) -> BinaryLoaderResult<FieldDefin
     })
 }
 
+/// Builds the `EnumDefinition` table.
+fn load_enum_defs(
+    binary: &VersionedBinary,
+    table: &Table,
+    enum_defs: &mut Vec<EnumDefinition>,
+) -> BinaryLoaderResult<()> {
+    let start = table.offset as usize;
+    let end = start + table.count as usize;
+    let mut cursor = binary.new_cursor(start, end);
+    while cursor.position() < u64::from(table.count) {
+        let enum_handle = load_struct_handle_index(&mut cursor)?;
+        let variant_count = load_variant_count(&mut cursor)?;
+        let mut variants = Vec::with_capacity(variant_count as usize);
+        for _ in 0..variant_count {
+            let variant_name = load_identifier_index(&mut cursor)?;
+            let fields = load_field_defs(&mut cursor)?;
+            variants.push(VariantDefinition {
+                variant_name,
+                fields,
+            });
+        }
+        enum_defs.push(EnumDefinition {
+            enum_handle,
+            variants,
+        });
+    }
+    Ok(())
+}
+
+/// Builds the `EnumDefInstantiation` table.
+fn load_enum_instantiations(
+    binary: &VersionedBinary,
+    table: &Table,
+    enum_insts: &mut Vec<EnumDefInstantiation>,
+) -> BinaryLoaderResult<()> {
+    let start = table.offset as usize;
+    let end = start + table.count as usize;
+    let mut cursor = binary.new_cursor(start, end);
+    while cursor.position() < u64::from(table.count) {
+        let def = load_enum_def_index(&mut cursor)?;
+        let type_parameters = load_signature_index(&mut cursor)?;
+        enum_insts.push(EnumDefInstantiation {
+            def,
+            type_parameters,
+        });
+    }
+    Ok(())
+}
+
+/// Builds the `VariantHandle` table.
+fn load_variant_handles(
+    binary: &VersionedBinary,
+    table: &Table,
+    variant_handles: &mut Vec<VariantHandle>,
+) -> BinaryLoaderResult<()> {
+    let start = table.offset as usize;
+    let end = start + table.count as usize;
+    let mut cursor = binary.new_cursor(start, end);
+    while cursor.position() < u64::from(table.count) {
+        let enum_def = load_enum_def_index(&mut cursor)?;
+        let variant = load_variant_tag(&mut cursor)?;
+        variant_handles.push(VariantHandle { enum_def, variant });
+    }
+    Ok(())
+}
+
+/// Builds the `VariantInstantiationHandle` table.
+fn load_variant_instantiation_handles(
+    binary: &VersionedBinary,
+    table: &Table,
+    variant_inst_handles: &mut Vec<VariantInstantiationHandle>,
+) -> BinaryLoaderResult<()> {
+    let start = table.offset as usize;
+    let end = start + table.count as usize;
+    let mut cursor = binary.new_cursor(start, end);
+    while cursor.position() < u64::from(table.count) {
+        let enum_def = load_enum_def_inst_index(&mut cursor)?;
+        let variant = load_variant_tag(&mut cursor)?;
+        variant_inst_handles.push(VariantInstantiationHandle { enum_def, variant });
+    }
+    Ok(())
+}
+
 /// Builds the `FunctionDefinition` table.
 fn load_function_defs(
     binary: &VersionedBinary,
@@ -1498,12 +1688,45 @@ fn load_code_unit(cursor: &mut VersionedCursor) -> BinaryLoaderResult<CodeUnit>
     let mut code_unit = CodeUnit {
         locals,
         code: vec![],
+        jump_tables: vec![],
     };
 
     load_code(cursor, &mut code_unit.code)?;
+    if cursor.version() >= VERSION_7 {
+        load_jump_tables(cursor, &mut code_unit.jump_tables)?;
+    }
     Ok(code_unit)
 }
 
+/// Deserializes the `VariantJumpTable`s of a code unit.
+fn load_jump_tables(
+    cursor: &mut VersionedCursor,
+    jump_tables: &mut Vec<VariantJumpTable>,
+) -> BinaryLoaderResult<()> {
+    let jump_table_count = load_jump_table_count(cursor)?;
+    for _ in 0..jump_table_count {
+        let head_enum = load_enum_def_index(cursor)?;
+        let flag = cursor.read_u8().map_err(|_| {
+            PartialVMError::new(StatusCode::MALFORMED).with_message("Unexpected EOF".to_string())
+        })?;
+        let jump_table = match SerializedJumpTableFlag::from_u8(flag)? {
+            SerializedJumpTableFlag::FULL => {
+                let count = load_variant_count(cursor)?;
+                let mut offsets = Vec::with_capacity(count as usize);
+                for _ in 0..count {
+                    offsets.push(load_bytecode_index(cursor)?);
+                }
+                JumpTableInner::Full(offsets)
+            }
+        };
+        jump_tables.push(VariantJumpTable {
+            head_enum,
+            jump_table,
+        });
+    }
+    Ok(())
+}
+
 /// Deserializes a code stream (`Bytecode`s).
 fn load_code(cursor: &mut VersionedCursor, code: &mut Vec<Bytecode>) -> BinaryLoaderResult<()> {
     let bytecode_count = load_bytecode_count(cursor)?;
@@ -1554,6 +1777,28 @@ fn load_code(cursor: &mut VersionedCursor, code: &mut Vec<Bytecode>) -> BinaryLo
             _ => (),
         };
 
+        match opcode {
+            Opcodes::PACK_VARIANT
+            | Opcodes::PACK_VARIANT_GENERIC
+            | Opcodes::UNPACK_VARIANT
+            | Opcodes::UNPACK_VARIANT_IMM_REF
+            | Opcodes::UNPACK_VARIANT_MUT_REF
+            | Opcodes::UNPACK_VARIANT_GENERIC
+            | Opcodes::UNPACK_VARIANT_GENERIC_IMM_REF
+            | Opcodes::UNPACK_VARIANT_GENERIC_MUT_REF
+            | Opcodes::VARIANT_SWITCH
+                if (cursor.version() < VERSION_7) =>
+            {
+                return Err(
+                    PartialVMError::new(StatusCode::MALFORMED).with_message(format!(
+                        "Enum variant operations not supported in bytecode version {}",
+                        cursor.version()
+                    )),
+                );
+            }
+            _ => (),
+        };
+
         // conversion
         let bytecode = match opcode {
             Opcodes::POP => Bytecode::Pop,
@@ -1683,6 +1928,29 @@ fn load_code(cursor: &mut VersionedCursor, code: &mut Vec<Bytecode>) -> BinaryLo
             Opcodes::MOVE_TO_GENERIC_DEPRECATED => {
                 Bytecode::MoveToGenericDeprecated(load_struct_def_inst_index(cursor)?)
             }
+            Opcodes::PACK_VARIANT => Bytecode::PackVariant(load_variant_handle_index(cursor)?),
+            Opcodes::PACK_VARIANT_GENERIC => {
+                Bytecode::PackVariantGeneric(load_variant_inst_handle_index(cursor)?)
+            }
+            Opcodes::UNPACK_VARIANT => Bytecode::UnpackVariant(load_variant_handle_index(cursor)?),
+            Opcodes::UNPACK_VARIANT_IMM_REF => {
+                Bytecode::UnpackVariantImmRef(load_variant_handle_index(cursor)?)
+            }
+            Opcodes::UNPACK_VARIANT_MUT_REF => {
+                Bytecode::UnpackVariantMutRef(load_variant_handle_index(cursor)?)
+            }
+            Opcodes::UNPACK_VARIANT_GENERIC => {
+                Bytecode::UnpackVariantGeneric(load_variant_inst_handle_index(cursor)?)
+            }
+            Opcodes::UNPACK_VARIANT_GENERIC_IMM_REF => {
+                Bytecode::UnpackVariantGenericImmRef(load_variant_inst_handle_index(cursor)?)
+            }
+            Opcodes::UNPACK_VARIANT_GENERIC_MUT_REF => {
+                Bytecode::UnpackVariantGenericMutRef(load_variant_inst_handle_index(cursor)?)
+            }
+            Opcodes::VARIANT_SWITCH => {
+                Bytecode::VariantSwitch(load_variant_jump_table_index(cursor)?)
+            }
         };
         code.push(bytecode);
     }
@@ -1707,6 +1975,10 @@ impl TableType {
             0xE => Ok(TableType::FIELD_INST),
             0xF => Ok(TableType::FRIEND_DECLS),
             0x10 => Ok(TableType::METADATA),
+            0x11 => Ok(TableType::ENUM_DEFS),
+            0x12 => Ok(TableType::ENUM_DEF_INST),
+            0x13 => Ok(TableType::VARIANT_HANDLES),
+            0x14 => Ok(TableType::VARIANT_INST_HANDLES),
             _ => Err(PartialVMError::new(StatusCode::UNKNOWN_TABLE_TYPE)),
         }
     }
@@ -1783,6 +2055,16 @@ impl SerializedNativeStructFlag {
     }
 }
 
+impl SerializedJumpTableFlag {
+    fn from_u8(value: u8) -> BinaryLoaderResult<SerializedJumpTableFlag> {
+        match value {
+            0x1 => Ok(SerializedJumpTableFlag::FULL),
+            _ => Err(PartialVMError::new(StatusCode::MALFORMED)
+                .with_message(format!("Unknown jump table flag {}", value))),
+        }
+    }
+}
+
 impl Opcodes {
     fn from_u8(value: u8) -> BinaryLoaderResult<Opcodes> {
         match value {
@@ -1863,6 +2145,15 @@ impl Opcodes {
             0x4B => Ok(Opcodes::CAST_U16),
             0x4C => Ok(Opcodes::CAST_U32),
             0x4D => Ok(Opcodes::CAST_U256),
+            0x4E => Ok(Opcodes::PACK_VARIANT),
+            0x4F => Ok(Opcodes::PACK_VARIANT_GENERIC),
+            0x50 => Ok(Opcodes::UNPACK_VARIANT),
+            0x51 => Ok(Opcodes::UNPACK_VARIANT_IMM_REF),
+            0x52 => Ok(Opcodes::UNPACK_VARIANT_MUT_REF),
+            0x53 => Ok(Opcodes::UNPACK_VARIANT_GENERIC),
+            0x54 => Ok(Opcodes::UNPACK_VARIANT_GENERIC_IMM_REF),
+            0x55 => Ok(Opcodes::UNPACK_VARIANT_GENERIC_MUT_REF),
+            0x56 => Ok(Opcodes::VARIANT_SWITCH),
             _ => Err(PartialVMError::new(StatusCode::UNKNOWN_OPCODE)),
         }
     }
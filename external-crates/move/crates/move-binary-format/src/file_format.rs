@@ -170,6 +170,31 @@ define_index! {
     kind: FunctionDefinition,
     doc: "Index into the `FunctionDefinition` table.",
 }
+define_index! {
+    name: EnumDefinitionIndex,
+    kind: EnumDefinition,
+    doc: "Index into the `EnumDefinition` table.",
+}
+define_index! {
+    name: EnumDefInstantiationIndex,
+    kind: EnumDefInstantiation,
+    doc: "Index into the `EnumDefInstantiation` table.",
+}
+define_index! {
+    name: VariantHandleIndex,
+    kind: VariantHandle,
+    doc: "Index into the `VariantHandle` table.",
+}
+define_index! {
+    name: VariantInstantiationHandleIndex,
+    kind: VariantInstantiationHandle,
+    doc: "Index into the `VariantInstantiationHandle` table.",
+}
+define_index! {
+    name: VariantJumpTableIndex,
+    kind: VariantJumpTable,
+    doc: "Index into the `VariantJumpTable` table of a `CodeUnit`.",
+}
 
 /// Index of a local variable in a function.
 ///
@@ -177,6 +202,9 @@ define_index! {
 pub type LocalIndex = u8;
 /// Max number of fields in a `StructDefinition`.
 pub type MemberCount = u16;
+/// The tag of a variant in an `EnumDefinition`, i.e. its position in the list
+/// of variants of the enum.
+pub type VariantTag = u16;
 /// Index into the code stream for a jump. The offset is relative to the
 /// beginning of the instruction stream.
 pub type CodeOffset = u16;
@@ -330,6 +358,28 @@ pub struct FieldHandle {
     pub field: MemberCount,
 }
 
+/// A variant access info (owner enum and the tag of the variant)
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(any(test, feature = "fuzzing"), derive(proptest_derive::Arbitrary))]
+#[cfg_attr(any(test, feature = "fuzzing"), proptest(no_params))]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "wasm", derive(Serialize, Deserialize))]
+pub struct VariantHandle {
+    pub enum_def: EnumDefinitionIndex,
+    pub variant: VariantTag,
+}
+
+/// A variant access info of a (possibly partially) instantiated generic enum.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(any(test, feature = "fuzzing"), derive(proptest_derive::Arbitrary))]
+#[cfg_attr(any(test, feature = "fuzzing"), proptest(no_params))]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "wasm", derive(Serialize, Deserialize))]
+pub struct VariantInstantiationHandle {
+    pub enum_def: EnumDefInstantiationIndex,
+    pub variant: VariantTag,
+}
+
 // DEFINITIONS:
 // Definitions are the module code. So the set of types and functions in the
 // module.
@@ -391,6 +441,17 @@ pub struct FieldInstantiation {
     pub type_parameters: SignatureIndex,
 }
 
+/// A complete or partial instantiation of a generic enum
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(any(test, feature = "fuzzing"), derive(proptest_derive::Arbitrary))]
+#[cfg_attr(any(test, feature = "fuzzing"), proptest(no_params))]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "wasm", derive(Serialize, Deserialize))]
+pub struct EnumDefInstantiation {
+    pub def: EnumDefinitionIndex,
+    pub type_parameters: SignatureIndex,
+}
+
 /// A `StructDefinition` is a type definition. It either indicates it is native
 /// or defines all the user-specified fields declared on the type.
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -428,6 +489,46 @@ impl StructDefinition {
     }
 }
 
+/// An `EnumDefinition` is a type definition of a tagged union. It defines the
+/// variants of the enum, each with its own list of fields.
+///
+/// The enum shares the `StructHandle` table with structs: the handle carries
+/// the name, abilities and type parameters of the enum, so that other modules
+/// can refer to the type without knowing whether it is a struct or an enum.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(any(test, feature = "fuzzing"), derive(proptest_derive::Arbitrary))]
+#[cfg_attr(any(test, feature = "fuzzing"), proptest(no_params))]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "wasm", derive(Serialize, Deserialize))]
+pub struct EnumDefinition {
+    /// The `StructHandle` for this `EnumDefinition`. This has the name and
+    /// the abilities for the type.
+    pub enum_handle: StructHandleIndex,
+    /// The variants of the enum, in declaration order. The position of a
+    /// variant in this list is its `VariantTag`.
+    pub variants: Vec<VariantDefinition>,
+}
+
+impl EnumDefinition {
+    pub fn variant(&self, tag: VariantTag) -> Option<&VariantDefinition> {
+        self.variants.get(tag as usize)
+    }
+}
+
+/// A `VariantDefinition` is the definition of a single variant of an enum: its
+/// name and the fields it carries (possibly none).
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(any(test, feature = "fuzzing"), derive(proptest_derive::Arbitrary))]
+#[cfg_attr(any(test, feature = "fuzzing"), proptest(no_params))]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "wasm", derive(Serialize, Deserialize))]
+pub struct VariantDefinition {
+    /// The name of the variant.
+    pub variant_name: IdentifierIndex,
+    /// The fields of the variant, in declaration order.
+    pub fields: Vec<FieldDefinition>,
+}
+
 /// A `FieldDefinition` is the definition of a field: its name and the field
 /// type.
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -1182,6 +1283,31 @@ pub struct Constant {
     pub data: Vec<u8>,
 }
 
+/// A jump table used by `VariantSwitch`. It is defined over the variants of
+/// `head_enum` and holds, for each variant tag, the code offset to branch to.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(any(test, feature = "fuzzing"), derive(proptest_derive::Arbitrary))]
+#[cfg_attr(any(test, feature = "fuzzing"), proptest(no_params))]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "wasm", derive(Serialize, Deserialize))]
+pub struct VariantJumpTable {
+    /// The enum the jump table switches over.
+    pub head_enum: EnumDefinitionIndex,
+    /// The branch targets.
+    pub jump_table: JumpTableInner,
+}
+
+/// The representation of the branch targets of a `VariantJumpTable`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(any(test, feature = "fuzzing"), derive(proptest_derive::Arbitrary))]
+#[cfg_attr(any(test, feature = "fuzzing"), proptest(no_params))]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "wasm", derive(Serialize, Deserialize))]
+pub enum JumpTableInner {
+    /// One code offset per variant of the enum, indexed by the variant tag.
+    Full(Vec<CodeOffset>),
+}
+
 /// A `CodeUnit` is the body of a function. It has the function header and the
 /// instruction stream.
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
@@ -1198,6 +1324,10 @@ pub struct CodeUnit {
         proptest(strategy = "vec(any::<Bytecode>(), 0..=params)")
     )]
     pub code: Vec<Bytecode>,
+    /// Jump tables used by the `VariantSwitch` instructions of the code
+    /// stream.
+    #[cfg_attr(any(test, feature = "fuzzing"), proptest(value = "vec![]"))]
+    pub jump_tables: Vec<VariantJumpTable>,
 }
 
 /// `Bytecode` is a VM instruction of variable size. The type of the bytecode
@@ -1680,6 +1810,54 @@ pub enum Bytecode {
     ///
     /// ```..., integer_value -> ..., u256_value```
     CastU256,
+    /// Create an instance of the enum variant specified via
+    /// `VariantHandleIndex` and push it on the stack. The values of the
+    /// fields of the variant, in the order they appear in the variant
+    /// declaration, must be pushed on the stack. All fields must be provided.
+    ///
+    /// Stack transition:
+    ///
+    /// ```..., field(1)_value, field(2)_value, ..., field(n)_value -> ...,
+    /// variant_value```
+    PackVariant(VariantHandleIndex),
+    PackVariantGeneric(VariantInstantiationHandleIndex),
+    /// Destroy an enum value of the variant specified via
+    /// `VariantHandleIndex` and push the values bound to each field of the
+    /// variant on the stack. Execution fails if the value is a different
+    /// variant of the enum.
+    ///
+    /// Stack transition:
+    ///
+    /// ```..., variant_value -> ..., field(1)_value, field(2)_value, ...,
+    /// field(n)_value```
+    UnpackVariant(VariantHandleIndex),
+    /// Same as `UnpackVariant`, but consumes an immutable reference to the
+    /// enum value and pushes immutable references to each of the fields.
+    ///
+    /// Stack transition:
+    ///
+    /// ```..., variant_reference -> ..., field(1)_reference, ...,
+    /// field(n)_reference```
+    UnpackVariantImmRef(VariantHandleIndex),
+    /// Same as `UnpackVariant`, but consumes a mutable reference to the enum
+    /// value and pushes mutable references to each of the fields.
+    ///
+    /// Stack transition:
+    ///
+    /// ```..., variant_reference -> ..., field(1)_reference, ...,
+    /// field(n)_reference```
+    UnpackVariantMutRef(VariantHandleIndex),
+    UnpackVariantGeneric(VariantInstantiationHandleIndex),
+    UnpackVariantGenericImmRef(VariantInstantiationHandleIndex),
+    UnpackVariantGenericMutRef(VariantInstantiationHandleIndex),
+    /// Branch to the instruction given by the `VariantJumpTable` for the tag
+    /// of the enum value referenced at the top of the stack. The reference can
+    /// be either immutable or mutable and is consumed.
+    ///
+    /// Stack transition:
+    ///
+    /// ```..., variant_reference -> ...```
+    VariantSwitch(VariantJumpTableIndex),
 
     // ******** DEPRECATED BYTECODES ********
     ExistsDeprecated(StructDefinitionIndex),
@@ -1778,6 +1956,19 @@ impl ::std::fmt::Debug for Bytecode {
             Bytecode::VecPopBack(a) => write!(f, "VecPopBack({})", a),
             Bytecode::VecUnpack(a, n) => write!(f, "VecUnpack({}, {})", a, n),
             Bytecode::VecSwap(a) => write!(f, "VecSwap({})", a),
+            Bytecode::PackVariant(a) => write!(f, "PackVariant({})", a),
+            Bytecode::PackVariantGeneric(a) => write!(f, "PackVariantGeneric({})", a),
+            Bytecode::UnpackVariant(a) => write!(f, "UnpackVariant({})", a),
+            Bytecode::UnpackVariantImmRef(a) => write!(f, "UnpackVariantImmRef({})", a),
+            Bytecode::UnpackVariantMutRef(a) => write!(f, "UnpackVariantMutRef({})", a),
+            Bytecode::UnpackVariantGeneric(a) => write!(f, "UnpackVariantGeneric({})", a),
+            Bytecode::UnpackVariantGenericImmRef(a) => {
+                write!(f, "UnpackVariantGenericImmRef({})", a)
+            }
+            Bytecode::UnpackVariantGenericMutRef(a) => {
+                write!(f, "UnpackVariantGenericMutRef({})", a)
+            }
+            Bytecode::VariantSwitch(a) => write!(f, "VariantSwitch({})", a),
         }
     }
 }
@@ -1785,7 +1976,11 @@ impl ::std::fmt::Debug for Bytecode {
 impl Bytecode {
     /// Return true if this bytecode instruction always branches
     pub fn is_unconditional_branch(&self) -> bool {
-        matches!(self, Bytecode::Ret | Bytecode::Abort | Bytecode::Branch(_))
+        // A switch never falls through: it always jumps to one of its targets
+        matches!(
+            self,
+            Bytecode::Ret | Bytecode::Abort | Bytecode::Branch(_) | Bytecode::VariantSwitch(_)
+        )
     }
 
     /// Return true if the branching behavior of this bytecode instruction
@@ -1800,19 +1995,30 @@ impl Bytecode {
         self.is_conditional_branch() || self.is_unconditional_branch()
     }
 
-    /// Returns the offset that this bytecode instruction branches to, if any.
+    /// Returns the offsets that this bytecode instruction branches to, if any.
     /// Note that return and abort are branch instructions, but have no offset.
-    pub fn offset(&self) -> Option<&CodeOffset> {
+    /// A `VariantSwitch` branches to every offset of its jump table.
+    pub fn offsets(&self, jump_tables: &[VariantJumpTable]) -> Vec<CodeOffset> {
         match self {
             Bytecode::BrFalse(offset) | Bytecode::BrTrue(offset) | Bytecode::Branch(offset) => {
-                Some(offset)
+                vec![*offset]
             }
-            _ => None,
+            Bytecode::VariantSwitch(jt_idx) => jump_tables
+                .get(jt_idx.into_index())
+                .map(|jt| match &jt.jump_table {
+                    JumpTableInner::Full(offsets) => offsets.clone(),
+                })
+                .unwrap_or_default(),
+            _ => vec![],
         }
     }
 
     /// Return the successor offsets of this bytecode instruction.
-    pub fn get_successors(pc: CodeOffset, code: &[Bytecode]) -> Vec<CodeOffset> {
+    pub fn get_successors(
+        pc: CodeOffset,
+        code: &[Bytecode],
+        jump_tables: &[VariantJumpTable],
+    ) -> Vec<CodeOffset> {
         assert!(
             // The program counter must remain within the bounds of the code
             pc < u16::MAX && (pc as usize) < code.len(),
@@ -1820,26 +2026,16 @@ impl Bytecode {
         );
 
         let bytecode = &code[pc as usize];
-        let mut v = vec![];
-
-        if let Some(offset) = bytecode.offset() {
-            v.push(*offset);
-        }
+        let mut v = bytecode.offsets(jump_tables);
 
         let next_pc = pc + 1;
-        if next_pc >= code.len() as CodeOffset {
-            return v;
+        if next_pc < code.len() as CodeOffset && !bytecode.is_unconditional_branch() {
+            v.push(next_pc);
         }
 
-        if !bytecode.is_unconditional_branch() && !v.contains(&next_pc) {
-            // avoid duplicates
-            v.push(pc + 1);
-        }
-
-        // always give successors in ascending order
-        if v.len() > 1 && v[0] > v[1] {
-            v.swap(0, 1);
-        }
+        // always give successors in ascending order, without duplicates
+        v.sort_unstable();
+        v.dedup();
 
         v
     }
@@ -1941,6 +2137,15 @@ pub struct CompiledModule {
     pub struct_defs: Vec<StructDefinition>,
     /// Function defined in this module.
     pub function_defs: Vec<FunctionDefinition>,
+
+    /// Enum types defined in this module.
+    pub enum_defs: Vec<EnumDefinition>,
+    /// Enum instantiations.
+    pub enum_def_instantiations: Vec<EnumDefInstantiation>,
+    /// Handles to variants of enums defined in this module.
+    pub variant_handles: Vec<VariantHandle>,
+    /// Handles to variants of enum instantiations.
+    pub variant_instantiation_handles: Vec<VariantInstantiationHandle>,
 }
 
 // Need a custom implementation of Arbitrary because as of proptest-derive
@@ -1978,7 +2183,7 @@ impl Arbitrary for CompiledScript {
                 )| {
                     // TODO actual constant generation
                     CompiledScript {
-                        version: file_format_common::VERSION_MAX,
+                        version: file_format_common::VERSION_DEFAULT,
                         module_handles,
                         struct_handles,
                         function_handles,
@@ -2034,7 +2239,7 @@ impl Arbitrary for CompiledModule {
                 )| {
                     // TODO actual constant generation
                     CompiledModule {
-                        version: file_format_common::VERSION_MAX,
+                        version: file_format_common::VERSION_DEFAULT,
                         module_handles,
                         struct_handles,
                         function_handles,
@@ -2051,6 +2256,10 @@ impl Arbitrary for CompiledModule {
                         metadata: vec![],
                         struct_defs,
                         function_defs,
+                        enum_defs: vec![],
+                        enum_def_instantiations: vec![],
+                        variant_handles: vec![],
+                        variant_instantiation_handles: vec![],
                     }
                 },
             )
@@ -2068,6 +2277,8 @@ impl CompiledModule {
                 | IndexKind::FieldDefinition
                 | IndexKind::TypeParameter
                 | IndexKind::MemberCount
+                | IndexKind::VariantJumpTable
+                | IndexKind::VariantTag
         ));
         match kind {
             IndexKind::ModuleHandle => self.module_handles.len(),
@@ -2084,12 +2295,18 @@ impl CompiledModule {
             IndexKind::Identifier => self.identifiers.len(),
             IndexKind::AddressIdentifier => self.address_identifiers.len(),
             IndexKind::ConstantPool => self.constant_pool.len(),
+            IndexKind::EnumDefinition => self.enum_defs.len(),
+            IndexKind::EnumDefInstantiation => self.enum_def_instantiations.len(),
+            IndexKind::VariantHandle => self.variant_handles.len(),
+            IndexKind::VariantInstantiationHandle => self.variant_instantiation_handles.len(),
             // XXX these two don't seem to belong here
             other @ IndexKind::LocalPool
             | other @ IndexKind::CodeDefinition
             | other @ IndexKind::FieldDefinition
             | other @ IndexKind::TypeParameter
-            | other @ IndexKind::MemberCount => unreachable!("invalid kind for count: {:?}", other),
+            | other @ IndexKind::MemberCount
+            | other @ IndexKind::VariantJumpTable
+            | other @ IndexKind::VariantTag => unreachable!("invalid kind for count: {:?}", other),
         }
     }
 
@@ -2110,7 +2327,7 @@ impl CompiledModule {
 /// Return the simplest module that will pass the bounds checker
 pub fn empty_module() -> CompiledModule {
     CompiledModule {
-        version: file_format_common::VERSION_MAX,
+        version: file_format_common::VERSION_DEFAULT,
         module_handles: vec![ModuleHandle {
             address: AddressIdentifierIndex(0),
             name: IdentifierIndex(0),
@@ -2130,6 +2347,10 @@ pub fn empty_module() -> CompiledModule {
         function_instantiations: vec![],
         field_instantiations: vec![],
         signatures: vec![Signature(vec![])],
+        enum_defs: vec![],
+        enum_def_instantiations: vec![],
+        variant_handles: vec![],
+        variant_instantiation_handles: vec![],
     }
 }
 
@@ -2163,6 +2384,7 @@ pub fn basic_test_module() -> CompiledModule {
         code: Some(CodeUnit {
             locals: SignatureIndex(0),
             code: vec![Bytecode::Ret],
+            jump_tables: vec![],
         }),
     });
 
@@ -2188,10 +2410,197 @@ pub fn basic_test_module() -> CompiledModule {
     m
 }
 
+/// Create the following module using enums, convenient in tests:
+/// ```move,ignore
+/// module <SELF> {
+///     enum E has copy, drop { A { x: u64 }, B }
+///     enum Box<T: copy + drop> has copy, drop { V { v: T } }
+///
+///     f(x: u64, b: bool): u64 {
+///         let e = if (b) E::A { x } else E::B;
+///         match (&e) {
+///             E::A { .. } => { let E::A { x } = e; x },
+///             E::B => { let E::B = &e; 0 },
+///         }
+///     }
+///
+///     g(x: u64): u64 {
+///         let b = Box::V<u64> { v: x };
+///         match (&b) {
+///             Box::V { v } => *v,
+///         }
+///     }
+/// }
+/// ```
+pub fn basic_enum_test_module() -> CompiledModule {
+    let mut m = empty_module();
+    m.version = file_format_common::VERSION_7;
+    m.identifiers.extend(
+        ["E", "A", "B", "x", "Box", "V", "v", "f", "g"]
+            .into_iter()
+            .map(|name| Identifier::new(name).unwrap()),
+    );
+    m.signatures = vec![
+        Signature(vec![]),
+        Signature(vec![SignatureToken::U64, SignatureToken::Bool]),
+        Signature(vec![SignatureToken::U64]),
+        Signature(vec![SignatureToken::Struct(StructHandleIndex(0))]),
+        Signature(vec![SignatureToken::StructInstantiation(Box::new((
+            StructHandleIndex(1),
+            vec![SignatureToken::U64],
+        )))]),
+    ];
+    let copy_drop = AbilitySet::EMPTY | Ability::Copy | Ability::Drop;
+
+    m.struct_handles = vec![
+        StructHandle {
+            module: ModuleHandleIndex(0),
+            name: IdentifierIndex(1),
+            abilities: copy_drop,
+            type_parameters: vec![],
+        },
+        StructHandle {
+            module: ModuleHandleIndex(0),
+            name: IdentifierIndex(5),
+            abilities: copy_drop,
+            type_parameters: vec![StructTypeParameter {
+                constraints: copy_drop,
+                is_phantom: false,
+            }],
+        },
+    ];
+    m.enum_defs = vec![
+        EnumDefinition {
+            enum_handle: StructHandleIndex(0),
+            variants: vec![
+                VariantDefinition {
+                    variant_name: IdentifierIndex(2),
+                    fields: vec![FieldDefinition {
+                        name: IdentifierIndex(4),
+                        signature: TypeSignature(SignatureToken::U64),
+                    }],
+                },
+                VariantDefinition {
+                    variant_name: IdentifierIndex(3),
+                    fields: vec![],
+                },
+            ],
+        },
+        EnumDefinition {
+            enum_handle: StructHandleIndex(1),
+            variants: vec![VariantDefinition {
+                variant_name: IdentifierIndex(6),
+                fields: vec![FieldDefinition {
+                    name: IdentifierIndex(7),
+                    signature: TypeSignature(SignatureToken::TypeParameter(0)),
+                }],
+            }],
+        },
+    ];
+    m.enum_def_instantiations = vec![EnumDefInstantiation {
+        def: EnumDefinitionIndex(1),
+        type_parameters: SignatureIndex(2),
+    }];
+    m.variant_handles = vec![
+        VariantHandle {
+            enum_def: EnumDefinitionIndex(0),
+            variant: 0,
+        },
+        VariantHandle {
+            enum_def: EnumDefinitionIndex(0),
+            variant: 1,
+        },
+    ];
+    m.variant_instantiation_handles = vec![VariantInstantiationHandle {
+        enum_def: EnumDefInstantiationIndex(0),
+        variant: 0,
+    }];
+
+    m.function_handles = vec![
+        FunctionHandle {
+            module: ModuleHandleIndex(0),
+            name: IdentifierIndex(8),
+            parameters: SignatureIndex(1),
+            return_: SignatureIndex(2),
+            type_parameters: vec![],
+        },
+        FunctionHandle {
+            module: ModuleHandleIndex(0),
+            name: IdentifierIndex(9),
+            parameters: SignatureIndex(2),
+            return_: SignatureIndex(2),
+            type_parameters: vec![],
+        },
+    ];
+    m.function_defs = vec![
+        FunctionDefinition {
+            function: FunctionHandleIndex(0),
+            visibility: Visibility::Public,
+            is_entry: false,
+            acquires_global_resources: vec![],
+            code: Some(CodeUnit {
+                locals: SignatureIndex(3),
+                code: vec![
+                    Bytecode::MoveLoc(1),
+                    Bytecode::BrFalse(6),
+                    Bytecode::CopyLoc(0),
+                    Bytecode::PackVariant(VariantHandleIndex(0)),
+                    Bytecode::StLoc(2),
+                    Bytecode::Branch(8),
+                    Bytecode::PackVariant(VariantHandleIndex(1)),
+                    Bytecode::StLoc(2),
+                    Bytecode::ImmBorrowLoc(2),
+                    Bytecode::VariantSwitch(VariantJumpTableIndex(0)),
+                    // E::A
+                    Bytecode::MoveLoc(2),
+                    Bytecode::UnpackVariant(VariantHandleIndex(0)),
+                    Bytecode::Ret,
+                    // E::B
+                    Bytecode::ImmBorrowLoc(2),
+                    Bytecode::UnpackVariantImmRef(VariantHandleIndex(1)),
+                    Bytecode::LdU64(0),
+                    Bytecode::Ret,
+                ],
+                jump_tables: vec![VariantJumpTable {
+                    head_enum: EnumDefinitionIndex(0),
+                    jump_table: JumpTableInner::Full(vec![10, 13]),
+                }],
+            }),
+        },
+        FunctionDefinition {
+            function: FunctionHandleIndex(1),
+            visibility: Visibility::Public,
+            is_entry: false,
+            acquires_global_resources: vec![],
+            code: Some(CodeUnit {
+                locals: SignatureIndex(4),
+                code: vec![
+                    Bytecode::MoveLoc(0),
+                    Bytecode::PackVariantGeneric(VariantInstantiationHandleIndex(0)),
+                    Bytecode::StLoc(1),
+                    Bytecode::ImmBorrowLoc(1),
+                    Bytecode::VariantSwitch(VariantJumpTableIndex(0)),
+                    // Box::V
+                    Bytecode::ImmBorrowLoc(1),
+                    Bytecode::UnpackVariantGenericImmRef(VariantInstantiationHandleIndex(0)),
+                    Bytecode::ReadRef,
+                    Bytecode::Ret,
+                ],
+                jump_tables: vec![VariantJumpTable {
+                    head_enum: EnumDefinitionIndex(1),
+                    jump_table: JumpTableInner::Full(vec![5]),
+                }],
+            }),
+        },
+    ];
+
+    m
+}
+
 /// Return a simple script that contains only a return in the main()
 pub fn empty_script() -> CompiledScript {
     CompiledScript {
-        version: file_format_common::VERSION_MAX,
+        version: file_format_common::VERSION_DEFAULT,
         module_handles: vec![],
         struct_handles: vec![],
         function_handles: vec![],
@@ -2210,6 +2619,7 @@ pub fn empty_script() -> CompiledScript {
         code: CodeUnit {
             locals: SignatureIndex(0),
             code: vec![Bytecode::Ret],
+            jump_tables: vec![],
         },
     }
 }
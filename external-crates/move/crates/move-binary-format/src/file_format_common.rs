@@ -56,6 +56,11 @@ pub const FIELD_HANDLE_INDEX_MAX: u64 = TABLE_INDEX_MAX;
 pub const FIELD_INST_INDEX_MAX: u64 = TABLE_INDEX_MAX;
 pub const STRUCT_DEF_INST_INDEX_MAX: u64 = TABLE_INDEX_MAX;
 pub const CONSTANT_INDEX_MAX: u64 = TABLE_INDEX_MAX;
+pub const ENUM_DEF_INDEX_MAX: u64 = TABLE_INDEX_MAX;
+pub const ENUM_DEF_INST_INDEX_MAX: u64 = TABLE_INDEX_MAX;
+pub const VARIANT_HANDLE_INDEX_MAX: u64 = TABLE_INDEX_MAX;
+pub const VARIANT_INST_HANDLE_INDEX_MAX: u64 = TABLE_INDEX_MAX;
+pub const VARIANT_JUMP_TABLE_INDEX_MAX: u64 = TABLE_INDEX_MAX;
 
 pub const BYTECODE_COUNT_MAX: u64 = 65535;
 pub const BYTECODE_INDEX_MAX: u64 = 65535;
@@ -76,6 +81,10 @@ pub const ACQUIRES_COUNT_MAX: u64 = 255;
 pub const FIELD_COUNT_MAX: u64 = 255;
 pub const FIELD_OFFSET_MAX: u64 = 255;
 
+pub const VARIANT_COUNT_MAX: u64 = 127;
+pub const VARIANT_TAG_MAX: u64 = VARIANT_COUNT_MAX - 1;
+pub const JUMP_TABLE_COUNT_MAX: u64 = 255;
+
 pub const TYPE_PARAMETER_COUNT_MAX: u64 = 255;
 pub const TYPE_PARAMETER_INDEX_MAX: u64 = 65536;
 
@@ -105,6 +114,10 @@ pub enum TableType {
     FIELD_INST              = 0xE,
     FRIEND_DECLS            = 0xF,
     METADATA                = 0x10,
+    ENUM_DEFS               = 0x11,
+    ENUM_DEF_INST           = 0x12,
+    VARIANT_HANDLES         = 0x13,
+    VARIANT_INST_HANDLES    = 0x14,
 }
 
 /// Constants for signature blob values.
@@ -139,6 +152,14 @@ pub enum SerializedNativeStructFlag {
     DECLARED                = 0x2,
 }
 
+#[rustfmt::skip]
+#[allow(non_camel_case_types)]
+#[repr(u8)]
+#[derive(Clone, Copy, Debug)]
+pub enum SerializedJumpTableFlag {
+    FULL                    = 0x1,
+}
+
 /// List of opcodes constants.
 #[rustfmt::skip]
 #[allow(non_camel_case_types)]
@@ -213,6 +234,15 @@ pub enum Opcodes {
     CAST_U16                    = 0x4B,
     CAST_U32                    = 0x4C,
     CAST_U256                   = 0x4D,
+    PACK_VARIANT                = 0x4E,
+    PACK_VARIANT_GENERIC        = 0x4F,
+    UNPACK_VARIANT              = 0x50,
+    UNPACK_VARIANT_IMM_REF      = 0x51,
+    UNPACK_VARIANT_MUT_REF      = 0x52,
+    UNPACK_VARIANT_GENERIC      = 0x53,
+    UNPACK_VARIANT_GENERIC_IMM_REF = 0x54,
+    UNPACK_VARIANT_GENERIC_MUT_REF = 0x55,
+    VARIANT_SWITCH              = 0x56,
 
     // ******** DEPRECATED BYTECODES ********
     // global storage opcodes are unused and deprecated
@@ -413,8 +443,17 @@ pub const VERSION_5: u32 = 5;
 ///  + u16, u32, u256 integers and corresponding Ld, Cast bytecodes
 pub const VERSION_6: u32 = 6;
 
+/// Version 7: changes compared with version 6
+///  + enum definitions, enum instantiations and variant handles
+///  + variant jump tables in code units
+///  + bytecodes to pack, unpack and switch over enum variants
+pub const VERSION_7: u32 = 7;
+
 // Mark which version is the latest version
-pub const VERSION_MAX: u32 = VERSION_6;
+pub const VERSION_MAX: u32 = VERSION_7;
+
+// Mark which version binaries are produced at when they do not use enums
+pub const VERSION_DEFAULT: u32 = VERSION_6;
 
 // Mark which oldest version is supported.
 // TODO(#145): finish v4 compatibility; as of now, only metadata is implemented
@@ -492,6 +531,15 @@ pub fn instruction_key(instruction: &Bytecode) -> u8 {
         CastU16 => Opcodes::CAST_U16,
         CastU32 => Opcodes::CAST_U32,
         CastU256 => Opcodes::CAST_U256,
+        PackVariant(_) => Opcodes::PACK_VARIANT,
+        PackVariantGeneric(_) => Opcodes::PACK_VARIANT_GENERIC,
+        UnpackVariant(_) => Opcodes::UNPACK_VARIANT,
+        UnpackVariantImmRef(_) => Opcodes::UNPACK_VARIANT_IMM_REF,
+        UnpackVariantMutRef(_) => Opcodes::UNPACK_VARIANT_MUT_REF,
+        UnpackVariantGeneric(_) => Opcodes::UNPACK_VARIANT_GENERIC,
+        UnpackVariantGenericImmRef(_) => Opcodes::UNPACK_VARIANT_GENERIC_IMM_REF,
+        UnpackVariantGenericMutRef(_) => Opcodes::UNPACK_VARIANT_GENERIC_MUT_REF,
+        VariantSwitch(_) => Opcodes::VARIANT_SWITCH,
         // ******** DEPRECATED BYTECODES ********
         ExistsDeprecated(_) => Opcodes::EXISTS_DEPRECATED,
         ExistsGenericDeprecated(_) => Opcodes::EXISTS_GENERIC_DEPRECATED,
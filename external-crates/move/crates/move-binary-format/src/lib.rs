@@ -53,6 +53,12 @@ pub enum IndexKind {
     CodeDefinition,
     TypeParameter,
     MemberCount,
+    EnumDefinition,
+    EnumDefInstantiation,
+    VariantHandle,
+    VariantInstantiationHandle,
+    VariantJumpTable,
+    VariantTag,
 }
 
 impl IndexKind {
@@ -79,6 +85,12 @@ impl IndexKind {
             CodeDefinition,
             TypeParameter,
             MemberCount,
+            EnumDefinition,
+            EnumDefInstantiation,
+            VariantHandle,
+            VariantInstantiationHandle,
+            VariantJumpTable,
+            VariantTag,
         ]
     }
 }
@@ -107,6 +119,12 @@ impl fmt::Display for IndexKind {
             CodeDefinition => "code definition pool",
             TypeParameter => "type parameter",
             MemberCount => "field offset",
+            EnumDefinition => "enum definition",
+            EnumDefInstantiation => "enum instantiation",
+            VariantHandle => "variant handle",
+            VariantInstantiationHandle => "variant instantiation handle",
+            VariantJumpTable => "variant jump table",
+            VariantTag => "variant tag",
         };
 
         f.write_str(desc)
@@ -16,13 +16,16 @@ use serde::{Deserialize, Serialize};
 use crate::{
     access::ModuleAccess,
     file_format::{
-        AbilitySet, Bytecode as FBytecode, CodeOffset, CompiledModule, FieldDefinition,
-        FieldHandle, FieldHandleIndex, FieldInstantiation, FieldInstantiationIndex,
-        FunctionDefinition, FunctionHandle, FunctionHandleIndex, FunctionInstantiation, LocalIndex,
-        SignatureIndex, SignatureToken, StructDefInstantiation, StructDefInstantiationIndex,
-        StructDefinition, StructDefinitionIndex, StructFieldInformation, StructTypeParameter,
-        TypeParameterIndex, Visibility,
+        AbilitySet, Bytecode as FBytecode, CodeOffset, CodeUnit, CompiledModule,
+        EnumDefInstantiation, EnumDefinition, FieldDefinition, FieldHandle, FieldHandleIndex,
+        FieldInstantiation, FieldInstantiationIndex, FunctionDefinition, FunctionHandle,
+        FunctionHandleIndex, FunctionInstantiation, JumpTableInner, LocalIndex, SignatureIndex,
+        SignatureToken, StructDefInstantiation, StructDefInstantiationIndex, StructDefinition,
+        StructDefinitionIndex, StructFieldInformation, StructTypeParameter, TypeParameterIndex,
+        VariantDefinition, VariantHandleIndex, VariantInstantiationHandleIndex, VariantTag,
+        Visibility,
     },
+    internals::ModuleIndex,
 };
 
 /// Defines normalized representations of Move types, fields, kinds, structs,
@@ -98,6 +101,23 @@ pub struct Struct {
     pub fields: Vec<Field>,
 }
 
+/// Normalized version of an `EnumDefinition`. Not safe to compare without an
+/// associated `ModuleId` or `Module`.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Ord, PartialOrd)]
+pub struct Enum {
+    pub abilities: AbilitySet,
+    pub type_parameters: Vec<StructTypeParameter>,
+    pub variants: Vec<Variant>,
+}
+
+/// Normalized version of a `VariantDefinition`. Variants are compared in
+/// declaration order, as the position of a variant is its tag.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Ord, PartialOrd)]
+pub struct Variant {
+    pub name: Identifier,
+    pub fields: Vec<Field>,
+}
+
 /// Normalized version of a `FunctionDefinition`. Not safe to compare without an
 /// associated `ModuleId` or `Module`.
 #[derive(Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Serialize, Deserialize)]
@@ -129,6 +149,12 @@ pub struct FieldRef {
 //   - The callee was in a different package and therefore public, and therefore
 //     the API of that
 //   function must not have changed by compatibility rules.
+#[derive(Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Serialize, Deserialize)]
+pub struct VariantRef {
+    pub enum_name: Identifier,
+    pub variant: VariantTag,
+}
+
 #[derive(Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Serialize, Deserialize)]
 pub struct FunctionRef {
     pub module_id: ModuleId,
@@ -205,6 +231,15 @@ pub enum Bytecode {
     CastU16,
     CastU32,
     CastU256,
+    PackVariant(VariantRef),
+    PackVariantGeneric((VariantRef, Vec<Type>)),
+    UnpackVariant(VariantRef),
+    UnpackVariantImmRef(VariantRef),
+    UnpackVariantMutRef(VariantRef),
+    UnpackVariantGeneric((VariantRef, Vec<Type>)),
+    UnpackVariantGenericImmRef((VariantRef, Vec<Type>)),
+    UnpackVariantGenericMutRef((VariantRef, Vec<Type>)),
+    VariantSwitch((Identifier, Vec<CodeOffset>)),
     // ******** DEPRECATED BYTECODES ********
     MutBorrowGlobalDeprecated(Identifier),
     MutBorrowGlobalGenericDeprecated((Identifier, Vec<Type>)),
@@ -237,6 +272,8 @@ pub struct Module {
     pub dependencies: Vec<ModuleId>,
     pub friends: Vec<ModuleId>,
     pub structs: BTreeMap<Identifier, Struct>,
+    #[serde(default)]
+    pub enums: BTreeMap<Identifier, Enum>,
     pub functions: BTreeMap<Identifier, Function>,
     pub constants: Vec<Constant>,
 }
@@ -249,6 +286,7 @@ impl Module {
     pub fn new(m: &CompiledModule) -> Self {
         let friends = m.immediate_friends();
         let structs = m.struct_defs().iter().map(|d| Struct::new(m, d)).collect();
+        let enums = m.enum_defs().iter().map(|d| Enum::new(m, d)).collect();
         let dependencies = m.immediate_dependencies();
         let constants = m
             .constant_pool()
@@ -266,6 +304,7 @@ impl Module {
             name: m.name().to_owned(),
             friends,
             structs,
+            enums,
             functions,
             dependencies,
             constants,
@@ -463,6 +502,34 @@ impl Struct {
     }
 }
 
+impl Enum {
+    /// Create an `Enum` for `EnumDefinition` `def` in module `m`.
+    pub fn new(m: &CompiledModule, def: &EnumDefinition) -> (Identifier, Self) {
+        let handle = m.struct_handle_at(def.enum_handle);
+        let name = m.identifier_at(handle.name).to_owned();
+        let e = Enum {
+            abilities: handle.abilities,
+            type_parameters: handle.type_parameters.clone(),
+            variants: def.variants.iter().map(|v| Variant::new(m, v)).collect(),
+        };
+        (name, e)
+    }
+
+    pub fn type_param_constraints(&self) -> impl ExactSizeIterator<Item = &AbilitySet> {
+        self.type_parameters.iter().map(|param| &param.constraints)
+    }
+}
+
+impl Variant {
+    /// Create a `Variant` for `VariantDefinition` `v` in module `m`.
+    pub fn new(m: &CompiledModule, v: &VariantDefinition) -> Self {
+        Variant {
+            name: m.identifier_at(v.variant_name).to_owned(),
+            fields: v.fields.iter().map(|f| Field::new(m, f)).collect(),
+        }
+    }
+}
+
 impl Function {
     /// Create a `FunctionSignature` for `FunctionHandle` `f` in module `m`.
     pub fn new(m: &CompiledModule, def: &FunctionDefinition) -> (Identifier, Self) {
@@ -474,7 +541,7 @@ impl Function {
             .map(|code| {
                 code.code
                     .iter()
-                    .map(|bytecode| Bytecode::new(m, bytecode))
+                    .map(|bytecode| Bytecode::new(m, code, bytecode))
                     .collect()
             })
             .unwrap_or_default();
@@ -548,6 +615,16 @@ impl FieldRef {
     }
 }
 
+impl VariantRef {
+    pub fn from_idx(m: &CompiledModule, idx: &VariantHandleIndex) -> Self {
+        let handle = m.variant_handle_at(*idx);
+        Self {
+            enum_name: m.enum_name(handle.enum_def).to_owned(),
+            variant: handle.variant,
+        }
+    }
+}
+
 impl FunctionRef {
     pub fn new(m: &CompiledModule, function_handle: &FunctionHandle) -> Self {
         Self {
@@ -562,7 +639,7 @@ impl FunctionRef {
 }
 
 impl Bytecode {
-    pub fn new(m: &CompiledModule, bytecode: &FBytecode) -> Self {
+    pub fn new(m: &CompiledModule, code: &CodeUnit, bytecode: &FBytecode) -> Self {
         use Bytecode as B;
         use FBytecode as FB;
         match bytecode {
@@ -673,6 +750,33 @@ impl Bytecode {
             FB::VecPopBack(sig_idx) => B::VecPopBack(signature_to_single_type(m, sig_idx)),
             FB::VecUnpack(sig_idx, len) => B::VecUnpack(signature_to_single_type(m, sig_idx), *len),
             FB::VecSwap(sig_idx) => B::VecSwap(signature_to_single_type(m, sig_idx)),
+            FB::PackVariant(vh_idx) => B::PackVariant(VariantRef::from_idx(m, vh_idx)),
+            FB::PackVariantGeneric(vi_idx) => {
+                B::PackVariantGeneric(variant_instantiation(m, vi_idx))
+            }
+            FB::UnpackVariant(vh_idx) => B::UnpackVariant(VariantRef::from_idx(m, vh_idx)),
+            FB::UnpackVariantImmRef(vh_idx) => {
+                B::UnpackVariantImmRef(VariantRef::from_idx(m, vh_idx))
+            }
+            FB::UnpackVariantMutRef(vh_idx) => {
+                B::UnpackVariantMutRef(VariantRef::from_idx(m, vh_idx))
+            }
+            FB::UnpackVariantGeneric(vi_idx) => {
+                B::UnpackVariantGeneric(variant_instantiation(m, vi_idx))
+            }
+            FB::UnpackVariantGenericImmRef(vi_idx) => {
+                B::UnpackVariantGenericImmRef(variant_instantiation(m, vi_idx))
+            }
+            FB::UnpackVariantGenericMutRef(vi_idx) => {
+                B::UnpackVariantGenericMutRef(variant_instantiation(m, vi_idx))
+            }
+            FB::VariantSwitch(jt_idx) => {
+                let jump_table = &code.jump_tables[jt_idx.into_index()];
+                let offsets = match &jump_table.jump_table {
+                    JumpTableInner::Full(offsets) => offsets.clone(),
+                };
+                B::VariantSwitch((m.enum_name(jump_table.head_enum).to_owned(), offsets))
+            }
         }
     }
 }
@@ -753,6 +857,28 @@ fn field_instantiation(m: &CompiledModule, idx: &FieldInstantiationIndex) -> (Fi
     (field_ref, types)
 }
 
+fn variant_instantiation(
+    m: &CompiledModule,
+    vi_idx: &VariantInstantiationHandleIndex,
+) -> (VariantRef, Vec<Type>) {
+    let handle = m.variant_instantiation_handle_at(*vi_idx);
+    let EnumDefInstantiation {
+        def,
+        type_parameters,
+    } = m.enum_instantiation_at(handle.enum_def);
+    let variant_ref = VariantRef {
+        enum_name: m.enum_name(*def).to_owned(),
+        variant: handle.variant,
+    };
+    let types = m
+        .signature_at(*type_parameters)
+        .0
+        .iter()
+        .map(|tok| Type::new(m, tok))
+        .collect();
+    (variant_ref, types)
+}
+
 fn signature_to_single_type(m: &CompiledModule, sig_idx: &SignatureIndex) -> Type {
     Type::new(m, &m.signature_at(*sig_idx).0[0])
 }
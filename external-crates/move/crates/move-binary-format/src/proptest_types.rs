@@ -365,7 +365,7 @@ impl CompiledModuleStrategyGen {
 
                     // Build a compiled module
                     CompiledModule {
-                        version: crate::file_format_common::VERSION_MAX,
+                        version: crate::file_format_common::VERSION_DEFAULT,
                         module_handles,
                         self_module_handle_idx,
                         struct_handles,
@@ -386,6 +386,10 @@ impl CompiledModuleStrategyGen {
                         address_identifiers,
                         constant_pool,
                         metadata,
+                        enum_defs: vec![],
+                        enum_def_instantiations: vec![],
+                        variant_handles: vec![],
+                        variant_instantiation_handles: vec![],
                     }
                 },
             )
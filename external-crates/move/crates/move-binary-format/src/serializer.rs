@@ -16,6 +16,11 @@
 //! generate compatible formats. However, not all of the newer language
 //! constructs might be supported for older versions, leading to serialization
 //! errors.
+//!
+//! When no version is requested, binaries that do not use enums are written at
+//! `VERSION_6` and only binaries that do are written at `VERSION_7`. This
+//! keeps the bytes of existing modules, and hence the digests of the packages
+//! containing them, stable.
 
 use anyhow::{anyhow, bail, Result};
 use move_core_types::{
@@ -37,7 +42,7 @@ impl CompiledScript {
         bytecode_version: Option<u32>,
         binary: &mut Vec<u8>,
     ) -> Result<()> {
-        let version = bytecode_version.unwrap_or(VERSION_MAX);
+        let version = bytecode_version.unwrap_or_else(|| default_version(script_uses_enums(self)));
         validate_version(version)?;
         let mut binary_data = BinaryData::from(binary.clone());
         let mut ser = ScriptSerializer::new(version);
@@ -203,6 +208,90 @@ fn serialize_local_index(binary: &mut BinaryData, idx: u8) -> Result<()> {
     write_as_uleb128(binary, idx, LOCAL_INDEX_MAX)
 }
 
+fn serialize_enum_def_index(binary: &mut BinaryData, idx: &EnumDefinitionIndex) -> Result<()> {
+    write_as_uleb128(binary, idx.0, ENUM_DEF_INDEX_MAX)
+}
+
+fn serialize_enum_def_inst_index(
+    binary: &mut BinaryData,
+    idx: &EnumDefInstantiationIndex,
+) -> Result<()> {
+    write_as_uleb128(binary, idx.0, ENUM_DEF_INST_INDEX_MAX)
+}
+
+fn serialize_variant_handle_index(binary: &mut BinaryData, idx: &VariantHandleIndex) -> Result<()> {
+    write_as_uleb128(binary, idx.0, VARIANT_HANDLE_INDEX_MAX)
+}
+
+fn serialize_variant_inst_handle_index(
+    binary: &mut BinaryData,
+    idx: &VariantInstantiationHandleIndex,
+) -> Result<()> {
+    write_as_uleb128(binary, idx.0, VARIANT_INST_HANDLE_INDEX_MAX)
+}
+
+fn serialize_variant_jump_table_index(
+    binary: &mut BinaryData,
+    idx: &VariantJumpTableIndex,
+) -> Result<()> {
+    write_as_uleb128(binary, idx.0, VARIANT_JUMP_TABLE_INDEX_MAX)
+}
+
+fn serialize_variant_count(binary: &mut BinaryData, len: usize) -> Result<()> {
+    write_as_uleb128(binary, len as u64, VARIANT_COUNT_MAX)
+}
+
+fn serialize_variant_tag(binary: &mut BinaryData, tag: VariantTag) -> Result<()> {
+    write_as_uleb128(binary, tag, VARIANT_TAG_MAX)
+}
+
+fn serialize_jump_table_count(binary: &mut BinaryData, len: usize) -> Result<()> {
+    write_as_uleb128(binary, len as u64, JUMP_TABLE_COUNT_MAX)
+}
+
+/// The version to serialize at when none is requested.
+fn default_version(uses_enums: bool) -> u32 {
+    if uses_enums {
+        VERSION_7
+    } else {
+        VERSION_DEFAULT
+    }
+}
+
+fn code_uses_enums(code: &CodeUnit) -> bool {
+    !code.jump_tables.is_empty()
+        || code.code.iter().any(|instr| {
+            matches!(
+                instr,
+                Bytecode::PackVariant(_)
+                    | Bytecode::PackVariantGeneric(_)
+                    | Bytecode::UnpackVariant(_)
+                    | Bytecode::UnpackVariantImmRef(_)
+                    | Bytecode::UnpackVariantMutRef(_)
+                    | Bytecode::UnpackVariantGeneric(_)
+                    | Bytecode::UnpackVariantGenericImmRef(_)
+                    | Bytecode::UnpackVariantGenericMutRef(_)
+                    | Bytecode::VariantSwitch(_)
+            )
+        })
+}
+
+fn module_uses_enums(module: &CompiledModule) -> bool {
+    !module.enum_defs.is_empty()
+        || !module.enum_def_instantiations.is_empty()
+        || !module.variant_handles.is_empty()
+        || !module.variant_instantiation_handles.is_empty()
+        || module
+            .function_defs
+            .iter()
+            .filter_map(|def| def.code.as_ref())
+            .any(code_uses_enums)
+}
+
+fn script_uses_enums(script: &CompiledScript) -> bool {
+    code_uses_enums(&script.code)
+}
+
 fn validate_version(version: u32) -> Result<()> {
     if !(VERSION_MIN..=VERSION_MAX).contains(&version) {
         bail!(
@@ -229,7 +318,7 @@ impl CompiledModule {
         bytecode_version: Option<u32>,
         binary: &mut Vec<u8>,
     ) -> Result<()> {
-        let version = bytecode_version.unwrap_or(VERSION_MAX);
+        let version = bytecode_version.unwrap_or_else(|| default_version(module_uses_enums(self)));
         validate_version(version)?;
         let mut binary_data = BinaryData::from(binary.clone());
         let mut ser = ModuleSerializer::new(version);
@@ -286,6 +375,10 @@ struct ModuleSerializer {
     field_handles: (u32, u32),
     field_instantiations: (u32, u32),
     friend_decls: (u32, u32),
+    enum_defs: (u32, u32),
+    enum_def_instantiations: (u32, u32),
+    variant_handles: (u32, u32),
+    variant_instantiation_handles: (u32, u32),
 }
 
 /// Holds data to compute the header of a transaction script binary.
@@ -577,6 +670,61 @@ fn serialize_struct_def_instantiation(
     Ok(())
 }
 
+fn serialize_enum_def_instantiation(
+    binary: &mut BinaryData,
+    enum_inst: &EnumDefInstantiation,
+) -> Result<()> {
+    serialize_enum_def_index(binary, &enum_inst.def)?;
+    serialize_signature_index(binary, &enum_inst.type_parameters)?;
+    Ok(())
+}
+
+/// Serializes an `EnumDefinition`.
+///
+/// An `EnumDefinition` gets serialized as follows:
+/// - `EnumDefinition.enum_handle` as a ULEB128 (index into the `StructHandle`
+///   table)
+/// - `EnumDefinition.variants` as a ULEB128 count followed by each
+///   `VariantDefinition`
+fn serialize_enum_definition(
+    binary: &mut BinaryData,
+    enum_definition: &EnumDefinition,
+) -> Result<()> {
+    serialize_struct_handle_index(binary, &enum_definition.enum_handle)?;
+    serialize_variant_count(binary, enum_definition.variants.len())?;
+    for variant in &enum_definition.variants {
+        serialize_variant_definition(binary, variant)?;
+    }
+    Ok(())
+}
+
+/// Serializes a `VariantDefinition`.
+///
+/// A `VariantDefinition` gets serialized as follows:
+/// - `VariantDefinition.variant_name` as a ULEB128 (index into the
+///   `IdentifierPool` table)
+/// - `VariantDefinition.fields` as the `FieldDefinition`s of a struct
+fn serialize_variant_definition(
+    binary: &mut BinaryData,
+    variant_definition: &VariantDefinition,
+) -> Result<()> {
+    serialize_identifier_index(binary, &variant_definition.variant_name)?;
+    serialize_field_definitions(binary, &variant_definition.fields)
+}
+
+fn serialize_variant_handle(binary: &mut BinaryData, variant_handle: &VariantHandle) -> Result<()> {
+    serialize_enum_def_index(binary, &variant_handle.enum_def)?;
+    serialize_variant_tag(binary, variant_handle.variant)
+}
+
+fn serialize_variant_instantiation_handle(
+    binary: &mut BinaryData,
+    variant_inst_handle: &VariantInstantiationHandle,
+) -> Result<()> {
+    serialize_enum_def_inst_index(binary, &variant_inst_handle.enum_def)?;
+    serialize_variant_tag(binary, variant_inst_handle.variant)
+}
+
 /// Serializes `FieldDefinition` within a struct.
 fn serialize_field_definitions(binary: &mut BinaryData, fields: &[FieldDefinition]) -> Result<()> {
     serialize_field_count(binary, fields.len())?;
@@ -733,9 +881,45 @@ fn serialize_ability_sets(binary: &mut BinaryData, sets: &[AbilitySet]) -> Resul
 /// - `CodeUnit.max_stack_size` as a ULEB128
 /// - `CodeUnit.locals` as a ULEB128 (index into the `LocalSignaturePool`)
 /// - `CodeUnit.code` as variable size byte stream for the bytecode
+/// - `CodeUnit.jump_tables` as a ULEB128 count followed by each
+///   `VariantJumpTable` (only from `VERSION_7` on)
 fn serialize_code_unit(major_version: u32, binary: &mut BinaryData, code: &CodeUnit) -> Result<()> {
     serialize_signature_index(binary, &code.locals)?;
-    serialize_code(major_version, binary, &code.code)
+    serialize_code(major_version, binary, &code.code)?;
+    if major_version >= VERSION_7 {
+        serialize_jump_tables(binary, &code.jump_tables)
+    } else if !code.jump_tables.is_empty() {
+        Err(anyhow!(
+            "Variant jump tables not supported in bytecode version {}",
+            major_version
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// Serializes the `VariantJumpTable`s of a `CodeUnit`.
+///
+/// A `VariantJumpTable` gets serialized as follows:
+/// - `VariantJumpTable.head_enum` as a ULEB128 (index into the `EnumDefinition`
+///   table)
+/// - a byte for the kind of the jump table (only `FULL` for now)
+/// - the code offsets of the table, as a ULEB128 count followed by each offset
+fn serialize_jump_tables(binary: &mut BinaryData, jump_tables: &[VariantJumpTable]) -> Result<()> {
+    serialize_jump_table_count(binary, jump_tables.len())?;
+    for jump_table in jump_tables {
+        serialize_enum_def_index(binary, &jump_table.head_enum)?;
+        match &jump_table.jump_table {
+            JumpTableInner::Full(offsets) => {
+                binary.push(SerializedJumpTableFlag::FULL as u8)?;
+                serialize_variant_count(binary, offsets.len())?;
+                for offset in offsets {
+                    serialize_bytecode_offset(binary, *offset)?;
+                }
+            }
+        }
+    }
+    Ok(())
 }
 
 /// Serializes a single `Bytecode` instruction.
@@ -758,6 +942,22 @@ fn serialize_instruction_inner(
                 major_version
             ));
         }
+        Bytecode::PackVariant(_)
+        | Bytecode::PackVariantGeneric(_)
+        | Bytecode::UnpackVariant(_)
+        | Bytecode::UnpackVariantImmRef(_)
+        | Bytecode::UnpackVariantMutRef(_)
+        | Bytecode::UnpackVariantGeneric(_)
+        | Bytecode::UnpackVariantGenericImmRef(_)
+        | Bytecode::UnpackVariantGenericMutRef(_)
+        | Bytecode::VariantSwitch(_)
+            if (major_version < VERSION_7) =>
+        {
+            return Err(anyhow!(
+                "Enum variant operations not supported in bytecode version {}",
+                major_version
+            ));
+        }
         _ => (),
     };
 
@@ -970,6 +1170,42 @@ fn serialize_instruction_inner(
         Bytecode::CastU16 => binary.push(Opcodes::CAST_U16 as u8),
         Bytecode::CastU32 => binary.push(Opcodes::CAST_U32 as u8),
         Bytecode::CastU256 => binary.push(Opcodes::CAST_U256 as u8),
+        Bytecode::PackVariant(idx) => {
+            binary.push(Opcodes::PACK_VARIANT as u8)?;
+            serialize_variant_handle_index(binary, idx)
+        }
+        Bytecode::PackVariantGeneric(idx) => {
+            binary.push(Opcodes::PACK_VARIANT_GENERIC as u8)?;
+            serialize_variant_inst_handle_index(binary, idx)
+        }
+        Bytecode::UnpackVariant(idx) => {
+            binary.push(Opcodes::UNPACK_VARIANT as u8)?;
+            serialize_variant_handle_index(binary, idx)
+        }
+        Bytecode::UnpackVariantImmRef(idx) => {
+            binary.push(Opcodes::UNPACK_VARIANT_IMM_REF as u8)?;
+            serialize_variant_handle_index(binary, idx)
+        }
+        Bytecode::UnpackVariantMutRef(idx) => {
+            binary.push(Opcodes::UNPACK_VARIANT_MUT_REF as u8)?;
+            serialize_variant_handle_index(binary, idx)
+        }
+        Bytecode::UnpackVariantGeneric(idx) => {
+            binary.push(Opcodes::UNPACK_VARIANT_GENERIC as u8)?;
+            serialize_variant_inst_handle_index(binary, idx)
+        }
+        Bytecode::UnpackVariantGenericImmRef(idx) => {
+            binary.push(Opcodes::UNPACK_VARIANT_GENERIC_IMM_REF as u8)?;
+            serialize_variant_inst_handle_index(binary, idx)
+        }
+        Bytecode::UnpackVariantGenericMutRef(idx) => {
+            binary.push(Opcodes::UNPACK_VARIANT_GENERIC_MUT_REF as u8)?;
+            serialize_variant_inst_handle_index(binary, idx)
+        }
+        Bytecode::VariantSwitch(idx) => {
+            binary.push(Opcodes::VARIANT_SWITCH as u8)?;
+            serialize_variant_jump_table_index(binary, idx)
+        }
     };
     res?;
     Ok(())
@@ -1268,6 +1504,10 @@ impl ModuleSerializer {
             field_handles: (0, 0),
             field_instantiations: (0, 0),
             friend_decls: (0, 0),
+            enum_defs: (0, 0),
+            enum_def_instantiations: (0, 0),
+            variant_handles: (0, 0),
+            variant_instantiation_handles: (0, 0),
         }
     }
 
@@ -1278,7 +1518,22 @@ impl ModuleSerializer {
         self.serialize_function_definitions(binary, &module.function_defs)?;
         self.serialize_field_handles(binary, &module.field_handles)?;
         self.serialize_field_instantiations(binary, &module.field_instantiations)?;
-        self.serialize_friend_declarations(binary, &module.friend_decls)
+        self.serialize_friend_declarations(binary, &module.friend_decls)?;
+        if self.common.major_version >= VERSION_7 {
+            self.serialize_enum_definitions(binary, &module.enum_defs)?;
+            self.serialize_enum_def_instantiations(binary, &module.enum_def_instantiations)?;
+            self.serialize_variant_handles(binary, &module.variant_handles)?;
+            self.serialize_variant_instantiation_handles(
+                binary,
+                &module.variant_instantiation_handles,
+            )?;
+        } else if module_uses_enums(module) {
+            bail!(
+                "Enums not supported in bytecode version {}",
+                self.common.major_version
+            );
+        }
+        Ok(())
     }
 
     fn serialize_table_indices(&mut self, binary: &mut BinaryData) -> Result<()> {
@@ -1319,6 +1574,30 @@ impl ModuleSerializer {
             self.friend_decls.0,
             self.friend_decls.1,
         )?;
+        serialize_table_index(
+            binary,
+            TableType::ENUM_DEFS,
+            self.enum_defs.0,
+            self.enum_defs.1,
+        )?;
+        serialize_table_index(
+            binary,
+            TableType::ENUM_DEF_INST,
+            self.enum_def_instantiations.0,
+            self.enum_def_instantiations.1,
+        )?;
+        serialize_table_index(
+            binary,
+            TableType::VARIANT_HANDLES,
+            self.variant_handles.0,
+            self.variant_handles.1,
+        )?;
+        serialize_table_index(
+            binary,
+            TableType::VARIANT_INST_HANDLES,
+            self.variant_instantiation_handles.0,
+            self.variant_instantiation_handles.1,
+        )?;
         Ok(())
     }
 
@@ -1454,6 +1733,76 @@ impl ModuleSerializer {
         Ok(())
     }
 
+    /// Serializes `EnumDefinition` table.
+    fn serialize_enum_definitions(
+        &mut self,
+        binary: &mut BinaryData,
+        enum_definitions: &[EnumDefinition],
+    ) -> Result<()> {
+        if !enum_definitions.is_empty() {
+            self.common.table_count = self.common.table_count.wrapping_add(1); // the count will bound to a small number
+            self.enum_defs.0 = check_index_in_binary(binary.len())?;
+            for enum_definition in enum_definitions {
+                serialize_enum_definition(binary, enum_definition)?;
+            }
+            self.enum_defs.1 = checked_calculate_table_size(binary, self.enum_defs.0)?;
+        }
+        Ok(())
+    }
+
+    /// Serializes `EnumDefInstantiation` table.
+    fn serialize_enum_def_instantiations(
+        &mut self,
+        binary: &mut BinaryData,
+        enum_def_instantiations: &[EnumDefInstantiation],
+    ) -> Result<()> {
+        if !enum_def_instantiations.is_empty() {
+            self.common.table_count = self.common.table_count.wrapping_add(1); // the count will bound to a small number
+            self.enum_def_instantiations.0 = check_index_in_binary(binary.len())?;
+            for enum_instantiation in enum_def_instantiations {
+                serialize_enum_def_instantiation(binary, enum_instantiation)?;
+            }
+            self.enum_def_instantiations.1 =
+                checked_calculate_table_size(binary, self.enum_def_instantiations.0)?;
+        }
+        Ok(())
+    }
+
+    /// Serializes `VariantHandle` table.
+    fn serialize_variant_handles(
+        &mut self,
+        binary: &mut BinaryData,
+        variant_handles: &[VariantHandle],
+    ) -> Result<()> {
+        if !variant_handles.is_empty() {
+            self.common.table_count = self.common.table_count.wrapping_add(1); // the count will bound to a small number
+            self.variant_handles.0 = check_index_in_binary(binary.len())?;
+            for variant_handle in variant_handles {
+                serialize_variant_handle(binary, variant_handle)?;
+            }
+            self.variant_handles.1 = checked_calculate_table_size(binary, self.variant_handles.0)?;
+        }
+        Ok(())
+    }
+
+    /// Serializes `VariantInstantiationHandle` table.
+    fn serialize_variant_instantiation_handles(
+        &mut self,
+        binary: &mut BinaryData,
+        variant_instantiation_handles: &[VariantInstantiationHandle],
+    ) -> Result<()> {
+        if !variant_instantiation_handles.is_empty() {
+            self.common.table_count = self.common.table_count.wrapping_add(1); // the count will bound to a small number
+            self.variant_instantiation_handles.0 = check_index_in_binary(binary.len())?;
+            for variant_inst_handle in variant_instantiation_handles {
+                serialize_variant_instantiation_handle(binary, variant_inst_handle)?;
+            }
+            self.variant_instantiation_handles.1 =
+                checked_calculate_table_size(binary, self.variant_instantiation_handles.0)?;
+        }
+        Ok(())
+    }
+
     fn serialize_friend_declarations(
         &mut self,
         binary: &mut BinaryData,
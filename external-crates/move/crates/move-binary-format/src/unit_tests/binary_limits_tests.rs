@@ -83,6 +83,10 @@ fn binary_limits_test() {
         field_handles: 11,
         field_instantiations: 16,
         friend_decls: 12,
+        enum_defs: 7,
+        enum_def_instantiations: 6,
+        variant_handles: 9,
+        variant_instantiation_handles: 14,
     };
 
     // From file_format_common.rs::TableType test table type of interest (no
@@ -257,6 +261,7 @@ fn binary_limits_test() {
             code: Some(CodeUnit {
                 locals: SignatureIndex(0),
                 code: vec![Bytecode::Ret],
+                jump_tables: vec![],
             }),
         },
         &binary_config,
@@ -98,6 +98,7 @@ fn mk_module_entry(vis: u8, is_entry: bool) -> normalized::Module {
                         Bytecode::LdConst(ConstantPoolIndex(2)),
                         Bytecode::Ret,
                     ],
+                    jump_tables: vec![],
                 }),
             },
         ],
@@ -127,6 +128,10 @@ fn mk_module_entry(vis: u8, is_entry: bool) -> normalized::Module {
         struct_def_instantiations: vec![],
         function_instantiations: vec![],
         field_instantiations: vec![],
+        enum_defs: vec![],
+        enum_def_instantiations: vec![],
+        variant_handles: vec![],
+        variant_instantiation_handles: vec![],
     };
     normalized::Module::new(&m)
 }
@@ -186,6 +191,7 @@ fn mk_module_plus_code_perm(vis: u8, code: Vec<Bytecode>, p: Permutation) -> nor
                 code: Some(CodeUnit {
                     locals: SignatureIndex(p.permute(0)),
                     code,
+                    jump_tables: vec![],
                 }),
             },
             // public(script) fun fn() { return; }
@@ -202,6 +208,7 @@ fn mk_module_plus_code_perm(vis: u8, code: Vec<Bytecode>, p: Permutation) -> nor
                         Bytecode::LdConst(ConstantPoolIndex(p.permute(2))),
                         Bytecode::Ret,
                     ],
+                    jump_tables: vec![],
                 }),
             },
         ]),
@@ -231,6 +238,10 @@ fn mk_module_plus_code_perm(vis: u8, code: Vec<Bytecode>, p: Permutation) -> nor
         struct_def_instantiations: vec![],
         function_instantiations: vec![],
         field_instantiations: vec![],
+        enum_defs: vec![],
+        enum_def_instantiations: vec![],
+        variant_handles: vec![],
+        variant_instantiation_handles: vec![],
     };
     normalized::Module::new(&m)
 }
@@ -370,6 +381,7 @@ fn make_complex_module_perm(p: Permutation) -> normalized::Module {
                 code: Some(CodeUnit {
                     locals: SignatureIndex(p.permute(0)),
                     code: vec![Bytecode::Ret],
+                    jump_tables: vec![],
                 }),
             },
             // fun g_fn<T>() { return; }
@@ -381,6 +393,7 @@ fn make_complex_module_perm(p: Permutation) -> normalized::Module {
                 code: Some(CodeUnit {
                     locals: SignatureIndex(p.permute(0)),
                     code: vec![Bytecode::Ret],
+                    jump_tables: vec![],
                 }),
             },
             FunctionDefinition {
@@ -391,6 +404,7 @@ fn make_complex_module_perm(p: Permutation) -> normalized::Module {
                 code: Some(CodeUnit {
                     locals: SignatureIndex(p.permute(0)),
                     code: vec![],
+                    jump_tables: vec![],
                 }),
             },
         ]),
@@ -424,6 +438,10 @@ fn make_complex_module_perm(p: Permutation) -> normalized::Module {
         struct_def_instantiations: vec![],
         function_instantiations: vec![],
         field_instantiations: vec![],
+        enum_defs: vec![],
+        enum_def_instantiations: vec![],
+        variant_handles: vec![],
+        variant_instantiation_handles: vec![],
     };
     normalized::Module::new(&m)
 }
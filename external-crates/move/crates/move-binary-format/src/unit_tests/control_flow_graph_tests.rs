@@ -11,16 +11,19 @@ use crate::{
 fn traversal_no_loops() {
     let cfg = {
         use Bytecode::*;
-        VMControlFlowGraph::new(&[
-            // L0
-            LdTrue,
-            //
-            BrTrue(3),
-            // L2
-            Branch(3),
-            // L3
-            Ret,
-        ])
+        VMControlFlowGraph::new(
+            &[
+                // L0
+                LdTrue,
+                //
+                BrTrue(3),
+                // L2
+                Branch(3),
+                // L3
+                Ret,
+            ],
+            &[],
+        )
     };
 
     cfg.display();
@@ -32,22 +35,25 @@ fn traversal_no_loops() {
 fn traversal_loops() {
     let cfg = {
         use Bytecode::*;
-        VMControlFlowGraph::new(&[
-            // L0: Outer head
-            LdTrue,
-            // Outer break
-            BrTrue(6),
-            // L2: Inner head
-            LdTrue,
-            // Inner break
-            BrTrue(5),
-            // L4: Inner continue
-            Branch(2),
-            // Outer continue
-            Branch(0),
-            // L6:
-            Ret,
-        ])
+        VMControlFlowGraph::new(
+            &[
+                // L0: Outer head
+                LdTrue,
+                // Outer break
+                BrTrue(6),
+                // L2: Inner head
+                LdTrue,
+                // Inner break
+                BrTrue(5),
+                // L4: Inner continue
+                Branch(2),
+                // Outer continue
+                Branch(0),
+                // L6:
+                Ret,
+            ],
+            &[],
+        )
     };
 
     cfg.display();
@@ -59,14 +65,17 @@ fn traversal_loops() {
 fn traversal_non_loop_back_branch() {
     let cfg = {
         use Bytecode::*;
-        VMControlFlowGraph::new(&[
-            // L0
-            Branch(2),
-            // L1
-            Ret,
-            // L2
-            Branch(1),
-        ])
+        VMControlFlowGraph::new(
+            &[
+                // L0
+                Branch(2),
+                // L1
+                Ret,
+                // L2
+                Branch(1),
+            ],
+            &[],
+        )
     };
 
     cfg.display();
@@ -7,7 +7,7 @@ use move_core_types::{metadata::Metadata, vm_status::StatusCode};
 
 use crate::{
     binary_config::BinaryConfig,
-    file_format::{basic_test_module, CompiledModule, CompiledScript},
+    file_format::{basic_enum_test_module, basic_test_module, CompiledModule, CompiledScript},
     file_format_common::*,
 };
 
@@ -314,6 +314,34 @@ fn deserialize_trailing_bytes() {
     test(&test3);
 }
 
+#[test]
+fn enum_module_round_trip() {
+    let module = basic_enum_test_module();
+    let mut bytes = vec![];
+    module.serialize(&mut bytes).unwrap();
+    let deserialized = CompiledModule::deserialize_with_defaults(&bytes).unwrap();
+    assert_eq!(deserialized.version, VERSION_7);
+    assert_eq!(deserialized, module);
+
+    // enums cannot be written nor read at an earlier version
+    assert!(
+        module
+            .serialize_for_version(Some(VERSION_6), &mut vec![])
+            .is_err()
+    );
+    let status_code =
+        CompiledModule::deserialize_with_config(&bytes, &BinaryConfig::legacy(VERSION_6, true))
+            .unwrap_err()
+            .major_status();
+    assert_eq!(status_code, StatusCode::UNKNOWN_VERSION);
+
+    // modules without enums keep being written at the default version
+    let mut bytes = vec![];
+    basic_test_module().serialize(&mut bytes).unwrap();
+    let deserialized = CompiledModule::deserialize_with_defaults(&bytes).unwrap();
+    assert_eq!(deserialized.version, VERSION_DEFAULT);
+}
+
 #[test]
 fn no_metadata() {
     let test = |bytes| {
@@ -8,8 +8,11 @@ use std::{borrow::Borrow, collections::BTreeMap, fmt::Write};
 use anyhow::{anyhow, bail, Result};
 use move_binary_format::{
     access::ModuleAccess,
-    file_format::{SignatureToken, StructDefinition, StructFieldInformation, StructHandleIndex},
-    normalized::{Struct, Type},
+    file_format::{
+        EnumDefinition, SignatureToken, StructDefinition, StructFieldInformation,
+        StructHandleIndex, StructTypeParameter,
+    },
+    normalized::{Enum, Struct, Type},
     CompiledModule,
 };
 use move_core_types::{
@@ -19,7 +22,7 @@ use move_core_types::{
     language_storage::{ModuleId, StructTag, TypeTag},
 };
 use serde::{Deserialize, Serialize};
-use serde_reflection::{ContainerFormat, Format, Named, Registry};
+use serde_reflection::{ContainerFormat, Format, Named, Registry, VariantFormat};
 
 use crate::module_cache::GetModule;
 
@@ -227,26 +230,27 @@ impl<'a, T: GetModule> SerdeLayoutBuilder<'a, T> {
             .get_module_by_id(module_id)
             .map_err(|e| anyhow::format_err!("{:?}", e))?
             .expect("Failed to resolve module");
-        let def = declaring_module
-            .borrow()
-            .find_struct_def_by_name(name)
-            .unwrap_or_else(|| {
-                panic!(
-                    "Could not find struct named {} in module {}",
-                    name,
-                    declaring_module.borrow().name()
-                )
-            });
-        let normalized_struct = Struct::new(declaring_module.borrow(), def).1;
+        let m = declaring_module.borrow();
+        let datatype = if let Some(def) = m.find_struct_def_by_name(name) {
+            NormalizedDatatype::Struct(Struct::new(m, def).1)
+        } else if let Some(def) = m.find_enum_def_by_name(name) {
+            NormalizedDatatype::Enum(Enum::new(m, def).1)
+        } else {
+            panic!(
+                "Could not find struct or enum named {} in module {}",
+                name,
+                m.name()
+            )
+        };
         assert_eq!(
-            normalized_struct.type_parameters.len(),
+            datatype.type_parameters().len(),
             type_arguments.len(),
             "Wrong number of type arguments for struct"
         );
 
         let generics: Vec<String> = type_arguments
             .iter()
-            .zip(normalized_struct.type_parameters.iter())
+            .zip(datatype.type_parameters().iter())
             .filter(|(_, type_param)| {
                 // do not include phantom type arguments in the struct key, since they do not
                 // affect the struct layout
@@ -291,7 +295,7 @@ impl<'a, T: GetModule> SerdeLayoutBuilder<'a, T> {
                 // check for conflicts (e.g., 0x1::M::T and 0x2::M::T that both get stripped to
                 // M::T because omit_addresses is on)
                 if old_struct.clone()
-                    != self.generate_serde_struct(normalized_struct, type_arguments, depth)?
+                    != self.generate_serde_container(datatype, type_arguments, depth)?
                 {
                     panic!(
                         "Name conflict: multiple structs with name {}, but different addresses",
@@ -301,23 +305,58 @@ impl<'a, T: GetModule> SerdeLayoutBuilder<'a, T> {
             }
         } else {
             // not found--generate and update registry
-            let serde_struct =
-                self.generate_serde_struct(normalized_struct, type_arguments, depth)?;
-            self.registry.0.insert(struct_key.clone(), serde_struct);
+            let serde_container = self.generate_serde_container(datatype, type_arguments, depth)?;
+            self.registry.0.insert(struct_key.clone(), serde_container);
         }
 
         Ok(Format::TypeName(struct_key))
     }
 
-    fn generate_serde_struct(
+    fn generate_serde_container(
         &mut self,
-        normalized_struct: Struct,
+        datatype: NormalizedDatatype,
         type_arguments: &[Format],
         depth: u64,
     ) -> Result<ContainerFormat> {
         check_depth!(depth);
-        let fields = normalized_struct
-            .fields
+        match datatype {
+            NormalizedDatatype::Struct(normalized_struct) => {
+                let fields =
+                    self.generate_serde_fields(&normalized_struct.fields, type_arguments, depth)?;
+                Ok(ContainerFormat::Struct(fields))
+            }
+            NormalizedDatatype::Enum(normalized_enum) => {
+                let mut variants = BTreeMap::new();
+                for (tag, variant) in normalized_enum.variants.iter().enumerate() {
+                    let value = if variant.fields.is_empty() {
+                        VariantFormat::Unit
+                    } else {
+                        VariantFormat::Struct(self.generate_serde_fields(
+                            &variant.fields,
+                            type_arguments,
+                            depth,
+                        )?)
+                    };
+                    variants.insert(
+                        tag as u32,
+                        Named {
+                            name: variant.name.to_string(),
+                            value,
+                        },
+                    );
+                }
+                Ok(ContainerFormat::Enum(variants))
+            }
+        }
+    }
+
+    fn generate_serde_fields(
+        &mut self,
+        fields: &[move_binary_format::normalized::Field],
+        type_arguments: &[Format],
+        depth: u64,
+    ) -> Result<Vec<Named<Format>>> {
+        fields
             .iter()
             .map(|f| {
                 self.build_normalized_type_layout(&f.type_, type_arguments, depth)
@@ -326,8 +365,22 @@ impl<'a, T: GetModule> SerdeLayoutBuilder<'a, T> {
                         value,
                     })
             })
-            .collect::<Result<Vec<Named<Format>>>>()?;
-        Ok(ContainerFormat::Struct(fields))
+            .collect()
+    }
+}
+
+/// A struct or enum declaration, as found by the serde layout builder.
+enum NormalizedDatatype {
+    Struct(Struct),
+    Enum(Enum),
+}
+
+impl NormalizedDatatype {
+    fn type_parameters(&self) -> &[StructTypeParameter] {
+        match self {
+            NormalizedDatatype::Struct(s) => &s.type_parameters,
+            NormalizedDatatype::Enum(e) => &e.type_parameters,
+        }
     }
 }
 
@@ -374,9 +427,7 @@ impl TypeLayoutBuilder {
             Vector(elem_t) => {
                 A::MoveTypeLayout::Vector(Box::new(Self::build(elem_t, resolver, depth + 1)?))
             }
-            Struct(s) => {
-                A::MoveTypeLayout::Struct(StructLayoutBuilder::build(s, resolver, depth + 1)?)
-            }
+            Struct(s) => StructLayoutBuilder::build(s, resolver, depth + 1)?,
         })
     }
 
@@ -397,13 +448,9 @@ impl TypeLayoutBuilder {
                 resolver,
                 depth + 1,
             )?)),
-            Struct(shi) => A::MoveTypeLayout::Struct(StructLayoutBuilder::build_from_handle_idx(
-                m,
-                *shi,
-                vec![],
-                resolver,
-                depth + 1,
-            )?),
+            Struct(shi) => {
+                StructLayoutBuilder::build_from_handle_idx(m, *shi, vec![], resolver, depth + 1)?
+            }
             StructInstantiation(struct_inst) => {
                 let (shi, type_actuals) = &**struct_inst;
                 let actual_layouts = type_actuals
@@ -412,13 +459,13 @@ impl TypeLayoutBuilder {
                         Self::build_from_signature_token(m, t, type_arguments, resolver, depth + 1)
                     })
                     .collect::<Result<Vec<_>>>()?;
-                A::MoveTypeLayout::Struct(StructLayoutBuilder::build_from_handle_idx(
+                StructLayoutBuilder::build_from_handle_idx(
                     m,
                     *shi,
                     actual_layouts,
                     resolver,
                     depth + 1,
-                )?)
+                )?
             }
             TypeParameter(i) => type_arguments[*i as usize].clone(),
             Bool => A::MoveTypeLayout::Bool,
@@ -435,11 +482,13 @@ impl TypeLayoutBuilder {
     }
 }
 
+/// Builds the layouts of datatypes: structs, and enums (which share struct tags
+/// and handles with structs).
 impl StructLayoutBuilder {
     /// Construct an expanded `TypeLayout` from `s`.
     /// Panics if `resolver` cannot resolved a module whose types are referenced
     /// directly or transitively by `s`.
-    fn build(s: &StructTag, resolver: &impl GetModule, depth: u64) -> Result<A::MoveStructLayout> {
+    fn build(s: &StructTag, resolver: &impl GetModule, depth: u64) -> Result<A::MoveTypeLayout> {
         check_depth!(depth);
         let type_arguments = s
             .type_params
@@ -498,29 +547,93 @@ impl StructLayoutBuilder {
         }
     }
 
+    fn build_from_enum_definition(
+        m: &CompiledModule,
+        e: &EnumDefinition,
+        type_arguments: Vec<A::MoveTypeLayout>,
+        resolver: &impl GetModule,
+        depth: u64,
+    ) -> Result<A::MoveEnumLayout> {
+        check_depth!(depth);
+        let e_handle = m.struct_handle_at(e.enum_handle);
+        if e_handle.type_parameters.len() != type_arguments.len() {
+            bail!("Wrong number of type arguments for enum")
+        }
+        let variants = e
+            .variants
+            .iter()
+            .map(|v| {
+                let fields = v
+                    .fields
+                    .iter()
+                    .map(|f| {
+                        let layout = TypeLayoutBuilder::build_from_signature_token(
+                            m,
+                            &f.signature.0,
+                            &type_arguments,
+                            resolver,
+                            depth,
+                        )?;
+                        Ok(A::MoveFieldLayout::new(
+                            m.identifier_at(f.name).to_owned(),
+                            layout,
+                        ))
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                Ok(A::MoveVariantLayout::new(
+                    m.identifier_at(v.variant_name).to_owned(),
+                    fields,
+                ))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let mid = m.self_id();
+        let type_params: Vec<TypeTag> = type_arguments.iter().map(|t| t.into()).collect();
+        let type_ = StructTag {
+            address: *mid.address(),
+            module: mid.name().to_owned(),
+            name: m.identifier_at(e_handle.name).to_owned(),
+            type_params,
+        };
+        Ok(A::MoveEnumLayout { type_, variants })
+    }
+
     fn build_from_name(
         declaring_module: &ModuleId,
         name: &IdentStr,
         type_arguments: Vec<A::MoveTypeLayout>,
         resolver: &impl GetModule,
         depth: u64,
-    ) -> Result<A::MoveStructLayout> {
+    ) -> Result<A::MoveTypeLayout> {
         check_depth!(depth);
         let module = match resolver.get_module_by_id(declaring_module) {
             Err(_) | Ok(None) => bail!("Could not find module"),
             Ok(Some(m)) => m,
         };
-        let def = module
-            .borrow()
-            .find_struct_def_by_name(name)
-            .ok_or_else(|| {
-                anyhow!(
-                    "Could not find struct named {} in module {}",
-                    name,
-                    declaring_module
-                )
-            })?;
-        Self::build_from_definition(module.borrow(), def, type_arguments, resolver, depth)
+        let m = module.borrow();
+        if let Some(def) = m.find_struct_def_by_name(name) {
+            return Ok(A::MoveTypeLayout::Struct(Self::build_from_definition(
+                m,
+                def,
+                type_arguments,
+                resolver,
+                depth,
+            )?));
+        }
+        let def = m.find_enum_def_by_name(name).ok_or_else(|| {
+            anyhow!(
+                "Could not find struct named {} in module {}",
+                name,
+                declaring_module
+            )
+        })?;
+        Ok(A::MoveTypeLayout::Enum(Self::build_from_enum_definition(
+            m,
+            def,
+            type_arguments,
+            resolver,
+            depth,
+        )?))
     }
 
     fn build_from_handle_idx(
@@ -529,11 +642,26 @@ impl StructLayoutBuilder {
         type_arguments: Vec<A::MoveTypeLayout>,
         resolver: &impl GetModule,
         depth: u64,
-    ) -> Result<A::MoveStructLayout> {
+    ) -> Result<A::MoveTypeLayout> {
         check_depth!(depth);
         if let Some(def) = m.find_struct_def(s) {
             // declared internally
-            Self::build_from_definition(m, def, type_arguments, resolver, depth)
+            Ok(A::MoveTypeLayout::Struct(Self::build_from_definition(
+                m,
+                def,
+                type_arguments,
+                resolver,
+                depth,
+            )?))
+        } else if let Some(def) = m.find_enum_def(s) {
+            // declared internally
+            Ok(A::MoveTypeLayout::Enum(Self::build_from_enum_definition(
+                m,
+                def,
+                type_arguments,
+                resolver,
+                depth,
+            )?))
         } else {
             let handle = m.struct_handle_at(s);
             let name = m.identifier_at(handle.name);
@@ -4,7 +4,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 //! This module implements a checker for verifying that all of the struct's
-//! fields satisfy the abilities required by the struct's abilities
+//! fields, and all of the fields of every enum variant, satisfy the abilities
+//! required by the type's abilities
 use move_binary_format::{
     access::ModuleAccess,
     binary_views::BinaryIndexedView,
@@ -49,5 +50,28 @@ fn verify_module_impl(module: &CompiledModule) -> PartialVMResult<()> {
             }
         }
     }
+    for (idx, enum_def) in module.enum_defs().iter().enumerate() {
+        let eh = module.struct_handle_at(enum_def.enum_handle);
+        let required_abilities = eh
+            .abilities
+            .into_iter()
+            .map(|a| a.requires())
+            .fold(AbilitySet::EMPTY, |acc, required| acc | required);
+        let type_parameter_abilities = eh
+            .type_parameters
+            .iter()
+            .map(|_| AbilitySet::ALL)
+            .collect::<Vec<_>>();
+        for field in enum_def.variants.iter().flat_map(|v| v.fields.iter()) {
+            let field_abilities = view.abilities(&field.signature.0, &type_parameter_abilities)?;
+            if !required_abilities.is_subset(field_abilities) {
+                return Err(verification_error(
+                    StatusCode::FIELD_MISSING_TYPE_ABILITY,
+                    IndexKind::EnumDefinition,
+                    idx as TableIndex,
+                ));
+            }
+        }
+    }
     Ok(())
 }
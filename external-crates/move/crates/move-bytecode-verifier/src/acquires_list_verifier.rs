@@ -135,6 +135,15 @@ impl<'a> AcquiresVerifier<'a> {
             | Bytecode::PackGeneric(_)
             | Bytecode::Unpack(_)
             | Bytecode::UnpackGeneric(_)
+            | Bytecode::PackVariant(_)
+            | Bytecode::PackVariantGeneric(_)
+            | Bytecode::UnpackVariant(_)
+            | Bytecode::UnpackVariantImmRef(_)
+            | Bytecode::UnpackVariantMutRef(_)
+            | Bytecode::UnpackVariantGeneric(_)
+            | Bytecode::UnpackVariantGenericImmRef(_)
+            | Bytecode::UnpackVariantGenericMutRef(_)
+            | Bytecode::VariantSwitch(_)
             | Bytecode::ReadRef
             | Bytecode::WriteRef
             | Bytecode::CastU8
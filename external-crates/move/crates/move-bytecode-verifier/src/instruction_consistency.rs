@@ -6,15 +6,17 @@
 //! This module defines the transfer functions for verifying consistency of each
 //! bytecode instruction, in particular, for the bytecode instructions that come
 //! in both generic and non-generic flavors. It also checks constraints on
-//! instructions like VecPack/VecUnpack.
+//! instructions like VecPack/VecUnpack, and that every variant jump table is
+//! used by a switch and covers all variants of its enum.
 
 use move_binary_format::{
     access::ModuleAccess,
     binary_views::BinaryIndexedView,
     errors::{Location, PartialVMError, PartialVMResult, VMResult},
     file_format::{
-        Bytecode, CodeOffset, CodeUnit, CompiledModule, CompiledScript, FieldHandleIndex,
-        FunctionDefinitionIndex, FunctionHandleIndex, StructDefinitionIndex, TableIndex,
+        Bytecode, CodeOffset, CodeUnit, CompiledModule, CompiledScript, EnumDefinitionIndex,
+        FieldHandleIndex, FunctionDefinitionIndex, FunctionHandleIndex, JumpTableInner,
+        StructDefinitionIndex, TableIndex, VariantJumpTableIndex,
     },
 };
 use move_core_types::vm_status::StatusCode;
@@ -60,6 +62,7 @@ impl<'a> InstructionConsistency<'a> {
     }
 
     fn check_instructions(&self, code: &CodeUnit) -> PartialVMResult<()> {
+        let mut used_jump_tables = vec![false; code.jump_tables.len()];
         for (offset, instr) in code.code.iter().enumerate() {
             use Bytecode::*;
 
@@ -134,6 +137,25 @@ impl<'a> InstructionConsistency<'a> {
                     let struct_inst = self.resolver.struct_instantiation_at(*idx)?;
                     self.check_type_op(offset, struct_inst.def, /* generic */ true)?;
                 }
+                PackVariant(idx)
+                | UnpackVariant(idx)
+                | UnpackVariantImmRef(idx)
+                | UnpackVariantMutRef(idx) => {
+                    let handle = self.resolver.variant_handle_at(*idx)?;
+                    self.check_enum_op(offset, handle.enum_def, /* generic */ false)?;
+                }
+                PackVariantGeneric(idx)
+                | UnpackVariantGeneric(idx)
+                | UnpackVariantGenericImmRef(idx)
+                | UnpackVariantGenericMutRef(idx) => {
+                    let handle = self.resolver.variant_instantiation_handle_at(*idx)?;
+                    let enum_inst = self.resolver.enum_instantiation_at(handle.enum_def)?;
+                    self.check_enum_op(offset, enum_inst.def, /* generic */ true)?;
+                }
+                VariantSwitch(idx) => {
+                    self.check_jump_table(offset, code, *idx)?;
+                    used_jump_tables[idx.0 as usize] = true;
+                }
                 VecPack(_, num) | VecUnpack(_, num) => {
                     if *num > u16::MAX as u64 {
                         return Err(PartialVMError::new(StatusCode::CONSTRAINT_NOT_SATISFIED)
@@ -153,6 +175,10 @@ impl<'a> InstructionConsistency<'a> {
                 | VecMutBorrow(_) | VecPushBack(_) | VecPopBack(_) | VecSwap(_) | Abort | Nop => (),
             }
         }
+        if used_jump_tables.iter().any(|used| !used) {
+            return Err(PartialVMError::new(StatusCode::UNUSED_JUMP_TABLE)
+                .at_code_offset(self.current_function(), 0));
+        }
         Ok(())
     }
 
@@ -192,6 +218,45 @@ impl<'a> InstructionConsistency<'a> {
         Ok(())
     }
 
+    fn check_enum_op(
+        &self,
+        offset: usize,
+        enum_def_index: EnumDefinitionIndex,
+        generic: bool,
+    ) -> PartialVMResult<()> {
+        let enum_def = self.resolver.enum_def_at(enum_def_index)?;
+        let enum_handle = self.resolver.struct_handle_at(enum_def.enum_handle);
+        if enum_handle.type_parameters.is_empty() == generic {
+            return Err(
+                PartialVMError::new(StatusCode::GENERIC_MEMBER_OPCODE_MISMATCH)
+                    .at_code_offset(self.current_function(), offset as CodeOffset),
+            );
+        }
+        Ok(())
+    }
+
+    // A jump table must have exactly one target per variant of its enum.
+    fn check_jump_table(
+        &self,
+        offset: usize,
+        code: &CodeUnit,
+        jump_table_index: VariantJumpTableIndex,
+    ) -> PartialVMResult<()> {
+        let jump_table = &code.jump_tables[jump_table_index.0 as usize];
+        let enum_def = self.resolver.enum_def_at(jump_table.head_enum)?;
+        let JumpTableInner::Full(offsets) = &jump_table.jump_table;
+        if offsets.len() != enum_def.variants.len() {
+            return Err(PartialVMError::new(StatusCode::INVALID_ENUM_SWITCH)
+                .at_code_offset(self.current_function(), offset as CodeOffset)
+                .with_message(format!(
+                    "jump table has {} targets but the enum has {} variants",
+                    offsets.len(),
+                    enum_def.variants.len()
+                )));
+        }
+        Ok(())
+    }
+
     fn check_function_op(
         &self,
         offset: usize,
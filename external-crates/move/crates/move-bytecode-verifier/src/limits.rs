@@ -6,7 +6,8 @@ use move_binary_format::{
     binary_views::BinaryIndexedView,
     errors::{verification_error, Location, PartialVMError, PartialVMResult, VMResult},
     file_format::{
-        CompiledModule, CompiledScript, SignatureToken, StructFieldInformation, TableIndex,
+        Bytecode, CompiledModule, CompiledScript, SignatureToken, StructFieldInformation,
+        TableIndex,
     },
     IndexKind,
 };
@@ -30,6 +31,7 @@ impl<'a> LimitsVerifier<'a> {
         let limit_check = Self {
             resolver: BinaryIndexedView::Module(module),
         };
+        limit_check.verify_enums(config, module)?;
         limit_check.verify_constants(config)?;
         limit_check.verify_function_handles(config)?;
         limit_check.verify_struct_handles(config)?;
@@ -54,6 +56,49 @@ impl<'a> LimitsVerifier<'a> {
         limit_check.verify_type_nodes(config)
     }
 
+    /// Rejects enum declarations and variant instructions unless enums are
+    /// enabled.
+    fn verify_enums(
+        &self,
+        config: &VerifierConfig,
+        module: &CompiledModule,
+    ) -> PartialVMResult<()> {
+        if config.enable_enums {
+            return Ok(());
+        }
+        let uses_variants = module
+            .function_defs
+            .iter()
+            .filter_map(|def| def.code.as_ref())
+            .any(|code| {
+                !code.jump_tables.is_empty()
+                    || code.code.iter().any(|instr| {
+                        matches!(
+                            instr,
+                            Bytecode::PackVariant(_)
+                                | Bytecode::PackVariantGeneric(_)
+                                | Bytecode::UnpackVariant(_)
+                                | Bytecode::UnpackVariantImmRef(_)
+                                | Bytecode::UnpackVariantMutRef(_)
+                                | Bytecode::UnpackVariantGeneric(_)
+                                | Bytecode::UnpackVariantGenericImmRef(_)
+                                | Bytecode::UnpackVariantGenericMutRef(_)
+                                | Bytecode::VariantSwitch(_)
+                        )
+                    })
+            });
+        if !module.enum_defs.is_empty()
+            || !module.enum_def_instantiations.is_empty()
+            || !module.variant_handles.is_empty()
+            || !module.variant_instantiation_handles.is_empty()
+            || uses_variants
+        {
+            return Err(PartialVMError::new(StatusCode::FEATURE_UNDER_GATING)
+                .with_message("enums are not supported".to_string()));
+        }
+        Ok(())
+    }
+
     fn verify_struct_handles(&self, config: &VerifierConfig) -> PartialVMResult<()> {
         if let Some(limit) = config.max_generic_instantiation_length {
             for (idx, struct_handle) in self.resolver.struct_handles().iter().enumerate() {
@@ -108,6 +153,15 @@ impl<'a> LimitsVerifier<'a> {
                 }
             }
         }
+        if let Some(edefs) = self.resolver.enum_defs() {
+            for edef in edefs {
+                for vdef in &edef.variants {
+                    for fdef in &vdef.fields {
+                        self.verify_type_node(config, &fdef.signature.0)?
+                    }
+                }
+            }
+        }
         Ok(())
     }
 
@@ -173,6 +227,29 @@ impl<'a> LimitsVerifier<'a> {
                 }
             }
         }
+        // enums count against the same limits as structs, and each variant
+        // against the field limit
+        if let Some(defs) = self.resolver.enum_defs() {
+            if let Some(max_struct_definitions) = config.max_struct_definitions {
+                let struct_count = self.resolver.struct_defs().map_or(0, |d| d.len());
+                if defs.len() + struct_count > max_struct_definitions {
+                    return Err(PartialVMError::new(
+                        StatusCode::MAX_STRUCT_DEFINITIONS_REACHED,
+                    ));
+                }
+            }
+            if let Some(max_fields_in_struct) = config.max_fields_in_struct {
+                for def in defs {
+                    for variant in &def.variants {
+                        if variant.fields.len() > max_fields_in_struct {
+                            return Err(PartialVMError::new(
+                                StatusCode::MAX_FIELD_DEFINITIONS_REACHED,
+                            ));
+                        }
+                    }
+                }
+            }
+        }
         Ok(())
     }
 
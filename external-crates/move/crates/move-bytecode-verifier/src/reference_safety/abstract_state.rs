@@ -460,6 +460,41 @@ impl AbstractState {
         Ok(AbstractValue::Reference(field_borrow_id))
     }
 
+    /// Unpacks the variant behind reference `id`, producing a reference to each
+    /// of its `field_count` fields. The variant fields are borrowed as a whole
+    /// from the enum, since they are not addressable through field handles.
+    pub fn unpack_enum_variant_ref(
+        &mut self,
+        offset: CodeOffset,
+        mut_: bool,
+        id: RefID,
+        field_count: usize,
+    ) -> PartialVMResult<Vec<AbstractValue>> {
+        if (mut_ && !self.is_writable(id)) || (!mut_ && !self.is_readable(id, None)) {
+            return Err(self.error(StatusCode::BORROWFIELD_EXISTS_MUTABLE_BORROW_ERROR, offset));
+        }
+
+        let field_refs = (0..field_count)
+            .map(|_| {
+                let field_borrow_id = self.new_ref(mut_);
+                self.add_borrow(id, field_borrow_id);
+                AbstractValue::Reference(field_borrow_id)
+            })
+            .collect();
+        self.release(id);
+        Ok(field_refs)
+    }
+
+    /// Reads the tag of the enum behind reference `id` for a `VariantSwitch`.
+    pub fn variant_switch(&mut self, offset: CodeOffset, id: RefID) -> PartialVMResult<()> {
+        if !self.is_readable(id, None) {
+            return Err(self.error(StatusCode::READREF_EXISTS_MUTABLE_BORROW_ERROR, offset));
+        }
+
+        self.release(id);
+        Ok(())
+    }
+
     pub fn borrow_global(
         &mut self,
         offset: CodeOffset,
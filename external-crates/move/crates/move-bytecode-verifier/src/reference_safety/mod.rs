@@ -22,8 +22,9 @@ use move_binary_format::{
     binary_views::{BinaryIndexedView, FunctionView},
     errors::{PartialVMError, PartialVMResult},
     file_format::{
-        Bytecode, CodeOffset, FunctionDefinitionIndex, FunctionHandle, IdentifierIndex,
-        SignatureIndex, SignatureToken, StructDefinition, StructFieldInformation,
+        Bytecode, CodeOffset, EnumDefinitionIndex, FunctionDefinitionIndex, FunctionHandle,
+        IdentifierIndex, SignatureIndex, SignatureToken, StructDefinition, StructFieldInformation,
+        VariantTag,
     },
     safe_assert, safe_unwrap, safe_unwrap_err,
 };
@@ -145,6 +146,45 @@ fn unpack(
     Ok(())
 }
 
+fn num_variant_fields(
+    verifier: &ReferenceSafetyAnalysis,
+    enum_def_idx: EnumDefinitionIndex,
+    tag: VariantTag,
+) -> PartialVMResult<usize> {
+    let enum_def = verifier.resolver.enum_def_at(enum_def_idx)?;
+    Ok(safe_unwrap!(enum_def.variant(tag)).fields.len())
+}
+
+fn pack_variant(verifier: &mut ReferenceSafetyAnalysis, field_count: usize) -> PartialVMResult<()> {
+    for _ in 0..field_count {
+        safe_assert!(safe_unwrap_err!(verifier.stack.pop()).is_value())
+    }
+    verifier.push(AbstractValue::NonReference)?;
+    Ok(())
+}
+
+fn unpack_variant(
+    verifier: &mut ReferenceSafetyAnalysis,
+    state: &mut AbstractState,
+    offset: CodeOffset,
+    field_count: usize,
+    by_ref: Option<bool>,
+) -> PartialVMResult<()> {
+    match by_ref {
+        None => {
+            safe_assert!(safe_unwrap_err!(verifier.stack.pop()).is_value());
+            verifier.push_n(AbstractValue::NonReference, field_count as u64)?;
+        }
+        Some(mut_) => {
+            let id = safe_unwrap!(safe_unwrap_err!(verifier.stack.pop()).ref_id());
+            for field_ref in state.unpack_enum_variant_ref(offset, mut_, id, field_count)? {
+                verifier.push(field_ref)?
+            }
+        }
+    }
+    Ok(())
+}
+
 fn vec_element_type(
     verifier: &mut ReferenceSafetyAnalysis,
     idx: SignatureIndex,
@@ -376,6 +416,47 @@ fn execute_inner(
             unpack(verifier, struct_def)?
         }
 
+        Bytecode::PackVariant(idx) => {
+            let handle = verifier.resolver.variant_handle_at(*idx)?;
+            let field_count = num_variant_fields(verifier, handle.enum_def, handle.variant)?;
+            pack_variant(verifier, field_count)?
+        }
+        Bytecode::PackVariantGeneric(idx) => {
+            let handle = verifier.resolver.variant_instantiation_handle_at(*idx)?;
+            let enum_inst = verifier.resolver.enum_instantiation_at(handle.enum_def)?;
+            let field_count = num_variant_fields(verifier, enum_inst.def, handle.variant)?;
+            pack_variant(verifier, field_count)?
+        }
+        Bytecode::UnpackVariant(idx)
+        | Bytecode::UnpackVariantImmRef(idx)
+        | Bytecode::UnpackVariantMutRef(idx) => {
+            let by_ref = match bytecode {
+                Bytecode::UnpackVariantImmRef(_) => Some(false),
+                Bytecode::UnpackVariantMutRef(_) => Some(true),
+                _ => None,
+            };
+            let handle = verifier.resolver.variant_handle_at(*idx)?;
+            let field_count = num_variant_fields(verifier, handle.enum_def, handle.variant)?;
+            unpack_variant(verifier, state, offset, field_count, by_ref)?
+        }
+        Bytecode::UnpackVariantGeneric(idx)
+        | Bytecode::UnpackVariantGenericImmRef(idx)
+        | Bytecode::UnpackVariantGenericMutRef(idx) => {
+            let by_ref = match bytecode {
+                Bytecode::UnpackVariantGenericImmRef(_) => Some(false),
+                Bytecode::UnpackVariantGenericMutRef(_) => Some(true),
+                _ => None,
+            };
+            let handle = verifier.resolver.variant_instantiation_handle_at(*idx)?;
+            let enum_inst = verifier.resolver.enum_instantiation_at(handle.enum_def)?;
+            let field_count = num_variant_fields(verifier, enum_inst.def, handle.variant)?;
+            unpack_variant(verifier, state, offset, field_count, by_ref)?
+        }
+        Bytecode::VariantSwitch(_) => {
+            let id = safe_unwrap!(safe_unwrap_err!(verifier.stack.pop()).ref_id());
+            state.variant_switch(offset, id)?
+        }
+
         Bytecode::VecPack(idx, num) => {
             if let Some(num_to_pop) = NonZeroU64::new(*num) {
                 let result = verifier.stack.pop_eq_n(num_to_pop);
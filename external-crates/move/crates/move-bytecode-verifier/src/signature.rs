@@ -4,7 +4,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 //! This module implements a checker for verifying signature tokens used in
-//! types of function parameters, locals, and fields of structs are well-formed.
+//! types of function parameters, locals, and fields of structs and enum
+//! variants are well-formed.
 //! References can only occur at the top-level in all tokens.  Additionally,
 //! references cannot occur at all in field types.
 use std::collections::{HashMap, HashSet};
@@ -14,9 +15,9 @@ use move_binary_format::{
     binary_views::BinaryIndexedView,
     errors::{Location, PartialVMError, PartialVMResult, VMResult},
     file_format::{
-        AbilitySet, Bytecode, CodeUnit, CompiledModule, CompiledScript, FunctionDefinition,
-        FunctionHandle, Signature, SignatureIndex, SignatureToken, StructDefinition,
-        StructFieldInformation, StructTypeParameter, TableIndex,
+        AbilitySet, Bytecode, CodeUnit, CompiledModule, CompiledScript, EnumDefinition,
+        FunctionDefinition, FunctionHandle, Signature, SignatureIndex, SignatureToken,
+        StructDefinition, StructFieldInformation, StructTypeParameter, TableIndex,
     },
     file_format_common::VERSION_6,
     IndexKind,
@@ -41,6 +42,7 @@ impl<'a> SignatureChecker<'a> {
         sig_check.verify_signature_pool(module.signatures())?;
         sig_check.verify_function_signatures(module.function_handles())?;
         sig_check.verify_fields(module.struct_defs())?;
+        sig_check.verify_variant_fields(module.enum_defs())?;
         sig_check.verify_code_units(module.function_handles(), module.function_defs())
     }
 
@@ -115,6 +117,33 @@ impl<'a> SignatureChecker<'a> {
         Ok(())
     }
 
+    fn verify_variant_fields(&self, enum_defs: &[EnumDefinition]) -> PartialVMResult<()> {
+        for (enum_def_idx, enum_def) in enum_defs.iter().enumerate() {
+            let enum_handle = self.resolver.struct_handle_at(enum_def.enum_handle);
+            let type_param_constraints: Vec<_> = enum_handle.type_param_constraints().collect();
+            for (tag, variant) in enum_def.variants.iter().enumerate() {
+                let err_handler = |err: PartialVMError, idx| {
+                    err.at_index(IndexKind::FieldDefinition, idx as TableIndex)
+                        .at_index(IndexKind::VariantTag, tag as TableIndex)
+                        .at_index(IndexKind::EnumDefinition, enum_def_idx as TableIndex)
+                };
+                for (field_offset, field_def) in variant.fields.iter().enumerate() {
+                    self.check_signature_token(&field_def.signature.0)
+                        .map_err(|err| err_handler(err, field_offset))?;
+                    self.check_type_instantiation(&field_def.signature.0, &type_param_constraints)
+                        .map_err(|err| err_handler(err, field_offset))?;
+                    self.check_phantom_params(
+                        &field_def.signature.0,
+                        false,
+                        &enum_handle.type_parameters,
+                    )
+                    .map_err(|err| err_handler(err, field_offset))?;
+                }
+            }
+        }
+        Ok(())
+    }
+
     fn verify_code_units(
         &mut self,
         function_handles: &[FunctionHandle],
@@ -176,6 +205,22 @@ impl<'a> SignatureChecker<'a> {
                         type_parameters,
                     )
                 }
+                PackVariantGeneric(idx)
+                | UnpackVariantGeneric(idx)
+                | UnpackVariantGenericImmRef(idx)
+                | UnpackVariantGenericMutRef(idx) => {
+                    let variant_inst = self.resolver.variant_instantiation_handle_at(*idx)?;
+                    let enum_inst = self.resolver.enum_instantiation_at(variant_inst.enum_def)?;
+                    let enum_def = self.resolver.enum_def_at(enum_inst.def)?;
+                    let enum_handle = self.resolver.struct_handle_at(enum_def.enum_handle);
+                    let type_arguments = &self.resolver.signature_at(enum_inst.type_parameters).0;
+                    self.check_signature_tokens(type_arguments)?;
+                    self.check_generic_instance(
+                        type_arguments,
+                        enum_handle.type_param_constraints(),
+                        type_parameters,
+                    )
+                }
                 ImmBorrowFieldGeneric(idx) | MutBorrowFieldGeneric(idx) => {
                     let field_inst = self.resolver.field_instantiation_at(*idx)?;
                     let field_handle = self.resolver.field_handle_at(field_inst.handle)?;
@@ -269,6 +314,11 @@ impl<'a> SignatureChecker<'a> {
                 | ExistsDeprecated(_)
                 | MoveToDeprecated(_)
                 | MoveFromDeprecated(_)
+                | PackVariant(_)
+                | UnpackVariant(_)
+                | UnpackVariantImmRef(_)
+                | UnpackVariantMutRef(_)
+                | VariantSwitch(_)
                 | Abort
                 | Nop => Ok(()),
             };
@@ -15,7 +15,10 @@ use move_binary_format::{
     binary_views::{BinaryIndexedView, FunctionView},
     control_flow_graph::{BlockId, ControlFlowGraph},
     errors::{PartialVMError, PartialVMResult},
-    file_format::{Bytecode, CodeUnit, FunctionDefinitionIndex, Signature, StructFieldInformation},
+    file_format::{
+        Bytecode, CodeUnit, EnumDefinitionIndex, FunctionDefinitionIndex, Signature,
+        StructFieldInformation, VariantTag,
+    },
 };
 use move_core_types::vm_status::StatusCode;
 use move_vm_config::verifier::VerifierConfig;
@@ -123,6 +126,7 @@ impl<'a> StackUsageVerifier<'a> {
             Bytecode::Pop
             | Bytecode::BrTrue(_)
             | Bytecode::BrFalse(_)
+            | Bytecode::VariantSwitch(_)
             | Bytecode::StLoc(_)
             | Bytecode::Abort => (1, 0),
 
@@ -267,9 +271,50 @@ impl<'a> StackUsageVerifier<'a> {
                 };
                 (1, field_count as u64)
             }
+
+            // PackVariant performs `num_fields` pops of the variant and one push
+            Bytecode::PackVariant(idx) => {
+                let handle = self.resolver.variant_handle_at(*idx)?;
+                let field_count = self.variant_field_count(handle.enum_def, handle.variant)?;
+                (field_count, 1)
+            }
+            Bytecode::PackVariantGeneric(idx) => {
+                let handle = self.resolver.variant_instantiation_handle_at(*idx)?;
+                let enum_inst = self.resolver.enum_instantiation_at(handle.enum_def)?;
+                let field_count = self.variant_field_count(enum_inst.def, handle.variant)?;
+                (field_count, 1)
+            }
+
+            // UnpackVariant (by value or by reference) performs one pop and
+            // `num_fields` pushes of the variant
+            Bytecode::UnpackVariant(idx)
+            | Bytecode::UnpackVariantImmRef(idx)
+            | Bytecode::UnpackVariantMutRef(idx) => {
+                let handle = self.resolver.variant_handle_at(*idx)?;
+                let field_count = self.variant_field_count(handle.enum_def, handle.variant)?;
+                (1, field_count)
+            }
+            Bytecode::UnpackVariantGeneric(idx)
+            | Bytecode::UnpackVariantGenericImmRef(idx)
+            | Bytecode::UnpackVariantGenericMutRef(idx) => {
+                let handle = self.resolver.variant_instantiation_handle_at(*idx)?;
+                let enum_inst = self.resolver.enum_instantiation_at(handle.enum_def)?;
+                let field_count = self.variant_field_count(enum_inst.def, handle.variant)?;
+                (1, field_count)
+            }
         })
     }
 
+    fn variant_field_count(
+        &self,
+        enum_def_idx: EnumDefinitionIndex,
+        tag: VariantTag,
+    ) -> PartialVMResult<u64> {
+        let enum_def = self.resolver.enum_def_at(enum_def_idx)?;
+        // An out of bounds tag has already been rejected by the bounds checker
+        Ok(enum_def.variant(tag).map_or(0, |v| v.fields.len()) as u64)
+    }
+
     fn current_function(&self) -> FunctionDefinitionIndex {
         self.current_function.unwrap_or(FunctionDefinitionIndex(0))
     }
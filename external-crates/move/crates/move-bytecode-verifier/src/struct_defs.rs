@@ -3,17 +3,18 @@
 // Modifications Copyright (c) 2024 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-//! This module provides a checker for verifying that struct definitions in a
-//! module are not recursive. Since the module dependency graph is acylic by
-//! construction, applying this checker to each module in isolation guarantees
-//! that there is no structural recursion globally.
+//! This module provides a checker for verifying that struct and enum
+//! definitions in a module are not recursive. Since the module dependency graph
+//! is acylic by construction, applying this checker to each module in isolation
+//! guarantees that there is no structural recursion globally.
 use std::collections::{BTreeMap, BTreeSet};
 
 use move_binary_format::{
     access::ModuleAccess,
     errors::{verification_error, Location, PartialVMError, PartialVMResult, VMResult},
     file_format::{
-        CompiledModule, SignatureToken, StructDefinitionIndex, StructHandleIndex, TableIndex,
+        CompiledModule, EnumDefinitionIndex, SignatureToken, StructDefinitionIndex,
+        StructHandleIndex, TableIndex,
     },
     internals::ModuleIndex,
     views::StructDefinitionView,
@@ -40,32 +41,57 @@ impl<'a> RecursiveStructDefChecker<'a> {
         // untrusted data.
         match toposort(&graph, None) {
             Ok(_) => Ok(()),
-            Err(cycle) => Err(verification_error(
-                StatusCode::RECURSIVE_STRUCT_DEFINITION,
-                IndexKind::StructDefinition,
-                cycle.node_id().into_index() as TableIndex,
-            )),
+            Err(cycle) => {
+                let (kind, idx) = match cycle.node_id() {
+                    DatatypeDefIndex::Struct(idx) => {
+                        (IndexKind::StructDefinition, idx.into_index())
+                    }
+                    DatatypeDefIndex::Enum(idx) => (IndexKind::EnumDefinition, idx.into_index()),
+                };
+                Err(verification_error(
+                    StatusCode::RECURSIVE_STRUCT_DEFINITION,
+                    kind,
+                    idx as TableIndex,
+                ))
+            }
         }
     }
 }
 
-/// Given a module, build a graph of struct definitions. This is useful when
-/// figuring out whether the struct definitions in module form a cycle.
+/// A node of the datatype graph: either a struct or an enum definition.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+enum DatatypeDefIndex {
+    Struct(StructDefinitionIndex),
+    Enum(EnumDefinitionIndex),
+}
+
+/// Given a module, build a graph of struct and enum definitions. This is
+/// useful when figuring out whether the definitions in module form a cycle.
 struct StructDefGraphBuilder<'a> {
     module: &'a CompiledModule,
     /// Used to follow field definitions' signatures' struct handles to their
-    /// struct definitions.
-    handle_to_def: BTreeMap<StructHandleIndex, StructDefinitionIndex>,
+    /// struct or enum definitions.
+    handle_to_def: BTreeMap<StructHandleIndex, DatatypeDefIndex>,
 }
 
 impl<'a> StructDefGraphBuilder<'a> {
     fn new(module: &'a CompiledModule) -> Self {
         let mut handle_to_def = BTreeMap::new();
-        // the mapping from struct definitions to struct handles is already checked to
-        // be 1-1 by DuplicationChecker
+        // the mapping from struct and enum definitions to struct handles is already
+        // checked to be 1-1 by DuplicationChecker
         for (idx, struct_def) in module.struct_defs().iter().enumerate() {
             let sh_idx = struct_def.struct_handle;
-            handle_to_def.insert(sh_idx, StructDefinitionIndex(idx as TableIndex));
+            handle_to_def.insert(
+                sh_idx,
+                DatatypeDefIndex::Struct(StructDefinitionIndex(idx as TableIndex)),
+            );
+        }
+        for (idx, enum_def) in module.enum_defs().iter().enumerate() {
+            let sh_idx = enum_def.enum_handle;
+            handle_to_def.insert(
+                sh_idx,
+                DatatypeDefIndex::Enum(EnumDefinitionIndex(idx as TableIndex)),
+            );
         }
 
         Self {
@@ -74,12 +100,16 @@ impl<'a> StructDefGraphBuilder<'a> {
         }
     }
 
-    fn build(self) -> PartialVMResult<DiGraphMap<StructDefinitionIndex, ()>> {
+    fn build(self) -> PartialVMResult<DiGraphMap<DatatypeDefIndex, ()>> {
         let mut neighbors = BTreeMap::new();
         for idx in 0..self.module.struct_defs().len() {
             let sd_idx = StructDefinitionIndex::new(idx as TableIndex);
             self.add_struct_defs(&mut neighbors, sd_idx)?
         }
+        for idx in 0..self.module.enum_defs().len() {
+            let ed_idx = EnumDefinitionIndex::new(idx as TableIndex);
+            self.add_enum_defs(&mut neighbors, ed_idx)?
+        }
 
         let edges = neighbors
             .into_iter()
@@ -89,7 +119,7 @@ impl<'a> StructDefGraphBuilder<'a> {
 
     fn add_struct_defs(
         &self,
-        neighbors: &mut BTreeMap<StructDefinitionIndex, BTreeSet<StructDefinitionIndex>>,
+        neighbors: &mut BTreeMap<DatatypeDefIndex, BTreeSet<DatatypeDefIndex>>,
         idx: StructDefinitionIndex,
     ) -> PartialVMResult<()> {
         let struct_def = self.module.struct_def_at(idx);
@@ -97,15 +127,31 @@ impl<'a> StructDefGraphBuilder<'a> {
         // The fields iterator is an option in the case of native structs. Flatten makes
         // an empty iterator for that case
         for field in struct_def.fields().into_iter().flatten() {
-            self.add_signature_token(neighbors, idx, field.signature_token())?
+            self.add_signature_token(
+                neighbors,
+                DatatypeDefIndex::Struct(idx),
+                field.signature_token(),
+            )?
+        }
+        Ok(())
+    }
+
+    fn add_enum_defs(
+        &self,
+        neighbors: &mut BTreeMap<DatatypeDefIndex, BTreeSet<DatatypeDefIndex>>,
+        idx: EnumDefinitionIndex,
+    ) -> PartialVMResult<()> {
+        let enum_def = self.module.enum_def_at(idx);
+        for field in enum_def.variants.iter().flat_map(|v| v.fields.iter()) {
+            self.add_signature_token(neighbors, DatatypeDefIndex::Enum(idx), &field.signature.0)?
         }
         Ok(())
     }
 
     fn add_signature_token(
         &self,
-        neighbors: &mut BTreeMap<StructDefinitionIndex, BTreeSet<StructDefinitionIndex>>,
-        cur_idx: StructDefinitionIndex,
+        neighbors: &mut BTreeMap<DatatypeDefIndex, BTreeSet<DatatypeDefIndex>>,
+        cur_idx: DatatypeDefIndex,
         token: &SignatureToken,
     ) -> PartialVMResult<()> {
         use SignatureToken as T;
@@ -15,9 +15,10 @@ use move_binary_format::{
     control_flow_graph::ControlFlowGraph,
     errors::{PartialVMError, PartialVMResult},
     file_format::{
-        AbilitySet, Bytecode, CodeOffset, FieldHandleIndex, FunctionDefinitionIndex,
-        FunctionHandle, LocalIndex, Signature, SignatureToken, SignatureToken as ST,
-        StructDefinition, StructDefinitionIndex, StructFieldInformation, StructHandleIndex,
+        AbilitySet, Bytecode, CodeOffset, EnumDefinition, FieldHandleIndex,
+        FunctionDefinitionIndex, FunctionHandle, LocalIndex, Signature, SignatureToken,
+        SignatureToken as ST, StructDefinition, StructDefinitionIndex, StructFieldInformation,
+        StructHandleIndex, VariantJumpTableIndex, VariantTag,
     },
     safe_unwrap_err,
 };
@@ -344,6 +345,115 @@ fn unpack(
     Ok(())
 }
 
+// How the fields of a variant are exposed by an unpack instruction
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum UnpackKind {
+    Value,
+    ImmRef,
+    MutRef,
+}
+
+fn variant_fields_signature(
+    verifier: &mut TypeSafetyChecker,
+    offset: CodeOffset,
+    enum_def: &EnumDefinition,
+    tag: VariantTag,
+    type_args: &Signature,
+) -> PartialVMResult<Signature> {
+    let variant = match enum_def.variant(tag) {
+        Some(variant) => variant,
+        None => return Err(verifier.error(StatusCode::VARIANT_TAG_MISMATCH, offset)),
+    };
+    Ok(Signature(
+        variant
+            .fields
+            .iter()
+            .map(|field_def| instantiate(&field_def.signature.0, type_args))
+            .collect(),
+    ))
+}
+
+fn pack_variant(
+    verifier: &mut TypeSafetyChecker,
+    meter: &mut impl Meter,
+    offset: CodeOffset,
+    enum_def: &EnumDefinition,
+    tag: VariantTag,
+    type_args: &Signature,
+) -> PartialVMResult<()> {
+    let enum_type = materialize_type(enum_def.enum_handle, type_args);
+    let field_sig = variant_fields_signature(verifier, offset, enum_def, tag, type_args)?;
+    for sig in field_sig.0.iter().rev() {
+        let arg = safe_unwrap_err!(verifier.stack.pop());
+        if &arg != sig {
+            return Err(verifier.error(StatusCode::PACK_TYPE_MISMATCH_ERROR, offset));
+        }
+    }
+
+    verifier.push(meter, enum_type)?;
+    Ok(())
+}
+
+fn unpack_variant(
+    verifier: &mut TypeSafetyChecker,
+    meter: &mut impl Meter,
+    offset: CodeOffset,
+    enum_def: &EnumDefinition,
+    tag: VariantTag,
+    type_args: &Signature,
+    kind: UnpackKind,
+) -> PartialVMResult<()> {
+    let enum_type = materialize_type(enum_def.enum_handle, type_args);
+
+    // Pop an abstract value from the stack and check if its type is equal to the
+    // one declared, taking into account whether it is unpacked by reference.
+    let arg = safe_unwrap_err!(verifier.stack.pop());
+    let matches = match (kind, arg) {
+        (UnpackKind::Value, arg) => arg == enum_type,
+        (UnpackKind::ImmRef, ST::Reference(inner))
+        | (UnpackKind::MutRef, ST::MutableReference(inner)) => *inner == enum_type,
+        _ => false,
+    };
+    if !matches {
+        return Err(verifier.error(StatusCode::UNPACK_TYPE_MISMATCH_ERROR, offset));
+    }
+
+    let field_sig = variant_fields_signature(verifier, offset, enum_def, tag, type_args)?;
+    for sig in field_sig.0 {
+        let field_type = match kind {
+            UnpackKind::Value => sig,
+            UnpackKind::ImmRef => ST::Reference(Box::new(sig)),
+            UnpackKind::MutRef => ST::MutableReference(Box::new(sig)),
+        };
+        verifier.push(meter, field_type)?
+    }
+    Ok(())
+}
+
+fn variant_switch(
+    verifier: &mut TypeSafetyChecker,
+    offset: CodeOffset,
+    jump_table_index: VariantJumpTableIndex,
+) -> PartialVMResult<()> {
+    // The switch inspects the tag of an enum value through a reference, so that
+    // the value itself is left untouched for the branch targets
+    let operand = safe_unwrap_err!(verifier.stack.pop());
+    let jump_table = &verifier.function_view.code().jump_tables[jump_table_index.0 as usize];
+    let enum_def = verifier.resolver.enum_def_at(jump_table.head_enum)?;
+    let handle = match &operand {
+        ST::Reference(inner) | ST::MutableReference(inner) => match &**inner {
+            ST::Struct(handle) => Some(*handle),
+            ST::StructInstantiation(inst) => Some(inst.0),
+            _ => None,
+        },
+        _ => None,
+    };
+    if handle != Some(enum_def.enum_handle) {
+        return Err(verifier.error(StatusCode::ENUM_SWITCH_BAD_OPERAND, offset));
+    }
+    Ok(())
+}
+
 fn exists(
     verifier: &mut TypeSafetyChecker,
     meter: &mut impl Meter,
@@ -649,6 +759,75 @@ fn verify_instr(
             unpack(verifier, meter, offset, struct_def, type_args)?
         }
 
+        Bytecode::PackVariant(idx) => {
+            let handle = verifier.resolver.variant_handle_at(*idx)?;
+            let enum_def = verifier.resolver.enum_def_at(handle.enum_def)?;
+            pack_variant(
+                verifier,
+                meter,
+                offset,
+                enum_def,
+                handle.variant,
+                &Signature(vec![]),
+            )?
+        }
+
+        Bytecode::PackVariantGeneric(idx) => {
+            let handle = verifier.resolver.variant_instantiation_handle_at(*idx)?;
+            let enum_inst = verifier.resolver.enum_instantiation_at(handle.enum_def)?;
+            let enum_def = verifier.resolver.enum_def_at(enum_inst.def)?;
+            let type_args = verifier.resolver.signature_at(enum_inst.type_parameters);
+            verifier.charge_tys(meter, &type_args.0)?;
+            pack_variant(verifier, meter, offset, enum_def, handle.variant, type_args)?
+        }
+
+        Bytecode::UnpackVariant(idx)
+        | Bytecode::UnpackVariantImmRef(idx)
+        | Bytecode::UnpackVariantMutRef(idx) => {
+            let kind = match bytecode {
+                Bytecode::UnpackVariantImmRef(_) => UnpackKind::ImmRef,
+                Bytecode::UnpackVariantMutRef(_) => UnpackKind::MutRef,
+                _ => UnpackKind::Value,
+            };
+            let handle = verifier.resolver.variant_handle_at(*idx)?;
+            let enum_def = verifier.resolver.enum_def_at(handle.enum_def)?;
+            unpack_variant(
+                verifier,
+                meter,
+                offset,
+                enum_def,
+                handle.variant,
+                &Signature(vec![]),
+                kind,
+            )?
+        }
+
+        Bytecode::UnpackVariantGeneric(idx)
+        | Bytecode::UnpackVariantGenericImmRef(idx)
+        | Bytecode::UnpackVariantGenericMutRef(idx) => {
+            let kind = match bytecode {
+                Bytecode::UnpackVariantGenericImmRef(_) => UnpackKind::ImmRef,
+                Bytecode::UnpackVariantGenericMutRef(_) => UnpackKind::MutRef,
+                _ => UnpackKind::Value,
+            };
+            let handle = verifier.resolver.variant_instantiation_handle_at(*idx)?;
+            let enum_inst = verifier.resolver.enum_instantiation_at(handle.enum_def)?;
+            let enum_def = verifier.resolver.enum_def_at(enum_inst.def)?;
+            let type_args = verifier.resolver.signature_at(enum_inst.type_parameters);
+            verifier.charge_tys(meter, &type_args.0)?;
+            unpack_variant(
+                verifier,
+                meter,
+                offset,
+                enum_def,
+                handle.variant,
+                type_args,
+                kind,
+            )?
+        }
+
+        Bytecode::VariantSwitch(idx) => variant_switch(verifier, offset, *idx)?,
+
         Bytecode::ReadRef => {
             let operand = safe_unwrap_err!(verifier.stack.pop());
             match operand {
@@ -284,6 +284,7 @@ impl AstDebug for MoveValue {
             }
             V::Struct(_) => panic!("ICE struct constants not supported"),
             V::Signer(_) => panic!("ICE signer constants not supported"),
+            V::Variant(_) => panic!("ICE enum constants not supported"),
         }
     }
 }
@@ -35,12 +35,26 @@ pub const MOVE_STRUCT_TYPE: &str = "type";
 /// struct with this as the second field
 pub const MOVE_STRUCT_FIELDS: &str = "fields";
 
+/// In the `WithTypes` configuration, a Move enum value gets serialized with the
+/// name of its variant under this field
+pub const MOVE_VARIANT_NAME: &str = "variant";
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct MoveStruct {
     pub type_: StructTag,
     pub fields: Vec<(Identifier, MoveValue)>,
 }
 
+/// A value of a Move enum, decorated with its type and the name of its
+/// variant.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct MoveVariant {
+    pub type_: StructTag,
+    pub variant_name: Identifier,
+    pub tag: u16,
+    pub fields: Vec<(Identifier, MoveValue)>,
+}
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub enum MoveValue {
     U8(u8),
@@ -55,6 +69,8 @@ pub enum MoveValue {
     U16(u16),
     U32(u32),
     U256(u256::U256),
+    // NOTE: Added in bytecode version v7, do not reorder!
+    Variant(MoveVariant),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -77,6 +93,19 @@ pub struct MoveStructLayout {
     pub fields: Vec<MoveFieldLayout>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MoveVariantLayout {
+    pub name: Identifier,
+    pub fields: Vec<MoveFieldLayout>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MoveEnumLayout {
+    pub type_: StructTag,
+    /// The variants of the enum, in tag order
+    pub variants: Vec<MoveVariantLayout>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum MoveTypeLayout {
     #[serde(rename(serialize = "bool", deserialize = "bool"))]
@@ -103,6 +132,10 @@ pub enum MoveTypeLayout {
     U32,
     #[serde(rename(serialize = "u256", deserialize = "u256"))]
     U256,
+
+    // NOTE: Added in bytecode version v7, do not reorder!
+    #[serde(rename(serialize = "enum", deserialize = "enum"))]
+    Enum(MoveEnumLayout),
 }
 
 impl MoveValue {
@@ -157,6 +190,7 @@ impl MoveValue {
     pub fn undecorate(self) -> R::MoveValue {
         match self {
             Self::Struct(s) => R::MoveValue::Struct(s.undecorate()),
+            Self::Variant(v) => R::MoveValue::Variant(v.undecorate()),
             Self::Vector(vals) => {
                 R::MoveValue::Vector(vals.into_iter().map(MoveValue::undecorate).collect())
             }
@@ -229,6 +263,37 @@ impl MoveStruct {
     }
 }
 
+impl MoveVariant {
+    pub fn new(
+        type_: StructTag,
+        variant_name: Identifier,
+        tag: u16,
+        fields: Vec<(Identifier, MoveValue)>,
+    ) -> Self {
+        Self {
+            type_,
+            variant_name,
+            tag,
+            fields,
+        }
+    }
+
+    pub fn into_fields(self) -> Vec<MoveValue> {
+        self.fields.into_iter().map(|(_, f)| f).collect()
+    }
+
+    pub fn undecorate(self) -> R::MoveVariant {
+        R::MoveVariant {
+            tag: self.tag,
+            fields: self
+                .into_fields()
+                .into_iter()
+                .map(MoveValue::undecorate)
+                .collect(),
+        }
+    }
+}
+
 impl MoveStructLayout {
     pub fn new(type_: StructTag, fields: Vec<MoveFieldLayout>) -> Self {
         Self { type_, fields }
@@ -239,6 +304,25 @@ impl MoveStructLayout {
     }
 }
 
+impl MoveVariantLayout {
+    pub fn new(name: Identifier, fields: Vec<MoveFieldLayout>) -> Self {
+        Self { name, fields }
+    }
+}
+
+impl MoveEnumLayout {
+    pub fn new(type_: StructTag, variants: Vec<MoveVariantLayout>) -> Self {
+        Self { type_, variants }
+    }
+
+    pub fn into_variants(self) -> Vec<Vec<MoveTypeLayout>> {
+        self.variants
+            .into_iter()
+            .map(|v| v.fields.into_iter().map(|f| f.layout).collect())
+            .collect()
+    }
+}
+
 impl<'d> serde::de::DeserializeSeed<'d> for &MoveTypeLayout {
     type Value = MoveValue;
     fn deserialize<D: serde::de::Deserializer<'d>>(
@@ -260,6 +344,7 @@ impl<'d> serde::de::DeserializeSeed<'d> for &MoveTypeLayout {
                 AccountAddress::deserialize(deserializer).map(MoveValue::Signer)
             }
             MoveTypeLayout::Struct(ty) => Ok(MoveValue::Struct(ty.deserialize(deserializer)?)),
+            MoveTypeLayout::Enum(ty) => Ok(MoveValue::Variant(ty.deserialize(deserializer)?)),
             MoveTypeLayout::Vector(layout) => Ok(MoveValue::Vector(
                 deserializer.deserialize_seq(VectorElementVisitor(layout))?,
             )),
@@ -339,10 +424,57 @@ impl<'d> serde::de::DeserializeSeed<'d> for &MoveStructLayout {
     }
 }
 
+impl<'d> serde::de::DeserializeSeed<'d> for &MoveEnumLayout {
+    type Value = MoveVariant;
+
+    fn deserialize<D: serde::de::Deserializer<'d>>(
+        self,
+        deserializer: D,
+    ) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_enum(R::MOVE_ENUM_NAME, &[], DecoratedEnumVariantVisitor(self))
+    }
+}
+
+struct DecoratedEnumVariantVisitor<'a>(&'a MoveEnumLayout);
+
+impl<'d, 'a> serde::de::Visitor<'d> for DecoratedEnumVariantVisitor<'a> {
+    type Value = MoveVariant;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("Enum")
+    }
+
+    fn visit_enum<A>(self, data: A) -> Result<Self::Value, A::Error>
+    where
+        A: serde::de::EnumAccess<'d>,
+    {
+        use serde::de::VariantAccess;
+
+        let (tag, variant) = data.variant::<u32>()?;
+        let Some(layout) = self.0.variants.get(tag as usize) else {
+            return Err(A::Error::invalid_value(
+                serde::de::Unexpected::Unsigned(tag as u64),
+                &self,
+            ));
+        };
+        let fields = variant.tuple_variant(
+            layout.fields.len(),
+            DecoratedStructFieldVisitor(&layout.fields),
+        )?;
+        Ok(MoveVariant {
+            type_: self.0.type_.clone(),
+            variant_name: layout.name.clone(),
+            tag: tag as u16,
+            fields,
+        })
+    }
+}
+
 impl serde::Serialize for MoveValue {
     fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
         match self {
             MoveValue::Struct(s) => s.serialize(serializer),
+            MoveValue::Variant(v) => v.serialize(serializer),
             MoveValue::Bool(b) => serializer.serialize_bool(*b),
             MoveValue::U8(i) => serializer.serialize_u8(*i),
             MoveValue::U16(i) => serializer.serialize_u16(*i),
@@ -393,6 +525,19 @@ impl serde::Serialize for MoveStruct {
     }
 }
 
+impl serde::Serialize for MoveVariant {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        // Serialize a Move enum value as a Serde struct named `enum` with three
+        // fields: its `type`, the name of its `variant`, and its `fields`, in
+        // the same way as structs.
+        let mut t = serializer.serialize_struct(R::MOVE_ENUM_NAME, 3)?;
+        t.serialize_field(MOVE_STRUCT_TYPE, &self.type_.to_string())?;
+        t.serialize_field(MOVE_VARIANT_NAME, &self.variant_name.as_str())?;
+        t.serialize_field(MOVE_STRUCT_FIELDS, &MoveFields(&self.fields))?;
+        t.end()
+    }
+}
+
 impl fmt::Display for MoveTypeLayout {
     fn fmt(&self, f: &mut fmt::Formatter) -> std::fmt::Result {
         use MoveTypeLayout::*;
@@ -410,6 +555,8 @@ impl fmt::Display for MoveTypeLayout {
             Vector(typ) => write!(f, "vector<{typ}>"),
             Struct(s) if f.alternate() => write!(f, "{s:#}"),
             Struct(s) => write!(f, "{s}"),
+            Enum(e) if f.alternate() => write!(f, "{e:#}"),
+            Enum(e) => write!(f, "{e}"),
         }
     }
 }
@@ -442,6 +589,36 @@ impl fmt::Display for MoveStructLayout {
     }
 }
 
+impl fmt::Display for MoveEnumLayout {
+    fn fmt(&self, f: &mut fmt::Formatter) -> std::fmt::Result {
+        use DebugAsDisplay as DD;
+        write!(f, "enum ")?;
+        write!(f, "{} ", self.type_)?;
+        let mut map = f.debug_map();
+        for variant in &self.variants {
+            map.entry(
+                &DD(&variant.name),
+                &DD(&VariantFieldsLayout(&variant.fields)),
+            );
+        }
+        map.finish()
+    }
+}
+
+/// Displays the fields of a variant in the same way as those of a struct.
+struct VariantFieldsLayout<'a>(&'a [MoveFieldLayout]);
+
+impl<'a> fmt::Display for VariantFieldsLayout<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> std::fmt::Result {
+        use DebugAsDisplay as DD;
+        let mut map = f.debug_map();
+        for field in self.0 {
+            map.entry(&DD(&field.name), &DD(&field.layout));
+        }
+        map.finish()
+    }
+}
+
 impl From<&MoveTypeLayout> for TypeTag {
     fn from(val: &MoveTypeLayout) -> TypeTag {
         match val {
@@ -459,6 +636,7 @@ impl From<&MoveTypeLayout> for TypeTag {
                 TypeTag::Vector(Box::new(inner_type.into()))
             }
             MoveTypeLayout::Struct(v) => TypeTag::Struct(Box::new(v.into())),
+            MoveTypeLayout::Enum(e) => TypeTag::Struct(Box::new(e.type_.clone())),
         }
     }
 }
@@ -492,6 +670,7 @@ impl fmt::Display for MoveValue {
                 list.finish()
             }
             MoveValue::Struct(s) => fmt::Display::fmt(s, f),
+            MoveValue::Variant(v) => fmt::Display::fmt(v, f),
         }
     }
 }
@@ -508,3 +687,16 @@ impl fmt::Display for MoveStruct {
         map.finish()
     }
 }
+
+impl fmt::Display for MoveVariant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use DebugAsDisplay as DD;
+        fmt::Display::fmt(&self.type_, f)?;
+        write!(f, "::{} ", self.variant_name)?;
+        let mut map = f.debug_map();
+        for (field, value) in &self.fields {
+            map.entry(&DD(field), &DD(value));
+        }
+        map.finish()
+    }
+}
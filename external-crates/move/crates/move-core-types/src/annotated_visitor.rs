@@ -6,7 +6,9 @@ use std::io::Read;
 
 use crate::{
     account_address::AccountAddress,
-    annotated_value::{MoveFieldLayout, MoveStructLayout, MoveTypeLayout},
+    annotated_value::{
+        MoveEnumLayout, MoveFieldLayout, MoveStructLayout, MoveTypeLayout, MoveVariantLayout,
+    },
     u256::U256,
 };
 
@@ -49,15 +51,21 @@ pub trait Visitor {
         &mut self,
         driver: &mut StructDriver<'_, '_, '_>,
     ) -> Result<Self::Value, Self::Error>;
+
+    fn visit_variant(
+        &mut self,
+        driver: &mut VariantDriver<'_, '_, '_>,
+    ) -> Result<Self::Value, Self::Error>;
 }
 
 /// A traversal is a special kind of visitor that doesn't return any values. The
 /// trait comes with default implementations for every variant that do nothing,
 /// allowing an implementor to focus on only the cases they care about.
 ///
-/// Note that the default implementation for structs and vectors recurse down
-/// into their elements. A traversal that doesn't want to look inside structs
-/// and vectors needs to provide a custom implementation with an empty body:
+/// Note that the default implementation for structs, enum variants and vectors
+/// recurse down into their elements. A traversal that doesn't want to look
+/// inside structs and vectors needs to provide a custom implementation with an
+/// empty body:
 ///
 /// ```rust,no_run
 /// fn traverse_vector(&mut self, _: &mut VecDriver) -> Result<(), Self::Error> {
@@ -115,6 +123,14 @@ pub trait Traversal {
         while driver.next_field(self)?.is_some() {}
         Ok(())
     }
+
+    fn traverse_variant(
+        &mut self,
+        driver: &mut VariantDriver<'_, '_, '_>,
+    ) -> Result<(), Self::Error> {
+        while driver.next_field(self)?.is_some() {}
+        Ok(())
+    }
 }
 
 /// Default implementation converting any traversal into a visitor.
@@ -171,6 +187,13 @@ impl<T: Traversal + ?Sized> Visitor for T {
     ) -> Result<Self::Value, Self::Error> {
         self.traverse_struct(driver)
     }
+
+    fn visit_variant(
+        &mut self,
+        driver: &mut VariantDriver<'_, '_, '_>,
+    ) -> Result<Self::Value, Self::Error> {
+        self.traverse_variant(driver)
+    }
 }
 
 /// Exposes information about a vector being visited (the element layout) to a
@@ -192,6 +215,18 @@ pub struct StructDriver<'r, 'b, 'l> {
     off: usize,
 }
 
+/// Exposes information about an enum value being visited (the layout of the
+/// enum, the variant the value belongs to, and details about the next field
+/// of that variant to be visited) to a visitor implementation, and allows that
+/// visitor to progress the traversal (by visiting or skipping fields).
+pub struct VariantDriver<'r, 'b, 'l> {
+    bytes: &'r mut &'b [u8],
+    layout: &'l MoveEnumLayout,
+    tag: u16,
+    variant: &'l MoveVariantLayout,
+    off: usize,
+}
+
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
     #[error("unexpected end of input")]
@@ -202,6 +237,9 @@ pub enum Error {
 
     #[error("trailing {0} byte(s) at the end of input")]
     TrailingBytes(usize),
+
+    #[error("unexpected variant tag: {0}")]
+    UnexpectedVariantTag(u64),
 }
 
 /// The null traversal implements `Traversal` and `Visitor` but without doing
@@ -319,6 +357,65 @@ impl<'r, 'b, 'l> StructDriver<'r, 'b, 'l> {
     }
 }
 
+impl<'r, 'b, 'l> VariantDriver<'r, 'b, 'l> {
+    fn new(
+        bytes: &'r mut &'b [u8],
+        layout: &'l MoveEnumLayout,
+        tag: u16,
+        variant: &'l MoveVariantLayout,
+    ) -> Self {
+        Self {
+            bytes,
+            layout,
+            tag,
+            variant,
+            off: 0,
+        }
+    }
+
+    /// The layout of the enum being visited.
+    pub fn enum_layout(&self) -> &'l MoveEnumLayout {
+        self.layout
+    }
+
+    /// The layout of the variant of the value being visited.
+    pub fn variant_layout(&self) -> &'l MoveVariantLayout {
+        self.variant
+    }
+
+    /// The tag of the variant of the value being visited.
+    pub fn tag(&self) -> u16 {
+        self.tag
+    }
+
+    /// The layout of the next field to be visited (if there is one), or `None`
+    /// otherwise.
+    pub fn peek_field(&self) -> Option<&'l MoveFieldLayout> {
+        self.variant.fields.get(self.off)
+    }
+
+    /// Visit the next field of the variant. Behaves like
+    /// `StructDriver::next_field`.
+    pub fn next_field<V: Visitor + ?Sized>(
+        &mut self,
+        visitor: &mut V,
+    ) -> Result<Option<(&'l MoveFieldLayout, V::Value)>, V::Error> {
+        let Some(field) = self.peek_field() else {
+            return Ok(None);
+        };
+
+        let res = visit_value(self.bytes, &field.layout, visitor)?;
+        self.off += 1;
+        Ok(Some((field, res)))
+    }
+
+    /// Skip the next field. Behaves like `StructDriver::skip_field`.
+    pub fn skip_field(&mut self) -> Result<Option<&'l MoveFieldLayout>, Error> {
+        self.next_field(&mut NullTraversal)
+            .map(|res| res.map(|(f, _)| f))
+    }
+}
+
 /// Visit a serialized Move value with the provided `layout`, held in `bytes`,
 /// using the provided visitor to build a value out of it. See
 /// `annotated_value::MoveValue::visit_deserialize` for details.
@@ -354,6 +451,17 @@ pub(crate) fn visit_value<V: Visitor + ?Sized>(
         }
 
         L::Struct(l) => visit_struct(bytes, l, visitor),
+
+        L::Enum(l) => {
+            let tag = leb128::read::unsigned(bytes).map_err(|_| Error::UnexpectedEof)?;
+            let Some(variant) = l.variants.get(tag as usize) else {
+                return Err(Error::UnexpectedVariantTag(tag).into());
+            };
+            let mut driver = VariantDriver::new(bytes, l, tag as u16, variant);
+            let res = visitor.visit_variant(&mut driver)?;
+            while driver.skip_field()?.is_some() {}
+            Ok(res)
+        }
     }
 }
 
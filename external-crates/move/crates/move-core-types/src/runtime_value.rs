@@ -9,7 +9,7 @@ use anyhow::{anyhow, Result as AResult};
 use move_proc_macros::test_variant_order;
 use serde::{
     de::Error as DeError,
-    ser::{SerializeSeq, SerializeTuple},
+    ser::{SerializeSeq, SerializeTuple, SerializeTupleVariant},
     Deserialize, Serialize,
 };
 
@@ -27,9 +27,20 @@ pub const MOVE_STRUCT_TYPE: &str = "type";
 /// struct with this as the second field
 pub const MOVE_STRUCT_FIELDS: &str = "fields";
 
+/// A Move enum gets serialized into a Serde enum with this name
+pub const MOVE_ENUM_NAME: &str = "enum";
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct MoveStruct(pub Vec<MoveValue>);
 
+/// A value of a Move enum: the tag of its variant, and the fields of that
+/// variant.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct MoveVariant {
+    pub tag: u16,
+    pub fields: Vec<MoveValue>,
+}
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub enum MoveValue {
     U8(u8),
@@ -44,11 +55,18 @@ pub enum MoveValue {
     U16(u16),
     U32(u32),
     U256(u256::U256),
+    // NOTE: Added in bytecode version v7, do not reorder!
+    Variant(MoveVariant),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MoveStructLayout(pub Vec<MoveTypeLayout>);
 
+/// The layout of a Move enum: for each variant, in tag order, the layouts of
+/// its fields.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MoveEnumLayout(pub Vec<Vec<MoveTypeLayout>>);
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[test_variant_order(src/unit_tests/staged_enum_variant_order/move_type_layout.yaml)]
 pub enum MoveTypeLayout {
@@ -76,6 +94,10 @@ pub enum MoveTypeLayout {
     U32,
     #[serde(rename(serialize = "u256", deserialize = "u256"))]
     U256,
+
+    // NOTE: Added in bytecode version v7, do not reorder!
+    #[serde(rename(serialize = "enum", deserialize = "enum"))]
+    Enum(MoveEnumLayout),
 }
 
 impl MoveValue {
@@ -121,6 +143,9 @@ impl MoveValue {
             (MoveValue::Struct(s), A::MoveTypeLayout::Struct(l)) => {
                 A::MoveValue::Struct(s.decorate(l))
             }
+            (MoveValue::Variant(v), A::MoveTypeLayout::Enum(l)) => {
+                A::MoveValue::Variant(v.decorate(l))
+            }
             (MoveValue::Vector(vals), A::MoveTypeLayout::Vector(t)) => {
                 A::MoveValue::Vector(vals.into_iter().map(|v| v.decorate(t)).collect())
             }
@@ -181,6 +206,49 @@ impl MoveStruct {
     }
 }
 
+impl MoveVariant {
+    pub fn new(tag: u16, fields: Vec<MoveValue>) -> Self {
+        Self { tag, fields }
+    }
+
+    pub fn decorate(self, layout: &A::MoveEnumLayout) -> A::MoveVariant {
+        let MoveVariant { tag, fields } = self;
+        let A::MoveEnumLayout { type_, variants } = layout;
+        // A tag beyond the layout means the value doesn't match its layout
+        let variant = variants
+            .get(tag as usize)
+            .expect("Invalid decoration: variant tag out of bounds");
+        A::MoveVariant {
+            type_: type_.clone(),
+            variant_name: variant.name.clone(),
+            tag,
+            fields: fields
+                .into_iter()
+                .zip(&variant.fields)
+                .map(|(v, l)| (l.name.clone(), v.decorate(&l.layout)))
+                .collect(),
+        }
+    }
+
+    pub fn fields(&self) -> &[MoveValue] {
+        &self.fields
+    }
+
+    pub fn into_fields(self) -> Vec<MoveValue> {
+        self.fields
+    }
+}
+
+impl MoveEnumLayout {
+    pub fn new(variants: Vec<Vec<MoveTypeLayout>>) -> Self {
+        Self(variants)
+    }
+
+    pub fn variants(&self) -> &[Vec<MoveTypeLayout>] {
+        &self.0
+    }
+}
+
 impl MoveStructLayout {
     pub fn new(types: Vec<MoveTypeLayout>) -> Self {
         Self(types)
@@ -216,6 +284,7 @@ impl<'d> serde::de::DeserializeSeed<'d> for &MoveTypeLayout {
                 AccountAddress::deserialize(deserializer).map(MoveValue::Signer)
             }
             MoveTypeLayout::Struct(ty) => Ok(MoveValue::Struct(ty.deserialize(deserializer)?)),
+            MoveTypeLayout::Enum(ty) => Ok(MoveValue::Variant(ty.deserialize(deserializer)?)),
             MoveTypeLayout::Vector(layout) => Ok(MoveValue::Vector(
                 deserializer.deserialize_seq(VectorElementVisitor(layout))?,
             )),
@@ -282,10 +351,52 @@ impl<'d> serde::de::DeserializeSeed<'d> for &MoveStructLayout {
     }
 }
 
+impl<'d> serde::de::DeserializeSeed<'d> for &MoveEnumLayout {
+    type Value = MoveVariant;
+
+    fn deserialize<D: serde::de::Deserializer<'d>>(
+        self,
+        deserializer: D,
+    ) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_enum(MOVE_ENUM_NAME, &[], EnumVariantVisitor(self))
+    }
+}
+
+struct EnumVariantVisitor<'a>(&'a MoveEnumLayout);
+
+impl<'d, 'a> serde::de::Visitor<'d> for EnumVariantVisitor<'a> {
+    type Value = MoveVariant;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("Enum")
+    }
+
+    fn visit_enum<A>(self, data: A) -> Result<Self::Value, A::Error>
+    where
+        A: serde::de::EnumAccess<'d>,
+    {
+        use serde::de::VariantAccess;
+
+        let (tag, variant) = data.variant::<u32>()?;
+        let Some(fields) = self.0.0.get(tag as usize) else {
+            return Err(A::Error::invalid_value(
+                serde::de::Unexpected::Unsigned(tag as u64),
+                &self,
+            ));
+        };
+        let fields = variant.tuple_variant(fields.len(), StructFieldVisitor(fields))?;
+        Ok(MoveVariant {
+            tag: tag as u16,
+            fields,
+        })
+    }
+}
+
 impl serde::Serialize for MoveValue {
     fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
         match self {
             MoveValue::Struct(s) => s.serialize(serializer),
+            MoveValue::Variant(v) => v.serialize(serializer),
             MoveValue::Bool(b) => serializer.serialize_bool(*b),
             MoveValue::U8(i) => serializer.serialize_u8(*i),
             MoveValue::U16(i) => serializer.serialize_u16(*i),
@@ -316,6 +427,23 @@ impl serde::Serialize for MoveStruct {
     }
 }
 
+impl serde::Serialize for MoveVariant {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        // The variant is identified by its tag only: the names are not part of
+        // the serialized form.
+        let mut t = serializer.serialize_tuple_variant(
+            MOVE_ENUM_NAME,
+            self.tag as u32,
+            "",
+            self.fields.len(),
+        )?;
+        for v in self.fields.iter() {
+            t.serialize_field(v)?;
+        }
+        t.end()
+    }
+}
+
 impl fmt::Display for MoveTypeLayout {
     fn fmt(&self, f: &mut fmt::Formatter) -> std::fmt::Result {
         use MoveTypeLayout::*;
@@ -333,6 +461,8 @@ impl fmt::Display for MoveTypeLayout {
             Vector(typ) => write!(f, "vector<{typ}>"),
             Struct(s) if f.alternate() => write!(f, "{s:#}"),
             Struct(s) => write!(f, "{s}"),
+            Enum(e) if f.alternate() => write!(f, "{e:#}"),
+            Enum(e) => write!(f, "{e}"),
         }
     }
 }
@@ -366,6 +496,20 @@ impl fmt::Display for MoveStructLayout {
     }
 }
 
+impl fmt::Display for MoveEnumLayout {
+    fn fmt(&self, f: &mut fmt::Formatter) -> std::fmt::Result {
+        use DebugAsDisplay as DD;
+
+        write!(f, "enum ")?;
+        let mut map = f.debug_map();
+        for (tag, fields) in self.0.iter().enumerate() {
+            map.entry(&tag, &DD(&MoveStructLayout::new(fields.clone())));
+        }
+
+        map.finish()
+    }
+}
+
 impl fmt::Display for MoveValue {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -381,6 +525,7 @@ impl fmt::Display for MoveValue {
             MoveValue::Signer(a) => write!(f, "signer({})", a.to_hex_literal()),
             MoveValue::Vector(v) => fmt_list(f, "vector[", v, "]"),
             MoveValue::Struct(s) => fmt::Display::fmt(s, f),
+            MoveValue::Variant(v) => fmt::Display::fmt(v, f),
         }
     }
 }
@@ -390,3 +535,10 @@ impl fmt::Display for MoveStruct {
         fmt_list(f, "struct[", &self.0, "]")
     }
 }
+
+impl fmt::Display for MoveVariant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "variant#{}", self.tag)?;
+        fmt_list(f, "[", &self.fields, "]")
+    }
+}
@@ -189,3 +189,60 @@ fn nested_typed_struct_formatted_display() {
 }"#
     );
 }
+
+#[test]
+fn variant_deserialization() {
+    let enum_type = StructTag {
+        address: AccountAddress::ZERO,
+        name: ident_str!("MyEnum").to_owned(),
+        module: ident_str!("MyModule").to_owned(),
+        type_params: vec![],
+    };
+    let runtime_layout = R::MoveTypeLayout::Enum(R::MoveEnumLayout::new(vec![
+        vec![],
+        vec![R::MoveTypeLayout::U64, R::MoveTypeLayout::Bool],
+    ]));
+    let annotated_layout = A::MoveTypeLayout::Enum(A::MoveEnumLayout::new(
+        enum_type,
+        vec![
+            A::MoveVariantLayout::new(ident_str!("A").to_owned(), vec![]),
+            A::MoveVariantLayout::new(
+                ident_str!("B").to_owned(),
+                vec![
+                    A::MoveFieldLayout::new(ident_str!("f").to_owned(), A::MoveTypeLayout::U64),
+                    A::MoveFieldLayout::new(ident_str!("g").to_owned(), A::MoveTypeLayout::Bool),
+                ],
+            ),
+        ],
+    ));
+
+    let runtime_value = R::MoveValue::Variant(R::MoveVariant::new(
+        1,
+        vec![R::MoveValue::U64(7), R::MoveValue::Bool(true)],
+    ));
+    let blob = runtime_value.simple_serialize().unwrap();
+    assert_eq!(blob, vec![1, 7, 0, 0, 0, 0, 0, 0, 0, 1]);
+    assert_eq!(
+        R::MoveValue::simple_deserialize(&blob, &runtime_layout).unwrap(),
+        runtime_value
+    );
+
+    let typed_value = A::MoveValue::simple_deserialize(&blob, &annotated_layout).unwrap();
+    assert_eq!(
+        typed_value,
+        runtime_value.clone().decorate(&annotated_layout)
+    );
+    assert_eq!(typed_value.clone().undecorate(), runtime_value);
+    assert_eq!(
+        serde_json::to_value(&typed_value).unwrap(),
+        json!({
+                "fields": { "f": 7, "g": true },
+                "type": "0x0::MyModule::MyEnum",
+                "variant": "B"
+            }
+        )
+    );
+
+    // Tags without a matching variant are rejected
+    assert!(R::MoveValue::simple_deserialize(&[2], &runtime_layout).is_err());
+}
@@ -6,8 +6,13 @@ use std::{fmt::Write, str::FromStr};
 
 use crate::{
     account_address::AccountAddress,
-    annotated_value::{MoveFieldLayout, MoveStruct, MoveStructLayout, MoveTypeLayout, MoveValue},
-    annotated_visitor::{self, NullTraversal, StructDriver, Traversal, VecDriver, Visitor},
+    annotated_value::{
+        MoveEnumLayout, MoveFieldLayout, MoveStruct, MoveStructLayout, MoveTypeLayout, MoveValue,
+        MoveVariant, MoveVariantLayout,
+    },
+    annotated_visitor::{
+        self, NullTraversal, StructDriver, Traversal, VariantDriver, VecDriver, Visitor,
+    },
     identifier::Identifier,
     language_storage::StructTag,
     u256::U256,
@@ -315,6 +320,32 @@ fn nested_typed_struct_visit() {
             let type_ = driver.struct_layout().type_.clone();
             Ok(V::Struct(MoveStruct { type_, fields }))
         }
+
+        fn visit_variant(
+            &mut self,
+            driver: &mut VariantDriver<'_, '_, '_>,
+        ) -> Result<Self::Value, Self::Error> {
+            let layout = driver.enum_layout();
+            write!(self.output, "\n[{}] {layout:#}", self.depth).unwrap();
+
+            let mut fields = vec![];
+            let mut field_visitor = Self {
+                depth: self.depth + 1,
+                output: std::mem::take(&mut self.output),
+            };
+
+            while let Some((field, value)) = driver.next_field(&mut field_visitor)? {
+                fields.push((field.name.clone(), value));
+            }
+
+            self.output = field_visitor.output;
+            Ok(V::Variant(MoveVariant {
+                type_: layout.type_.clone(),
+                variant_name: driver.variant_layout().name.clone(),
+                tag: driver.tag(),
+                fields,
+            }))
+        }
     }
 
     let type_layout = layout_(
@@ -463,6 +494,14 @@ fn peek_field_test() {
         ) -> Result<Self::Value, Self::Error> {
             Ok(None)
         }
+
+        /// Field specifier doesn't support enums either.
+        fn visit_variant(
+            &mut self,
+            _: &mut VariantDriver<'_, '_, '_>,
+        ) -> Result<Self::Value, Self::Error> {
+            Ok(None)
+        }
     }
 
     let type_layout = layout_(
@@ -511,6 +550,67 @@ fn peek_field_test() {
     assert_eq!(visit_struct(&["d", "e"]), Some(45));
 }
 
+#[test]
+fn variant_visit() {
+    use MoveTypeLayout as T;
+    use MoveValue as V;
+
+    #[derive(Default)]
+    struct CountingTraversal(usize);
+
+    impl Traversal for CountingTraversal {
+        type Error = annotated_visitor::Error;
+
+        fn traverse_u64(&mut self, _: u64) -> Result<(), Self::Error> {
+            self.0 += 1;
+            Ok(())
+        }
+    }
+
+    let type_ = StructTag::from_str("0x0::foo::Shape").unwrap();
+    let type_layout = T::Enum(MoveEnumLayout::new(
+        type_.clone(),
+        vec![
+            MoveVariantLayout::new(Identifier::new("Empty").unwrap(), vec![]),
+            MoveVariantLayout::new(
+                Identifier::new("Rect").unwrap(),
+                vec![
+                    MoveFieldLayout::new(Identifier::new("w").unwrap(), T::U64),
+                    MoveFieldLayout::new(Identifier::new("h").unwrap(), T::U64),
+                ],
+            ),
+        ],
+    ));
+
+    let value = V::Variant(MoveVariant::new(
+        type_,
+        Identifier::new("Rect").unwrap(),
+        1,
+        vec![
+            (Identifier::new("w").unwrap(), V::U64(3)),
+            (Identifier::new("h").unwrap(), V::U64(4)),
+        ],
+    ));
+
+    // The tag is encoded as a single ULEB128 byte, followed by the fields.
+    let bytes = serialize(value.clone());
+    assert_eq!(bytes[0], 1);
+    assert_eq!(bytes.len(), 1 + 8 + 8);
+
+    let mut count = CountingTraversal::default();
+    MoveValue::visit_deserialize(&bytes, &type_layout, &mut count).unwrap();
+    assert_eq!(count.0, 2);
+
+    assert_eq!(
+        MoveValue::simple_deserialize(&bytes, &type_layout).unwrap(),
+        value
+    );
+
+    // A tag with no matching variant is rejected.
+    let err = MoveValue::visit_deserialize(&[2], &type_layout, &mut NullTraversal).unwrap_err();
+    assert_eq!(err.to_string(), "unexpected variant tag: 2");
+}
+
 /// Create a struct value for test purposes.
 fn value_(rep: &str, fields: Vec<(&str, MoveValue)>) -> MoveValue {
     let type_ = StructTag::from_str(rep).unwrap();
@@ -309,6 +309,14 @@ pub enum StatusCode {
     // Bytecode verifier determined code to complex
     // or took too long to run based on metering policies
     PROGRAM_TOO_COMPLEX = 1130,
+    // Reported when an enum has zero variants
+    ZERO_SIZED_ENUM = 1131,
+    // A variant switch does not cover every variant of the enum exactly once
+    INVALID_ENUM_SWITCH = 1132,
+    // The value switched over is not a reference to the enum of the jump table
+    ENUM_SWITCH_BAD_OPERAND = 1133,
+    // A jump table is not used by any variant switch in the function
+    UNUSED_JUMP_TABLE = 1134,
 
     // These are errors that the VM might raise if a violation of internal
     // invariants takes place.
@@ -382,6 +390,8 @@ pub enum StatusCode {
     STORAGE_WRITE_LIMIT_REACHED = 4027,
     MEMORY_LIMIT_EXCEEDED = 4028,
     VM_MAX_TYPE_NODES_REACHED = 4029,
+    // The variant of an enum value does not match the variant being unpacked
+    VARIANT_TAG_MISMATCH = 4030,
 
     // A reserved status to represent an unknown vm status.
     // this is u64::MAX, but we can't pattern match on that, so put the hardcoded value in
@@ -19,7 +19,7 @@ fn cfg_compile_script_ret() {
         } }
         ";
     let code = compile_module_with_single_function(text);
-    let cfg: VMControlFlowGraph = VMControlFlowGraph::new(&code);
+    let cfg: VMControlFlowGraph = VMControlFlowGraph::new(&code, &[]);
     cfg.display();
     assert_eq!(cfg.blocks().len(), 1);
     assert_eq!(cfg.num_blocks(), 1);
@@ -41,7 +41,7 @@ fn cfg_compile_script_let() {
         } }
         ";
     let code = compile_module_with_single_function(text);
-    let cfg: VMControlFlowGraph = VMControlFlowGraph::new(&code);
+    let cfg: VMControlFlowGraph = VMControlFlowGraph::new(&code, &[]);
     assert_eq!(cfg.blocks().len(), 1);
     assert_eq!(cfg.num_blocks(), 1);
     assert_eq!(cfg.reachable_from(0).len(), 1);
@@ -65,7 +65,7 @@ fn cfg_compile_if() {
         } }
         ";
     let code = compile_module_with_single_function(text);
-    let cfg: VMControlFlowGraph = VMControlFlowGraph::new(&code);
+    let cfg: VMControlFlowGraph = VMControlFlowGraph::new(&code, &[]);
     assert_eq!(cfg.blocks().len(), 4);
     assert_eq!(cfg.num_blocks(), 4);
     assert_eq!(cfg.reachable_from(0).len(), 4);
@@ -92,7 +92,7 @@ fn cfg_compile_if_else() {
         } }
         ";
     let code = compile_module_with_single_function(text);
-    let cfg: VMControlFlowGraph = VMControlFlowGraph::new(&code);
+    let cfg: VMControlFlowGraph = VMControlFlowGraph::new(&code, &[]);
     assert_eq!(cfg.blocks().len(), 4);
     assert_eq!(cfg.num_blocks(), 4);
     assert_eq!(cfg.reachable_from(0).len(), 4);
@@ -115,7 +115,7 @@ fn cfg_compile_if_else_with_else_return() {
         } }
         ";
     let code = compile_module_with_single_function(text);
-    let cfg: VMControlFlowGraph = VMControlFlowGraph::new(&code);
+    let cfg: VMControlFlowGraph = VMControlFlowGraph::new(&code, &[]);
     assert_eq!(cfg.blocks().len(), 4);
     assert_eq!(cfg.num_blocks(), 4);
     assert_eq!(cfg.reachable_from(0).len(), 4);
@@ -146,7 +146,7 @@ fn cfg_compile_nested_if() {
         } }
         ";
     let code = compile_module_with_single_function(text);
-    let cfg: VMControlFlowGraph = VMControlFlowGraph::new(&code);
+    let cfg: VMControlFlowGraph = VMControlFlowGraph::new(&code, &[]);
     assert_eq!(cfg.blocks().len(), 7);
     assert_eq!(cfg.num_blocks(), 7);
     assert_eq!(cfg.reachable_from(8).len(), 3);
@@ -169,7 +169,7 @@ fn cfg_compile_if_else_with_if_return() {
         } }
         ";
     let code = compile_module_with_single_function(text);
-    let cfg: VMControlFlowGraph = VMControlFlowGraph::new(&code);
+    let cfg: VMControlFlowGraph = VMControlFlowGraph::new(&code, &[]);
     assert_eq!(cfg.blocks().len(), 4);
     assert_eq!(cfg.num_blocks(), 4);
     assert_eq!(cfg.reachable_from(0).len(), 4);
@@ -192,7 +192,7 @@ fn cfg_compile_if_else_with_two_returns() {
         } }
         ";
     let code = compile_module_with_single_function(text);
-    let cfg: VMControlFlowGraph = VMControlFlowGraph::new(&code);
+    let cfg: VMControlFlowGraph = VMControlFlowGraph::new(&code, &[]);
     assert_eq!(cfg.blocks().len(), 4);
     assert_eq!(cfg.num_blocks(), 4);
     assert_eq!(cfg.reachable_from(0).len(), 3);
@@ -218,7 +218,7 @@ fn cfg_compile_if_else_with_else_abort() {
         } }
         ";
     let code = compile_module_with_single_function(text);
-    let cfg: VMControlFlowGraph = VMControlFlowGraph::new(&code);
+    let cfg: VMControlFlowGraph = VMControlFlowGraph::new(&code, &[]);
     cfg.display();
     assert_eq!(cfg.blocks().len(), 4);
     assert_eq!(cfg.num_blocks(), 4);
@@ -242,7 +242,7 @@ fn cfg_compile_if_else_with_if_abort() {
         } }
         ";
     let code = compile_module_with_single_function(text);
-    let cfg: VMControlFlowGraph = VMControlFlowGraph::new(&code);
+    let cfg: VMControlFlowGraph = VMControlFlowGraph::new(&code, &[]);
     cfg.display();
     assert_eq!(cfg.blocks().len(), 4);
     assert_eq!(cfg.num_blocks(), 4);
@@ -266,7 +266,7 @@ fn cfg_compile_if_else_with_two_aborts() {
         } }
         ";
     let code = compile_module_with_single_function(text);
-    let cfg: VMControlFlowGraph = VMControlFlowGraph::new(&code);
+    let cfg: VMControlFlowGraph = VMControlFlowGraph::new(&code, &[]);
     cfg.display();
     assert_eq!(cfg.blocks().len(), 4);
     assert_eq!(cfg.num_blocks(), 4);
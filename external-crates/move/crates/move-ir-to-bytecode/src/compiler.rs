@@ -21,7 +21,7 @@ use move_binary_format::{
         StructHandleIndex, StructTypeParameter, TableIndex, TypeParameterIndex, TypeSignature,
         Visibility,
     },
-    file_format_common::VERSION_MAX,
+    file_format_common::VERSION_DEFAULT,
 };
 use move_bytecode_source_map::source_map::SourceMap;
 use move_command_line_common::error_bitset::ErrorBitsetBuilder;
@@ -417,7 +417,7 @@ pub fn compile_module<'a>(
         source_map,
     ) = context.materialize_pools();
     let module = CompiledModule {
-        version: VERSION_MAX,
+        version: VERSION_DEFAULT,
         module_handles,
         self_module_handle_idx,
         struct_handles,
@@ -434,6 +434,10 @@ pub fn compile_module<'a>(
         metadata: vec![],
         struct_defs,
         function_defs,
+        enum_defs: vec![],
+        enum_def_instantiations: vec![],
+        variant_handles: vec![],
+        variant_instantiation_handles: vec![],
     };
     Ok((module, source_map))
 }
@@ -496,7 +500,7 @@ fn compile_explicit_dependency_declarations(
             _source_map,
         ) = context.materialize_pools();
         let compiled_module = CompiledModule {
-            version: VERSION_MAX,
+            version: VERSION_DEFAULT,
             module_handles,
             self_module_handle_idx,
             struct_handles,
@@ -513,6 +517,10 @@ fn compile_explicit_dependency_declarations(
             metadata: vec![],
             struct_defs: vec![],
             function_defs: vec![],
+            enum_defs: vec![],
+            enum_def_instantiations: vec![],
+            variant_handles: vec![],
+            variant_instantiation_handles: vec![],
         };
         dependencies_acc = compiled_deps;
         dependencies_acc.insert(
@@ -840,6 +848,7 @@ fn compile_function_body(
     Ok(CodeUnit {
         locals: context.signature_index(locals_signature)?,
         code: compile_blocks(context, &mut function_frame, blocks)?,
+        jump_tables: vec![],
     })
 }
 
@@ -1483,6 +1492,7 @@ fn compile_function_body_bytecode(
     Ok(CodeUnit {
         locals: sig_idx,
         code,
+        jump_tables: vec![],
     })
 }
 
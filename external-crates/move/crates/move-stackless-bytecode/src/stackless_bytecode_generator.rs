@@ -93,9 +93,14 @@ impl<'a> StacklessBytecodeGenerator<'a> {
             };
         }
 
-        // Generate bytecode.
+        // Generate bytecode, stopping at the first instruction which cannot be
+        // translated. The error has been reported to the environment then.
+        let error_count = self.func_env.module_env.env.error_count();
         for (code_offset, bytecode) in original_code.iter().enumerate() {
             self.generate_bytecode(bytecode, code_offset as CodeOffset, &label_map);
+            if self.func_env.module_env.env.error_count() > error_count {
+                break;
+            }
         }
 
         // Eliminate fall-through for non-branching instructions
@@ -1298,6 +1303,18 @@ impl<'a> StacklessBytecodeGenerator<'a> {
                     None,
                 ))
             }
+            MoveBytecode::PackVariant(_)
+            | MoveBytecode::PackVariantGeneric(_)
+            | MoveBytecode::UnpackVariant(_)
+            | MoveBytecode::UnpackVariantImmRef(_)
+            | MoveBytecode::UnpackVariantMutRef(_)
+            | MoveBytecode::UnpackVariantGeneric(_)
+            | MoveBytecode::UnpackVariantGenericImmRef(_)
+            | MoveBytecode::UnpackVariantGenericMutRef(_)
+            | MoveBytecode::VariantSwitch(_) => global_env.error(
+                &self.func_env.get_bytecode_loc(code_offset),
+                "enum bytecode is not supported by the move model",
+            ),
         }
     }
 
@@ -199,8 +199,8 @@ mod testing {
 
     use move_binary_format::errors::{PartialVMError, PartialVMResult};
     use move_core_types::{
-        account_address::AccountAddress, annotated_value as A, language_storage::TypeTag,
-        runtime_value as R, vm_status::StatusCode,
+        account_address::AccountAddress, annotated_value as A, identifier::Identifier,
+        language_storage::TypeTag, runtime_value as R, vm_status::StatusCode,
     };
     use move_vm_runtime::native_functions::NativeContext;
     use move_vm_types::{loaded_data::runtime_types::Type, values::Value};
@@ -298,7 +298,10 @@ mod testing {
     }
 
     fn is_vector_or_struct_move_value(mv: &A::MoveValue) -> bool {
-        matches!(mv, A::MoveValue::Vector(_) | A::MoveValue::Struct(_))
+        matches!(
+            mv,
+            A::MoveValue::Vector(_) | A::MoveValue::Struct(_) | A::MoveValue::Variant(_)
+        )
     }
 
     /// Prints any `Value` in a user-friendly manner.
@@ -571,63 +574,103 @@ mod testing {
                     write!(out, "\"{}\"", str).map_err(fmt_error_to_partial_vm_error)?
                 } else {
                     write!(out, "{} ", type_tag).map_err(fmt_error_to_partial_vm_error)?;
-                    write!(out, "{}", STRUCT_BEGIN).map_err(fmt_error_to_partial_vm_error)?;
-
-                    // For each field, print its name and value (and type)
-                    let mut iter = fields.into_iter();
-                    if let Some((field_name, field_value)) = iter.next() {
-                        // Start an indented new line
-                        if !single_line {
-                            writeln!(out).map_err(fmt_error_to_partial_vm_error)?;
-                            print_padding_at_depth(out, depth + 1)?;
-                        }
-
-                        write!(out, "{}: ", field_name.into_string())
-                            .map_err(fmt_error_to_partial_vm_error)?;
-                        print_move_value(
-                            out,
-                            field_value,
-                            move_std_addr,
-                            depth + 1,
-                            canonicalize,
-                            single_line,
-                            include_int_types,
-                        )?;
+                    print_fields(
+                        out,
+                        fields,
+                        move_std_addr,
+                        depth,
+                        canonicalize,
+                        single_line,
+                        include_int_types,
+                    )?;
+                }
+            }
+            A::MoveValue::Variant(A::MoveVariant {
+                type_,
+                variant_name,
+                tag: _,
+                fields,
+            }) => {
+                let type_tag = TypeTag::from(type_);
+                write!(out, "{}::{} ", type_tag, variant_name)
+                    .map_err(fmt_error_to_partial_vm_error)?;
+                print_fields(
+                    out,
+                    fields,
+                    move_std_addr,
+                    depth,
+                    canonicalize,
+                    single_line,
+                    include_int_types,
+                )?;
+            }
+        }
 
-                        for (field_name, field_value) in iter {
-                            write!(out, "{}", VECTOR_OR_STRUCT_SEP)
-                                .map_err(fmt_error_to_partial_vm_error)?;
-
-                            if !single_line {
-                                writeln!(out).map_err(fmt_error_to_partial_vm_error)?;
-                                print_padding_at_depth(out, depth + 1)?;
-                            } else {
-                                write!(out, " ").map_err(fmt_error_to_partial_vm_error)?;
-                            }
-                            write!(out, "{}: ", field_name.into_string())
-                                .map_err(fmt_error_to_partial_vm_error)?;
-                            print_move_value(
-                                out,
-                                field_value,
-                                move_std_addr,
-                                depth + 1,
-                                canonicalize,
-                                single_line,
-                                include_int_types,
-                            )?;
-                        }
-                    }
+        Ok(())
+    }
 
-                    // Ends printing the struct with "}", which could be on its own line
-                    if !single_line {
-                        writeln!(out).map_err(fmt_error_to_partial_vm_error)?;
-                        print_padding_at_depth(out, depth)?;
-                    }
-                    write!(out, "{}", STRUCT_END).map_err(fmt_error_to_partial_vm_error)?;
+    /// Prints the fields of a struct or enum variant between braces.
+    fn print_fields(
+        out: &mut String,
+        fields: Vec<(Identifier, A::MoveValue)>,
+        move_std_addr: &AccountAddress,
+        depth: usize,
+        canonicalize: bool,
+        single_line: bool,
+        include_int_types: bool,
+    ) -> PartialVMResult<()> {
+        write!(out, "{}", STRUCT_BEGIN).map_err(fmt_error_to_partial_vm_error)?;
+
+        // For each field, print its name and value (and type)
+        let mut iter = fields.into_iter();
+        if let Some((field_name, field_value)) = iter.next() {
+            // Start an indented new line
+            if !single_line {
+                writeln!(out).map_err(fmt_error_to_partial_vm_error)?;
+                print_padding_at_depth(out, depth + 1)?;
+            }
+
+            write!(out, "{}: ", field_name.into_string()).map_err(fmt_error_to_partial_vm_error)?;
+            print_move_value(
+                out,
+                field_value,
+                move_std_addr,
+                depth + 1,
+                canonicalize,
+                single_line,
+                include_int_types,
+            )?;
+
+            for (field_name, field_value) in iter {
+                write!(out, "{}", VECTOR_OR_STRUCT_SEP).map_err(fmt_error_to_partial_vm_error)?;
+
+                if !single_line {
+                    writeln!(out).map_err(fmt_error_to_partial_vm_error)?;
+                    print_padding_at_depth(out, depth + 1)?;
+                } else {
+                    write!(out, " ").map_err(fmt_error_to_partial_vm_error)?;
                 }
+                write!(out, "{}: ", field_name.into_string())
+                    .map_err(fmt_error_to_partial_vm_error)?;
+                print_move_value(
+                    out,
+                    field_value,
+                    move_std_addr,
+                    depth + 1,
+                    canonicalize,
+                    single_line,
+                    include_int_types,
+                )?;
             }
         }
 
+        // Ends printing the struct with "}", which could be on its own line
+        if !single_line {
+            writeln!(out).map_err(fmt_error_to_partial_vm_error)?;
+            print_padding_at_depth(out, depth)?;
+        }
+        write!(out, "{}", STRUCT_END).map_err(fmt_error_to_partial_vm_error)?;
+
         Ok(())
     }
 
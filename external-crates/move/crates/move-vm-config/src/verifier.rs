@@ -27,6 +27,7 @@ pub struct VerifierConfig {
     pub max_identifier_len: Option<u64>,
     pub allow_receiving_object_id: bool,
     pub reject_mutable_random_on_entry_functions: bool,
+    pub enable_enums: bool,
 }
 
 impl Default for VerifierConfig {
@@ -66,6 +67,7 @@ impl Default for VerifierConfig {
             max_identifier_len: Some(DEFAULT_MAX_IDENTIFIER_LENGTH),
             allow_receiving_object_id: true,
             reject_mutable_random_on_entry_functions: true,
+            enable_enums: true,
         }
     }
 }
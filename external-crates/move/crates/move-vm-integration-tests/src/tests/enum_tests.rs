@@ -0,0 +1,94 @@
+// Copyright (c) 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use move_binary_format::{
+    errors::VMResult,
+    file_format::{basic_enum_test_module, CompiledModule, JumpTableInner},
+};
+use move_core_types::{
+    identifier::Identifier,
+    runtime_value::{MoveTypeLayout, MoveValue},
+    vm_status::StatusCode,
+};
+use move_vm_config::runtime::VMConfig;
+use move_vm_runtime::move_vm::MoveVM;
+use move_vm_test_utils::InMemoryStorage;
+use move_vm_types::gas::UnmeteredGasMeter;
+
+fn run(m: &CompiledModule, fun_name: &str, args: Vec<MoveValue>) -> VMResult<u64> {
+    let mut blob = vec![];
+    m.serialize(&mut blob).unwrap();
+    let mut storage = InMemoryStorage::new();
+    storage.publish_or_overwrite_module(m.self_id(), blob);
+
+    let vm = MoveVM::new(vec![]).unwrap();
+    let mut sess = vm.new_session(&storage);
+    let args: Vec<_> = args
+        .into_iter()
+        .map(|val| val.simple_serialize().unwrap())
+        .collect();
+    let return_values = sess
+        .execute_function_bypass_visibility(
+            &m.self_id(),
+            &Identifier::new(fun_name).unwrap(),
+            vec![],
+            args,
+            &mut UnmeteredGasMeter,
+        )?
+        .return_values;
+
+    assert_eq!(return_values.len(), 1);
+    match MoveValue::simple_deserialize(&return_values[0].0, &MoveTypeLayout::U64).unwrap() {
+        MoveValue::U64(value) => Ok(value),
+        value => panic!("unexpected return value {value:?}"),
+    }
+}
+
+#[test]
+fn pack_unpack_and_switch() {
+    let m = basic_enum_test_module();
+    let args = |b| vec![MoveValue::U64(7), MoveValue::Bool(b)];
+    assert_eq!(run(&m, "f", args(true)).unwrap(), 7);
+    assert_eq!(run(&m, "f", args(false)).unwrap(), 0);
+}
+
+#[test]
+fn pack_unpack_and_switch_generic() {
+    let m = basic_enum_test_module();
+    assert_eq!(run(&m, "g", vec![MoveValue::U64(9)]).unwrap(), 9);
+}
+
+#[test]
+fn unpack_wrong_variant() {
+    let mut m = basic_enum_test_module();
+    // swap the targets of the switch, so that each variant is unpacked as the
+    // other one
+    m.function_defs[0].code.as_mut().unwrap().jump_tables[0].jump_table =
+        JumpTableInner::Full(vec![13, 10]);
+    let args = |b| vec![MoveValue::U64(7), MoveValue::Bool(b)];
+    for b in [true, false] {
+        assert_eq!(
+            run(&m, "f", args(b)).unwrap_err().major_status(),
+            StatusCode::VARIANT_TAG_MISMATCH
+        );
+    }
+}
+
+#[test]
+fn enums_disabled() {
+    let m = basic_enum_test_module();
+    let mut blob = vec![];
+    m.serialize(&mut blob).unwrap();
+
+    let mut vm_config = VMConfig::default();
+    vm_config.verifier.enable_enums = false;
+    let vm = MoveVM::new_with_config(vec![], vm_config).unwrap();
+    let storage = InMemoryStorage::new();
+    let mut sess = vm.new_session(&storage);
+    assert_eq!(
+        sess.publish_module(blob, *m.self_id().address(), &mut UnmeteredGasMeter)
+            .unwrap_err()
+            .major_status(),
+        StatusCode::FEATURE_UNDER_GATING
+    );
+}
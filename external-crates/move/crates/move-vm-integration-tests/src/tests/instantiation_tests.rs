@@ -441,6 +441,7 @@ fn make_module(
                 code: Some(CodeUnit {
                     locals: SignatureIndex(locals_idx as u16),
                     code,
+                    jump_tables: vec![],
                 }),
             },
             FunctionDefinition {
@@ -463,6 +464,7 @@ fn make_module(
                         CallGeneric(FunctionInstantiationIndex(0)),
                         Ret,
                     ],
+                    jump_tables: vec![],
                 }),
             },
             FunctionDefinition {
@@ -473,6 +475,7 @@ fn make_module(
                 code: Some(CodeUnit {
                     locals: SignatureIndex(locals_idx as u16),
                     code: vec![Ret],
+                    jump_tables: vec![],
                 }),
             },
         ],
@@ -509,6 +512,10 @@ fn make_module(
         friend_decls: vec![],
         field_instantiations: vec![],
         metadata: vec![],
+        enum_defs: vec![],
+        enum_def_instantiations: vec![],
+        variant_handles: vec![],
+        variant_instantiation_handles: vec![],
     };
     // uncomment to see the module generated
     // println!("Module: {:#?}", module);
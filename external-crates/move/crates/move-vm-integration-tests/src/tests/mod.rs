@@ -6,6 +6,7 @@
 mod bad_entry_point_tests;
 mod bad_storage_tests;
 mod binary_format_version;
+mod enum_tests;
 mod exec_func_effects_tests;
 mod function_arg_tests;
 mod instantiation_tests;
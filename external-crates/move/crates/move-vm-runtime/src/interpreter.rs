@@ -8,7 +8,7 @@ use std::{cmp::min, collections::VecDeque, fmt::Write, sync::Arc};
 use fail::fail_point;
 use move_binary_format::{
     errors::*,
-    file_format::{Bytecode, FunctionHandleIndex, FunctionInstantiationIndex},
+    file_format::{Bytecode, FunctionHandleIndex, FunctionInstantiationIndex, JumpTableInner},
 };
 use move_core_types::{
     account_address::AccountAddress,
@@ -1034,6 +1034,106 @@ impl Frame {
                     interpreter.operand_stack.push(value)?;
                 }
             }
+            Bytecode::PackVariant(vh_idx) => {
+                let (field_count, tag) = resolver.variant_field_count_and_tag(*vh_idx);
+                let enum_type = resolver.get_enum_type(*vh_idx);
+                Self::check_depth_of_type(resolver, &enum_type)?;
+                gas_meter.charge_pack(
+                    false,
+                    interpreter.operand_stack.last_n(field_count as usize)?,
+                )?;
+                let args = interpreter.operand_stack.popn(field_count)?;
+                interpreter
+                    .operand_stack
+                    .push(Value::struct_(Struct::pack_variant(tag, args)))?;
+            }
+            Bytecode::PackVariantGeneric(vi_idx) => {
+                let (field_count, tag) =
+                    resolver.variant_instantiation_field_count_and_tag(*vi_idx);
+                let ty = resolver.instantiate_generic_enum(*vi_idx, ty_args)?;
+                Self::check_depth_of_type(resolver, &ty)?;
+                gas_meter.charge_pack(
+                    true,
+                    interpreter.operand_stack.last_n(field_count as usize)?,
+                )?;
+                let args = interpreter.operand_stack.popn(field_count)?;
+                interpreter
+                    .operand_stack
+                    .push(Value::struct_(Struct::pack_variant(tag, args)))?;
+            }
+            Bytecode::UnpackVariant(vh_idx) => {
+                let (_, tag) = resolver.variant_field_count_and_tag(*vh_idx);
+                let struct_ = interpreter.operand_stack.pop_as::<Struct>()?;
+
+                gas_meter.charge_unpack(false, struct_.field_views())?;
+
+                for value in struct_.unpack_variant(tag)? {
+                    interpreter.operand_stack.push(value)?;
+                }
+            }
+            Bytecode::UnpackVariantGeneric(vi_idx) => {
+                let (_, tag) = resolver.variant_instantiation_field_count_and_tag(*vi_idx);
+                let struct_ = interpreter.operand_stack.pop_as::<Struct>()?;
+
+                gas_meter.charge_unpack(true, struct_.field_views())?;
+
+                for value in struct_.unpack_variant(tag)? {
+                    interpreter.operand_stack.push(value)?;
+                }
+            }
+            Bytecode::UnpackVariantImmRef(vh_idx) | Bytecode::UnpackVariantMutRef(vh_idx) => {
+                let (field_count, tag) = resolver.variant_field_count_and_tag(*vh_idx);
+                let instr = match instruction {
+                    Bytecode::UnpackVariantMutRef(_) => S::MutBorrowField,
+                    _ => S::ImmBorrowField,
+                };
+                // Borrowing the fields of a variant is charged as borrowing each of
+                // them individually.
+                for _ in 0..field_count {
+                    gas_meter.charge_simple_instr(instr)?;
+                }
+
+                let reference = interpreter.operand_stack.pop_as::<StructRef>()?;
+                for field_ref in reference.unpack_variant_ref(tag)? {
+                    interpreter.operand_stack.push(field_ref)?;
+                }
+            }
+            Bytecode::UnpackVariantGenericImmRef(vi_idx)
+            | Bytecode::UnpackVariantGenericMutRef(vi_idx) => {
+                let (field_count, tag) =
+                    resolver.variant_instantiation_field_count_and_tag(*vi_idx);
+                let instr = match instruction {
+                    Bytecode::UnpackVariantGenericMutRef(_) => S::MutBorrowFieldGeneric,
+                    _ => S::ImmBorrowFieldGeneric,
+                };
+                for _ in 0..field_count {
+                    gas_meter.charge_simple_instr(instr)?;
+                }
+
+                let reference = interpreter.operand_stack.pop_as::<StructRef>()?;
+                for field_ref in reference.unpack_variant_ref(tag)? {
+                    interpreter.operand_stack.push(field_ref)?;
+                }
+            }
+            Bytecode::VariantSwitch(jt_idx) => {
+                gas_meter.charge_simple_instr(S::VariantSwitch)?;
+                let reference = interpreter.operand_stack.pop_as::<StructRef>()?;
+                let tag = reference.variant_tag()?;
+                let JumpTableInner::Full(offsets) = &function
+                    .jump_tables()
+                    .get(jt_idx.0 as usize)
+                    .ok_or_else(|| {
+                        PartialVMError::new(StatusCode::VERIFIER_INVARIANT_VIOLATION)
+                            .with_message(format!("Missing jump table {}", jt_idx))
+                    })?
+                    .jump_table;
+                *pc = *offsets.get(tag as usize).ok_or_else(|| {
+                    PartialVMError::new(StatusCode::VERIFIER_INVARIANT_VIOLATION).with_message(
+                        format!("Variant tag {} out of bounds of jump table {}", tag, jt_idx),
+                    )
+                })?;
+                return Ok(InstrRet::Branch);
+            }
             Bytecode::ReadRef => {
                 let reference = interpreter.operand_stack.pop_as::<Reference>()?;
                 gas_meter.charge_read_ref(reference.value_view())?;
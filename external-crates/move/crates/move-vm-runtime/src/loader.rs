@@ -19,6 +19,7 @@ use move_binary_format::{
         FieldInstantiationIndex, FunctionDefinition, FunctionDefinitionIndex, FunctionHandleIndex,
         FunctionInstantiationIndex, SignatureIndex, SignatureToken, StructDefInstantiationIndex,
         StructDefinitionIndex, StructFieldInformation, TableIndex, TypeParameterIndex,
+        VariantHandleIndex, VariantInstantiationHandleIndex, VariantJumpTable, VariantTag,
     },
     IndexKind,
 };
@@ -35,7 +36,7 @@ use move_core_types::{
 use move_vm_config::runtime::VMConfig;
 use move_vm_types::{
     data_store::DataStore,
-    loaded_data::runtime_types::{CachedStructIndex, DepthFormula, StructType, Type},
+    loaded_data::runtime_types::{CachedStructIndex, DepthFormula, StructType, Type, VariantType},
 };
 use parking_lot::RwLock;
 use tracing::error;
@@ -127,6 +128,21 @@ struct CacheCursor {
     last_function: usize,
 }
 
+// Sets the field types of a newly loaded type. For enums, `fields` holds the
+// field types of all variants in declaration order, which are split back
+// between the variants.
+fn set_field_types(struct_type: &mut StructType, fields: Vec<Type>) {
+    match &mut struct_type.variants {
+        None => struct_type.fields = fields,
+        Some(variants) => {
+            let mut fields = fields.into_iter();
+            for variant in variants {
+                variant.fields = fields.by_ref().take(variant.field_names.len()).collect();
+            }
+        }
+    }
+}
+
 impl ModuleCache {
     fn new() -> Self {
         Self {
@@ -258,6 +274,7 @@ impl ModuleCache {
                     runtime_id: runtime_id.clone(),
                     struct_def: StructDefinitionIndex(idx as u16),
                     depth: None,
+                    variants: None,
                 },
             )?;
 
@@ -269,6 +286,58 @@ impl ModuleCache {
             field_signatures.push(signatures)
         }
 
+        // Add new enums after the structs. The field signatures of all their variants
+        // are collected into a single list, and split back per variant once they
+        // have been converted into types.
+        for (idx, enum_def) in module.enum_defs().iter().enumerate() {
+            let enum_handle = module.struct_handle_at(enum_def.enum_handle);
+            let name = module.identifier_at(enum_handle.name);
+            let enum_key = (runtime_id.clone(), name.to_owned());
+
+            if self.structs.contains(&enum_key) {
+                continue;
+            }
+
+            let variants = enum_def
+                .variants
+                .iter()
+                .map(|variant| VariantType {
+                    name: module.identifier_at(variant.variant_name).to_owned(),
+                    fields: vec![],
+                    field_names: variant
+                        .fields
+                        .iter()
+                        .map(|f| module.identifier_at(f.name).to_owned())
+                        .collect(),
+                })
+                .collect();
+
+            let defining_id = data_store.defining_module(&runtime_id, name)?;
+
+            self.structs.insert(
+                enum_key,
+                StructType {
+                    fields: vec![],
+                    field_names: vec![],
+                    abilities: enum_handle.abilities,
+                    type_parameters: enum_handle.type_parameters.clone(),
+                    name: name.to_owned(),
+                    defining_id,
+                    runtime_id: runtime_id.clone(),
+                    struct_def: StructDefinitionIndex(idx as u16),
+                    depth: None,
+                    variants: Some(variants),
+                },
+            )?;
+
+            let signatures: Vec<_> = enum_def
+                .variants
+                .iter()
+                .flat_map(|variant| variant.fields.iter().map(|f| &f.signature.0))
+                .collect();
+            field_signatures.push(signatures)
+        }
+
         // Convert field signatures into types after adding all structs because field
         // types might refer to structs in the same module.
         let mut field_types = vec![];
@@ -282,16 +351,16 @@ impl ModuleCache {
 
         let field_types_len = field_types.len();
 
-        // Add the field types to the newly added structs, processing them in reverse,
-        // to line them up with the structs we added at the end of the global
-        // cache.
+        // Add the field types to the newly added structs and enums, processing them in
+        // reverse, to line them up with the types we added at the end of the
+        // global cache.
         for (fields, struct_type) in field_types
             .into_iter()
             .rev()
             .zip(self.structs.binaries.iter_mut().rev())
         {
             match Arc::get_mut(struct_type) {
-                Some(struct_type) => struct_type.fields = fields,
+                Some(struct_type) => set_field_types(struct_type, fields),
                 None => {
                     // we have pending references to the `Arc` which is impossible,
                     // given the code that adds the `Arc` is above and no reference to
@@ -300,7 +369,7 @@ impl ModuleCache {
                     // over and log the issue.
                     error!("Arc<StructType> cannot have any live reference while publishing");
                     let mut struct_copy = (**struct_type).clone();
-                    struct_copy.fields = fields;
+                    set_field_types(&mut struct_copy, fields);
                     *struct_type = Arc::new(struct_copy);
                 }
             }
@@ -410,8 +479,7 @@ impl ModuleCache {
         }
 
         let formulas = struct_type
-            .fields
-            .iter()
+            .all_field_types()
             .map(|field_type| self.calculate_depth_of_type(field_type, depth_cache))
             .collect::<PartialVMResult<Vec<_>>>()?;
         let mut formula = DepthFormula::normalize(formulas);
@@ -1479,11 +1547,36 @@ impl<'a> Resolver<'a> {
         &self,
         idx: StructDefInstantiationIndex,
         ty_args: &[Type],
+    ) -> PartialVMResult<Type> {
+        let struct_inst = self.binary.loaded.struct_instantiation_at(idx.0);
+        self.instantiate_type_common(struct_inst.def, struct_inst.instantiation_idx, ty_args)
+    }
+
+    pub(crate) fn get_enum_type(&self, idx: VariantHandleIndex) -> Type {
+        Type::Struct(self.binary.loaded.variant_handle_at(idx).enum_idx)
+    }
+
+    pub(crate) fn instantiate_generic_enum(
+        &self,
+        idx: VariantInstantiationHandleIndex,
+        ty_args: &[Type],
     ) -> PartialVMResult<Type> {
         let loaded_module = &*self.binary.loaded;
-        let struct_inst = loaded_module.struct_instantiation_at(idx.0);
-        let instantiation =
-            loaded_module.instantiation_signature_at(struct_inst.instantiation_idx)?;
+        let variant_inst = loaded_module.variant_instantiation_handle_at(idx);
+        let enum_inst = loaded_module.enum_instantiation_at(variant_inst.enum_inst);
+        self.instantiate_type_common(enum_inst.def, enum_inst.instantiation_idx, ty_args)
+    }
+
+    fn instantiate_type_common(
+        &self,
+        def: CachedStructIndex,
+        instantiation_idx: SignatureIndex,
+        ty_args: &[Type],
+    ) -> PartialVMResult<Type> {
+        let instantiation = self
+            .binary
+            .loaded
+            .instantiation_signature_at(instantiation_idx)?;
 
         // Before instantiating the type, count the # of nodes of all type arguments
         // plus existing type instantiation.
@@ -1499,7 +1592,7 @@ impl<'a> Resolver<'a> {
         }
 
         Ok(Type::StructInstantiation(Box::new((
-            struct_inst.def,
+            def,
             instantiation
                 .iter()
                 .map(|ty| self.subst(ty, ty_args))
@@ -1547,6 +1640,22 @@ impl<'a> Resolver<'a> {
         self.binary.loaded.field_instantiation_count(idx.0)
     }
 
+    // Variants resolution
+    //
+
+    pub(crate) fn variant_field_count_and_tag(&self, idx: VariantHandleIndex) -> (u16, VariantTag) {
+        let handle = self.binary.loaded.variant_handle_at(idx);
+        (handle.field_count, handle.tag)
+    }
+
+    pub(crate) fn variant_instantiation_field_count_and_tag(
+        &self,
+        idx: VariantInstantiationHandleIndex,
+    ) -> (u16, VariantTag) {
+        let handle = self.binary.loaded.variant_instantiation_handle_at(idx);
+        (handle.field_count, handle.tag)
+    }
+
     pub(crate) fn type_to_type_layout(&self, ty: &Type) -> PartialVMResult<R::MoveTypeLayout> {
         self.loader.type_to_type_layout(ty)
     }
@@ -1586,6 +1695,16 @@ pub(crate) struct LoadedModule {
     // materialized instantiations, whether partial or not
     struct_instantiations: Vec<StructInstantiation>,
 
+    // enums, like structs, as indexes into the global vector of types
+    enums: Vec<CachedStructIndex>,
+    // materialized instantiations, whether partial or not
+    enum_instantiations: Vec<EnumInstantiation>,
+
+    // variants as the enum they belong to, their tag and their field count
+    variant_handles: Vec<VariantHandle>,
+    // materialized instantiations, whether partial or not
+    variant_instantiation_handles: Vec<VariantInstantiationHandle>,
+
     // functions as indexes into the Loader function list
     // That is effectively an indirection over the ref table:
     // the instruction carries an index into this table which contains the index into the
@@ -1648,6 +1767,10 @@ impl LoadedModule {
         let mut struct_refs = vec![];
         let mut structs = vec![];
         let mut struct_instantiations = vec![];
+        let mut enums = vec![];
+        let mut enum_instantiations = vec![];
+        let mut variant_handles = vec![];
+        let mut variant_instantiation_handles = vec![];
         let mut function_refs = vec![];
         let mut function_instantiations = vec![];
         let mut field_handles = vec![];
@@ -1687,6 +1810,51 @@ impl LoadedModule {
             });
         }
 
+        for enum_def in module.enum_defs() {
+            enums.push(struct_refs[enum_def.enum_handle.0 as usize]);
+        }
+
+        for enum_inst in module.enum_instantiations() {
+            let instantiation_idx = enum_inst.type_parameters;
+            cache_signatures(
+                &mut instantiation_signatures,
+                module,
+                instantiation_idx,
+                cache,
+            )?;
+            enum_instantiations.push(EnumInstantiation {
+                def: enums[enum_inst.def.0 as usize],
+                instantiation_idx,
+            });
+        }
+
+        // Number of fields of the variant `tag` of the enum at `enum_def` in this
+        // module.
+        let variant_field_count = |enum_def: usize, tag: VariantTag| {
+            module.enum_defs()[enum_def].variants[tag as usize]
+                .fields
+                .len() as u16
+        };
+
+        for variant_handle in module.variant_handles() {
+            let enum_def = variant_handle.enum_def.0 as usize;
+            variant_handles.push(VariantHandle {
+                enum_idx: enums[enum_def],
+                tag: variant_handle.variant,
+                field_count: variant_field_count(enum_def, variant_handle.variant),
+            });
+        }
+
+        for variant_inst in module.variant_instantiation_handles() {
+            let enum_inst = variant_inst.enum_def.0;
+            let enum_def = module.enum_instantiations()[enum_inst as usize].def.0 as usize;
+            variant_instantiation_handles.push(VariantInstantiationHandle {
+                enum_inst,
+                tag: variant_inst.variant,
+                field_count: variant_field_count(enum_def, variant_inst.variant),
+            });
+        }
+
         for func_handle in module.function_handles() {
             let func_name = module.identifier_at(func_handle.name);
             let module_handle = module.module_handle_at(func_handle.module);
@@ -1792,6 +1960,10 @@ impl LoadedModule {
             struct_refs,
             structs,
             struct_instantiations,
+            enums,
+            enum_instantiations,
+            variant_handles,
+            variant_instantiation_handles,
             function_refs,
             function_instantiations,
             field_handles,
@@ -1810,6 +1982,21 @@ impl LoadedModule {
         &self.struct_instantiations[idx as usize]
     }
 
+    fn enum_instantiation_at(&self, idx: u16) -> &EnumInstantiation {
+        &self.enum_instantiations[idx as usize]
+    }
+
+    fn variant_handle_at(&self, idx: VariantHandleIndex) -> &VariantHandle {
+        &self.variant_handles[idx.0 as usize]
+    }
+
+    fn variant_instantiation_handle_at(
+        &self,
+        idx: VariantInstantiationHandleIndex,
+    ) -> &VariantInstantiationHandle {
+        &self.variant_instantiation_handles[idx.0 as usize]
+    }
+
     fn function_at(&self, idx: u16) -> usize {
         self.function_refs[idx as usize]
     }
@@ -1857,6 +2044,7 @@ pub(crate) struct Function {
     file_format_version: u32,
     index: FunctionDefinitionIndex,
     code: Vec<Bytecode>,
+    jump_tables: Vec<VariantJumpTable>,
     parameters: SignatureIndex,
     return_: SignatureIndex,
     type_parameters: Vec<AbilitySet>,
@@ -1894,12 +2082,13 @@ impl Function {
         let parameters = handle.parameters;
         let parameters_len = module.signature_at(parameters).0.len();
         // Native functions do not have a code unit
-        let (code, locals_len) = match &def.code {
+        let (code, jump_tables, locals_len) = match &def.code {
             Some(code) => (
                 code.code.clone(),
+                code.jump_tables.clone(),
                 parameters_len + module.signature_at(code.locals).0.len(),
             ),
-            None => (vec![], 0),
+            None => (vec![], vec![], 0),
         };
         let return_ = handle.return_;
         let return_len = module.signature_at(return_).0.len();
@@ -1908,6 +2097,7 @@ impl Function {
             file_format_version: module.version(),
             index,
             code,
+            jump_tables,
             parameters,
             return_,
             type_parameters,
@@ -1963,6 +2153,10 @@ impl Function {
         &self.code
     }
 
+    pub(crate) fn jump_tables(&self) -> &[VariantJumpTable] {
+        &self.jump_tables
+    }
+
     pub(crate) fn type_parameters(&self) -> &[AbilitySet] {
         &self.type_parameters
     }
@@ -2043,6 +2237,33 @@ struct StructInstantiation {
     instantiation_idx: SignatureIndex,
 }
 
+#[derive(Debug)]
+struct EnumInstantiation {
+    // `ModuleCache::structs` global table index. It is the generic type.
+    def: CachedStructIndex,
+    instantiation_idx: SignatureIndex,
+}
+
+// A variant handle. Packing and unpacking a variant only needs its tag and the
+// number of fields it carries.
+#[derive(Debug)]
+struct VariantHandle {
+    // `ModuleCache::structs` global table index of the enum.
+    enum_idx: CachedStructIndex,
+    tag: VariantTag,
+    field_count: u16,
+}
+
+// A variant instantiation handle, the variant of a (possibly partially)
+// instantiated generic enum.
+#[derive(Debug)]
+struct VariantInstantiationHandle {
+    // index into the module's `enum_instantiations`
+    enum_inst: u16,
+    tag: VariantTag,
+    field_count: u16,
+}
+
 // A field handle. The offset is the only used information when operating on a
 // field
 #[derive(Debug)]
@@ -2068,8 +2289,9 @@ struct FieldInstantiation {
 struct StructInfo {
     runtime_struct_tag: Option<StructTag>,
     defining_struct_tag: Option<StructTag>,
-    struct_layout: Option<R::MoveStructLayout>,
-    annotated_struct_layout: Option<A::MoveStructLayout>,
+    // layouts of structs and enums
+    struct_layout: Option<R::MoveTypeLayout>,
+    annotated_struct_layout: Option<A::MoveTypeLayout>,
     node_count: Option<u64>,
     annotated_node_count: Option<u64>,
 }
@@ -2250,7 +2472,7 @@ impl Loader {
         ty_args: &[Type],
         count: &mut u64,
         depth: u64,
-    ) -> PartialVMResult<R::MoveStructLayout> {
+    ) -> PartialVMResult<R::MoveTypeLayout> {
         if let Some(struct_map) = self.type_cache.read().structs.get(&gidx) {
             if let Some(struct_info) = struct_map.get(ty_args) {
                 if let Some(node_count) = &struct_info.node_count {
@@ -2264,19 +2486,28 @@ impl Loader {
 
         let count_before = *count;
         let struct_type = self.module_cache.read().struct_at(gidx);
-        let field_tys = struct_type
-            .fields
-            .iter()
-            .map(|ty| self.subst(ty, ty_args))
-            .collect::<PartialVMResult<Vec<_>>>()?;
-        let field_layouts = field_tys
-            .iter()
-            .map(|ty| self.type_to_type_layout_impl(ty, count, depth + 1))
-            .collect::<PartialVMResult<Vec<_>>>()?;
+        let mut field_layouts = |fields: &[Type]| {
+            fields
+                .iter()
+                .map(|ty| {
+                    let ty = self.subst(ty, ty_args)?;
+                    self.type_to_type_layout_impl(&ty, count, depth + 1)
+                })
+                .collect::<PartialVMResult<Vec<_>>>()
+        };
+        let struct_layout = match &struct_type.variants {
+            None => R::MoveTypeLayout::Struct(R::MoveStructLayout::new(field_layouts(
+                &struct_type.fields,
+            )?)),
+            Some(variants) => R::MoveTypeLayout::Enum(R::MoveEnumLayout::new(
+                variants
+                    .iter()
+                    .map(|variant| field_layouts(&variant.fields))
+                    .collect::<PartialVMResult<_>>()?,
+            )),
+        };
         let field_node_count = *count - count_before;
 
-        let struct_layout = R::MoveStructLayout::new(field_layouts);
-
         let mut cache = self.type_cache.write();
         let info = cache
             .structs
@@ -2316,17 +2547,10 @@ impl Loader {
             Type::Vector(ty) => R::MoveTypeLayout::Vector(Box::new(
                 self.type_to_type_layout_impl(ty, count, depth + 1)?,
             )),
-            Type::Struct(gidx) => R::MoveTypeLayout::Struct(self.struct_gidx_to_type_layout(
-                *gidx,
-                &[],
-                count,
-                depth,
-            )?),
+            Type::Struct(gidx) => self.struct_gidx_to_type_layout(*gidx, &[], count, depth)?,
             Type::StructInstantiation(struct_inst) => {
                 let (gidx, ty_args) = &**struct_inst;
-                R::MoveTypeLayout::Struct(
-                    self.struct_gidx_to_type_layout(*gidx, ty_args, count, depth)?,
-                )
+                self.struct_gidx_to_type_layout(*gidx, ty_args, count, depth)?
             }
             Type::Reference(_) | Type::MutableReference(_) | Type::TyParam(_) => {
                 return Err(
@@ -2343,7 +2567,7 @@ impl Loader {
         ty_args: &[Type],
         count: &mut u64,
         depth: u64,
-    ) -> PartialVMResult<A::MoveStructLayout> {
+    ) -> PartialVMResult<A::MoveTypeLayout> {
         if let Some(struct_map) = self.type_cache.read().structs.get(&gidx) {
             if let Some(struct_info) = struct_map.get(ty_args) {
                 if let Some(annotated_node_count) = &struct_info.annotated_node_count {
@@ -2356,27 +2580,46 @@ impl Loader {
         }
 
         let struct_type = self.module_cache.read().struct_at(gidx);
-        if struct_type.fields.len() != struct_type.field_names.len() {
-            return Err(
-                PartialVMError::new(StatusCode::UNKNOWN_INVARIANT_VIOLATION_ERROR).with_message(
-                    "Field types did not match the length of field names in loaded struct"
-                        .to_owned(),
-                ),
-            );
-        }
         let count_before = *count;
         let struct_tag = self.struct_gidx_to_type_tag(gidx, ty_args, StructTagType::Defining)?;
-        let field_layouts = struct_type
-            .field_names
-            .iter()
-            .zip(&struct_type.fields)
-            .map(|(n, ty)| {
-                let ty = self.subst(ty, ty_args)?;
-                let l = self.type_to_fully_annotated_layout_impl(&ty, count, depth + 1)?;
-                Ok(A::MoveFieldLayout::new(n.clone(), l))
-            })
-            .collect::<PartialVMResult<Vec<_>>>()?;
-        let struct_layout = A::MoveStructLayout::new(struct_tag, field_layouts);
+        let mut field_layouts = |field_names: &[Identifier], fields: &[Type]| {
+            if fields.len() != field_names.len() {
+                return Err(
+                    PartialVMError::new(StatusCode::UNKNOWN_INVARIANT_VIOLATION_ERROR)
+                        .with_message(
+                            "Field types did not match the length of field names in loaded type"
+                                .to_owned(),
+                        ),
+                );
+            }
+            field_names
+                .iter()
+                .zip(fields)
+                .map(|(n, ty)| {
+                    let ty = self.subst(ty, ty_args)?;
+                    let l = self.type_to_fully_annotated_layout_impl(&ty, count, depth + 1)?;
+                    Ok(A::MoveFieldLayout::new(n.clone(), l))
+                })
+                .collect::<PartialVMResult<Vec<_>>>()
+        };
+        let struct_layout = match &struct_type.variants {
+            None => A::MoveTypeLayout::Struct(A::MoveStructLayout::new(
+                struct_tag,
+                field_layouts(&struct_type.field_names, &struct_type.fields)?,
+            )),
+            Some(variants) => A::MoveTypeLayout::Enum(A::MoveEnumLayout::new(
+                struct_tag,
+                variants
+                    .iter()
+                    .map(|variant| {
+                        Ok(A::MoveVariantLayout {
+                            name: variant.name.clone(),
+                            fields: field_layouts(&variant.field_names, &variant.fields)?,
+                        })
+                    })
+                    .collect::<PartialVMResult<_>>()?,
+            )),
+        };
         let field_node_count = *count - count_before;
 
         let mut cache = self.type_cache.write();
@@ -2418,14 +2661,12 @@ impl Loader {
             Type::Vector(ty) => A::MoveTypeLayout::Vector(Box::new(
                 self.type_to_fully_annotated_layout_impl(ty, count, depth + 1)?,
             )),
-            Type::Struct(gidx) => A::MoveTypeLayout::Struct(
-                self.struct_gidx_to_fully_annotated_layout(*gidx, &[], count, depth)?,
-            ),
+            Type::Struct(gidx) => {
+                self.struct_gidx_to_fully_annotated_layout(*gidx, &[], count, depth)?
+            }
             Type::StructInstantiation(struct_inst) => {
                 let (gidx, ty_args) = &**struct_inst;
-                A::MoveTypeLayout::Struct(
-                    self.struct_gidx_to_fully_annotated_layout(*gidx, ty_args, count, depth)?,
-                )
+                self.struct_gidx_to_fully_annotated_layout(*gidx, ty_args, count, depth)?
             }
             Type::Reference(_) | Type::MutableReference(_) | Type::TyParam(_) => {
                 return Err(
@@ -108,8 +108,13 @@ fn make_module_with_function(
             code: Some(CodeUnit {
                 locals: SignatureIndex(0),
                 code: vec![Bytecode::LdU64(0), Bytecode::Abort],
+                jump_tables: vec![],
             }),
         }],
+        enum_defs: vec![],
+        enum_def_instantiations: vec![],
+        variant_handles: vec![],
+        variant_instantiation_handles: vec![],
     };
     (module, function_name)
 }
@@ -68,6 +68,8 @@ pub enum SimpleInstruction {
     CastU16,
     CastU32,
     CastU256,
+
+    VariantSwitch,
 }
 
 /// Trait that defines a generic gas meter interface, allowing clients of the
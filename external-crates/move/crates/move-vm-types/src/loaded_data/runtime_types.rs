@@ -139,14 +139,41 @@ pub struct StructType {
     pub name: Identifier,
     pub defining_id: ModuleId,
     pub runtime_id: ModuleId,
+    /// Index of the definition in its module: into the struct definitions for
+    /// structs, and into the enum definitions for enums.
     pub struct_def: StructDefinitionIndex,
     pub depth: Option<DepthFormula>,
+    /// The variants of the type if it is an enum, `None` for structs. Enums
+    /// have no `fields` of their own.
+    pub variants: Option<Vec<VariantType>>,
+}
+
+#[derive(Debug, Clone, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct VariantType {
+    pub name: Identifier,
+    pub fields: Vec<Type>,
+    pub field_names: Vec<Identifier>,
 }
 
 impl StructType {
     pub fn type_param_constraints(&self) -> impl ExactSizeIterator<Item = &AbilitySet> {
         self.type_parameters.iter().map(|param| &param.constraints)
     }
+
+    pub fn is_enum(&self) -> bool {
+        self.variants.is_some()
+    }
+
+    /// The types of all fields of the type, including the fields of every
+    /// variant for enums.
+    pub fn all_field_types(&self) -> impl Iterator<Item = &Type> {
+        self.fields.iter().chain(
+            self.variants
+                .iter()
+                .flatten()
+                .flat_map(|variant| variant.fields.iter()),
+        )
+    }
 }
 
 #[derive(Debug, Copy, Clone, Eq, Hash, Ord, PartialEq, PartialOrd)]
@@ -4,7 +4,12 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use move_binary_format::errors::*;
-use move_core_types::{account_address::AccountAddress, u256::U256};
+use move_core_types::{
+    account_address::AccountAddress,
+    runtime_value::{MoveEnumLayout, MoveTypeLayout},
+    u256::U256,
+    vm_status::StatusCode,
+};
 
 use crate::{loaded_data::runtime_types::Type, values::*, views::*};
 
@@ -127,6 +132,70 @@ fn struct_borrow_nested() -> PartialVMResult<()> {
     Ok(())
 }
 
+#[test]
+fn variant_pack_and_unpack() -> PartialVMResult<()> {
+    let v = Struct::pack_variant(1, vec![Value::u64(20), Value::bool(true)]);
+    let unpacked: Vec<_> = v.unpack_variant(1)?.collect();
+    assert_eq!(unpacked.len(), 2);
+    assert!(unpacked[0].equals(&Value::u64(20))?);
+    assert!(unpacked[1].equals(&Value::bool(true))?);
+
+    let v = Struct::pack_variant(1, vec![Value::u64(20)]);
+    let err = v.unpack_variant(0).err().unwrap();
+    assert_eq!(err.major_status(), StatusCode::VARIANT_TAG_MISMATCH);
+
+    Ok(())
+}
+
+#[test]
+fn variant_borrow_fields() -> PartialVMResult<()> {
+    let mut locals = Locals::new(1);
+    locals.store_loc(
+        0,
+        Value::struct_(Struct::pack_variant(2, vec![Value::u8(10)])),
+        true,
+    )?;
+    let r: StructRef = locals.borrow_loc(0)?.value_as()?;
+    assert_eq!(r.variant_tag()?, 2);
+
+    {
+        let fields = r.unpack_variant_ref(2)?;
+        assert_eq!(fields.len(), 1);
+        let f: Reference = fields.into_iter().next().unwrap().value_as()?;
+        f.write_ref(Value::u8(11))?;
+    }
+
+    let err = r.unpack_variant_ref(0).err().unwrap();
+    assert_eq!(err.major_status(), StatusCode::VARIANT_TAG_MISMATCH);
+
+    let v: Struct = locals.move_loc(0, true)?.value_as()?;
+    let unpacked: Vec<_> = v.unpack_variant(2)?.collect();
+    assert!(unpacked[0].equals(&Value::u8(11))?);
+
+    Ok(())
+}
+
+#[test]
+fn variant_serialization() {
+    let layout = MoveTypeLayout::Enum(MoveEnumLayout::new(vec![
+        vec![],
+        vec![MoveTypeLayout::U64, MoveTypeLayout::Bool],
+    ]));
+    let v = Value::struct_(Struct::pack_variant(
+        1,
+        vec![Value::u64(7), Value::bool(true)],
+    ));
+
+    let blob = v.simple_serialize(&layout).unwrap();
+    assert_eq!(blob, vec![1, 7, 0, 0, 0, 0, 0, 0, 0, 1]);
+
+    let roundtrip = Value::simple_deserialize(&blob, &layout).unwrap();
+    assert!(roundtrip.equals(&v).unwrap());
+
+    // The tag is out of bounds for the layout
+    assert!(Value::simple_deserialize(&[2], &layout).is_none());
+}
+
 #[test]
 fn global_value_non_struct() -> PartialVMResult<()> {
     assert!(GlobalValue::cached(Value::u64(100)).is_err());
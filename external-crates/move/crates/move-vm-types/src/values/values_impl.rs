@@ -12,13 +12,13 @@ use std::{
 
 use move_binary_format::{
     errors::*,
-    file_format::{Constant, SignatureToken},
+    file_format::{Constant, SignatureToken, VariantTag},
 };
 use move_core_types::{
     account_address::AccountAddress,
     effects::Op,
     gas_algebra::AbstractMemorySize,
-    runtime_value::{MoveStructLayout, MoveTypeLayout},
+    runtime_value::{MoveEnumLayout, MoveStructLayout, MoveTypeLayout, MOVE_ENUM_NAME},
     u256,
     vm_status::{sub_status::NFE_VECTOR_ERROR_BASE, StatusCode},
 };
@@ -944,6 +944,36 @@ impl StructRef {
     pub fn borrow_field(&self, idx: usize) -> PartialVMResult<Value> {
         Ok(Value(self.0.borrow_elem(idx)?))
     }
+
+    /// Reads the tag of the enum variant behind this reference.
+    pub fn variant_tag(&self) -> PartialVMResult<VariantTag> {
+        match self.0.container() {
+            Container::Struct(r) => match r.borrow().first() {
+                Some(ValueImpl::U16(tag)) => Ok(*tag),
+                v => Err(
+                    PartialVMError::new(StatusCode::UNKNOWN_INVARIANT_VIOLATION_ERROR)
+                        .with_message(format!("expected a variant tag, got {:?}", v)),
+                ),
+            },
+            c => Err(
+                PartialVMError::new(StatusCode::UNKNOWN_INVARIANT_VIOLATION_ERROR)
+                    .with_message(format!("expected a variant, got {:?}", c)),
+            ),
+        }
+    }
+
+    /// Borrows the fields of the enum variant behind this reference, checking
+    /// that the variant has the expected tag.
+    pub fn unpack_variant_ref(&self, tag: VariantTag) -> PartialVMResult<Vec<Value>> {
+        let actual = self.variant_tag()?;
+        if actual != tag {
+            return Err(PartialVMError::new(StatusCode::VARIANT_TAG_MISMATCH)
+                .with_message(format!("expected variant tag {}, got {}", tag, actual)));
+        }
+        (1..self.0.container().len())
+            .map(|idx| self.borrow_field(idx))
+            .collect()
+    }
 }
 
 impl Locals {
@@ -2484,6 +2514,28 @@ impl Struct {
     pub fn unpack(self) -> PartialVMResult<impl Iterator<Item = Value>> {
         Ok(self.fields.into_iter().map(Value))
     }
+
+    /// Packs an enum variant. Variants are represented as structs whose first
+    /// field holds the variant tag, followed by the fields of the variant.
+    pub fn pack_variant<I: IntoIterator<Item = Value>>(tag: VariantTag, vals: I) -> Self {
+        Self::pack(iter::once(Value::u16(tag)).chain(vals))
+    }
+
+    /// Unpacks an enum variant, checking that it has the expected tag.
+    pub fn unpack_variant(self, tag: VariantTag) -> PartialVMResult<impl Iterator<Item = Value>> {
+        let mut fields = self.fields.into_iter();
+        match fields.next() {
+            Some(ValueImpl::U16(actual)) if actual == tag => Ok(fields.map(Value)),
+            Some(ValueImpl::U16(actual)) => {
+                Err(PartialVMError::new(StatusCode::VARIANT_TAG_MISMATCH)
+                    .with_message(format!("expected variant tag {}, got {}", tag, actual)))
+            }
+            v => Err(
+                PartialVMError::new(StatusCode::UNKNOWN_INVARIANT_VIOLATION_ERROR)
+                    .with_message(format!("expected a variant tag, got {:?}", v)),
+            ),
+        }
+    }
 }
 
 /// ****************************************************************************
@@ -2988,7 +3040,7 @@ pub mod debug {
 /// *******
 use serde::{
     de::Error as DeError,
-    ser::{Error as SerError, SerializeSeq, SerializeTuple},
+    ser::{Error as SerError, SerializeSeq, SerializeTuple, SerializeTupleVariant},
     Deserialize,
 };
 
@@ -3051,6 +3103,14 @@ impl<'a, 'b> serde::Serialize for AnnotatedValue<'a, 'b, MoveTypeLayout, ValueIm
                 .serialize(serializer)
             }
 
+            (MoveTypeLayout::Enum(enum_layout), ValueImpl::Container(Container::Struct(r))) => {
+                (AnnotatedValue {
+                    layout: enum_layout,
+                    val: &*r.borrow(),
+                })
+                .serialize(serializer)
+            }
+
             (MoveTypeLayout::Vector(layout), ValueImpl::Container(c)) => {
                 let layout = &**layout;
                 match (layout, c) {
@@ -3131,6 +3191,41 @@ impl<'a, 'b> serde::Serialize for AnnotatedValue<'a, 'b, MoveStructLayout, Vec<V
     }
 }
 
+impl<'a, 'b> serde::Serialize for AnnotatedValue<'a, 'b, MoveEnumLayout, Vec<ValueImpl>> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let (tag, values) = match self.val.split_first() {
+            Some((ValueImpl::U16(tag), values)) => (*tag, values),
+            _ => {
+                return Err(invariant_violation::<S>(format!(
+                    "cannot serialize enum value {:?} as {:?} -- missing variant tag",
+                    self.val, self.layout
+                )));
+            }
+        };
+        let Some(fields) = self.layout.variants().get(tag as usize) else {
+            return Err(invariant_violation::<S>(format!(
+                "cannot serialize enum value {:?} as {:?} -- invalid variant tag",
+                self.val, self.layout
+            )));
+        };
+        if fields.len() != values.len() {
+            return Err(invariant_violation::<S>(format!(
+                "cannot serialize enum value {:?} as {:?} -- number of fields mismatch",
+                self.val, self.layout
+            )));
+        }
+        let mut t =
+            serializer.serialize_tuple_variant(MOVE_ENUM_NAME, tag as u32, "", values.len())?;
+        for (field_layout, val) in fields.iter().zip(values.iter()) {
+            t.serialize_field(&AnnotatedValue {
+                layout: field_layout,
+                val,
+            })?;
+        }
+        t.end()
+    }
+}
+
 #[derive(Clone)]
 struct SeedWrapper<L> {
     layout: L,
@@ -3163,6 +3258,13 @@ impl<'d> serde::de::DeserializeSeed<'d> for SeedWrapper<&MoveTypeLayout> {
                 .deserialize(deserializer)?,
             )),
 
+            L::Enum(enum_layout) => Ok(Value::struct_(
+                SeedWrapper {
+                    layout: enum_layout,
+                }
+                .deserialize(deserializer)?,
+            )),
+
             L::Vector(layout) => {
                 let container = match &**layout {
                     L::U8 => {
@@ -3215,6 +3317,17 @@ impl<'d> serde::de::DeserializeSeed<'d> for SeedWrapper<&MoveStructLayout> {
     }
 }
 
+impl<'d> serde::de::DeserializeSeed<'d> for SeedWrapper<&MoveEnumLayout> {
+    type Value = Struct;
+
+    fn deserialize<D: serde::de::Deserializer<'d>>(
+        self,
+        deserializer: D,
+    ) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_enum(MOVE_ENUM_NAME, &[], EnumVariantVisitor(self.layout))
+    }
+}
+
 struct VectorElementVisitor<'a>(SeedWrapper<&'a MoveTypeLayout>);
 
 impl<'d, 'a> serde::de::Visitor<'d> for VectorElementVisitor<'a> {
@@ -3263,6 +3376,33 @@ impl<'d, 'a> serde::de::Visitor<'d> for StructFieldVisitor<'a> {
     }
 }
 
+struct EnumVariantVisitor<'a>(&'a MoveEnumLayout);
+
+impl<'d, 'a> serde::de::Visitor<'d> for EnumVariantVisitor<'a> {
+    type Value = Struct;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("Enum")
+    }
+
+    fn visit_enum<A>(self, data: A) -> Result<Self::Value, A::Error>
+    where
+        A: serde::de::EnumAccess<'d>,
+    {
+        use serde::de::VariantAccess;
+
+        let (tag, variant) = data.variant::<u32>()?;
+        let Some(fields) = self.0.variants().get(tag as usize) else {
+            return Err(A::Error::invalid_value(
+                serde::de::Unexpected::Unsigned(tag as u64),
+                &self,
+            ));
+        };
+        let fields = variant.tuple_variant(fields.len(), StructFieldVisitor(fields))?;
+        Ok(Struct::pack_variant(tag as VariantTag, fields))
+    }
+}
+
 /// ****************************************************************************
 /// *********
 ///
@@ -3681,6 +3821,21 @@ pub mod prop {
                 .collect::<Vec<_>>()
                 .prop_map(move |vals| Value::struct_(Struct::pack(vals)))
                 .boxed(),
+
+            L::Enum(enum_layout) => {
+                let variants = enum_layout.variants().to_vec();
+                (0..variants.len())
+                    .prop_flat_map(move |tag| {
+                        variants[tag]
+                            .iter()
+                            .map(value_strategy_with_layout)
+                            .collect::<Vec<_>>()
+                            .prop_map(move |vals| {
+                                Value::struct_(Struct::pack_variant(tag as VariantTag, vals))
+                            })
+                    })
+                    .boxed()
+            }
         }
     }
 
@@ -3702,8 +3857,10 @@ pub mod prop {
         leaf.prop_recursive(8, 32, 2, |inner| {
             prop_oneof![
                 1 => inner.clone().prop_map(|layout| L::Vector(Box::new(layout))),
-                1 => vec(inner, 0..1).prop_map(|f_layouts| {
+                1 => vec(inner.clone(), 0..1).prop_map(|f_layouts| {
                      L::Struct(MoveStructLayout::new(f_layouts))}),
+                1 => vec(vec(inner, 0..1), 1..3).prop_map(|v_layouts| {
+                     L::Enum(MoveEnumLayout::new(v_layouts))}),
             ]
         })
     }
@@ -3716,7 +3873,7 @@ pub mod prop {
     }
 }
 
-use move_core_types::runtime_value::{MoveStruct, MoveValue};
+use move_core_types::runtime_value::{MoveStruct, MoveValue, MoveVariant};
 
 impl ValueImpl {
     pub fn as_move_value(&self, layout: &MoveTypeLayout) -> MoveValue {
@@ -3740,6 +3897,21 @@ impl ValueImpl {
                 MoveValue::Struct(MoveStruct::new(fields))
             }
 
+            (L::Enum(enum_layout), ValueImpl::Container(Container::Struct(r))) => {
+                let r = r.borrow();
+                let Some((ValueImpl::U16(tag), values)) = r.split_first() else {
+                    panic!("Unexpected variant without a tag: {:?}", r);
+                };
+                let mut fields = vec![];
+                for (v, field_layout) in values
+                    .iter()
+                    .zip(enum_layout.variants()[*tag as usize].iter())
+                {
+                    fields.push(v.as_move_value(field_layout));
+                }
+                MoveValue::Variant(MoveVariant::new(*tag, fields))
+            }
+
             (L::Vector(inner_layout), ValueImpl::Container(c)) => MoveValue::Vector(match c {
                 Container::VecU8(r) => r.borrow().iter().map(|u| MoveValue::U8(*u)).collect(),
                 Container::VecU16(r) => r.borrow().iter().map(|u| MoveValue::U16(*u)).collect(),
@@ -151,6 +151,17 @@ impl<'a> InstructionConsistency<'a> {
                 | Or | And | Not | Eq | Neq | Lt | Gt | Le | Ge | CopyLoc(_) | MoveLoc(_)
                 | StLoc(_) | MutBorrowLoc(_) | ImmBorrowLoc(_) | VecLen(_) | VecImmBorrow(_)
                 | VecMutBorrow(_) | VecPushBack(_) | VecPopBack(_) | VecSwap(_) | Abort | Nop => (),
+
+                // Enums are rejected by the limits verifier before reaching this point.
+                PackVariant(_)
+                | PackVariantGeneric(_)
+                | UnpackVariant(_)
+                | UnpackVariantImmRef(_)
+                | UnpackVariantMutRef(_)
+                | UnpackVariantGeneric(_)
+                | UnpackVariantGenericImmRef(_)
+                | UnpackVariantGenericMutRef(_)
+                | VariantSwitch(_) => (),
             }
         }
         Ok(())
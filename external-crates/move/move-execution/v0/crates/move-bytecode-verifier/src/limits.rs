@@ -6,7 +6,8 @@ use move_binary_format::{
     binary_views::BinaryIndexedView,
     errors::{verification_error, Location, PartialVMError, PartialVMResult, VMResult},
     file_format::{
-        CompiledModule, CompiledScript, SignatureToken, StructFieldInformation, TableIndex,
+        Bytecode, CompiledModule, CompiledScript, SignatureToken, StructFieldInformation,
+        TableIndex,
     },
     IndexKind,
 };
@@ -30,6 +31,7 @@ impl<'a> LimitsVerifier<'a> {
         let limit_check = Self {
             resolver: BinaryIndexedView::Module(module),
         };
+        Self::verify_no_enums(module)?;
         limit_check.verify_constants(config)?;
         limit_check.verify_function_handles(config)?;
         limit_check.verify_struct_handles(config)?;
@@ -54,6 +56,42 @@ impl<'a> LimitsVerifier<'a> {
         limit_check.verify_type_nodes(config)
     }
 
+    /// Enums are not supported by this version of the verifier, whatever the
+    /// configuration says.
+    fn verify_no_enums(module: &CompiledModule) -> PartialVMResult<()> {
+        let uses_variants = module
+            .function_defs
+            .iter()
+            .filter_map(|def| def.code.as_ref())
+            .any(|code| {
+                !code.jump_tables.is_empty()
+                    || code.code.iter().any(|instr| {
+                        matches!(
+                            instr,
+                            Bytecode::PackVariant(_)
+                                | Bytecode::PackVariantGeneric(_)
+                                | Bytecode::UnpackVariant(_)
+                                | Bytecode::UnpackVariantImmRef(_)
+                                | Bytecode::UnpackVariantMutRef(_)
+                                | Bytecode::UnpackVariantGeneric(_)
+                                | Bytecode::UnpackVariantGenericImmRef(_)
+                                | Bytecode::UnpackVariantGenericMutRef(_)
+                                | Bytecode::VariantSwitch(_)
+                        )
+                    })
+            });
+        if !module.enum_defs.is_empty()
+            || !module.enum_def_instantiations.is_empty()
+            || !module.variant_handles.is_empty()
+            || !module.variant_instantiation_handles.is_empty()
+            || uses_variants
+        {
+            return Err(PartialVMError::new(StatusCode::FEATURE_UNDER_GATING)
+                .with_message("enums are not supported".to_string()));
+        }
+        Ok(())
+    }
+
     fn verify_struct_handles(&self, config: &VerifierConfig) -> PartialVMResult<()> {
         if let Some(limit) = config.max_generic_instantiation_length {
             for (idx, struct_handle) in self.resolver.struct_handles().iter().enumerate() {
@@ -269,6 +269,15 @@ impl<'a> SignatureChecker<'a> {
                 | ExistsDeprecated(_)
                 | MoveToDeprecated(_)
                 | MoveFromDeprecated(_)
+                | PackVariant(_)
+                | PackVariantGeneric(_)
+                | UnpackVariant(_)
+                | UnpackVariantImmRef(_)
+                | UnpackVariantMutRef(_)
+                | UnpackVariantGeneric(_)
+                | UnpackVariantGenericImmRef(_)
+                | UnpackVariantGenericMutRef(_)
+                | VariantSwitch(_)
                 | Abort
                 | Nop => Ok(()),
             };
@@ -202,6 +202,22 @@ impl<'a> StackUsageVerifier<'a> {
             // VecSwap pops three times but does not push
             Bytecode::VecSwap(_) => (3, 0),
 
+            // Enums are rejected by the limits verifier before reaching this point
+            Bytecode::PackVariant(_)
+            | Bytecode::PackVariantGeneric(_)
+            | Bytecode::UnpackVariant(_)
+            | Bytecode::UnpackVariantImmRef(_)
+            | Bytecode::UnpackVariantMutRef(_)
+            | Bytecode::UnpackVariantGeneric(_)
+            | Bytecode::UnpackVariantGenericImmRef(_)
+            | Bytecode::UnpackVariantGenericMutRef(_)
+            | Bytecode::VariantSwitch(_) => {
+                return Err(
+                    PartialVMError::new(StatusCode::VERIFIER_INVARIANT_VIOLATION)
+                        .with_message("enums are not supported".to_string()),
+                );
+            }
+
             // Branch and Nop neither pops nor pushes
             Bytecode::Branch(_) | Bytecode::Nop => (0, 0),
 
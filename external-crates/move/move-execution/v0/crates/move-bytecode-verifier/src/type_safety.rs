@@ -914,6 +914,22 @@ fn verify_instr(
                 _ => return Err(verifier.error(StatusCode::TYPE_MISMATCH, offset)),
             };
         }
+
+        // Enums are rejected by the limits verifier before reaching this point
+        Bytecode::PackVariant(_)
+        | Bytecode::PackVariantGeneric(_)
+        | Bytecode::UnpackVariant(_)
+        | Bytecode::UnpackVariantImmRef(_)
+        | Bytecode::UnpackVariantMutRef(_)
+        | Bytecode::UnpackVariantGeneric(_)
+        | Bytecode::UnpackVariantGenericImmRef(_)
+        | Bytecode::UnpackVariantGenericMutRef(_)
+        | Bytecode::VariantSwitch(_) => {
+            return Err(verifier
+                .error(StatusCode::VERIFIER_INVARIANT_VIOLATION, offset)
+                .with_message("enums are not supported".to_string()));
+        }
+
         Bytecode::CastU16 => {
             let operand = safe_unwrap_err!(verifier.stack.pop());
             if !operand.is_integer() {
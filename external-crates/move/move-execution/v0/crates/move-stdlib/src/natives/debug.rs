@@ -626,6 +626,10 @@ mod testing {
                     write!(out, "{}", STRUCT_END).map_err(fmt_error_to_partial_vm_error)?;
                 }
             }
+            A::MoveValue::Variant(_) => {
+                return Err(PartialVMError::new(StatusCode::INTERNAL_TYPE_ERROR)
+                    .with_message("Enum values are not supported".to_string()));
+            }
         }
 
         Ok(())
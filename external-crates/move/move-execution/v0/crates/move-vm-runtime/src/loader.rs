@@ -258,6 +258,7 @@ impl ModuleCache {
                     runtime_id: runtime_id.clone(),
                     struct_def: StructDefinitionIndex(idx as u16),
                     depth: None,
+                    variants: None,
                 },
             )?;
 
@@ -30,8 +30,8 @@ use move_binary_format::{
     binary_views::{BinaryIndexedView, FunctionView},
     errors::PartialVMError,
     file_format::{
-        Bytecode, CodeOffset, CompiledModule, FunctionDefinitionIndex, FunctionHandle, LocalIndex,
-        StructDefinition, StructFieldInformation,
+        Bytecode, CodeOffset, CompiledModule, EnumDefinitionIndex, FunctionDefinitionIndex,
+        FunctionHandle, LocalIndex, StructDefinition, StructFieldInformation, VariantTag,
     },
 };
 use move_bytecode_verifier::{
@@ -375,6 +375,19 @@ fn unpack(
     verifier.stack_pushn(num_fields(struct_def), AbstractValue::Other)
 }
 
+fn num_variant_fields(
+    verifier: &IDLeakAnalysis,
+    enum_def: EnumDefinitionIndex,
+    tag: VariantTag,
+) -> Result<u64, PartialVMError> {
+    let enum_def = expect_ok(verifier.binary_view.enum_def_at(enum_def))?;
+    let variant = enum_def.variant(tag).ok_or_else(|| {
+        PartialVMError::new(StatusCode::VERIFIER_INVARIANT_VIOLATION)
+            .with_message(format!("Unknown variant tag {tag}"))
+    })?;
+    Ok(variant.fields.len() as u64)
+}
+
 fn execute_inner(
     verifier: &mut IDLeakAnalysis,
     state: &mut AbstractState,
@@ -522,6 +535,41 @@ fn execute_inner(
             unpack(verifier, struct_def)?;
         }
 
+        // Enums cannot have the key ability, so none of their values is an object.
+        Bytecode::PackVariant(idx) => {
+            let handle = expect_ok(verifier.binary_view.variant_handle_at(*idx))?;
+            let num_fields = num_variant_fields(verifier, handle.enum_def, handle.variant)?;
+            verifier.stack_popn(num_fields)?;
+            verifier.stack_push(AbstractValue::Other)?;
+        }
+        Bytecode::PackVariantGeneric(idx) => {
+            let handle = expect_ok(verifier.binary_view.variant_instantiation_handle_at(*idx))?;
+            let enum_inst = expect_ok(verifier.binary_view.enum_instantiation_at(handle.enum_def))?;
+            let num_fields = num_variant_fields(verifier, enum_inst.def, handle.variant)?;
+            verifier.stack_popn(num_fields)?;
+            verifier.stack_push(AbstractValue::Other)?;
+        }
+        Bytecode::UnpackVariant(idx)
+        | Bytecode::UnpackVariantImmRef(idx)
+        | Bytecode::UnpackVariantMutRef(idx) => {
+            let handle = expect_ok(verifier.binary_view.variant_handle_at(*idx))?;
+            let num_fields = num_variant_fields(verifier, handle.enum_def, handle.variant)?;
+            verifier.stack.pop().unwrap();
+            verifier.stack_pushn(num_fields, AbstractValue::Other)?;
+        }
+        Bytecode::UnpackVariantGeneric(idx)
+        | Bytecode::UnpackVariantGenericImmRef(idx)
+        | Bytecode::UnpackVariantGenericMutRef(idx) => {
+            let handle = expect_ok(verifier.binary_view.variant_instantiation_handle_at(*idx))?;
+            let enum_inst = expect_ok(verifier.binary_view.enum_instantiation_at(handle.enum_def))?;
+            let num_fields = num_variant_fields(verifier, enum_inst.def, handle.variant)?;
+            verifier.stack.pop().unwrap();
+            verifier.stack_pushn(num_fields, AbstractValue::Other)?;
+        }
+        Bytecode::VariantSwitch(_) => {
+            verifier.stack.pop().unwrap();
+        }
+
         Bytecode::VecPack(_, num) => {
             verifier.stack_popn(*num )?;
             verifier.stack_push(AbstractValue::Other)?;
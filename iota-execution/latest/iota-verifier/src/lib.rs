@@ -84,5 +84,6 @@ pub fn default_verifier_config(
         allow_receiving_object_id: protocol_config.allow_receiving_object_id(),
         reject_mutable_random_on_entry_functions: protocol_config
             .reject_mutable_random_on_entry_functions(),
+        enable_enums: protocol_config.enable_move_enums(),
     }
 }
@@ -6,6 +6,8 @@
 //! the `key` ability. The properties checked are
 //! - The first field is named "id"
 //! - The first field has type `iota::object::UID`
+//!
+//! It also verifies that no enum has the `key` ability.
 
 use iota_types::{
     error::ExecutionError,
@@ -22,7 +24,21 @@ use move_binary_format::{
 use crate::verification_failure;
 
 pub fn verify_module(module: &CompiledModule) -> Result<(), ExecutionError> {
-    verify_key_structs(module)
+    verify_key_structs(module)?;
+    verify_no_key_enums(module)
+}
+
+fn verify_no_key_enums(module: &CompiledModule) -> Result<(), ExecutionError> {
+    for def in &module.enum_defs {
+        let handle = module.struct_handle_at(def.enum_handle);
+        if handle.abilities.has_key() {
+            return Err(verification_failure(format!(
+                "Enum {} cannot have the 'key' ability. Enums cannot be objects",
+                module.identifier_at(handle.name)
+            )));
+        }
+    }
+    Ok(())
 }
 
 fn verify_key_structs(module: &CompiledModule) -> Result<(), ExecutionError> {
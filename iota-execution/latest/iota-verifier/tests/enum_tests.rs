@@ -0,0 +1,34 @@
+// Copyright (c) 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use iota_protocol_config::ProtocolConfig;
+use iota_verifier_latest::{default_verifier_config, struct_with_key_verifier};
+use move_binary_format::file_format::{basic_enum_test_module, Ability};
+use move_bytecode_verifier::verify_module_with_config_unmetered;
+use move_core_types::vm_status::StatusCode;
+
+#[test]
+fn enums_gated_by_protocol_config() {
+    let module = basic_enum_test_module();
+    let mut protocol_config = ProtocolConfig::get_for_max_version_UNSAFE();
+
+    protocol_config.set_enable_move_enums_for_testing(true);
+    let config = default_verifier_config(&protocol_config, false);
+    verify_module_with_config_unmetered(&config, &module).unwrap();
+
+    protocol_config.set_enable_move_enums_for_testing(false);
+    let config = default_verifier_config(&protocol_config, false);
+    let status = verify_module_with_config_unmetered(&config, &module)
+        .unwrap_err()
+        .major_status();
+    assert_eq!(status, StatusCode::FEATURE_UNDER_GATING);
+}
+
+#[test]
+fn enums_cannot_have_key() {
+    let mut module = basic_enum_test_module();
+    struct_with_key_verifier::verify_module(&module).unwrap();
+
+    module.struct_handles[0].abilities = module.struct_handles[0].abilities | Ability::Key;
+    assert!(struct_with_key_verifier::verify_module(&module).is_err());
+}
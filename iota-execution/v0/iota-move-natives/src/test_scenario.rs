@@ -16,7 +16,7 @@ use iota_types::{
 use move_binary_format::errors::{PartialVMError, PartialVMResult};
 use move_core_types::{
     account_address::AccountAddress,
-    annotated_value::{MoveStruct, MoveValue},
+    annotated_value::{MoveStruct, MoveValue, MoveVariant},
     identifier::Identifier,
     language_storage::StructTag,
     vm_status::StatusCode,
@@ -738,5 +738,10 @@ fn visit_structs_impl<FVisitTypes>(
                 visit_structs_impl(v, visit_with_types, next_depth)
             }
         }
+        MoveValue::Variant(MoveVariant { fields, .. }) => {
+            for (_, v) in fields {
+                visit_structs_impl(v, visit_with_types, next_depth)
+            }
+        }
     }
 }
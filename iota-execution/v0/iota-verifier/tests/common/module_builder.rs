@@ -52,6 +52,10 @@ impl ModuleBuilder {
                 struct_def_instantiations: vec![],
                 function_instantiations: vec![],
                 field_instantiations: vec![],
+                enum_defs: vec![],
+                enum_def_instantiations: vec![],
+                variant_handles: vec![],
+                variant_instantiation_handles: vec![],
             },
         }
     }
@@ -126,6 +130,7 @@ impl ModuleBuilder {
             CodeUnit {
                 locals: SignatureIndex(0),
                 code: vec![Bytecode::Ret],
+                jump_tables: vec![],
             },
         )
     }